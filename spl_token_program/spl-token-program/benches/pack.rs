@@ -0,0 +1,71 @@
+//! Borsh 与手写 pack（pack::ManualPack / no_alloc）的链下对比：
+//! `cargo bench --features no-alloc`，结果在 target/criterion 下。
+//! 链上计算单元的对比见 spl_token_tests 中的 `npm run bench-cu`。
+
+use borsh::BorshDeserialize;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use solana_program::pubkey::Pubkey;
+use spl_token_program::{
+    pack::ManualPack,
+    test_utils::{MintFixture, TokenAccountFixture},
+    Mint, TokenAccount, TokenInstruction,
+};
+
+fn mint_state(c: &mut Criterion) {
+    let authority = Pubkey::new_unique();
+    let data = MintFixture::new().decimals(9).supply(1_000_000).mint_authority(authority).to_bytes();
+    let mint = Mint::deserialize(&data).unwrap();
+    let mut buffer = data.clone();
+
+    let mut group = c.benchmark_group("mint");
+    group.bench_function("borsh_unpack", |b| {
+        b.iter(|| <Mint as BorshDeserialize>::deserialize(&mut black_box(&data[..])).unwrap())
+    });
+    group.bench_function("manual_unpack", |b| b.iter(|| Mint::unpack_from_slice(black_box(&data)).unwrap()));
+    group.bench_function("borsh_pack", |b| {
+        b.iter(|| borsh::to_writer(black_box(&mut buffer[..]), black_box(&mint)).unwrap())
+    });
+    group.bench_function("manual_pack", |b| {
+        b.iter(|| black_box(&mint).pack_into_slice(black_box(&mut buffer)).unwrap())
+    });
+    group.finish();
+}
+
+fn token_account_state(c: &mut Criterion) {
+    let data = TokenAccountFixture::new(Pubkey::new_unique(), Pubkey::new_unique())
+        .amount(500)
+        .vote_delegate(Pubkey::new_unique())
+        .to_bytes();
+    let account = TokenAccount::deserialize(&data).unwrap();
+    let mut buffer = data.clone();
+
+    let mut group = c.benchmark_group("token_account");
+    group.bench_function("borsh_unpack", |b| {
+        b.iter(|| <TokenAccount as BorshDeserialize>::deserialize(&mut black_box(&data[..])).unwrap())
+    });
+    group.bench_function("manual_unpack", |b| {
+        b.iter(|| TokenAccount::unpack_from_slice(black_box(&data)).unwrap())
+    });
+    group.bench_function("borsh_pack", |b| {
+        b.iter(|| borsh::to_writer(black_box(&mut buffer[..]), black_box(&account)).unwrap())
+    });
+    group.bench_function("manual_pack", |b| {
+        b.iter(|| black_box(&account).pack_into_slice(black_box(&mut buffer)).unwrap())
+    });
+    group.finish();
+}
+
+fn instruction_decode(c: &mut Criterion) {
+    let data = borsh::to_vec(&TokenInstruction::Transfer { amount: 42 }).unwrap();
+
+    let mut group = c.benchmark_group("transfer_instruction");
+    group.bench_function("borsh_unpack", |b| b.iter(|| TokenInstruction::unpack(black_box(&data)).unwrap()));
+    #[cfg(feature = "no-alloc")]
+    group.bench_function("manual_unpack", |b| {
+        b.iter(|| spl_token_program::no_alloc::unpack(black_box(&data)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, mint_state, token_account_state, instruction_decode);
+criterion_main!(benches);
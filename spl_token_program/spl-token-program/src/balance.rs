@@ -0,0 +1,125 @@
+//! 余额与供应量变更的纯函数，处理函数与 Kani 验证共用；
+//! 所有加减都显式检查，不允许静默回绕。
+
+use crate::TokenError;
+
+/// 增加余额，溢出时返回 Overflow
+pub fn credit(balance: u64, amount: u64) -> Result<u64, TokenError> {
+    balance.checked_add(amount).ok_or(TokenError::Overflow)
+}
+
+/// 扣减余额，不足时返回 InsufficientFunds
+pub fn debit(balance: u64, amount: u64) -> Result<u64, TokenError> {
+    balance.checked_sub(amount).ok_or(TokenError::InsufficientFunds)
+}
+
+/// 两个不同账户之间转账，返回 (源余额, 目标余额)
+pub fn transfer_amounts(source: u64, destination: u64, amount: u64) -> Result<(u64, u64), TokenError> {
+    Ok((debit(source, amount)?, credit(destination, amount)?))
+}
+
+/// 销毁，返回 (供应量, 账户余额)
+pub fn burn_amounts(supply: u64, balance: u64, amount: u64) -> Result<(u64, u64), TokenError> {
+    let balance = debit(balance, amount)?;
+    let supply = supply.checked_sub(amount).ok_or(TokenError::Overflow)?;
+    Ok((supply, balance))
+}
+
+/// 按有符号增量调整余额：正数入账，负数扣减
+pub fn apply_delta(balance: u64, delta: i64) -> Result<u64, TokenError> {
+    if delta >= 0 {
+        credit(balance, delta as u64)
+    } else {
+        debit(balance, delta.unsigned_abs())
+    }
+}
+
+/// 余额从 before 变为 after 后的持有人数：0 -> 非 0 加一，非 0 -> 0 减一，其余不变
+pub fn holder_count_after(count: u64, before: u64, after: u64) -> Result<u64, TokenError> {
+    match (before == 0, after == 0) {
+        (true, false) => credit(count, 1),
+        (false, true) => count.checked_sub(1).ok_or(TokenError::Overflow),
+        _ => Ok(count),
+    }
+}
+
+/// 按基点份额拆分 total，份额之和必须为 10_000；
+/// 每份向下取整，取整余数全部归第一个目标，保证各份之和恰好为 total
+pub fn split_amounts(total: u64, shares: &[u16]) -> Result<Vec<u64>, TokenError> {
+    let share_sum: u32 = shares.iter().map(|&share| share as u32).sum();
+    if shares.is_empty() || share_sum != 10_000 {
+        return Err(TokenError::InvalidInstruction);
+    }
+    let mut amounts: Vec<u64> = shares
+        .iter()
+        .map(|&share| (total as u128 * share as u128 / 10_000) as u64)
+        .collect();
+    let distributed: u64 = amounts.iter().sum();
+    amounts[0] = credit(amounts[0], total - distributed)?;
+    Ok(amounts)
+}
+
+// 运行 `cargo kani` 验证
+#[cfg(kani)]
+mod verification {
+    use super::*;
+    use crate::Mint;
+    use solana_program::pubkey::Pubkey;
+
+    #[kani::proof]
+    fn transfer_conserves_total_balance() {
+        let source: u64 = kani::any();
+        let destination: u64 = kani::any();
+        let amount: u64 = kani::any();
+        if let Ok((new_source, new_destination)) = transfer_amounts(source, destination, amount) {
+            assert_eq!(
+                new_source as u128 + new_destination as u128,
+                source as u128 + destination as u128
+            );
+        } else {
+            assert!(amount > source || destination.checked_add(amount).is_none());
+        }
+    }
+
+    #[kani::proof]
+    fn mint_to_increases_supply_by_amount() {
+        let mut mint = Mint::new(kani::any(), Pubkey::default(), None);
+        let supply: u64 = kani::any();
+        let balance: u64 = kani::any();
+        let amount: u64 = kani::any();
+        mint.supply = supply;
+        match (mint.mint_supply(amount), credit(balance, amount)) {
+            (Ok(()), Ok(new_balance)) => {
+                assert_eq!(mint.supply as u128, supply as u128 + amount as u128);
+                assert_eq!(new_balance as u128, balance as u128 + amount as u128);
+            }
+            (Err(_), _) => assert_eq!(mint.supply, supply),
+            (Ok(()), Err(_)) => assert!(balance.checked_add(amount).is_none()),
+        }
+    }
+
+    #[kani::proof]
+    fn burn_never_underflows() {
+        let supply: u64 = kani::any();
+        let balance: u64 = kani::any();
+        let amount: u64 = kani::any();
+        match burn_amounts(supply, balance, amount) {
+            Ok((new_supply, new_balance)) => {
+                assert!(new_supply <= supply && new_balance <= balance);
+                assert_eq!(supply - new_supply, amount);
+                assert_eq!(balance - new_balance, amount);
+            }
+            Err(_) => assert!(amount > balance || amount > supply),
+        }
+    }
+
+    #[kani::proof]
+    #[kani::unwind(4)]
+    fn split_sums_to_total() {
+        let total: u64 = kani::any();
+        let shares: [u16; 3] = [kani::any(), kani::any(), kani::any()];
+        kani::assume(shares.iter().map(|&share| share as u32).sum::<u32>() == 10_000);
+        let amounts = split_amounts(total, &shares).unwrap();
+        assert_eq!(amounts.iter().map(|&amount| amount as u128).sum::<u128>(), total as u128);
+    }
+}
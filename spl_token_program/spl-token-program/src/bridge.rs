@@ -0,0 +1,75 @@
+//! 跨链桥消息：BridgeBurn 销毁代币后，把与 Wormhole Token Bridge 转账载荷（payload id 1）布局相同的消息
+//! 写入 "Program data:" 日志，标准中继者取出后转发到目标链；从其他链回来的代币由验证者签名的 BridgeMint 铸造。
+//! 载荷中的整数均为大端序，u256 字段用 32 字节表示。
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{entrypoint::ProgramResult, log::sol_log_data, pubkey::Pubkey};
+
+/// Wormhole 中 Solana 的链 ID
+pub const SOLANA_CHAIN_ID: u16 = 1;
+/// Token Bridge 转账载荷类型
+pub const TRANSFER_PAYLOAD_ID: u8 = 1;
+/// 跨链金额最多保留的小数位数
+pub const MAX_BRIDGE_DECIMALS: u8 = 8;
+
+/// 按跨链精度截断金额，返回 (规范化金额, 实际需要销毁的本地金额)；截掉的零头留在原账户
+pub fn normalize_amount(amount: u64, decimals: u8) -> (u64, u64) {
+    if decimals <= MAX_BRIDGE_DECIMALS {
+        return (amount, amount);
+    }
+    let factor = 10u64.pow((decimals - MAX_BRIDGE_DECIMALS) as u32);
+    let normalized = amount / factor;
+    (normalized, normalized * factor)
+}
+
+/// Token Bridge 转账载荷
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferPayload {
+    /// 规范化后的金额（最多 8 位小数）
+    pub amount: u64,
+    /// 代币在原生链上的地址（本程序铸币的公钥）
+    pub token_address: [u8; 32],
+    pub token_chain: u16,
+    /// 目标链上的接收地址（不足 32 字节的地址左侧补零）
+    pub to: [u8; 32],
+    pub to_chain: u16,
+    pub fee: u64,
+}
+
+impl TransferPayload {
+    pub const LEN: usize = 1 + 32 + 32 + 2 + 32 + 2 + 32;
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut payload = Vec::with_capacity(Self::LEN);
+        payload.push(TRANSFER_PAYLOAD_ID);
+        payload.extend_from_slice(&u256_be(self.amount));
+        payload.extend_from_slice(&self.token_address);
+        payload.extend_from_slice(&self.token_chain.to_be_bytes());
+        payload.extend_from_slice(&self.to);
+        payload.extend_from_slice(&self.to_chain.to_be_bytes());
+        payload.extend_from_slice(&u256_be(self.fee));
+        payload
+    }
+}
+
+fn u256_be(value: u64) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[24..].copy_from_slice(&value.to_be_bytes());
+    bytes
+}
+
+/// 跨链桥事件（Borsh 序列化后写入 "Program data:" 日志）
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum BridgeEvent {
+    /// 销毁出桥，payload 为 TransferPayload::encode 的结果，sequence 在每个铸币内递增
+    Burned { mint: Pubkey, sequence: u64, payload: Vec<u8> },
+    /// 入桥铸造
+    Minted { mint: Pubkey, source_chain: u16, emitter: [u8; 32], sequence: u64, amount: u64 },
+}
+
+impl BridgeEvent {
+    pub fn emit(&self) -> ProgramResult {
+        sol_log_data(&[&self.try_to_vec()?]);
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+//! 压缩余额：大量小额持有人的余额不再各自占用一个需要租金豁免的代币账户，
+//! 而是作为叶子存放在一棵只追加的默克尔树中，对应的代币统一托管在树的托管账户里。
+//!
+//! 叶子一旦写入就不再修改，花费时在树账户尾部的位图中标记为已花费，并把新余额作为新叶子追加。
+//! 由于已有叶子不变，针对任一历史根的证明仍然有效，因此只要根还在最近 ROOT_HISTORY 个之内，
+//! 同一棵树上的并发交易不会因为根被其他交易推进而失败。
+//! 链上只保存根与最右侧路径（frontier），叶子内容通过 `CompressionEvent` 日志发布，由索引服务重建证明。
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    entrypoint::ProgramResult, hash::hashv, log::sol_log_data, program_error::ProgramError, pubkey::Pubkey,
+};
+
+use crate::TokenError;
+
+/// 树的最大深度（2^20 个叶子）
+pub const MAX_COMPRESSED_TREE_DEPTH: usize = 20;
+/// 保留的历史根数量，证明可以针对其中任意一个
+pub const ROOT_HISTORY: usize = 16;
+
+/// 压缩余额叶子：owner 在该树中拥有 amount 个代币，index 为叶子位置
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct CompressedLeaf {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub index: u64,
+}
+
+impl CompressedLeaf {
+    /// 叶子哈希 hash(0x00 || owner || amount || index)，与内部节点的 0x01 前缀区分
+    pub fn hash(&self) -> [u8; 32] {
+        hashv(&[&[0u8], self.owner.as_ref(), &self.amount.to_le_bytes(), &self.index.to_le_bytes()]).to_bytes()
+    }
+}
+
+/// 内部节点哈希 hash(0x01 || left || right)
+pub fn hash_nodes(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hashv(&[&[1u8], left, right]).to_bytes()
+}
+
+/// 压缩树账户头部，其后是已花费位图（每个叶子 1 位）
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct CompressedTree {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub escrow: Pubkey, //32 托管代币账户，所有者为树的 authority PDA
+    pub depth: u8, //1
+    pub next_index: u64, //8
+    pub root_cursor: u8, //1 最新根在 roots 中的位置
+    pub roots: [[u8; 32]; ROOT_HISTORY], //32 * ROOT_HISTORY
+    pub frontier: [[u8; 32]; MAX_COMPRESSED_TREE_DEPTH], //32 * MAX_COMPRESSED_TREE_DEPTH 每层最右侧的左节点
+}
+
+impl CompressedTree {
+    pub const LEN: usize = 1 + 32 + 32 + 1 + 8 + 1 + 32 * ROOT_HISTORY + 32 * MAX_COMPRESSED_TREE_DEPTH;
+
+    /// 深度为 depth 的树账户所需空间（头部 + 已花费位图）
+    pub const fn account_len(depth: u8) -> usize {
+        Self::LEN + (1usize << depth).div_ceil(8)
+    }
+
+    pub fn new(mint: Pubkey, escrow: Pubkey, depth: u8) -> Self {
+        let mut roots = [[0u8; 32]; ROOT_HISTORY];
+        roots[0] = zero_hashes(depth as usize)[depth as usize];
+        Self {
+            is_initialized: true,
+            mint,
+            escrow,
+            depth,
+            next_index: 0,
+            root_cursor: 0,
+            roots,
+            frontier: [[0u8; 32]; MAX_COMPRESSED_TREE_DEPTH],
+        }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.roots[self.root_cursor as usize]
+    }
+
+    pub fn capacity(&self) -> u64 {
+        1u64 << self.depth
+    }
+
+    pub fn is_known_root(&self, root: &[u8; 32]) -> bool {
+        self.roots.contains(root)
+    }
+
+    /// 追加叶子，返回其位置；树满时返回 Overflow
+    pub fn append(&mut self, leaf_hash: [u8; 32]) -> Result<u64, TokenError> {
+        let leaf_index = self.next_index;
+        if leaf_index >= self.capacity() {
+            return Err(TokenError::Overflow);
+        }
+        let zeros = zero_hashes(self.depth as usize);
+        let mut node = leaf_hash;
+        let mut index = leaf_index;
+        for (level, zero) in zeros.iter().enumerate().take(self.depth as usize) {
+            if index & 1 == 0 {
+                self.frontier[level] = node;
+                node = hash_nodes(&node, zero);
+            } else {
+                node = hash_nodes(&self.frontier[level], &node);
+            }
+            index /= 2;
+        }
+        self.root_cursor = ((self.root_cursor as usize + 1) % ROOT_HISTORY) as u8;
+        self.roots[self.root_cursor as usize] = node;
+        self.next_index += 1;
+        Ok(leaf_index)
+    }
+
+    /// 校验叶子在 root 下位于 index（proof 自底向上，长度等于树深度）
+    pub fn verify(&self, leaf_hash: [u8; 32], index: u64, proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+        if proof.len() != self.depth as usize || index >= self.next_index {
+            return false;
+        }
+        let mut node = leaf_hash;
+        let mut index = index;
+        for sibling in proof {
+            node = if index & 1 == 0 { hash_nodes(&node, sibling) } else { hash_nodes(sibling, &node) };
+            index /= 2;
+        }
+        node == *root
+    }
+}
+
+/// 各层空子树的哈希：zeros[0] 为空叶子，zeros[depth] 为空树的根
+fn zero_hashes(depth: usize) -> Vec<[u8; 32]> {
+    let mut zeros = vec![[0u8; 32]];
+    for level in 0..depth {
+        let below = zeros[level];
+        zeros.push(hash_nodes(&below, &below));
+    }
+    zeros
+}
+
+/// 已花费位图中的字节位置与掩码
+fn spent_bit(index: u64) -> (usize, u8) {
+    ((index / 8) as usize, 1 << (index % 8))
+}
+
+pub fn is_spent(bitmap: &[u8], index: u64) -> Result<bool, ProgramError> {
+    let (byte, mask) = spent_bit(index);
+    bitmap.get(byte).map(|value| value & mask != 0).ok_or(ProgramError::InvalidAccountData)
+}
+
+pub fn mark_spent(bitmap: &mut [u8], index: u64) -> ProgramResult {
+    let (byte, mask) = spent_bit(index);
+    *bitmap.get_mut(byte).ok_or(ProgramError::InvalidAccountData)? |= mask;
+    Ok(())
+}
+
+/// 压缩树事件（Borsh 序列化后写入 "Program data:" 日志），索引服务据此重建叶子与证明
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum CompressionEvent {
+    LeafAppended { tree: Pubkey, leaf: CompressedLeaf },
+    LeafSpent { tree: Pubkey, index: u64 },
+}
+
+impl CompressionEvent {
+    pub fn emit(&self) -> ProgramResult {
+        sol_log_data(&[&self.try_to_vec()?]);
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+//! 指令处理上下文：按顺序读取账户，并把铸币 / 代币账户一次性反序列化为带类型的包装（加载时校验归本程序所有且已初始化），
+//! 处理函数修改 `state` 后调用 `save` 写回，避免在每个函数里重复借用与反序列化。
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{validators, Mint, TokenAccount, TokenError};
+
+/// 已加载的铸币账户
+pub struct LoadedMint<'a, 'b> {
+    pub info: &'a AccountInfo<'b>,
+    pub state: Mint,
+}
+
+impl<'a, 'b> LoadedMint<'a, 'b> {
+    /// 读取铸币账户：必须归本程序所有且已初始化
+    pub fn load(program_id: &Pubkey, info: &'a AccountInfo<'b>) -> Result<Self, ProgramError> {
+        validators::assert_owned_by(info, program_id)?;
+        let state = Mint::deserialize(&info.data.borrow())?;
+        validators::assert_initialized(&state)?;
+        Ok(Self { info, state })
+    }
+
+    /// 校验 authority 为当前铸币权限且已签名
+    pub fn check_mint_authority(&self, authority: &AccountInfo) -> ProgramResult {
+        validators::assert_signer(authority)?;
+        if self.state.mint_authority != Some(*authority.key) {
+            return Err(TokenError::Unauthorized.into());
+        }
+        Ok(())
+    }
+
+    /// 校验 authority 为冻结权限且已签名
+    pub fn check_freeze_authority(&self, authority: &AccountInfo) -> ProgramResult {
+        validators::assert_signer(authority)?;
+        if self.state.freeze_authority != Some(*authority.key) {
+            return Err(TokenError::Unauthorized.into());
+        }
+        Ok(())
+    }
+
+    pub fn save(&self) -> ProgramResult {
+        self.state.serialize(&mut self.info.data.borrow_mut())
+    }
+}
+
+/// 已加载的代币账户
+pub struct LoadedTokenAccount<'a, 'b> {
+    pub info: &'a AccountInfo<'b>,
+    pub state: TokenAccount,
+}
+
+impl<'a, 'b> LoadedTokenAccount<'a, 'b> {
+    /// 读取代币账户：必须归本程序所有且已初始化
+    pub fn load(program_id: &Pubkey, info: &'a AccountInfo<'b>) -> Result<Self, ProgramError> {
+        validators::assert_owned_by(info, program_id)?;
+        let state = TokenAccount::deserialize(&info.data.borrow())?;
+        validators::assert_initialized(&state)?;
+        Ok(Self { info, state })
+    }
+
+    /// 校验 owner 为账户所有者且已签名
+    pub fn check_owner(&self, owner: &AccountInfo) -> ProgramResult {
+        validators::assert_signer(owner)?;
+        if self.state.owner != *owner.key {
+            msg!("Token account {} is not owned by signer {}", self.info.key, owner.key);
+            return Err(TokenError::Unauthorized.into());
+        }
+        Ok(())
+    }
+
+    /// 校验余额充足且未冻结
+    pub fn check_spendable(&self, amount: u64) -> ProgramResult {
+        if self.state.amount < amount {
+            return Err(TokenError::InsufficientFunds.into());
+        }
+        self.check_not_frozen()
+    }
+
+    pub fn check_not_frozen(&self) -> ProgramResult {
+        if self.state.is_frozen() {
+            return Err(TokenError::AccountFrozen.into());
+        }
+        Ok(())
+    }
+
+    pub fn save(&self) -> ProgramResult {
+        self.state.serialize(&mut self.info.data.borrow_mut())
+    }
+}
+
+/// 指令处理上下文
+pub struct ProcessingContext<'a, 'b> {
+    pub program_id: &'a Pubkey,
+    /// 尚未读取的账户，剩余的可选账户（投票检查点等）直接从这里透传
+    pub accounts: std::slice::Iter<'a, AccountInfo<'b>>,
+}
+
+impl<'a, 'b> ProcessingContext<'a, 'b> {
+    pub fn new(program_id: &'a Pubkey, accounts: &'a [AccountInfo<'b>]) -> Self {
+        Self { program_id, accounts: accounts.iter() }
+    }
+
+    pub fn next_account(&mut self) -> Result<&'a AccountInfo<'b>, ProgramError> {
+        next_account_info(&mut self.accounts)
+    }
+
+    /// 读取下一个账户并要求其已签名
+    pub fn next_signer(&mut self) -> Result<&'a AccountInfo<'b>, ProgramError> {
+        let account = self.next_account()?;
+        validators::assert_signer(account)?;
+        Ok(account)
+    }
+
+    pub fn next_mint(&mut self) -> Result<LoadedMint<'a, 'b>, ProgramError> {
+        LoadedMint::load(self.program_id, self.next_account()?)
+    }
+
+    pub fn next_token_account(&mut self) -> Result<LoadedTokenAccount<'a, 'b>, ProgramError> {
+        LoadedTokenAccount::load(self.program_id, self.next_account()?)
+    }
+}
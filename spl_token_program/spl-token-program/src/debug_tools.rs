@@ -0,0 +1,131 @@
+//! 序列化调试演示（debug-tools 特性）：在程序日志中打印 InitializeMint 指令与铸币状态的编码结果，
+//! 用于排查客户端与程序的编码差异。链上构建默认不包含，避免演示代码与日志字符串占用程序体积。
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{msg, pubkey::Pubkey};
+
+use crate::{pack::ManualPack, Mint, TokenInstruction};
+
+pub fn serialize_token_instruction() {
+    test1();
+}
+
+pub fn test1(){
+    msg!("🔧 Rust 序列化测试");    
+    // 你的数据
+    let decimals = 9;
+    let mint_authority: Pubkey = "5higFJ6xCuganUCvFFLDnZhL4Jb28KYEfBrVzCDGpGt8".parse().unwrap();
+    //let freeze_authority: Option<Pubkey> = None;
+     let freeze_authority: Option<Pubkey> = Some("GjphYQcbP1m3SYTXkHC1E3MJrCEeH8vL6f3HuoZ9fJ2x".parse().unwrap());
+    
+    msg!("输入数据:");
+    msg!("  decimals: {}", decimals);
+    msg!("  mint_authority: {}", mint_authority);
+    msg!("  freeze_authority: {:?}", freeze_authority);
+    
+    // 创建指令
+    let instruction = TokenInstruction::InitializeMint {
+        decimals,
+        mint_authority,
+        freeze_authority,
+    };
+    
+    // 序列化
+    match instruction.try_to_vec() {
+        Ok(serialized) => {
+            msg!("\n✅ 序列化成功!");
+            msg!("序列化结果:");
+            msg!("  长度: {} 字节", serialized.len());
+            msg!("  十六进制: {:?}", serialized.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>());
+            msg!("  字节数组: {:?}", serialized);
+            
+            // 详细字节分析
+            msg!("\n🔬 详细字节分析:");
+            msg!("  [0] 枚举判别式: {} (InitializeMint)", serialized[0]);
+            msg!("  [1] decimals: {}", serialized[1]);
+            msg!("  [2-33] mint_authority: 32 bytes");
+            
+            // 检查 mint_authority 是否正确
+            let mint_auth_bytes = &serialized[2..34];
+            if let Ok(reconstructed_mint) = Pubkey::try_from(mint_auth_bytes) {
+                msg!("     重建的 mint_authority: {}", reconstructed_mint);
+                msg!("     匹配: {}", reconstructed_mint == mint_authority);
+            }
+            
+            msg!("  [34] freeze_authority option: {} (0 = None)", serialized[34]);
+            msg!("  [35-66] freeze_authority data: 32 bytes of zeros");
+            
+            // 验证总长度
+            let expected_length = 1 + 1 + 32 + 1 + 32; // 67 bytes
+            msg!("\n📏 长度验证:");
+            msg!("  期望: {} 字节", expected_length);
+            msg!("  实际: {} 字节", serialized.len());
+            msg!("  匹配: {}", serialized.len() == expected_length);
+            
+            // 反序列化验证
+            msg!("\n🔄 反序列化验证:");
+            match TokenInstruction::try_from_slice(&serialized) {
+                Ok(deserialized) => {
+                    msg!("  ✅ 反序列化成功!");
+                    if let TokenInstruction::InitializeMint { decimals: d, mint_authority: ma, freeze_authority: fa } = deserialized {
+                        msg!("     decimals: {} (匹配: {})", d, d == decimals);
+                        msg!("     mint_authority: {} (匹配: {})", ma, ma == mint_authority);
+                        msg!("     freeze_authority: {:?} (匹配: {})", fa, fa == freeze_authority);
+                    }
+                }
+                Err(e) => {
+                    msg!("  ❌ 反序列化失败: {:?}", e);
+                }
+            }
+        }
+        Err(e) => {
+            msg!("❌ 序列化失败: {:?}", e);
+        }
+    }
+}
+
+pub fn test2(){
+    msg!("🔧 Rust 序列化测试");    
+    // 你的数据
+    let decimals = 9;
+    let mint_authority: Pubkey = "5higFJ6xCuganUCvFFLDnZhL4Jb28KYEfBrVzCDGpGt8".parse().unwrap();
+    //let freeze_authority: Option<Pubkey> = None;
+     let freeze_authority: Option<Pubkey> = Some("GjphYQcbP1m3SYTXkHC1E3MJrCEeH8vL6f3HuoZ9fJ2x".parse().unwrap());
+    
+    msg!("输入数据:");
+    msg!("  decimals: {}", decimals);
+    msg!("  mint_authority: {}", mint_authority);
+    msg!("  freeze_authority: {:?}", freeze_authority);
+
+
+    let instruction = Mint::new(decimals, mint_authority, freeze_authority);
+
+    // 序列化
+    match instruction.pack_to_vec() {
+        Ok(serialized) => {
+            msg!("\n✅ 序列化成功!");
+            msg!("序列化结果:");
+            msg!("  长度: {} 字节", serialized.len());
+            msg!("  十六进制: {:?}", serialized.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>());
+            msg!("  字节数组: {:?}", serialized);          
+             
+            // 反序列化验证
+            msg!("\n🔄 反序列化验证:");
+            match Mint::deserialize(&serialized) {
+                Ok(deserialized) => {
+                    msg!("--->反序列化成功!");                    
+                    msg!("decimals: {}", deserialized.decimals);
+                    msg!("mint_authority: {} ", deserialized.mint_authority.unwrap());
+                    msg!("freeze_authority: {:?} ", deserialized.freeze_authority.unwrap());
+                    
+                }
+                Err(e) => {
+                    msg!("--->反序列化失败: {:?}", e);
+                }
+            }
+        }
+        Err(e) => {
+            msg!("❌ 序列化失败: {:?}", e);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! 铸币 / 代币账户的 TLV 扩展区（类似 Token-2022 的 StateWithExtensions）
+//!
+//! 账户布局：`[基础状态，占 BaseState::LEN 字节][TLV 条目...]`，
+//! 每个条目为 `类型 u16 LE | 长度 u16 LE | Borsh 编码的值`。
+//! 类型为 0（Uninitialized）表示此后为空闲空间；旧账户尾部为零，因此天然“没有扩展”。
+//! 遇到未知类型时按长度跳过，新程序版本添加的扩展不影响旧条目的读取。
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{entrypoint::ProgramResult, msg, program_error::ProgramError};
+
+use crate::{Mint, TokenAccount};
+
+const TYPE_LEN: usize = 2;
+const LENGTH_LEN: usize = 2;
+const HEADER_LEN: usize = TYPE_LEN + LENGTH_LEN;
+
+/// 扩展类型（新扩展只能追加，不能重排）
+#[repr(u16)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionType {
+    Uninitialized = 0,
+    /// 空铸币自动关闭配置（MintCloseConfig）
+    MintCloseConfig = 1,
+    /// 代币账户社交恢复监护人（GuardianConfig）
+    GuardianConfig = 2,
+    /// 铸币持有人计数（HolderCount）
+    HolderCount = 3,
+    /// 代币账户活动时间（AccountActivity）
+    AccountActivity = 4,
+    /// 代币账户转账备注环形缓冲区（MemoLog）
+    MemoLog = 5,
+    /// 铸币精度调整记录（DecimalsRescale）
+    DecimalsRescale = 6,
+    /// 代币账户余额所在的精度纪元（RescaleEpoch）
+    RescaleEpoch = 7,
+    /// 铸币全局冻结状态（GlobalFreezeState）
+    GlobalFreezeState = 8,
+    /// 代币账户定时解冻（ScheduledThaw）
+    ScheduledThaw = 9,
+    /// 铸币级销毁权限（BurnAuthority）
+    BurnAuthority = 10,
+    /// 全局冻结豁免（FreezeExempt）
+    FreezeExempt = 11,
+    /// 代币账户标签（AccountLabel）
+    AccountLabel = 12,
+    /// 铸币积分有效期（PointsConfig）
+    PointsConfig = 13,
+    /// 代币账户积分批次（PointsLots）
+    PointsLots = 14,
+    /// 铸币休眠账户政策（DormancyPolicy）
+    DormancyPolicy = 15,
+    /// 铸币分项暂停状态（PauseState）
+    PauseState = 16,
+    /// 铸币激活要求（ActivationPolicy）
+    ActivationPolicy = 17,
+    /// 代币账户激活状态（AccountActivation）
+    AccountActivation = 18,
+}
+
+/// 可以携带扩展区的基础状态
+pub trait BaseState {
+    /// 基础状态占用的最大字节数，扩展区从这里开始
+    const LEN: usize;
+}
+
+impl BaseState for Mint {
+    const LEN: usize = Mint::LEN;
+}
+
+impl BaseState for TokenAccount {
+    const LEN: usize = TokenAccount::LEN;
+}
+
+/// 存放在扩展区中的状态
+pub trait Extension: BorshSerialize + BorshDeserialize {
+    const TYPE: ExtensionType;
+    /// 序列化后的最大字节数，即条目中值的长度
+    const LEN: usize;
+}
+
+/// 携带扩展 E 的账户所需的空间
+pub const fn account_len<S: BaseState, E: Extension>() -> usize {
+    S::LEN + HEADER_LEN + E::LEN
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2).map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+/// 查找类型为 extension_type 的条目，返回值的字节范围；
+/// 未找到时返回 Err(第一个空闲位置)
+fn find_entry(tlv: &[u8], extension_type: u16) -> Result<Result<(usize, usize), usize>, ProgramError> {
+    let mut offset = 0;
+    while offset + HEADER_LEN <= tlv.len() {
+        let entry_type = read_u16(tlv, offset).ok_or(ProgramError::InvalidAccountData)?;
+        if entry_type == ExtensionType::Uninitialized as u16 {
+            return Ok(Err(offset));
+        }
+        let length = read_u16(tlv, offset + TYPE_LEN).ok_or(ProgramError::InvalidAccountData)? as usize;
+        let start = offset + HEADER_LEN;
+        let end = start.checked_add(length).ok_or(ProgramError::InvalidAccountData)?;
+        if end > tlv.len() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if entry_type == extension_type {
+            return Ok(Ok((start, end)));
+        }
+        offset = end;
+    }
+    Ok(Err(offset))
+}
+
+fn tlv_region<S: BaseState>(data: &[u8]) -> &[u8] {
+    data.get(S::LEN..).unwrap_or(&[])
+}
+
+/// 读取扩展，账户没有该扩展时返回 None
+pub fn get_extension<S: BaseState, E: Extension>(data: &[u8]) -> Result<Option<E>, ProgramError> {
+    match find_entry(tlv_region::<S>(data), E::TYPE as u16)? {
+        Ok((start, end)) => {
+            let value = &tlv_region::<S>(data)[start..end];
+            E::deserialize(&mut &value[..]).map(Some).map_err(|_| ProgramError::InvalidAccountData)
+        }
+        Err(_) => Ok(None),
+    }
+}
+
+/// 写入扩展：已存在时原地覆盖，否则追加到第一个空闲位置（空间不足时报错）
+pub fn init_extension<S: BaseState, E: Extension>(data: &mut [u8], extension: &E) -> ProgramResult {
+    if data.len() < S::LEN {
+        msg!("Account too small for extensions: {} < {}", data.len(), S::LEN);
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    let tlv = &mut data[S::LEN..];
+    let (start, end) = match find_entry(tlv, E::TYPE as u16)? {
+        Ok(range) => range,
+        Err(offset) => {
+            let end = offset + HEADER_LEN + E::LEN;
+            if end > tlv.len() {
+                msg!("No space for extension {:?}: need {} bytes", E::TYPE, HEADER_LEN + E::LEN);
+                return Err(ProgramError::AccountDataTooSmall);
+            }
+            tlv[offset..offset + TYPE_LEN].copy_from_slice(&(E::TYPE as u16).to_le_bytes());
+            tlv[offset + TYPE_LEN..offset + HEADER_LEN].copy_from_slice(&(E::LEN as u16).to_le_bytes());
+            (offset + HEADER_LEN, end)
+        }
+    };
+    let value = &mut tlv[start..end];
+    value.fill(0);
+    borsh::to_writer(value, extension).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// 列出账户上已初始化的扩展类型（原始 u16，包含本版本未知的类型）
+pub fn get_extension_types<S: BaseState>(data: &[u8]) -> Result<Vec<u16>, ProgramError> {
+    let tlv = tlv_region::<S>(data);
+    let mut types = Vec::new();
+    let mut offset = 0;
+    while offset + HEADER_LEN <= tlv.len() {
+        let entry_type = read_u16(tlv, offset).ok_or(ProgramError::InvalidAccountData)?;
+        if entry_type == ExtensionType::Uninitialized as u16 {
+            break;
+        }
+        let length = read_u16(tlv, offset + TYPE_LEN).ok_or(ProgramError::InvalidAccountData)? as usize;
+        offset = offset + HEADER_LEN + length;
+        if offset > tlv.len() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        types.push(entry_type);
+    }
+    Ok(types)
+}
+
+/// 扩展位图（ExtensionsInitialized）：第 n 位表示类型为 n 的扩展已初始化，类型不小于 64 的未知扩展不计入
+pub fn extension_bitmap<S: BaseState>(data: &[u8]) -> Result<u64, ProgramError> {
+    Ok(get_extension_types::<S>(data)?
+        .into_iter()
+        .filter_map(|entry_type| 1u64.checked_shl(entry_type as u32))
+        .fold(0, |bitmap, bit| bitmap | bit))
+}
@@ -0,0 +1,76 @@
+//! 转账前后钩子：process_transfer 在修改余额前依次调用 `pre_transfer`，
+//! 写回余额后依次调用 `post_transfer`。新的扩展实现 `TransferHook` 并登记到
+//! `TRANSFER_HOOKS` 即可，不需要改动转账处理函数本身。
+
+use solana_program::{entrypoint::ProgramResult, msg, pubkey::Pubkey};
+
+use crate::{TokenAccount, TokenError};
+
+/// 钩子看到的转账信息（余额为修改前 / 修改后的快照）
+pub struct TransferInfo<'a> {
+    pub source_key: &'a Pubkey,
+    pub destination_key: &'a Pubkey,
+    pub source: &'a TokenAccount,
+    pub destination: &'a TokenAccount,
+    pub amount: u64,
+}
+
+pub trait TransferHook {
+    /// 余额修改前调用，返回错误即拒绝本次转账
+    fn pre_transfer(&self, _transfer: &TransferInfo) -> ProgramResult {
+        Ok(())
+    }
+
+    /// 余额写回后调用
+    fn post_transfer(&self, _transfer: &TransferInfo) -> ProgramResult {
+        Ok(())
+    }
+}
+
+/// 余额与冻结状态限制：源账户余额充足、双方均未冻结、目标余额不溢出
+pub struct AccountLimitsHook;
+
+impl TransferHook for AccountLimitsHook {
+    fn pre_transfer(&self, transfer: &TransferInfo) -> ProgramResult {
+        if transfer.source.amount < transfer.amount {
+            return Err(TokenError::InsufficientFunds.into());
+        }
+        if transfer.source.is_frozen() || transfer.destination.is_frozen() {
+            return Err(TokenError::AccountFrozen.into());
+        }
+        if transfer.source_key != transfer.destination_key {
+            transfer
+                .destination
+                .amount
+                .checked_add(transfer.amount)
+                .ok_or(TokenError::Overflow)?;
+        }
+        Ok(())
+    }
+}
+
+/// 转账日志
+pub struct LoggingHook;
+
+impl TransferHook for LoggingHook {
+    fn post_transfer(&self, transfer: &TransferInfo) -> ProgramResult {
+        msg!(
+            "Transferred {} tokens from {} to {}",
+            transfer.amount,
+            transfer.source_key,
+            transfer.destination_key
+        );
+        Ok(())
+    }
+}
+
+/// 按顺序执行的转账钩子
+pub const TRANSFER_HOOKS: &[&dyn TransferHook] = &[&AccountLimitsHook, &LoggingHook];
+
+pub fn run_pre_transfer(transfer: &TransferInfo) -> ProgramResult {
+    TRANSFER_HOOKS.iter().try_for_each(|hook| hook.pre_transfer(transfer))
+}
+
+pub fn run_post_transfer(transfer: &TransferInfo) -> ProgramResult {
+    TRANSFER_HOOKS.iter().try_for_each(|hook| hook.post_transfer(transfer))
+}
@@ -0,0 +1,168 @@
+//! 经典 SPL Token 线格式编解码（legacy-codec 特性开启时由 process_instruction 使用）
+//!
+//! 单字节标签 + 小端序字段，可选公钥为 1 字节标签 + 32 字节（与 spl-token 指令打包一致）。
+//! 与经典程序共有的指令沿用其标签，账户顺序本就相同；其余扩展指令使用
+//! `EXTENDED_TAG` 前缀，后接 `TokenInstruction::unpack` 可解析的数据（裸 Borsh 或版本化信封）。
+//! ThawAccount 在经典标签后追加 2 字节小端序原因码（经典程序没有该字段）。
+//! 经典 SetAuthority（MintTokens）解析为 SetAuthority，已弃用的 SetMintAuthority 只按扩展指令编码。
+
+use borsh::BorshSerialize;
+use solana_program::{msg, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{AuthorityType, TokenError, TokenInstruction};
+
+pub const INITIALIZE_MINT_TAG: u8 = 0;
+pub const INITIALIZE_ACCOUNT_TAG: u8 = 1;
+pub const TRANSFER_TAG: u8 = 3;
+pub const SET_AUTHORITY_TAG: u8 = 6;
+pub const MINT_TO_TAG: u8 = 7;
+pub const BURN_TAG: u8 = 8;
+pub const THAW_ACCOUNT_TAG: u8 = 11;
+/// 扩展指令前缀：后接 Borsh 编码的 TokenInstruction
+pub const EXTENDED_TAG: u8 = 255;
+
+/// SetAuthority 中的 AuthorityType::MintTokens，本程序只支持这一种
+const AUTHORITY_TYPE_MINT_TOKENS: u8 = 0;
+
+fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), ProgramError> {
+    if input.len() < 8 {
+        return Err(TokenError::InvalidInstruction.into());
+    }
+    let (value, rest) = input.split_at(8);
+    let value = u64::from_le_bytes(value.try_into().map_err(|_| TokenError::InvalidInstruction)?);
+    Ok((value, rest))
+}
+
+/// 数据恰好是一个 u64
+fn unpack_u64_exact(input: &[u8]) -> Result<u64, ProgramError> {
+    let (value, rest) = unpack_u64(input)?;
+    expect_empty(rest)?;
+    Ok(value)
+}
+
+fn unpack_u16(input: &[u8]) -> Result<(u16, &[u8]), ProgramError> {
+    if input.len() < 2 {
+        return Err(TokenError::InvalidInstruction.into());
+    }
+    let (value, rest) = input.split_at(2);
+    let value = u16::from_le_bytes(value.try_into().map_err(|_| TokenError::InvalidInstruction)?);
+    Ok((value, rest))
+}
+
+fn unpack_pubkey(input: &[u8]) -> Result<(Pubkey, &[u8]), ProgramError> {
+    if input.len() < 32 {
+        return Err(TokenError::InvalidInstruction.into());
+    }
+    let (key, rest) = input.split_at(32);
+    let key = Pubkey::try_from(key).map_err(|_| TokenError::InvalidInstruction)?;
+    Ok((key, rest))
+}
+
+fn unpack_pubkey_option(input: &[u8]) -> Result<(Option<Pubkey>, &[u8]), ProgramError> {
+    match input.split_first() {
+        Some((0, rest)) => Ok((None, rest)),
+        Some((1, rest)) => {
+            let (key, rest) = unpack_pubkey(rest)?;
+            Ok((Some(key), rest))
+        }
+        _ => Err(TokenError::InvalidInstruction.into()),
+    }
+}
+
+/// 定长字段解析完后不允许再有剩余字节（与 TokenInstruction::unpack 对尾随数据的处理一致）
+fn expect_empty(rest: &[u8]) -> Result<(), ProgramError> {
+    if !rest.is_empty() {
+        msg!("{} trailing bytes after the encoded instruction", rest.len());
+        return Err(TokenError::InvalidInstructionData.into());
+    }
+    Ok(())
+}
+
+fn pack_pubkey_option(key: &Option<Pubkey>, buf: &mut Vec<u8>) {
+    match key {
+        Some(key) => {
+            buf.push(1);
+            buf.extend_from_slice(key.as_ref());
+        }
+        None => buf.push(0),
+    }
+}
+
+/// 按经典线格式解析指令
+pub fn unpack(input: &[u8]) -> Result<TokenInstruction, ProgramError> {
+    let (&tag, rest) = input.split_first().ok_or(TokenError::InvalidInstruction)?;
+    Ok(match tag {
+        INITIALIZE_MINT_TAG => {
+            let (&decimals, rest) = rest.split_first().ok_or(TokenError::InvalidInstruction)?;
+            let (mint_authority, rest) = unpack_pubkey(rest)?;
+            let (freeze_authority, rest) = unpack_pubkey_option(rest)?;
+            expect_empty(rest)?;
+            TokenInstruction::InitializeMint { decimals, mint_authority, freeze_authority }
+        }
+        INITIALIZE_ACCOUNT_TAG => {
+            expect_empty(rest)?;
+            TokenInstruction::InitializeAccount
+        }
+        TRANSFER_TAG => TokenInstruction::Transfer { amount: unpack_u64_exact(rest)? },
+        SET_AUTHORITY_TAG => {
+            let (&authority_type, rest) = rest.split_first().ok_or(TokenError::InvalidInstruction)?;
+            if authority_type != AUTHORITY_TYPE_MINT_TOKENS {
+                return Err(TokenError::InvalidInstruction.into());
+            }
+            let (new_authority, rest) = unpack_pubkey_option(rest)?;
+            expect_empty(rest)?;
+            TokenInstruction::SetAuthority { authority_type: AuthorityType::MintTokens, new_authority }
+        }
+        MINT_TO_TAG => TokenInstruction::MintTo { amount: unpack_u64_exact(rest)? },
+        BURN_TAG => TokenInstruction::Burn { amount: unpack_u64_exact(rest)? },
+        THAW_ACCOUNT_TAG => {
+            let (reason_code, rest) = unpack_u16(rest)?;
+            expect_empty(rest)?;
+            TokenInstruction::ThawAccount { reason_code }
+        }
+        EXTENDED_TAG => TokenInstruction::unpack(rest)?,
+        _ => return Err(TokenError::InvalidInstruction.into()),
+    })
+}
+
+/// 按经典线格式编码指令
+pub fn pack(instruction: &TokenInstruction) -> Result<Vec<u8>, ProgramError> {
+    let mut buf = Vec::new();
+    match instruction {
+        TokenInstruction::InitializeMint { decimals, mint_authority, freeze_authority } => {
+            buf.push(INITIALIZE_MINT_TAG);
+            buf.push(*decimals);
+            buf.extend_from_slice(mint_authority.as_ref());
+            pack_pubkey_option(freeze_authority, &mut buf);
+        }
+        TokenInstruction::InitializeAccount => buf.push(INITIALIZE_ACCOUNT_TAG),
+        TokenInstruction::Transfer { amount } => {
+            buf.push(TRANSFER_TAG);
+            buf.extend_from_slice(&amount.to_le_bytes());
+        }
+        TokenInstruction::SetAuthority { authority_type: AuthorityType::MintTokens, new_authority } => {
+            buf.push(SET_AUTHORITY_TAG);
+            buf.push(AUTHORITY_TYPE_MINT_TOKENS);
+            pack_pubkey_option(new_authority, &mut buf);
+        }
+        TokenInstruction::MintTo { amount } => {
+            buf.push(MINT_TO_TAG);
+            buf.extend_from_slice(&amount.to_le_bytes());
+        }
+        TokenInstruction::Burn { amount } => {
+            buf.push(BURN_TAG);
+            buf.extend_from_slice(&amount.to_le_bytes());
+        }
+        TokenInstruction::ThawAccount { reason_code } => {
+            buf.push(THAW_ACCOUNT_TAG);
+            buf.extend_from_slice(&reason_code.to_le_bytes());
+        }
+        other => {
+            buf.push(EXTENDED_TAG);
+            other
+                .serialize(&mut buf)
+                .map_err(|_| ProgramError::InvalidInstructionData)?;
+        }
+    }
+    Ok(buf)
+}
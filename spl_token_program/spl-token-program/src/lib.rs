@@ -4,17 +4,73 @@ use borsh::{BorshDeserialize, BorshSerialize};
 
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    entrypoint,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
     entrypoint::ProgramResult,
+    epoch_schedule::EpochSchedule,
+    hash::hashv,
+    instruction::{AccountMeta, Instruction},
+    log::sol_log_data,
     program_error::ProgramError,
-    pubkey::Pubkey,
-    sysvar::{rent::Rent, Sysvar},
+    pubkey::{Pubkey, MAX_SEED_LEN},
+    sysvar::{
+        clock::Clock,
+        instructions::{load_current_index_checked, load_instruction_at_checked},
+        rent::Rent,
+        Sysvar,
+    },
     msg,
-    program::invoke,
+    program::{invoke, invoke_signed, set_return_data},
+    program_pack::IsInitialized,
+    program_utils::limited_deserialize,
     system_instruction,
+    system_program,
 };
 use std::collections::BTreeMap;
 
+pub mod balance;
+pub mod bridge;
+pub mod compression;
+pub mod context;
+#[cfg(feature = "debug-tools")]
+pub mod debug_tools;
+pub mod extension;
+pub mod hooks;
+pub mod legacy;
+pub mod math;
+#[cfg(feature = "no-alloc")]
+pub mod no_alloc;
+pub mod pack;
+#[cfg(feature = "parser")]
+pub mod parser;
+pub mod permit;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+pub mod validators;
+
+use bridge::{BridgeEvent, TransferPayload};
+use compression::{CompressedLeaf, CompressedTree, CompressionEvent};
+use context::{LoadedMint, LoadedTokenAccount, ProcessingContext};
+use extension::{Extension, ExtensionType};
+use hooks::TransferInfo;
+use pack::ManualPack;
+use permit::PermitMessage;
+
+/// 已关闭账户首字节的墓碑标记：租金转走后账户在同一 slot 内仍可被读到，
+/// 铸币与各 PDA 状态的首字段都是 bool，0xFF 不是合法取值，旧数据无法再被反序列化
+pub const CLOSED_ACCOUNT_DISCRIMINATOR: u8 = 0xFF;
+
+// 规范部署的程序 ID（id() / check_id()）；处理函数本身不限制 program_id，同一份程序可部署在其他地址
+solana_program::declare_id!("ByiUxkVUtZM8fHoVFM3wsWVmaxL43i81G8eAHWKbwBBu");
+
+/// program_id 必须是规范部署的程序 ID，供指令构造函数与 CPI 调用方在拼装指令前检查
+pub fn check_program_account(program_id: &Pubkey) -> ProgramResult {
+    if !check_id(program_id) {
+        msg!("Program id {} is not the canonical token program {}", program_id, ID);
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
 // 错误类型定义
 #[derive(Debug, Clone)]
 pub enum TokenError {
@@ -24,6 +80,34 @@ pub enum TokenError {
     Unauthorized,
     MintMismatch,
     AccountFrozen,
+    Overflow,
+    SlippageExceeded,
+    InvalidCurve,
+    InvalidOracle,
+    StaleOraclePrice,
+    InvalidRebase,
+    InvalidProof,
+    AlreadyClaimed,
+    CheckpointUnavailable,
+    ReceiptAlreadyExists,
+    NftSupplyExceeded,
+    InvalidMetadata,
+    NotNftMint,
+    EditionSupplyExceeded,
+    TransferProgramNotAllowed,
+    TimelockNotElapsed,
+    InvalidAuction,
+    AuctionSoldOut,
+    SaleCapReached,
+    UnsupportedInstructionVersion,
+    AuthorityRevoked,
+    HtlcExpired,
+    InvalidInstructionData,
+    RescaleEpochMismatch,
+    OwnerOffCurve,
+    OwnerOnCurve,
+    OperationPaused,
+    AccountNotActivated,
 }
 impl From<TokenError> for ProgramError {
     fn from(e: TokenError) -> Self {
@@ -57,6 +141,7 @@ pub enum TokenInstruction {
     /// [0] 铸币账户 (可写)
     /// [1] 目标代币账户 (可写)
     /// [2] 铸币权限账户 (签名者)
+    /// [3] 投票检查点 PDA (可写, 仅当目标账户已委托投票)
     MintTo {
         amount: u64,
     },
@@ -66,546 +151,10455 @@ pub enum TokenInstruction {
     /// [0] 源代币账户 (可写)
     /// [1] 目标代币账户 (可写)
     /// [2] 账户所有者 (签名者)
+    /// [3] 源账户所属的铸币账户 (可写, 用于同步持有人计数、检查全局冻结与转账暂停)
+    /// [..] 转账速度跟踪 PDA (可写, 仅当源账户开启了速度限制) seeds = ["velocity", source]
+    ///     窗口内累计转出超过上限时，源账户被自动冻结，本次转账不执行（交易仍成功以保留冻结状态）
+    /// [..] 投票检查点 PDA (可写, 源/目标账户已委托投票时依次提供)
+    /// [..] NFT 账户转账时再依次提供: 转账白名单 PDA seeds = ["transfer_allowlist", mint], 指令 sysvar
+    /// [..] 引用键 (只读, 可选, 至多 MAX_TRANSFER_REFERENCES 个)：不参与任何校验，记入 TransferEvent::Referenced，
+    ///     商户可按 Solana Pay 的方式用订单 ID 对应的地址关联链上支付
     Transfer {
         amount: u64,
     },
     
-    /// 销毁代币
+    /// 销毁代币（所有者、铸币的销毁权限，或在 PermitApprove 额度内代为销毁的受托人，额度随之扣减）
     /// 账户列表:
     /// [0] 代币账户 (可写)
     /// [1] 铸币账户 (可写)
-    /// [2] 账户所有者 (签名者)
+    /// [2] 账户所有者、销毁权限或受托人 (签名者)
+    /// [..] 额度 PDA (可写, 仅当签名者为受托人) seeds = ["allowance", token_account, delegate]
+    /// [..] 投票检查点 PDA (可写, 仅当代币账户已委托投票)
     Burn {
         amount: u64,
     },
     
-    /// 设置铸币权限
+    /// 设置铸币权限（已弃用，请改用 SetAuthority { authority_type: MintTokens }）
+    /// 按 SetAuthority { authority_type: MintTokens } 处理，成功后额外发出 AuthorityEvent::DeprecatedSetMintAuthority，
+    /// 供运维定位仍在使用旧指令的客户端；客户端可用 migrateSetMintAuthorityInstruction 改写旧指令
     /// 账户列表:
     /// [0] 铸币账户 (可写)
     /// [1] 当前铸币权限 (签名者)
     SetMintAuthority {
         new_authority: Option<Pubkey>,
     },
-}
 
-// 铸币账户状态
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
-pub struct Mint {
-    pub is_initialized: bool, 
-    pub decimals: u8,  
-    pub mint_authority: Option<Pubkey>, 
-    pub supply: u64,  
-    pub freeze_authority: Option<Pubkey>, 
-}
-/*[1, 9, 1, 
-155, 22, 161, 0, 165, 161, 89, 151, 
-69, 21, 189, 198, 115, 47, 220, 42, 
-56, 108, 222, 27, 178, 156, 220, 16, 
-176, 224, 163, 9, 165, 49, 153, 117,
-0, 0, 0, 0, 0, 0, 0, 0,
-0]",*/
-impl Mint {
-    pub const LEN: usize = 1 + 1 + 33 + 8 + 33; // 序列化后的大小
-    
-    pub fn new(
-        decimals: u8,
-        mint_authority: Pubkey,
-        freeze_authority: Option<Pubkey>,
-    ) -> Self {
-        Self {
-            is_initialized: true,
-            decimals,
-            mint_authority: Some(mint_authority),
-            supply: 0,
-            freeze_authority,
-        }
-    }
-}
+    /// 初始化联合曲线（铸币权限移交给曲线 PDA）
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 曲线状态 PDA (可写) seeds = ["curve", mint]
+    /// [2] SOL 金库 PDA (可写) seeds = ["curve_vault", mint]
+    /// [3] 当前铸币权限 (签名者)
+    /// [4] 付款账户 (签名者, 可写)
+    /// [5] 系统程序
+    InitializeCurve {
+        curve: CurveType,
+    },
 
-// 代币账户状态
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
-pub struct TokenAccount {
-    pub is_initialized: bool, //1
-    pub mint: Pubkey, //32
-    pub owner: Pubkey, //32
-    pub amount: u64, //8
-    pub is_frozen: bool,//1
-}
+    /// 按曲线价格用 SOL 购买（铸造）代币
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 曲线状态 PDA
+    /// [2] SOL 金库 PDA (可写)
+    /// [3] 买方代币账户 (可写)
+    /// [4] 买方 (签名者, 可写)
+    /// [5] 系统程序
+    /// [6] 投票检查点 PDA (可写, 仅当买方代币账户已委托投票)
+    BuyFromCurve {
+        amount: u64,
+        max_lamports: u64,
+    },
 
-impl TokenAccount {
-    pub const LEN: usize = 1 + 32 + 32 + 8 + 1; // 序列化后的大小
-    
-    pub fn new(mint: Pubkey, owner: Pubkey) -> Self {
-        Self {
-            is_initialized: true,
-            mint,
-            owner,
-            amount: 0,
-            is_frozen: false,
-        }
-    }
-}
+    /// 按曲线价格卖出（销毁）代币换回 SOL
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 曲线状态 PDA
+    /// [2] SOL 金库 PDA (可写)
+    /// [3] 卖方代币账户 (可写)
+    /// [4] 代币账户所有者 (签名者, 可写，接收 SOL)
+    /// [5] 投票检查点 PDA (可写, 仅当卖方代币账户已委托投票)
+    SellToCurve {
+        amount: u64,
+        min_lamports: u64,
+    },
 
-// 程序入口点
-entrypoint!(process_instruction);
+    /// 配置预言机喂价（首次配置时铸币权限移交给预言机配置 PDA）
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 预言机配置 PDA (可写) seeds = ["oracle", mint]
+    /// [2] 当前权限 (签名者) 首次为铸币权限，之后为配置中的 authority
+    /// [3] 付款账户 (签名者, 可写)
+    /// [4] 系统程序
+    ConfigureOracle {
+        price_feed: Option<Pubkey>,
+        max_staleness_slots: u64,
+        collateral_ratio_bps: u16,
+    },
 
-pub fn process_instruction(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    instruction_data: &[u8],
-) -> ProgramResult {
-    msg!("SPL Token Program: Processing instruction");
-    // 现在尝试 Borsh 反序列化
-    let instruction = TokenInstruction::try_from_slice(instruction_data)
-        .map_err(|_| { TokenError::InvalidInstruction })?;    
+    /// 存入 SOL 抵押品，按 Pyth 喂价铸造代币
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 预言机配置 PDA
+    /// [2] Pyth 价格账户
+    /// [3] 抵押品金库 PDA (可写) seeds = ["collateral_vault", mint]
+    /// [4] 目标代币账户 (可写)
+    /// [5] 存款人 (签名者, 可写)
+    /// [6] 系统程序
+    /// [7] 投票检查点 PDA (可写, 仅当目标代币账户已委托投票)
+    MintAgainstCollateral {
+        collateral_lamports: u64,
+        min_amount: u64,
+    },
 
-    match instruction {
-        TokenInstruction::InitializeMint { decimals, mint_authority, freeze_authority } => {
-            msg!("====InitializeMint====");
-            process_initialize_mint(program_id, accounts, decimals, mint_authority, freeze_authority)
-        }
-        TokenInstruction::InitializeAccount => {
-            msg!("====InitializeAccount====");
-            process_initialize_account(program_id, accounts)
-        }
-        TokenInstruction::MintTo { amount } => {
-            msg!("====MintTo====");
-            process_mint_to(program_id, accounts, amount)
-        }
-        TokenInstruction::Transfer { amount } => {
-            msg!("====Transfer====");
-            process_transfer(program_id, accounts, amount)
-        }
-        TokenInstruction::Burn { amount } => {
-            msg!("====Burn====");
-            process_burn(program_id, accounts, amount)
-        }
-        TokenInstruction::SetMintAuthority { new_authority } => {
-            msg!("====SetMintAuthority====");
-            process_set_mint_authority(program_id, accounts, new_authority)
-        }
-    }
-}
+    /// 为铸币开启弹性供应（rebase）模式
+    /// 账户列表:
+    /// [0] 铸币账户
+    /// [1] rebase 配置 PDA (可写) seeds = ["rebase", mint]
+    /// [2] 铸币权限 (签名者)
+    /// [3] 付款账户 (签名者, 可写)
+    /// [4] 系统程序
+    EnableRebase,
 
-/// 初始化铸币账户
-fn process_initialize_mint(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    decimals: u8,
-    mint_authority: Pubkey,
-    freeze_authority: Option<Pubkey>,
-) -> ProgramResult {
-    let account_info_iter = &mut accounts.iter();
-    let mint_account = next_account_info(account_info_iter)?;
-    let rent_sysvar_account = next_account_info(account_info_iter)?;
-   
-    // 验证账户所有权
-    if mint_account.owner != program_id {
-        return Err(ProgramError::IncorrectProgramId);
-    }
-    
-    // 检查租金豁免
-    let rent = &Rent::from_account_info(rent_sysvar_account)?;
-    if !rent.is_exempt(mint_account.lamports(), mint_account.data_len()) {
-        return Err(TokenError::NotRentExempt.into());
-    }
+    /// 调整全局缩放因子：multiplier = multiplier * numerator / denominator
+    /// 账户列表:
+    /// [0] 铸币账户
+    /// [1] rebase 配置 PDA (可写)
+    /// [2] rebase 权限 (签名者)
+    Rebase {
+        numerator: u64,
+        denominator: u64,
+    },
 
+    /// 注入分红：把奖励代币转入分红金库，并记录余额快照（默克尔根）
+    /// 快照决定每个持有人能领多少，因此只有持仓铸币的铸币权限可以注资
+    /// 账户列表:
+    /// [0] 持仓铸币账户
+    /// [1] 分红 PDA (可写) seeds = ["distribution", mint, distribution_id(le)]
+    /// [2] 奖励金库代币账户 (可写) 所有者须为分红 PDA
+    /// [3] 注资方奖励代币账户 (可写)
+    /// [4] 注资方 (签名者, 可写) 须为持仓铸币的铸币权限
+    /// [5] 系统程序
+    /// [6] 奖励铸币账户 (可写)
+    /// [7] 投票检查点 PDA (可写, 仅当注资方代币账户已委托投票)
+    FundDistribution {
+        distribution_id: u64,
+        snapshot_root: [u8; 32],
+        snapshot_supply: u64,
+        amount: u64,
+    },
 
-    
-    // 初始化铸币账户
-    let mut mint_data = mint_account.data.borrow_mut();
-    let mint = Mint::new(decimals, mint_authority, Some(Pubkey::new_from_array([1;32])));
-    //let mint = Mint::new(decimals, mint_authority, freeze_authority);
-    mint.serialize(&mut &mut mint_data[..])?;
-    
-    msg!("Mint initialized with authority: {}", mint_authority);
-    msg!("Mint initialized with mint_data: {:?}", &mut mint_data[..]);
-    Ok(())
-}
+    /// 按快照余额领取分红
+    /// 账户列表:
+    /// [0] 持仓铸币账户
+    /// [1] 分红 PDA (可写)
+    /// [2] 奖励金库代币账户 (可写)
+    /// [3] 持仓代币账户（快照中的叶子）
+    /// [4] 接收奖励的代币账户 (可写)
+    /// [5] 领取记录 PDA (可写) seeds = ["dividend_claim", distribution, holder_token_account]
+    /// [6] 持仓代币账户所有者 (签名者, 可写)
+    /// [7] 系统程序
+    /// [8] 奖励铸币账户 (可写)
+    /// [9] 投票检查点 PDA (可写, 仅当接收账户已委托投票)
+    ClaimDividend {
+        snapshot_balance: u64,
+        proof: Vec<[u8; 32]>,
+    },
 
-fn serialize_token_instruction() {
-    test1();
-}
+    /// 委托（或取消委托）代币账户的投票权
+    /// 账户列表:
+    /// [0] 代币账户 (可写)
+    /// [1] 账户所有者 (签名者, 可写，首次委托时为检查点账户付租金)
+    /// [2] 系统程序
+    /// [3] 原受托人投票检查点 PDA (可写, 仅当当前已委托)
+    /// [4] 新受托人投票检查点 PDA (可写, 仅当 delegatee 为 Some) seeds = ["votes", mint, delegatee]
+    DelegateVotes {
+        delegatee: Option<Pubkey>,
+    },
 
-fn test1(){
-    msg!("🔧 Rust 序列化测试");    
-    // 你的数据
-    let decimals = 9;
-    let mint_authority: Pubkey = "5higFJ6xCuganUCvFFLDnZhL4Jb28KYEfBrVzCDGpGt8".parse().unwrap();
-    //let freeze_authority: Option<Pubkey> = None;
-     let freeze_authority: Option<Pubkey> = Some("GjphYQcbP1m3SYTXkHC1E3MJrCEeH8vL6f3HuoZ9fJ2x".parse().unwrap());
-    
-    msg!("输入数据:");
-    msg!("  decimals: {}", decimals);
-    msg!("  mint_authority: {}", mint_authority);
-    msg!("  freeze_authority: {:?}", freeze_authority);
-    
-    // 创建指令
-    let instruction = TokenInstruction::InitializeMint {
-        decimals,
-        mint_authority,
-        freeze_authority,
-    };
-    
-    // 序列化
-    match instruction.try_to_vec() {
-        Ok(serialized) => {
-            msg!("\n✅ 序列化成功!");
-            msg!("序列化结果:");
-            msg!("  长度: {} 字节", serialized.len());
-            msg!("  十六进制: {:?}", serialized.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>());
-            msg!("  字节数组: {:?}", serialized);
-            
-            // 详细字节分析
-            msg!("\n🔬 详细字节分析:");
-            msg!("  [0] 枚举判别式: {} (InitializeMint)", serialized[0]);
-            msg!("  [1] decimals: {}", serialized[1]);
-            msg!("  [2-33] mint_authority: 32 bytes");
-            
-            // 检查 mint_authority 是否正确
-            let mint_auth_bytes = &serialized[2..34];
-            if let Ok(reconstructed_mint) = Pubkey::try_from(mint_auth_bytes) {
-                msg!("     重建的 mint_authority: {}", reconstructed_mint);
-                msg!("     匹配: {}", reconstructed_mint == mint_authority);
-            }
-            
-            msg!("  [34] freeze_authority option: {} (0 = None)", serialized[34]);
-            msg!("  [35-66] freeze_authority data: 32 bytes of zeros");
-            
-            // 验证总长度
-            let expected_length = 1 + 1 + 32 + 1 + 32; // 67 bytes
-            msg!("\n📏 长度验证:");
-            msg!("  期望: {} 字节", expected_length);
-            msg!("  实际: {} 字节", serialized.len());
-            msg!("  匹配: {}", serialized.len() == expected_length);
-            
-            // 反序列化验证
-            msg!("\n🔄 反序列化验证:");
-            match TokenInstruction::try_from_slice(&serialized) {
-                Ok(deserialized) => {
-                    msg!("  ✅ 反序列化成功!");
-                    if let TokenInstruction::InitializeMint { decimals: d, mint_authority: ma, freeze_authority: fa } = deserialized {
-                        msg!("     decimals: {} (匹配: {})", d, d == decimals);
-                        msg!("     mint_authority: {} (匹配: {})", ma, ma == mint_authority);
-                        msg!("     freeze_authority: {:?} (匹配: {})", fa, fa == freeze_authority);
-                    }
-                }
-                Err(e) => {
-                    msg!("  ❌ 反序列化失败: {:?}", e);
-                }
-            }
-        }
-        Err(e) => {
-            msg!("❌ 序列化失败: {:?}", e);
-        }
-    }
-}
+    /// 查询受托人在指定 slot 的投票权（通过 return data 返回 u64 小端序）
+    /// 账户列表:
+    /// [0] 投票检查点 PDA
+    GetVotesAt {
+        slot: u64,
+    },
 
-fn test2(){
-    msg!("🔧 Rust 序列化测试");    
-    // 你的数据
-    let decimals = 9;
-    let mint_authority: Pubkey = "5higFJ6xCuganUCvFFLDnZhL4Jb28KYEfBrVzCDGpGt8".parse().unwrap();
-    //let freeze_authority: Option<Pubkey> = None;
-     let freeze_authority: Option<Pubkey> = Some("GjphYQcbP1m3SYTXkHC1E3MJrCEeH8vL6f3HuoZ9fJ2x".parse().unwrap());
-    
-    msg!("输入数据:");
-    msg!("  decimals: {}", decimals);
-    msg!("  mint_authority: {}", mint_authority);
-    msg!("  freeze_authority: {:?}", freeze_authority);
-
-
-    let instruction = Mint::new(decimals, mint_authority, freeze_authority);
-
-    // 序列化
-    match instruction.try_to_vec() {
-        Ok(serialized) => {
-            msg!("\n✅ 序列化成功!");
-            msg!("序列化结果:");
-            msg!("  长度: {} 字节", serialized.len());
-            msg!("  十六进制: {:?}", serialized.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>());
-            msg!("  字节数组: {:?}", serialized);          
-             
-            // 反序列化验证
-            msg!("\n🔄 反序列化验证:");
-            match Mint::try_from_slice(&serialized) {
-                Ok(deserialized) => {
-                    msg!("--->反序列化成功!");                    
-                    msg!("decimals: {}", deserialized.decimals);
-                    msg!("mint_authority: {} ", deserialized.mint_authority.unwrap());
-                    msg!("freeze_authority: {:?} ", deserialized.freeze_authority.unwrap());
-                    
-                }
-                Err(e) => {
-                    msg!("--->反序列化失败: {:?}", e);
-                }
-            }
-        }
-        Err(e) => {
-            msg!("❌ 序列化失败: {:?}", e);
-        }
-    }
-}
-/// 初始化代币账户
-fn process_initialize_account(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-) -> ProgramResult {
-    let account_info_iter = &mut accounts.iter();
-    let token_account = next_account_info(account_info_iter)?;
-    let mint_account = next_account_info(account_info_iter)?;
-    let owner_account = next_account_info(account_info_iter)?;
-    let rent_sysvar_account = next_account_info(account_info_iter)?;
-    
-    // 验证账户所有权
-    if token_account.owner != program_id {
-        return Err(ProgramError::IncorrectProgramId);
-    }
-    
-    // 检查租金豁免
-    let rent = &Rent::from_account_info(rent_sysvar_account)?;
-    if !rent.is_exempt(token_account.lamports(), token_account.data_len()) {
-        return Err(TokenError::NotRentExempt.into());
-    }
-    
-    // 初始化代币账户
-    let mut token_data = token_account.data.borrow_mut();
-    let token_acc = TokenAccount::new(*mint_account.key, *owner_account.key);
-    token_acc.serialize(&mut &mut token_data[..])?;
-    
-    msg!("Token account initialized for owner: {}", owner_account.key);
-    msg!("Token account initialized for token: {:?}", &mut token_data[..]);
-    Ok(())
-}
+    /// 为代币/铸币账户补充租金，并重新校验租金豁免
+    /// lamports 为 0 时自动补足差额
+    /// 账户列表:
+    /// [0] 目标账户 (可写, 本程序拥有)
+    /// [1] 付款账户 (签名者, 可写)
+    /// [2] 系统程序
+    TopUpRent {
+        lamports: u64,
+    },
 
-/// 铸造代币
-fn process_mint_to(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    amount: u64,
-) -> ProgramResult {
-   
-    let account_info_iter = &mut accounts.iter();
-    let mint_account = next_account_info(account_info_iter)?;    
-    let token_account = next_account_info(account_info_iter)?;
-    let mint_authority_account = next_account_info(account_info_iter)?;
-    
-    // 验证铸币权限
-    //pub const LEN: usize = 1 + 1 + 33 + 8 + 33 = 76; // 序列化后的大小
-    msg!("mint_account: {}", mint_account.key);
-    let mint_data = mint_account.data.borrow();
-    let mut len:usize = mint_data.len();
-
-    if mint_data[43] == 0 {
-        len = 44;
-    }
-    msg!("mint_account: {}; len: {}", mint_account.key, len);
-    msg!("all mint_data: {:?}", &mint_data[..]);
-    msg!("mint_data: {:?}", &mint_data[..len]);
-
-    let expected_size = std::mem::size_of::<Mint>();
-    //let serialized_len = mint.try_to_vec().unwrap().len();
-    msg!("expected_size: {};", expected_size);
-    let mut mint = Mint::deserialize(&mut &mint_data[..76])?;
-    
-    if !mint_authority_account.is_signer {
-        msg!("follow1");
-        return Err(TokenError::Unauthorized.into());
-    }
-    
-    if let Some(auth) = mint.mint_authority {
-        if auth != *mint_authority_account.key {
-            msg!("follow2");
-            return Err(TokenError::Unauthorized.into());
-        }
-    } else {
-        msg!("follow3");
-        return Err(TokenError::Unauthorized.into());
-    }
-    msg!("follow4");
-    // 更新铸币账户
-    mint.supply += amount;
-    drop(mint_data);
-    mint.serialize(&mut &mut mint_account.data.borrow_mut()[..])?;
-    msg!("follow5");
-    // 更新代币账户
-    let mut token_data = token_account.data.borrow_mut();
-    msg!("follow6");
-    let mut token_acc = TokenAccount::deserialize(&mut &token_data[..74])?;
-    msg!("follow7");
-    token_acc.amount += amount;
-    token_acc.serialize(&mut &mut token_data[..])?;
-    
-    msg!("Minted {} tokens to {}", amount, token_account.key);
-    Ok(())
-}
+    /// 为接收者创建关联代币账户（不存在时）并铸造代币
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 关联代币账户 PDA (可写) seeds = ["associated", wallet, mint]
+    /// [2] 接收者钱包
+    /// [3] 铸币权限 (签名者)
+    /// [4] 付款账户 (签名者, 可写)
+    /// [5] 系统程序
+    /// [6] 投票检查点 PDA (可写, 仅当已存在的关联账户已委托投票)
+    MintToNewAccount {
+        amount: u64,
+    },
 
-/// 转移代币
-fn process_transfer(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    amount: u64,
-) -> ProgramResult {
-    let account_info_iter = &mut accounts.iter();
-    let source_account = next_account_info(account_info_iter)?;
-    let dest_account = next_account_info(account_info_iter)?;
-    let owner_account = next_account_info(account_info_iter)?;
-    
-    // 验证所有者权限
-    if !owner_account.is_signer {
-        return Err(TokenError::Unauthorized.into());
-    }
-    
-    // 更新源账户
-    let mut source_data = source_account.data.borrow_mut();
-    let mut source_acc = TokenAccount::deserialize(&mut &source_data[..])?;
-    
-    if source_acc.owner != *owner_account.key {
-        return Err(TokenError::Unauthorized.into());
-    }
-    
-    if source_acc.amount < amount {
-        return Err(TokenError::InsufficientFunds.into());
-    }
-    
-    source_acc.amount -= amount;
-    source_acc.serialize(&mut &mut source_data[..])?;
-    
-    // 更新目标账户
-    let mut dest_data = dest_account.data.borrow_mut();
-    let mut dest_acc = TokenAccount::deserialize(&mut &dest_data[..])?;
-    dest_acc.amount += amount;
-    dest_acc.serialize(&mut &mut dest_data[..])?;
-    
-    msg!("Transferred {} tokens from {} to {}", amount, source_account.key, dest_account.key);
-    Ok(())
-}
+    /// 销毁代币并写入销毁凭证 PDA（供跨链桥、销毁兑换等场景验证）
+    /// 账户列表:
+    /// [0] 代币账户 (可写)
+    /// [1] 铸币账户 (可写)
+    /// [2] 账户所有者 (签名者, 可写, 支付凭证租金)
+    /// [3] 销毁凭证 PDA (可写) seeds = ["burn_receipt", mint, owner, receipt_id]
+    /// [4] 系统程序
+    /// [5] 投票检查点 PDA (可写, 仅当代币账户已委托投票)
+    BurnWithReceipt {
+        amount: u64,
+        receipt_id: u64,
+    },
 
-/// 销毁代币
-fn process_burn(
-    program_id: &Pubkey,
+    /// 初始化 NFT 铸币账户（decimals 固定为 0，供应量上限为 1，首次铸造后自动撤销铸币权限）
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 租金系统账户
+    InitializeNftMint {
+        mint_authority: Pubkey,
+        freeze_authority: Option<Pubkey>,
+    },
+
+    /// 通过 CPI 为铸币创建 Metaplex 元数据账户（名称、符号、图片 URI）
+    /// 账户列表:
+    /// [0] 铸币账户
+    /// [1] 元数据 PDA (可写) seeds = ["metadata", metadata_program, mint] (元数据程序下推导)
+    /// [2] 铸币权限 (签名者, 同时作为元数据更新权限)
+    /// [3] 付款账户 (签名者, 可写)
+    /// [4] 系统程序
+    /// [5] Metaplex Token Metadata 程序
+    CreateMetadata {
+        args: MetadataArgs,
+        is_mutable: bool,
+    },
+
+    /// 把已铸造的 NFT 设为母版，之后可从中印刷编号副本
+    /// 账户列表:
+    /// [0] 母版铸币账户 (NFT 模式, 供应量为 1)
+    /// [1] 母版版本 PDA (可写) seeds = ["master_edition", mint]
+    /// [2] 持有母版的代币账户
+    /// [3] 代币账户所有者 (签名者, 可写, 支付租金)
+    /// [4] 系统程序
+    CreateMasterEdition {
+        max_supply: Option<u64>,
+    },
+
+    /// 从母版印刷一份编号副本：向新的 NFT 铸币铸造 1 个代币并记录版本号
+    /// 账户列表:
+    /// [0] 母版铸币账户
+    /// [1] 母版版本 PDA (可写)
+    /// [2] 持有母版的代币账户
+    /// [3] 代币账户所有者 (签名者, 可写, 支付租金)
+    /// [4] 副本铸币账户 (可写, NFT 模式, 供应量为 0, 铸币权限为母版持有人)
+    /// [5] 副本版本 PDA (可写) seeds = ["edition", new_mint]
+    /// [6] 接收副本的代币账户 (可写)
+    /// [7] 系统程序
+    /// [8] 投票检查点 PDA (可写, 仅当接收账户已委托投票)
+    PrintEdition,
+
+    /// 设置 NFT 铸币的转账程序白名单：只有白名单内的顶层程序才能发起 Transfer
+    /// （需要允许钱包直接转账时，把本程序 ID 加入白名单）
+    /// 首次设置须由铸币权限发起，因此需在首次铸造（自动撤销铸币权限）之前完成
+    /// 账户列表:
+    /// [0] 铸币账户 (NFT 模式)
+    /// [1] 转账白名单 PDA (可写) seeds = ["transfer_allowlist", mint]
+    /// [2] 当前权限 (签名者) 首次为铸币权限，之后为白名单中的 authority
+    /// [3] 付款账户 (签名者, 可写)
+    /// [4] 系统程序
+    SetTransferAllowlist {
+        programs: Vec<Pubkey>,
+    },
+
+    /// 为代币账户设置转账速度限制：window_slots 个 slot 内累计转出超过 max_amount 时自动冻结
+    /// max_amount 为 0 时关闭限制
+    /// 超限的那次转出不执行而指令仍返回成功，Transfer / TransferSplit / SweepAll / TransferFrom /
+    /// 许可与会话转账 / Debit 都是如此，调用方须按余额变化确认到账
+    /// 账户列表:
+    /// [0] 代币账户 (可写)
+    /// [1] 铸币账户
+    /// [2] 速度跟踪 PDA (可写) seeds = ["velocity", token_account]
+    /// [3] 冻结权限 (签名者)
+    /// [4] 付款账户 (签名者, 可写)
+    /// [5] 系统程序
+    SetVelocityLimit {
+        max_amount: u64,
+        window_slots: u64,
+    },
+
+    /// 解冻代币账户（仅冻结权限），并清空速度跟踪窗口；reason_code 记入 AuthorityEvent::AccountThawed（规则见 FreezeAccount）
+    /// 账户列表:
+    /// [0] 代币账户 (可写)
+    /// [1] 铸币账户
+    /// [2] 冻结权限 (签名者)
+    /// [3] 速度跟踪 PDA (可写, 仅当账户开启了速度限制)
+    ThawAccount {
+        reason_code: u16,
+    },
+
+    /// 公开排队一笔铸造，MINT_TIMELOCK_SLOTS 个 slot 后才能执行
+    /// 每个 (铸币, 目标账户) 同时只能有一笔排队
+    /// 账户列表:
+    /// [0] 铸币账户
+    /// [1] 排队铸造 PDA (可写) seeds = ["queued_mint", mint, destination]
+    /// [2] 铸币权限 (签名者, 可写, 支付租金)
+    /// [3] 系统程序
+    QueueMint {
+        amount: u64,
+        destination: Pubkey,
+    },
+
+    /// 执行已到期的排队铸造（任何人可调用），并关闭排队 PDA（租金退回铸币权限）
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 排队铸造 PDA (可写)
+    /// [2] 目标代币账户 (可写)
+    /// [3] 排队时的铸币权限 (可写, 接收退回的租金)
+    /// [4] 投票检查点 PDA (可写, 仅当目标账户已委托投票)
+    ExecuteQueuedMint,
+
+    /// 取消排队铸造，并关闭排队 PDA
+    /// 账户列表:
+    /// [0] 铸币账户
+    /// [1] 排队铸造 PDA (可写)
+    /// [2] 排队时的铸币权限 (签名者, 可写)
+    CancelQueuedMint,
+
+    /// 创建铸币的国库代币账户（归国库 PDA 自身所有，任何人可向其转入代币）
+    /// 账户列表:
+    /// [0] 铸币账户
+    /// [1] 国库代币账户 PDA (可写) seeds = ["treasury", mint]
+    /// [2] 付款账户 (签名者, 可写)
+    /// [3] 系统程序
+    InitializeTreasury,
+
+    /// 销毁国库中的全部代币（任何人可调用），并通过 sol_log_data 输出 TreasuryBurnEvent
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 国库代币账户 PDA (可写)
+    BurnTreasury,
+
+    /// OTC 挂单：卖方把 give_amount 个代币托管到归挂单 PDA 所有的代币账户，
+    /// 按固定价格换取 want_amount 个 want_mint 代币
+    /// 托管账户需事先通过 InitializeAccount 创建（owner 为挂单 PDA，余额为 0）
+    /// 账户列表:
+    /// [0] 挂单 PDA (可写) seeds = ["order", escrow_token_account]
+    /// [1] 托管代币账户 (可写)
+    /// [2] 卖方代币账户 (可写)
+    /// [3] 卖方接收 want_mint 的代币账户
+    /// [4] 卖方 (签名者, 可写, 支付租金)
+    /// [5] 系统程序
+    /// [6] give_mint 铸币账户 (可写)
+    /// [7] 投票检查点 PDA (可写, 仅当卖方账户已委托投票)
+    PlaceOrder {
+        give_amount: u64,
+        want_mint: Pubkey,
+        want_amount: u64,
+    },
+
+    /// 吃单：买方支付 want_amount 给卖方，取得托管中的全部代币，并关闭挂单 PDA（租金退回卖方）
+    /// 账户列表:
+    /// [0] 挂单 PDA (可写)
+    /// [1] 托管代币账户 (可写)
+    /// [2] 卖方接收代币账户 (可写)
+    /// [3] 买方支付代币账户 (可写)
+    /// [4] 买方接收代币账户 (可写)
+    /// [5] 买方 (签名者)
+    /// [6] 卖方 (可写, 接收退回的租金)
+    /// [7] want_mint 铸币账户 (可写)
+    /// [8] give_mint 铸币账户 (可写)
+    /// [9..] 投票检查点 PDA (可写, 先为 want_mint 的支付方/接收方, 再为托管/买方接收账户, 仅当已委托投票)
+    FillOrder,
+
+    /// 撤单：托管代币退回卖方，并关闭挂单 PDA
+    /// 账户列表:
+    /// [0] 挂单 PDA (可写)
+    /// [1] 托管代币账户 (可写)
+    /// [2] 退款代币账户 (可写)
+    /// [3] 卖方 (签名者, 可写)
+    /// [4] give_mint 铸币账户 (可写)
+    /// [5] 投票检查点 PDA (可写, 仅当退款账户已委托投票)
+    CancelOrder,
+
+    /// 创建荷兰式拍卖（铸币权限移交给拍卖 PDA）
+    /// 每个完整代币的价格从 start_price 起每个 slot 下降 decay，最低为 floor_price（单位 lamports）
+    /// 最多通过拍卖铸造 supply 个基础单位
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 拍卖状态 PDA (可写) seeds = ["auction", mint]
+    /// [2] SOL 金库 PDA (可写) seeds = ["auction_vault", mint]
+    /// [3] 当前铸币权限 (签名者)
+    /// [4] 付款账户 (签名者, 可写)
+    /// [5] 系统程序
+    CreateAuction {
+        start_price: u64,
+        floor_price: u64,
+        decay: u64,
+        supply: u64,
+    },
+
+    /// 按当前拍卖价格用 SOL 购买（铸造）代币，货款存入金库
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 拍卖状态 PDA (可写)
+    /// [2] SOL 金库 PDA (可写)
+    /// [3] 买方代币账户 (可写)
+    /// [4] 买方 (签名者, 可写)
+    /// [5] 系统程序
+    /// [6] 投票检查点 PDA (可写, 仅当买方代币账户已委托投票)
+    Purchase {
+        amount: u64,
+        max_lamports: u64,
+    },
+
+    /// 初始化固定价格众筹（铸币权限移交给众筹 PDA），最多售出 cap 个基础单位
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 众筹状态 PDA (可写) seeds = ["sale", mint]
+    /// [2] SOL 金库 PDA (可写) seeds = ["sale_vault", mint]
+    /// [3] 当前铸币权限 (签名者, 成为众筹 authority)
+    /// [4] 付款账户 (签名者, 可写)
+    /// [5] 系统程序
+    InitializeSale {
+        price_lamports_per_token: u64,
+        cap: u64,
+    },
+
+    /// 按众筹价格用 SOL 购买（铸造）代币
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 众筹状态 PDA (可写)
+    /// [2] SOL 金库 PDA (可写)
+    /// [3] 买方代币账户 (可写)
+    /// [4] 买方 (签名者, 可写)
+    /// [5] 系统程序
+    /// [6] 投票检查点 PDA (可写, 仅当买方代币账户已委托投票)
+    BuyTokens {
+        amount: u64,
+    },
+
+    /// 众筹 authority 提取金库中的全部货款（保留租金豁免余额）
+    /// 账户列表:
+    /// [0] 铸币账户
+    /// [1] 众筹状态 PDA
+    /// [2] SOL 金库 PDA (可写)
+    /// [3] 众筹 authority (签名者, 可写, 接收 SOL)
+    WithdrawProceeds,
+
+    /// 初始化份额金库：存入资产铸币的代币，按比例铸造份额代币（份额铸币权限移交给金库 PDA）
+    /// 资产托管账户需事先通过 InitializeAccount 创建（owner 为金库 PDA，余额为 0）
+    /// 账户列表:
+    /// [0] 份额铸币账户 (可写, 供应量为 0)
+    /// [1] 份额金库 PDA (可写) seeds = ["share_vault", share_mint]
+    /// [2] 资产托管代币账户
+    /// [3] 份额铸币权限 (签名者)
+    /// [4] 付款账户 (签名者, 可写)
+    /// [5] 系统程序
+    InitializeShareVault,
+
+    /// 存入资产，按当前兑换率铸造份额：shares = amount * 份额供应量 / 托管资产（首次存入 1:1）
+    /// 账户列表:
+    /// [0] 份额金库 PDA
+    /// [1] 份额铸币账户 (可写)
+    /// [2] 资产托管代币账户 (可写)
+    /// [3] 存款人资产代币账户 (可写)
+    /// [4] 存款人份额代币账户 (可写)
+    /// [5] 存款人 (签名者)
+    /// [6] 资产铸币账户 (可写)
+    /// [7..] 投票检查点 PDA (可写, 先为资产转移, 再为份额铸造, 仅当已委托投票)
+    Deposit {
+        amount: u64,
+    },
+
+    /// 销毁份额，按当前兑换率取回资产：amount = shares * 托管资产 / 份额供应量
+    /// 账户列表:
+    /// [0] 份额金库 PDA
+    /// [1] 份额铸币账户 (可写)
+    /// [2] 资产托管代币账户 (可写)
+    /// [3] 持有人份额代币账户 (可写)
+    /// [4] 持有人资产代币账户 (可写)
+    /// [5] 持有人 (签名者)
+    /// [6] 资产铸币账户 (可写)
+    /// [7..] 投票检查点 PDA (可写, 先为份额销毁, 再为资产转移, 仅当已委托投票)
+    Withdraw {
+        shares: u64,
+    },
+
+    /// 把代币锁入治理领域（realm）以获得投票权重，首次存入时创建记录与托管账户
+    /// SPL Governance 只能托管经典 SPL Token 账户，本程序的代币改用内置锁仓，
+    /// PDA seeds 与 SPL Governance 的 TokenOwnerRecord / 托管账户保持一致
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 代币持有人记录 PDA (可写) seeds = ["governance", realm, mint, owner]
+    /// [2] 治理托管代币账户 PDA (可写) seeds = ["governance", realm, mint]
+    /// [3] 持有人代币账户 (可写)
+    /// [4] 持有人 (签名者, 可写, 支付租金)
+    /// [5] 系统程序
+    /// [6] 投票检查点 PDA (可写, 仅当持有人代币账户已委托投票)
+    DepositGoverningTokens {
+        realm: Pubkey,
+        amount: u64,
+    },
+
+    /// 从治理领域取回锁仓的代币
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 代币持有人记录 PDA (可写)
+    /// [2] 治理托管代币账户 PDA (可写)
+    /// [3] 接收代币账户 (可写)
+    /// [4] 持有人 (签名者)
+    /// [5] 投票检查点 PDA (可写, 仅当接收账户已委托投票)
+    WithdrawGoverningTokens {
+        realm: Pubkey,
+        amount: u64,
+    },
+
+    /// 设置紧急管理员（部署后由程序升级权限调用一次，之后不可更改）
+    /// 账户列表:
+    /// [0] 紧急配置 PDA (可写) seeds = ["emergency_config"]
+    /// [1] 本程序的 ProgramData 账户
+    /// [2] 程序升级权限 (签名者, 可写, 支付租金)
+    /// [3] 系统程序
+    InitializeEmergencyConfig {
+        emergency_admin: Pubkey,
+    },
+
+    /// 紧急管理员公开提议替换丢失的铸币 / 冻结权限，EMERGENCY_RECOVERY_TIMELOCK_SLOTS 个 slot 后才能执行
+    /// 已撤销（None）的权限不能恢复；每个 (铸币, 权限类型) 同时只能有一笔提议
+    /// 账户列表:
+    /// [0] 紧急配置 PDA
+    /// [1] 铸币账户
+    /// [2] 权限恢复 PDA (可写) seeds = ["authority_recovery", mint, authority_type]
+    /// [3] 紧急管理员 (签名者, 可写, 支付租金)
+    /// [4] 系统程序
+    ProposeAuthorityRecovery {
+        authority_type: AuthorityType,
+        new_authority: Pubkey,
+    },
+
+    /// 执行已到期的权限恢复（任何人可调用），并关闭恢复 PDA（租金退回紧急管理员）
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 权限恢复 PDA (可写)
+    /// [2] 提议时的紧急管理员 (可写)
+    ExecuteAuthorityRecovery,
+
+    /// 取消权限恢复：紧急管理员或当前权限持有人（说明密钥并未丢失）均可取消
+    /// 账户列表:
+    /// [0] 铸币账户
+    /// [1] 权限恢复 PDA (可写)
+    /// [2] 紧急管理员或当前权限 (签名者)
+    /// [3] 提议时的紧急管理员 (可写, 接收退回的租金)
+    CancelAuthorityRecovery,
+
+    /// 开启空铸币自动关闭：在铸币的扩展区写入 MintCloseConfig（重复调用覆盖回收地址）
+    /// 铸币账户需按 MINT_WITH_CLOSE_CONFIG_LEN 分配空间
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 铸币权限 (签名者)
+    EnableAutoClose {
+        close_destination: Pubkey,
+    },
+
+    /// 关闭供应量为 0 且铸币权限已撤销的铸币（任何人可调用），租金退回预设的回收地址
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 回收地址 (可写, 必须与 MintCloseConfig 一致)
+    CloseEmptyMint,
+
+    /// 按基点份额把 total 拆分转给多个目标（份额之和必须为 10_000，取整余数归第一个目标）
+    /// 账户列表:
+    /// [0] 源账户 (可写)
+    /// [1] 所有者 (签名者)
+    /// [2..2+N] 目标账户 (可写, 与 shares 一一对应, 不能是源账户)
+    /// [..] 源账户所属的铸币账户 (可写)
+    /// [..] 速度限制跟踪 PDA (可写, 仅当源账户开启速度限制)
+    /// 其后为各目标依次需要的投票检查点 PDA，NFT 源账户最后附加转账白名单账户（同 Transfer）
+    TransferSplit {
+        total: u64,
+        shares: Vec<u16>,
+    },
+
+    /// 创建哈希时间锁（HTLC）：发送方把 amount 个代币托管到归 HTLC PDA 所有的代币账户，
+    /// 在 timeout（unix 时间戳，秒）之前出示 sha256(preimage) == hash 的原像即可转给接收方，过期后可退回发送方
+    /// 托管账户需事先通过 InitializeAccount 创建（owner 为 HTLC PDA，余额为 0）
+    /// 账户列表:
+    /// [0] HTLC PDA (可写) seeds = ["htlc", escrow_token_account]
+    /// [1] 托管代币账户 (可写)
+    /// [2] 发送方代币账户 (可写, 过期后退款也退回这里)
+    /// [3] 接收方代币账户
+    /// [4] 发送方 (签名者, 可写, 支付租金)
+    /// [5] 系统程序
+    /// [6] 铸币账户 (可写)
+    /// [7] 投票检查点 PDA (可写, 仅当发送方账户已委托投票)
+    CreateHtlc {
+        hash: [u8; 32],
+        amount: u64,
+        timeout: i64,
+    },
+
+    /// 出示原像赎回 HTLC（任何人可调用，必须在 timeout 之前），代币转入接收方，并关闭 HTLC PDA（租金退回发送方）
+    /// 账户列表:
+    /// [0] HTLC PDA (可写)
+    /// [1] 托管代币账户 (可写)
+    /// [2] 接收方代币账户 (可写)
+    /// [3] 发送方 (可写, 接收退回的租金)
+    /// [4] 铸币账户 (可写)
+    /// [5] 投票检查点 PDA (可写, 仅当接收方账户已委托投票)
+    RedeemHtlc {
+        preimage: Vec<u8>,
+    },
+
+    /// 过期退款（任何人可调用，timeout 之后），代币退回发送方代币账户，并关闭 HTLC PDA
+    /// 账户列表:
+    /// [0] HTLC PDA (可写)
+    /// [1] 托管代币账户 (可写)
+    /// [2] 发送方代币账户 (可写)
+    /// [3] 发送方 (可写, 接收退回的租金)
+    /// [4] 铸币账户 (可写)
+    /// [5] 投票检查点 PDA (可写, 仅当发送方账户已委托投票)
+    RefundHtlc,
+
+    /// 双方原子互换：A 方转 amount_a 个 X 给 B 方，B 方转 amount_b 个 Y 给 A 方，双方在同一交易中签名，无需托管
+    /// 账户列表:
+    /// [0] A 方 X 代币账户 (可写)
+    /// [1] B 方接收 X 的代币账户 (可写)
+    /// [2] B 方 Y 代币账户 (可写)
+    /// [3] A 方接收 Y 的代币账户 (可写)
+    /// [4] A 方 (签名者)
+    /// [5] B 方 (签名者)
+    /// 其后依次为：X、Y 铸币账户、A、B 方速度限制跟踪 PDA（仅当开启）、X 再 Y 的投票检查点 PDA（仅当已委托投票）、
+    /// X 再 Y 的转账白名单账户（仅当为 NFT）
+    AtomicSwap {
+        amount_a: u64,
+        amount_b: u64,
+    },
+
+    /// 按所有者的链下签名设置 delegate 的转账额度（中继者提交并支付费用，所有者无需上链签名）
+    /// 交易中紧邻本指令之前必须是验证所有者对 PermitMessage::Approve 签名的 ed25519 程序指令；
+    /// nonce 必须等于额度 PDA 当前的 nonce（新 PDA 为 0），成功后加一，deadline 为 unix 时间戳
+    /// 账户列表:
+    /// [0] 代币账户
+    /// [1] 额度 PDA (可写) seeds = ["allowance", token_account, delegate]
+    /// [2] 付款账户 (签名者, 可写, 首次创建额度 PDA 时支付租金)
+    /// [3] 指令 sysvar
+    /// [4] 系统程序
+    PermitApprove {
+        delegate: Pubkey,
+        amount: u64,
+        nonce: u64,
+        deadline: i64,
+    },
+
+    /// 受托人在额度内从代币账户转出；转出后源账户余额为 0 时剩余额度一并清零（SweepAll 与受托人 Burn 同样处理）
+    /// 所有者自己清空账户的指令不携带额度 PDA，不会清零额度
+    /// 账户列表:
+    /// [0] 源账户 (可写)
+    /// [1] 目标账户 (可写)
+    /// [2] 额度 PDA (可写)
+    /// [3] 受托人 (签名者)
+    /// 其后的铸币账户（持有人计数）、速度限制跟踪 PDA、投票检查点、NFT 转账白名单账户同 Transfer
+    TransferFrom {
+        amount: u64,
+    },
+
+    /// 中继者代付的转账：所有者只在链下对 PermitMessage::Transfer 签名，中继者提交交易并支付手续费，
+    /// 从转出的 amount 中取 tip 付给中继者的代币账户，其余转入目标账户
+    /// 交易中紧邻本指令之前必须是验证该签名的 ed25519 程序指令；nonce 必须等于 permit nonce PDA 当前值（新 PDA 为 0）
+    /// 账户列表:
+    /// [0] 源账户 (可写)
+    /// [1] 目标账户 (可写)
+    /// [2] 中继者接收小费的代币账户 (可写)
+    /// [3] permit nonce PDA (可写) seeds = ["permit_nonce", source]
+    /// [4] 中继者 (签名者, 可写, 首次创建 nonce PDA 时支付租金)
+    /// [5] 指令 sysvar
+    /// [6] 系统程序
+    /// 其后的铸币账户（持有人计数）、速度限制跟踪 PDA、投票检查点（先目标后小费账户）、NFT 转账白名单账户同 Transfer
+    TransferWithPermit {
+        amount: u64,
+        tip: u64,
+        nonce: u64,
+        deadline: i64,
+    },
+
+    /// 为临时会话密钥授权：在 expires_at_slot（含）之前，key 可通过 SessionTransfer 累计转出最多 max_amount
+    /// 对同一 key 再次调用会重置额度与过期时间
+    /// 账户列表:
+    /// [0] 代币账户
+    /// [1] 会话 PDA (可写) seeds = ["session", token_account, key]
+    /// [2] 所有者 (签名者, 可写, 支付租金)
+    /// [3] 系统程序
+    CreateSession {
+        key: Pubkey,
+        max_amount: u64,
+        expires_at_slot: u64,
+    },
+
+    /// 撤销会话，关闭会话 PDA（租金退回所有者）
+    /// 账户列表:
+    /// [0] 代币账户
+    /// [1] 会话 PDA (可写)
+    /// [2] 所有者 (签名者, 可写)
+    RevokeSession,
+
+    /// 会话密钥代所有者转账（检查过期时间与剩余额度）
+    /// 账户列表:
+    /// [0] 源账户 (可写)
+    /// [1] 目标账户 (可写)
+    /// [2] 会话 PDA (可写)
+    /// [3] 会话密钥 (签名者)
+    /// 其后的铸币账户（持有人计数）、速度限制跟踪 PDA、投票检查点、NFT 转账白名单账户同 Transfer
+    SessionTransfer {
+        amount: u64,
+    },
+
+    /// 设置社交恢复监护人：在代币账户的扩展区写入 GuardianConfig（guardians 为空且 threshold 为 0 表示关闭）
+    /// 代币账户需按 TOKEN_ACCOUNT_WITH_GUARDIANS_LEN 分配空间
+    /// 账户列表:
+    /// [0] 代币账户 (可写)
+    /// [1] 所有者 (签名者)
+    SetGuardians {
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+    },
+
+    /// 至少 threshold 个监护人共同签名，提议把代币账户所有者更换为 new_owner，
+    /// OWNER_RECOVERY_TIMELOCK_SLOTS 个 slot 后才能执行；期间原所有者可以取消
+    /// 账户列表:
+    /// [0] 代币账户
+    /// [1] 所有者恢复 PDA (可写) seeds = ["owner_recovery", token_account]
+    /// [2] 付款账户 (签名者, 可写, 支付租金)
+    /// [3] 系统程序
+    /// [4..] 监护人 (签名者)
+    ProposeOwnerRecovery {
+        new_owner: Pubkey,
+    },
+
+    /// 原所有者取消恢复提议（说明密钥并未丢失），关闭恢复 PDA（租金退回付款账户）
+    /// 账户列表:
+    /// [0] 代币账户
+    /// [1] 所有者恢复 PDA (可写)
+    /// [2] 所有者 (签名者)
+    /// [3] 提议时的付款账户 (可写)
+    CancelOwnerRecovery,
+
+    /// 时间锁到期后更换代币账户所有者（任何人可调用），并关闭恢复 PDA
+    /// 原所有者签出的额度 / 会话仍按代币账户记录，新所有者需要自行撤销
+    /// 账户列表:
+    /// [0] 代币账户 (可写)
+    /// [1] 所有者恢复 PDA (可写)
+    /// [2] 提议时的付款账户 (可写)
+    RecoverOwner,
+
+    /// 开启持有人计数：在铸币的扩展区写入 HolderCount（只能在供应量为 0 时开启，此时持有人数为 0）
+    /// 此后收款的账户带上 HOLDER_TRACKED 标志，余额变化时由附带的铸币账户同步计数
+    /// 铸币账户需按 MINT_WITH_HOLDER_COUNT_LEN 分配空间
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 铸币权限 (签名者)
+    EnableHolderCount,
+
+    /// 开启活动时间记录：在代币账户的扩展区写入 AccountActivity，此后每次余额变化都会更新 last_activity_slot
+    /// 在 InitializeAccount 的同一交易中开启时，created_at 即账户的创建 slot
+    /// 代币账户需按 TOKEN_ACCOUNT_WITH_ACTIVITY_LEN 分配空间（与其他扩展同时使用时空间相加）
+    /// 账户列表:
+    /// [0] 代币账户 (可写)
+    /// [1] 所有者 (签名者)
+    EnableActivityTracking,
+
+    /// 开启转账备注记录：在代币账户的扩展区写入空的 MemoLog，此后 TransferWithMemo 会把
+    /// 备注哈希与 slot 写入环形缓冲区（保留最近 MEMO_LOG_CAPACITY 条）
+    /// 代币账户需按 TOKEN_ACCOUNT_WITH_MEMO_LOG_LEN 分配空间（与其他扩展同时使用时空间相加）
+    /// 账户列表:
+    /// [0] 代币账户 (可写)
+    /// [1] 所有者 (签名者)
+    EnableMemoLog,
+
+    /// 带备注的转账：账户与 Transfer 相同，转账执行后把 sha256(memo) 记入源 / 目标账户的 MemoLog（仅当已开启）
+    TransferWithMemo {
+        amount: u64,
+        memo: Vec<u8>,
+    },
+
+    /// 归集：把 source_count 个源账户的全部余额转入同一目标（交易所热钱包批量归集充值地址）
+    /// authority 为源账户的所有者，或持有该源账户额度 PDA 的受托人（此时最多转出剩余额度）
+    /// 账户列表:
+    /// [0] 目标账户 (可写)
+    /// [1] authority (签名者)
+    /// [2..2+N] 源账户 (可写, 互不相同, 与目标同一铸币)
+    /// 其后依次为：authority 不是所有者的源账户的额度 PDA (可写, 按源账户顺序)、
+    /// 铸币账户 (可写)，
+    /// 再按源账户顺序提供各自的速度限制跟踪 PDA（仅当开启）与投票检查点 PDA（同 Transfer），
+    /// NFT 铸币最后附加转账白名单账户；可转金额为 0 的源账户直接跳过，不读取其速度限制与投票账户
+    SweepAll {
+        source_count: u8,
+    },
+
+    /// 批量结算：按 deltas 对各账户原子地入账 / 扣款，每个铸币的增量之和必须为 0
+    /// 被扣款账户的所有者必须签名；已委托投票、开启速度限制、NFT、带 HOLDER_TRACKED 标志或属于积分铸币的账户
+    /// 不参与结算（增量必须为 0）
+    /// 不更新持有人计数；入账账户须已激活；余额先换算到铸币当前的精度纪元，deltas 按当前纪元计
+    /// 账户列表:
+    /// [0..N] 代币账户 (可写, 互不相同, 与 deltas 一一对应)
+    /// [N..] 被扣款账户的所有者 (签名者) 与增量不为 0 的账户所属的铸币账户，顺序不限，每个账户提供一次
+    SettleBatch {
+        deltas: Vec<i64>,
+    },
+
+    /// 初始化压缩余额树（任何人可调用）
+    /// 树账户需事先由客户端创建（所有者为本程序，空间为 CompressedTree::account_len(depth)），
+    /// 托管账户需事先通过 InitializeAccount 创建（owner 为树的 authority PDA，余额为 0）
+    /// 账户列表:
+    /// [0] 树账户 (可写)
+    /// [1] 铸币账户
+    /// [2] 托管代币账户 seeds(owner) = ["compressed_tree", tree]
+    InitializeCompressedTree {
+        depth: u8,
+    },
+
+    /// 压缩：把代币账户中的 amount 个代币转入树的托管账户，并为 recipient 追加一个压缩余额叶子
+    /// 账户列表:
+    /// [0] 源代币账户 (可写)
+    /// [1] 所有者 (签名者)
+    /// [2] 树账户 (可写)
+    /// [3] 托管代币账户 (可写)
+    /// 其后的铸币账户（持有人计数）、速度限制跟踪 PDA、投票检查点同 Transfer；NFT 账户不能压缩
+    Compress {
+        recipient: Pubkey,
+        amount: u64,
+    },
+
+    /// 压缩余额之间转账：花费 leaf（需证明其在 root 下，root 为最近 ROOT_HISTORY 个根之一），
+    /// 为 recipient 追加 amount 的叶子，余额有剩余时再为原所有者追加找零叶子
+    /// 账户列表:
+    /// [0] 树账户 (可写)
+    /// [1] 叶子所有者 (签名者)
+    CompressedTransfer {
+        leaf: CompressedLeaf,
+        root: [u8; 32],
+        proof: Vec<[u8; 32]>,
+        recipient: Pubkey,
+        amount: u64,
+    },
+
+    /// 解压：花费 leaf，并把其全部余额从托管账户转入目标代币账户
+    /// 账户列表:
+    /// [0] 树账户 (可写)
+    /// [1] 托管代币账户 (可写)
+    /// [2] 目标代币账户 (可写)
+    /// [3] 叶子所有者 (签名者)
+    /// 其后的铸币账户、投票检查点同 Transfer
+    Decompress {
+        leaf: CompressedLeaf,
+        root: [u8; 32],
+        proof: Vec<[u8; 32]>,
+    },
+
+    /// 设置跨链桥验证者（铸币权限签名，首次调用创建桥配置 PDA，之后覆盖验证者）
+    /// 验证者签名的 BridgeMint 可以铸造该铸币，相当于授予其铸币权
+    /// 账户列表:
+    /// [0] 铸币账户
+    /// [1] 桥配置 PDA (可写) seeds = ["bridge", mint]
+    /// [2] 铸币权限 (签名者, 可写, 支付租金)
+    /// [3] 系统程序
+    ConfigureBridge {
+        verifier: Pubkey,
+    },
+
+    /// 销毁代币出桥：按 8 位小数截断 amount 后销毁，并输出 Wormhole Token Bridge 转账载荷（BridgeEvent::Burned）
+    /// target_address 为目标链上的接收地址（左侧补零到 32 字节）
+    /// 账户列表:
+    /// [0] 代币账户 (可写)
+    /// [1] 铸币账户 (可写)
+    /// [2] 账户所有者 (签名者)
+    /// [3] 桥配置 PDA (可写)
+    /// [4] 投票检查点 PDA (可写, 仅当代币账户已委托投票)
+    BridgeBurn {
+        target_chain: u16,
+        target_address: [u8; 32],
+        amount: u64,
+    },
+
+    /// 入桥铸造：验证者确认来源链消息后签名铸造，每条消息 (source_chain, emitter, sequence) 只能铸造一次
+    /// 账户列表:
+    /// [0] 桥配置 PDA
+    /// [1] 铸币账户 (可写)
+    /// [2] 目标代币账户 (可写)
+    /// [3] 消息领取 PDA (可写) seeds = ["bridge_claim", mint, source_chain, emitter, sequence]
+    /// [4] 验证者 (签名者, 可写, 支付租金)
+    /// [5] 系统程序
+    /// [6] 投票检查点 PDA (可写, 仅当目标账户已委托投票)
+    BridgeMint {
+        source_chain: u16,
+        emitter: [u8; 32],
+        sequence: u64,
+        amount: u64,
+    },
+
+    /// 调整铸币精度（只能调大，最多 MAX_RESCALE_EPOCHS 次）：供应量立即乘以 10^(new_decimals - decimals)，
+    /// 铸币扩展区的 DecimalsRescale 进入新的纪元；代币账户余额不在此处改写，
+    /// 而是在下一次与铸币一同处理（MintTo、Burn，Burn { amount: 0 } 可单独用于同步）时惰性换算，
+    /// 换算后的纪元记入账户的 RescaleEpoch 扩展（代币账户需按 TOKEN_ACCOUNT_WITH_RESCALE_EPOCH_LEN 分配空间）。
+    /// 纪元不同的两个账户之间不能转账；调整后新建的代币账户直接处于当前纪元
+    /// 铸币账户需按 MINT_WITH_DECIMALS_RESCALE_LEN 分配空间（与其他扩展同时使用时空间相加）
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 铸币权限 (签名者)
+    RescaleMint {
+        new_decimals: u8,
+    },
+
+    /// 全局冻结：在铸币扩展区写入 GlobalFreezeState { frozen: true }，冻结期间该铸币的转账与销毁全部失败（SetFreezeExempt 豁免的账户除外）
+    /// 所有转出余额的指令都要求附带源账户所属的铸币账户，扩展写入之前已有的余额同样受约束
+    /// 铸币账户需按 MINT_WITH_GLOBAL_FREEZE_LEN 分配空间（与其他扩展同时使用时空间相加）
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 冻结权限 (签名者)
+    GlobalFreeze,
+
+    /// 解除全局冻结（扩展不存在时写入未冻结状态），账户列表同 GlobalFreeze
+    GlobalThaw,
+
+    /// 冻结代币账户（仅冻结权限）；thaw_at_slot 不为空时到达该 slot 后自动按未冻结处理，无需 ThawAccount
+    /// 定时解冻记在代币账户的 ScheduledThaw 扩展中（账户需按 TOKEN_ACCOUNT_WITH_SCHEDULED_THAW_LEN 分配空间），
+    /// 再次冻结（包括速度限制触发的冻结）或 ThawAccount 会覆盖 / 清除之前的定时
+    /// reason_code 为发行方定义的合规原因码，记入 AuthorityEvent::AccountFrozen 供下游报表使用；
+    /// 0（未说明）与 FREEZE_REASON_VELOCITY_LIMIT（程序触发的冻结）不能使用
+    /// 账户列表:
+    /// [0] 代币账户 (可写)
+    /// [1] 铸币账户
+    /// [2] 冻结权限 (签名者)
+    FreezeAccount {
+        thaw_at_slot: Option<u64>,
+        reason_code: u16,
+    },
+
+    /// 通用权限变更：由 authority_type 对应的当前权限签名，new_authority 为 None 时撤销
+    /// MintTokens 取代已弃用的 SetMintAuthority；BurnTokens 为可选的铸币级销毁权限（可从该铸币的任意账户销毁），
+    /// 存放在铸币的 BurnAuthority 扩展中，从未设置时由铸币权限签名设置，撤销后不能再设置，
+    /// 铸币账户需按 MINT_WITH_BURN_AUTHORITY_LEN 分配空间（与其他扩展同时使用时空间相加）
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 当前权限 (签名者)
+    SetAuthority {
+        authority_type: AuthorityType,
+        new_authority: Option<Pubkey>,
+    },
+
+    /// 设置代币账户是否豁免全局冻结（仅冻结权限），供手续费金库、国库等运营账户在应急期间继续使用
+    /// 豁免记在代币账户的 FreezeExempt 扩展中，账户需按 TOKEN_ACCOUNT_WITH_FREEZE_EXEMPT_LEN 分配空间；
+    /// 只影响 GlobalFreeze，FreezeAccount / 速度限制对单个账户的冻结照常生效
+    /// 账户列表:
+    /// [0] 代币账户 (可写)
+    /// [1] 铸币账户
+    /// [2] 冻结权限 (签名者)
+    SetFreezeExempt {
+        exempt: bool,
+    },
+
+    /// 创建带仲裁人的条件转账：发送方把 amount 个代币托管到归条件转账 PDA 所有的代币账户，
+    /// 由 arbiter 签名 ReleaseConditionalTransfer 转给接收方，或签名 RefundConditionalTransfer 退回发送方，没有超时
+    /// 托管账户需事先通过 InitializeAccount 创建（owner 为条件转账 PDA，余额为 0）
+    /// 账户列表:
+    /// [0] 条件转账 PDA (可写) seeds = ["conditional", escrow_token_account]
+    /// [1] 托管代币账户 (可写)
+    /// [2] 发送方代币账户 (可写, 退款也退回这里)
+    /// [3] 接收方代币账户
+    /// [4] 发送方 (签名者, 可写, 支付租金)
+    /// [5] 系统程序
+    /// [6] 铸币账户 (可写)
+    /// [7] 投票检查点 PDA (可写, 仅当发送方账户已委托投票)
+    CreateConditionalTransfer {
+        arbiter: Pubkey,
+        amount: u64,
+    },
+
+    /// 仲裁人放款：代币转入接收方，并关闭条件转账 PDA（租金退回发送方）
+    /// 账户列表:
+    /// [0] 条件转账 PDA (可写)
+    /// [1] 托管代币账户 (可写)
+    /// [2] 接收方代币账户 (可写)
+    /// [3] 发送方 (可写, 接收退回的租金)
+    /// [4] 仲裁人 (签名者)
+    /// [5] 铸币账户 (可写)
+    /// [6] 投票检查点 PDA (可写, 仅当接收方账户已委托投票)
+    ReleaseConditionalTransfer,
+
+    /// 仲裁人退款：代币退回发送方代币账户，并关闭条件转账 PDA
+    /// 账户列表:
+    /// [0] 条件转账 PDA (可写)
+    /// [1] 托管代币账户 (可写)
+    /// [2] 发送方代币账户 (可写)
+    /// [3] 发送方 (可写, 接收退回的租金)
+    /// [4] 仲裁人 (签名者)
+    /// [5] 铸币账户 (可写)
+    /// [6] 投票检查点 PDA (可写, 仅当发送方账户已委托投票)
+    RefundConditionalTransfer,
+
+    /// 批量设置受托人额度：approvals 中的每一项 (delegate, amount) 对应一组 (代币账户, 额度 PDA)，
+    /// 代币账户必须都归签名者所有，额度直接覆盖为 amount（不改变 permit nonce），PDA 不存在时由签名者支付租金创建
+    /// 账户列表:
+    /// [0] 所有者 (签名者, 可写)
+    /// [1] 系统程序
+    /// [2..2+2N] 依次为代币账户、额度 PDA (可写) seeds = ["allowance", token_account, delegate]，与 approvals 一一对应
+    ApproveMulti {
+        approvals: Vec<(Pubkey, u64)>,
+    },
+
+    /// 设置代币账户标签（仅所有者）：UTF-8 文本，不足 ACCOUNT_LABEL_LEN 字节时尾部补 0，全 0 表示清除
+    /// 标签记在代币账户的 AccountLabel 扩展中，账户需按 TOKEN_ACCOUNT_WITH_LABEL_LEN 分配空间（与其他扩展同时使用时空间相加）
+    /// 账户列表:
+    /// [0] 代币账户 (可写)
+    /// [1] 所有者 (签名者)
+    SetAccountLabel {
+        label: [u8; ACCOUNT_LABEL_LEN],
+    },
+
+    /// 在 (authority, seed) 推导的 PDA 上创建并初始化铸币，同一权限与种子在任何环境下得到相同的铸币地址
+    /// authority 即铸币权限，seed 不超过 MAX_SEED_LEN 字节；铸币账户只分配 Mint::LEN，不预留扩展空间
+    /// 账户列表:
+    /// [0] 铸币 PDA (可写) seeds = ["mint", authority, seed]
+    /// [1] 铸币权限 (签名者, 可写, 支付租金)
+    /// [2] 系统程序
+    CreateMintWithSeed {
+        seed: String,
+        decimals: u8,
+        freeze_authority: Option<Pubkey>,
+    },
+
+    /// 查询铸币或代币账户已初始化的扩展（只读）：以 u64 LE 位图写入 return data，第 n 位对应类型为 n 的扩展，
+    /// 客户端无需自行解析 TLV 区即可判断账户开启了哪些功能；is_mint 指明账户类型（两者扩展区的起始位置不同）
+    /// 账户列表:
+    /// [0] 铸币或代币账户
+    GetExtensionTypes {
+        is_mint: bool,
+    },
+
+    /// 初始化代币账户，并校验所有者地址的类型：OnCurve 要求所有者为普通钱包（误填 PDA 时返回 OwnerOffCurve），
+    /// OffCurve 要求所有者为 PDA（如金库、托管，误填钱包时返回 OwnerOnCurve）；其余行为与 InitializeAccount 相同
+    /// 账户列表:
+    /// [0] 代币账户 (可写)
+    /// [1] 铸币账户
+    /// [2] 账户所有者
+    /// [3] 租金系统账户
+    InitializeAccountWithOwnerCheck {
+        owner_curve: OwnerCurve,
+    },
+
+    /// 清理到期状态（任何人可调用）：按 items 顺序逐项处理，任一项尚未到期时整笔失败；
+    /// 每关闭一个 PDA，从其租金中划出 CRANK_REWARD_LAMPORTS 给调用者，其余租金照常退回原付款人
+    /// 账户列表:
+    /// [0] 调用者 (签名者, 可写, 接收奖励)
+    /// 其后依次为每一项的账户，见 CrankItem 各变体
+    Crank {
+        items: Vec<CrankItem>,
+    },
+
+    /// 预授权扣款（拉取式支付）：所有者授权 merchant 每 cycle_slots 个 slot 最多从代币账户扣走 amount_per_cycle，
+    /// 周期内未用完的额度不累积到下一周期；对同一 merchant 再次调用会覆盖额度并从当前 slot 开始新周期
+    /// 账户列表:
+    /// [0] 付款方代币账户
+    /// [1] 预授权 PDA (可写) seeds = ["preauth", token_account, merchant]
+    /// [2] 所有者 (签名者, 可写, 支付租金)
+    /// [3] 系统程序
+    CreatePreAuthorization {
+        merchant: Pubkey,
+        amount_per_cycle: u64,
+        cycle_slots: u64,
+    },
+
+    /// 商户按预授权从付款方扣款（检查当前周期的剩余额度）
+    /// 账户列表:
+    /// [0] 付款方代币账户 (可写)
+    /// [1] 目标代币账户 (可写)
+    /// [2] 预授权 PDA (可写)
+    /// [3] 商户 (签名者)
+    /// 其后的铸币账户（持有人计数）、速度限制跟踪 PDA、投票检查点、NFT 转账白名单账户同 Transfer
+    Debit {
+        amount: u64,
+    },
+
+    /// 所有者撤销预授权，关闭预授权 PDA（租金退回所有者）
+    /// 账户列表:
+    /// [0] 付款方代币账户
+    /// [1] 预授权 PDA (可写)
+    /// [2] 所有者 (签名者, 可写)
+    ClosePreAuthorization,
+
+    /// 为铸币创建储蓄金库（每个铸币一个）：存款按份额记账，份额价格 = 托管余额 / 总份额，
+    /// yield_authority 通过 FundYield 注入收益抬高份额价格；需铸币权限签名
+    /// 账户列表:
+    /// [0] 铸币账户
+    /// [1] 储蓄金库 PDA (可写) seeds = ["savings", mint]
+    /// [2] 托管代币账户 (所有者为储蓄金库 PDA，余额为 0)
+    /// [3] 铸币权限 (签名者)
+    /// [4] 付款人 (签名者, 可写)
+    /// [5] 系统程序
+    InitializeSavingsVault {
+        yield_authority: Pubkey,
+    },
+
+    /// 收益权限向储蓄金库注入收益：资产进入托管账户但不增发份额，所有存款人按份额比例分享
+    /// 账户列表:
+    /// [0] 储蓄金库 PDA
+    /// [1] 托管代币账户 (可写)
+    /// [2] 收益来源代币账户 (可写)
+    /// [3] 收益权限 (签名者，须为来源账户所有者)
+    /// [4] 储蓄铸币账户 (可写)
+    /// 其后的投票检查点账户同 Deposit
+    FundYield {
+        amount: u64,
+    },
+
+    /// 存入储蓄：按当前份额价格记入存款人的储蓄仓位，仓位不存在时创建
+    /// 账户列表:
+    /// [0] 储蓄金库 PDA (可写)
+    /// [1] 托管代币账户 (可写)
+    /// [2] 来源代币账户 (可写)
+    /// [3] 储蓄仓位 PDA (可写) seeds = ["savings_position", savings_vault, owner]
+    /// [4] 所有者 (签名者, 可写, 支付租金)
+    /// [5] 系统程序
+    /// [6] 储蓄铸币账户 (可写)
+    /// 其后的投票检查点账户同 Deposit
+    DepositSavings {
+        amount: u64,
+    },
+
+    /// 赎回储蓄份额：按当前份额价格取回资产（含已注入的收益），份额清零时关闭仓位（租金退回所有者）
+    /// 账户列表:
+    /// [0] 储蓄金库 PDA (可写)
+    /// [1] 托管代币账户 (可写)
+    /// [2] 储蓄仓位 PDA (可写)
+    /// [3] 目标代币账户 (可写, 须归仓位所有者)
+    /// [4] 所有者 (签名者, 可写)
+    /// [5] 储蓄铸币账户 (可写)
+    /// 其后的投票检查点账户同 Withdraw
+    WithdrawSavings {
+        shares: u64,
+    },
+
+    /// 为铸币设置排放计划：[start_slot, end_slot) 内每个 slot 排放 tokens_per_slot，按 weight 分给登记的接收账户；
+    /// 铸币权限移交给排放配置 PDA，之后任何人都可以调用 DistributeEmissions，排放结束时权限交还原铸币权限
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 排放配置 PDA (可写) seeds = ["emissions", mint]
+    /// [2] 铸币权限 (签名者)
+    /// [3] 付款人 (签名者, 可写)
+    /// [4] 系统程序
+    InitializeEmissions {
+        tokens_per_slot: u64,
+        start_slot: u64,
+        end_slot: u64,
+        recipients: Vec<EmissionRecipient>,
+    },
+
+    /// 铸造上次分发以来累计的排放（任何人可调用），按权重分给各接收账户，余数计入最后一个
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 排放配置 PDA (可写)
+    /// [2..2+N] 各接收代币账户 (可写)，顺序与登记时一致
+    /// 其后的投票检查点账户同 MintTo，按接收账户顺序排列
+    DistributeEmissions,
+
+    /// 开启积分模式：在铸币的扩展区写入 PointsConfig（只能在供应量为 0 时开启），
+    /// 此后 MintTo / DistributeEmissions 发放的积分在 lifetime_slots 个 slot 后过期，由 ExpirePoints 销毁；
+    /// 新建的代币账户带上 PointsLots 扩展（需按 TOKEN_ACCOUNT_WITH_POINTS_LOTS_LEN 分配空间）。
+    /// 转账时批次随积分一起转移并保持原到期时间，收款账户必须带有 PointsLots 扩展
+    /// 铸币账户需按 MINT_WITH_POINTS_CONFIG_LEN 分配空间
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 铸币权限 (签名者)
+    EnablePointsExpiry {
+        lifetime_slots: u64,
+    },
+
+    /// 销毁过期积分（任何人可调用）：余额中超出未过期批次之和的部分视为过期（先到期的批次先花费）
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// 其后依次为各代币账户 (可写)，设置了投票受托人的账户紧跟其投票检查点 PDA
+    ExpirePoints,
+
+    /// 设置休眠账户政策：在铸币的扩展区写入 DormancyPolicy，签名者成为政策权限。
+    /// 余额连续 dormant_epochs 个 epoch 没有变化的账户可由政策权限发出警告（WarnDormant），
+    /// 警告后再过 notice_epochs 个 epoch 仍无活动时，余额可被归集到 recovery_vault（SweepDormant）。
+    /// 此后新建的代币账户自动开启活动时间记录（需按 TOKEN_ACCOUNT_WITH_ACTIVITY_LEN 分配空间）；
+    /// 已有账户须由持有人自行开启 EnableActivityTracking，否则不受政策约束
+    /// 铸币账户需按 MINT_WITH_DORMANCY_POLICY_LEN 分配空间
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 首次设置时为铸币权限，之后为当前政策权限 (签名者)
+    SetDormancyPolicy {
+        recovery_vault: Pubkey,
+        dormant_epochs: u64,
+        notice_epochs: u64,
+    },
+
+    /// 对休眠账户发出警告：创建警告 PDA（["dormancy", 代币账户]）并发出 DormancyEvent::Warned
+    /// 账户列表:
+    /// [0] 铸币账户
+    /// [1] 代币账户
+    /// [2] 警告 PDA (可写)
+    /// [3] 政策权限 (签名者, 可写，支付租金)
+    /// [4] 系统程序
+    WarnDormant,
+
+    /// 归集休眠账户余额到回收账户；警告后账户有过活动时只关闭警告 PDA（DormancyEvent::Cancelled）
+    /// 警告 PDA 的租金退回政策权限
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 代币账户 (可写)
+    /// [2] 回收代币账户 (可写)，必须是政策中登记的 recovery_vault
+    /// [3] 警告 PDA (可写)
+    /// [4] 政策权限 (签名者, 可写)
+    /// 其后的投票检查点账户同 Transfer
+    SweepDormant,
+
+    /// 更新储备证明：铸币权限与审计方共同签名，写入审计方确认的储备数量（与供应量同为最小单位）。
+    /// 证明 PDA（["attestation", 铸币]）不存在时创建，铸币权限支付租金
+    /// 账户列表:
+    /// [0] 铸币账户
+    /// [1] 证明 PDA (可写)
+    /// [2] 铸币权限 (签名者, 可写)
+    /// [3] 审计方 (签名者)
+    /// [4] 系统程序
+    UpdateAttestation {
+        reserves: u64,
+    },
+
+    /// 核对供应量是否有储备支撑（任何人可调用）：比较当前供应量与最近一次证明的储备，发出 ReserveEvent::Verified
+    /// 账户列表:
+    /// [0] 铸币账户
+    /// [1] 证明 PDA
+    VerifySupplyBacked,
+
+    /// 开启代币迁移：旧铸币的持有人可按固定比率把旧代币换成新铸币，新代币从兑换 PDA 持有的托管账户支付。
+    /// 兑换 PDA 为 ["conversion", 旧铸币]；rate 为每个旧代币最小单位兑换的新代币最小单位数乘以 CONVERSION_RATE_SCALE，
+    /// 两个铸币精度不同时由 rate 折算。托管账户之后用普通的 Transfer / MintTo 注资
+    /// 账户列表:
+    /// [0] 旧铸币账户
+    /// [1] 新铸币账户
+    /// [2] 兑换 PDA (可写)
+    /// [3] 托管代币账户（新铸币，所有者为兑换 PDA）
+    /// [4] 旧铸币的铸币权限 (签名者)
+    /// [5] 付款账户 (签名者, 可写)
+    /// [6] 系统程序
+    InitializeConversion {
+        rate: u64,
+    },
+
+    /// 兑换：销毁 amount 个旧代币，按兑换比率（向下取整）从托管账户转出新代币
+    /// 账户列表:
+    /// [0] 旧铸币账户 (可写)
+    /// [1] 新铸币账户 (可写)
+    /// [2] 旧代币账户 (可写)
+    /// [3] 接收新代币的账户 (可写)
+    /// [4] 兑换 PDA
+    /// [5] 托管代币账户 (可写)
+    /// [6] 旧代币账户所有者 (签名者)
+    /// 其后为旧代币账户受托人的投票检查点（同 Burn），再其后为接收账户受托人的投票检查点
+    ConvertMint {
+        amount: u64,
+    },
+
+    /// 同一所有者、同一铸币的两个账户之间调拨余额：不执行转账钩子、速度限制、备注与 NFT 白名单检查，
+    /// 供托管方调整内部账簿。冻结状态仍然生效
+    /// 账户列表:
+    /// [0] 源代币账户 (可写)
+    /// [1] 目标代币账户 (可写)
+    /// [2] 所有者 (签名者)
+    /// [3] 铸币账户 (可写)
+    /// 其后的投票检查点同 Transfer
+    Rebalance {
+        amount: u64,
+    },
+
+    /// 分项暂停（仅冻结权限）：转账、铸造、销毁三项各自独立开关，为 None 的项保持不变。
+    /// 例如遭遇攻击时只暂停转账，持有人仍可销毁赎回。状态写入铸币的 PauseState 扩展，
+    /// 铸币账户需按 MINT_WITH_PAUSE_STATE_LEN 分配空间（与其他扩展同时使用时空间相加）。
+    /// 与 GlobalFreeze 相同，所有转出余额的指令都会按附带的铸币账户检查；暂停不受 FreezeExempt 豁免
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 冻结权限 (签名者)
+    SetPauseFlags {
+        pause_transfers: Option<bool>,
+        pause_minting: Option<bool>,
+        pause_burning: Option<bool>,
+    },
+
+    /// 设置激活要求（仅铸币权限）：required 为 true 时，此后新建的代币账户在所有者签名 ActivateAccount 之前
+    /// 不能收款（Transfer、调拨与铸造），避免把代币误转到刚派生出来、尚无人控制的地址。
+    /// 新账户需按 TOKEN_ACCOUNT_WITH_ACTIVATION_LEN 分配空间；关闭要求不影响已创建的待激活账户。
+    /// 铸币账户需按 MINT_WITH_ACTIVATION_POLICY_LEN 分配空间
+    /// 账户列表:
+    /// [0] 铸币账户 (可写)
+    /// [1] 铸币权限 (签名者)
+    SetActivationRequired {
+        required: bool,
+    },
+
+    /// 激活代币账户：所有者签名证明控制该地址，此后账户可以正常收款；账户不需要激活时不做任何改动
+    /// 账户列表:
+    /// [0] 代币账户 (可写)
+    /// [1] 所有者 (签名者)
+    ActivateAccount,
+}
+
+/// 指令数据当前版本号（版本化信封 `[VERSIONED_TAG, version, ...]` 中的 version）
+pub const INSTRUCTION_VERSION: u8 = 1;
+/// 首字节 0..=127 为现有指令的 Borsh 变体标签
+pub const MAX_INSTRUCTION_TAG: u8 = 127;
+/// 首字节 128..=253 预留给将来的指令，旧程序遇到时返回 UnsupportedInstructionVersion 而非解析错误
+pub const RESERVED_TAG_START: u8 = 128;
+/// 版本化信封前缀：后接 1 字节版本号与 Borsh 编码的指令（255 已被 legacy::EXTENDED_TAG 占用）
+pub const VERSIONED_TAG: u8 = 254;
+/// 单笔交易的数据包上限，没有更小上限的变长指令按它计算最大长度
+pub const MAX_INSTRUCTION_DATA_LEN: usize = 1232;
+
+impl TokenInstruction {
+    /// 解析指令数据，兼容裸 Borsh 编码与版本化信封。
+    /// 末尾多余的字节或超过 max_data_len 的数据返回 InvalidInstructionData，新增字段需要提升指令版本
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&tag, rest) = input.split_first().ok_or(TokenError::InvalidInstruction)?;
+        let mut data = match tag {
+            VERSIONED_TAG => {
+                let (&version, rest) = rest.split_first().ok_or(TokenError::InvalidInstruction)?;
+                if version == 0 || version > INSTRUCTION_VERSION {
+                    msg!("Unsupported instruction version: {}", version);
+                    return Err(TokenError::UnsupportedInstructionVersion.into());
+                }
+                rest
+            }
+            RESERVED_TAG_START..=u8::MAX => {
+                msg!("Instruction tag {} is reserved for a newer program version", tag);
+                return Err(TokenError::UnsupportedInstructionVersion.into());
+            }
+            _ => input,
+        };
+        let len = data.len();
+        let instruction = Self::deserialize(&mut data).map_err(|_| TokenError::InvalidInstruction)?;
+        if !data.is_empty() {
+            msg!("Instruction data is {} bytes, {} trailing bytes after the encoded instruction", len, data.len());
+            return Err(TokenError::InvalidInstructionData.into());
+        }
+        if len > instruction.max_data_len() {
+            msg!("Instruction data is {} bytes, at most {} allowed", len, instruction.max_data_len());
+            return Err(TokenError::InvalidInstructionData.into());
+        }
+        Ok(instruction)
+    }
+
+    /// 指令 Borsh 编码（含变体标签，不含版本信封）允许的最大字节数，变长字段按程序允许的上限计算
+    pub fn max_data_len(&self) -> usize {
+        const KEY: usize = 32;
+        const OPTION_KEY: usize = 1 + KEY;
+        const U64: usize = 8;
+        // Borsh 的 Vec / String 长度前缀
+        const LEN_PREFIX: usize = 4;
+        const LEAF: usize = KEY + 2 * U64;
+        const PROOF: usize = LEN_PREFIX + KEY * compression::MAX_COMPRESSED_TREE_DEPTH;
+
+        let fields = match self {
+            Self::InitializeAccount
+            | Self::EnableRebase
+            | Self::PrintEdition
+            | Self::ExecuteQueuedMint
+            | Self::CancelQueuedMint
+            | Self::InitializeTreasury
+            | Self::BurnTreasury
+            | Self::FillOrder
+            | Self::CancelOrder
+            | Self::WithdrawProceeds
+            | Self::InitializeShareVault
+            | Self::ExecuteAuthorityRecovery
+            | Self::CancelAuthorityRecovery
+            | Self::CloseEmptyMint
+            | Self::RefundHtlc
+            | Self::RevokeSession
+            | Self::CancelOwnerRecovery
+            | Self::RecoverOwner
+            | Self::EnableHolderCount
+            | Self::EnableActivityTracking
+            | Self::EnableMemoLog
+            | Self::GlobalFreeze
+            | Self::GlobalThaw
+            | Self::ReleaseConditionalTransfer
+            | Self::RefundConditionalTransfer
+            | Self::ClosePreAuthorization
+            | Self::DistributeEmissions
+            | Self::ExpirePoints
+            | Self::WarnDormant
+            | Self::SweepDormant
+            | Self::VerifySupplyBacked
+            | Self::ActivateAccount => 0,
+            Self::SweepAll { .. }
+            | Self::InitializeCompressedTree { .. }
+            | Self::RescaleMint { .. }
+            | Self::SetFreezeExempt { .. }
+            | Self::GetExtensionTypes { .. }
+            | Self::InitializeAccountWithOwnerCheck { .. }
+            | Self::SetActivationRequired { .. } => 1,
+            Self::ThawAccount { .. } => 2,
+            Self::MintTo { .. }
+            | Self::Transfer { .. }
+            | Self::Burn { .. }
+            | Self::GetVotesAt { .. }
+            | Self::TopUpRent { .. }
+            | Self::MintToNewAccount { .. }
+            | Self::BuyTokens { .. }
+            | Self::Deposit { .. }
+            | Self::Withdraw { .. }
+            | Self::TransferFrom { .. }
+            | Self::SessionTransfer { .. }
+            | Self::Debit { .. }
+            | Self::FundYield { .. }
+            | Self::DepositSavings { .. }
+            | Self::WithdrawSavings { .. }
+            | Self::EnablePointsExpiry { .. }
+            | Self::UpdateAttestation { .. }
+            | Self::InitializeConversion { .. }
+            | Self::ConvertMint { .. }
+            | Self::Rebalance { .. } => U64,
+            Self::CreateMasterEdition { .. } => 1 + U64,
+            Self::FreezeAccount { .. } => 1 + U64 + 2,
+            // 三个 Option<bool>
+            Self::SetPauseFlags { .. } => 3 * 2,
+            Self::BuyFromCurve { .. }
+            | Self::SellToCurve { .. }
+            | Self::MintAgainstCollateral { .. }
+            | Self::Rebase { .. }
+            | Self::BurnWithReceipt { .. }
+            | Self::SetVelocityLimit { .. }
+            | Self::Purchase { .. }
+            | Self::InitializeSale { .. }
+            | Self::AtomicSwap { .. } => 2 * U64,
+            // 变体标签 + 两个 u64（Linear 为两个 u64，Exponential 为 u64 + u16）
+            Self::InitializeCurve { .. } => 1 + 2 * U64,
+            Self::CreateAuction { .. } | Self::TransferWithPermit { .. } => 4 * U64,
+            Self::SetMintAuthority { .. } | Self::DelegateVotes { .. } => OPTION_KEY,
+            Self::InitializeEmergencyConfig { .. }
+            | Self::EnableAutoClose { .. }
+            | Self::ProposeOwnerRecovery { .. }
+            | Self::ConfigureBridge { .. }
+            | Self::InitializeSavingsVault { .. } => KEY,
+            Self::ProposeAuthorityRecovery { .. } => 1 + KEY,
+            Self::SetAuthority { .. } => 1 + OPTION_KEY,
+            Self::InitializeMint { .. } => 1 + KEY + OPTION_KEY,
+            Self::InitializeNftMint { .. } => KEY + OPTION_KEY,
+            Self::ConfigureOracle { .. } => OPTION_KEY + U64 + 2,
+            Self::QueueMint { .. }
+            | Self::DepositGoverningTokens { .. }
+            | Self::WithdrawGoverningTokens { .. }
+            | Self::Compress { .. } => KEY + U64,
+            Self::PlaceOrder { .. }
+            | Self::CreateSession { .. }
+            | Self::CreateHtlc { .. }
+            | Self::CreatePreAuthorization { .. }
+            | Self::SetDormancyPolicy { .. } => KEY + 2 * U64,
+            Self::CreateConditionalTransfer { .. } => KEY + U64,
+            Self::SetAccountLabel { .. } => ACCOUNT_LABEL_LEN,
+            Self::CreateMintWithSeed { .. } => LEN_PREFIX + MAX_SEED_LEN + 1 + OPTION_KEY,
+            Self::FundDistribution { .. } | Self::PermitApprove { .. } => KEY + 3 * U64,
+            Self::BridgeBurn { .. } => 2 + KEY + U64,
+            Self::BridgeMint { .. } => 2 + KEY + 2 * U64,
+            Self::CreateMetadata { .. } => {
+                3 * LEN_PREFIX + MAX_METADATA_NAME_LEN + MAX_METADATA_SYMBOL_LEN + MAX_METADATA_URI_LEN + 2 + 1
+            }
+            Self::SetTransferAllowlist { .. } => LEN_PREFIX + KEY * MAX_ALLOWED_PROGRAMS,
+            Self::SetGuardians { .. } => LEN_PREFIX + KEY * MAX_GUARDIANS + 1,
+            Self::InitializeEmissions { .. } => 3 * U64 + LEN_PREFIX + (KEY + 2) * MAX_EMISSION_RECIPIENTS,
+            Self::CompressedTransfer { .. } => LEAF + KEY + PROOF + KEY + U64,
+            Self::Decompress { .. } => LEAF + KEY + PROOF,
+            Self::ClaimDividend { .. }
+            | Self::TransferSplit { .. }
+            | Self::RedeemHtlc { .. }
+            | Self::TransferWithMemo { .. }
+            | Self::SettleBatch { .. }
+            | Self::ApproveMulti { .. }
+            | Self::Crank { .. } => return MAX_INSTRUCTION_DATA_LEN,
+        };
+        1 + fields
+    }
+
+    /// 以版本化信封编码指令
+    pub fn pack_versioned(&self) -> Result<Vec<u8>, ProgramError> {
+        let mut buf = vec![VERSIONED_TAG, INSTRUCTION_VERSION];
+        self.serialize(&mut buf).map_err(|_| ProgramError::InvalidInstructionData)?;
+        Ok(buf)
+    }
+}
+
+// 铸币账户状态（账户数据由 pack::ManualPack 编解码，Borsh 派生仅供测试对照）
+#[cfg_attr(any(test, feature = "test-utils"), derive(BorshSerialize, BorshDeserialize))]
+#[derive(Debug, Clone)]
+pub struct Mint {
+    pub is_initialized: bool, 
+    pub decimals: u8,  
+    pub mint_authority: Option<Pubkey>, 
+    pub supply: u64,  
+    pub freeze_authority: Option<Pubkey>, 
+    pub is_nft: bool,
+}
+/*[1, 9, 1, 
+155, 22, 161, 0, 165, 161, 89, 151, 
+69, 21, 189, 198, 115, 47, 220, 42, 
+56, 108, 222, 27, 178, 156, 220, 16, 
+176, 224, 163, 9, 165, 49, 153, 117,
+0, 0, 0, 0, 0, 0, 0, 0,
+0]",*/
+impl Mint {
+    /// 序列化后的大小。末尾的 is_nft 随 InitializeNftMint 加入，此前创建的铸币账户只分配了 LEN - 1 字节：
+    /// 两个权限都存在时账户里没有这个字节，读取时视为 false，写回时省略；这类账户不能再追加扩展
+    pub const LEN: usize = 1 + 1 + 33 + 8 + 33 + 1;
+    
+    pub fn new(
+        decimals: u8,
+        mint_authority: Pubkey,
+        freeze_authority: Option<Pubkey>,
+    ) -> Self {
+        Self {
+            is_initialized: true,
+            decimals,
+            mint_authority: Some(mint_authority),
+            supply: 0,
+            freeze_authority,
+            is_nft: false,
+        }
+    }
+
+    pub fn new_nft(mint_authority: Pubkey, freeze_authority: Option<Pubkey>) -> Self {
+        Self {
+            is_nft: true,
+            ..Self::new(0, mint_authority, freeze_authority)
+        }
+    }
+
+    /// 增加供应量；NFT 模式下铸造后供应量必须恰好为 1，并自动撤销铸币权限
+    pub fn mint_supply(&mut self, amount: u64) -> ProgramResult {
+        let supply = self.supply.checked_add(amount).ok_or(TokenError::Overflow)?;
+        if self.is_nft {
+            if supply != NFT_SUPPLY {
+                msg!("NFT mint supply must be exactly {}, got {}", NFT_SUPPLY, supply);
+                return Err(TokenError::NftSupplyExceeded.into());
+            }
+            self.mint_authority = None;
+        }
+        self.supply = supply;
+        Ok(())
+    }
+}
+
+/// NFT 模式下的固定供应量
+pub const NFT_SUPPLY: u64 = 1;
+
+// 代币账户标志位（单字节，位于原 is_initialized 所在的第 0 字节，旧账户的 is_initialized=1 即 INITIALIZED）
+#[cfg_attr(any(test, feature = "test-utils"), derive(BorshSerialize, BorshDeserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AccountFlags(pub u8);
+
+impl AccountFlags {
+    pub const INITIALIZED: u8 = 1 << 0;
+    pub const FROZEN: u8 = 1 << 1;
+    /// 所属铸币为 NFT 模式，转账需检查白名单
+    pub const NFT: u8 = 1 << 2;
+    /// 转账需经过速度跟踪
+    pub const VELOCITY_LIMITED: u8 = 1 << 3;
+    // 以下为预留标志，尚无处理逻辑
+    pub const IMMUTABLE_OWNER: u8 = 1 << 4;
+    pub const CPI_GUARD: u8 = 1 << 5;
+    pub const MEMO_REQUIRED: u8 = 1 << 6;
+    /// 所属铸币开启了持有人计数或带有全局冻结 / 暂停扩展；SettleBatch 不同步计数，因此不结算这些账户
+    pub const HOLDER_TRACKED: u8 = 1 << 7;
+
+    pub fn contains(&self, flag: u8) -> bool {
+        self.0 & flag == flag
+    }
+
+    pub fn set(&mut self, flag: u8, value: bool) {
+        if value {
+            self.0 |= flag;
+        } else {
+            self.0 &= !flag;
+        }
+    }
+}
+
+// 代币账户状态（账户数据由 pack::ManualPack 编解码，Borsh 派生仅供测试对照）
+#[cfg_attr(any(test, feature = "test-utils"), derive(BorshSerialize, BorshDeserialize))]
+#[derive(Debug, Clone)]
+pub struct TokenAccount {
+    pub flags: AccountFlags, //1
+    pub mint: Pubkey, //32
+    pub owner: Pubkey, //32
+    pub amount: u64, //8
+    pub vote_delegate: Option<Pubkey>, //33
+}
+
+impl TokenAccount {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 33; // 序列化后的大小
+    
+    pub fn new(mint: Pubkey, owner: Pubkey) -> Self {
+        Self {
+            flags: AccountFlags(AccountFlags::INITIALIZED),
+            mint,
+            owner,
+            amount: 0,
+            vote_delegate: None,
+        }
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.flags.contains(AccountFlags::INITIALIZED)
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.flags.contains(AccountFlags::FROZEN)
+    }
+
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.flags.set(AccountFlags::FROZEN, frozen);
+    }
+
+    pub fn is_nft(&self) -> bool {
+        self.flags.contains(AccountFlags::NFT)
+    }
+
+    pub fn set_nft(&mut self, is_nft: bool) {
+        self.flags.set(AccountFlags::NFT, is_nft);
+    }
+
+    pub fn velocity_limited(&self) -> bool {
+        self.flags.contains(AccountFlags::VELOCITY_LIMITED)
+    }
+
+    pub fn set_velocity_limited(&mut self, limited: bool) {
+        self.flags.set(AccountFlags::VELOCITY_LIMITED, limited);
+    }
+
+    pub fn holder_tracked(&self) -> bool {
+        self.flags.contains(AccountFlags::HOLDER_TRACKED)
+    }
+
+    pub fn set_holder_tracked(&mut self, tracked: bool) {
+        self.flags.set(AccountFlags::HOLDER_TRACKED, tracked);
+    }
+
+    /// 同时带有 NFT 标志的新账户（NFT 铸币下的托管 / 国库账户）
+    pub fn with_nft(mut self, is_nft: bool) -> Self {
+        self.set_nft(is_nft);
+        self
+    }
+}
+
+// 联合曲线价格模型
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum CurveType {
+    /// 线性曲线：每个完整代币的价格 = base_price + slope * 当前供应量(完整代币)
+    Linear { base_price: u64, slope: u64 },
+    /// 指数曲线：每铸造一个完整代币，价格上涨 growth_bps 个基点
+    Exponential { base_price: u64, growth_bps: u16 },
+}
+
+/// 指数曲线定点数精度
+const CURVE_FIXED_SCALE: u128 = 1_000_000_000;
+
+impl CurveType {
+    pub fn validate(&self) -> ProgramResult {
+        match self {
+            CurveType::Linear { base_price, slope } => {
+                if *base_price == 0 && *slope == 0 {
+                    return Err(TokenError::InvalidCurve.into());
+                }
+            }
+            CurveType::Exponential { base_price, growth_bps } => {
+                if *base_price == 0 || *growth_bps == 0 || *growth_bps > 10_000 {
+                    return Err(TokenError::InvalidCurve.into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 供应量为 supply (基础单位) 时金库应持有的 SOL 储备（曲线下面积）
+    /// 买入成本 = R(s + a) - R(s)，卖出所得 = R(s) - R(s - a)
+    pub fn reserve_for_supply(&self, supply: u64, decimals: u8) -> Result<u128, ProgramError> {
+        let unit = math::one_token(decimals)?;
+        let x = supply as u128;
+        match self {
+            CurveType::Linear { base_price, slope } => {
+                // R(x) = base * x / unit + slope * x^2 / (2 * unit^2)
+                let base_part = (*base_price as u128)
+                    .checked_mul(x)
+                    .and_then(|v| v.checked_mul(unit))
+                    .and_then(|v| v.checked_mul(2))
+                    .ok_or(TokenError::Overflow)?;
+                let slope_part = x
+                    .checked_mul(x)
+                    .and_then(|v| v.checked_mul(*slope as u128))
+                    .ok_or(TokenError::Overflow)?;
+                let denominator = unit
+                    .checked_mul(unit)
+                    .and_then(|v| v.checked_mul(2))
+                    .ok_or(TokenError::Overflow)?;
+                Ok(base_part.checked_add(slope_part).ok_or(TokenError::Overflow)? / denominator)
+            }
+            CurveType::Exponential { base_price, growth_bps } => {
+                // 完整代币部分按几何级数求和，零头部分按当前台阶价格线性计价
+                let whole = (x / unit) as u64;
+                let fraction = x % unit;
+                let ratio = CURVE_FIXED_SCALE * (10_000 + *growth_bps as u128) / 10_000;
+                let power = fixed_pow(ratio, whole)?;
+                let base = *base_price as u128;
+                let whole_part = base
+                    .checked_mul(power - CURVE_FIXED_SCALE)
+                    .and_then(|v| v.checked_mul(10_000))
+                    .ok_or(TokenError::Overflow)?
+                    / (*growth_bps as u128 * CURVE_FIXED_SCALE);
+                let step_price = base.checked_mul(power).ok_or(TokenError::Overflow)? / CURVE_FIXED_SCALE;
+                let fraction_part = step_price.checked_mul(fraction).ok_or(TokenError::Overflow)? / unit;
+                whole_part.checked_add(fraction_part).ok_or_else(|| TokenError::Overflow.into())
+            }
+        }
+    }
+}
+
+/// 定点数快速幂：返回 base^exp，base 与结果均以 CURVE_FIXED_SCALE 为精度
+fn fixed_pow(base: u128, mut exp: u64) -> Result<u128, ProgramError> {
+    let mut result = CURVE_FIXED_SCALE;
+    let mut factor = base;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result.checked_mul(factor).ok_or(TokenError::Overflow)? / CURVE_FIXED_SCALE;
+        }
+        exp >>= 1;
+        if exp > 0 {
+            factor = factor.checked_mul(factor).ok_or(TokenError::Overflow)? / CURVE_FIXED_SCALE;
+        }
+    }
+    Ok(result)
+}
+
+pub const CURVE_SEED: &[u8] = b"curve";
+pub const CURVE_VAULT_SEED: &[u8] = b"curve_vault";
+
+// 联合曲线状态账户
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct BondingCurve {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub curve: CurveType, //1 + 16
+    pub bump: u8, //1
+    pub vault_bump: u8, //1
+}
+
+impl BondingCurve {
+    pub const LEN: usize = 1 + 32 + (1 + 16) + 1 + 1;
+}
+
+pub const ORACLE_SEED: &[u8] = b"oracle";
+pub const COLLATERAL_VAULT_SEED: &[u8] = b"collateral_vault";
+
+// 预言机配置账户
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct OracleConfig {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub authority: Pubkey, //32
+    pub price_feed: Option<Pubkey>, //33
+    pub max_staleness_slots: u64, //8
+    pub collateral_ratio_bps: u16, //2
+    pub bump: u8, //1
+    pub vault_bump: u8, //1
+}
+
+impl OracleConfig {
+    pub const LEN: usize = 1 + 32 + 32 + 33 + 8 + 2 + 1 + 1;
+}
+
+/// Pyth v2 价格账户中用到的字段
+#[derive(Debug, Clone, Copy)]
+pub struct PythPrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_slot: u64,
+}
+
+const PYTH_MAGIC: u32 = 0xa1b2_c3d4;
+const PYTH_VERSION_2: u32 = 2;
+const PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+
+impl PythPrice {
+    /// 按 Pyth v2 PriceAccount 布局读取聚合价格
+    pub fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < 240 {
+            return Err(TokenError::InvalidOracle.into());
+        }
+        let read_u32 = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+        let read_u64 = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+
+        if read_u32(0) != PYTH_MAGIC
+            || read_u32(4) != PYTH_VERSION_2
+            || read_u32(8) != PYTH_ACCOUNT_TYPE_PRICE
+        {
+            return Err(TokenError::InvalidOracle.into());
+        }
+        if read_u32(224) != PYTH_STATUS_TRADING {
+            msg!("Pyth price is not in trading status");
+            return Err(TokenError::InvalidOracle.into());
+        }
+
+        Ok(Self {
+            expo: read_u32(20) as i32,
+            price: read_u64(208) as i64,
+            conf: read_u64(216),
+            publish_slot: read_u64(232),
+        })
+    }
+}
+
+pub const REBASE_SEED: &[u8] = b"rebase";
+/// rebase 缩放因子精度（1.0 = REBASE_SCALE）
+pub const REBASE_SCALE: u128 = 1_000_000_000_000_000_000;
+
+// 弹性供应配置：账户中存储的是原始份额，展示余额 = 原始份额 * multiplier / REBASE_SCALE
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct RebaseConfig {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub authority: Pubkey, //32
+    pub multiplier: u128, //16
+    pub rebase_count: u64, //8
+}
+
+impl RebaseConfig {
+    pub const LEN: usize = 1 + 32 + 32 + 16 + 8;
+
+    /// 原始份额 -> 展示余额
+    pub fn to_rebased_amount(&self, raw_amount: u64) -> Result<u64, ProgramError> {
+        let amount = (raw_amount as u128)
+            .checked_mul(self.multiplier)
+            .ok_or(TokenError::Overflow)?
+            / REBASE_SCALE;
+        u64::try_from(amount).map_err(|_| TokenError::Overflow.into())
+    }
+
+    /// 展示余额 -> 原始份额（向下取整）
+    pub fn to_raw_amount(&self, rebased_amount: u64) -> Result<u64, ProgramError> {
+        let amount = (rebased_amount as u128)
+            .checked_mul(REBASE_SCALE)
+            .ok_or(TokenError::Overflow)?
+            / self.multiplier;
+        u64::try_from(amount).map_err(|_| TokenError::Overflow.into())
+    }
+}
+
+pub const DISTRIBUTION_SEED: &[u8] = b"distribution";
+pub const DIVIDEND_CLAIM_SEED: &[u8] = b"dividend_claim";
+
+// 分红状态账户
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct Distribution {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub distribution_id: u64, //8
+    pub funder: Pubkey, //32
+    pub reward_mint: Pubkey, //32
+    pub reward_vault: Pubkey, //32
+    pub snapshot_root: [u8; 32], //32
+    pub snapshot_supply: u64, //8
+    pub snapshot_slot: u64, //8
+    pub total_amount: u64, //8
+    pub claimed_amount: u64, //8
+    pub bump: u8, //1
+}
+
+impl Distribution {
+    pub const LEN: usize = 1 + 32 + 8 + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1;
+
+    /// 快照叶子 = hash(0x00 || 持仓代币账户 || 快照余额)
+    pub fn leaf(holder_token_account: &Pubkey, snapshot_balance: u64) -> [u8; 32] {
+        hashv(&[&[0u8], holder_token_account.as_ref(), &snapshot_balance.to_le_bytes()]).to_bytes()
+    }
+
+    /// 校验默克尔证明（节点按字节序排序后 hash(0x01 || a || b)）
+    pub fn verify_proof(&self, leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+        let computed = proof.iter().fold(leaf, |node, sibling| {
+            let (a, b) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+            hashv(&[&[1u8], &a, &b]).to_bytes()
+        });
+        computed == self.snapshot_root
+    }
+}
+
+// 分红领取记录（存在即表示已领取）
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct DividendClaim {
+    pub is_initialized: bool, //1
+    pub distribution: Pubkey, //32
+    pub holder_token_account: Pubkey, //32
+    pub amount: u64, //8
+}
+
+impl DividendClaim {
+    pub const LEN: usize = 1 + 32 + 32 + 8;
+}
+
+pub const VOTES_SEED: &[u8] = b"votes";
+/// 每个受托人保留的检查点数量，超出后丢弃最早的记录
+pub const MAX_VOTE_CHECKPOINTS: usize = 32;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct VoteCheckpoint {
+    pub slot: u64,
+    pub votes: u64,
+}
+
+// 受托人投票权检查点
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct VoteCheckpoints {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub delegatee: Pubkey, //32
+    pub truncated: bool, //1 是否丢弃过早期检查点
+    pub checkpoints: Vec<VoteCheckpoint>, //4 + 16 * MAX_VOTE_CHECKPOINTS
+}
+
+impl VoteCheckpoints {
+    pub const LEN: usize = 1 + 32 + 32 + 1 + 4 + 16 * MAX_VOTE_CHECKPOINTS;
+
+    pub fn current_votes(&self) -> u64 {
+        self.checkpoints.last().map_or(0, |checkpoint| checkpoint.votes)
+    }
+
+    /// 二分查找 slot 时刻的投票权
+    pub fn votes_at(&self, slot: u64) -> Result<u64, ProgramError> {
+        let index = self.checkpoints.partition_point(|checkpoint| checkpoint.slot <= slot);
+        if index == 0 {
+            // 早于第一个检查点：若历史被截断则无法给出准确结果
+            if self.truncated {
+                return Err(TokenError::CheckpointUnavailable.into());
+            }
+            return Ok(0);
+        }
+        Ok(self.checkpoints[index - 1].votes)
+    }
+
+    /// 写入新的检查点，同一 slot 内只保留最后一次
+    pub fn record(&mut self, slot: u64, votes: u64) {
+        if let Some(last) = self.checkpoints.last_mut() {
+            if last.slot == slot {
+                last.votes = votes;
+                return;
+            }
+        }
+        if self.checkpoints.len() == MAX_VOTE_CHECKPOINTS {
+            self.checkpoints.remove(0);
+            self.truncated = true;
+        }
+        self.checkpoints.push(VoteCheckpoint { slot, votes });
+    }
+}
+
+pub const MINT_SEED: &[u8] = b"mint";
+
+/// 推导 (铸币权限, 种子) 对应的铸币地址（CreateMintWithSeed）
+pub fn find_mint_address(authority: &Pubkey, seed: &str, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_SEED, authority.as_ref(), seed.as_bytes()], program_id)
+}
+
+pub const ASSOCIATED_SEED: &[u8] = b"associated";
+
+/// 推导本程序内 (钱包, 铸币) 对应的关联代币账户地址
+pub fn get_associated_token_address(wallet: &Pubkey, mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ASSOCIATED_SEED, wallet.as_ref(), mint.as_ref()], program_id)
+}
+
+pub const BURN_RECEIPT_SEED: &[u8] = b"burn_receipt";
+
+// 销毁凭证
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct BurnReceipt {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub burner: Pubkey, //32
+    pub token_account: Pubkey, //32
+    pub receipt_id: u64, //8
+    pub amount: u64, //8
+    pub slot: u64, //8
+}
+
+impl BurnReceipt {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8 + 8 + 8;
+}
+
+/// Metaplex Token Metadata 程序
+pub const METADATA_PROGRAM_ID: Pubkey = solana_program::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+pub const METADATA_SEED: &[u8] = b"metadata";
+/// CreateMetadataAccountV3 指令判别式
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+pub const MAX_METADATA_NAME_LEN: usize = 32;
+pub const MAX_METADATA_SYMBOL_LEN: usize = 10;
+pub const MAX_METADATA_URI_LEN: usize = 200;
+
+// 元数据内容（对应 Metaplex DataV2 中的必填字段，creators/collection/uses 均为 None）
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct MetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+}
+
+impl MetadataArgs {
+    pub fn validate(&self) -> ProgramResult {
+        if self.name.len() > MAX_METADATA_NAME_LEN
+            || self.symbol.len() > MAX_METADATA_SYMBOL_LEN
+            || self.uri.len() > MAX_METADATA_URI_LEN
+            || self.seller_fee_basis_points > 10_000
+        {
+            return Err(TokenError::InvalidMetadata.into());
+        }
+        Ok(())
+    }
+}
+
+/// 推导铸币对应的 Metaplex 元数据账户地址
+pub fn find_metadata_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[METADATA_SEED, METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &METADATA_PROGRAM_ID,
+    )
+}
+
+/// 构造 Metaplex CreateMetadataAccountV3 指令
+pub fn create_metadata_accounts_v3_instruction(
+    metadata: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    update_authority: &Pubkey,
+    args: &MetadataArgs,
+    is_mutable: bool,
+) -> Result<Instruction, ProgramError> {
+    let mut data = vec![CREATE_METADATA_ACCOUNT_V3];
+    args.serialize(&mut data).map_err(|_| ProgramError::InvalidInstructionData)?;
+    // creators / collection / uses: None
+    data.extend_from_slice(&[0, 0, 0]);
+    data.push(u8::from(is_mutable));
+    // collection_details: None
+    data.push(0);
+    Ok(Instruction {
+        program_id: METADATA_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*metadata, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*update_authority, mint_authority == update_authority),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data,
+    })
+}
+
+pub const MASTER_EDITION_SEED: &[u8] = b"master_edition";
+pub const EDITION_SEED: &[u8] = b"edition";
+
+// 母版版本：记录最大印刷数量与已印刷数量
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct MasterEdition {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub max_supply: Option<u64>, //9 None 表示不限量
+    pub supply: u64, //8
+    pub bump: u8, //1
+}
+
+impl MasterEdition {
+    pub const LEN: usize = 1 + 32 + 9 + 8 + 1;
+}
+
+// 印刷副本：记录所属母版与编号（从 1 开始）
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct Edition {
+    pub is_initialized: bool, //1
+    pub parent: Pubkey, //32 母版铸币
+    pub mint: Pubkey, //32
+    pub edition: u64, //8
+}
+
+impl Edition {
+    pub const LEN: usize = 1 + 32 + 32 + 8;
+}
+
+pub const TRANSFER_ALLOWLIST_SEED: &[u8] = b"transfer_allowlist";
+/// 每个铸币白名单中最多的程序数量
+pub const MAX_ALLOWED_PROGRAMS: usize = 8;
+
+// NFT 转账程序白名单
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct TransferAllowlist {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub authority: Pubkey, //32
+    pub programs: Vec<Pubkey>, //4 + 32 * MAX_ALLOWED_PROGRAMS
+}
+
+impl TransferAllowlist {
+    pub const LEN: usize = 1 + 32 + 32 + 4 + 32 * MAX_ALLOWED_PROGRAMS;
+}
+
+pub const VELOCITY_SEED: &[u8] = b"velocity";
+
+// 代币账户转账速度跟踪
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct VelocityTracker {
+    pub is_initialized: bool, //1
+    pub token_account: Pubkey, //32
+    pub max_amount: u64, //8
+    pub window_slots: u64, //8
+    pub window_start_slot: u64, //8
+    pub window_amount: u64, //8
+}
+
+impl VelocityTracker {
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 8 + 8;
+
+    /// 记录一笔转出，返回累计金额是否仍在上限内
+    pub fn record(&mut self, slot: u64, amount: u64) -> bool {
+        if slot.saturating_sub(self.window_start_slot) >= self.window_slots {
+            self.window_start_slot = slot;
+            self.window_amount = 0;
+        }
+        self.window_amount = self.window_amount.saturating_add(amount);
+        self.window_amount <= self.max_amount
+    }
+}
+
+pub const QUEUED_MINT_SEED: &[u8] = b"queued_mint";
+/// 排队铸造的时间锁（约 1 天）
+pub const MINT_TIMELOCK_SLOTS: u64 = 216_000;
+
+// 排队中的铸造
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct QueuedMint {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub destination: Pubkey, //32
+    pub authority: Pubkey, //32
+    pub amount: u64, //8
+    pub queued_slot: u64, //8
+    pub executable_slot: u64, //8
+}
+
+impl QueuedMint {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8 + 8 + 8;
+}
+
+pub const TREASURY_SEED: &[u8] = b"treasury";
+
+/// 推导铸币的国库代币账户地址
+pub fn find_treasury_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_SEED, mint.as_ref()], program_id)
+}
+
+// 国库销毁事件（Borsh 序列化后写入 "Program data:" 日志）
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct TreasuryBurnEvent {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub supply: u64,
+    pub slot: u64,
+}
+
+pub const ORDER_SEED: &[u8] = b"order";
+
+/// 推导托管代币账户对应的挂单 PDA
+pub fn find_order_address(escrow: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ORDER_SEED, escrow.as_ref()], program_id)
+}
+
+// OTC 挂单
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct Order {
+    pub is_initialized: bool, //1
+    pub maker: Pubkey, //32
+    pub escrow: Pubkey, //32
+    pub maker_receive: Pubkey, //32 卖方接收 want_mint 的代币账户
+    pub give_mint: Pubkey, //32
+    pub give_amount: u64, //8
+    pub want_mint: Pubkey, //32
+    pub want_amount: u64, //8
+}
+
+impl Order {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 32 + 8 + 32 + 8;
+}
+
+pub const AUCTION_SEED: &[u8] = b"auction";
+pub const AUCTION_VAULT_SEED: &[u8] = b"auction_vault";
+
+// 荷兰式拍卖状态
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct DutchAuction {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub authority: Pubkey, //32 创建拍卖时的铸币权限
+    pub start_slot: u64, //8
+    pub start_price: u64, //8
+    pub floor_price: u64, //8
+    pub decay: u64, //8 每个 slot 的降价幅度
+    pub supply: u64, //8
+    pub sold: u64, //8
+    pub bump: u8, //1
+    pub vault_bump: u8, //1
+}
+
+impl DutchAuction {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 1;
+
+    /// 指定 slot 时每个完整代币的价格
+    pub fn price_at(&self, slot: u64) -> u64 {
+        let elapsed = slot.saturating_sub(self.start_slot);
+        self.start_price
+            .saturating_sub(elapsed.saturating_mul(self.decay))
+            .max(self.floor_price)
+    }
+}
+
+pub const SALE_SEED: &[u8] = b"sale";
+pub const SALE_VAULT_SEED: &[u8] = b"sale_vault";
+
+// 固定价格众筹状态
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct Sale {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub authority: Pubkey, //32
+    pub price_lamports_per_token: u64, //8 每个完整代币的价格
+    pub cap: u64, //8
+    pub sold: u64, //8
+    pub bump: u8, //1
+    pub vault_bump: u8, //1
+}
+
+impl Sale {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 8 + 1 + 1;
+}
+
+pub const SHARE_VAULT_SEED: &[u8] = b"share_vault";
+
+/// 推导份额铸币对应的份额金库 PDA
+pub fn find_share_vault_address(share_mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SHARE_VAULT_SEED, share_mint.as_ref()], program_id)
+}
+
+// 份额金库
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct ShareVault {
+    pub is_initialized: bool, //1
+    pub asset_mint: Pubkey, //32
+    pub share_mint: Pubkey, //32
+    pub asset_vault: Pubkey, //32 归金库 PDA 所有的资产托管代币账户
+    pub bump: u8, //1
+}
+
+impl ShareVault {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 1;
+
+    /// 存入 amount 资产可得的份额（向下取整）
+    pub fn shares_for_deposit(amount: u64, total_assets: u64, total_shares: u64) -> Result<u64, ProgramError> {
+        if total_shares == 0 || total_assets == 0 {
+            return Ok(amount);
+        }
+        let shares = (amount as u128)
+            .checked_mul(total_shares as u128)
+            .ok_or(TokenError::Overflow)?
+            / total_assets as u128;
+        u64::try_from(shares).map_err(|_| TokenError::Overflow.into())
+    }
+
+    /// 赎回 shares 份额可得的资产（向下取整）
+    pub fn assets_for_withdraw(shares: u64, total_assets: u64, total_shares: u64) -> Result<u64, ProgramError> {
+        if total_shares == 0 {
+            return Ok(0);
+        }
+        let assets = (shares as u128)
+            .checked_mul(total_assets as u128)
+            .ok_or(TokenError::Overflow)?
+            / total_shares as u128;
+        u64::try_from(assets).map_err(|_| TokenError::Overflow.into())
+    }
+}
+
+pub const SAVINGS_VAULT_SEED: &[u8] = b"savings";
+pub const SAVINGS_POSITION_SEED: &[u8] = b"savings_position";
+
+/// 推导铸币的储蓄金库 PDA
+pub fn find_savings_vault_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SAVINGS_VAULT_SEED, mint.as_ref()], program_id)
+}
+
+/// 推导存款人在储蓄金库中的仓位 PDA
+pub fn find_savings_position_address(savings_vault: &Pubkey, owner: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SAVINGS_POSITION_SEED, savings_vault.as_ref(), owner.as_ref()], program_id)
+}
+
+// 储蓄金库：与 ShareVault 相同的份额换算，但份额记在各存款人的仓位 PDA 中而不是另发份额代币
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct SavingsVault {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub vault: Pubkey, //32 归金库 PDA 所有的托管代币账户
+    pub yield_authority: Pubkey, //32
+    pub total_shares: u64, //8
+    pub bump: u8, //1
+}
+
+impl SavingsVault {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8 + 1;
+}
+
+// 储蓄仓位
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct SavingsPosition {
+    pub is_initialized: bool, //1
+    pub savings_vault: Pubkey, //32
+    pub owner: Pubkey, //32
+    pub shares: u64, //8
+}
+
+impl SavingsPosition {
+    pub const LEN: usize = 1 + 32 + 32 + 8;
+}
+
+pub const EMISSIONS_SEED: &[u8] = b"emissions";
+pub const MAX_EMISSION_RECIPIENTS: usize = 8;
+
+/// 推导铸币的排放配置 PDA
+pub fn find_emissions_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EMISSIONS_SEED, mint.as_ref()], program_id)
+}
+
+/// 排放接收账户及其分配权重
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct EmissionRecipient {
+    pub vault: Pubkey, //32 接收排放的代币账户
+    pub weight: u16, //2
+}
+
+// 流动性挖矿排放计划
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct EmissionsConfig {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub authority: Pubkey, //32 原铸币权限，排放结束后交还
+    pub tokens_per_slot: u64, //8
+    pub start_slot: u64, //8
+    pub end_slot: u64, //8
+    pub distributed_until_slot: u64, //8 此前的排放已铸造
+    pub recipients: Vec<EmissionRecipient>, //4 + 34 * MAX_EMISSION_RECIPIENTS
+}
+
+impl EmissionsConfig {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 8 + 8 + 4 + 34 * MAX_EMISSION_RECIPIENTS;
+
+    /// 截至 slot 尚未铸造的排放：返回 (新的 distributed_until_slot, 数量)
+    pub fn pending(&self, slot: u64) -> Result<(u64, u64), ProgramError> {
+        let until = slot.min(self.end_slot);
+        let from = self.distributed_until_slot.max(self.start_slot);
+        if until <= from {
+            return Ok((self.distributed_until_slot, 0));
+        }
+        let amount = (until - from).checked_mul(self.tokens_per_slot).ok_or(TokenError::Overflow)?;
+        Ok((until, amount))
+    }
+
+    /// 按权重拆分 amount（向下取整），余数计入最后一个接收账户
+    pub fn split(&self, amount: u64) -> Vec<u64> {
+        let total_weight: u128 = self.recipients.iter().map(|recipient| recipient.weight as u128).sum();
+        let mut shares: Vec<u64> = self
+            .recipients
+            .iter()
+            .map(|recipient| (amount as u128 * recipient.weight as u128 / total_weight) as u64)
+            .collect();
+        let remainder = amount - shares.iter().sum::<u64>();
+        if let Some(last) = shares.last_mut() {
+            *last += remainder;
+        }
+        shares
+    }
+}
+
+pub const DORMANCY_SEED: &[u8] = b"dormancy";
+
+/// 推导代币账户的休眠警告 PDA
+pub fn find_dormancy_notice_address(token_account: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DORMANCY_SEED, token_account.as_ref()], program_id)
+}
+
+// 休眠警告：记录警告时账户的最近活动，之后余额有变化即视为账户恢复活跃
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct DormancyNotice {
+    pub is_initialized: bool, //1
+    pub token_account: Pubkey, //32
+    pub last_activity_slot: u64, //8 警告时的 AccountActivity.last_activity_slot
+    pub warned_at_slot: u64, //8
+}
+
+impl DormancyNotice {
+    pub const LEN: usize = 1 + 32 + 8 + 8;
+}
+
+pub const ATTESTATION_SEED: &[u8] = b"attestation";
+
+/// 推导铸币的储备证明 PDA
+pub fn find_attestation_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ATTESTATION_SEED, mint.as_ref()], program_id)
+}
+
+// 储备证明：审计方签名确认的链下储备数量
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct Attestation {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub attester: Pubkey, //32 最近一次签名的审计方
+    pub reserves: u64, //8 与供应量同为最小单位
+    pub attested_at_slot: u64, //8
+}
+
+impl Attestation {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8;
+}
+
+pub const CONVERSION_SEED: &[u8] = b"conversion";
+/// 兑换比率的定点精度：rate 为 CONVERSION_RATE_SCALE 时按最小单位 1:1 兑换
+pub const CONVERSION_RATE_SCALE: u128 = 1_000_000_000;
+
+/// 推导旧铸币的兑换 PDA
+pub fn find_conversion_address(old_mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONVERSION_SEED, old_mint.as_ref()], program_id)
+}
+
+// 代币迁移：旧铸币按固定比率兑换新铸币，新代币来自兑换 PDA 持有的托管账户
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct Conversion {
+    pub is_initialized: bool, //1
+    pub old_mint: Pubkey, //32
+    pub new_mint: Pubkey, //32
+    pub vault: Pubkey, //32 新铸币的托管代币账户
+    pub rate: u64, //8 乘以 CONVERSION_RATE_SCALE
+    pub total_converted: u64, //8 已销毁的旧代币
+}
+
+impl Conversion {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8 + 8;
+
+    /// 兑换 amount 个旧代币得到的新代币数量（向下取整）
+    pub fn converted_amount(&self, amount: u64) -> Result<u64, ProgramError> {
+        let converted = amount as u128 * self.rate as u128 / CONVERSION_RATE_SCALE;
+        u64::try_from(converted).map_err(|_| TokenError::Overflow.into())
+    }
+}
+
+pub const GOVERNANCE_SEED: &[u8] = b"governance";
+
+/// 推导治理领域内某铸币的托管代币账户地址
+pub fn find_governing_token_holding_address(realm: &Pubkey, mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GOVERNANCE_SEED, realm.as_ref(), mint.as_ref()], program_id)
+}
+
+/// 推导代币持有人记录地址
+pub fn find_token_owner_record_address(
+    realm: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[GOVERNANCE_SEED, realm.as_ref(), mint.as_ref(), owner.as_ref()],
+        program_id,
+    )
+}
+
+// 治理锁仓记录：governing_token_deposit_amount 即持有人在该领域的投票权重
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct TokenOwnerRecord {
+    pub is_initialized: bool, //1
+    pub realm: Pubkey, //32
+    pub governing_token_mint: Pubkey, //32
+    pub governing_token_owner: Pubkey, //32
+    pub governing_token_deposit_amount: u64, //8
+}
+
+impl TokenOwnerRecord {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8;
+}
+
+/// 构造 DepositGoverningTokens 指令，供 DAO 程序 CPI 调用；program_id 必须是规范部署的程序 ID
+pub fn deposit_governing_tokens_instruction(
+    program_id: &Pubkey,
+    realm: &Pubkey,
+    mint: &Pubkey,
+    source: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(program_id)?;
+    let data = TokenInstruction::DepositGoverningTokens { realm: *realm, amount }
+        .try_to_vec()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let (record, _) = find_token_owner_record_address(realm, mint, owner, program_id);
+    let (holding, _) = find_governing_token_holding_address(realm, mint, program_id);
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(record, false),
+            AccountMeta::new(holding, false),
+            AccountMeta::new(*source, false),
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data,
+    })
+}
+
+/// 构造 WithdrawGoverningTokens 指令，供 DAO 程序 CPI 调用
+pub fn withdraw_governing_tokens_instruction(
+    program_id: &Pubkey,
+    realm: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = TokenInstruction::WithdrawGoverningTokens { realm: *realm, amount }
+        .try_to_vec()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let (record, _) = find_token_owner_record_address(realm, mint, owner, program_id);
+    let (holding, _) = find_governing_token_holding_address(realm, mint, program_id);
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(record, false),
+            AccountMeta::new(holding, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data,
+    })
+}
+
+pub const EMERGENCY_CONFIG_SEED: &[u8] = b"emergency_config";
+pub const AUTHORITY_RECOVERY_SEED: &[u8] = b"authority_recovery";
+/// 紧急权限恢复的时间锁（约 7 天）
+pub const EMERGENCY_RECOVERY_TIMELOCK_SLOTS: u64 = 7 * 216_000;
+
+/// 推导紧急配置 PDA
+pub fn find_emergency_config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EMERGENCY_CONFIG_SEED], program_id)
+}
+
+/// 推导权限恢复 PDA
+pub fn find_authority_recovery_address(
+    mint: &Pubkey,
+    authority_type: AuthorityType,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[AUTHORITY_RECOVERY_SEED, mint.as_ref(), &[authority_type as u8]],
+        program_id,
+    )
+}
+
+// 铸币上的权限类型
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub enum AuthorityType {
+    MintTokens,
+    FreezeAccount,
+    /// 铸币级销毁权限（BurnAuthority 扩展）
+    BurnTokens,
+}
+
+/// InitializeAccountWithOwnerCheck 对所有者地址的要求
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub enum OwnerCurve {
+    /// 在 ed25519 曲线上，即持有私钥的钱包
+    OnCurve,
+    /// 不在曲线上，即 PDA
+    OffCurve,
+}
+
+/// Crank 可清理的到期状态
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub enum CrankItem {
+    /// 已过期的会话（slot 超过 expires_at_slot）：关闭会话 PDA，租金退回所有者
+    /// 账户: 代币账户、会话 PDA (可写)、所有者 (可写)
+    ExpiredSession,
+    /// 已过 timeout 的 HTLC：同 RefundHtlc，代币退回发送方并关闭 HTLC PDA
+    /// 账户: HTLC PDA (可写)、托管代币账户 (可写)、发送方代币账户 (可写)、发送方 (可写)、投票检查点 PDA (可写, 仅当发送方账户已委托投票)
+    ExpiredHtlc,
+    /// 定时解冻已到期的代币账户：清除 FROZEN 标志与 ScheduledThaw 扩展（没有可回收的租金，不发奖励）
+    /// 账户: 代币账户 (可写)
+    MaturedThaw,
+}
+
+/// Crank 每关闭一个 PDA 付给调用者的奖励，从该 PDA 的租金中扣除
+pub const CRANK_REWARD_LAMPORTS: u64 = 5_000;
+
+// 紧急管理员配置
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct EmergencyConfig {
+    pub is_initialized: bool, //1
+    pub emergency_admin: Pubkey, //32
+}
+
+impl EmergencyConfig {
+    pub const LEN: usize = 1 + 32;
+}
+
+// 待执行的权限恢复
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct AuthorityRecovery {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub authority_type: AuthorityType, //1
+    pub new_authority: Pubkey, //32
+    pub emergency_admin: Pubkey, //32
+    pub proposed_slot: u64, //8
+    pub executable_slot: u64, //8
+}
+
+impl AuthorityRecovery {
+    pub const LEN: usize = 1 + 32 + 1 + 32 + 32 + 8 + 8;
+}
+
+// 紧急恢复各步骤的事件（Borsh 序列化后写入 "Program data:" 日志）
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum EmergencyRecoveryEvent {
+    ConfigInitialized {
+        emergency_admin: Pubkey,
+        slot: u64,
+    },
+    RecoveryProposed {
+        mint: Pubkey,
+        authority_type: AuthorityType,
+        new_authority: Pubkey,
+        executable_slot: u64,
+        slot: u64,
+    },
+    RecoveryExecuted {
+        mint: Pubkey,
+        authority_type: AuthorityType,
+        old_authority: Pubkey,
+        new_authority: Pubkey,
+        slot: u64,
+    },
+    RecoveryCancelled {
+        mint: Pubkey,
+        authority_type: AuthorityType,
+        cancelled_by: Pubkey,
+        slot: u64,
+    },
+}
+
+impl EmergencyRecoveryEvent {
+    fn emit(&self) -> ProgramResult {
+        sol_log_data(&[&self.try_to_vec()?]);
+        Ok(())
+    }
+}
+
+// 特权操作事件（Borsh 序列化后写入 "Program data:" 日志），供监控告警追踪铸币上的权限变更与冻结 / 解冻
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum AuthorityEvent {
+    AuthorityChanged {
+        mint: Pubkey,
+        authority_type: AuthorityType,
+        old_authority: Option<Pubkey>,
+        new_authority: Option<Pubkey>,
+        slot: u64,
+    },
+    AccountFrozen {
+        mint: Pubkey,
+        account: Pubkey,
+        reason_code: u16,
+        slot: u64,
+    },
+    AccountThawed {
+        mint: Pubkey,
+        account: Pubkey,
+        authority: Pubkey,
+        reason_code: u16,
+        slot: u64,
+    },
+    /// 通过已弃用的 SetMintAuthority 变更了铸币权限（AuthorityChanged 照常发出）
+    DeprecatedSetMintAuthority {
+        mint: Pubkey,
+        slot: u64,
+    },
+}
+
+/// 速度限制触发的冻结在 AccountFrozen 事件中使用的原因码，冻结权限不能使用
+pub const FREEZE_REASON_VELOCITY_LIMIT: u16 = u16::MAX;
+
+impl AuthorityEvent {
+    fn emit(&self) -> ProgramResult {
+        sol_log_data(&[&self.try_to_vec()?]);
+        Ok(())
+    }
+}
+
+/// Transfer 可附带的引用键上限
+pub const MAX_TRANSFER_REFERENCES: usize = 8;
+
+// 转账事件（Borsh 序列化后写入 "Program data:" 日志），目前只在附带引用键时发出
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum TransferEvent {
+    Referenced {
+        mint: Pubkey,
+        source: Pubkey,
+        destination: Pubkey,
+        amount: u64,
+        references: Vec<Pubkey>,
+        slot: u64,
+    },
+}
+
+impl TransferEvent {
+    fn emit(&self) -> ProgramResult {
+        sol_log_data(&[&self.try_to_vec()?]);
+        Ok(())
+    }
+}
+
+// 休眠账户事件（Borsh 序列化后写入 "Program data:" 日志），警告事件即归集前的链上通知
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum DormancyEvent {
+    Warned {
+        mint: Pubkey,
+        account: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+        last_activity_slot: u64,
+        /// 从这个 epoch 起可以归集
+        sweepable_epoch: u64,
+        slot: u64,
+    },
+    Cancelled {
+        mint: Pubkey,
+        account: Pubkey,
+        slot: u64,
+    },
+    Swept {
+        mint: Pubkey,
+        account: Pubkey,
+        recovery_vault: Pubkey,
+        amount: u64,
+        slot: u64,
+    },
+}
+
+impl DormancyEvent {
+    fn emit(&self) -> ProgramResult {
+        sol_log_data(&[&self.try_to_vec()?]);
+        Ok(())
+    }
+}
+
+// 储备证明事件（Borsh 序列化后写入 "Program data:" 日志），监控方据 backed 判断供应量是否超发
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum ReserveEvent {
+    Attested {
+        mint: Pubkey,
+        attester: Pubkey,
+        reserves: u64,
+        slot: u64,
+    },
+    Verified {
+        mint: Pubkey,
+        supply: u64,
+        reserves: u64,
+        attested_at_slot: u64,
+        backed: bool,
+        slot: u64,
+    },
+}
+
+impl ReserveEvent {
+    fn emit(&self) -> ProgramResult {
+        sol_log_data(&[&self.try_to_vec()?]);
+        Ok(())
+    }
+}
+
+fn emit_authority_changed(
+    mint: &Pubkey,
+    authority_type: AuthorityType,
+    old_authority: Option<Pubkey>,
+    new_authority: Option<Pubkey>,
+) -> ProgramResult {
+    AuthorityEvent::AuthorityChanged {
+        mint: *mint,
+        authority_type,
+        old_authority,
+        new_authority,
+        slot: Clock::get()?.slot,
+    }
+    .emit()
+}
+
+/// 空铸币自动关闭配置（铸币扩展）：供应量归零且铸币权限撤销后，任何人都可以把租金回收到 close_destination
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct MintCloseConfig {
+    pub close_destination: Pubkey,
+}
+
+impl Extension for MintCloseConfig {
+    const TYPE: ExtensionType = ExtensionType::MintCloseConfig;
+    const LEN: usize = 32;
+}
+
+/// 携带 MintCloseConfig 扩展的铸币账户大小
+pub const MINT_WITH_CLOSE_CONFIG_LEN: usize = extension::account_len::<Mint, MintCloseConfig>();
+
+/// 持有人计数（铸币扩展）：余额非 0 的代币账户数量
+/// 铸造、销毁与转移余额的指令都经 credit_minted / move_balance / update_holder_count 维护计数，
+/// 因此这些指令须把对应的铸币账户以可写方式传入；SettleBatch 不结算开启计数的铸币的账户
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct HolderCount {
+    pub count: u64,
+}
+
+impl Extension for HolderCount {
+    const TYPE: ExtensionType = ExtensionType::HolderCount;
+    const LEN: usize = 8;
+}
+
+/// 携带 HolderCount 扩展的铸币账户大小
+pub const MINT_WITH_HOLDER_COUNT_LEN: usize = extension::account_len::<Mint, HolderCount>();
+
+/// 定时解冻（代币账户扩展）：thaw_at_slot 到达后账户按未冻结读取，下次写回时清除 FROZEN 标志
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct ScheduledThaw {
+    pub thaw_at_slot: Option<u64>,
+}
+
+impl Extension for ScheduledThaw {
+    const TYPE: ExtensionType = ExtensionType::ScheduledThaw;
+    const LEN: usize = 1 + 8;
+}
+
+/// 携带 ScheduledThaw 扩展的代币账户大小
+pub const TOKEN_ACCOUNT_WITH_SCHEDULED_THAW_LEN: usize = extension::account_len::<TokenAccount, ScheduledThaw>();
+
+/// 全局冻结豁免（代币账户扩展）
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct FreezeExempt {
+    pub exempt: bool,
+}
+
+impl Extension for FreezeExempt {
+    const TYPE: ExtensionType = ExtensionType::FreezeExempt;
+    const LEN: usize = 1;
+}
+
+/// 携带 FreezeExempt 扩展的代币账户大小
+pub const TOKEN_ACCOUNT_WITH_FREEZE_EXEMPT_LEN: usize = extension::account_len::<TokenAccount, FreezeExempt>();
+
+/// 代币账户标签的字节长度
+pub const ACCOUNT_LABEL_LEN: usize = 32;
+
+/// 代币账户标签（代币账户扩展），供托管方标记子账户用途
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct AccountLabel {
+    pub label: [u8; ACCOUNT_LABEL_LEN],
+}
+
+impl AccountLabel {
+    /// 去掉尾部补齐的 0 后的标签文本
+    pub fn as_str(&self) -> Option<&str> {
+        let len = self.label.iter().rposition(|&byte| byte != 0).map_or(0, |last| last + 1);
+        std::str::from_utf8(&self.label[..len]).ok()
+    }
+}
+
+impl Extension for AccountLabel {
+    const TYPE: ExtensionType = ExtensionType::AccountLabel;
+    const LEN: usize = ACCOUNT_LABEL_LEN;
+}
+
+/// 携带 AccountLabel 扩展的代币账户大小
+pub const TOKEN_ACCOUNT_WITH_LABEL_LEN: usize = extension::account_len::<TokenAccount, AccountLabel>();
+
+/// 积分有效期（铸币扩展）：发放的积分在 lifetime_slots 个 slot 后过期
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct PointsConfig {
+    pub lifetime_slots: u64,
+}
+
+impl Extension for PointsConfig {
+    const TYPE: ExtensionType = ExtensionType::PointsConfig;
+    const LEN: usize = 8;
+}
+
+/// 携带 PointsConfig 扩展的铸币账户大小
+pub const MINT_WITH_POINTS_CONFIG_LEN: usize = extension::account_len::<Mint, PointsConfig>();
+
+/// 每个代币账户保留的积分批次数
+pub const MAX_POINTS_LOTS: usize = 8;
+
+/// 一批积分（amount 为 0 表示空位）
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct PointsLot {
+    pub amount: u64,
+    pub expires_at_slot: u64,
+}
+
+/// 积分批次（代币账户扩展）：记录尚未过期的发放批次，花费时按先到期先花费的顺序抵扣
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Default)]
+pub struct PointsLots {
+    pub lots: [PointsLot; MAX_POINTS_LOTS],
+}
+
+impl PointsLots {
+    /// 记入一批积分：与已有批次同时到期时合并；没有空位时并入最晚到期的批次并顺延其到期时间（不会提前过期）
+    pub fn add(&mut self, amount: u64, expires_at_slot: u64) -> ProgramResult {
+        let index = self
+            .lots
+            .iter()
+            .position(|lot| lot.amount != 0 && lot.expires_at_slot == expires_at_slot)
+            .or_else(|| self.lots.iter().position(|lot| lot.amount == 0))
+            .or_else(|| (0..MAX_POINTS_LOTS).max_by_key(|&i| self.lots[i].expires_at_slot))
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let lot = &mut self.lots[index];
+        lot.amount = lot.amount.checked_add(amount).ok_or(TokenError::Overflow)?;
+        lot.expires_at_slot = lot.expires_at_slot.max(expires_at_slot);
+        Ok(())
+    }
+
+    /// 按 slot 时的 balance 结算：清除已到期的批次，返回余额中超出未过期批次之和的过期数量；
+    /// 未过期批次之和超过余额时，差额已被花掉，从最早到期的批次中扣除
+    pub fn expire(&mut self, slot: u64, balance: u64) -> u64 {
+        for lot in self.lots.iter_mut().filter(|lot| lot.expires_at_slot <= slot) {
+            *lot = PointsLot::default();
+        }
+        let live = self.lots.iter().fold(0u64, |sum, lot| sum.saturating_add(lot.amount));
+        self.spend_earliest(live.saturating_sub(balance));
+        balance.saturating_sub(live)
+    }
+
+    /// 转出 amount：按 slot 时的 balance 结算后先花费余额中已过期的部分，其余从最早到期的批次中取出，
+    /// 返回取出的批次（保持原到期时间）
+    pub fn take(&mut self, slot: u64, balance: u64, amount: u64) -> PointsLots {
+        let expired = self.expire(slot, balance);
+        self.spend_earliest(amount.saturating_sub(expired))
+    }
+
+    /// 按先到期先花费的顺序从批次中扣除 amount，返回被扣除的部分
+    fn spend_earliest(&mut self, mut amount: u64) -> PointsLots {
+        let mut spent = PointsLots::default();
+        let mut order: Vec<usize> = (0..MAX_POINTS_LOTS).filter(|&i| self.lots[i].amount != 0).collect();
+        order.sort_by_key(|&i| self.lots[i].expires_at_slot);
+        for i in order {
+            let used = amount.min(self.lots[i].amount);
+            spent.lots[i] = PointsLot { amount: used, expires_at_slot: self.lots[i].expires_at_slot };
+            self.lots[i].amount -= used;
+            amount -= used;
+            if self.lots[i].amount == 0 {
+                self.lots[i] = PointsLot::default();
+            }
+        }
+        spent
+    }
+}
+
+impl Extension for PointsLots {
+    const TYPE: ExtensionType = ExtensionType::PointsLots;
+    const LEN: usize = 16 * MAX_POINTS_LOTS;
+}
+
+/// 携带 PointsLots 扩展的代币账户大小
+pub const TOKEN_ACCOUNT_WITH_POINTS_LOTS_LEN: usize = extension::account_len::<TokenAccount, PointsLots>();
+
+/// 休眠账户政策（铸币扩展）：供托管类产品把长期无人认领的余额归集到回收账户
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct DormancyPolicy {
+    /// 发出警告与归集余额的权限
+    pub authority: Pubkey,
+    /// 接收归集余额的代币账户（同一铸币）
+    pub recovery_vault: Pubkey,
+    /// 余额无变化多少个 epoch 后可以发出警告
+    pub dormant_epochs: u64,
+    /// 警告后至少等待多少个 epoch 才能归集
+    pub notice_epochs: u64,
+}
+
+impl Extension for DormancyPolicy {
+    const TYPE: ExtensionType = ExtensionType::DormancyPolicy;
+    const LEN: usize = 32 + 32 + 8 + 8;
+}
+
+/// 携带 DormancyPolicy 扩展的铸币账户大小
+pub const MINT_WITH_DORMANCY_POLICY_LEN: usize = extension::account_len::<Mint, DormancyPolicy>();
+
+/// 铸币级销毁权限（铸币扩展），可从该铸币的任意代币账户销毁
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct BurnAuthority {
+    pub authority: Option<Pubkey>,
+}
+
+impl Extension for BurnAuthority {
+    const TYPE: ExtensionType = ExtensionType::BurnAuthority;
+    const LEN: usize = 1 + 32;
+}
+
+/// 携带 BurnAuthority 扩展的铸币账户大小
+pub const MINT_WITH_BURN_AUTHORITY_LEN: usize = extension::account_len::<Mint, BurnAuthority>();
+
+/// 全局冻结状态（铸币扩展），frozen 为 true 时该铸币的转账与销毁全部失败
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct GlobalFreezeState {
+    pub frozen: bool,
+}
+
+impl Extension for GlobalFreezeState {
+    const TYPE: ExtensionType = ExtensionType::GlobalFreezeState;
+    const LEN: usize = 1;
+}
+
+/// 携带 GlobalFreezeState 扩展的铸币账户大小
+pub const MINT_WITH_GLOBAL_FREEZE_LEN: usize = extension::account_len::<Mint, GlobalFreezeState>();
+
+/// 分项暂停状态（铸币扩展），三项互相独立，也与 GlobalFreezeState 无关
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq)]
+pub struct PauseState {
+    pub pause_transfers: bool,
+    pub pause_minting: bool,
+    pub pause_burning: bool,
+}
+
+impl Extension for PauseState {
+    const TYPE: ExtensionType = ExtensionType::PauseState;
+    const LEN: usize = 3;
+}
+
+/// 携带 PauseState 扩展的铸币账户大小
+pub const MINT_WITH_PAUSE_STATE_LEN: usize = extension::account_len::<Mint, PauseState>();
+
+/// 激活要求（铸币扩展），required 为 true 时新建的代币账户需所有者激活后才能收款
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct ActivationPolicy {
+    pub required: bool,
+}
+
+impl Extension for ActivationPolicy {
+    const TYPE: ExtensionType = ExtensionType::ActivationPolicy;
+    const LEN: usize = 1;
+}
+
+/// 携带 ActivationPolicy 扩展的铸币账户大小
+pub const MINT_WITH_ACTIVATION_POLICY_LEN: usize = extension::account_len::<Mint, ActivationPolicy>();
+
+/// 一个铸币最多调整精度的次数
+pub const MAX_RESCALE_EPOCHS: usize = 4;
+
+/// 精度调整记录（铸币扩展）：epoch 为已调整的次数，epoch_decimals[i] 为第 i 个纪元的精度（纪元 0 为初始精度）
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct DecimalsRescale {
+    pub epoch: u8,
+    pub epoch_decimals: [u8; MAX_RESCALE_EPOCHS + 1],
+}
+
+impl DecimalsRescale {
+    /// 按第 epoch 纪元精度记账的余额换算到当前精度需要乘的倍数
+    pub fn factor_since(&self, epoch: u8) -> Result<u64, ProgramError> {
+        let decimals_at = |epoch: u8| self.epoch_decimals.get(epoch as usize).copied();
+        let (from, to) = decimals_at(epoch).zip(decimals_at(self.epoch)).ok_or(ProgramError::InvalidAccountData)?;
+        let factor = math::one_token(to.checked_sub(from).ok_or(ProgramError::InvalidAccountData)?)?;
+        u64::try_from(factor).map_err(|_| TokenError::Overflow.into())
+    }
+}
+
+impl Extension for DecimalsRescale {
+    const TYPE: ExtensionType = ExtensionType::DecimalsRescale;
+    const LEN: usize = 1 + MAX_RESCALE_EPOCHS + 1;
+}
+
+/// 携带 DecimalsRescale 扩展的铸币账户大小
+pub const MINT_WITH_DECIMALS_RESCALE_LEN: usize = extension::account_len::<Mint, DecimalsRescale>();
+
+/// 代币账户余额所在的精度纪元（代币账户扩展），没有该扩展的账户处于纪元 0
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct RescaleEpoch {
+    pub epoch: u8,
+}
+
+impl Extension for RescaleEpoch {
+    const TYPE: ExtensionType = ExtensionType::RescaleEpoch;
+    const LEN: usize = 1;
+}
+
+/// 携带 RescaleEpoch 扩展的代币账户大小
+pub const TOKEN_ACCOUNT_WITH_RESCALE_EPOCH_LEN: usize = extension::account_len::<TokenAccount, RescaleEpoch>();
+
+pub const HTLC_SEED: &[u8] = b"htlc";
+
+/// 推导托管代币账户对应的 HTLC PDA
+pub fn find_htlc_address(escrow: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[HTLC_SEED, escrow.as_ref()], program_id)
+}
+
+// 哈希时间锁
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct Htlc {
+    pub is_initialized: bool, //1
+    pub sender: Pubkey, //32
+    pub escrow: Pubkey, //32
+    pub refund: Pubkey, //32 发送方代币账户
+    pub recipient: Pubkey, //32 接收方代币账户
+    pub mint: Pubkey, //32
+    pub amount: u64, //8
+    pub hash: [u8; 32], //32 sha256(preimage)
+    pub timeout: i64, //8 unix 时间戳
+}
+
+impl Htlc {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 32 + 32 + 8 + 32 + 8;
+}
+
+pub const CONDITIONAL_TRANSFER_SEED: &[u8] = b"conditional";
+
+/// 推导托管代币账户对应的条件转账 PDA
+pub fn find_conditional_transfer_address(escrow: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONDITIONAL_TRANSFER_SEED, escrow.as_ref()], program_id)
+}
+
+// 带仲裁人的条件转账
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct ConditionalTransfer {
+    pub is_initialized: bool, //1
+    pub sender: Pubkey, //32
+    pub escrow: Pubkey, //32
+    pub refund: Pubkey, //32 发送方代币账户
+    pub recipient: Pubkey, //32 接收方代币账户
+    pub mint: Pubkey, //32
+    pub arbiter: Pubkey, //32
+    pub amount: u64, //8
+}
+
+impl ConditionalTransfer {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 32 + 32 + 32 + 8;
+}
+
+pub const ALLOWANCE_SEED: &[u8] = b"allowance";
+
+/// 推导代币账户对 delegate 的额度 PDA
+pub fn find_allowance_address(token_account: &Pubkey, delegate: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ALLOWANCE_SEED, token_account.as_ref(), delegate.as_ref()], program_id)
+}
+
+// 受托人转账额度（不随额度用完而关闭，nonce 持续递增以防 permit 重放）
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct Allowance {
+    pub is_initialized: bool, //1
+    pub token_account: Pubkey, //32
+    pub delegate: Pubkey, //32
+    pub amount: u64, //8 剩余额度
+    pub nonce: u64, //8 下一个 permit 必须使用的 nonce
+}
+
+impl Allowance {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8;
+}
+
+pub const PERMIT_NONCE_SEED: &[u8] = b"permit_nonce";
+
+/// 推导代币账户的转账 permit nonce PDA
+pub fn find_permit_nonce_address(token_account: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PERMIT_NONCE_SEED, token_account.as_ref()], program_id)
+}
+
+// 转账 permit 的防重放计数
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct PermitNonce {
+    pub is_initialized: bool, //1
+    pub token_account: Pubkey, //32
+    pub nonce: u64, //8 下一个 permit 必须使用的 nonce
+}
+
+impl PermitNonce {
+    pub const LEN: usize = 1 + 32 + 8;
+}
+
+pub const SESSION_SEED: &[u8] = b"session";
+
+/// 推导代币账户的会话 PDA
+pub fn find_session_address(token_account: &Pubkey, key: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SESSION_SEED, token_account.as_ref(), key.as_ref()], program_id)
+}
+
+// 会话密钥授权
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct Session {
+    pub is_initialized: bool, //1
+    pub token_account: Pubkey, //32
+    pub key: Pubkey, //32
+    pub remaining: u64, //8 剩余可转出额度
+    pub expires_at_slot: u64, //8
+}
+
+impl Session {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8;
+}
+
+pub const PRE_AUTHORIZATION_SEED: &[u8] = b"preauth";
+
+/// 推导代币账户对某个商户的预授权 PDA
+pub fn find_pre_authorization_address(token_account: &Pubkey, merchant: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PRE_AUTHORIZATION_SEED, token_account.as_ref(), merchant.as_ref()], program_id)
+}
+
+// 预授权扣款（拉取式支付）
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct PreAuthorization {
+    pub is_initialized: bool, //1
+    pub token_account: Pubkey, //32 付款方代币账户
+    pub merchant: Pubkey, //32
+    pub amount_per_cycle: u64, //8
+    pub cycle_slots: u64, //8
+    pub cycle_start_slot: u64, //8 当前周期的起始 slot
+    pub debited_in_cycle: u64, //8 当前周期已扣金额
+}
+
+impl PreAuthorization {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 8 + 8;
+
+    /// 扣款 amount：slot 已进入新周期时先把起点前移整数个周期并清零已扣金额，超出本周期剩余额度时报错
+    pub fn debit(&mut self, slot: u64, amount: u64) -> ProgramResult {
+        let elapsed_cycles = slot.saturating_sub(self.cycle_start_slot) / self.cycle_slots;
+        if elapsed_cycles > 0 {
+            self.cycle_start_slot += elapsed_cycles * self.cycle_slots;
+            self.debited_in_cycle = 0;
+        }
+        let debited = self.debited_in_cycle.checked_add(amount).ok_or(TokenError::Overflow)?;
+        if debited > self.amount_per_cycle {
+            msg!(
+                "Pre-authorization allows {} per cycle, {} already debited since slot {}",
+                self.amount_per_cycle,
+                self.debited_in_cycle,
+                self.cycle_start_slot
+            );
+            return Err(TokenError::InsufficientFunds.into());
+        }
+        self.debited_in_cycle = debited;
+        Ok(())
+    }
+}
+
+pub const BRIDGE_SEED: &[u8] = b"bridge";
+pub const BRIDGE_CLAIM_SEED: &[u8] = b"bridge_claim";
+
+/// 推导铸币的跨链桥配置 PDA
+pub fn find_bridge_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BRIDGE_SEED, mint.as_ref()], program_id)
+}
+
+/// 推导入桥消息的领取 PDA
+pub fn find_bridge_claim_address(
+    mint: &Pubkey,
+    source_chain: u16,
+    emitter: &[u8; 32],
+    sequence: u64,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[BRIDGE_CLAIM_SEED, mint.as_ref(), &source_chain.to_le_bytes(), emitter, &sequence.to_le_bytes()],
+        program_id,
+    )
+}
+
+// 跨链桥配置
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct BridgeConfig {
+    pub is_initialized: bool, //1
+    pub mint: Pubkey, //32
+    pub verifier: Pubkey, //32 可签名 BridgeMint 的验证者
+    pub sequence: u64, //8 下一条出桥消息的序号
+}
+
+impl BridgeConfig {
+    pub const LEN: usize = 1 + 32 + 32 + 8;
+}
+
+// 已领取的入桥消息（存在即表示已铸造）
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct BridgeClaim {
+    pub is_initialized: bool, //1
+    pub recipient: Pubkey, //32
+    pub amount: u64, //8
+}
+
+impl BridgeClaim {
+    pub const LEN: usize = 1 + 32 + 8;
+}
+
+pub const MAX_GUARDIANS: usize = 5;
+pub const OWNER_RECOVERY_SEED: &[u8] = b"owner_recovery";
+/// 社交恢复的时间锁（约 3 天），给原所有者留出取消的时间
+pub const OWNER_RECOVERY_TIMELOCK_SLOTS: u64 = 3 * 216_000;
+
+/// 推导代币账户的所有者恢复 PDA
+pub fn find_owner_recovery_address(token_account: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[OWNER_RECOVERY_SEED, token_account.as_ref()], program_id)
+}
+
+/// 社交恢复监护人（代币账户扩展）
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct GuardianConfig {
+    pub threshold: u8,
+    pub guardians: Vec<Pubkey>,
+}
+
+impl Extension for GuardianConfig {
+    const TYPE: ExtensionType = ExtensionType::GuardianConfig;
+    const LEN: usize = 1 + 4 + 32 * MAX_GUARDIANS;
+}
+
+/// 携带 GuardianConfig 扩展的代币账户大小
+pub const TOKEN_ACCOUNT_WITH_GUARDIANS_LEN: usize = extension::account_len::<TokenAccount, GuardianConfig>();
+
+/// 活动时间（代币账户扩展）：钱包与审计方据此直接识别长期不活跃的账户
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct AccountActivity {
+    /// 开启记录时的 slot
+    pub created_at: u64,
+    /// 最近一次余额变化的 slot（由 TokenAccount::serialize 在余额变化时更新）
+    pub last_activity_slot: u64,
+}
+
+impl Extension for AccountActivity {
+    const TYPE: ExtensionType = ExtensionType::AccountActivity;
+    const LEN: usize = 8 + 8;
+}
+
+/// 携带 AccountActivity 扩展的代币账户大小
+pub const TOKEN_ACCOUNT_WITH_ACTIVITY_LEN: usize = extension::account_len::<TokenAccount, AccountActivity>();
+
+/// 激活状态（代币账户扩展）：只在铸币要求激活时由 InitializeAccount 写入，没有该扩展的账户不受限制
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct AccountActivation {
+    pub activated: bool,
+}
+
+impl Extension for AccountActivation {
+    const TYPE: ExtensionType = ExtensionType::AccountActivation;
+    const LEN: usize = 1;
+}
+
+/// 携带 AccountActivation 扩展的代币账户大小
+pub const TOKEN_ACCOUNT_WITH_ACTIVATION_LEN: usize = extension::account_len::<TokenAccount, AccountActivation>();
+
+/// 每个代币账户保留的转账备注条数
+pub const MEMO_LOG_CAPACITY: usize = 8;
+
+/// 一条转账备注记录（slot 为 0 表示空位）
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct MemoEntry {
+    pub hash: [u8; 32],
+    pub slot: u64,
+}
+
+/// 转账备注环形缓冲区（代币账户扩展）：客服可按备注哈希直接定位入账，无需索引完整历史
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Default)]
+pub struct MemoLog {
+    /// 下一条记录写入的位置
+    pub next: u8,
+    pub entries: [MemoEntry; MEMO_LOG_CAPACITY],
+}
+
+impl MemoLog {
+    /// 写入一条记录，缓冲区已满时覆盖最旧的一条
+    pub fn push(&mut self, entry: MemoEntry) {
+        let index = self.next as usize % MEMO_LOG_CAPACITY;
+        self.entries[index] = entry;
+        self.next = ((index + 1) % MEMO_LOG_CAPACITY) as u8;
+    }
+}
+
+impl Extension for MemoLog {
+    const TYPE: ExtensionType = ExtensionType::MemoLog;
+    const LEN: usize = 1 + (32 + 8) * MEMO_LOG_CAPACITY;
+}
+
+/// 携带 MemoLog 扩展的代币账户大小
+pub const TOKEN_ACCOUNT_WITH_MEMO_LOG_LEN: usize = extension::account_len::<TokenAccount, MemoLog>();
+
+// 待执行的所有者恢复
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct OwnerRecovery {
+    pub is_initialized: bool, //1
+    pub token_account: Pubkey, //32
+    pub new_owner: Pubkey, //32
+    pub payer: Pubkey, //32 支付租金、关闭时接收退款
+    pub executable_slot: u64, //8
+}
+
+impl OwnerRecovery {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8;
+}
+
+pub const COMPRESSED_TREE_SEED: &[u8] = b"compressed_tree";
+
+/// 推导压缩树的 authority PDA（托管代币账户的所有者）
+pub fn find_compressed_tree_authority(tree: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COMPRESSED_TREE_SEED, tree.as_ref()], program_id)
+}
+
+/// 构造 Transfer 指令，供其他程序 CPI 调用
+///
+/// `mint` 是源账户所属的铸币账户，必须提供：全局冻结、转账暂停与持有人计数都要从铸币读取。
+/// 速度跟踪 PDA、投票检查点、NFT 转账白名单等只在特定账户上需要的附加账户不在此列，
+/// 涉及这类账户的转账需要自行在 `accounts` 末尾追加（顺序见 TokenInstruction::Transfer）。
+///
+/// **注意：指令成功不代表代币已到账。** 源账户开启了速度限制且本次转出超过上限时，程序冻结源账户、
+/// 放弃本次转账并照常返回成功（否则冻结会随交易一起回滚）。CPI 调用方如果要按转账金额记账，
+/// 必须在调用后重新读取代币账户，按余额的实际变化记账。
+pub fn transfer_instruction(
+    program_id: &Pubkey,
+    source: &Pubkey,
+    destination: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = TokenInstruction::Transfer { amount }
+        .try_to_vec()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(*mint, false),
+        ],
+        data,
+    })
+}
+
+/// 检查代币账户的所有者是否为 `owner_program_id` 下由 `seeds`（不含 bump）推导出的 PDA
+pub fn is_owned_by_pda(token_account: &TokenAccount, seeds: &[&[u8]], owner_program_id: &Pubkey) -> bool {
+    let (pda, _) = Pubkey::find_program_address(seeds, owner_program_id);
+    token_account.owner == pda
+}
+
+/// 从 PDA 所有的代币账户转出，由调用方程序（`owner_program_id`）在自身指令中使用
+///
+/// PDA 没有私钥，运行时用 `signer_seeds`（必须以 bump 结尾）和调用方的程序 ID
+/// 重新推导地址，结果等于 `authority` 时才把它视为签名者。这里先在本地做同样的
+/// 推导，seeds 写错时直接返回 InvalidSeeds，而不是 CPI 后才得到难以排查的
+/// Unauthorized / PrivilegeEscalation。`authority` 本身无需在交易中签名。
+///
+/// **注意：返回 Ok 不代表代币已转出。** 与 `transfer_instruction` 相同，源账户触发速度限制时只会被冻结、
+/// 余额不变而 CPI 仍然成功；调用方需要比较转账前后的余额，不能直接按 `amount` 记账。
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_signed<'a>(
+    owner_program_id: &Pubkey,
+    token_program: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    amount: u64,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let expected = Pubkey::create_program_address(signer_seeds, owner_program_id)
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+    if expected != *authority.key {
+        msg!("Signer seeds derive {}, expected authority {}", expected, authority.key);
+        return Err(ProgramError::InvalidSeeds);
+    }
+    invoke_signed(
+        &transfer_instruction(token_program.key, source.key, destination.key, authority.key, mint.key, amount)?,
+        &[
+            source.clone(),
+            destination.clone(),
+            authority.clone(),
+            mint.clone(),
+            token_program.clone(),
+        ],
+        &[signer_seeds],
+    )
+}
+
+// 程序入口点（仅 program 特性导出；作为依赖库被其他程序或链下客户端引用时关闭默认特性或开启 client / no-entrypoint）
+#[cfg(all(feature = "program", not(feature = "no-entrypoint")))]
+solana_program::entrypoint!(process_instruction);
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    msg!("SPL Token Program: Processing instruction");
+    // legacy-codec 特性开启时按经典 SPL Token 线格式解析，no-alloc 特性开启时手动解析小指令，否则使用 Borsh
+    #[cfg(feature = "legacy-codec")]
+    let instruction = legacy::unpack(instruction_data)?;
+    #[cfg(all(feature = "no-alloc", not(feature = "legacy-codec")))]
+    let instruction = no_alloc::unpack(instruction_data)?;
+    #[cfg(not(any(feature = "legacy-codec", feature = "no-alloc")))]
+    let instruction = TokenInstruction::unpack(instruction_data)?;
+
+    match instruction {
+        TokenInstruction::InitializeMint { decimals, mint_authority, freeze_authority } => {
+            msg!("====InitializeMint====");
+            process_initialize_mint(program_id, accounts, decimals, mint_authority, freeze_authority)
+        }
+        TokenInstruction::InitializeAccount => {
+            msg!("====InitializeAccount====");
+            process_initialize_account(program_id, accounts, None)
+        }
+        TokenInstruction::MintTo { amount } => {
+            msg!("====MintTo====");
+            process_mint_to(&mut ProcessingContext::new(program_id, accounts), amount)
+        }
+        TokenInstruction::Transfer { amount } => {
+            msg!("====Transfer====");
+            process_transfer(&mut ProcessingContext::new(program_id, accounts), amount, None)
+        }
+        TokenInstruction::Burn { amount } => {
+            msg!("====Burn====");
+            process_burn(&mut ProcessingContext::new(program_id, accounts), amount)
+        }
+        TokenInstruction::SetMintAuthority { new_authority } => {
+            msg!("====SetMintAuthority====");
+            process_set_mint_authority(&mut ProcessingContext::new(program_id, accounts), new_authority)
+        }
+        TokenInstruction::InitializeCurve { curve } => {
+            msg!("====InitializeCurve====");
+            process_initialize_curve(program_id, accounts, curve)
+        }
+        TokenInstruction::BuyFromCurve { amount, max_lamports } => {
+            msg!("====BuyFromCurve====");
+            process_buy_from_curve(program_id, accounts, amount, max_lamports)
+        }
+        TokenInstruction::SellToCurve { amount, min_lamports } => {
+            msg!("====SellToCurve====");
+            process_sell_to_curve(program_id, accounts, amount, min_lamports)
+        }
+        TokenInstruction::ConfigureOracle { price_feed, max_staleness_slots, collateral_ratio_bps } => {
+            msg!("====ConfigureOracle====");
+            process_configure_oracle(program_id, accounts, price_feed, max_staleness_slots, collateral_ratio_bps)
+        }
+        TokenInstruction::MintAgainstCollateral { collateral_lamports, min_amount } => {
+            msg!("====MintAgainstCollateral====");
+            process_mint_against_collateral(program_id, accounts, collateral_lamports, min_amount)
+        }
+        TokenInstruction::EnableRebase => {
+            msg!("====EnableRebase====");
+            process_enable_rebase(program_id, accounts)
+        }
+        TokenInstruction::Rebase { numerator, denominator } => {
+            msg!("====Rebase====");
+            process_rebase(program_id, accounts, numerator, denominator)
+        }
+        TokenInstruction::FundDistribution { distribution_id, snapshot_root, snapshot_supply, amount } => {
+            msg!("====FundDistribution====");
+            process_fund_distribution(program_id, accounts, distribution_id, snapshot_root, snapshot_supply, amount)
+        }
+        TokenInstruction::ClaimDividend { snapshot_balance, proof } => {
+            msg!("====ClaimDividend====");
+            process_claim_dividend(&mut ProcessingContext::new(program_id, accounts), snapshot_balance, proof)
+        }
+        TokenInstruction::DelegateVotes { delegatee } => {
+            msg!("====DelegateVotes====");
+            process_delegate_votes(program_id, accounts, delegatee)
+        }
+        TokenInstruction::GetVotesAt { slot } => {
+            msg!("====GetVotesAt====");
+            process_get_votes_at(program_id, accounts, slot)
+        }
+        TokenInstruction::TopUpRent { lamports } => {
+            msg!("====TopUpRent====");
+            process_top_up_rent(program_id, accounts, lamports)
+        }
+        TokenInstruction::MintToNewAccount { amount } => {
+            msg!("====MintToNewAccount====");
+            process_mint_to_new_account(program_id, accounts, amount)
+        }
+        TokenInstruction::BurnWithReceipt { amount, receipt_id } => {
+            msg!("====BurnWithReceipt====");
+            process_burn_with_receipt(program_id, accounts, amount, receipt_id)
+        }
+        TokenInstruction::InitializeNftMint { mint_authority, freeze_authority } => {
+            msg!("====InitializeNftMint====");
+            process_initialize_nft_mint(program_id, accounts, mint_authority, freeze_authority)
+        }
+        TokenInstruction::CreateMetadata { args, is_mutable } => {
+            msg!("====CreateMetadata====");
+            process_create_metadata(program_id, accounts, args, is_mutable)
+        }
+        TokenInstruction::CreateMasterEdition { max_supply } => {
+            msg!("====CreateMasterEdition====");
+            process_create_master_edition(program_id, accounts, max_supply)
+        }
+        TokenInstruction::PrintEdition => {
+            msg!("====PrintEdition====");
+            process_print_edition(program_id, accounts)
+        }
+        TokenInstruction::SetTransferAllowlist { programs } => {
+            msg!("====SetTransferAllowlist====");
+            process_set_transfer_allowlist(program_id, accounts, programs)
+        }
+        TokenInstruction::SetVelocityLimit { max_amount, window_slots } => {
+            msg!("====SetVelocityLimit====");
+            process_set_velocity_limit(program_id, accounts, max_amount, window_slots)
+        }
+        TokenInstruction::ThawAccount { reason_code } => {
+            msg!("====ThawAccount====");
+            process_thaw_account(program_id, accounts, reason_code)
+        }
+        TokenInstruction::QueueMint { amount, destination } => {
+            msg!("====QueueMint====");
+            process_queue_mint(program_id, accounts, amount, destination)
+        }
+        TokenInstruction::ExecuteQueuedMint => {
+            msg!("====ExecuteQueuedMint====");
+            process_execute_queued_mint(program_id, accounts)
+        }
+        TokenInstruction::CancelQueuedMint => {
+            msg!("====CancelQueuedMint====");
+            process_cancel_queued_mint(program_id, accounts)
+        }
+        TokenInstruction::InitializeTreasury => {
+            msg!("====InitializeTreasury====");
+            process_initialize_treasury(program_id, accounts)
+        }
+        TokenInstruction::BurnTreasury => {
+            msg!("====BurnTreasury====");
+            process_burn_treasury(program_id, accounts)
+        }
+        TokenInstruction::PlaceOrder { give_amount, want_mint, want_amount } => {
+            msg!("====PlaceOrder====");
+            process_place_order(program_id, accounts, give_amount, want_mint, want_amount)
+        }
+        TokenInstruction::FillOrder => {
+            msg!("====FillOrder====");
+            process_fill_order(program_id, accounts)
+        }
+        TokenInstruction::CancelOrder => {
+            msg!("====CancelOrder====");
+            process_cancel_order(program_id, accounts)
+        }
+        TokenInstruction::CreateAuction { start_price, floor_price, decay, supply } => {
+            msg!("====CreateAuction====");
+            process_create_auction(program_id, accounts, start_price, floor_price, decay, supply)
+        }
+        TokenInstruction::Purchase { amount, max_lamports } => {
+            msg!("====Purchase====");
+            process_purchase(program_id, accounts, amount, max_lamports)
+        }
+        TokenInstruction::InitializeSale { price_lamports_per_token, cap } => {
+            msg!("====InitializeSale====");
+            process_initialize_sale(program_id, accounts, price_lamports_per_token, cap)
+        }
+        TokenInstruction::BuyTokens { amount } => {
+            msg!("====BuyTokens====");
+            process_buy_tokens(program_id, accounts, amount)
+        }
+        TokenInstruction::WithdrawProceeds => {
+            msg!("====WithdrawProceeds====");
+            process_withdraw_proceeds(program_id, accounts)
+        }
+        TokenInstruction::InitializeShareVault => {
+            msg!("====InitializeShareVault====");
+            process_initialize_share_vault(program_id, accounts)
+        }
+        TokenInstruction::Deposit { amount } => {
+            msg!("====Deposit====");
+            process_deposit(program_id, accounts, amount)
+        }
+        TokenInstruction::Withdraw { shares } => {
+            msg!("====Withdraw====");
+            process_withdraw(program_id, accounts, shares)
+        }
+        TokenInstruction::DepositGoverningTokens { realm, amount } => {
+            msg!("====DepositGoverningTokens====");
+            process_deposit_governing_tokens(program_id, accounts, realm, amount)
+        }
+        TokenInstruction::WithdrawGoverningTokens { realm, amount } => {
+            msg!("====WithdrawGoverningTokens====");
+            process_withdraw_governing_tokens(program_id, accounts, realm, amount)
+        }
+        TokenInstruction::InitializeEmergencyConfig { emergency_admin } => {
+            msg!("====InitializeEmergencyConfig====");
+            process_initialize_emergency_config(program_id, accounts, emergency_admin)
+        }
+        TokenInstruction::ProposeAuthorityRecovery { authority_type, new_authority } => {
+            msg!("====ProposeAuthorityRecovery====");
+            process_propose_authority_recovery(program_id, accounts, authority_type, new_authority)
+        }
+        TokenInstruction::ExecuteAuthorityRecovery => {
+            msg!("====ExecuteAuthorityRecovery====");
+            process_execute_authority_recovery(program_id, accounts)
+        }
+        TokenInstruction::CancelAuthorityRecovery => {
+            msg!("====CancelAuthorityRecovery====");
+            process_cancel_authority_recovery(program_id, accounts)
+        }
+        TokenInstruction::EnableAutoClose { close_destination } => {
+            msg!("====EnableAutoClose====");
+            process_enable_auto_close(&mut ProcessingContext::new(program_id, accounts), close_destination)
+        }
+        TokenInstruction::CloseEmptyMint => {
+            msg!("====CloseEmptyMint====");
+            process_close_empty_mint(&mut ProcessingContext::new(program_id, accounts))
+        }
+        TokenInstruction::TransferSplit { total, shares } => {
+            msg!("====TransferSplit====");
+            process_transfer_split(&mut ProcessingContext::new(program_id, accounts), total, &shares)
+        }
+        TokenInstruction::CreateHtlc { hash, amount, timeout } => {
+            msg!("====CreateHtlc====");
+            process_create_htlc(program_id, accounts, hash, amount, timeout)
+        }
+        TokenInstruction::RedeemHtlc { preimage } => {
+            msg!("====RedeemHtlc====");
+            process_redeem_htlc(program_id, accounts, &preimage)
+        }
+        TokenInstruction::RefundHtlc => {
+            msg!("====RefundHtlc====");
+            process_refund_htlc(program_id, accounts)
+        }
+        TokenInstruction::AtomicSwap { amount_a, amount_b } => {
+            msg!("====AtomicSwap====");
+            process_atomic_swap(&mut ProcessingContext::new(program_id, accounts), amount_a, amount_b)
+        }
+        TokenInstruction::PermitApprove { delegate, amount, nonce, deadline } => {
+            msg!("====PermitApprove====");
+            process_permit_approve(program_id, accounts, delegate, amount, nonce, deadline)
+        }
+        TokenInstruction::TransferFrom { amount } => {
+            msg!("====TransferFrom====");
+            process_transfer_from(&mut ProcessingContext::new(program_id, accounts), amount)
+        }
+        TokenInstruction::TransferWithPermit { amount, tip, nonce, deadline } => {
+            msg!("====TransferWithPermit====");
+            process_transfer_with_permit(&mut ProcessingContext::new(program_id, accounts), amount, tip, nonce, deadline)
+        }
+        TokenInstruction::CreateSession { key, max_amount, expires_at_slot } => {
+            msg!("====CreateSession====");
+            process_create_session(program_id, accounts, key, max_amount, expires_at_slot)
+        }
+        TokenInstruction::RevokeSession => {
+            msg!("====RevokeSession====");
+            process_revoke_session(program_id, accounts)
+        }
+        TokenInstruction::SessionTransfer { amount } => {
+            msg!("====SessionTransfer====");
+            process_session_transfer(&mut ProcessingContext::new(program_id, accounts), amount)
+        }
+        TokenInstruction::SetGuardians { guardians, threshold } => {
+            msg!("====SetGuardians====");
+            process_set_guardians(program_id, accounts, guardians, threshold)
+        }
+        TokenInstruction::ProposeOwnerRecovery { new_owner } => {
+            msg!("====ProposeOwnerRecovery====");
+            process_propose_owner_recovery(program_id, accounts, new_owner)
+        }
+        TokenInstruction::CancelOwnerRecovery => {
+            msg!("====CancelOwnerRecovery====");
+            process_cancel_owner_recovery(program_id, accounts)
+        }
+        TokenInstruction::RecoverOwner => {
+            msg!("====RecoverOwner====");
+            process_recover_owner(program_id, accounts)
+        }
+        TokenInstruction::EnableHolderCount => {
+            msg!("====EnableHolderCount====");
+            process_enable_holder_count(&mut ProcessingContext::new(program_id, accounts))
+        }
+        TokenInstruction::EnableActivityTracking => {
+            msg!("====EnableActivityTracking====");
+            process_enable_activity_tracking(program_id, accounts)
+        }
+        TokenInstruction::EnableMemoLog => {
+            msg!("====EnableMemoLog====");
+            process_enable_memo_log(program_id, accounts)
+        }
+        TokenInstruction::TransferWithMemo { amount, memo } => {
+            msg!("====TransferWithMemo====");
+            process_transfer(&mut ProcessingContext::new(program_id, accounts), amount, Some(&memo))
+        }
+        TokenInstruction::SweepAll { source_count } => {
+            msg!("====SweepAll====");
+            process_sweep_all(&mut ProcessingContext::new(program_id, accounts), source_count)
+        }
+        TokenInstruction::SettleBatch { deltas } => {
+            msg!("====SettleBatch====");
+            process_settle_batch(&mut ProcessingContext::new(program_id, accounts), &deltas)
+        }
+        TokenInstruction::InitializeCompressedTree { depth } => {
+            msg!("====InitializeCompressedTree====");
+            process_initialize_compressed_tree(program_id, accounts, depth)
+        }
+        TokenInstruction::Compress { recipient, amount } => {
+            msg!("====Compress====");
+            process_compress(&mut ProcessingContext::new(program_id, accounts), recipient, amount)
+        }
+        TokenInstruction::CompressedTransfer { leaf, root, proof, recipient, amount } => {
+            msg!("====CompressedTransfer====");
+            process_compressed_transfer(
+                &mut ProcessingContext::new(program_id, accounts),
+                leaf,
+                root,
+                &proof,
+                recipient,
+                amount,
+            )
+        }
+        TokenInstruction::Decompress { leaf, root, proof } => {
+            msg!("====Decompress====");
+            process_decompress(&mut ProcessingContext::new(program_id, accounts), leaf, root, &proof)
+        }
+        TokenInstruction::ConfigureBridge { verifier } => {
+            msg!("====ConfigureBridge====");
+            process_configure_bridge(program_id, accounts, verifier)
+        }
+        TokenInstruction::BridgeBurn { target_chain, target_address, amount } => {
+            msg!("====BridgeBurn====");
+            process_bridge_burn(&mut ProcessingContext::new(program_id, accounts), target_chain, target_address, amount)
+        }
+        TokenInstruction::BridgeMint { source_chain, emitter, sequence, amount } => {
+            msg!("====BridgeMint====");
+            process_bridge_mint(program_id, accounts, source_chain, emitter, sequence, amount)
+        }
+        TokenInstruction::RescaleMint { new_decimals } => {
+            msg!("====RescaleMint====");
+            process_rescale_mint(&mut ProcessingContext::new(program_id, accounts), new_decimals)
+        }
+        TokenInstruction::GlobalFreeze => {
+            msg!("====GlobalFreeze====");
+            process_set_global_freeze(&mut ProcessingContext::new(program_id, accounts), true)
+        }
+        TokenInstruction::GlobalThaw => {
+            msg!("====GlobalThaw====");
+            process_set_global_freeze(&mut ProcessingContext::new(program_id, accounts), false)
+        }
+        TokenInstruction::FreezeAccount { thaw_at_slot, reason_code } => {
+            msg!("====FreezeAccount====");
+            process_freeze_account(program_id, accounts, thaw_at_slot, reason_code)
+        }
+        TokenInstruction::SetAuthority { authority_type, new_authority } => {
+            msg!("====SetAuthority====");
+            process_set_authority(&mut ProcessingContext::new(program_id, accounts), authority_type, new_authority)
+        }
+        TokenInstruction::SetFreezeExempt { exempt } => {
+            msg!("====SetFreezeExempt====");
+            process_set_freeze_exempt(program_id, accounts, exempt)
+        }
+        TokenInstruction::CreateConditionalTransfer { arbiter, amount } => {
+            msg!("====CreateConditionalTransfer====");
+            process_create_conditional_transfer(program_id, accounts, arbiter, amount)
+        }
+        TokenInstruction::ReleaseConditionalTransfer => {
+            msg!("====ReleaseConditionalTransfer====");
+            process_settle_conditional_transfer(program_id, accounts, true)
+        }
+        TokenInstruction::RefundConditionalTransfer => {
+            msg!("====RefundConditionalTransfer====");
+            process_settle_conditional_transfer(program_id, accounts, false)
+        }
+        TokenInstruction::ApproveMulti { approvals } => {
+            msg!("====ApproveMulti====");
+            process_approve_multi(program_id, accounts, &approvals)
+        }
+        TokenInstruction::SetAccountLabel { label } => {
+            msg!("====SetAccountLabel====");
+            process_set_account_label(&mut ProcessingContext::new(program_id, accounts), label)
+        }
+        TokenInstruction::CreateMintWithSeed { seed, decimals, freeze_authority } => {
+            msg!("====CreateMintWithSeed====");
+            process_create_mint_with_seed(program_id, accounts, &seed, decimals, freeze_authority)
+        }
+        TokenInstruction::GetExtensionTypes { is_mint } => {
+            msg!("====GetExtensionTypes====");
+            process_get_extension_types(program_id, accounts, is_mint)
+        }
+        TokenInstruction::InitializeAccountWithOwnerCheck { owner_curve } => {
+            msg!("====InitializeAccountWithOwnerCheck====");
+            process_initialize_account(program_id, accounts, Some(owner_curve))
+        }
+        TokenInstruction::Crank { items } => {
+            msg!("====Crank====");
+            process_crank(&mut ProcessingContext::new(program_id, accounts), &items)
+        }
+        TokenInstruction::CreatePreAuthorization { merchant, amount_per_cycle, cycle_slots } => {
+            msg!("====CreatePreAuthorization====");
+            process_create_pre_authorization(program_id, accounts, merchant, amount_per_cycle, cycle_slots)
+        }
+        TokenInstruction::Debit { amount } => {
+            msg!("====Debit====");
+            process_debit(&mut ProcessingContext::new(program_id, accounts), amount)
+        }
+        TokenInstruction::ClosePreAuthorization => {
+            msg!("====ClosePreAuthorization====");
+            process_close_pre_authorization(program_id, accounts)
+        }
+        TokenInstruction::InitializeSavingsVault { yield_authority } => {
+            msg!("====InitializeSavingsVault====");
+            process_initialize_savings_vault(program_id, accounts, yield_authority)
+        }
+        TokenInstruction::FundYield { amount } => {
+            msg!("====FundYield====");
+            process_fund_yield(program_id, accounts, amount)
+        }
+        TokenInstruction::DepositSavings { amount } => {
+            msg!("====DepositSavings====");
+            process_deposit_savings(&mut ProcessingContext::new(program_id, accounts), amount)
+        }
+        TokenInstruction::WithdrawSavings { shares } => {
+            msg!("====WithdrawSavings====");
+            process_withdraw_savings(&mut ProcessingContext::new(program_id, accounts), shares)
+        }
+        TokenInstruction::InitializeEmissions { tokens_per_slot, start_slot, end_slot, recipients } => {
+            msg!("====InitializeEmissions====");
+            process_initialize_emissions(program_id, accounts, tokens_per_slot, start_slot, end_slot, recipients)
+        }
+        TokenInstruction::DistributeEmissions => {
+            msg!("====DistributeEmissions====");
+            process_distribute_emissions(&mut ProcessingContext::new(program_id, accounts))
+        }
+        TokenInstruction::EnablePointsExpiry { lifetime_slots } => {
+            msg!("====EnablePointsExpiry====");
+            process_enable_points_expiry(&mut ProcessingContext::new(program_id, accounts), lifetime_slots)
+        }
+        TokenInstruction::ExpirePoints => {
+            msg!("====ExpirePoints====");
+            process_expire_points(&mut ProcessingContext::new(program_id, accounts))
+        }
+        TokenInstruction::SetDormancyPolicy { recovery_vault, dormant_epochs, notice_epochs } => {
+            msg!("====SetDormancyPolicy====");
+            process_set_dormancy_policy(program_id, accounts, recovery_vault, dormant_epochs, notice_epochs)
+        }
+        TokenInstruction::WarnDormant => {
+            msg!("====WarnDormant====");
+            process_warn_dormant(program_id, accounts)
+        }
+        TokenInstruction::SweepDormant => {
+            msg!("====SweepDormant====");
+            process_sweep_dormant(&mut ProcessingContext::new(program_id, accounts))
+        }
+        TokenInstruction::UpdateAttestation { reserves } => {
+            msg!("====UpdateAttestation====");
+            process_update_attestation(program_id, accounts, reserves)
+        }
+        TokenInstruction::VerifySupplyBacked => {
+            msg!("====VerifySupplyBacked====");
+            process_verify_supply_backed(program_id, accounts)
+        }
+        TokenInstruction::InitializeConversion { rate } => {
+            msg!("====InitializeConversion====");
+            process_initialize_conversion(program_id, accounts, rate)
+        }
+        TokenInstruction::ConvertMint { amount } => {
+            msg!("====ConvertMint====");
+            process_convert_mint(&mut ProcessingContext::new(program_id, accounts), amount)
+        }
+        TokenInstruction::Rebalance { amount } => {
+            msg!("====Rebalance====");
+            process_rebalance(&mut ProcessingContext::new(program_id, accounts), amount)
+        }
+        TokenInstruction::SetPauseFlags { pause_transfers, pause_minting, pause_burning } => {
+            msg!("====SetPauseFlags====");
+            process_set_pause_flags(
+                &mut ProcessingContext::new(program_id, accounts),
+                pause_transfers,
+                pause_minting,
+                pause_burning,
+            )
+        }
+        TokenInstruction::SetActivationRequired { required } => {
+            msg!("====SetActivationRequired====");
+            process_set_activation_required(&mut ProcessingContext::new(program_id, accounts), required)
+        }
+        TokenInstruction::ActivateAccount => {
+            msg!("====ActivateAccount====");
+            process_activate_account(&mut ProcessingContext::new(program_id, accounts))
+        }
+    }
+}
+
+/// 初始化铸币账户
+fn process_initialize_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    decimals: u8,
+    mint_authority: Pubkey,
+    freeze_authority: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+   
+    // 验证账户所有权
+    validators::assert_owned_by(mint_account, program_id)?;
+    
+    // 检查租金豁免
+    let rent = &Rent::from_account_info(rent_sysvar_account)?;
+    validators::assert_rent_exempt(rent, mint_account)?;
+    // 重复初始化会覆盖权限与供应量
+    if Mint::deserialize(&mint_account.data.borrow())?.is_initialized {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    
+    // 初始化铸币账户
+    let mut mint_data = mint_account.data.borrow_mut();
+    let mint = Mint::new(decimals, mint_authority, freeze_authority);
+    mint.serialize(&mut mint_data[..])?;
+    
+    msg!("Mint initialized with authority: {}", mint_authority);
+    Ok(())
+}
+
+/// 在 (铸币权限, 种子) 推导的 PDA 上创建并初始化铸币
+fn process_create_mint_with_seed(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    seed: &str,
+    decimals: u8,
+    freeze_authority: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(authority_account)?;
+    if seed.len() > MAX_SEED_LEN {
+        msg!("Mint seed is {} bytes, max {}", seed.len(), MAX_SEED_LEN);
+        return Err(ProgramError::InvalidArgument);
+    }
+    let (mint_key, bump) = find_mint_address(authority_account.key, seed, program_id);
+    if mint_key != *mint_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    create_pda_account(
+        authority_account,
+        mint_account,
+        system_program_account,
+        program_id,
+        Mint::LEN,
+        &[MINT_SEED, authority_account.key.as_ref(), seed.as_bytes(), &[bump]],
+    )?;
+    let mint = Mint::new(decimals, *authority_account.key, freeze_authority);
+    mint.serialize(&mut mint_account.data.borrow_mut())?;
+
+    msg!("Mint {} created with seed {:?} for authority {}", mint_key, seed, authority_account.key);
+    Ok(())
+}
+
+/// 初始化代币账户
+fn process_initialize_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    owner_curve: Option<OwnerCurve>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+    
+    // 验证账户所有权
+    validators::assert_owned_by(token_account, program_id)?;
+    if let Some(owner_curve) = owner_curve {
+        validators::assert_owner_curve(owner_account.key, owner_curve)?;
+    }
+    
+    // 检查租金豁免
+    let rent = &Rent::from_account_info(rent_sysvar_account)?;
+    validators::assert_rent_exempt(rent, token_account)?;
+    // 重复初始化会覆盖所有者并清空余额
+    if TokenAccount::deserialize(&token_account.data.borrow())?.is_initialized() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    
+    // 初始化代币账户
+    let mint = LoadedMint::load(program_id, mint_account)?;
+    let mut token_data = token_account.data.borrow_mut();
+    let mut token_acc = TokenAccount::new(*mint_account.key, *owner_account.key);
+    token_acc.set_nft(mint.state.is_nft);
+    token_acc.serialize(&mut token_data[..])?;
+    // 调整过精度的铸币：新账户余额为 0，直接处于当前纪元
+    if let Some(rescale) = extension::get_extension::<Mint, DecimalsRescale>(&mint_account.data.borrow())? {
+        extension::init_extension::<TokenAccount, _>(&mut token_data[..], &RescaleEpoch { epoch: rescale.epoch })?;
+    }
+    // 积分铸币：新账户从空的批次开始
+    if extension::get_extension::<Mint, PointsConfig>(&mint_account.data.borrow())?.is_some() {
+        extension::init_extension::<TokenAccount, _>(&mut token_data[..], &PointsLots::default())?;
+    }
+    // 休眠账户政策：新账户从创建时起记录活动时间
+    if extension::get_extension::<Mint, DormancyPolicy>(&mint_account.data.borrow())?.is_some() {
+        let slot = Clock::get()?.slot;
+        let activity = AccountActivity { created_at: slot, last_activity_slot: slot };
+        extension::init_extension::<TokenAccount, _>(&mut token_data[..], &activity)?;
+    }
+    // 激活要求：新账户在所有者激活前不能收款
+    let policy = extension::get_extension::<Mint, ActivationPolicy>(&mint_account.data.borrow())?;
+    if policy.is_some_and(|policy| policy.required) {
+        extension::init_extension::<TokenAccount, _>(&mut token_data[..], &AccountActivation { activated: false })?;
+    }
+    
+    msg!("Token account initialized for owner: {}", owner_account.key);
+    Ok(())
+}
+
+/// 铸造代币
+fn process_mint_to(ctx: &mut ProcessingContext, amount: u64) -> ProgramResult {
+    let mut mint = ctx.next_mint()?;
+    let token_account = ctx.next_account()?;
+    let mint_authority_account = ctx.next_account()?;
+
+    // 验证铸币权限
+    mint.check_mint_authority(mint_authority_account)?;
+    // 更新铸币账户
+    check_not_paused(mint.info, PausedOperation::Minting)?;
+    mint.state.mint_supply(amount)?;
+    mint.save()?;
+    // 更新代币账户
+    let token = LoadedTokenAccount::load(ctx.program_id, token_account)?;
+    credit_minted(ctx.program_id, &mint, token, amount, &mut ctx.accounts)?;
+
+    msg!("Minted {} tokens to {}", amount, token_account.key);
+    Ok(())
+}
+
+/// 把新铸造的 amount 记入代币账户（铸币供应量由调用方更新），同步持有人计数、积分批次与投票权
+/// account_info_iter 为剩余的投票检查点账户，逐个读取账户的处理函数直接传入自己的迭代器
+fn credit_minted<'a, 'b: 'a, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    program_id: &Pubkey,
+    mint: &LoadedMint,
+    mut token: LoadedTokenAccount,
+    amount: u64,
+    account_info_iter: &mut I,
+) -> ProgramResult {
+    validators::assert_mint_matches(&token.state.mint, mint.info.key)?;
+    check_activated(token.info)?;
+    sync_rescale(mint.info, &mut token)?;
+    let balance_before = token.state.amount;
+    token.state.amount = balance::credit(token.state.amount, amount)?;
+    if update_holder_count(mint.info, &[(balance_before, token.state.amount)])? || has_global_freeze(mint.info)? {
+        token.state.set_holder_tracked(true);
+    }
+    token.save()?;
+    record_points_lot(mint.info, token.info, amount)?;
+    move_delegated_votes(program_id, mint.info.key, None, token.state.vote_delegate, amount, account_info_iter)
+}
+
+/// 转移代币（memo 不为空时记入双方的 MemoLog）
+fn process_transfer(ctx: &mut ProcessingContext, amount: u64, memo: Option<&[u8]>) -> ProgramResult {
+    let source_account = ctx.next_account()?;
+    let dest_account = ctx.next_account()?;
+    // 验证所有者权限
+    let owner_account = ctx.next_signer()?;
+
+    let mut source = LoadedTokenAccount::load(ctx.program_id, source_account)?;
+    source.check_owner(owner_account)?;
+    let mut dest = LoadedTokenAccount::load(ctx.program_id, dest_account)?;
+    validators::assert_mint_matches(&dest.state.mint, &source.state.mint)?;
+    check_same_rescale_epoch(source_account, dest_account)?;
+    check_activated(dest_account)?;
+    hooks::run_pre_transfer(&TransferInfo {
+        source_key: source_account.key,
+        destination_key: dest_account.key,
+        source: &source.state,
+        destination: &dest.state,
+        amount,
+    })?;
+
+    let mint_account = next_source_mint(ctx, &source)?;
+    // 速度限制：超限时冻结源账户并放弃本次转账
+    if !record_velocity(ctx, &mut source, amount)? {
+        return Ok(());
+    }
+
+    // 更新源账户与目标账户（两者相同时余额不变）
+    if source_account.key != dest_account.key {
+        move_balance(&mut source, &mut dest, amount, mint_account)?;
+        source.save()?;
+        dest.save()?;
+    }
+    move_delegated_votes(
+        ctx.program_id,
+        &source.state.mint,
+        source.state.vote_delegate,
+        dest.state.vote_delegate,
+        amount,
+        &mut ctx.accounts,
+    )?;
+    if source.state.is_nft() {
+        check_transfer_allowed(ctx.program_id, &source.state.mint, &mut ctx.accounts)?;
+    }
+    if let Some(memo) = memo {
+        let entry = MemoEntry { hash: hashv(&[memo]).to_bytes(), slot: Clock::get()?.slot };
+        record_memo(source_account, entry)?;
+        if dest_account.key != source_account.key {
+            record_memo(dest_account, entry)?;
+        }
+    }
+
+    hooks::run_post_transfer(&TransferInfo {
+        source_key: source_account.key,
+        destination_key: dest_account.key,
+        source: &source.state,
+        destination: &dest.state,
+        amount,
+    })?;
+    emit_transfer_references(ctx, source_account.key, dest_account.key, &source.state.mint, amount)
+}
+
+/// 可选账户读完后剩下的账户都是引用键，存在时发出 TransferEvent::Referenced
+fn emit_transfer_references(
+    ctx: &mut ProcessingContext,
+    source: &Pubkey,
+    destination: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    let references: Vec<Pubkey> = ctx.accounts.by_ref().map(|account| *account.key).collect();
+    if references.is_empty() {
+        return Ok(());
+    }
+    if references.len() > MAX_TRANSFER_REFERENCES {
+        msg!("{} reference keys, at most {} allowed", references.len(), MAX_TRANSFER_REFERENCES);
+        return Err(ProgramError::InvalidArgument);
+    }
+    TransferEvent::Referenced {
+        mint: *mint,
+        source: *source,
+        destination: *destination,
+        amount,
+        references,
+        slot: Clock::get()?.slot,
+    }
+    .emit()
+}
+
+/// 代币账户开启了 MemoLog 时写入一条备注记录
+fn record_memo(token_account: &AccountInfo, entry: MemoEntry) -> ProgramResult {
+    let mut data = token_account.data.borrow_mut();
+    if let Some(mut log) = extension::get_extension::<TokenAccount, MemoLog>(&data)? {
+        log.push(entry);
+        extension::init_extension::<TokenAccount, _>(&mut data, &log)?;
+    }
+    Ok(())
+}
+
+/// 积分铸币：把本次发放记为一批在 lifetime_slots 后过期的积分
+fn record_points_lot(mint_account: &AccountInfo, token_account: &AccountInfo, amount: u64) -> ProgramResult {
+    let config = match extension::get_extension::<Mint, PointsConfig>(&mint_account.data.borrow())? {
+        Some(config) => config,
+        None => return Ok(()),
+    };
+    let mut data = token_account.data.borrow_mut();
+    let mut lots = extension::get_extension::<TokenAccount, PointsLots>(&data)?.ok_or_else(|| {
+        msg!("Points account {} has no PointsLots extension", token_account.key);
+        ProgramError::InvalidAccountData
+    })?;
+    let expires_at_slot = Clock::get()?.slot.saturating_add(config.lifetime_slots);
+    lots.add(amount, expires_at_slot)?;
+    extension::init_extension::<TokenAccount, _>(&mut data, &lots)
+}
+
+/// 源账户开启速度限制时记录转出金额（跟踪 PDA 从上下文读取）；
+/// 超限时冻结源账户并返回 false，调用方应放弃本次转账
+fn record_velocity(ctx: &mut ProcessingContext, source: &mut LoadedTokenAccount, amount: u64) -> Result<bool, ProgramError> {
+    if !source.state.velocity_limited() {
+        return Ok(true);
+    }
+    let tracker_account = ctx.next_account()?;
+    let mut tracker = load_velocity_tracker(ctx.program_id, source.info.key, tracker_account)?;
+    let within_limit = tracker.record(Clock::get()?.slot, amount);
+    pack_state(&tracker, &mut tracker_account.data.borrow_mut())?;
+    if !within_limit {
+        source.state.set_frozen(true);
+        source.save()?;
+        set_scheduled_thaw(source.info, None)?;
+        AuthorityEvent::AccountFrozen {
+            mint: source.state.mint,
+            account: *source.info.key,
+            reason_code: FREEZE_REASON_VELOCITY_LIMIT,
+            slot: Clock::get()?.slot,
+        }
+        .emit()?;
+        msg!(
+            "Velocity limit exceeded: {} transferred within {} slots (max {}), account {} frozen",
+            tracker.window_amount,
+            tracker.window_slots,
+            tracker.max_amount,
+            source.info.key
+        );
+    }
+    Ok(within_limit)
+}
+
+/// 从上下文读取源账户所属的铸币账户，铸币处于全局冻结或暂停转账时报错
+fn next_source_mint<'a, 'b>(
+    ctx: &mut ProcessingContext<'a, 'b>,
+    source: &LoadedTokenAccount,
+) -> Result<&'a AccountInfo<'b>, ProgramError> {
+    next_transfer_mint(&mut ctx.accounts, source.info, &source.state.mint)
+}
+
+/// 读取转出账户所属的铸币账户并检查全局冻结与转账暂停（供逐个读取账户的处理函数使用）
+/// 铸币的扩展只能从铸币账户读到，因此所有转出余额的指令都必须附带铸币账户
+fn next_transfer_mint<'a, 'b: 'a, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    account_info_iter: &mut I,
+    source_account: &AccountInfo,
+    mint: &Pubkey,
+) -> Result<&'a AccountInfo<'b>, ProgramError> {
+    let mint_account = next_account_info(account_info_iter)?;
+    validators::assert_mint_matches(mint_account.key, mint)?;
+    check_global_freeze(mint_account, source_account)?;
+    check_not_paused(mint_account, PausedOperation::Transfers)?;
+    Ok(mint_account)
+}
+
+/// 铸币带有全局冻结或分项暂停扩展（无论当前是否生效），收款账户需打上 HOLDER_TRACKED
+fn has_global_freeze(mint_account: &AccountInfo) -> Result<bool, ProgramError> {
+    let data = mint_account.data.borrow();
+    Ok(extension::get_extension::<Mint, GlobalFreezeState>(&data)?.is_some()
+        || extension::get_extension::<Mint, PauseState>(&data)?.is_some())
+}
+
+/// PauseState 中可以单独暂停的操作
+#[derive(Debug, Clone, Copy)]
+enum PausedOperation {
+    Transfers,
+    Minting,
+    Burning,
+}
+
+/// 铸币暂停了该项操作时报错；没有 PauseState 扩展时不受限制
+fn check_not_paused(mint_account: &AccountInfo, operation: PausedOperation) -> ProgramResult {
+    let Some(state) = extension::get_extension::<Mint, PauseState>(&mint_account.data.borrow())? else {
+        return Ok(());
+    };
+    let paused = match operation {
+        PausedOperation::Transfers => state.pause_transfers,
+        PausedOperation::Minting => state.pause_minting,
+        PausedOperation::Burning => state.pause_burning,
+    };
+    if paused {
+        msg!("{:?} are paused for mint {}", operation, mint_account.key);
+        return Err(TokenError::OperationPaused.into());
+    }
+    Ok(())
+}
+
+/// 铸币处于全局冻结时拒绝转账与销毁，带 FreezeExempt 豁免的代币账户除外
+fn check_global_freeze(mint_account: &AccountInfo, token_account: &AccountInfo) -> ProgramResult {
+    let state = extension::get_extension::<Mint, GlobalFreezeState>(&mint_account.data.borrow())?;
+    if state.is_some_and(|state| state.frozen) {
+        let exempt = extension::get_extension::<TokenAccount, FreezeExempt>(&token_account.data.borrow())?;
+        if exempt.is_some_and(|exempt| exempt.exempt) {
+            return Ok(());
+        }
+        msg!("Mint {} is globally frozen", mint_account.key);
+        return Err(TokenError::AccountFrozen.into());
+    }
+    Ok(())
+}
+
+/// 按各账户余额的 (变化前, 变化后) 更新铸币的持有人计数；铸币未开启计数时返回 false
+fn update_holder_count(mint_account: &AccountInfo, changes: &[(u64, u64)]) -> Result<bool, ProgramError> {
+    let mut holders = match extension::get_extension::<Mint, HolderCount>(&mint_account.data.borrow())? {
+        Some(holders) => holders,
+        None => return Ok(false),
+    };
+    for &(before, after) in changes {
+        holders.count = balance::holder_count_after(holders.count, before, after)?;
+    }
+    extension::init_extension::<Mint, _>(&mut mint_account.data.borrow_mut(), &holders)?;
+    Ok(true)
+}
+
+/// 两个不同账户之间记账，同步铸币的持有人计数与积分批次，并标记目标账户
+fn move_balance(
+    source: &mut LoadedTokenAccount,
+    dest: &mut LoadedTokenAccount,
+    amount: u64,
+    mint_account: &AccountInfo,
+) -> ProgramResult {
+    let before = (source.state.amount, dest.state.amount);
+    (source.state.amount, dest.state.amount) =
+        balance::transfer_amounts(source.state.amount, dest.state.amount, amount)?;
+    let changes = [(before.0, source.state.amount), (before.1, dest.state.amount)];
+    if update_holder_count(mint_account, &changes)? || has_global_freeze(mint_account)? {
+        dest.state.set_holder_tracked(true);
+    }
+    move_points_lots(mint_account, source, dest, before, amount)
+}
+
+/// 积分铸币：转出的积分带着批次一起转移，目标收到的积分保持原到期时间（余额中已过期的部分先转出，不带批次）；
+/// 目标没有 PointsLots 扩展时拒绝，否则收到的积分会被 ExpirePoints 当作已过期销毁
+fn move_points_lots(
+    mint_account: &AccountInfo,
+    source: &LoadedTokenAccount,
+    dest: &LoadedTokenAccount,
+    balances_before: (u64, u64),
+    amount: u64,
+) -> ProgramResult {
+    if extension::get_extension::<Mint, PointsConfig>(&mint_account.data.borrow())?.is_none() {
+        return Ok(());
+    }
+    let slot = Clock::get()?.slot;
+    // 源账户没有批次时整个余额都已过期，没有可转移的批次
+    let Some(mut source_lots) = extension::get_extension::<TokenAccount, PointsLots>(&source.info.data.borrow())?
+    else {
+        return Ok(());
+    };
+    let taken = source_lots.take(slot, balances_before.0, amount);
+    extension::init_extension::<TokenAccount, _>(&mut source.info.data.borrow_mut(), &source_lots)?;
+    if taken.lots.iter().all(|lot| lot.amount == 0) {
+        return Ok(());
+    }
+    let mut dest_lots = extension::get_extension::<TokenAccount, PointsLots>(&dest.info.data.borrow())?
+        .ok_or_else(|| {
+            msg!("Points account {} has no PointsLots extension", dest.info.key);
+            ProgramError::InvalidAccountData
+        })?;
+    // 目标先按转入前的余额结算，已花掉的批次不会抵扣新收到的积分
+    dest_lots.expire(slot, balances_before.1);
+    for lot in taken.lots.iter().filter(|lot| lot.amount != 0) {
+        dest_lots.add(lot.amount, lot.expires_at_slot)?;
+    }
+    extension::init_extension::<TokenAccount, _>(&mut dest.info.data.borrow_mut(), &dest_lots)
+}
+
+/// 代币账户余额所在的精度纪元
+fn account_rescale_epoch(token_account: &AccountInfo) -> Result<u8, ProgramError> {
+    let epoch = extension::get_extension::<TokenAccount, RescaleEpoch>(&token_account.data.borrow())?;
+    Ok(epoch.map_or(0, |epoch| epoch.epoch))
+}
+
+/// 铸币调整过精度时，把代币账户余额换算到当前纪元并记录纪元（基础状态由调用方写回）
+fn sync_rescale(mint_account: &AccountInfo, token: &mut LoadedTokenAccount) -> ProgramResult {
+    if token.state.mint != *mint_account.key {
+        return Ok(());
+    }
+    let rescale = match extension::get_extension::<Mint, DecimalsRescale>(&mint_account.data.borrow())? {
+        Some(rescale) => rescale,
+        None => return Ok(()),
+    };
+    let epoch = account_rescale_epoch(token.info)?;
+    if epoch == rescale.epoch {
+        return Ok(());
+    }
+    let amount = token.state.amount.checked_mul(rescale.factor_since(epoch)?).ok_or(TokenError::Overflow)?;
+    let synced = RescaleEpoch { epoch: rescale.epoch };
+    extension::init_extension::<TokenAccount, _>(&mut token.info.data.borrow_mut(), &synced)?;
+    msg!("Rescaled {} from epoch {} to {}: {} -> {}", token.info.key, epoch, rescale.epoch, token.state.amount, amount);
+    token.state.amount = amount;
+    Ok(())
+}
+
+/// 两个账户的余额必须按同一纪元的精度记账
+fn check_same_rescale_epoch(source: &AccountInfo, dest: &AccountInfo) -> ProgramResult {
+    let (source_epoch, dest_epoch) = (account_rescale_epoch(source)?, account_rescale_epoch(dest)?);
+    if source_epoch != dest_epoch {
+        msg!(
+            "Rescale epoch mismatch: source {} at {}, destination {} at {}",
+            source.key,
+            source_epoch,
+            dest.key,
+            dest_epoch
+        );
+        return Err(TokenError::RescaleEpochMismatch.into());
+    }
+    Ok(())
+}
+
+/// 要求激活的账户在所有者签名 ActivateAccount 之前不能收款
+fn check_activated(token_account: &AccountInfo) -> ProgramResult {
+    let activation = extension::get_extension::<TokenAccount, AccountActivation>(&token_account.data.borrow())?;
+    if activation.is_some_and(|activation| !activation.activated) {
+        msg!("Token account {} has not been activated by its owner", token_account.key);
+        return Err(TokenError::AccountNotActivated.into());
+    }
+    Ok(())
+}
+
+/// 处理函数内部在同一铸币的两个账户之间记账（托管、金库、分红等不经过 Transfer 的路径）：
+/// 目标须已激活；两边先换算到铸币当前的精度纪元，再经 move_balance 记账（同步持有人计数）并写回；
+/// 余额不足时返回 InsufficientFunds。mint_account 为 next_transfer_mint 读取的铸币账户
+fn move_synced(
+    mint_account: &AccountInfo,
+    source: &mut LoadedTokenAccount,
+    dest: &mut LoadedTokenAccount,
+    amount: u64,
+) -> ProgramResult {
+    if dest.info.key == source.info.key {
+        msg!("Destination cannot be the source account");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    validators::assert_mint_matches(&source.state.mint, mint_account.key)?;
+    validators::assert_mint_matches(&dest.state.mint, mint_account.key)?;
+    check_activated(dest.info)?;
+    sync_rescale(mint_account, source)?;
+    sync_rescale(mint_account, dest)?;
+    check_same_rescale_epoch(source.info, dest.info)?;
+    move_balance(source, dest, amount, mint_account)?;
+    source.save()?;
+    dest.save()
+}
+
+/// 从已加载的源账户向 dest_account 转账：读取目标 -> 运行钩子 -> 记账 -> 写回 -> 同步投票权
+/// 目标在此处才读取，调用方可以对同一目标多次调用；目标不能是源账户且必须同一铸币
+/// mint_account 为 next_source_mint 读取的铸币账户
+fn transfer_to(
+    ctx: &mut ProcessingContext,
+    source: &mut LoadedTokenAccount,
+    dest_account: &AccountInfo,
+    amount: u64,
+    mint_account: &AccountInfo,
+) -> ProgramResult {
+    if dest_account.key == source.info.key {
+        msg!("Destination cannot be the source account");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let mut dest = LoadedTokenAccount::load(ctx.program_id, dest_account)?;
+    validators::assert_mint_matches(&dest.state.mint, &source.state.mint)?;
+    check_same_rescale_epoch(source.info, dest_account)?;
+    check_activated(dest_account)?;
+    hooks::run_pre_transfer(&TransferInfo {
+        source_key: source.info.key,
+        destination_key: dest_account.key,
+        source: &source.state,
+        destination: &dest.state,
+        amount,
+    })?;
+
+    move_balance(source, &mut dest, amount, mint_account)?;
+    source.save()?;
+    dest.save()?;
+    move_delegated_votes(
+        ctx.program_id,
+        &source.state.mint,
+        source.state.vote_delegate,
+        dest.state.vote_delegate,
+        amount,
+        &mut ctx.accounts,
+    )?;
+
+    hooks::run_post_transfer(&TransferInfo {
+        source_key: source.info.key,
+        destination_key: dest_account.key,
+        source: &source.state,
+        destination: &dest.state,
+        amount,
+    })
+}
+
+/// 同一所有者的账户间调拨
+fn process_rebalance(ctx: &mut ProcessingContext, amount: u64) -> ProgramResult {
+    let mut source = ctx.next_token_account()?;
+    let mut dest = ctx.next_token_account()?;
+    let owner_account = ctx.next_account()?;
+
+    if source.info.key == dest.info.key {
+        msg!("Destination cannot be the source account");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    source.check_owner(owner_account)?;
+    dest.check_owner(owner_account)?;
+    validators::assert_mint_matches(&dest.state.mint, &source.state.mint)?;
+    check_same_rescale_epoch(source.info, dest.info)?;
+    check_activated(dest.info)?;
+    source.check_spendable(amount)?;
+    dest.check_not_frozen()?;
+    let mint_account = next_source_mint(ctx, &source)?;
+
+    move_balance(&mut source, &mut dest, amount, mint_account)?;
+    source.save()?;
+    dest.save()?;
+    move_delegated_votes(
+        ctx.program_id,
+        &source.state.mint,
+        source.state.vote_delegate,
+        dest.state.vote_delegate,
+        amount,
+        &mut ctx.accounts,
+    )?;
+
+    msg!("Rebalanced {} from {} to {}", amount, source.info.key, dest.info.key);
+    Ok(())
+}
+
+/// 按基点份额拆分转账
+fn process_transfer_split(ctx: &mut ProcessingContext, total: u64, shares: &[u16]) -> ProgramResult {
+    let amounts = balance::split_amounts(total, shares).inspect_err(|_| {
+        msg!("Split shares must be non-empty and sum to 10000 basis points");
+    })?;
+    let mut source = ctx.next_token_account()?;
+    let owner_account = ctx.next_signer()?;
+    source.check_owner(owner_account)?;
+    let destination_accounts = (0..amounts.len())
+        .map(|_| ctx.next_account())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mint_account = next_source_mint(ctx, &source)?;
+    if !record_velocity(ctx, &mut source, total)? {
+        return Ok(());
+    }
+
+    // 逐个目标读取 -> 记账 -> 写回，同一目标出现多次时按各自份额累加
+    for (dest_account, &amount) in destination_accounts.iter().zip(&amounts) {
+        transfer_to(ctx, &mut source, dest_account, amount, mint_account)?;
+    }
+    if source.state.is_nft() {
+        check_transfer_allowed(ctx.program_id, &source.state.mint, &mut ctx.accounts)?;
+    }
+
+    msg!("Split {} tokens from {} across {} destinations", total, source.info.key, amounts.len());
+    Ok(())
+}
+
+/// 把多个源账户的余额归集到同一目标
+fn process_sweep_all(ctx: &mut ProcessingContext, source_count: u8) -> ProgramResult {
+    let dest_account = ctx.next_account()?;
+    let authority_account = ctx.next_signer()?;
+    let mut sources = (0..source_count)
+        .map(|_| ctx.next_token_account())
+        .collect::<Result<Vec<_>, _>>()?;
+    if sources.is_empty() {
+        msg!("SweepAll needs at least one source account");
+        return Err(ProgramError::InvalidArgument);
+    }
+    // 同一源账户出现两次时第二份快照已过期，会重复转出
+    if sources.iter().enumerate().any(|(i, source)| sources[..i].iter().any(|other| other.info.key == source.info.key)) {
+        msg!("SweepAll source accounts must be distinct");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // authority 不是所有者时按受托人处理，额度 PDA 按源账户顺序提供
+    let mut allowances = Vec::with_capacity(sources.len());
+    for source in &sources {
+        if source.state.owner == *authority_account.key {
+            allowances.push(None);
+            continue;
+        }
+        let allowance_account = ctx.next_account()?;
+        let allowance = load_allowance(ctx.program_id, allowance_account, source.info.key)?;
+        if allowance.delegate != *authority_account.key {
+            msg!("{} is neither owner nor delegate of {}", authority_account.key, source.info.key);
+            return Err(TokenError::Unauthorized.into());
+        }
+        allowances.push(Some((allowance_account, allowance)));
+    }
+    let mint_account = next_source_mint(ctx, &sources[0])?;
+    for source in &sources[1..] {
+        check_global_freeze(mint_account, source.info)?;
+    }
+
+    let mut swept: u64 = 0;
+    for (source, allowance) in sources.iter_mut().zip(allowances.iter_mut()) {
+        let amount = match allowance {
+            Some((_, allowance)) => source.state.amount.min(allowance.amount),
+            None => source.state.amount,
+        };
+        if amount == 0 {
+            continue;
+        }
+        // 超出速度限制的源账户被冻结并跳过，其余照常归集
+        if !record_velocity(ctx, source, amount)? {
+            continue;
+        }
+        if let Some((allowance_account, allowance)) = allowance {
+            spend_allowance(allowance_account, allowance, amount, source.state.amount - amount)?;
+        }
+        transfer_to(ctx, source, dest_account, amount, mint_account)?;
+        swept = balance::credit(swept, amount)?;
+    }
+    if sources[0].state.is_nft() {
+        check_transfer_allowed(ctx.program_id, &sources[0].state.mint, &mut ctx.accounts)?;
+    }
+
+    msg!("Swept {} tokens from {} accounts into {}", swept, sources.len(), dest_account.key);
+    Ok(())
+}
+
+/// 批量结算
+fn process_settle_batch(ctx: &mut ProcessingContext, deltas: &[i64]) -> ProgramResult {
+    let mut token_accounts = deltas
+        .iter()
+        .map(|_| ctx.next_token_account())
+        .collect::<Result<Vec<_>, _>>()?;
+    let remaining: Vec<&AccountInfo> = ctx.accounts.by_ref().collect();
+    let signers: Vec<&Pubkey> =
+        remaining.iter().filter(|account| account.is_signer).map(|account| account.key).collect();
+    if token_accounts.iter().enumerate().any(|(i, account)| {
+        token_accounts[..i].iter().any(|other| other.info.key == account.info.key)
+    }) {
+        msg!("SettleBatch accounts must be distinct");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // 各铸币的净增量，必须全部为 0 才能保证供应量不变
+    let mut net_by_mint: BTreeMap<Pubkey, i128> = BTreeMap::new();
+    for (account, &delta) in token_accounts.iter_mut().zip(deltas) {
+        if delta == 0 {
+            continue;
+        }
+        // 这些账户的余额变化需要额外账户（检查点 / 跟踪 PDA / 白名单）配合，批量结算不处理
+        if account.state.vote_delegate.is_some()
+            || account.state.velocity_limited()
+            || account.state.is_nft()
+            || account.state.holder_tracked()
+        {
+            msg!("Account {} has delegated votes, a velocity limit, is an NFT or is holder tracked", account.info.key);
+            return Err(ProgramError::InvalidArgument);
+        }
+        account.check_not_frozen()?;
+        // 铸币扩展只能从铸币账户读到，参与结算的账户所属的铸币都必须提供
+        let Some(mint_account) = remaining.iter().find(|remaining| *remaining.key == account.state.mint) else {
+            msg!("Mint {} of settled account {} must be provided", account.state.mint, account.info.key);
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        // 增量无法对应到具体批次，积分铸币的余额只能经转账指令移动
+        if extension::get_extension::<Mint, PointsConfig>(&mint_account.data.borrow())?.is_some() {
+            msg!("Points balances of mint {} cannot be settled in a batch", account.state.mint);
+            return Err(ProgramError::InvalidArgument);
+        }
+        if delta < 0 {
+            if !signers.contains(&&account.state.owner) {
+                msg!("Owner {} of debited account {} must sign", account.state.owner, account.info.key);
+                return Err(TokenError::Unauthorized.into());
+            }
+            check_global_freeze(mint_account, account.info)?;
+            check_not_paused(mint_account, PausedOperation::Transfers)?;
+        } else {
+            check_activated(account.info)?;
+        }
+        // 同一铸币的账户都换算到当前纪元，增量之和为 0 才能保证供应量不变
+        sync_rescale(mint_account, account)?;
+        account.state.amount = balance::apply_delta(account.state.amount, delta)?;
+        *net_by_mint.entry(account.state.mint).or_default() += delta as i128;
+    }
+    if let Some((mint, net)) = net_by_mint.iter().find(|(_, net)| **net != 0) {
+        msg!("Deltas for mint {} sum to {}, must be 0", mint, net);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    for account in &token_accounts {
+        account.save()?;
+    }
+
+    msg!("Settled {} deltas across {} mints", deltas.len(), net_by_mint.len());
+    Ok(())
+}
+
+/// 初始化压缩余额树
+fn process_initialize_compressed_tree(program_id: &Pubkey, accounts: &[AccountInfo], depth: u8) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let tree_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let escrow_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(tree_account, program_id)?;
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_owned_by(escrow_account, program_id)?;
+    if depth == 0 || depth as usize > compression::MAX_COMPRESSED_TREE_DEPTH {
+        msg!("Tree depth must be between 1 and {}", compression::MAX_COMPRESSED_TREE_DEPTH);
+        return Err(ProgramError::InvalidArgument);
+    }
+    if tree_account.data_len() < CompressedTree::account_len(depth) {
+        msg!("Tree account needs {} bytes", CompressedTree::account_len(depth));
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    let existing: CompressedTree = unpack_state(&tree_account.data.borrow())?;
+    if existing.is_initialized {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let escrow = TokenAccount::deserialize(&escrow_account.data.borrow())?;
+    let (authority, _) = find_compressed_tree_authority(tree_account.key, program_id);
+    if escrow.owner != authority || escrow.mint != *mint_account.key || escrow.amount != 0 {
+        msg!("Escrow must be an empty account of mint {} owned by {}", mint_account.key, authority);
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    pack_state(
+        &CompressedTree::new(*mint_account.key, *escrow_account.key, depth),
+        &mut tree_account.data.borrow_mut(),
+    )?;
+
+    msg!("Compressed tree {} initialized for mint {} (depth {})", tree_account.key, mint_account.key, depth);
+    Ok(())
+}
+
+/// 读取并校验压缩树账户
+fn load_compressed_tree(program_id: &Pubkey, tree_account: &AccountInfo) -> Result<CompressedTree, ProgramError> {
+    validators::assert_owned_by(tree_account, program_id)?;
+    let tree: CompressedTree = unpack_state(&tree_account.data.borrow())?;
+    validators::assert_initialized(&tree)?;
+    Ok(tree)
+}
+
+/// 为 owner 追加压缩余额叶子并发布事件
+fn append_compressed_leaf(tree: &mut CompressedTree, tree_account: &AccountInfo, owner: Pubkey, amount: u64) -> ProgramResult {
+    let mut leaf = CompressedLeaf { owner, amount, index: tree.next_index };
+    leaf.index = tree.append(leaf.hash())?;
+    pack_state(tree, &mut tree_account.data.borrow_mut())?;
+    CompressionEvent::LeafAppended { tree: *tree_account.key, leaf }.emit()
+}
+
+/// 校验叶子所有者签名与证明，并把叶子标记为已花费
+fn spend_compressed_leaf(
+    tree: &CompressedTree,
+    tree_account: &AccountInfo,
+    owner_account: &AccountInfo,
+    leaf: &CompressedLeaf,
+    root: &[u8; 32],
+    proof: &[[u8; 32]],
+) -> ProgramResult {
+    validators::assert_signer(owner_account)?;
+    if leaf.owner != *owner_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    if !tree.is_known_root(root) {
+        msg!("Root is not among the last {} roots", compression::ROOT_HISTORY);
+        return Err(TokenError::InvalidProof.into());
+    }
+    if !tree.verify(leaf.hash(), leaf.index, proof, root) {
+        msg!("Invalid proof for leaf {}", leaf.index);
+        return Err(TokenError::InvalidProof.into());
+    }
+    let mut data = tree_account.data.borrow_mut();
+    let bitmap = &mut data[CompressedTree::LEN..];
+    if compression::is_spent(bitmap, leaf.index)? {
+        msg!("Leaf {} already spent", leaf.index);
+        return Err(TokenError::AlreadyClaimed.into());
+    }
+    compression::mark_spent(bitmap, leaf.index)?;
+    drop(data);
+    CompressionEvent::LeafSpent { tree: *tree_account.key, index: leaf.index }.emit()
+}
+
+/// 压缩代币账户中的余额
+fn process_compress(ctx: &mut ProcessingContext, recipient: Pubkey, amount: u64) -> ProgramResult {
+    let mut source = ctx.next_token_account()?;
+    let owner_account = ctx.next_signer()?;
+    let tree_account = ctx.next_account()?;
+    let escrow_account = ctx.next_account()?;
+    source.check_owner(owner_account)?;
+
+    let mut tree = load_compressed_tree(ctx.program_id, tree_account)?;
+    if tree.escrow != *escrow_account.key {
+        msg!("Escrow of tree {} is {}", tree_account.key, tree.escrow);
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if amount == 0 || source.state.is_nft() {
+        msg!("Only a non-zero amount of fungible tokens can be compressed");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mint_account = next_source_mint(ctx, &source)?;
+    if !record_velocity(ctx, &mut source, amount)? {
+        return Ok(());
+    }
+    transfer_to(ctx, &mut source, escrow_account, amount, mint_account)?;
+    append_compressed_leaf(&mut tree, tree_account, recipient, amount)?;
+
+    msg!("Compressed {} tokens from {} for {}", amount, source.info.key, recipient);
+    Ok(())
+}
+
+/// 压缩余额之间转账
+fn process_compressed_transfer(
+    ctx: &mut ProcessingContext,
+    leaf: CompressedLeaf,
+    root: [u8; 32],
+    proof: &[[u8; 32]],
+    recipient: Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    let tree_account = ctx.next_account()?;
+    let owner_account = ctx.next_account()?;
+
+    let mut tree = load_compressed_tree(ctx.program_id, tree_account)?;
+    if amount == 0 || amount > leaf.amount {
+        msg!("Amount must be between 1 and {}", leaf.amount);
+        return Err(TokenError::InsufficientFunds.into());
+    }
+    spend_compressed_leaf(&tree, tree_account, owner_account, &leaf, &root, proof)?;
+
+    append_compressed_leaf(&mut tree, tree_account, recipient, amount)?;
+    let change = leaf.amount - amount;
+    if change > 0 {
+        append_compressed_leaf(&mut tree, tree_account, leaf.owner, change)?;
+    }
+
+    msg!("Compressed transfer of {} from {} to {}", amount, leaf.owner, recipient);
+    Ok(())
+}
+
+/// 解压到代币账户
+fn process_decompress(ctx: &mut ProcessingContext, leaf: CompressedLeaf, root: [u8; 32], proof: &[[u8; 32]]) -> ProgramResult {
+    let tree_account = ctx.next_account()?;
+    let mut escrow = ctx.next_token_account()?;
+    let dest_account = ctx.next_account()?;
+    let owner_account = ctx.next_account()?;
+
+    let tree = load_compressed_tree(ctx.program_id, tree_account)?;
+    if tree.escrow != *escrow.info.key {
+        msg!("Escrow of tree {} is {}", tree_account.key, tree.escrow);
+        return Err(ProgramError::InvalidAccountData);
+    }
+    spend_compressed_leaf(&tree, tree_account, owner_account, &leaf, &root, proof)?;
+
+    let mint_account = next_source_mint(ctx, &escrow)?;
+    transfer_to(ctx, &mut escrow, dest_account, leaf.amount, mint_account)?;
+
+    msg!("Decompressed {} tokens of leaf {} into {}", leaf.amount, leaf.index, dest_account.key);
+    Ok(())
+}
+
+/// 双方原子互换
+fn process_atomic_swap(ctx: &mut ProcessingContext, amount_a: u64, amount_b: u64) -> ProgramResult {
+    let mut source_a = ctx.next_token_account()?;
+    let dest_a_account = ctx.next_account()?;
+    let mut source_b = ctx.next_token_account()?;
+    let dest_b_account = ctx.next_account()?;
+    let owner_a_account = ctx.next_signer()?;
+    let owner_b_account = ctx.next_signer()?;
+    source_a.check_owner(owner_a_account)?;
+    source_b.check_owner(owner_b_account)?;
+
+    // 两条腿的铸币不同，各自的目标又必须与源同一铸币，因此四个账户互不重叠
+    if source_a.state.mint == source_b.state.mint {
+        msg!("Atomic swap legs must use different mints");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mint_a_account = next_source_mint(ctx, &source_a)?;
+    let mint_b_account = next_source_mint(ctx, &source_b)?;
+    // 任一方超出速度限制时冻结该账户并放弃整笔互换
+    if !record_velocity(ctx, &mut source_a, amount_a)? || !record_velocity(ctx, &mut source_b, amount_b)? {
+        return Ok(());
+    }
+
+    transfer_to(ctx, &mut source_a, dest_a_account, amount_a, mint_a_account)?;
+    transfer_to(ctx, &mut source_b, dest_b_account, amount_b, mint_b_account)?;
+    if source_a.state.is_nft() {
+        check_transfer_allowed(ctx.program_id, &source_a.state.mint, &mut ctx.accounts)?;
+    }
+    if source_b.state.is_nft() {
+        check_transfer_allowed(ctx.program_id, &source_b.state.mint, &mut ctx.accounts)?;
+    }
+
+    msg!(
+        "Swapped {} of {} for {} of {}",
+        amount_a,
+        source_a.state.mint,
+        amount_b,
+        source_b.state.mint
+    );
+    Ok(())
+}
+
+/// 按链下签名设置受托人额度
+fn process_permit_approve(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    delegate: Pubkey,
+    amount: u64,
+    nonce: u64,
+    deadline: i64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let allowance_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let instructions_sysvar = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(payer_account)?;
+    validators::assert_owned_by(token_account, program_id)?;
+    let now = Clock::get()?.unix_timestamp;
+    if now > deadline {
+        msg!("Permit expired at {}, now {}", deadline, now);
+        return Err(TokenError::InvalidProof.into());
+    }
+
+    let token = TokenAccount::deserialize(&token_account.data.borrow())?;
+    let message = PermitMessage::Approve {
+        program_id: *program_id,
+        token_account: *token_account.key,
+        delegate,
+        amount,
+        nonce,
+        deadline,
+    };
+    permit::verify_preceding_signature(instructions_sysvar, &token.owner, &message.to_bytes()?)?;
+
+    let mut allowance = load_or_create_allowance(
+        program_id,
+        token_account,
+        allowance_account,
+        &delegate,
+        payer_account,
+        system_program_account,
+    )?;
+    if allowance.nonce != nonce {
+        msg!("Permit nonce {} does not match expected {}", nonce, allowance.nonce);
+        return Err(TokenError::InvalidProof.into());
+    }
+
+    allowance.amount = amount;
+    allowance.nonce = allowance.nonce.checked_add(1).ok_or(TokenError::Overflow)?;
+    pack_state(&allowance, &mut allowance_account.data.borrow_mut())?;
+
+    msg!("Approved {} to spend {} from {}", delegate, amount, token_account.key);
+    Ok(())
+}
+
+/// 批量设置受托人额度
+fn process_approve_multi(program_id: &Pubkey, accounts: &[AccountInfo], approvals: &[(Pubkey, u64)]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let owner_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(owner_account)?;
+    for &(delegate, amount) in approvals {
+        let token_account = next_account_info(account_info_iter)?;
+        let allowance_account = next_account_info(account_info_iter)?;
+        validators::assert_owned_by(token_account, program_id)?;
+        let token = TokenAccount::deserialize(&token_account.data.borrow())?;
+        if token.owner != *owner_account.key {
+            msg!("Token account {} is not owned by {}", token_account.key, owner_account.key);
+            return Err(TokenError::Unauthorized.into());
+        }
+
+        let mut allowance = load_or_create_allowance(
+            program_id,
+            token_account,
+            allowance_account,
+            &delegate,
+            owner_account,
+            system_program_account,
+        )?;
+        allowance.amount = amount;
+        pack_state(&allowance, &mut allowance_account.data.borrow_mut())?;
+        msg!("Approved {} to spend {} from {}", delegate, amount, token_account.key);
+    }
+    Ok(())
+}
+
+/// 加载额度 PDA，不存在时由 payer 支付租金创建（额度与 nonce 均为 0）
+fn load_or_create_allowance<'a>(
+    program_id: &Pubkey,
+    token_account: &AccountInfo<'a>,
+    allowance_account: &AccountInfo<'a>,
+    delegate: &Pubkey,
+    payer_account: &AccountInfo<'a>,
+    system_program_account: &AccountInfo<'a>,
+) -> Result<Allowance, ProgramError> {
+    let (allowance_key, bump) = find_allowance_address(token_account.key, delegate, program_id);
+    if allowance_key != *allowance_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if allowance_account.data_is_empty() {
+        create_pda_account(
+            payer_account,
+            allowance_account,
+            system_program_account,
+            program_id,
+            Allowance::LEN,
+            &[ALLOWANCE_SEED, token_account.key.as_ref(), delegate.as_ref(), &[bump]],
+        )?;
+        pack_state(
+            &Allowance {
+                is_initialized: true,
+                token_account: *token_account.key,
+                delegate: *delegate,
+                amount: 0,
+                nonce: 0,
+            },
+            &mut allowance_account.data.borrow_mut(),
+        )?;
+    }
+    load_allowance(program_id, allowance_account, token_account.key)
+}
+
+/// 加载并校验代币账户的额度 PDA
+fn load_allowance(
+    program_id: &Pubkey,
+    allowance_account: &AccountInfo,
+    token_account: &Pubkey,
+) -> Result<Allowance, ProgramError> {
+    validators::assert_owned_by(allowance_account, program_id)?;
+    let allowance: Allowance = unpack_state(&allowance_account.data.borrow())?;
+    if !allowance.is_initialized || allowance.token_account != *token_account {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(allowance)
+}
+
+/// 加载额度 PDA，并校验其属于 delegate 且剩余额度不少于 amount
+fn load_delegate_allowance(
+    program_id: &Pubkey,
+    allowance_account: &AccountInfo,
+    token_account: &Pubkey,
+    delegate_account: &AccountInfo,
+    amount: u64,
+) -> Result<Allowance, ProgramError> {
+    let allowance = load_allowance(program_id, allowance_account, token_account)?;
+    if allowance.delegate != *delegate_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    if allowance.amount < amount {
+        msg!("Allowance {} is less than {}", allowance.amount, amount);
+        return Err(TokenError::InsufficientFunds.into());
+    }
+    Ok(allowance)
+}
+
+/// 扣减额度并写回；扣款后账户余额为 0 时撤销剩余额度，避免账户之后重新入账时旧授权仍然有效
+fn spend_allowance(
+    allowance_account: &AccountInfo,
+    allowance: &mut Allowance,
+    amount: u64,
+    balance_after: u64,
+) -> ProgramResult {
+    allowance.amount = if balance_after == 0 { 0 } else { allowance.amount - amount };
+    pack_state(allowance, &mut allowance_account.data.borrow_mut())
+}
+
+/// 受托人在额度内转账
+fn process_transfer_from(ctx: &mut ProcessingContext, amount: u64) -> ProgramResult {
+    let mut source = ctx.next_token_account()?;
+    let dest_account = ctx.next_account()?;
+    let allowance_account = ctx.next_account()?;
+    let delegate_account = ctx.next_signer()?;
+
+    let mut allowance =
+        load_delegate_allowance(ctx.program_id, allowance_account, source.info.key, delegate_account, amount)?;
+
+    let mint_account = next_source_mint(ctx, &source)?;
+    if !record_velocity(ctx, &mut source, amount)? {
+        return Ok(());
+    }
+    spend_allowance(allowance_account, &mut allowance, amount, source.state.amount.saturating_sub(amount))?;
+    transfer_to(ctx, &mut source, dest_account, amount, mint_account)?;
+    if source.state.is_nft() {
+        check_transfer_allowed(ctx.program_id, &source.state.mint, &mut ctx.accounts)?;
+    }
+
+    msg!("Delegate {} transferred {} from {}", delegate_account.key, amount, source.info.key);
+    Ok(())
+}
+
+/// 中继者代付的 permit 转账
+fn process_transfer_with_permit(
+    ctx: &mut ProcessingContext,
+    amount: u64,
+    tip: u64,
+    nonce: u64,
+    deadline: i64,
+) -> ProgramResult {
+    let mut source = ctx.next_token_account()?;
+    let dest_account = ctx.next_account()?;
+    let tip_account = ctx.next_account()?;
+    let nonce_account = ctx.next_account()?;
+    let relayer_account = ctx.next_signer()?;
+    let instructions_sysvar = ctx.next_account()?;
+    let system_program_account = ctx.next_account()?;
+
+    let net_amount = amount.checked_sub(tip).ok_or_else(|| {
+        msg!("Relayer tip {} exceeds amount {}", tip, amount);
+        ProgramError::InvalidArgument
+    })?;
+    let now = Clock::get()?.unix_timestamp;
+    if now > deadline {
+        msg!("Permit expired at {}, now {}", deadline, now);
+        return Err(TokenError::InvalidProof.into());
+    }
+
+    let message = PermitMessage::Transfer {
+        program_id: *ctx.program_id,
+        source: *source.info.key,
+        destination: *dest_account.key,
+        amount,
+        tip,
+        nonce,
+        deadline,
+    };
+    permit::verify_preceding_signature(instructions_sysvar, &source.state.owner, &message.to_bytes()?)?;
+
+    let (nonce_key, bump) = find_permit_nonce_address(source.info.key, ctx.program_id);
+    if nonce_key != *nonce_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if nonce_account.data_is_empty() {
+        create_pda_account(
+            relayer_account,
+            nonce_account,
+            system_program_account,
+            ctx.program_id,
+            PermitNonce::LEN,
+            &[PERMIT_NONCE_SEED, source.info.key.as_ref(), &[bump]],
+        )?;
+        pack_state(
+            &PermitNonce { is_initialized: true, token_account: *source.info.key, nonce: 0 },
+            &mut nonce_account.data.borrow_mut(),
+        )?;
+    }
+    validators::assert_owned_by(nonce_account, ctx.program_id)?;
+    let mut permit_nonce: PermitNonce = unpack_state(&nonce_account.data.borrow())?;
+    if !permit_nonce.is_initialized || permit_nonce.token_account != *source.info.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if permit_nonce.nonce != nonce {
+        msg!("Permit nonce {} does not match expected {}", nonce, permit_nonce.nonce);
+        return Err(TokenError::InvalidProof.into());
+    }
+    permit_nonce.nonce = permit_nonce.nonce.checked_add(1).ok_or(TokenError::Overflow)?;
+    pack_state(&permit_nonce, &mut nonce_account.data.borrow_mut())?;
+
+    let mint_account = next_source_mint(ctx, &source)?;
+    if !record_velocity(ctx, &mut source, amount)? {
+        return Ok(());
+    }
+    transfer_to(ctx, &mut source, dest_account, net_amount, mint_account)?;
+    if tip > 0 {
+        transfer_to(ctx, &mut source, tip_account, tip, mint_account)?;
+    }
+    if source.state.is_nft() {
+        check_transfer_allowed(ctx.program_id, &source.state.mint, &mut ctx.accounts)?;
+    }
+
+    msg!(
+        "Relayer {} transferred {} from {} (tip {})",
+        relayer_account.key,
+        net_amount,
+        source.info.key,
+        tip
+    );
+    Ok(())
+}
+
+/// 创建 / 重置会话密钥
+fn process_create_session(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    key: Pubkey,
+    max_amount: u64,
+    expires_at_slot: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let session_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(token_account, program_id)?;
+    let token = TokenAccount::deserialize(&token_account.data.borrow())?;
+    validators::assert_signer(owner_account)?;
+    if token.owner != *owner_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    if expires_at_slot <= Clock::get()?.slot {
+        msg!("Session expiry {} is not in the future", expires_at_slot);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (session_key, bump) = find_session_address(token_account.key, &key, program_id);
+    if session_key != *session_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if session_account.data_is_empty() {
+        create_pda_account(
+            owner_account,
+            session_account,
+            system_program_account,
+            program_id,
+            Session::LEN,
+            &[SESSION_SEED, token_account.key.as_ref(), key.as_ref(), &[bump]],
+        )?;
+    } else {
+        validators::assert_owned_by(session_account, program_id)?;
+    }
+
+    let session = Session {
+        is_initialized: true,
+        token_account: *token_account.key,
+        key,
+        remaining: max_amount,
+        expires_at_slot,
+    };
+    pack_state(&session, &mut session_account.data.borrow_mut())?;
+
+    msg!("Session {} may spend {} from {} until slot {}", key, max_amount, token_account.key, expires_at_slot);
+    Ok(())
+}
+
+/// 加载并校验代币账户的会话 PDA
+fn load_session(
+    program_id: &Pubkey,
+    session_account: &AccountInfo,
+    token_account: &Pubkey,
+) -> Result<Session, ProgramError> {
+    validators::assert_owned_by(session_account, program_id)?;
+    let session: Session = unpack_state(&session_account.data.borrow())?;
+    if !session.is_initialized || session.token_account != *token_account {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(session)
+}
+
+/// 撤销会话密钥
+fn process_revoke_session(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let session_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(token_account, program_id)?;
+    let token = TokenAccount::deserialize(&token_account.data.borrow())?;
+    validators::assert_signer(owner_account)?;
+    if token.owner != *owner_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    let session = load_session(program_id, session_account, token_account.key)?;
+    close_pda_account(session_account, owner_account)?;
+
+    msg!("Session {} revoked for {}", session.key, token_account.key);
+    Ok(())
+}
+
+/// 会话密钥转账
+fn process_session_transfer(ctx: &mut ProcessingContext, amount: u64) -> ProgramResult {
+    let mut source = ctx.next_token_account()?;
+    let dest_account = ctx.next_account()?;
+    let session_account = ctx.next_account()?;
+    let session_key_account = ctx.next_signer()?;
+
+    let mut session = load_session(ctx.program_id, session_account, source.info.key)?;
+    if session.key != *session_key_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    let slot = Clock::get()?.slot;
+    if slot > session.expires_at_slot {
+        msg!("Session expired at slot {}, now {}", session.expires_at_slot, slot);
+        return Err(TokenError::Unauthorized.into());
+    }
+    if session.remaining < amount {
+        msg!("Session allowance {} is less than {}", session.remaining, amount);
+        return Err(TokenError::InsufficientFunds.into());
+    }
+
+    let mint_account = next_source_mint(ctx, &source)?;
+    if !record_velocity(ctx, &mut source, amount)? {
+        return Ok(());
+    }
+    session.remaining -= amount;
+    pack_state(&session, &mut session_account.data.borrow_mut())?;
+    transfer_to(ctx, &mut source, dest_account, amount, mint_account)?;
+    if source.state.is_nft() {
+        check_transfer_allowed(ctx.program_id, &source.state.mint, &mut ctx.accounts)?;
+    }
+
+    msg!("Session {} transferred {} from {}", session.key, amount, source.info.key);
+    Ok(())
+}
+
+/// 创建 / 覆盖预授权扣款
+fn process_create_pre_authorization(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    merchant: Pubkey,
+    amount_per_cycle: u64,
+    cycle_slots: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let pre_authorization_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    let token = LoadedTokenAccount::load(program_id, token_account)?;
+    token.check_owner(owner_account)?;
+    if amount_per_cycle == 0 || cycle_slots == 0 {
+        msg!("Pre-authorization needs a non-zero amount and cycle");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (pre_authorization_key, bump) = find_pre_authorization_address(token_account.key, &merchant, program_id);
+    if pre_authorization_key != *pre_authorization_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if pre_authorization_account.data_is_empty() {
+        create_pda_account(
+            owner_account,
+            pre_authorization_account,
+            system_program_account,
+            program_id,
+            PreAuthorization::LEN,
+            &[PRE_AUTHORIZATION_SEED, token_account.key.as_ref(), merchant.as_ref(), &[bump]],
+        )?;
+    } else {
+        validators::assert_owned_by(pre_authorization_account, program_id)?;
+    }
+
+    let pre_authorization = PreAuthorization {
+        is_initialized: true,
+        token_account: *token_account.key,
+        merchant,
+        amount_per_cycle,
+        cycle_slots,
+        cycle_start_slot: Clock::get()?.slot,
+        debited_in_cycle: 0,
+    };
+    pack_state(&pre_authorization, &mut pre_authorization_account.data.borrow_mut())?;
+
+    msg!(
+        "Merchant {} may debit {} every {} slots from {}",
+        merchant,
+        amount_per_cycle,
+        cycle_slots,
+        token_account.key
+    );
+    Ok(())
+}
+
+/// 加载并校验代币账户的预授权 PDA
+fn load_pre_authorization(
+    program_id: &Pubkey,
+    pre_authorization_account: &AccountInfo,
+    token_account: &Pubkey,
+) -> Result<PreAuthorization, ProgramError> {
+    validators::assert_owned_by(pre_authorization_account, program_id)?;
+    let pre_authorization: PreAuthorization = unpack_state(&pre_authorization_account.data.borrow())?;
+    validators::assert_initialized(&pre_authorization)?;
+    if pre_authorization.token_account != *token_account {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(pre_authorization)
+}
+
+/// 商户按预授权扣款
+fn process_debit(ctx: &mut ProcessingContext, amount: u64) -> ProgramResult {
+    let mut source = ctx.next_token_account()?;
+    let dest_account = ctx.next_account()?;
+    let pre_authorization_account = ctx.next_account()?;
+    let merchant_account = ctx.next_signer()?;
+
+    let mut pre_authorization = load_pre_authorization(ctx.program_id, pre_authorization_account, source.info.key)?;
+    if pre_authorization.merchant != *merchant_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    pre_authorization.debit(Clock::get()?.slot, amount)?;
+
+    let mint_account = next_source_mint(ctx, &source)?;
+    if !record_velocity(ctx, &mut source, amount)? {
+        return Ok(());
+    }
+    pack_state(&pre_authorization, &mut pre_authorization_account.data.borrow_mut())?;
+    transfer_to(ctx, &mut source, dest_account, amount, mint_account)?;
+    if source.state.is_nft() {
+        check_transfer_allowed(ctx.program_id, &source.state.mint, &mut ctx.accounts)?;
+    }
+
+    msg!("Merchant {} debited {} from {}", merchant_account.key, amount, source.info.key);
+    Ok(())
+}
+
+/// 撤销预授权
+fn process_close_pre_authorization(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let pre_authorization_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+
+    LoadedTokenAccount::load(program_id, token_account)?.check_owner(owner_account)?;
+    let pre_authorization = load_pre_authorization(program_id, pre_authorization_account, token_account.key)?;
+    close_pda_account(pre_authorization_account, owner_account)?;
+
+    msg!("Pre-authorization of {} revoked for {}", pre_authorization.merchant, token_account.key);
+    Ok(())
+}
+
+/// 设置社交恢复监护人
+fn process_set_guardians(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    guardians: Vec<Pubkey>,
+    threshold: u8,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(token_account, program_id)?;
+    let token = TokenAccount::deserialize(&token_account.data.borrow())?;
+    validators::assert_signer(owner_account)?;
+    if token.owner != *owner_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    if guardians.len() > MAX_GUARDIANS || threshold as usize > guardians.len() {
+        msg!("Need threshold <= guardians <= {}, got {} of {}", MAX_GUARDIANS, threshold, guardians.len());
+        return Err(ProgramError::InvalidArgument);
+    }
+    if threshold == 0 && !guardians.is_empty() {
+        msg!("Threshold must be at least 1");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if guardians.iter().enumerate().any(|(i, guardian)| guardians[..i].contains(guardian) || *guardian == token.owner) {
+        msg!("Guardians must be distinct and must not include the owner");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let count = guardians.len();
+    extension::init_extension::<TokenAccount, _>(
+        &mut token_account.data.borrow_mut(),
+        &GuardianConfig { threshold, guardians },
+    )?;
+
+    msg!("Account {} guarded by {} of {} guardians", token_account.key, threshold, count);
+    Ok(())
+}
+
+/// 监护人提议恢复所有者
+fn process_propose_owner_recovery(program_id: &Pubkey, accounts: &[AccountInfo], new_owner: Pubkey) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let recovery_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(payer_account)?;
+    validators::assert_owned_by(token_account, program_id)?;
+    let config = extension::get_extension::<TokenAccount, GuardianConfig>(&token_account.data.borrow())?
+        .filter(|config| config.threshold > 0)
+        .ok_or_else(|| {
+            msg!("Account {} has no guardians", token_account.key);
+            TokenError::Unauthorized
+        })?;
+
+    // 剩余账户中已签名的不同监护人
+    let mut approvals: Vec<Pubkey> = Vec::new();
+    for guardian_account in account_info_iter {
+        if guardian_account.is_signer
+            && config.guardians.contains(guardian_account.key)
+            && !approvals.contains(guardian_account.key)
+        {
+            approvals.push(*guardian_account.key);
+        }
+    }
+    if approvals.len() < config.threshold as usize {
+        msg!("{} guardian signatures, {} required", approvals.len(), config.threshold);
+        return Err(TokenError::Unauthorized.into());
+    }
+
+    let (recovery_key, bump) = find_owner_recovery_address(token_account.key, program_id);
+    if recovery_key != *recovery_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    create_pda_account(
+        payer_account,
+        recovery_account,
+        system_program_account,
+        program_id,
+        OwnerRecovery::LEN,
+        &[OWNER_RECOVERY_SEED, token_account.key.as_ref(), &[bump]],
+    )?;
+
+    let recovery = OwnerRecovery {
+        is_initialized: true,
+        token_account: *token_account.key,
+        new_owner,
+        payer: *payer_account.key,
+        executable_slot: Clock::get()?
+            .slot
+            .checked_add(OWNER_RECOVERY_TIMELOCK_SLOTS)
+            .ok_or(TokenError::Overflow)?,
+    };
+    pack_state(&recovery, &mut recovery_account.data.borrow_mut())?;
+
+    msg!(
+        "Owner recovery of {} to {} proposed, executable at slot {}",
+        token_account.key,
+        new_owner,
+        recovery.executable_slot
+    );
+    Ok(())
+}
+
+/// 加载并校验代币账户的所有者恢复 PDA
+fn load_owner_recovery(
+    program_id: &Pubkey,
+    recovery_account: &AccountInfo,
+    token_account: &Pubkey,
+    payer_account: &AccountInfo,
+) -> Result<OwnerRecovery, ProgramError> {
+    validators::assert_owned_by(recovery_account, program_id)?;
+    let recovery: OwnerRecovery = unpack_state(&recovery_account.data.borrow())?;
+    if !recovery.is_initialized || recovery.token_account != *token_account || recovery.payer != *payer_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(recovery)
+}
+
+/// 原所有者取消恢复
+fn process_cancel_owner_recovery(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let recovery_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(token_account, program_id)?;
+    let token = TokenAccount::deserialize(&token_account.data.borrow())?;
+    validators::assert_signer(owner_account)?;
+    if token.owner != *owner_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    let recovery = load_owner_recovery(program_id, recovery_account, token_account.key, payer_account)?;
+    close_pda_account(recovery_account, payer_account)?;
+
+    msg!("Owner recovery of {} to {} cancelled", token_account.key, recovery.new_owner);
+    Ok(())
+}
+
+/// 时间锁到期后更换所有者
+fn process_recover_owner(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let recovery_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(token_account, program_id)?;
+    let recovery = load_owner_recovery(program_id, recovery_account, token_account.key, payer_account)?;
+    let slot = Clock::get()?.slot;
+    if slot < recovery.executable_slot {
+        msg!("Owner recovery executable at slot {}, now {}", recovery.executable_slot, slot);
+        return Err(TokenError::TimelockNotElapsed.into());
+    }
+
+    let mut token = TokenAccount::deserialize(&token_account.data.borrow())?;
+    let old_owner = std::mem::replace(&mut token.owner, recovery.new_owner);
+    token.serialize(&mut token_account.data.borrow_mut())?;
+    close_pda_account(recovery_account, payer_account)?;
+
+    msg!("Owner of {} recovered: {} -> {}", token_account.key, old_owner, recovery.new_owner);
+    Ok(())
+}
+
+/// 开启活动时间记录
+fn process_enable_activity_tracking(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(token_account, program_id)?;
+    let token = TokenAccount::deserialize(&token_account.data.borrow())?;
+    validators::assert_signer(owner_account)?;
+    if token.owner != *owner_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    if extension::get_extension::<TokenAccount, AccountActivity>(&token_account.data.borrow())?.is_some() {
+        msg!("Activity tracking already enabled for {}", token_account.key);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let slot = Clock::get()?.slot;
+    extension::init_extension::<TokenAccount, _>(
+        &mut token_account.data.borrow_mut(),
+        &AccountActivity { created_at: slot, last_activity_slot: slot },
+    )?;
+
+    msg!("Activity tracking enabled for {} at slot {}", token_account.key, slot);
+    Ok(())
+}
+
+/// 开启转账备注记录
+fn process_enable_memo_log(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(token_account, program_id)?;
+    let token = TokenAccount::deserialize(&token_account.data.borrow())?;
+    validators::assert_signer(owner_account)?;
+    if token.owner != *owner_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    if extension::get_extension::<TokenAccount, MemoLog>(&token_account.data.borrow())?.is_some() {
+        msg!("Memo log already enabled for {}", token_account.key);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    extension::init_extension::<TokenAccount, _>(&mut token_account.data.borrow_mut(), &MemoLog::default())?;
+
+    msg!("Memo log enabled for {} ({} entries)", token_account.key, MEMO_LOG_CAPACITY);
+    Ok(())
+}
+
+/// 设置代币账户标签
+fn process_set_account_label(ctx: &mut ProcessingContext, label: [u8; ACCOUNT_LABEL_LEN]) -> ProgramResult {
+    let token = ctx.next_token_account()?;
+    let owner_account = ctx.next_account()?;
+
+    token.check_owner(owner_account)?;
+    let label = AccountLabel { label };
+    let Some(text) = label.as_str() else {
+        msg!("Account label must be UTF-8");
+        return Err(ProgramError::InvalidArgument);
+    };
+    extension::init_extension::<TokenAccount, _>(&mut token.info.data.borrow_mut(), &label)?;
+
+    msg!("Token account {} labeled {:?}", token.info.key, text);
+    Ok(())
+}
+
+/// 查询账户的扩展位图
+fn process_get_extension_types(program_id: &Pubkey, accounts: &[AccountInfo], is_mint: bool) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(account, program_id)?;
+    let data = account.data.borrow();
+    let (initialized, bitmap) = if is_mint {
+        (Mint::deserialize(&data)?.is_initialized, extension::extension_bitmap::<Mint>(&data)?)
+    } else {
+        (TokenAccount::deserialize(&data)?.is_initialized(), extension::extension_bitmap::<TokenAccount>(&data)?)
+    };
+    if !initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    set_return_data(&bitmap.to_le_bytes());
+
+    msg!("Extensions of {}: {:#x}", account.key, bitmap);
+    Ok(())
+}
+
+/// 设置跨链桥验证者
+fn process_configure_bridge(program_id: &Pubkey, accounts: &[AccountInfo], verifier: Pubkey) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    let mint = LoadedMint::load(program_id, mint_account)?;
+    mint.check_mint_authority(authority_account)?;
+
+    let (bridge_key, bump) = find_bridge_address(mint_account.key, program_id);
+    if bridge_key != *bridge_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let sequence = if bridge_account.data_is_empty() {
+        create_pda_account(
+            authority_account,
+            bridge_account,
+            system_program_account,
+            program_id,
+            BridgeConfig::LEN,
+            &[BRIDGE_SEED, mint_account.key.as_ref(), &[bump]],
+        )?;
+        0
+    } else {
+        load_bridge_config(program_id, bridge_account, mint_account.key)?.sequence
+    };
+    pack_state(
+        &BridgeConfig { is_initialized: true, mint: *mint_account.key, verifier, sequence },
+        &mut bridge_account.data.borrow_mut(),
+    )?;
+
+    msg!("Bridge verifier for mint {} set to {}", mint_account.key, verifier);
+    Ok(())
+}
+
+/// 加载并校验铸币的跨链桥配置
+fn load_bridge_config(program_id: &Pubkey, bridge_account: &AccountInfo, mint: &Pubkey) -> Result<BridgeConfig, ProgramError> {
+    validators::assert_owned_by(bridge_account, program_id)?;
+    let config: BridgeConfig = unpack_state(&bridge_account.data.borrow())?;
+    if !config.is_initialized || config.mint != *mint {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(config)
+}
+
+/// 销毁代币出桥
+fn process_bridge_burn(
+    ctx: &mut ProcessingContext,
+    target_chain: u16,
+    target_address: [u8; 32],
+    amount: u64,
+) -> ProgramResult {
+    let mut token = ctx.next_token_account()?;
+    let mut mint = ctx.next_mint()?;
+    let owner_account = ctx.next_signer()?;
+    let bridge_account = ctx.next_account()?;
+
+    validators::assert_mint_matches(&token.state.mint, mint.info.key)?;
+    token.check_owner(owner_account)?;
+    check_global_freeze(mint.info, token.info)?;
+    check_not_paused(mint.info, PausedOperation::Burning)?;
+    let mut config = load_bridge_config(ctx.program_id, bridge_account, mint.info.key)?;
+    if target_chain == bridge::SOLANA_CHAIN_ID || target_chain == 0 {
+        msg!("Invalid bridge target chain {}", target_chain);
+        return Err(ProgramError::InvalidArgument);
+    }
+    let (normalized, burn_amount) = bridge::normalize_amount(amount, mint.state.decimals);
+    if normalized == 0 {
+        msg!("Amount {} is below the bridge precision of {} decimals", amount, bridge::MAX_BRIDGE_DECIMALS);
+        return Err(ProgramError::InvalidArgument);
+    }
+    sync_rescale(mint.info, &mut token)?;
+    token.check_spendable(burn_amount)?;
+
+    let balance_before = token.state.amount;
+    (mint.state.supply, token.state.amount) = balance::burn_amounts(mint.state.supply, token.state.amount, burn_amount)?;
+    token.save()?;
+    mint.save()?;
+    update_holder_count(mint.info, &[(balance_before, token.state.amount)])?;
+    move_delegated_votes(ctx.program_id, mint.info.key, token.state.vote_delegate, None, burn_amount, &mut ctx.accounts)?;
+
+    let sequence = config.sequence;
+    config.sequence = sequence.checked_add(1).ok_or(TokenError::Overflow)?;
+    pack_state(&config, &mut bridge_account.data.borrow_mut())?;
+    let payload = TransferPayload {
+        amount: normalized,
+        token_address: mint.info.key.to_bytes(),
+        token_chain: bridge::SOLANA_CHAIN_ID,
+        to: target_address,
+        to_chain: target_chain,
+        fee: 0,
+    };
+    BridgeEvent::Burned { mint: *mint.info.key, sequence, payload: payload.encode() }.emit()?;
+
+    msg!("Bridged out {} tokens from {} to chain {} (sequence {})", burn_amount, token.info.key, target_chain, sequence);
+    Ok(())
+}
+
+/// 入桥铸造
+fn process_bridge_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    source_chain: u16,
+    emitter: [u8; 32],
+    sequence: u64,
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let bridge_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let claim_account = next_account_info(account_info_iter)?;
+    let verifier_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_owned_by(token_account, program_id)?;
+    let config = load_bridge_config(program_id, bridge_account, mint_account.key)?;
+    validators::assert_signer(verifier_account)?;
+    if config.verifier != *verifier_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+
+    // 领取 PDA 只能创建一次，防止同一条消息重复铸造
+    let (claim_key, bump) = find_bridge_claim_address(mint_account.key, source_chain, &emitter, sequence, program_id);
+    if claim_key != *claim_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !claim_account.data_is_empty() {
+        msg!("Bridge message {}:{} already minted", source_chain, sequence);
+        return Err(TokenError::AlreadyClaimed.into());
+    }
+    create_pda_account(
+        verifier_account,
+        claim_account,
+        system_program_account,
+        program_id,
+        BridgeClaim::LEN,
+        &[
+            BRIDGE_CLAIM_SEED,
+            mint_account.key.as_ref(),
+            &source_chain.to_le_bytes(),
+            &emitter,
+            &sequence.to_le_bytes(),
+            &[bump],
+        ],
+    )?;
+    pack_state(
+        &BridgeClaim { is_initialized: true, recipient: *token_account.key, amount },
+        &mut claim_account.data.borrow_mut(),
+    )?;
+
+    let mut mint = LoadedMint::load(program_id, mint_account)?;
+    let token = LoadedTokenAccount::load(program_id, token_account)?;
+    check_not_paused(mint_account, PausedOperation::Minting)?;
+    mint.state.mint_supply(amount)?;
+    mint.save()?;
+    credit_minted(program_id, &mint, token, amount, account_info_iter)?;
+
+    BridgeEvent::Minted { mint: *mint_account.key, source_chain, emitter, sequence, amount }.emit()?;
+    msg!("Bridged in {} tokens to {} (chain {}, sequence {})", amount, token_account.key, source_chain, sequence);
+    Ok(())
+}
+
+/// 销毁代币
+fn process_burn(ctx: &mut ProcessingContext, amount: u64) -> ProgramResult {
+    let token_account = ctx.next_account()?;
+    let mint_account = ctx.next_account()?;
+    let authority_account = ctx.next_signer()?;
+
+    // 两个账户先读取再分别写回，不能是同一个账户
+    if token_account.key == mint_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let mut token = LoadedTokenAccount::load(ctx.program_id, token_account)?;
+    let mut mint = LoadedMint::load(ctx.program_id, mint_account)?;
+    // 冻结、暂停与供应量都按传入的铸币计算，必须是代币账户所属的铸币
+    validators::assert_mint_matches(&token.state.mint, mint_account.key)?;
+    // 签名者依次按所有者、铸币的销毁权限、受托人（额度 PDA 紧随其后）处理
+    let is_burner = burn_authority(mint_account)? == Some(*authority_account.key);
+    let delegate_allowance = if token.state.owner == *authority_account.key || is_burner {
+        None
+    } else {
+        let allowance_account = ctx.next_account()?;
+        let allowance =
+            load_delegate_allowance(ctx.program_id, allowance_account, token_account.key, authority_account, amount)?;
+        Some((allowance_account, allowance))
+    };
+    sync_rescale(mint_account, &mut token)?;
+    token.check_spendable(amount)?;
+    check_global_freeze(mint_account, token_account)?;
+    check_not_paused(mint_account, PausedOperation::Burning)?;
+    if let Some((allowance_account, mut allowance)) = delegate_allowance {
+        spend_allowance(allowance_account, &mut allowance, amount, token.state.amount - amount)?;
+    }
+
+    // 更新代币账户与铸币账户
+    let balance_before = token.state.amount;
+    (mint.state.supply, token.state.amount) = balance::burn_amounts(mint.state.supply, token.state.amount, amount)?;
+    token.save()?;
+    mint.save()?;
+    update_holder_count(mint_account, &[(balance_before, token.state.amount)])?;
+    move_delegated_votes(ctx.program_id, mint_account.key, token.state.vote_delegate, None, amount, &mut ctx.accounts)?;
+
+    msg!("Burned {} tokens from {}", amount, token_account.key);
+    Ok(())
+}
+
+/// 铸币的销毁权限（没有 BurnAuthority 扩展时为 None）
+fn burn_authority(mint_account: &AccountInfo) -> Result<Option<Pubkey>, ProgramError> {
+    let burn_authority = extension::get_extension::<Mint, BurnAuthority>(&mint_account.data.borrow())?;
+    Ok(burn_authority.and_then(|burn_authority| burn_authority.authority))
+}
+
+/// 通用权限变更
+fn process_set_authority(
+    ctx: &mut ProcessingContext,
+    authority_type: AuthorityType,
+    new_authority: Option<Pubkey>,
+) -> ProgramResult {
+    let mut mint = ctx.next_mint()?;
+    let current_authority_account = ctx.next_account()?;
+
+    validators::assert_signer(current_authority_account)?;
+    let signing_authority = match authority_type {
+        // 从未设置销毁权限（没有扩展）时由铸币权限设置；扩展存在但为 None 表示已撤销，任何人都不能再设置
+        AuthorityType::BurnTokens => match extension::get_extension::<Mint, BurnAuthority>(&mint.info.data.borrow())? {
+            Some(burn_authority) => burn_authority.authority,
+            None => mint.state.mint_authority,
+        },
+        _ => current_authority(mint.info, &mint.state, authority_type)?,
+    };
+    if signing_authority != Some(*current_authority_account.key) {
+        return Err(TokenError::Unauthorized.into());
+    }
+
+    let old_authority = replace_authority(mint.info, &mut mint.state, authority_type, new_authority)?;
+    mint.save()?;
+    emit_authority_changed(mint.info.key, authority_type, old_authority, new_authority)?;
+
+    msg!("{:?} authority updated", authority_type);
+    Ok(())
+}
+
+/// 设置铸币权限（已弃用）：转交 SetAuthority 处理，成功后发出弃用事件
+fn process_set_mint_authority(ctx: &mut ProcessingContext, new_authority: Option<Pubkey>) -> ProgramResult {
+    let mint = *ctx.accounts.as_slice().first().ok_or(ProgramError::NotEnoughAccountKeys)?.key;
+    process_set_authority(ctx, AuthorityType::MintTokens, new_authority)?;
+    AuthorityEvent::DeprecatedSetMintAuthority { mint, slot: Clock::get()?.slot }.emit()?;
+    msg!("SetMintAuthority is deprecated, use SetAuthority with MintTokens");
+    Ok(())
+}
+
+/// 初始化联合曲线
+fn process_initialize_curve(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    curve: CurveType,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let curve_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_signer(authority_account)?;
+    curve.validate()?;
+
+    let mut mint = Mint::deserialize(&mint_account.data.borrow())?;
+    if mint.mint_authority != Some(*authority_account.key) {
+        return Err(TokenError::Unauthorized.into());
+    }
+
+    // 验证 PDA 地址
+    let (curve_key, bump) =
+        Pubkey::find_program_address(&[CURVE_SEED, mint_account.key.as_ref()], program_id);
+    let (vault_key, vault_bump) =
+        Pubkey::find_program_address(&[CURVE_VAULT_SEED, mint_account.key.as_ref()], program_id);
+    if curve_key != *curve_account.key || vault_key != *vault_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    create_pda_account(
+        payer_account,
+        curve_account,
+        system_program_account,
+        program_id,
+        BondingCurve::LEN,
+        &[CURVE_SEED, mint_account.key.as_ref(), &[bump]],
+    )?;
+    create_pda_account(
+        payer_account,
+        vault_account,
+        system_program_account,
+        program_id,
+        0,
+        &[CURVE_VAULT_SEED, mint_account.key.as_ref(), &[vault_bump]],
+    )?;
+
+    let state = BondingCurve {
+        is_initialized: true,
+        mint: *mint_account.key,
+        curve,
+        bump,
+        vault_bump,
+    };
+    pack_state(&state, &mut curve_account.data.borrow_mut())?;
+
+    // 铸币权限移交给曲线 PDA，之后只能通过曲线铸造
+    let old_authority = mint.mint_authority.replace(curve_key);
+    mint.serialize(&mut mint_account.data.borrow_mut())?;
+    emit_authority_changed(mint_account.key, AuthorityType::MintTokens, old_authority, mint.mint_authority)?;
+
+    msg!("Bonding curve initialized for mint {}: {:?}", mint_account.key, state.curve);
+    Ok(())
+}
+
+/// 加载并校验联合曲线状态及金库地址
+fn load_bonding_curve(
+    program_id: &Pubkey,
+    mint_account: &AccountInfo,
+    curve_account: &AccountInfo,
+    vault_account: &AccountInfo,
+) -> Result<BondingCurve, ProgramError> {
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_owned_by(curve_account, program_id)?;
+    validators::assert_owned_by(vault_account, program_id)?;
+    let state: BondingCurve = unpack_state(&curve_account.data.borrow())?;
+    validators::assert_initialized(&state)?;
+    validators::assert_mint_matches(&state.mint, mint_account.key)?;
+    let vault_key = Pubkey::create_program_address(
+        &[CURVE_VAULT_SEED, mint_account.key.as_ref(), &[state.vault_bump]],
+        program_id,
+    )?;
+    if vault_key != *vault_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    Ok(state)
+}
+
+/// 按曲线购买代币
+fn process_buy_from_curve(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    max_lamports: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let curve_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let buyer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(buyer_account)?;
+    validators::assert_owned_by(token_account, program_id)?;
+    let state = load_bonding_curve(program_id, mint_account, curve_account, vault_account)?;
+
+    let mut mint = LoadedMint::load(program_id, mint_account)?;
+    let token = LoadedTokenAccount::load(program_id, token_account)?;
+    validators::assert_mint_matches(&token.state.mint, mint_account.key)?;
+    token.check_not_frozen()?;
+
+    // 计算价格
+    let new_supply = mint.state.supply.checked_add(amount).ok_or(TokenError::Overflow)?;
+    let cost = state
+        .curve
+        .reserve_for_supply(new_supply, mint.state.decimals)?
+        .checked_sub(state.curve.reserve_for_supply(mint.state.supply, mint.state.decimals)?)
+        .ok_or(TokenError::Overflow)?;
+    let cost = u64::try_from(cost).map_err(|_| TokenError::Overflow)?;
+    if cost > max_lamports {
+        msg!("Curve cost {} exceeds max_lamports {}", cost, max_lamports);
+        return Err(TokenError::SlippageExceeded.into());
+    }
+
+    // SOL 存入金库
+    invoke(
+        &system_instruction::transfer(buyer_account.key, vault_account.key, cost),
+        &[
+            buyer_account.clone(),
+            vault_account.clone(),
+            system_program_account.clone(),
+        ],
+    )?;
+
+    check_not_paused(mint_account, PausedOperation::Minting)?;
+    mint.state.mint_supply(amount)?;
+    mint.save()?;
+    credit_minted(program_id, &mint, token, amount, account_info_iter)?;
+
+    msg!("Bought {} tokens from curve for {} lamports", amount, cost);
+    Ok(())
+}
+
+/// 按曲线卖出代币
+fn process_sell_to_curve(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    min_lamports: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let curve_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(owner_account)?;
+    validators::assert_owned_by(token_account, program_id)?;
+    let state = load_bonding_curve(program_id, mint_account, curve_account, vault_account)?;
+
+    check_global_freeze(mint_account, token_account)?;
+    check_not_paused(mint_account, PausedOperation::Burning)?;
+    let mut mint = Mint::deserialize(&mint_account.data.borrow())?;
+    let mut token = LoadedTokenAccount::load(program_id, token_account)?;
+    validators::assert_mint_matches(&token.state.mint, mint_account.key)?;
+    if token.state.owner != *owner_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    sync_rescale(mint_account, &mut token)?;
+    token.check_spendable(amount)?;
+
+    // 计算可赎回的 SOL
+    let new_supply = mint.supply.checked_sub(amount).ok_or(TokenError::InsufficientFunds)?;
+    let refund = state
+        .curve
+        .reserve_for_supply(mint.supply, mint.decimals)?
+        .checked_sub(state.curve.reserve_for_supply(new_supply, mint.decimals)?)
+        .ok_or(TokenError::Overflow)?;
+    let refund = u64::try_from(refund).map_err(|_| TokenError::Overflow)?;
+    if refund < min_lamports {
+        msg!("Curve refund {} below min_lamports {}", refund, min_lamports);
+        return Err(TokenError::SlippageExceeded.into());
+    }
+
+    // 金库必须保持租金豁免
+    let rent = Rent::get()?;
+    let available = vault_account
+        .lamports()
+        .saturating_sub(rent.minimum_balance(vault_account.data_len()));
+    if available < refund {
+        return Err(TokenError::InsufficientFunds.into());
+    }
+
+    mint.supply = new_supply;
+    mint.serialize(&mut mint_account.data.borrow_mut())?;
+    let balance_before = token.state.amount;
+    token.state.amount -= amount;
+    token.save()?;
+    update_holder_count(mint_account, &[(balance_before, token.state.amount)])?;
+    move_delegated_votes(program_id, mint_account.key, token.state.vote_delegate, None, amount, account_info_iter)?;
+
+    **vault_account.try_borrow_mut_lamports()? -= refund;
+    **owner_account.try_borrow_mut_lamports()? += refund;
+
+    msg!("Sold {} tokens to curve for {} lamports", amount, refund);
+    Ok(())
+}
+
+/// 配置预言机喂价
+fn process_configure_oracle(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    price_feed: Option<Pubkey>,
+    max_staleness_slots: u64,
+    collateral_ratio_bps: u16,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_signer(authority_account)?;
+    // 抵押率不得低于 100%
+    if collateral_ratio_bps < 10_000 {
+        return Err(TokenError::InvalidOracle.into());
+    }
+
+    let (config_key, bump) =
+        Pubkey::find_program_address(&[ORACLE_SEED, mint_account.key.as_ref()], program_id);
+    if config_key != *config_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if config_account.data_is_empty() {
+        // 首次配置：由铸币权限发起，并把铸币权限移交给配置 PDA
+        let mut mint = Mint::deserialize(&mint_account.data.borrow())?;
+        if mint.mint_authority != Some(*authority_account.key) {
+            return Err(TokenError::Unauthorized.into());
+        }
+        let (_, vault_bump) =
+            Pubkey::find_program_address(&[COLLATERAL_VAULT_SEED, mint_account.key.as_ref()], program_id);
+
+        create_pda_account(
+            payer_account,
+            config_account,
+            system_program_account,
+            program_id,
+            OracleConfig::LEN,
+            &[ORACLE_SEED, mint_account.key.as_ref(), &[bump]],
+        )?;
+        let config = OracleConfig {
+            is_initialized: true,
+            mint: *mint_account.key,
+            authority: *authority_account.key,
+            price_feed,
+            max_staleness_slots,
+            collateral_ratio_bps,
+            bump,
+            vault_bump,
+        };
+        pack_state(&config, &mut config_account.data.borrow_mut())?;
+
+        let old_authority = mint.mint_authority.replace(config_key);
+        mint.serialize(&mut mint_account.data.borrow_mut())?;
+        emit_authority_changed(mint_account.key, AuthorityType::MintTokens, old_authority, mint.mint_authority)?;
+    } else {
+        validators::assert_owned_by(config_account, program_id)?;
+        let mut config: OracleConfig = unpack_state(&config_account.data.borrow())?;
+        if config.authority != *authority_account.key {
+            return Err(TokenError::Unauthorized.into());
+        }
+        config.price_feed = price_feed;
+        config.max_staleness_slots = max_staleness_slots;
+        config.collateral_ratio_bps = collateral_ratio_bps;
+        pack_state(&config, &mut config_account.data.borrow_mut())?;
+    }
+
+    msg!("Oracle configured for mint {}: feed {:?}", mint_account.key, price_feed);
+    Ok(())
+}
+
+/// 按预言机价格抵押铸造
+fn process_mint_against_collateral(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    collateral_lamports: u64,
+    min_amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let price_feed_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let depositor_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(depositor_account)?;
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_owned_by(config_account, program_id)?;
+    validators::assert_owned_by(token_account, program_id)?;
+
+    let config: OracleConfig = unpack_state(&config_account.data.borrow())?;
+    if !config.is_initialized || config.mint != *mint_account.key {
+        return Err(TokenError::MintMismatch.into());
+    }
+    if config.price_feed != Some(*price_feed_account.key) {
+        msg!("Price feed {} is not configured for this mint", price_feed_account.key);
+        return Err(TokenError::InvalidOracle.into());
+    }
+    let vault_key = Pubkey::create_program_address(
+        &[COLLATERAL_VAULT_SEED, mint_account.key.as_ref(), &[config.vault_bump]],
+        program_id,
+    )?;
+    if vault_key != *vault_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // 校验价格：状态、时效性、取置信区间下界
+    let price = PythPrice::load(&price_feed_account.data.borrow())?;
+    let current_slot = Clock::get()?.slot;
+    if current_slot.saturating_sub(price.publish_slot) > config.max_staleness_slots {
+        msg!("Pyth price is stale: published at slot {}, now {}", price.publish_slot, current_slot);
+        return Err(TokenError::StaleOraclePrice.into());
+    }
+    let conservative_price = price.price.saturating_sub(price.conf as i64);
+    if conservative_price <= 0 {
+        return Err(TokenError::InvalidOracle.into());
+    }
+
+    let mut mint = LoadedMint::load(program_id, mint_account)?;
+    let token = LoadedTokenAccount::load(program_id, token_account)?;
+    validators::assert_mint_matches(&token.state.mint, mint_account.key)?;
+    token.check_not_frozen()?;
+
+    // amount = lamports / 1e9 * price * 10^expo * 10^decimals / 抵押率
+    let mut numerator = (collateral_lamports as u128)
+        .checked_mul(conservative_price as u128)
+        .and_then(|v| v.checked_mul(math::one_token(mint.state.decimals).ok()?))
+        .and_then(|v| v.checked_mul(10_000))
+        .ok_or(TokenError::Overflow)?;
+    let mut denominator = 1_000_000_000u128
+        .checked_mul(config.collateral_ratio_bps as u128)
+        .ok_or(TokenError::Overflow)?;
+    if price.expo < 0 {
+        denominator = denominator
+            .checked_mul(10u128.checked_pow(price.expo.unsigned_abs()).ok_or(TokenError::Overflow)?)
+            .ok_or(TokenError::Overflow)?;
+    } else {
+        numerator = numerator
+            .checked_mul(10u128.checked_pow(price.expo as u32).ok_or(TokenError::Overflow)?)
+            .ok_or(TokenError::Overflow)?;
+    }
+    let amount = u64::try_from(numerator / denominator).map_err(|_| TokenError::Overflow)?;
+    if amount < min_amount {
+        msg!("Collateral mint amount {} below min_amount {}", amount, min_amount);
+        return Err(TokenError::SlippageExceeded.into());
+    }
+
+    // 抵押品存入金库（系统账户 PDA，首次存入不得低于租金豁免最低额）
+    invoke(
+        &system_instruction::transfer(depositor_account.key, vault_account.key, collateral_lamports),
+        &[
+            depositor_account.clone(),
+            vault_account.clone(),
+            system_program_account.clone(),
+        ],
+    )?;
+
+    check_not_paused(mint_account, PausedOperation::Minting)?;
+    mint.state.mint_supply(amount)?;
+    mint.save()?;
+    credit_minted(program_id, &mint, token, amount, account_info_iter)?;
+
+    msg!(
+        "Minted {} tokens against {} lamports collateral at price {}e{}",
+        amount,
+        collateral_lamports,
+        price.price,
+        price.expo
+    );
+    Ok(())
+}
+
+/// 开启弹性供应模式
+fn process_enable_rebase(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_signer(authority_account)?;
+    let mint = Mint::deserialize(&mint_account.data.borrow())?;
+    if mint.mint_authority != Some(*authority_account.key) {
+        return Err(TokenError::Unauthorized.into());
+    }
+
+    let (config_key, bump) =
+        Pubkey::find_program_address(&[REBASE_SEED, mint_account.key.as_ref()], program_id);
+    if config_key != *config_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    create_pda_account(
+        payer_account,
+        config_account,
+        system_program_account,
+        program_id,
+        RebaseConfig::LEN,
+        &[REBASE_SEED, mint_account.key.as_ref(), &[bump]],
+    )?;
+
+    let config = RebaseConfig {
+        is_initialized: true,
+        mint: *mint_account.key,
+        authority: *authority_account.key,
+        multiplier: REBASE_SCALE,
+        rebase_count: 0,
+    };
+    pack_state(&config, &mut config_account.data.borrow_mut())?;
+
+    msg!("Rebase mode enabled for mint {}", mint_account.key);
+    Ok(())
+}
+
+/// 调整 rebase 缩放因子
+fn process_rebase(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    numerator: u64,
+    denominator: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(config_account, program_id)?;
+    validators::assert_signer(authority_account)?;
+    if numerator == 0 || denominator == 0 {
+        return Err(TokenError::InvalidRebase.into());
+    }
+
+    let mut config: RebaseConfig = unpack_state(&config_account.data.borrow())?;
+    if !config.is_initialized || config.mint != *mint_account.key {
+        return Err(TokenError::MintMismatch.into());
+    }
+    if config.authority != *authority_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+
+    let multiplier = config
+        .multiplier
+        .checked_mul(numerator as u128)
+        .ok_or(TokenError::Overflow)?
+        / denominator as u128;
+    // 缩放因子不能归零，否则所有余额都无法换算回来
+    if multiplier == 0 {
+        return Err(TokenError::InvalidRebase.into());
+    }
+
+    let old_multiplier = config.multiplier;
+    config.multiplier = multiplier;
+    config.rebase_count += 1;
+    pack_state(&config, &mut config_account.data.borrow_mut())?;
+
+    msg!(
+        "Rebase #{}: multiplier {} -> {} ({}/{})",
+        config.rebase_count,
+        old_multiplier,
+        multiplier,
+        numerator,
+        denominator
+    );
+    Ok(())
+}
+
+/// 注入分红
+fn process_fund_distribution(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    distribution_id: u64,
+    snapshot_root: [u8; 32],
+    snapshot_supply: u64,
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let distribution_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let source_account = next_account_info(account_info_iter)?;
+    let funder_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(funder_account)?;
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_owned_by(vault_account, program_id)?;
+    validators::assert_owned_by(source_account, program_id)?;
+    let mint = Mint::deserialize(&mint_account.data.borrow())?;
+    validators::assert_initialized(&mint)?;
+    if mint.mint_authority != Some(*funder_account.key) {
+        msg!("Distributions for {} must be funded by its mint authority", mint_account.key);
+        return Err(TokenError::Unauthorized.into());
+    }
+    if snapshot_supply == 0 || amount == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (distribution_key, bump) = Pubkey::find_program_address(
+        &[DISTRIBUTION_SEED, mint_account.key.as_ref(), &distribution_id.to_le_bytes()],
+        program_id,
+    );
+    if distribution_key != *distribution_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // 金库必须由分红 PDA 持有，且与注资账户同一奖励铸币
+    let mut vault = LoadedTokenAccount::load(program_id, vault_account)?;
+    let mut source = LoadedTokenAccount::load(program_id, source_account)?;
+    if vault.state.owner != distribution_key {
+        msg!("Reward vault must be owned by the distribution PDA");
+        return Err(TokenError::Unauthorized.into());
+    }
+    validators::assert_mint_matches(&vault.state.mint, &source.state.mint)?;
+    if source.state.owner != *funder_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    let reward_mint_account = next_transfer_mint(account_info_iter, source_account, &source.state.mint)?;
+    source.check_not_frozen()?;
+    vault.check_not_frozen()?;
+
+    create_pda_account(
+        funder_account,
+        distribution_account,
+        system_program_account,
+        program_id,
+        Distribution::LEN,
+        &[DISTRIBUTION_SEED, mint_account.key.as_ref(), &distribution_id.to_le_bytes(), &[bump]],
+    )?;
+
+    move_synced(reward_mint_account, &mut source, &mut vault, amount)?;
+    move_delegated_votes(
+        program_id,
+        &vault.state.mint,
+        source.state.vote_delegate,
+        vault.state.vote_delegate,
+        amount,
+        account_info_iter,
+    )?;
+
+    let distribution = Distribution {
+        is_initialized: true,
+        mint: *mint_account.key,
+        distribution_id,
+        funder: *funder_account.key,
+        reward_mint: vault.state.mint,
+        reward_vault: *vault_account.key,
+        snapshot_root,
+        snapshot_supply,
+        snapshot_slot: Clock::get()?.slot,
+        total_amount: amount,
+        claimed_amount: 0,
+        bump,
+    };
+    pack_state(&distribution, &mut distribution_account.data.borrow_mut())?;
+
+    msg!(
+        "Distribution {} funded with {} reward tokens over snapshot supply {}",
+        distribution_id,
+        amount,
+        snapshot_supply
+    );
+    Ok(())
+}
+
+/// 领取分红
+fn process_claim_dividend(ctx: &mut ProcessingContext, snapshot_balance: u64, proof: Vec<[u8; 32]>) -> ProgramResult {
+    let mint_account = ctx.next_account()?;
+    let distribution_account = ctx.next_account()?;
+    let mut vault = ctx.next_token_account()?;
+    let holder = ctx.next_token_account()?;
+    let mut destination = ctx.next_token_account()?;
+    let claim_account = ctx.next_account()?;
+    let owner_account = ctx.next_account()?;
+    let system_program_account = ctx.next_account()?;
+    let program_id = ctx.program_id;
+    let holder_account = holder.info;
+
+    validators::assert_owned_by(distribution_account, program_id)?;
+    let mut distribution: Distribution = unpack_state(&distribution_account.data.borrow())?;
+    if !distribution.is_initialized || distribution.mint != *mint_account.key {
+        return Err(TokenError::MintMismatch.into());
+    }
+    if distribution.reward_vault != *vault.info.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // 持仓账户的所有者签名领取
+    validators::assert_mint_matches(&holder.state.mint, &distribution.mint)?;
+    holder.check_owner(owner_account)?;
+
+    let leaf = Distribution::leaf(holder_account.key, snapshot_balance);
+    if !distribution.verify_proof(leaf, &proof) {
+        msg!("Snapshot proof does not match distribution root");
+        return Err(TokenError::InvalidProof.into());
+    }
+
+    // 领取记录 PDA 只能创建一次，防止重复领取
+    let (claim_key, claim_bump) = Pubkey::find_program_address(
+        &[DIVIDEND_CLAIM_SEED, distribution_account.key.as_ref(), holder_account.key.as_ref()],
+        program_id,
+    );
+    if claim_key != *claim_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !claim_account.data_is_empty() {
+        return Err(TokenError::AlreadyClaimed.into());
+    }
+
+    let share = (snapshot_balance as u128)
+        .checked_mul(distribution.total_amount as u128)
+        .ok_or(TokenError::Overflow)?
+        / distribution.snapshot_supply as u128;
+    let share = u64::try_from(share).map_err(|_| TokenError::Overflow)?;
+
+    validators::assert_mint_matches(&destination.state.mint, &distribution.reward_mint)?;
+    destination.check_not_frozen()?;
+    let reward_mint_account = next_transfer_mint(&mut ctx.accounts, vault.info, &distribution.reward_mint)?;
+
+    create_pda_account(
+        owner_account,
+        claim_account,
+        system_program_account,
+        program_id,
+        DividendClaim::LEN,
+        &[
+            DIVIDEND_CLAIM_SEED,
+            distribution_account.key.as_ref(),
+            holder_account.key.as_ref(),
+            &[claim_bump],
+        ],
+    )?;
+    let claim = DividendClaim {
+        is_initialized: true,
+        distribution: *distribution_account.key,
+        holder_token_account: *holder_account.key,
+        amount: share,
+    };
+    pack_state(&claim, &mut claim_account.data.borrow_mut())?;
+
+    move_synced(reward_mint_account, &mut vault, &mut destination, share)?;
+    move_delegated_votes(
+        program_id,
+        &distribution.reward_mint,
+        vault.state.vote_delegate,
+        destination.state.vote_delegate,
+        share,
+        &mut ctx.accounts,
+    )?;
+
+    distribution.claimed_amount = distribution
+        .claimed_amount
+        .checked_add(share)
+        .ok_or(TokenError::Overflow)?;
+    pack_state(&distribution, &mut distribution_account.data.borrow_mut())?;
+
+    msg!("Claimed {} reward tokens for snapshot balance {}", share, snapshot_balance);
+    Ok(())
+}
+
+/// 委托投票权
+fn process_delegate_votes(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    delegatee: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(token_account, program_id)?;
+    validators::assert_signer(owner_account)?;
+    let mut token_acc = TokenAccount::deserialize(&token_account.data.borrow())?;
+    if token_acc.owner != *owner_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+
+    // 新受托人的检查点账户不存在时先创建
+    if let Some(new_delegatee) = delegatee {
+        if token_acc.vote_delegate != Some(new_delegatee) {
+            let slot_offset = usize::from(token_acc.vote_delegate.is_some());
+            let votes_account = accounts
+                .get(3 + slot_offset)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            let (votes_key, bump) = Pubkey::find_program_address(
+                &[VOTES_SEED, token_acc.mint.as_ref(), new_delegatee.as_ref()],
+                program_id,
+            );
+            if votes_key != *votes_account.key {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            if votes_account.data_is_empty() {
+                create_pda_account(
+                    owner_account,
+                    votes_account,
+                    system_program_account,
+                    program_id,
+                    VoteCheckpoints::LEN,
+                    &[VOTES_SEED, token_acc.mint.as_ref(), new_delegatee.as_ref(), &[bump]],
+                )?;
+                let checkpoints = VoteCheckpoints {
+                    is_initialized: true,
+                    mint: token_acc.mint,
+                    delegatee: new_delegatee,
+                    truncated: false,
+                    checkpoints: Vec::new(),
+                };
+                pack_state(&checkpoints, &mut votes_account.data.borrow_mut())?;
+            }
+        }
+    }
+
+    // 把当前余额对应的投票权从原受托人移到新受托人
+    move_delegated_votes(
+        program_id,
+        &token_acc.mint,
+        token_acc.vote_delegate,
+        delegatee,
+        token_acc.amount,
+        account_info_iter,
+    )?;
+
+    token_acc.vote_delegate = delegatee;
+    token_acc.serialize(&mut token_account.data.borrow_mut())?;
+
+    msg!("Votes of {} delegated to {:?}", token_account.key, delegatee);
+    Ok(())
+}
+
+/// 查询历史投票权
+fn process_get_votes_at(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    slot: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let votes_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(votes_account, program_id)?;
+    let checkpoints: VoteCheckpoints = unpack_state(&votes_account.data.borrow())?;
+    validators::assert_initialized(&checkpoints)?;
+
+    let votes = checkpoints.votes_at(slot)?;
+    set_return_data(&votes.to_le_bytes());
+
+    msg!("Votes of {} at slot {}: {}", checkpoints.delegatee, slot, votes);
+    Ok(())
+}
+
+/// 补充租金
+fn process_top_up_rent(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    lamports: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let target_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(target_account, program_id)?;
+    validators::assert_signer(payer_account)?;
+
+    let rent = Rent::get()?;
+    let required = rent.minimum_balance(target_account.data_len());
+    let deficit = required.saturating_sub(target_account.lamports());
+    let lamports = if lamports == 0 { deficit } else { lamports };
+
+    if lamports > 0 {
+        invoke(
+            &system_instruction::transfer(payer_account.key, target_account.key, lamports),
+            &[
+                payer_account.clone(),
+                target_account.clone(),
+                system_program_account.clone(),
+            ],
+        )?;
+    }
+
+    // 补充后重新校验租金豁免
+    if !rent.is_exempt(target_account.lamports(), target_account.data_len()) {
+        msg!(
+            "Account {} still below rent exemption: {} < {}",
+            target_account.key,
+            target_account.lamports(),
+            required
+        );
+        return Err(TokenError::NotRentExempt.into());
+    }
+
+    msg!("Topped up {} lamports to {}", lamports, target_account.key);
+    Ok(())
+}
+
+/// 创建关联代币账户并铸造
+fn process_mint_to_new_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let mint_authority_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    let mut mint = LoadedMint::load(program_id, mint_account)?;
+    mint.check_mint_authority(mint_authority_account)?;
+
+    let (associated_key, bump) =
+        get_associated_token_address(wallet_account.key, mint_account.key, program_id);
+    if associated_key != *token_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // 关联账户不存在时创建并初始化
+    let token = if token_account.data_is_empty() {
+        create_pda_account(
+            payer_account,
+            token_account,
+            system_program_account,
+            program_id,
+            TokenAccount::LEN,
+            &[ASSOCIATED_SEED, wallet_account.key.as_ref(), mint_account.key.as_ref(), &[bump]],
+        )?;
+        msg!("Associated token account created for wallet {}", wallet_account.key);
+        let state = TokenAccount::new(*mint_account.key, *wallet_account.key).with_nft(mint.state.is_nft);
+        LoadedTokenAccount { info: token_account, state }
+    } else {
+        let existing = LoadedTokenAccount::load(program_id, token_account)?;
+        validators::assert_mint_matches(&existing.state.mint, mint_account.key)?;
+        existing
+    };
+    token.check_not_frozen()?;
+
+    check_not_paused(mint_account, PausedOperation::Minting)?;
+    mint.state.mint_supply(amount)?;
+    mint.save()?;
+    credit_minted(program_id, &mint, token, amount, account_info_iter)?;
+
+    msg!("Minted {} tokens to associated account {}", amount, token_account.key);
+    Ok(())
+}
+
+/// 销毁代币并记录销毁凭证
+fn process_burn_with_receipt(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    receipt_id: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let receipt_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_owned_by(token_account, program_id)?;
+    let token_acc = TokenAccount::deserialize(&token_account.data.borrow())?;
+    validators::assert_mint_matches(&token_acc.mint, mint_account.key)?;
+
+    let id_bytes = receipt_id.to_le_bytes();
+    let (receipt_key, bump) = Pubkey::find_program_address(
+        &[BURN_RECEIPT_SEED, mint_account.key.as_ref(), owner_account.key.as_ref(), &id_bytes],
+        program_id,
+    );
+    if receipt_key != *receipt_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !receipt_account.data_is_empty() {
+        return Err(TokenError::ReceiptAlreadyExists.into());
+    }
+
+    // 复用 Burn 的权限与余额检查，剩余账户（投票检查点）原样透传
+    let mut burn_accounts = vec![token_account.clone(), mint_account.clone(), owner_account.clone()];
+    burn_accounts.extend(account_info_iter.cloned());
+    process_burn(&mut ProcessingContext::new(program_id, &burn_accounts), amount)?;
+
+    create_pda_account(
+        owner_account,
+        receipt_account,
+        system_program_account,
+        program_id,
+        BurnReceipt::LEN,
+        &[BURN_RECEIPT_SEED, mint_account.key.as_ref(), owner_account.key.as_ref(), &id_bytes, &[bump]],
+    )?;
+    let receipt = BurnReceipt {
+        is_initialized: true,
+        mint: *mint_account.key,
+        burner: *owner_account.key,
+        token_account: *token_account.key,
+        receipt_id,
+        amount,
+        slot: Clock::get()?.slot,
+    };
+    pack_state(&receipt, &mut receipt_account.data.borrow_mut())?;
+
+    msg!("Burn receipt {} recorded at {}", receipt_id, receipt_account.key);
+    Ok(())
+}
+
+/// 初始化 NFT 铸币账户
+fn process_initialize_nft_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint_authority: Pubkey,
+    freeze_authority: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(mint_account, program_id)?;
+    let rent = &Rent::from_account_info(rent_sysvar_account)?;
+    validators::assert_rent_exempt(rent, mint_account)?;
+    if mint_account.data_len() < Mint::LEN {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    if Mint::deserialize(&mint_account.data.borrow())?.is_initialized {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let mint = Mint::new_nft(mint_authority, freeze_authority);
+    mint.serialize(&mut mint_account.data.borrow_mut())?;
+
+    msg!("NFT mint {} initialized with authority: {}", mint_account.key, mint_authority);
+    Ok(())
+}
+
+/// 通过 CPI 创建 Metaplex 元数据账户
+fn process_create_metadata(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: MetadataArgs,
+    is_mutable: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let metadata_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+    let metadata_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(mint_account, program_id)?;
+    if *metadata_program_account.key != METADATA_PROGRAM_ID
+        || *system_program_account.key != system_program::id()
+    {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    validators::assert_signer(authority_account)?;
+    args.validate()?;
+
+    let mint = Mint::deserialize(&mint_account.data.borrow())?;
+    if mint.mint_authority != Some(*authority_account.key) {
+        return Err(TokenError::Unauthorized.into());
+    }
+    let (metadata_key, _) = find_metadata_address(mint_account.key);
+    if metadata_key != *metadata_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let instruction = create_metadata_accounts_v3_instruction(
+        metadata_account.key,
+        mint_account.key,
+        authority_account.key,
+        payer_account.key,
+        authority_account.key,
+        &args,
+        is_mutable,
+    )?;
+    invoke(
+        &instruction,
+        &[
+            metadata_account.clone(),
+            mint_account.clone(),
+            authority_account.clone(),
+            payer_account.clone(),
+            authority_account.clone(),
+            system_program_account.clone(),
+            metadata_program_account.clone(),
+        ],
+    )?;
+
+    msg!("Metadata {} created for mint {}: {}", metadata_account.key, mint_account.key, args.name);
+    Ok(())
+}
+
+/// 校验调用者持有母版 NFT，返回母版铸币状态
+fn check_master_holder(
+    program_id: &Pubkey,
+    mint_account: &AccountInfo,
+    token_account: &AccountInfo,
+    owner_account: &AccountInfo,
+) -> Result<Mint, ProgramError> {
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_owned_by(token_account, program_id)?;
+    validators::assert_signer(owner_account)?;
+    let mint = Mint::deserialize(&mint_account.data.borrow())?;
+    if !mint.is_nft || mint.supply != NFT_SUPPLY {
+        return Err(TokenError::NotNftMint.into());
+    }
+    let token_acc = TokenAccount::deserialize(&token_account.data.borrow())?;
+    validators::assert_mint_matches(&token_acc.mint, mint_account.key)?;
+    if token_acc.owner != *owner_account.key || token_acc.amount != NFT_SUPPLY {
+        return Err(TokenError::Unauthorized.into());
+    }
+    Ok(mint)
+}
+
+/// 创建母版版本
+fn process_create_master_edition(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    max_supply: Option<u64>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let master_edition_account = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    check_master_holder(program_id, mint_account, token_account, owner_account)?;
+
+    let (master_edition_key, bump) =
+        Pubkey::find_program_address(&[MASTER_EDITION_SEED, mint_account.key.as_ref()], program_id);
+    if master_edition_key != *master_edition_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    create_pda_account(
+        owner_account,
+        master_edition_account,
+        system_program_account,
+        program_id,
+        MasterEdition::LEN,
+        &[MASTER_EDITION_SEED, mint_account.key.as_ref(), &[bump]],
+    )?;
+
+    let master_edition = MasterEdition {
+        is_initialized: true,
+        mint: *mint_account.key,
+        max_supply,
+        supply: 0,
+        bump,
+    };
+    pack_state(&master_edition, &mut master_edition_account.data.borrow_mut())?;
+
+    msg!("Master edition created for mint {} with max supply {:?}", mint_account.key, max_supply);
+    Ok(())
+}
+
+/// 从母版印刷编号副本
+fn process_print_edition(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let master_edition_account = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let new_mint_account = next_account_info(account_info_iter)?;
+    let edition_account = next_account_info(account_info_iter)?;
+    let destination_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    check_master_holder(program_id, mint_account, token_account, owner_account)?;
+    validators::assert_owned_by(master_edition_account, program_id)?;
+    validators::assert_owned_by(new_mint_account, program_id)?;
+    validators::assert_owned_by(destination_account, program_id)?;
+
+    let mut master_edition: MasterEdition = unpack_state(&master_edition_account.data.borrow())?;
+    if !master_edition.is_initialized || master_edition.mint != *mint_account.key {
+        return Err(TokenError::MintMismatch.into());
+    }
+    let edition_number = master_edition.supply.checked_add(1).ok_or(TokenError::Overflow)?;
+    if master_edition.max_supply.is_some_and(|max_supply| edition_number > max_supply) {
+        msg!("Master edition {} reached max supply", master_edition_account.key);
+        return Err(TokenError::EditionSupplyExceeded.into());
+    }
+
+    // 副本必须是尚未铸造的 NFT 铸币，且铸币权限由母版持有人掌握
+    let mut new_mint = LoadedMint::load(program_id, new_mint_account)?;
+    if !new_mint.state.is_nft || new_mint.state.supply != 0 {
+        return Err(TokenError::NotNftMint.into());
+    }
+    if new_mint.state.mint_authority != Some(*owner_account.key) {
+        return Err(TokenError::Unauthorized.into());
+    }
+    let destination = LoadedTokenAccount::load(program_id, destination_account)?;
+    validators::assert_mint_matches(&destination.state.mint, new_mint_account.key)?;
+    destination.check_not_frozen()?;
+
+    let (edition_key, edition_bump) =
+        Pubkey::find_program_address(&[EDITION_SEED, new_mint_account.key.as_ref()], program_id);
+    if edition_key != *edition_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    create_pda_account(
+        owner_account,
+        edition_account,
+        system_program_account,
+        program_id,
+        Edition::LEN,
+        &[EDITION_SEED, new_mint_account.key.as_ref(), &[edition_bump]],
+    )?;
+    let edition = Edition {
+        is_initialized: true,
+        parent: *mint_account.key,
+        mint: *new_mint_account.key,
+        edition: edition_number,
+    };
+    pack_state(&edition, &mut edition_account.data.borrow_mut())?;
+
+    // 铸造副本（NFT 模式下自动撤销铸币权限）
+    check_not_paused(new_mint_account, PausedOperation::Minting)?;
+    new_mint.state.mint_supply(NFT_SUPPLY)?;
+    new_mint.save()?;
+    credit_minted(program_id, &new_mint, destination, NFT_SUPPLY, account_info_iter)?;
+
+    master_edition.supply = edition_number;
+    pack_state(&master_edition, &mut master_edition_account.data.borrow_mut())?;
+
+    msg!("Printed edition {} of {} as mint {}", edition_number, mint_account.key, new_mint_account.key);
+    Ok(())
+}
+
+/// 设置 NFT 转账程序白名单
+fn process_set_transfer_allowlist(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    programs: Vec<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let allowlist_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_signer(authority_account)?;
+    if programs.len() > MAX_ALLOWED_PROGRAMS {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let mint = Mint::deserialize(&mint_account.data.borrow())?;
+    if !mint.is_nft {
+        return Err(TokenError::NotNftMint.into());
+    }
+
+    let (allowlist_key, bump) =
+        Pubkey::find_program_address(&[TRANSFER_ALLOWLIST_SEED, mint_account.key.as_ref()], program_id);
+    if allowlist_key != *allowlist_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let allowlist = if allowlist_account.data_is_empty() {
+        // 首次设置：由铸币权限发起
+        if mint.mint_authority != Some(*authority_account.key) {
+            return Err(TokenError::Unauthorized.into());
+        }
+        create_pda_account(
+            payer_account,
+            allowlist_account,
+            system_program_account,
+            program_id,
+            TransferAllowlist::LEN,
+            &[TRANSFER_ALLOWLIST_SEED, mint_account.key.as_ref(), &[bump]],
+        )?;
+        TransferAllowlist {
+            is_initialized: true,
+            mint: *mint_account.key,
+            authority: *authority_account.key,
+            programs,
+        }
+    } else {
+        validators::assert_owned_by(allowlist_account, program_id)?;
+        let mut allowlist: TransferAllowlist = unpack_state(&allowlist_account.data.borrow())?;
+        if allowlist.authority != *authority_account.key {
+            return Err(TokenError::Unauthorized.into());
+        }
+        allowlist.programs = programs;
+        allowlist
+    };
+    pack_state(&allowlist, &mut allowlist_account.data.borrow_mut())?;
+
+    msg!("Transfer allowlist for mint {}: {:?}", mint_account.key, allowlist.programs);
+    Ok(())
+}
+
+/// 设置转账速度限制
+fn process_set_velocity_limit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    max_amount: u64,
+    window_slots: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let tracker_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    let mut token_acc = check_freeze_authority(program_id, token_account, mint_account, authority_account)?;
+    if max_amount > 0 && window_slots == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (tracker_key, bump) =
+        Pubkey::find_program_address(&[VELOCITY_SEED, token_account.key.as_ref()], program_id);
+    if tracker_key != *tracker_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if tracker_account.data_is_empty() {
+        create_pda_account(
+            payer_account,
+            tracker_account,
+            system_program_account,
+            program_id,
+            VelocityTracker::LEN,
+            &[VELOCITY_SEED, token_account.key.as_ref(), &[bump]],
+        )?;
+    } else {
+        validators::assert_owned_by(tracker_account, program_id)?;
+    }
+    let tracker = VelocityTracker {
+        is_initialized: true,
+        token_account: *token_account.key,
+        max_amount,
+        window_slots,
+        window_start_slot: Clock::get()?.slot,
+        window_amount: 0,
+    };
+    pack_state(&tracker, &mut tracker_account.data.borrow_mut())?;
+
+    token_acc.set_velocity_limited(max_amount > 0);
+    token_acc.serialize(&mut token_account.data.borrow_mut())?;
+
+    msg!("Velocity limit for {}: {} per {} slots", token_account.key, max_amount, window_slots);
+    Ok(())
+}
+
+/// 冻结代币账户，可选定时解冻
+fn process_freeze_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    thaw_at_slot: Option<u64>,
+    reason_code: u16,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+
+    let mut token_acc = check_freeze_authority(program_id, token_account, mint_account, authority_account)?;
+    check_freeze_reason(reason_code)?;
+    let slot = Clock::get()?.slot;
+    if thaw_at_slot.is_some_and(|thaw_at_slot| thaw_at_slot <= slot) {
+        msg!("Thaw slot {:?} must be after the current slot {}", thaw_at_slot, slot);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    token_acc.set_frozen(true);
+    token_acc.serialize(&mut token_account.data.borrow_mut())?;
+    set_scheduled_thaw(token_account, thaw_at_slot)?;
+    AuthorityEvent::AccountFrozen { mint: token_acc.mint, account: *token_account.key, reason_code, slot }.emit()?;
+
+    msg!("Token account {} frozen until slot {:?} (reason {})", token_account.key, thaw_at_slot, reason_code);
+    Ok(())
+}
+
+/// 冻结权限给出的原因码不能为 0（未说明）或程序保留的原因码
+fn check_freeze_reason(reason_code: u16) -> ProgramResult {
+    if reason_code == 0 || reason_code == FREEZE_REASON_VELOCITY_LIMIT {
+        msg!("Reason code {} is reserved, freezes and thaws need an issuer-defined reason code", reason_code);
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// 写入或清除定时解冻；清除时账户没有该扩展则无需写入
+fn set_scheduled_thaw(token_account: &AccountInfo, thaw_at_slot: Option<u64>) -> ProgramResult {
+    let mut data = token_account.data.borrow_mut();
+    if thaw_at_slot.is_none() && extension::get_extension::<TokenAccount, ScheduledThaw>(&data)?.is_none() {
+        return Ok(());
+    }
+    extension::init_extension::<TokenAccount, _>(&mut data, &ScheduledThaw { thaw_at_slot })
+}
+
+/// 设置代币账户的全局冻结豁免
+fn process_set_freeze_exempt(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    exempt: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+
+    check_freeze_authority(program_id, token_account, mint_account, authority_account)?;
+    extension::init_extension::<TokenAccount, _>(&mut token_account.data.borrow_mut(), &FreezeExempt { exempt })?;
+
+    msg!("Token account {} freeze exempt: {}", token_account.key, exempt);
+    Ok(())
+}
+
+/// 解冻代币账户
+fn process_thaw_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    reason_code: u16,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+
+    let mut token_acc = check_freeze_authority(program_id, token_account, mint_account, authority_account)?;
+    check_freeze_reason(reason_code)?;
+
+    // 清空窗口，否则下一笔转账会立即再次触发冻结
+    if token_acc.velocity_limited() {
+        let tracker_account = next_account_info(account_info_iter)?;
+        let mut tracker = load_velocity_tracker(program_id, token_account.key, tracker_account)?;
+        tracker.window_start_slot = Clock::get()?.slot;
+        tracker.window_amount = 0;
+        pack_state(&tracker, &mut tracker_account.data.borrow_mut())?;
+    }
+
+    token_acc.set_frozen(false);
+    token_acc.serialize(&mut token_account.data.borrow_mut())?;
+    set_scheduled_thaw(token_account, None)?;
+    AuthorityEvent::AccountThawed {
+        mint: token_acc.mint,
+        account: *token_account.key,
+        authority: *authority_account.key,
+        reason_code,
+        slot: Clock::get()?.slot,
+    }
+    .emit()?;
+
+    msg!("Token account {} thawed (reason {})", token_account.key, reason_code);
+    Ok(())
+}
+
+/// 校验冻结权限签名，返回代币账户状态
+fn check_freeze_authority(
+    program_id: &Pubkey,
+    token_account: &AccountInfo,
+    mint_account: &AccountInfo,
+    authority_account: &AccountInfo,
+) -> Result<TokenAccount, ProgramError> {
+    validators::assert_owned_by(token_account, program_id)?;
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_signer(authority_account)?;
+    let mint = Mint::deserialize(&mint_account.data.borrow())?;
+    if mint.freeze_authority != Some(*authority_account.key) {
+        return Err(TokenError::Unauthorized.into());
+    }
+    let token_acc = TokenAccount::deserialize(&token_account.data.borrow())?;
+    validators::assert_mint_matches(&token_acc.mint, mint_account.key)?;
+    Ok(token_acc)
+}
+
+/// 加载并校验代币账户的速度跟踪 PDA
+fn load_velocity_tracker(
+    program_id: &Pubkey,
+    token_account: &Pubkey,
+    tracker_account: &AccountInfo,
+) -> Result<VelocityTracker, ProgramError> {
+    validators::assert_owned_by(tracker_account, program_id)?;
+    let tracker: VelocityTracker = unpack_state(&tracker_account.data.borrow())?;
+    if !tracker.is_initialized || tracker.token_account != *token_account {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(tracker)
+}
+
+/// 排队铸造
+fn process_queue_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    destination: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let queued_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_signer(authority_account)?;
+    let mint = Mint::deserialize(&mint_account.data.borrow())?;
+    if mint.mint_authority != Some(*authority_account.key) {
+        return Err(TokenError::Unauthorized.into());
+    }
+
+    let (queued_key, bump) = Pubkey::find_program_address(
+        &[QUEUED_MINT_SEED, mint_account.key.as_ref(), destination.as_ref()],
+        program_id,
+    );
+    if queued_key != *queued_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    create_pda_account(
+        authority_account,
+        queued_account,
+        system_program_account,
+        program_id,
+        QueuedMint::LEN,
+        &[QUEUED_MINT_SEED, mint_account.key.as_ref(), destination.as_ref(), &[bump]],
+    )?;
+
+    let queued_slot = Clock::get()?.slot;
+    let queued = QueuedMint {
+        is_initialized: true,
+        mint: *mint_account.key,
+        destination,
+        authority: *authority_account.key,
+        amount,
+        queued_slot,
+        executable_slot: queued_slot.checked_add(MINT_TIMELOCK_SLOTS).ok_or(TokenError::Overflow)?,
+    };
+    pack_state(&queued, &mut queued_account.data.borrow_mut())?;
+
+    msg!(
+        "Queued mint of {} tokens to {}, executable at slot {}",
+        amount,
+        destination,
+        queued.executable_slot
+    );
+    Ok(())
+}
+
+/// 加载并校验排队铸造记录
+fn load_queued_mint(
+    program_id: &Pubkey,
+    mint_account: &AccountInfo,
+    queued_account: &AccountInfo,
+) -> Result<QueuedMint, ProgramError> {
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_owned_by(queued_account, program_id)?;
+    let queued: QueuedMint = unpack_state(&queued_account.data.borrow())?;
+    validators::assert_initialized(&queued)?;
+    validators::assert_mint_matches(&queued.mint, mint_account.key)?;
+    Ok(queued)
+}
+
+/// 执行到期的排队铸造（任何人可调用）
+fn process_execute_queued_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let queued_account = next_account_info(account_info_iter)?;
+    let destination_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+
+    let queued = load_queued_mint(program_id, mint_account, queued_account)?;
+    if queued.destination != *destination_account.key || queued.authority != *authority_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let current_slot = Clock::get()?.slot;
+    if current_slot < queued.executable_slot {
+        msg!("Queued mint executable at slot {}, now {}", queued.executable_slot, current_slot);
+        return Err(TokenError::TimelockNotElapsed.into());
+    }
+
+    // 排队期间铸币权限若已变更或撤销，则该排队作废
+    let mut mint = LoadedMint::load(program_id, mint_account)?;
+    if mint.state.mint_authority != Some(queued.authority) {
+        return Err(TokenError::Unauthorized.into());
+    }
+    let destination = LoadedTokenAccount::load(program_id, destination_account)?;
+    validators::assert_mint_matches(&destination.state.mint, mint_account.key)?;
+    destination.check_not_frozen()?;
+
+    check_not_paused(mint_account, PausedOperation::Minting)?;
+    mint.state.mint_supply(queued.amount)?;
+    mint.save()?;
+    credit_minted(program_id, &mint, destination, queued.amount, account_info_iter)?;
+
+    close_pda_account(queued_account, authority_account)?;
+
+    msg!("Executed queued mint of {} tokens to {}", queued.amount, destination_account.key);
+    Ok(())
+}
+
+/// 取消排队铸造
+fn process_cancel_queued_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let queued_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(authority_account)?;
+    let queued = load_queued_mint(program_id, mint_account, queued_account)?;
+    if queued.authority != *authority_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+
+    close_pda_account(queued_account, authority_account)?;
+
+    msg!("Cancelled queued mint of {} tokens to {}", queued.amount, queued.destination);
+    Ok(())
+}
+
+/// 创建国库代币账户
+fn process_initialize_treasury(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let treasury_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(mint_account, program_id)?;
+    let mint = Mint::deserialize(&mint_account.data.borrow())?;
+    let (treasury_key, bump) = find_treasury_address(mint_account.key, program_id);
+    if treasury_key != *treasury_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    create_pda_account(
+        payer_account,
+        treasury_account,
+        system_program_account,
+        program_id,
+        TokenAccount::LEN,
+        &[TREASURY_SEED, mint_account.key.as_ref(), &[bump]],
+    )?;
+
+    // 国库账户归自身所有：没有私钥能签名转出，只能通过 BurnTreasury 销毁
+    let treasury = TokenAccount::new(*mint_account.key, treasury_key).with_nft(mint.is_nft);
+    treasury.serialize(&mut treasury_account.data.borrow_mut())?;
+
+    msg!("Treasury {} initialized for mint {}", treasury_key, mint_account.key);
+    Ok(())
+}
+
+/// 销毁国库全部余额（任何人可调用）
+fn process_burn_treasury(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let treasury_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_owned_by(treasury_account, program_id)?;
+    let (treasury_key, _) = find_treasury_address(mint_account.key, program_id);
+    if treasury_key != *treasury_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    check_global_freeze(mint_account, treasury_account)?;
+    check_not_paused(mint_account, PausedOperation::Burning)?;
+
+    let mut treasury = LoadedTokenAccount::load(program_id, treasury_account)?;
+    sync_rescale(mint_account, &mut treasury)?;
+    let amount = treasury.state.amount;
+    let mut mint = Mint::deserialize(&mint_account.data.borrow())?;
+    mint.supply = mint.supply.checked_sub(amount).ok_or(TokenError::Overflow)?;
+    mint.serialize(&mut mint_account.data.borrow_mut())?;
+    treasury.state.amount = 0;
+    treasury.save()?;
+    update_holder_count(mint_account, &[(amount, 0)])?;
+
+    let event = TreasuryBurnEvent {
+        mint: *mint_account.key,
+        amount,
+        supply: mint.supply,
+        slot: Clock::get()?.slot,
+    };
+    sol_log_data(&[&event.try_to_vec()?]);
+
+    msg!("Burned {} tokens from treasury, supply now {}", amount, mint.supply);
+    Ok(())
+}
+
+/// OTC 挂单：卖方代币转入托管账户
+fn process_place_order(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    give_amount: u64,
+    want_mint: Pubkey,
+    want_amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let order_account = next_account_info(account_info_iter)?;
+    let escrow_account = next_account_info(account_info_iter)?;
+    let source_account = next_account_info(account_info_iter)?;
+    let receive_account = next_account_info(account_info_iter)?;
+    let maker_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(maker_account)?;
+    validators::assert_owned_by(escrow_account, program_id)?;
+    validators::assert_owned_by(source_account, program_id)?;
+    validators::assert_owned_by(receive_account, program_id)?;
+    if give_amount == 0 || want_amount == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (order_key, bump) = find_order_address(escrow_account.key, program_id);
+    if order_key != *order_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // 托管账户必须由挂单 PDA 持有且为空，与卖方账户同一铸币
+    let mut escrow = LoadedTokenAccount::load(program_id, escrow_account)?;
+    let mut source = LoadedTokenAccount::load(program_id, source_account)?;
+    let receive = TokenAccount::deserialize(&receive_account.data.borrow())?;
+    if escrow.state.owner != order_key {
+        msg!("Escrow token account must be owned by the order PDA");
+        return Err(TokenError::Unauthorized.into());
+    }
+    if escrow.state.amount != 0 {
+        msg!("Escrow token account must be empty");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    validators::assert_mint_matches(&escrow.state.mint, &source.state.mint)?;
+    validators::assert_mint_matches(&receive.mint, &want_mint)?;
+    if source.state.owner != *maker_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    let give_mint_account = next_transfer_mint(account_info_iter, source_account, &source.state.mint)?;
+    source.check_not_frozen()?;
+    escrow.check_not_frozen()?;
+
+    create_pda_account(
+        maker_account,
+        order_account,
+        system_program_account,
+        program_id,
+        Order::LEN,
+        &[ORDER_SEED, escrow_account.key.as_ref(), &[bump]],
+    )?;
+
+    move_synced(give_mint_account, &mut source, &mut escrow, give_amount)?;
+    move_delegated_votes(
+        program_id,
+        &escrow.state.mint,
+        source.state.vote_delegate,
+        escrow.state.vote_delegate,
+        give_amount,
+        account_info_iter,
+    )?;
+
+    let order = Order {
+        is_initialized: true,
+        maker: *maker_account.key,
+        escrow: *escrow_account.key,
+        maker_receive: *receive_account.key,
+        give_mint: escrow.state.mint,
+        give_amount,
+        want_mint,
+        want_amount,
+    };
+    pack_state(&order, &mut order_account.data.borrow_mut())?;
+
+    msg!("Order placed: {} of {} for {} of {}", give_amount, order.give_mint, want_amount, want_mint);
+    Ok(())
+}
+
+fn load_order(
+    program_id: &Pubkey,
+    order_account: &AccountInfo,
+    escrow_account: &AccountInfo,
+) -> Result<Order, ProgramError> {
+    validators::assert_owned_by(order_account, program_id)?;
+    validators::assert_owned_by(escrow_account, program_id)?;
+    let order: Order = unpack_state(&order_account.data.borrow())?;
+    validators::assert_initialized(&order)?;
+    if order.escrow != *escrow_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(order)
+}
+
+/// OTC 吃单：整单成交
+fn process_fill_order(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let order_account = next_account_info(account_info_iter)?;
+    let escrow_account = next_account_info(account_info_iter)?;
+    let maker_receive_account = next_account_info(account_info_iter)?;
+    let taker_source_account = next_account_info(account_info_iter)?;
+    let taker_receive_account = next_account_info(account_info_iter)?;
+    let taker_account = next_account_info(account_info_iter)?;
+    let maker_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(taker_account)?;
+    let order = load_order(program_id, order_account, escrow_account)?;
+    if order.maker_receive != *maker_receive_account.key || order.maker != *maker_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    validators::assert_owned_by(maker_receive_account, program_id)?;
+    validators::assert_owned_by(taker_source_account, program_id)?;
+    validators::assert_owned_by(taker_receive_account, program_id)?;
+    // 各账户先整体读出再分别写回，同一账户出现两次会凭空增发
+    if taker_source_account.key == maker_receive_account.key
+        || taker_receive_account.key == escrow_account.key
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut escrow = LoadedTokenAccount::load(program_id, escrow_account)?;
+    let mut maker_receive = LoadedTokenAccount::load(program_id, maker_receive_account)?;
+    let mut taker_source = LoadedTokenAccount::load(program_id, taker_source_account)?;
+    let mut taker_receive = LoadedTokenAccount::load(program_id, taker_receive_account)?;
+    if taker_source.state.owner != *taker_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    if taker_source.state.mint != order.want_mint
+        || maker_receive.state.mint != order.want_mint
+        || taker_receive.state.mint != order.give_mint
+    {
+        return Err(TokenError::MintMismatch.into());
+    }
+    let want_mint_account = next_transfer_mint(account_info_iter, taker_source_account, &order.want_mint)?;
+    let give_mint_account = next_transfer_mint(account_info_iter, escrow_account, &order.give_mint)?;
+    for account in [&escrow, &maker_receive, &taker_source, &taker_receive] {
+        account.check_not_frozen()?;
+    }
+
+    move_synced(want_mint_account, &mut taker_source, &mut maker_receive, order.want_amount)?;
+    move_synced(give_mint_account, &mut escrow, &mut taker_receive, order.give_amount)?;
+
+    move_delegated_votes(
+        program_id,
+        &order.want_mint,
+        taker_source.state.vote_delegate,
+        maker_receive.state.vote_delegate,
+        order.want_amount,
+        account_info_iter,
+    )?;
+    move_delegated_votes(
+        program_id,
+        &order.give_mint,
+        escrow.state.vote_delegate,
+        taker_receive.state.vote_delegate,
+        order.give_amount,
+        account_info_iter,
+    )?;
+    close_pda_account(order_account, maker_account)?;
+
+    msg!("Order filled: {} of {} for {} of {}", order.give_amount, order.give_mint, order.want_amount, order.want_mint);
+    Ok(())
+}
+
+/// OTC 撤单：托管代币退回卖方
+fn process_cancel_order(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let order_account = next_account_info(account_info_iter)?;
+    let escrow_account = next_account_info(account_info_iter)?;
+    let refund_account = next_account_info(account_info_iter)?;
+    let maker_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(maker_account)?;
+    let order = load_order(program_id, order_account, escrow_account)?;
+    if order.maker != *maker_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    validators::assert_owned_by(refund_account, program_id)?;
+    if refund_account.key == escrow_account.key {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut escrow = LoadedTokenAccount::load(program_id, escrow_account)?;
+    let mut refund = LoadedTokenAccount::load(program_id, refund_account)?;
+    validators::assert_mint_matches(&refund.state.mint, &order.give_mint)?;
+    let give_mint_account = next_transfer_mint(account_info_iter, escrow_account, &order.give_mint)?;
+    escrow.check_not_frozen()?;
+    refund.check_not_frozen()?;
+
+    move_synced(give_mint_account, &mut escrow, &mut refund, order.give_amount)?;
+    move_delegated_votes(
+        program_id,
+        &order.give_mint,
+        escrow.state.vote_delegate,
+        refund.state.vote_delegate,
+        order.give_amount,
+        account_info_iter,
+    )?;
+    close_pda_account(order_account, maker_account)?;
+
+    msg!("Order cancelled, {} of {} refunded", order.give_amount, order.give_mint);
+    Ok(())
+}
+
+/// 创建荷兰式拍卖
+fn process_create_auction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    start_price: u64,
+    floor_price: u64,
+    decay: u64,
+    supply: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let auction_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_signer(authority_account)?;
+    if supply == 0 || start_price == 0 || floor_price > start_price {
+        return Err(TokenError::InvalidAuction.into());
+    }
+
+    let mut mint = Mint::deserialize(&mint_account.data.borrow())?;
+    if mint.mint_authority != Some(*authority_account.key) {
+        return Err(TokenError::Unauthorized.into());
+    }
+
+    let (auction_key, bump) =
+        Pubkey::find_program_address(&[AUCTION_SEED, mint_account.key.as_ref()], program_id);
+    let (vault_key, vault_bump) =
+        Pubkey::find_program_address(&[AUCTION_VAULT_SEED, mint_account.key.as_ref()], program_id);
+    if auction_key != *auction_account.key || vault_key != *vault_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    create_pda_account(
+        payer_account,
+        auction_account,
+        system_program_account,
+        program_id,
+        DutchAuction::LEN,
+        &[AUCTION_SEED, mint_account.key.as_ref(), &[bump]],
+    )?;
+    create_pda_account(
+        payer_account,
+        vault_account,
+        system_program_account,
+        program_id,
+        0,
+        &[AUCTION_VAULT_SEED, mint_account.key.as_ref(), &[vault_bump]],
+    )?;
+
+    let auction = DutchAuction {
+        is_initialized: true,
+        mint: *mint_account.key,
+        authority: *authority_account.key,
+        start_slot: Clock::get()?.slot,
+        start_price,
+        floor_price,
+        decay,
+        supply,
+        sold: 0,
+        bump,
+        vault_bump,
+    };
+    pack_state(&auction, &mut auction_account.data.borrow_mut())?;
+
+    // 铸币权限移交给拍卖 PDA，之后只能通过拍卖铸造
+    let old_authority = mint.mint_authority.replace(auction_key);
+    mint.serialize(&mut mint_account.data.borrow_mut())?;
+    emit_authority_changed(mint_account.key, AuthorityType::MintTokens, old_authority, mint.mint_authority)?;
+
+    msg!(
+        "Dutch auction created for mint {}: {} -> {} lamports, decay {} per slot, supply {}",
+        mint_account.key,
+        start_price,
+        floor_price,
+        decay,
+        supply
+    );
+    Ok(())
+}
+
+/// 按当前拍卖价格购买代币
+fn process_purchase(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    max_lamports: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let auction_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let buyer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(buyer_account)?;
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_owned_by(auction_account, program_id)?;
+    validators::assert_owned_by(vault_account, program_id)?;
+    validators::assert_owned_by(token_account, program_id)?;
+    if amount == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut auction: DutchAuction = unpack_state(&auction_account.data.borrow())?;
+    validators::assert_initialized(&auction)?;
+    validators::assert_mint_matches(&auction.mint, mint_account.key)?;
+    let vault_key = Pubkey::create_program_address(
+        &[AUCTION_VAULT_SEED, mint_account.key.as_ref(), &[auction.vault_bump]],
+        program_id,
+    )?;
+    if vault_key != *vault_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let sold = auction.sold.checked_add(amount).ok_or(TokenError::Overflow)?;
+    if sold > auction.supply {
+        msg!("Auction has {} tokens left", auction.supply - auction.sold);
+        return Err(TokenError::AuctionSoldOut.into());
+    }
+
+    let mut mint = LoadedMint::load(program_id, mint_account)?;
+    let token = LoadedTokenAccount::load(program_id, token_account)?;
+    validators::assert_mint_matches(&token.state.mint, mint_account.key)?;
+    token.check_not_frozen()?;
+
+    // 价格按完整代币计，零头向上取整
+    let price = auction.price_at(Clock::get()?.slot);
+    let unit = math::one_token(mint.state.decimals)?;
+    let cost = (price as u128)
+        .checked_mul(amount as u128)
+        .ok_or(TokenError::Overflow)?
+        .div_ceil(unit);
+    let cost = u64::try_from(cost).map_err(|_| TokenError::Overflow)?;
+    if cost > max_lamports {
+        msg!("Auction cost {} exceeds max_lamports {}", cost, max_lamports);
+        return Err(TokenError::SlippageExceeded.into());
+    }
+
+    // SOL 存入金库
+    invoke(
+        &system_instruction::transfer(buyer_account.key, vault_account.key, cost),
+        &[
+            buyer_account.clone(),
+            vault_account.clone(),
+            system_program_account.clone(),
+        ],
+    )?;
+
+    check_not_paused(mint_account, PausedOperation::Minting)?;
+    mint.state.mint_supply(amount)?;
+    mint.save()?;
+    credit_minted(program_id, &mint, token, amount, account_info_iter)?;
+
+    auction.sold = sold;
+    pack_state(&auction, &mut auction_account.data.borrow_mut())?;
+
+    msg!("Purchased {} tokens at {} lamports each for {} lamports", amount, price, cost);
+    Ok(())
+}
+
+/// 初始化固定价格众筹
+fn process_initialize_sale(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    price_lamports_per_token: u64,
+    cap: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let sale_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_signer(authority_account)?;
+    if price_lamports_per_token == 0 || cap == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut mint = Mint::deserialize(&mint_account.data.borrow())?;
+    if mint.mint_authority != Some(*authority_account.key) {
+        return Err(TokenError::Unauthorized.into());
+    }
+
+    let (sale_key, bump) =
+        Pubkey::find_program_address(&[SALE_SEED, mint_account.key.as_ref()], program_id);
+    let (vault_key, vault_bump) =
+        Pubkey::find_program_address(&[SALE_VAULT_SEED, mint_account.key.as_ref()], program_id);
+    if sale_key != *sale_account.key || vault_key != *vault_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    create_pda_account(
+        payer_account,
+        sale_account,
+        system_program_account,
+        program_id,
+        Sale::LEN,
+        &[SALE_SEED, mint_account.key.as_ref(), &[bump]],
+    )?;
+    create_pda_account(
+        payer_account,
+        vault_account,
+        system_program_account,
+        program_id,
+        0,
+        &[SALE_VAULT_SEED, mint_account.key.as_ref(), &[vault_bump]],
+    )?;
+
+    let sale = Sale {
+        is_initialized: true,
+        mint: *mint_account.key,
+        authority: *authority_account.key,
+        price_lamports_per_token,
+        cap,
+        sold: 0,
+        bump,
+        vault_bump,
+    };
+    pack_state(&sale, &mut sale_account.data.borrow_mut())?;
+
+    // 铸币权限移交给众筹 PDA，之后只能通过众筹铸造
+    let old_authority = mint.mint_authority.replace(sale_key);
+    mint.serialize(&mut mint_account.data.borrow_mut())?;
+    emit_authority_changed(mint_account.key, AuthorityType::MintTokens, old_authority, mint.mint_authority)?;
+
+    msg!(
+        "Sale initialized for mint {}: {} lamports per token, cap {}",
+        mint_account.key,
+        price_lamports_per_token,
+        cap
+    );
+    Ok(())
+}
+
+/// 加载并校验众筹状态及金库地址
+fn load_sale(
+    program_id: &Pubkey,
+    mint_account: &AccountInfo,
+    sale_account: &AccountInfo,
+    vault_account: &AccountInfo,
+) -> Result<Sale, ProgramError> {
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_owned_by(sale_account, program_id)?;
+    validators::assert_owned_by(vault_account, program_id)?;
+    let sale: Sale = unpack_state(&sale_account.data.borrow())?;
+    validators::assert_initialized(&sale)?;
+    validators::assert_mint_matches(&sale.mint, mint_account.key)?;
+    let vault_key = Pubkey::create_program_address(
+        &[SALE_VAULT_SEED, mint_account.key.as_ref(), &[sale.vault_bump]],
+        program_id,
+    )?;
+    if vault_key != *vault_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    Ok(sale)
+}
+
+/// 按众筹价格购买代币
+fn process_buy_tokens(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let sale_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let buyer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(buyer_account)?;
+    validators::assert_owned_by(token_account, program_id)?;
+    if amount == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let mut sale = load_sale(program_id, mint_account, sale_account, vault_account)?;
+    let sold = sale.sold.checked_add(amount).ok_or(TokenError::Overflow)?;
+    if sold > sale.cap {
+        msg!("Sale has {} tokens left", sale.cap - sale.sold);
+        return Err(TokenError::SaleCapReached.into());
+    }
+
+    let mut mint = LoadedMint::load(program_id, mint_account)?;
+    let token = LoadedTokenAccount::load(program_id, token_account)?;
+    validators::assert_mint_matches(&token.state.mint, mint_account.key)?;
+    token.check_not_frozen()?;
+
+    // 价格按完整代币计，零头向上取整
+    let unit = math::one_token(mint.state.decimals)?;
+    let cost = (sale.price_lamports_per_token as u128)
+        .checked_mul(amount as u128)
+        .ok_or(TokenError::Overflow)?
+        .div_ceil(unit);
+    let cost = u64::try_from(cost).map_err(|_| TokenError::Overflow)?;
+
+    // SOL 存入金库
+    invoke(
+        &system_instruction::transfer(buyer_account.key, vault_account.key, cost),
+        &[
+            buyer_account.clone(),
+            vault_account.clone(),
+            system_program_account.clone(),
+        ],
+    )?;
+
+    check_not_paused(mint_account, PausedOperation::Minting)?;
+    mint.state.mint_supply(amount)?;
+    mint.save()?;
+    credit_minted(program_id, &mint, token, amount, account_info_iter)?;
+
+    sale.sold = sold;
+    pack_state(&sale, &mut sale_account.data.borrow_mut())?;
+
+    msg!("Bought {} tokens from sale for {} lamports ({} / {} sold)", amount, cost, sale.sold, sale.cap);
+    Ok(())
+}
+
+/// 提取众筹货款
+fn process_withdraw_proceeds(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let sale_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(authority_account)?;
+    let sale = load_sale(program_id, mint_account, sale_account, vault_account)?;
+    if sale.authority != *authority_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+
+    // 金库必须保持租金豁免
+    let rent = Rent::get()?;
+    let available = vault_account
+        .lamports()
+        .saturating_sub(rent.minimum_balance(vault_account.data_len()));
+    if available == 0 {
+        return Err(TokenError::InsufficientFunds.into());
+    }
+
+    **vault_account.try_borrow_mut_lamports()? -= available;
+    **authority_account.try_borrow_mut_lamports()? += available;
+
+    msg!("Withdrew {} lamports of sale proceeds", available);
+    Ok(())
+}
+
+/// 初始化份额金库
+fn process_initialize_share_vault(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let share_mint_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let asset_vault_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(share_mint_account, program_id)?;
+    validators::assert_owned_by(asset_vault_account, program_id)?;
+    validators::assert_signer(authority_account)?;
+
+    let mut share_mint = Mint::deserialize(&share_mint_account.data.borrow())?;
+    if share_mint.mint_authority != Some(*authority_account.key) {
+        return Err(TokenError::Unauthorized.into());
+    }
+    if share_mint.supply != 0 || share_mint.is_nft {
+        msg!("Share mint must be a fungible mint with zero supply");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let (vault_key, bump) = find_share_vault_address(share_mint_account.key, program_id);
+    if vault_key != *vault_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // 托管账户必须由金库 PDA 持有且为空，资产铸币不能是份额铸币本身
+    let asset_vault = TokenAccount::deserialize(&asset_vault_account.data.borrow())?;
+    if asset_vault.owner != vault_key {
+        msg!("Asset vault token account must be owned by the share vault PDA");
+        return Err(TokenError::Unauthorized.into());
+    }
+    if asset_vault.amount != 0 {
+        msg!("Asset vault token account must be empty");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if asset_vault.mint == *share_mint_account.key {
+        return Err(TokenError::MintMismatch.into());
+    }
+
+    create_pda_account(
+        payer_account,
+        vault_account,
+        system_program_account,
+        program_id,
+        ShareVault::LEN,
+        &[SHARE_VAULT_SEED, share_mint_account.key.as_ref(), &[bump]],
+    )?;
+    let vault = ShareVault {
+        is_initialized: true,
+        asset_mint: asset_vault.mint,
+        share_mint: *share_mint_account.key,
+        asset_vault: *asset_vault_account.key,
+        bump,
+    };
+    pack_state(&vault, &mut vault_account.data.borrow_mut())?;
+
+    // 份额铸币权限移交给金库 PDA，之后只能通过存款铸造
+    let old_authority = share_mint.mint_authority.replace(vault_key);
+    share_mint.serialize(&mut share_mint_account.data.borrow_mut())?;
+    emit_authority_changed(share_mint_account.key, AuthorityType::MintTokens, old_authority, share_mint.mint_authority)?;
+
+    msg!("Share vault initialized: asset mint {}, share mint {}", vault.asset_mint, vault.share_mint);
+    Ok(())
+}
+
+/// 加载并校验份额金库及其份额铸币、资产托管账户
+fn load_share_vault(
+    program_id: &Pubkey,
+    vault_account: &AccountInfo,
+    share_mint_account: &AccountInfo,
+    asset_vault_account: &AccountInfo,
+) -> Result<ShareVault, ProgramError> {
+    validators::assert_owned_by(vault_account, program_id)?;
+    validators::assert_owned_by(share_mint_account, program_id)?;
+    validators::assert_owned_by(asset_vault_account, program_id)?;
+    let vault: ShareVault = unpack_state(&vault_account.data.borrow())?;
+    validators::assert_initialized(&vault)?;
+    validators::assert_mint_matches(&vault.share_mint, share_mint_account.key)?;
+    if vault.asset_vault != *asset_vault_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(vault)
+}
+
+/// 存入资产换取份额
+fn process_deposit(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let vault_account = next_account_info(account_info_iter)?;
+    let share_mint_account = next_account_info(account_info_iter)?;
+    let asset_vault_account = next_account_info(account_info_iter)?;
+    let source_account = next_account_info(account_info_iter)?;
+    let share_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(owner_account)?;
+    let vault = load_share_vault(program_id, vault_account, share_mint_account, asset_vault_account)?;
+    validators::assert_owned_by(source_account, program_id)?;
+    validators::assert_owned_by(share_account, program_id)?;
+
+    let mut share_mint = LoadedMint::load(program_id, share_mint_account)?;
+    let mut asset_vault = LoadedTokenAccount::load(program_id, asset_vault_account)?;
+    let mut source = LoadedTokenAccount::load(program_id, source_account)?;
+    let share_acc = LoadedTokenAccount::load(program_id, share_account)?;
+    validators::assert_mint_matches(&source.state.mint, &vault.asset_mint)?;
+    validators::assert_mint_matches(&share_acc.state.mint, &vault.share_mint)?;
+    if source.state.owner != *owner_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    let asset_mint_account = next_transfer_mint(account_info_iter, source_account, &vault.asset_mint)?;
+    for account in [&source, &share_acc, &asset_vault] {
+        account.check_not_frozen()?;
+    }
+
+    // 份额按换算到当前纪元的托管余额定价
+    sync_rescale(asset_mint_account, &mut asset_vault)?;
+    let shares = ShareVault::shares_for_deposit(amount, asset_vault.state.amount, share_mint.state.supply)?;
+    if shares == 0 {
+        msg!("Deposit of {} is too small to mint any shares", amount);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    move_synced(asset_mint_account, &mut source, &mut asset_vault, amount)?;
+    move_delegated_votes(
+        program_id,
+        &vault.asset_mint,
+        source.state.vote_delegate,
+        asset_vault.state.vote_delegate,
+        amount,
+        account_info_iter,
+    )?;
+    check_not_paused(share_mint_account, PausedOperation::Minting)?;
+    share_mint.state.mint_supply(shares)?;
+    share_mint.save()?;
+    credit_minted(program_id, &share_mint, share_acc, shares, account_info_iter)?;
+
+    msg!("Deposited {} assets for {} shares", amount, shares);
+    Ok(())
+}
+
+/// 销毁份额取回资产
+fn process_withdraw(program_id: &Pubkey, accounts: &[AccountInfo], shares: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let vault_account = next_account_info(account_info_iter)?;
+    let share_mint_account = next_account_info(account_info_iter)?;
+    let asset_vault_account = next_account_info(account_info_iter)?;
+    let share_account = next_account_info(account_info_iter)?;
+    let destination_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(owner_account)?;
+    let vault = load_share_vault(program_id, vault_account, share_mint_account, asset_vault_account)?;
+    validators::assert_owned_by(share_account, program_id)?;
+    validators::assert_owned_by(destination_account, program_id)?;
+    // 各账户先整体读出再分别写回，目标账户不能是托管账户本身
+    if destination_account.key == asset_vault_account.key {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut share_mint = LoadedMint::load(program_id, share_mint_account)?;
+    let mut asset_vault = LoadedTokenAccount::load(program_id, asset_vault_account)?;
+    let mut share_acc = LoadedTokenAccount::load(program_id, share_account)?;
+    let mut destination = LoadedTokenAccount::load(program_id, destination_account)?;
+    validators::assert_mint_matches(&share_acc.state.mint, &vault.share_mint)?;
+    validators::assert_mint_matches(&destination.state.mint, &vault.asset_mint)?;
+    if share_acc.state.owner != *owner_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    check_global_freeze(share_mint_account, share_account)?;
+    check_not_paused(share_mint_account, PausedOperation::Burning)?;
+    let asset_mint_account = next_transfer_mint(account_info_iter, asset_vault_account, &vault.asset_mint)?;
+    destination.check_not_frozen()?;
+    asset_vault.check_not_frozen()?;
+    sync_rescale(share_mint_account, &mut share_acc)?;
+    share_acc.check_spendable(shares)?;
+
+    // 赎回金额按换算到当前纪元的托管余额计算
+    sync_rescale(asset_mint_account, &mut asset_vault)?;
+    let amount = ShareVault::assets_for_withdraw(shares, asset_vault.state.amount, share_mint.state.supply)?;
+    if amount == 0 {
+        msg!("Withdrawal of {} shares is too small to return any assets", shares);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let shares_before = share_acc.state.amount;
+    (share_mint.state.supply, share_acc.state.amount) =
+        balance::burn_amounts(share_mint.state.supply, share_acc.state.amount, shares)?;
+    share_acc.save()?;
+    share_mint.save()?;
+    update_holder_count(share_mint_account, &[(shares_before, share_acc.state.amount)])?;
+    move_synced(asset_mint_account, &mut asset_vault, &mut destination, amount)?;
+
+    let delegate = share_acc.state.vote_delegate;
+    move_delegated_votes(program_id, &vault.share_mint, delegate, None, shares, account_info_iter)?;
+    move_delegated_votes(
+        program_id,
+        &vault.asset_mint,
+        asset_vault.state.vote_delegate,
+        destination.state.vote_delegate,
+        amount,
+        account_info_iter,
+    )?;
+
+    msg!("Withdrew {} assets for {} shares", amount, shares);
+    Ok(())
+}
+
+/// 创建储蓄金库
+fn process_initialize_savings_vault(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    yield_authority: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let savings_vault_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_owned_by(vault_account, program_id)?;
+    validators::assert_signer(authority_account)?;
+
+    let mint = Mint::deserialize(&mint_account.data.borrow())?;
+    if mint.mint_authority != Some(*authority_account.key) {
+        return Err(TokenError::Unauthorized.into());
+    }
+    if mint.is_nft {
+        msg!("Savings vaults only accept fungible mints");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let (savings_vault_key, bump) = find_savings_vault_address(mint_account.key, program_id);
+    if savings_vault_key != *savings_vault_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // 托管账户必须由金库 PDA 持有且为空，否则第一位存款人会拿走已有余额
+    let vault = TokenAccount::deserialize(&vault_account.data.borrow())?;
+    validators::assert_mint_matches(&vault.mint, mint_account.key)?;
+    if vault.owner != savings_vault_key {
+        msg!("Vault token account must be owned by the savings vault PDA");
+        return Err(TokenError::Unauthorized.into());
+    }
+    if vault.amount != 0 {
+        msg!("Vault token account must be empty");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    create_pda_account(
+        payer_account,
+        savings_vault_account,
+        system_program_account,
+        program_id,
+        SavingsVault::LEN,
+        &[SAVINGS_VAULT_SEED, mint_account.key.as_ref(), &[bump]],
+    )?;
+    let savings_vault = SavingsVault {
+        is_initialized: true,
+        mint: *mint_account.key,
+        vault: *vault_account.key,
+        yield_authority,
+        total_shares: 0,
+        bump,
+    };
+    pack_state(&savings_vault, &mut savings_vault_account.data.borrow_mut())?;
+
+    msg!("Savings vault initialized for mint {}, yield authority {}", mint_account.key, yield_authority);
+    Ok(())
+}
+
+/// 加载并校验储蓄金库及其托管代币账户
+fn load_savings_vault(
+    program_id: &Pubkey,
+    savings_vault_account: &AccountInfo,
+    vault_account: &AccountInfo,
+) -> Result<SavingsVault, ProgramError> {
+    validators::assert_owned_by(savings_vault_account, program_id)?;
+    validators::assert_owned_by(vault_account, program_id)?;
+    let savings_vault: SavingsVault = unpack_state(&savings_vault_account.data.borrow())?;
+    validators::assert_initialized(&savings_vault)?;
+    if savings_vault.vault != *vault_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(savings_vault)
+}
+
+/// 注入收益
+fn process_fund_yield(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let savings_vault_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let source_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(authority_account)?;
+    let savings_vault = load_savings_vault(program_id, savings_vault_account, vault_account)?;
+    if savings_vault.yield_authority != *authority_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    validators::assert_owned_by(source_account, program_id)?;
+    if source_account.key == vault_account.key {
+        return Err(ProgramError::InvalidArgument);
+    }
+    // 没有份额时注入的收益会全部归下一位存款人
+    if savings_vault.total_shares == 0 {
+        msg!("Savings vault has no depositors to receive yield");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut vault = LoadedTokenAccount::load(program_id, vault_account)?;
+    let mut source = LoadedTokenAccount::load(program_id, source_account)?;
+    validators::assert_mint_matches(&source.state.mint, &savings_vault.mint)?;
+    if source.state.owner != *authority_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    let mint_account = next_transfer_mint(account_info_iter, source_account, &savings_vault.mint)?;
+    source.check_not_frozen()?;
+    vault.check_not_frozen()?;
+
+    move_synced(mint_account, &mut source, &mut vault, amount)?;
+    move_delegated_votes(
+        program_id,
+        &savings_vault.mint,
+        source.state.vote_delegate,
+        vault.state.vote_delegate,
+        amount,
+        account_info_iter,
+    )?;
+
+    msg!(
+        "Funded {} yield: vault now holds {} for {} shares",
+        amount,
+        vault.state.amount,
+        savings_vault.total_shares
+    );
+    Ok(())
+}
+
+/// 存入储蓄
+fn process_deposit_savings(ctx: &mut ProcessingContext, amount: u64) -> ProgramResult {
+    let savings_vault_account = ctx.next_account()?;
+    let mut vault = ctx.next_token_account()?;
+    let mut source = ctx.next_token_account()?;
+    let position_account = ctx.next_account()?;
+    let owner_account = ctx.next_account()?;
+    let system_program_account = ctx.next_account()?;
+    let program_id = ctx.program_id;
+
+    let mut savings_vault = load_savings_vault(program_id, savings_vault_account, vault.info)?;
+    if source.info.key == vault.info.key {
+        return Err(ProgramError::InvalidArgument);
+    }
+    validators::assert_mint_matches(&source.state.mint, &savings_vault.mint)?;
+    source.check_owner(owner_account)?;
+    vault.check_not_frozen()?;
+    source.check_not_frozen()?;
+    let mint_account = next_source_mint(ctx, &source)?;
+
+    // 份额按换算到当前纪元的金库余额定价
+    sync_rescale(mint_account, &mut vault)?;
+    let shares = ShareVault::shares_for_deposit(amount, vault.state.amount, savings_vault.total_shares)?;
+    if shares == 0 {
+        msg!("Deposit of {} is too small to buy any shares", amount);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (position_key, bump) = find_savings_position_address(savings_vault_account.key, owner_account.key, program_id);
+    if position_key != *position_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut position = if position_account.data_is_empty() {
+        create_pda_account(
+            owner_account,
+            position_account,
+            system_program_account,
+            program_id,
+            SavingsPosition::LEN,
+            &[SAVINGS_POSITION_SEED, savings_vault_account.key.as_ref(), owner_account.key.as_ref(), &[bump]],
+        )?;
+        SavingsPosition {
+            is_initialized: true,
+            savings_vault: *savings_vault_account.key,
+            owner: *owner_account.key,
+            shares: 0,
+        }
+    } else {
+        validators::assert_owned_by(position_account, program_id)?;
+        unpack_state(&position_account.data.borrow())?
+    };
+
+    position.shares = position.shares.checked_add(shares).ok_or(TokenError::Overflow)?;
+    pack_state(&position, &mut position_account.data.borrow_mut())?;
+    savings_vault.total_shares = savings_vault.total_shares.checked_add(shares).ok_or(TokenError::Overflow)?;
+    pack_state(&savings_vault, &mut savings_vault_account.data.borrow_mut())?;
+    move_synced(mint_account, &mut source, &mut vault, amount)?;
+
+    move_delegated_votes(
+        program_id,
+        &savings_vault.mint,
+        source.state.vote_delegate,
+        vault.state.vote_delegate,
+        amount,
+        &mut ctx.accounts,
+    )?;
+
+    msg!("Deposited {} savings for {} shares", amount, shares);
+    Ok(())
+}
+
+/// 赎回储蓄份额
+fn process_withdraw_savings(ctx: &mut ProcessingContext, shares: u64) -> ProgramResult {
+    let savings_vault_account = ctx.next_account()?;
+    let mut vault = ctx.next_token_account()?;
+    let position_account = ctx.next_account()?;
+    let mut destination = ctx.next_token_account()?;
+    let owner_account = ctx.next_signer()?;
+    let program_id = ctx.program_id;
+
+    let mut savings_vault = load_savings_vault(program_id, savings_vault_account, vault.info)?;
+    validators::assert_owned_by(position_account, program_id)?;
+    if destination.info.key == vault.info.key {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut position: SavingsPosition = unpack_state(&position_account.data.borrow())?;
+    validators::assert_initialized(&position)?;
+    if position.savings_vault != *savings_vault_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if position.owner != *owner_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    if position.shares < shares {
+        return Err(TokenError::InsufficientFunds.into());
+    }
+
+    validators::assert_mint_matches(&destination.state.mint, &savings_vault.mint)?;
+    // 赎回不经过 transfer_to 的转账检查，只允许赎回到仓位所有者自己的代币账户
+    if destination.state.owner != position.owner {
+        msg!("Savings can only be withdrawn to an account owned by {}", position.owner);
+        return Err(TokenError::Unauthorized.into());
+    }
+    destination.check_not_frozen()?;
+    vault.check_not_frozen()?;
+    let mint_account = next_source_mint(ctx, &vault)?;
+
+    // 赎回金额按换算到当前纪元的金库余额计算
+    sync_rescale(mint_account, &mut vault)?;
+    let amount = ShareVault::assets_for_withdraw(shares, vault.state.amount, savings_vault.total_shares)?;
+    if amount == 0 {
+        msg!("Withdrawal of {} shares is too small to return any savings", shares);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    savings_vault.total_shares -= shares;
+    pack_state(&savings_vault, &mut savings_vault_account.data.borrow_mut())?;
+    move_synced(mint_account, &mut vault, &mut destination, amount)?;
+    position.shares -= shares;
+    if position.shares == 0 {
+        close_pda_account(position_account, owner_account)?;
+    } else {
+        pack_state(&position, &mut position_account.data.borrow_mut())?;
+    }
+
+    move_delegated_votes(
+        program_id,
+        &savings_vault.mint,
+        vault.state.vote_delegate,
+        destination.state.vote_delegate,
+        amount,
+        &mut ctx.accounts,
+    )?;
+
+    msg!("Withdrew {} savings for {} shares", amount, shares);
+    Ok(())
+}
+
+/// 设置排放计划
+fn process_initialize_emissions(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    tokens_per_slot: u64,
+    start_slot: u64,
+    end_slot: u64,
+    recipients: Vec<EmissionRecipient>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let emissions_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    let mut mint = LoadedMint::load(program_id, mint_account)?;
+    mint.check_mint_authority(authority_account)?;
+    if mint.state.is_nft {
+        msg!("Emissions need a fungible mint");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if tokens_per_slot == 0 || start_slot >= end_slot {
+        msg!("Emissions need a non-zero rate and start_slot < end_slot");
+        return Err(ProgramError::InvalidArgument);
+    }
+    // 整个排放期的总量必须能用 u64 表示，之后每次分发都不会溢出
+    (end_slot - start_slot).checked_mul(tokens_per_slot).ok_or(TokenError::Overflow)?;
+    if recipients.is_empty() || recipients.len() > MAX_EMISSION_RECIPIENTS {
+        msg!("Need 1 to {} emission recipients, got {}", MAX_EMISSION_RECIPIENTS, recipients.len());
+        return Err(ProgramError::InvalidArgument);
+    }
+    let duplicate = |i: usize, vault: &Pubkey| recipients[..i].iter().any(|other| other.vault == *vault);
+    if recipients.iter().enumerate().any(|(i, recipient)| recipient.weight == 0 || duplicate(i, &recipient.vault)) {
+        msg!("Emission recipients must be distinct and have non-zero weights");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (emissions_key, bump) = find_emissions_address(mint_account.key, program_id);
+    if emissions_key != *emissions_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    create_pda_account(
+        payer_account,
+        emissions_account,
+        system_program_account,
+        program_id,
+        EmissionsConfig::LEN,
+        &[EMISSIONS_SEED, mint_account.key.as_ref(), &[bump]],
+    )?;
+    let count = recipients.len();
+    let config = EmissionsConfig {
+        is_initialized: true,
+        mint: *mint_account.key,
+        authority: *authority_account.key,
+        tokens_per_slot,
+        start_slot,
+        end_slot,
+        distributed_until_slot: start_slot,
+        recipients,
+    };
+    pack_state(&config, &mut emissions_account.data.borrow_mut())?;
+
+    // 排放期间只有配置 PDA 能铸币，分发不需要任何私钥签名
+    let old_authority = mint.state.mint_authority.replace(emissions_key);
+    mint.save()?;
+    emit_authority_changed(mint_account.key, AuthorityType::MintTokens, old_authority, mint.state.mint_authority)?;
+
+    msg!(
+        "Emitting {} per slot from slot {} to {} across {} recipients",
+        tokens_per_slot,
+        start_slot,
+        end_slot,
+        count
+    );
+    Ok(())
+}
+
+/// 分发累计的排放
+fn process_distribute_emissions(ctx: &mut ProcessingContext) -> ProgramResult {
+    let mint_account = ctx.next_account()?;
+    let emissions_account = ctx.next_account()?;
+
+    validators::assert_owned_by(mint_account, ctx.program_id)?;
+    validators::assert_owned_by(emissions_account, ctx.program_id)?;
+    let mut config: EmissionsConfig = unpack_state(&emissions_account.data.borrow())?;
+    validators::assert_initialized(&config)?;
+    validators::assert_mint_matches(&config.mint, mint_account.key)?;
+
+    let (until, amount) = config.pending(Clock::get()?.slot)?;
+    if amount == 0 {
+        msg!("No emissions pending since slot {}", config.distributed_until_slot);
+        return Ok(());
+    }
+    let mut mint = LoadedMint::load(ctx.program_id, mint_account)?;
+    if mint.state.mint_authority != Some(*emissions_account.key) {
+        return Err(TokenError::Unauthorized.into());
+    }
+
+    let mut recipients = Vec::with_capacity(config.recipients.len());
+    for recipient in &config.recipients {
+        let account = ctx.next_account()?;
+        validators::assert_owned_by(account, ctx.program_id)?;
+        if *account.key != recipient.vault {
+            msg!("Expected emission recipient {}, got {}", recipient.vault, account.key);
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let token = LoadedTokenAccount::load(ctx.program_id, account)?;
+        validators::assert_mint_matches(&token.state.mint, mint_account.key)?;
+        recipients.push(token);
+    }
+
+    check_not_paused(mint_account, PausedOperation::Minting)?;
+    mint.state.mint_supply(amount)?;
+    // 排放结束：铸币权限交还原权限
+    if until == config.end_slot {
+        mint.state.mint_authority = Some(config.authority);
+    }
+    mint.save()?;
+    for (token, share) in recipients.into_iter().zip(config.split(amount)) {
+        credit_minted(ctx.program_id, &mint, token, share, &mut ctx.accounts)?;
+    }
+    if until == config.end_slot {
+        emit_authority_changed(
+            mint_account.key,
+            AuthorityType::MintTokens,
+            Some(*emissions_account.key),
+            mint.state.mint_authority,
+        )?;
+    }
+
+    config.distributed_until_slot = until;
+    pack_state(&config, &mut emissions_account.data.borrow_mut())?;
+
+    msg!("Distributed {} emissions through slot {}", amount, until);
+    Ok(())
+}
+
+/// 开启积分模式
+fn process_enable_points_expiry(ctx: &mut ProcessingContext, lifetime_slots: u64) -> ProgramResult {
+    let mint = ctx.next_mint()?;
+    let authority_account = ctx.next_account()?;
+
+    mint.check_mint_authority(authority_account)?;
+    // 已有余额没有批次，开启后会被当作过期积分销毁
+    if mint.state.supply != 0 {
+        msg!("Points expiry must be enabled before any tokens are minted (supply {})", mint.state.supply);
+        return Err(TokenError::InvalidInstruction.into());
+    }
+    if lifetime_slots == 0 {
+        msg!("Points lifetime must be at least one slot");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if extension::get_extension::<Mint, PointsConfig>(&mint.info.data.borrow())?.is_some() {
+        msg!("Points expiry already enabled for mint {}", mint.info.key);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    extension::init_extension::<Mint, _>(&mut mint.info.data.borrow_mut(), &PointsConfig { lifetime_slots })?;
+
+    msg!("Points minted from {} expire after {} slots", mint.info.key, lifetime_slots);
+    Ok(())
+}
+
+/// 销毁过期积分
+fn process_expire_points(ctx: &mut ProcessingContext) -> ProgramResult {
+    let mut mint = ctx.next_mint()?;
+    if extension::get_extension::<Mint, PointsConfig>(&mint.info.data.borrow())?.is_none() {
+        msg!("Mint {} is not a points mint", mint.info.key);
+        return Err(ProgramError::InvalidAccountData);
+    }
+    check_not_paused(mint.info, PausedOperation::Burning)?;
+    let slot = Clock::get()?.slot;
+
+    let mut total = 0u64;
+    while let Some(token_account) = ctx.accounts.next() {
+        validators::assert_owned_by(token_account, ctx.program_id)?;
+        if token_account.key == mint.info.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut token = LoadedTokenAccount::load(ctx.program_id, token_account)?;
+        validators::assert_mint_matches(&token.state.mint, mint.info.key)?;
+        // 没有批次的账户（开启积分模式前创建）整个余额都视为过期
+        let lots = extension::get_extension::<TokenAccount, PointsLots>(&token_account.data.borrow())?;
+        let expired = match lots {
+            Some(mut lots) => {
+                let expired = lots.expire(slot, token.state.amount);
+                extension::init_extension::<TokenAccount, _>(&mut token_account.data.borrow_mut(), &lots)?;
+                expired
+            }
+            None => token.state.amount,
+        };
+        if expired == 0 {
+            continue;
+        }
+
+        let balance_before = token.state.amount;
+        (mint.state.supply, token.state.amount) =
+            balance::burn_amounts(mint.state.supply, token.state.amount, expired)?;
+        token.save()?;
+        mint.save()?;
+        update_holder_count(mint.info, &[(balance_before, token.state.amount)])?;
+        let delegate = token.state.vote_delegate;
+        move_delegated_votes(ctx.program_id, mint.info.key, delegate, None, expired, &mut ctx.accounts)?;
+        total += expired;
+        msg!("Expired {} points from {}", expired, token_account.key);
+    }
+
+    msg!("Expired {} points in total", total);
+    Ok(())
+}
+
+/// 设置休眠账户政策
+fn process_set_dormancy_policy(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    recovery_vault: Pubkey,
+    dormant_epochs: u64,
+    notice_epochs: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+
+    let mint = LoadedMint::load(program_id, mint_account)?;
+    // 已有政策只能由政策权限修改，铸币权限之后变更也不影响
+    match extension::get_extension::<Mint, DormancyPolicy>(&mint_account.data.borrow())? {
+        Some(policy) => {
+            validators::assert_signer(authority_account)?;
+            if policy.authority != *authority_account.key {
+                return Err(TokenError::Unauthorized.into());
+            }
+        }
+        None => mint.check_mint_authority(authority_account)?,
+    }
+    if dormant_epochs == 0 {
+        msg!("Accounts must be inactive for at least one epoch before they are dormant");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let policy = DormancyPolicy { authority: *authority_account.key, recovery_vault, dormant_epochs, notice_epochs };
+    extension::init_extension::<Mint, _>(&mut mint_account.data.borrow_mut(), &policy)?;
+
+    msg!(
+        "Accounts of {} inactive for {} epochs can be swept to {} {} epochs after a warning",
+        mint_account.key,
+        dormant_epochs,
+        recovery_vault,
+        notice_epochs
+    );
+    Ok(())
+}
+
+/// 读取铸币的休眠账户政策，并校验权限签名
+fn load_dormancy_policy(
+    mint_account: &AccountInfo,
+    authority_account: &AccountInfo,
+) -> Result<DormancyPolicy, ProgramError> {
+    let policy = extension::get_extension::<Mint, DormancyPolicy>(&mint_account.data.borrow())?.ok_or_else(|| {
+        msg!("Mint {} has no dormancy policy", mint_account.key);
+        ProgramError::InvalidAccountData
+    })?;
+    validators::assert_signer(authority_account)?;
+    if policy.authority != *authority_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    Ok(policy)
+}
+
+/// slot 所在的 epoch
+fn epoch_of(slot: u64) -> Result<u64, ProgramError> {
+    Ok(EpochSchedule::get()?.get_epoch(slot))
+}
+
+/// 对休眠账户发出警告
+fn process_warn_dormant(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let notice_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_owned_by(token_account, program_id)?;
+    let policy = load_dormancy_policy(mint_account, authority_account)?;
+    let token = LoadedTokenAccount::load(program_id, token_account)?;
+    validators::assert_mint_matches(&token.state.mint, mint_account.key)?;
+    if token.state.amount == 0 || *token_account.key == policy.recovery_vault {
+        msg!("Account {} has nothing to sweep", token_account.key);
+        return Err(ProgramError::InvalidArgument);
+    }
+    let activity = extension::get_extension::<TokenAccount, AccountActivity>(&token_account.data.borrow())?
+        .ok_or_else(|| {
+            msg!("Account {} does not track activity", token_account.key);
+            ProgramError::InvalidAccountData
+        })?;
+
+    let clock = Clock::get()?;
+    let epoch = epoch_of(clock.slot)?;
+    let dormant_epoch = epoch_of(activity.last_activity_slot)?.saturating_add(policy.dormant_epochs);
+    if epoch < dormant_epoch {
+        msg!("Account {} is not dormant until epoch {}", token_account.key, dormant_epoch);
+        return Err(TokenError::TimelockNotElapsed.into());
+    }
+
+    let (notice_key, bump) = find_dormancy_notice_address(token_account.key, program_id);
+    if notice_key != *notice_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    create_pda_account(
+        authority_account,
+        notice_account,
+        system_program_account,
+        program_id,
+        DormancyNotice::LEN,
+        &[DORMANCY_SEED, token_account.key.as_ref(), &[bump]],
+    )?;
+    let notice = DormancyNotice {
+        is_initialized: true,
+        token_account: *token_account.key,
+        last_activity_slot: activity.last_activity_slot,
+        warned_at_slot: clock.slot,
+    };
+    pack_state(&notice, &mut notice_account.data.borrow_mut())?;
+
+    let sweepable_epoch = epoch.saturating_add(policy.notice_epochs);
+    DormancyEvent::Warned {
+        mint: *mint_account.key,
+        account: *token_account.key,
+        owner: token.state.owner,
+        amount: token.state.amount,
+        last_activity_slot: activity.last_activity_slot,
+        sweepable_epoch,
+        slot: clock.slot,
+    }
+    .emit()?;
+
+    msg!("Dormant account {} warned, sweepable from epoch {}", token_account.key, sweepable_epoch);
+    Ok(())
+}
+
+/// 归集休眠账户余额
+fn process_sweep_dormant(ctx: &mut ProcessingContext) -> ProgramResult {
+    let mint_account = ctx.next_account()?;
+    let mut source = ctx.next_token_account()?;
+    let recovery_account = ctx.next_account()?;
+    let notice_account = ctx.next_account()?;
+    let authority_account = ctx.next_account()?;
+
+    validators::assert_owned_by(mint_account, ctx.program_id)?;
+    validators::assert_owned_by(notice_account, ctx.program_id)?;
+    let policy = load_dormancy_policy(mint_account, authority_account)?;
+    validators::assert_mint_matches(&source.state.mint, mint_account.key)?;
+    if *recovery_account.key != policy.recovery_vault {
+        msg!("Recovery vault must be {}", policy.recovery_vault);
+        return Err(ProgramError::InvalidArgument);
+    }
+    let (notice_key, _) = find_dormancy_notice_address(source.info.key, ctx.program_id);
+    if notice_key != *notice_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let notice: DormancyNotice = unpack_state(&notice_account.data.borrow())?;
+    validators::assert_initialized(&notice)?;
+
+    let clock = Clock::get()?;
+    let activity = extension::get_extension::<TokenAccount, AccountActivity>(&source.info.data.borrow())?
+        .ok_or(ProgramError::InvalidAccountData)?;
+    // 警告后余额有过变化：账户恢复活跃，警告作废
+    if activity.last_activity_slot != notice.last_activity_slot {
+        close_pda_account(notice_account, authority_account)?;
+        DormancyEvent::Cancelled { mint: *mint_account.key, account: *source.info.key, slot: clock.slot }.emit()?;
+        msg!("Account {} became active again, dormancy notice cancelled", source.info.key);
+        return Ok(());
+    }
+    let sweepable_epoch = epoch_of(notice.warned_at_slot)?.saturating_add(policy.notice_epochs);
+    if epoch_of(clock.slot)? < sweepable_epoch {
+        msg!("Account {} cannot be swept until epoch {}", source.info.key, sweepable_epoch);
+        return Err(TokenError::TimelockNotElapsed.into());
+    }
+    source.check_not_frozen()?;
+    check_global_freeze(mint_account, source.info)?;
+    check_not_paused(mint_account, PausedOperation::Transfers)?;
+
+    let amount = source.state.amount;
+    transfer_to(ctx, &mut source, recovery_account, amount, mint_account)?;
+    close_pda_account(notice_account, authority_account)?;
+    DormancyEvent::Swept {
+        mint: *mint_account.key,
+        account: *source.info.key,
+        recovery_vault: *recovery_account.key,
+        amount,
+        slot: clock.slot,
+    }
+    .emit()?;
+
+    msg!("Swept {} from dormant account {} to {}", amount, source.info.key, recovery_account.key);
+    Ok(())
+}
+
+/// 更新储备证明
+fn process_update_attestation(program_id: &Pubkey, accounts: &[AccountInfo], reserves: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let attestation_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let attester_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    LoadedMint::load(program_id, mint_account)?.check_mint_authority(authority_account)?;
+    // 审计方必须独立签名，铸币权限不能自证
+    validators::assert_signer(attester_account)?;
+    if attester_account.key == authority_account.key {
+        msg!("Attester must be independent of the mint authority");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (attestation_key, bump) = find_attestation_address(mint_account.key, program_id);
+    if attestation_key != *attestation_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if attestation_account.data_is_empty() {
+        create_pda_account(
+            authority_account,
+            attestation_account,
+            system_program_account,
+            program_id,
+            Attestation::LEN,
+            &[ATTESTATION_SEED, mint_account.key.as_ref(), &[bump]],
+        )?;
+    } else {
+        validators::assert_owned_by(attestation_account, program_id)?;
+    }
+
+    let slot = Clock::get()?.slot;
+    let attestation = Attestation {
+        is_initialized: true,
+        mint: *mint_account.key,
+        attester: *attester_account.key,
+        reserves,
+        attested_at_slot: slot,
+    };
+    pack_state(&attestation, &mut attestation_account.data.borrow_mut())?;
+    ReserveEvent::Attested { mint: *mint_account.key, attester: *attester_account.key, reserves, slot }.emit()?;
+
+    msg!("Attester {} reported reserves of {} for {}", attester_account.key, reserves, mint_account.key);
+    Ok(())
+}
+
+/// 核对供应量是否有储备支撑
+fn process_verify_supply_backed(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let attestation_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(attestation_account, program_id)?;
+    let mint = LoadedMint::load(program_id, mint_account)?;
+    let (attestation_key, _) = find_attestation_address(mint_account.key, program_id);
+    if attestation_key != *attestation_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let attestation: Attestation = unpack_state(&attestation_account.data.borrow())?;
+    validators::assert_initialized(&attestation)?;
+
+    let backed = mint.state.supply <= attestation.reserves;
+    ReserveEvent::Verified {
+        mint: *mint_account.key,
+        supply: mint.state.supply,
+        reserves: attestation.reserves,
+        attested_at_slot: attestation.attested_at_slot,
+        backed,
+        slot: Clock::get()?.slot,
+    }
+    .emit()?;
+
+    msg!(
+        "Supply {} of {} is {} by reserves of {}",
+        mint.state.supply,
+        mint_account.key,
+        if backed { "backed" } else { "NOT backed" },
+        attestation.reserves
+    );
+    Ok(())
+}
+
+/// 开启代币迁移
+fn process_initialize_conversion(program_id: &Pubkey, accounts: &[AccountInfo], rate: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let old_mint_account = next_account_info(account_info_iter)?;
+    let new_mint_account = next_account_info(account_info_iter)?;
+    let conversion_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(vault_account, program_id)?;
+    let old_mint = LoadedMint::load(program_id, old_mint_account)?;
+    old_mint.check_mint_authority(authority_account)?;
+    let new_mint = LoadedMint::load(program_id, new_mint_account)?;
+    if old_mint_account.key == new_mint_account.key || old_mint.state.is_nft || new_mint.state.is_nft {
+        msg!("Conversion needs two distinct fungible mints");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if rate == 0 {
+        msg!("Conversion rate must be non-zero");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (conversion_key, bump) = find_conversion_address(old_mint_account.key, program_id);
+    if conversion_key != *conversion_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    // 托管账户只能由兑换 PDA 持有，兑换时程序直接记账转出
+    let vault = TokenAccount::deserialize(&vault_account.data.borrow())?;
+    validators::assert_mint_matches(&vault.mint, new_mint_account.key)?;
+    if vault.owner != conversion_key {
+        msg!("Vault token account must be owned by the conversion PDA");
+        return Err(TokenError::Unauthorized.into());
+    }
+
+    create_pda_account(
+        payer_account,
+        conversion_account,
+        system_program_account,
+        program_id,
+        Conversion::LEN,
+        &[CONVERSION_SEED, old_mint_account.key.as_ref(), &[bump]],
+    )?;
+    let conversion = Conversion {
+        is_initialized: true,
+        old_mint: *old_mint_account.key,
+        new_mint: *new_mint_account.key,
+        vault: *vault_account.key,
+        rate,
+        total_converted: 0,
+    };
+    pack_state(&conversion, &mut conversion_account.data.borrow_mut())?;
+
+    msg!(
+        "Holders of {} can convert to {} at {}/{}",
+        old_mint_account.key,
+        new_mint_account.key,
+        rate,
+        CONVERSION_RATE_SCALE
+    );
+    Ok(())
+}
+
+/// 兑换旧代币
+fn process_convert_mint(ctx: &mut ProcessingContext, amount: u64) -> ProgramResult {
+    let mut old_mint = ctx.next_mint()?;
+    let new_mint_account = ctx.next_account()?;
+    let mut source = ctx.next_token_account()?;
+    let dest_account = ctx.next_account()?;
+    let conversion_account = ctx.next_account()?;
+    let mut vault = ctx.next_token_account()?;
+    let owner_account = ctx.next_account()?;
+
+    validators::assert_owned_by(conversion_account, ctx.program_id)?;
+    let (conversion_key, _) = find_conversion_address(old_mint.info.key, ctx.program_id);
+    if conversion_key != *conversion_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut conversion: Conversion = unpack_state(&conversion_account.data.borrow())?;
+    validators::assert_initialized(&conversion)?;
+    validators::assert_mint_matches(new_mint_account.key, &conversion.new_mint)?;
+    if conversion.vault != *vault.info.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    source.check_owner(owner_account)?;
+    validators::assert_mint_matches(&source.state.mint, old_mint.info.key)?;
+    sync_rescale(old_mint.info, &mut source)?;
+    source.check_spendable(amount)?;
+    check_global_freeze(old_mint.info, source.info)?;
+    check_not_paused(old_mint.info, PausedOperation::Burning)?;
+    check_global_freeze(new_mint_account, vault.info)?;
+    check_not_paused(new_mint_account, PausedOperation::Transfers)?;
+
+    let converted = conversion.converted_amount(amount)?;
+    if converted == 0 {
+        msg!("Converting {} yields no tokens of the new mint", amount);
+        return Err(ProgramError::InvalidArgument);
+    }
+    vault.check_spendable(converted)?;
+
+    // 销毁旧代币（同 Burn）
+    let balance_before = source.state.amount;
+    (old_mint.state.supply, source.state.amount) =
+        balance::burn_amounts(old_mint.state.supply, source.state.amount, amount)?;
+    source.save()?;
+    old_mint.save()?;
+    update_holder_count(old_mint.info, &[(balance_before, source.state.amount)])?;
+    let delegate = source.state.vote_delegate;
+    move_delegated_votes(ctx.program_id, old_mint.info.key, delegate, None, amount, &mut ctx.accounts)?;
+
+    conversion.total_converted = conversion.total_converted.checked_add(amount).ok_or(TokenError::Overflow)?;
+    pack_state(&conversion, &mut conversion_account.data.borrow_mut())?;
+    transfer_to(ctx, &mut vault, dest_account, converted, new_mint_account)?;
+
+    msg!("Converted {} of {} into {} of {}", amount, old_mint.info.key, converted, new_mint_account.key);
+    Ok(())
+}
+
+/// 锁入治理代币
+fn process_deposit_governing_tokens(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    realm: Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let record_account = next_account_info(account_info_iter)?;
+    let holding_account = next_account_info(account_info_iter)?;
+    let source_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(owner_account)?;
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_owned_by(source_account, program_id)?;
+    if amount == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (record_key, record_bump) =
+        find_token_owner_record_address(&realm, mint_account.key, owner_account.key, program_id);
+    let (holding_key, holding_bump) =
+        find_governing_token_holding_address(&realm, mint_account.key, program_id);
+    if record_key != *record_account.key || holding_key != *holding_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut source = LoadedTokenAccount::load(program_id, source_account)?;
+    validators::assert_mint_matches(&source.state.mint, mint_account.key)?;
+    if source.state.owner != *owner_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    check_global_freeze(mint_account, source_account)?;
+    check_not_paused(mint_account, PausedOperation::Transfers)?;
+    source.check_not_frozen()?;
+
+    // 托管账户归自身所有，只能通过 WithdrawGoverningTokens 转出
+    if holding_account.data_is_empty() {
+        create_pda_account(
+            owner_account,
+            holding_account,
+            system_program_account,
+            program_id,
+            TokenAccount::LEN,
+            &[GOVERNANCE_SEED, realm.as_ref(), mint_account.key.as_ref(), &[holding_bump]],
+        )?;
+        let holding = TokenAccount::new(*mint_account.key, holding_key).with_nft(source.state.is_nft());
+        holding.serialize(&mut holding_account.data.borrow_mut())?;
+    }
+    if record_account.data_is_empty() {
+        create_pda_account(
+            owner_account,
+            record_account,
+            system_program_account,
+            program_id,
+            TokenOwnerRecord::LEN,
+            &[
+                GOVERNANCE_SEED,
+                realm.as_ref(),
+                mint_account.key.as_ref(),
+                owner_account.key.as_ref(),
+                &[record_bump],
+            ],
+        )?;
+        let record = TokenOwnerRecord {
+            is_initialized: true,
+            realm,
+            governing_token_mint: *mint_account.key,
+            governing_token_owner: *owner_account.key,
+            governing_token_deposit_amount: 0,
+        };
+        pack_state(&record, &mut record_account.data.borrow_mut())?;
+    }
+
+    let mut holding = LoadedTokenAccount::load(program_id, holding_account)?;
+    let mut record: TokenOwnerRecord = unpack_state(&record_account.data.borrow())?;
+    holding.check_not_frozen()?;
+
+    move_synced(mint_account, &mut source, &mut holding, amount)?;
+    record.governing_token_deposit_amount = record
+        .governing_token_deposit_amount
+        .checked_add(amount)
+        .ok_or(TokenError::Overflow)?;
+    pack_state(&record, &mut record_account.data.borrow_mut())?;
+    move_delegated_votes(program_id, mint_account.key, source.state.vote_delegate, None, amount, account_info_iter)?;
+
+    msg!(
+        "Deposited {} governing tokens into realm {}, total {}",
+        amount,
+        realm,
+        record.governing_token_deposit_amount
+    );
+    Ok(())
+}
+
+/// 取回治理代币
+fn process_withdraw_governing_tokens(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    realm: Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let record_account = next_account_info(account_info_iter)?;
+    let holding_account = next_account_info(account_info_iter)?;
+    let destination_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(owner_account)?;
+    validators::assert_owned_by(record_account, program_id)?;
+    validators::assert_owned_by(holding_account, program_id)?;
+    validators::assert_owned_by(destination_account, program_id)?;
+    let (record_key, _) = find_token_owner_record_address(&realm, mint_account.key, owner_account.key, program_id);
+    let (holding_key, _) = find_governing_token_holding_address(&realm, mint_account.key, program_id);
+    if record_key != *record_account.key || holding_key != *holding_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut record: TokenOwnerRecord = unpack_state(&record_account.data.borrow())?;
+    validators::assert_initialized(&record)?;
+    if record.governing_token_deposit_amount < amount {
+        return Err(TokenError::InsufficientFunds.into());
+    }
+
+    let mut holding = LoadedTokenAccount::load(program_id, holding_account)?;
+    let mut destination = LoadedTokenAccount::load(program_id, destination_account)?;
+    validators::assert_mint_matches(&destination.state.mint, mint_account.key)?;
+    check_global_freeze(mint_account, holding_account)?;
+    check_not_paused(mint_account, PausedOperation::Transfers)?;
+    holding.check_not_frozen()?;
+    destination.check_not_frozen()?;
+
+    record.governing_token_deposit_amount -= amount;
+    pack_state(&record, &mut record_account.data.borrow_mut())?;
+    move_synced(mint_account, &mut holding, &mut destination, amount)?;
+    move_delegated_votes(
+        program_id,
+        mint_account.key,
+        None,
+        destination.state.vote_delegate,
+        amount,
+        account_info_iter,
+    )?;
+
+    msg!(
+        "Withdrew {} governing tokens from realm {}, remaining {}",
+        amount,
+        realm,
+        record.governing_token_deposit_amount
+    );
+    Ok(())
+}
+
+/// 设置紧急管理员：只有 ProgramData 中记录的升级权限可以调用
+fn process_initialize_emergency_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    emergency_admin: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let program_data_account = next_account_info(account_info_iter)?;
+    let upgrade_authority_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(upgrade_authority_account)?;
+    let (program_data_key, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    if program_data_key != *program_data_account.key || *program_data_account.owner != bpf_loader_upgradeable::id() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let metadata_len = UpgradeableLoaderState::size_of_programdata_metadata();
+    let program_data = program_data_account.data.borrow();
+    let state: UpgradeableLoaderState = program_data
+        .get(..metadata_len)
+        .and_then(|header| limited_deserialize(header, metadata_len as u64).ok())
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let UpgradeableLoaderState::ProgramData { upgrade_authority_address, .. } = state else {
+        return Err(ProgramError::InvalidAccountData);
+    };
+    if upgrade_authority_address != Some(*upgrade_authority_account.key) {
+        msg!("Only the program upgrade authority can set the emergency admin");
+        return Err(TokenError::Unauthorized.into());
+    }
+    drop(program_data);
+
+    let (config_key, bump) = find_emergency_config_address(program_id);
+    if config_key != *config_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    create_pda_account(
+        upgrade_authority_account,
+        config_account,
+        system_program_account,
+        program_id,
+        EmergencyConfig::LEN,
+        &[EMERGENCY_CONFIG_SEED, &[bump]],
+    )?;
+    let config = EmergencyConfig { is_initialized: true, emergency_admin };
+    pack_state(&config, &mut config_account.data.borrow_mut())?;
+
+    EmergencyRecoveryEvent::ConfigInitialized { emergency_admin, slot: Clock::get()?.slot }.emit()?;
+    msg!("Emergency admin set to {}", emergency_admin);
+    Ok(())
+}
+
+/// 读取铸币上指定类型的当前权限
+fn current_authority(
+    mint_account: &AccountInfo,
+    mint: &Mint,
+    authority_type: AuthorityType,
+) -> Result<Option<Pubkey>, ProgramError> {
+    Ok(match authority_type {
+        AuthorityType::MintTokens => mint.mint_authority,
+        AuthorityType::FreezeAccount => mint.freeze_authority,
+        AuthorityType::BurnTokens => burn_authority(mint_account)?,
+    })
+}
+
+/// 替换指定类型的权限并返回原权限；销毁权限直接写入扩展区，其余由调用方写回铸币状态
+fn replace_authority(
+    mint_account: &AccountInfo,
+    mint: &mut Mint,
+    authority_type: AuthorityType,
+    new_authority: Option<Pubkey>,
+) -> Result<Option<Pubkey>, ProgramError> {
+    Ok(match authority_type {
+        AuthorityType::MintTokens => std::mem::replace(&mut mint.mint_authority, new_authority),
+        AuthorityType::FreezeAccount => std::mem::replace(&mut mint.freeze_authority, new_authority),
+        AuthorityType::BurnTokens => {
+            let old_authority = burn_authority(mint_account)?;
+            let burn_authority = BurnAuthority { authority: new_authority };
+            extension::init_extension::<Mint, _>(&mut mint_account.data.borrow_mut(), &burn_authority)?;
+            old_authority
+        }
+    })
+}
+
+/// 紧急管理员提议权限恢复
+fn process_propose_authority_recovery(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    authority_type: AuthorityType,
+    new_authority: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let recovery_account = next_account_info(account_info_iter)?;
+    let admin_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(config_account, program_id)?;
+    validators::assert_owned_by(mint_account, program_id)?;
+    let (config_key, _) = find_emergency_config_address(program_id);
+    if config_key != *config_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let config: EmergencyConfig = unpack_state(&config_account.data.borrow())?;
+    validators::assert_initialized(&config)?;
+    validators::assert_signer(admin_account)?;
+    if config.emergency_admin != *admin_account.key {
+        return Err(TokenError::Unauthorized.into());
+    }
+    let mint = Mint::deserialize(&mint_account.data.borrow())?;
+    if current_authority(mint_account, &mint, authority_type)?.is_none() {
+        msg!("{:?} authority was revoked and cannot be recovered", authority_type);
+        return Err(TokenError::AuthorityRevoked.into());
+    }
+
+    let (recovery_key, bump) = find_authority_recovery_address(mint_account.key, authority_type, program_id);
+    if recovery_key != *recovery_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    create_pda_account(
+        admin_account,
+        recovery_account,
+        system_program_account,
+        program_id,
+        AuthorityRecovery::LEN,
+        &[AUTHORITY_RECOVERY_SEED, mint_account.key.as_ref(), &[authority_type as u8], &[bump]],
+    )?;
+
+    let proposed_slot = Clock::get()?.slot;
+    let recovery = AuthorityRecovery {
+        is_initialized: true,
+        mint: *mint_account.key,
+        authority_type,
+        new_authority,
+        emergency_admin: *admin_account.key,
+        proposed_slot,
+        executable_slot: proposed_slot
+            .checked_add(EMERGENCY_RECOVERY_TIMELOCK_SLOTS)
+            .ok_or(TokenError::Overflow)?,
+    };
+    pack_state(&recovery, &mut recovery_account.data.borrow_mut())?;
+
+    EmergencyRecoveryEvent::RecoveryProposed {
+        mint: recovery.mint,
+        authority_type,
+        new_authority,
+        executable_slot: recovery.executable_slot,
+        slot: proposed_slot,
+    }
+    .emit()?;
+    msg!(
+        "Proposed {:?} authority recovery to {}, executable at slot {}",
+        authority_type,
+        new_authority,
+        recovery.executable_slot
+    );
+    Ok(())
+}
+
+/// 加载并校验权限恢复提议
+fn load_authority_recovery(
+    program_id: &Pubkey,
+    mint_account: &AccountInfo,
+    recovery_account: &AccountInfo,
+    admin_account: &AccountInfo,
+) -> Result<AuthorityRecovery, ProgramError> {
+    validators::assert_owned_by(mint_account, program_id)?;
+    validators::assert_owned_by(recovery_account, program_id)?;
+    let recovery: AuthorityRecovery = unpack_state(&recovery_account.data.borrow())?;
+    validators::assert_initialized(&recovery)?;
+    validators::assert_mint_matches(&recovery.mint, mint_account.key)?;
+    if recovery.emergency_admin != *admin_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(recovery)
+}
+
+/// 执行到期的权限恢复（任何人可调用）
+fn process_execute_authority_recovery(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let recovery_account = next_account_info(account_info_iter)?;
+    let admin_account = next_account_info(account_info_iter)?;
+
+    let recovery = load_authority_recovery(program_id, mint_account, recovery_account, admin_account)?;
+    let current_slot = Clock::get()?.slot;
+    if current_slot < recovery.executable_slot {
+        msg!("Authority recovery executable at slot {}, now {}", recovery.executable_slot, current_slot);
+        return Err(TokenError::TimelockNotElapsed.into());
+    }
+
+    // 时间锁期间权限若已被撤销，则不再恢复
+    let mut mint = Mint::deserialize(&mint_account.data.borrow())?;
+    let old_authority =
+        current_authority(mint_account, &mint, recovery.authority_type)?.ok_or(TokenError::AuthorityRevoked)?;
+    replace_authority(mint_account, &mut mint, recovery.authority_type, Some(recovery.new_authority))?;
+    mint.serialize(&mut mint_account.data.borrow_mut())?;
+    emit_authority_changed(
+        mint_account.key,
+        recovery.authority_type,
+        Some(old_authority),
+        Some(recovery.new_authority),
+    )?;
+
+    close_pda_account(recovery_account, admin_account)?;
+
+    EmergencyRecoveryEvent::RecoveryExecuted {
+        mint: recovery.mint,
+        authority_type: recovery.authority_type,
+        old_authority,
+        new_authority: recovery.new_authority,
+        slot: current_slot,
+    }
+    .emit()?;
+    msg!(
+        "Recovered {:?} authority: {} -> {}",
+        recovery.authority_type,
+        old_authority,
+        recovery.new_authority
+    );
+    Ok(())
+}
+
+/// 取消权限恢复
+fn process_cancel_authority_recovery(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account = next_account_info(account_info_iter)?;
+    let recovery_account = next_account_info(account_info_iter)?;
+    let signer_account = next_account_info(account_info_iter)?;
+    let admin_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(signer_account)?;
+    let recovery = load_authority_recovery(program_id, mint_account, recovery_account, admin_account)?;
+    let mint = Mint::deserialize(&mint_account.data.borrow())?;
+    if *signer_account.key != recovery.emergency_admin
+        && current_authority(mint_account, &mint, recovery.authority_type)? != Some(*signer_account.key)
+    {
+        return Err(TokenError::Unauthorized.into());
+    }
+
+    close_pda_account(recovery_account, admin_account)?;
+
+    EmergencyRecoveryEvent::RecoveryCancelled {
+        mint: recovery.mint,
+        authority_type: recovery.authority_type,
+        cancelled_by: *signer_account.key,
+        slot: Clock::get()?.slot,
+    }
+    .emit()?;
+    msg!("Cancelled {:?} authority recovery for mint {}", recovery.authority_type, recovery.mint);
+    Ok(())
+}
+
+/// 开启空铸币自动关闭
+fn process_enable_auto_close(ctx: &mut ProcessingContext, close_destination: Pubkey) -> ProgramResult {
+    let mint = ctx.next_mint()?;
+    let authority_account = ctx.next_account()?;
+
+    mint.check_mint_authority(authority_account)?;
+
+    extension::init_extension::<Mint, _>(
+        &mut mint.info.data.borrow_mut(),
+        &MintCloseConfig { close_destination },
+    )?;
+
+    msg!("Mint {} will close to {} once empty", mint.info.key, close_destination);
+    Ok(())
+}
+
+/// 开启持有人计数
+fn process_enable_holder_count(ctx: &mut ProcessingContext) -> ProgramResult {
+    let mint = ctx.next_mint()?;
+    let authority_account = ctx.next_account()?;
+
+    mint.check_mint_authority(authority_account)?;
+    // 已有余额的账户没有 HOLDER_TRACKED 标志，计数无法从中途开始
+    if mint.state.supply != 0 {
+        msg!("Holder count must be enabled before any tokens are minted (supply {})", mint.state.supply);
+        return Err(TokenError::InvalidInstruction.into());
+    }
+    if extension::get_extension::<Mint, HolderCount>(&mint.info.data.borrow())?.is_some() {
+        msg!("Holder count already enabled for mint {}", mint.info.key);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    extension::init_extension::<Mint, _>(&mut mint.info.data.borrow_mut(), &HolderCount { count: 0 })?;
+
+    msg!("Holder count enabled for mint {}", mint.info.key);
+    Ok(())
+}
+
+/// 调整铸币精度
+fn process_rescale_mint(ctx: &mut ProcessingContext, new_decimals: u8) -> ProgramResult {
+    let mut mint = ctx.next_mint()?;
+    let authority_account = ctx.next_account()?;
+
+    mint.check_mint_authority(authority_account)?;
+    if mint.state.is_nft {
+        msg!("NFT mints cannot be rescaled");
+        return Err(TokenError::InvalidInstruction.into());
+    }
+    // 只能调大：调小会让余额换算时截断
+    if new_decimals <= mint.state.decimals {
+        msg!("New decimals {} must be greater than current decimals {}", new_decimals, mint.state.decimals);
+        return Err(ProgramError::InvalidArgument);
+    }
+    let mut rescale = extension::get_extension::<Mint, DecimalsRescale>(&mint.info.data.borrow())?.unwrap_or(
+        DecimalsRescale { epoch: 0, epoch_decimals: [mint.state.decimals; MAX_RESCALE_EPOCHS + 1] },
+    );
+    if rescale.epoch as usize >= MAX_RESCALE_EPOCHS {
+        msg!("Mint {} has already been rescaled {} times", mint.info.key, rescale.epoch);
+        return Err(TokenError::InvalidInstruction.into());
+    }
+    rescale.epoch += 1;
+    rescale.epoch_decimals[rescale.epoch as usize] = new_decimals;
+    let factor = rescale.factor_since(rescale.epoch - 1)?;
+    mint.state.supply = mint.state.supply.checked_mul(factor).ok_or(TokenError::Overflow)?;
+    mint.state.decimals = new_decimals;
+    mint.save()?;
+    extension::init_extension::<Mint, _>(&mut mint.info.data.borrow_mut(), &rescale)?;
+
+    msg!("Mint {} rescaled to {} decimals (epoch {})", mint.info.key, new_decimals, rescale.epoch);
+    Ok(())
+}
+
+/// 设置或解除全局冻结
+fn process_set_global_freeze(ctx: &mut ProcessingContext, frozen: bool) -> ProgramResult {
+    let mint = ctx.next_mint()?;
+    let authority_account = ctx.next_account()?;
+
+    mint.check_freeze_authority(authority_account)?;
+    extension::init_extension::<Mint, _>(&mut mint.info.data.borrow_mut(), &GlobalFreezeState { frozen })?;
+
+    msg!("Mint {} globally {}", mint.info.key, if frozen { "frozen" } else { "thawed" });
+    Ok(())
+}
+
+/// 设置分项暂停标志，为 None 的项保持原值
+fn process_set_pause_flags(
+    ctx: &mut ProcessingContext,
+    pause_transfers: Option<bool>,
+    pause_minting: Option<bool>,
+    pause_burning: Option<bool>,
+) -> ProgramResult {
+    let mint = ctx.next_mint()?;
+    let authority_account = ctx.next_account()?;
+
+    mint.check_freeze_authority(authority_account)?;
+    let mut state = extension::get_extension::<Mint, PauseState>(&mint.info.data.borrow())?.unwrap_or_default();
+    state.pause_transfers = pause_transfers.unwrap_or(state.pause_transfers);
+    state.pause_minting = pause_minting.unwrap_or(state.pause_minting);
+    state.pause_burning = pause_burning.unwrap_or(state.pause_burning);
+    extension::init_extension::<Mint, _>(&mut mint.info.data.borrow_mut(), &state)?;
+
+    msg!(
+        "Mint {} pause flags: transfers {}, minting {}, burning {}",
+        mint.info.key,
+        state.pause_transfers,
+        state.pause_minting,
+        state.pause_burning
+    );
+    Ok(())
+}
+
+/// 设置新建账户是否需要激活
+fn process_set_activation_required(ctx: &mut ProcessingContext, required: bool) -> ProgramResult {
+    let mint = ctx.next_mint()?;
+    let authority_account = ctx.next_account()?;
+
+    mint.check_mint_authority(authority_account)?;
+    extension::init_extension::<Mint, _>(&mut mint.info.data.borrow_mut(), &ActivationPolicy { required })?;
+
+    msg!("Mint {} activation {}", mint.info.key, if required { "required" } else { "not required" });
+    Ok(())
+}
+
+/// 所有者激活代币账户
+fn process_activate_account(ctx: &mut ProcessingContext) -> ProgramResult {
+    let token = ctx.next_token_account()?;
+    let owner_account = ctx.next_account()?;
+
+    token.check_owner(owner_account)?;
+    let activation = extension::get_extension::<TokenAccount, AccountActivation>(&token.info.data.borrow())?;
+    if activation != Some(AccountActivation { activated: false }) {
+        msg!("Token account {} does not need activation", token.info.key);
+        return Ok(());
+    }
+    extension::init_extension::<TokenAccount, _>(
+        &mut token.info.data.borrow_mut(),
+        &AccountActivation { activated: true },
+    )?;
+
+    msg!("Token account {} activated", token.info.key);
+    Ok(())
+}
+
+/// 关闭空铸币
+fn process_close_empty_mint(ctx: &mut ProcessingContext) -> ProgramResult {
+    let mint = ctx.next_mint()?;
+    let destination_account = ctx.next_account()?;
+
+    let config = extension::get_extension::<Mint, MintCloseConfig>(&mint.info.data.borrow())?.ok_or_else(|| {
+        msg!("Mint {} has not enabled auto close", mint.info.key);
+        TokenError::Unauthorized
+    })?;
+    if *destination_account.key != config.close_destination {
+        msg!("Close destination must be {}", config.close_destination);
+        return Err(TokenError::Unauthorized.into());
+    }
+    if mint.state.supply != 0 {
+        msg!("Mint supply is {}, must be 0", mint.state.supply);
+        return Err(TokenError::InsufficientFunds.into());
+    }
+    // 仍有铸币权限时供应量可能再次增长，只有不可再铸造的铸币才能被任何人关闭
+    if mint.state.mint_authority.is_some() {
+        msg!("Mint authority must be revoked before the mint can be closed");
+        return Err(TokenError::Unauthorized.into());
+    }
+
+    let lamports = mint.info.lamports();
+    close_pda_account(mint.info, destination_account)?;
+
+    msg!("Closed empty mint {}, reclaimed {} lamports", mint.info.key, lamports);
+    Ok(())
+}
+
+/// 创建哈希时间锁
+fn process_create_htlc(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
+    hash: [u8; 32],
     amount: u64,
+    timeout: i64,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
-    let token_account = next_account_info(account_info_iter)?;
-    let mint_account = next_account_info(account_info_iter)?;
-    let owner_account = next_account_info(account_info_iter)?;
-         msg!("process_burn1");
-    // 验证所有者权限
-    if !owner_account.is_signer {
-        msg!("owner_account is signer false: {:?}", owner_account.key);
+    let htlc_account = next_account_info(account_info_iter)?;
+    let escrow_account = next_account_info(account_info_iter)?;
+    let source_account = next_account_info(account_info_iter)?;
+    let recipient_account = next_account_info(account_info_iter)?;
+    let sender_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(sender_account)?;
+    validators::assert_owned_by(escrow_account, program_id)?;
+    validators::assert_owned_by(source_account, program_id)?;
+    validators::assert_owned_by(recipient_account, program_id)?;
+    if amount == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    if timeout <= Clock::get()?.unix_timestamp {
+        msg!("HTLC timeout {} is already in the past", timeout);
+        return Err(TokenError::HtlcExpired.into());
+    }
+
+    let (htlc_key, bump) = find_htlc_address(escrow_account.key, program_id);
+    if htlc_key != *htlc_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // 托管账户必须由 HTLC PDA 持有且为空，与双方账户同一铸币
+    let mut escrow = LoadedTokenAccount::load(program_id, escrow_account)?;
+    let mut source = LoadedTokenAccount::load(program_id, source_account)?;
+    let recipient = TokenAccount::deserialize(&recipient_account.data.borrow())?;
+    if escrow.state.owner != htlc_key {
+        msg!("Escrow token account must be owned by the HTLC PDA");
         return Err(TokenError::Unauthorized.into());
     }
-    msg!("process_burn2");
-    // 更新代币账户
-    let mut token_data = token_account.data.borrow_mut();
-    let mut token_acc = TokenAccount::deserialize(&mut &token_data[..])?;
-    msg!("process_burn3");
-    if token_acc.owner != *owner_account.key {
-        msg!("token_acc.owner{:?} !=  owner_account.key {:?}", token_acc.owner, *owner_account.key);
+    if escrow.state.amount != 0 {
+        msg!("Escrow token account must be empty");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    validators::assert_mint_matches(&escrow.state.mint, &source.state.mint)?;
+    validators::assert_mint_matches(&recipient.mint, &source.state.mint)?;
+    if source.state.owner != *sender_account.key {
         return Err(TokenError::Unauthorized.into());
     }
-    msg!("process_burn4");
-    if token_acc.amount < amount {
-        msg!("token_acc.amount {} < amount {}", token_acc.amount, amount);
-        return Err(TokenError::InsufficientFunds.into());
+    let mint_account = next_transfer_mint(account_info_iter, source_account, &source.state.mint)?;
+    source.check_not_frozen()?;
+    escrow.check_not_frozen()?;
+
+    create_pda_account(
+        sender_account,
+        htlc_account,
+        system_program_account,
+        program_id,
+        Htlc::LEN,
+        &[HTLC_SEED, escrow_account.key.as_ref(), &[bump]],
+    )?;
+
+    move_synced(mint_account, &mut source, &mut escrow, amount)?;
+    move_delegated_votes(
+        program_id,
+        &escrow.state.mint,
+        source.state.vote_delegate,
+        escrow.state.vote_delegate,
+        amount,
+        account_info_iter,
+    )?;
+
+    let htlc = Htlc {
+        is_initialized: true,
+        sender: *sender_account.key,
+        escrow: *escrow_account.key,
+        refund: *source_account.key,
+        recipient: *recipient_account.key,
+        mint: escrow.state.mint,
+        amount,
+        hash,
+        timeout,
+    };
+    pack_state(&htlc, &mut htlc_account.data.borrow_mut())?;
+
+    msg!("HTLC created: {} of {} locked until {}", amount, htlc.mint, timeout);
+    Ok(())
+}
+
+fn load_htlc(
+    program_id: &Pubkey,
+    htlc_account: &AccountInfo,
+    escrow_account: &AccountInfo,
+    sender_account: &AccountInfo,
+) -> Result<Htlc, ProgramError> {
+    validators::assert_owned_by(htlc_account, program_id)?;
+    validators::assert_owned_by(escrow_account, program_id)?;
+    let htlc: Htlc = unpack_state(&htlc_account.data.borrow())?;
+    validators::assert_initialized(&htlc)?;
+    if htlc.escrow != *escrow_account.key || htlc.sender != *sender_account.key {
+        return Err(ProgramError::InvalidAccountData);
     }
-    msg!("process_burn5");
-    token_acc.amount -= amount;
-    token_acc.serialize(&mut &mut token_data[..])?;
-    msg!("process_burn6");
-    // 更新铸币账户
-    let mut mint_data = mint_account.data.borrow_mut();
-    let mut mint = Mint::deserialize(&mut &mint_data[..])?;
-    mint.supply -= amount;
-    mint.serialize(&mut &mut mint_data[..])?;
-    
-    msg!("Burned {} tokens from {}", amount, token_account.key);
+    Ok(htlc)
+}
+
+/// 把托管代币转入 destination（HTLC 与条件转账的放款、退款共用），托管状态 PDA 由调用方关闭
+fn release_escrow<'a, 'b: 'a, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    escrow_account: &AccountInfo,
+    destination_account: &AccountInfo,
+    account_info_iter: &mut I,
+) -> ProgramResult {
+    validators::assert_owned_by(destination_account, program_id)?;
+    if destination_account.key == escrow_account.key {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut escrow = LoadedTokenAccount::load(program_id, escrow_account)?;
+    let mut destination = LoadedTokenAccount::load(program_id, destination_account)?;
+    validators::assert_mint_matches(&destination.state.mint, mint)?;
+    let mint_account = next_transfer_mint(account_info_iter, escrow_account, mint)?;
+    escrow.check_not_frozen()?;
+    destination.check_not_frozen()?;
+
+    move_synced(mint_account, &mut escrow, &mut destination, amount)?;
+    move_delegated_votes(
+        program_id,
+        mint,
+        escrow.state.vote_delegate,
+        destination.state.vote_delegate,
+        amount,
+        account_info_iter,
+    )
+}
+
+/// 出示原像赎回 HTLC
+fn process_redeem_htlc(program_id: &Pubkey, accounts: &[AccountInfo], preimage: &[u8]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let htlc_account = next_account_info(account_info_iter)?;
+    let escrow_account = next_account_info(account_info_iter)?;
+    let recipient_account = next_account_info(account_info_iter)?;
+    let sender_account = next_account_info(account_info_iter)?;
+
+    let htlc = load_htlc(program_id, htlc_account, escrow_account, sender_account)?;
+    if htlc.recipient != *recipient_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    // 过期后只能退款，避免赎回与退款同时成立
+    if Clock::get()?.unix_timestamp >= htlc.timeout {
+        msg!("HTLC expired at {}", htlc.timeout);
+        return Err(TokenError::HtlcExpired.into());
+    }
+    if hashv(&[preimage]).to_bytes() != htlc.hash {
+        msg!("Preimage does not match HTLC hash");
+        return Err(TokenError::InvalidProof.into());
+    }
+
+    release_escrow(program_id, &htlc.mint, htlc.amount, escrow_account, recipient_account, account_info_iter)?;
+    close_pda_account(htlc_account, sender_account)?;
+
+    msg!("HTLC redeemed: {} of {} to {}", htlc.amount, htlc.mint, recipient_account.key);
+    Ok(())
+}
+
+/// HTLC 过期退款
+fn process_refund_htlc(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    refund_htlc(program_id, &mut accounts.iter(), None).map(|_| ())
+}
+
+/// 过期退款：代币退回发送方代币账户并关闭 HTLC PDA；cranker 不为空时先从租金中划出 Crank 奖励，返回奖励金额
+fn refund_htlc<'a, 'b: 'a, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    program_id: &Pubkey,
+    account_info_iter: &mut I,
+    cranker: Option<&AccountInfo>,
+) -> Result<u64, ProgramError> {
+    let htlc_account = next_account_info(account_info_iter)?;
+    let escrow_account = next_account_info(account_info_iter)?;
+    let refund_account = next_account_info(account_info_iter)?;
+    let sender_account = next_account_info(account_info_iter)?;
+
+    let htlc = load_htlc(program_id, htlc_account, escrow_account, sender_account)?;
+    if htlc.refund != *refund_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let now = Clock::get()?.unix_timestamp;
+    if now < htlc.timeout {
+        msg!("HTLC refundable after {}, now {}", htlc.timeout, now);
+        return Err(TokenError::TimelockNotElapsed.into());
+    }
+
+    release_escrow(program_id, &htlc.mint, htlc.amount, escrow_account, refund_account, account_info_iter)?;
+    let reward = match cranker {
+        Some(cranker) => pay_crank_reward(htlc_account, cranker)?,
+        None => 0,
+    };
+    close_pda_account(htlc_account, sender_account)?;
+
+    msg!("HTLC refunded: {} of {} to {}", htlc.amount, htlc.mint, refund_account.key);
+    Ok(reward)
+}
+
+/// 清理到期状态
+fn process_crank(ctx: &mut ProcessingContext, items: &[CrankItem]) -> ProgramResult {
+    let cranker_account = ctx.next_signer()?;
+
+    if items.is_empty() {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let mut reward = 0u64;
+    for item in items {
+        let item_reward = match item {
+            CrankItem::ExpiredSession => crank_expired_session(ctx, cranker_account)?,
+            CrankItem::ExpiredHtlc => refund_htlc(ctx.program_id, &mut ctx.accounts, Some(cranker_account))?,
+            CrankItem::MaturedThaw => crank_matured_thaw(ctx)?,
+        };
+        reward = reward.checked_add(item_reward).ok_or(TokenError::Overflow)?;
+    }
+
+    msg!("Cranked {} items, {} lamports rewarded to {}", items.len(), reward, cranker_account.key);
     Ok(())
 }
 
-/// 设置铸币权限
-fn process_set_mint_authority(
+/// 关闭已过期的会话 PDA，返回奖励金额
+fn crank_expired_session(ctx: &mut ProcessingContext, cranker_account: &AccountInfo) -> Result<u64, ProgramError> {
+    let token = ctx.next_token_account()?;
+    let session_account = ctx.next_account()?;
+    let owner_account = ctx.next_account()?;
+    let token_account = token.info;
+
+    if token.state.owner != *owner_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let session = load_session(ctx.program_id, session_account, token_account.key)?;
+    let slot = Clock::get()?.slot;
+    if slot <= session.expires_at_slot {
+        msg!("Session {} expires at slot {}, now {}", session.key, session.expires_at_slot, slot);
+        return Err(TokenError::TimelockNotElapsed.into());
+    }
+
+    let reward = pay_crank_reward(session_account, cranker_account)?;
+    close_pda_account(session_account, owner_account)?;
+    msg!("Expired session {} closed for {}", session.key, token_account.key);
+    Ok(reward)
+}
+
+/// 清除已到期的定时解冻：写回未冻结状态并删除 ScheduledThaw
+fn crank_matured_thaw(ctx: &mut ProcessingContext) -> Result<u64, ProgramError> {
+    let token = ctx.next_token_account()?;
+    let token_account = token.info;
+
+    // 存储的标志仍为冻结、按当前 slot 读取已解冻，说明定时解冻已到期但尚未写回
+    let stored = TokenAccount::unpack_from_slice(&token_account.data.borrow())?;
+    if !stored.is_frozen() || token.state.is_frozen() {
+        msg!("Token account {} has no matured scheduled thaw", token_account.key);
+        return Err(TokenError::TimelockNotElapsed.into());
+    }
+
+    token.save()?;
+    set_scheduled_thaw(token_account, None)?;
+    msg!("Scheduled thaw of {} finalized", token_account.key);
+    Ok(0)
+}
+
+/// 从即将关闭的 PDA 中划出 Crank 奖励给调用者（PDA 余额不足时全部划出），返回实际金额
+fn pay_crank_reward(account: &AccountInfo, cranker: &AccountInfo) -> Result<u64, ProgramError> {
+    let reward = CRANK_REWARD_LAMPORTS.min(account.lamports());
+    **account.try_borrow_mut_lamports()? -= reward;
+    **cranker.try_borrow_mut_lamports()? = cranker.lamports().checked_add(reward).ok_or(TokenError::Overflow)?;
+    Ok(reward)
+}
+
+/// 创建带仲裁人的条件转账
+fn process_create_conditional_transfer(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    new_authority: Option<Pubkey>,
+    arbiter: Pubkey,
+    amount: u64,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
-    let mint_account = next_account_info(account_info_iter)?;
-    let current_authority_account = next_account_info(account_info_iter)?;
-    
-    // 验证当前铸币权限
-    let mut mint_data = mint_account.data.borrow_mut();
-    let mut mint = Mint::deserialize(&mut &mint_data[..])?;
-    
-    if !current_authority_account.is_signer {
+    let conditional_account = next_account_info(account_info_iter)?;
+    let escrow_account = next_account_info(account_info_iter)?;
+    let source_account = next_account_info(account_info_iter)?;
+    let recipient_account = next_account_info(account_info_iter)?;
+    let sender_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    validators::assert_signer(sender_account)?;
+    validators::assert_owned_by(escrow_account, program_id)?;
+    validators::assert_owned_by(source_account, program_id)?;
+    validators::assert_owned_by(recipient_account, program_id)?;
+    if amount == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (conditional_key, bump) = find_conditional_transfer_address(escrow_account.key, program_id);
+    if conditional_key != *conditional_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // 托管账户必须由条件转账 PDA 持有且为空，与双方账户同一铸币
+    let mut escrow = LoadedTokenAccount::load(program_id, escrow_account)?;
+    let mut source = LoadedTokenAccount::load(program_id, source_account)?;
+    let recipient = TokenAccount::deserialize(&recipient_account.data.borrow())?;
+    if escrow.state.owner != conditional_key {
+        msg!("Escrow token account must be owned by the conditional transfer PDA");
         return Err(TokenError::Unauthorized.into());
     }
-    
-    if let Some(auth) = mint.mint_authority {
-        if auth != *current_authority_account.key {
-            return Err(TokenError::Unauthorized.into());
-        }
-    } else {
+    if escrow.state.amount != 0 {
+        msg!("Escrow token account must be empty");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    validators::assert_mint_matches(&escrow.state.mint, &source.state.mint)?;
+    validators::assert_mint_matches(&recipient.mint, &source.state.mint)?;
+    if source.state.owner != *sender_account.key {
         return Err(TokenError::Unauthorized.into());
     }
-    
-    // 更新铸币权限
-    mint.mint_authority = new_authority;
-    mint.serialize(&mut &mut mint_data[..])?;
-    
-    msg!("Mint authority updated");
+    let mint_account = next_transfer_mint(account_info_iter, source_account, &source.state.mint)?;
+    source.check_not_frozen()?;
+    escrow.check_not_frozen()?;
+
+    create_pda_account(
+        sender_account,
+        conditional_account,
+        system_program_account,
+        program_id,
+        ConditionalTransfer::LEN,
+        &[CONDITIONAL_TRANSFER_SEED, escrow_account.key.as_ref(), &[bump]],
+    )?;
+
+    move_synced(mint_account, &mut source, &mut escrow, amount)?;
+    move_delegated_votes(
+        program_id,
+        &escrow.state.mint,
+        source.state.vote_delegate,
+        escrow.state.vote_delegate,
+        amount,
+        account_info_iter,
+    )?;
+
+    let conditional = ConditionalTransfer {
+        is_initialized: true,
+        sender: *sender_account.key,
+        escrow: *escrow_account.key,
+        refund: *source_account.key,
+        recipient: *recipient_account.key,
+        mint: escrow.state.mint,
+        arbiter,
+        amount,
+    };
+    pack_state(&conditional, &mut conditional_account.data.borrow_mut())?;
+
+    msg!("Conditional transfer created: {} of {} held for arbiter {}", amount, conditional.mint, arbiter);
+    Ok(())
+}
+
+/// 仲裁人放款（release 为 true）或退款
+fn process_settle_conditional_transfer(program_id: &Pubkey, accounts: &[AccountInfo], release: bool) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let conditional_account = next_account_info(account_info_iter)?;
+    let escrow_account = next_account_info(account_info_iter)?;
+    let destination_account = next_account_info(account_info_iter)?;
+    let sender_account = next_account_info(account_info_iter)?;
+    let arbiter_account = next_account_info(account_info_iter)?;
+
+    validators::assert_owned_by(conditional_account, program_id)?;
+    validators::assert_owned_by(escrow_account, program_id)?;
+    validators::assert_signer(arbiter_account)?;
+    let conditional: ConditionalTransfer = unpack_state(&conditional_account.data.borrow())?;
+    validators::assert_initialized(&conditional)?;
+    if conditional.escrow != *escrow_account.key || conditional.sender != *sender_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if conditional.arbiter != *arbiter_account.key {
+        msg!("Conditional transfer can only be settled by arbiter {}", conditional.arbiter);
+        return Err(TokenError::Unauthorized.into());
+    }
+    let expected_destination = if release { conditional.recipient } else { conditional.refund };
+    if expected_destination != *destination_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    release_escrow(
+        program_id,
+        &conditional.mint,
+        conditional.amount,
+        escrow_account,
+        destination_account,
+        account_info_iter,
+    )?;
+    close_pda_account(conditional_account, sender_account)?;
+
+    msg!(
+        "Conditional transfer {}: {} of {} to {}",
+        if release { "released" } else { "refunded" },
+        conditional.amount,
+        conditional.mint,
+        destination_account.key
+    );
+    Ok(())
+}
+
+// 通用辅助函数
+
+/// 通过 invoke_signed 创建由本程序拥有的 PDA 账户（租金豁免）
+fn create_pda_account<'a>(
+    payer: &AccountInfo<'a>,
+    new_account: &AccountInfo<'a>,
+    system_program_account: &AccountInfo<'a>,
+    program_id: &Pubkey,
+    space: usize,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    if *system_program_account.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let lamports = Rent::get()?.minimum_balance(space);
+    invoke_signed(
+        &system_instruction::create_account(payer.key, new_account.key, lamports, space as u64, program_id),
+        &[payer.clone(), new_account.clone(), system_program_account.clone()],
+        &[seeds],
+    )
+}
+
+/// 关闭本程序拥有的账户（PDA 或空铸币）：租金退回 destination，整个数据区清零后写入墓碑标记
+fn close_pda_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(TokenError::Overflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+    let mut data = account.data.borrow_mut();
+    data.fill(0);
+    if let Some(first) = data.first_mut() {
+        *first = CLOSED_ACCOUNT_DISCRIMINATOR;
+    }
+    Ok(())
+}
+
+/// 反序列化 PDA 状态账户（允许账户尾部有未使用空间）
+fn unpack_state<T: BorshDeserialize>(data: &[u8]) -> Result<T, ProgramError> {
+    T::deserialize(&mut &data[..]).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// 序列化 PDA 状态账户
+fn pack_state<T: BorshSerialize>(state: &T, data: &mut [u8]) -> ProgramResult {
+    borsh::to_writer(data, state).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// 为带 is_initialized 字段的状态账户实现 IsInitialized，供 validators::assert_initialized 使用
+macro_rules! impl_is_initialized {
+    ($($state:ty),* $(,)?) => {
+        $(impl IsInitialized for $state {
+            fn is_initialized(&self) -> bool {
+                self.is_initialized
+            }
+        })*
+    };
+}
+
+impl_is_initialized!(
+    Attestation,
+    AuthorityRecovery,
+    BondingCurve,
+    CompressedTree,
+    ConditionalTransfer,
+    Conversion,
+    DormancyNotice,
+    DutchAuction,
+    EmergencyConfig,
+    EmissionsConfig,
+    Htlc,
+    Mint,
+    Order,
+    PreAuthorization,
+    QueuedMint,
+    Sale,
+    SavingsPosition,
+    SavingsVault,
+    ShareVault,
+    TokenOwnerRecord,
+    VoteCheckpoints,
+);
+
+impl IsInitialized for TokenAccount {
+    fn is_initialized(&self) -> bool {
+        TokenAccount::is_initialized(self)
+    }
+}
+
+/// 余额变化时同步受托人的投票权检查点
+/// from/to 为转出、转入账户的受托人；需要的检查点 PDA 依次从账户迭代器中读取
+fn move_delegated_votes<'a, 'b: 'a, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    from: Option<Pubkey>,
+    to: Option<Pubkey>,
+    amount: u64,
+    account_info_iter: &mut I,
+) -> ProgramResult {
+    if from == to || amount == 0 {
+        return Ok(());
+    }
+    let slot = Clock::get()?.slot;
+    for (delegatee, increase) in [(from, false), (to, true)] {
+        let Some(delegatee) = delegatee else {
+            continue;
+        };
+        let votes_account = next_account_info(account_info_iter)?;
+        validators::assert_owned_by(votes_account, program_id)?;
+        let mut checkpoints: VoteCheckpoints = unpack_state(&votes_account.data.borrow())?;
+        if checkpoints.mint != *mint || checkpoints.delegatee != delegatee {
+            msg!("Vote checkpoint account does not belong to delegatee {}", delegatee);
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let current = checkpoints.current_votes();
+        let votes = if increase {
+            current.checked_add(amount).ok_or(TokenError::Overflow)?
+        } else {
+            current.checked_sub(amount).ok_or(TokenError::Overflow)?
+        };
+        checkpoints.record(slot, votes);
+        pack_state(&checkpoints, &mut votes_account.data.borrow_mut())?;
+    }
+    Ok(())
+}
+
+/// NFT 转账白名单检查：读取白名单 PDA（未设置时不限制），
+/// 再通过指令 sysvar 找到当前顶层指令的程序，即发起 Transfer 的调用方
+fn check_transfer_allowed<'a, 'b: 'a, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    account_info_iter: &mut I,
+) -> ProgramResult {
+    let allowlist_account = next_account_info(account_info_iter)?;
+    let instructions_sysvar = next_account_info(account_info_iter)?;
+
+    let (allowlist_key, _) =
+        Pubkey::find_program_address(&[TRANSFER_ALLOWLIST_SEED, mint.as_ref()], program_id);
+    if allowlist_key != *allowlist_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if allowlist_account.data_is_empty() {
+        return Ok(());
+    }
+    validators::assert_owned_by(allowlist_account, program_id)?;
+    let allowlist: TransferAllowlist = unpack_state(&allowlist_account.data.borrow())?;
+
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let caller = load_instruction_at_checked(current_index as usize, instructions_sysvar)?.program_id;
+    if !allowlist.programs.contains(&caller) {
+        msg!("Program {} is not allowed to transfer mint {}", caller, mint);
+        return Err(TokenError::TransferProgramNotAllowed.into());
+    }
     Ok(())
 }
 
 // 修正序列化/反序列化方法
 impl Mint {
     pub fn serialize(&self, data: &mut [u8]) -> Result<(), ProgramError> {
-        borsh::to_writer(&mut data[..], self)
-            .map_err(|_| ProgramError::InvalidAccountData)
+        self.pack_into_slice(data)
     }
-    
-    // pub fn deserialize(data: &[u8]) -> Result<Self, ProgramError> {
-    //     // 现在这个应该能正常工作了
-    //     Self::try_from_slice(data).map_err(|_| ProgramError::InvalidAccountData)
-    // }
+
     pub fn deserialize(data: &[u8]) -> Result<Self, ProgramError> {
-        let slice_76 = &data[..76];
-        Self::try_from_slice(slice_76).map_err(|e| {
-            // 记录详细的调试信息
-            msg!("=== BORSH DESERIALIZATION ERROR ===");
-            solana_program::msg!("Error type: {:?}", e);
-            solana_program::msg!("Data length: {} bytes", data.len());
-            
-            // 打印前几个字节用于调试
-            if data.len() > 0 {
-                solana_program::msg!("First 10 bytes: {:?}", &data[..std::cmp::min(10, data.len())]);
-            } else {
-                solana_program::msg!("Data is empty!");
-            }
-            
-            // 检查预期的数据大小
-            let expected_size = std::mem::size_of::<Mint>();
-            solana_program::msg!("Expected Mint size: {} bytes", expected_size);
-            solana_program::msg!("Actual data size: {} bytes", data.len());
-            
-            // 返回更具体的错误
-            ProgramError::InvalidAccountData
+        // Option 字段为 None 时只占 1 字节（如 NFT 铸造后撤销了铸币权限），因此按顺序读取、忽略尾部空间
+        Self::unpack_from_slice(data).inspect_err(|_| {
+            msg!("Invalid mint data ({} bytes): {:?}", data.len(), &data[..data.len().min(10)]);
         })
-        }
-
+    }
 }
 
 impl TokenAccount {
+    /// 写回账户；余额与写回前不同且账户带 AccountActivity 扩展时同步更新 last_activity_slot
+    /// （所有修改余额的指令都经由这里写回，因此不需要在各处理函数中单独维护）
     pub fn serialize(&self, data: &mut [u8]) -> Result<(), ProgramError> {
-        borsh::to_writer(&mut data[..], self)
-            .map_err(|_| ProgramError::InvalidAccountData)
+        let balance_changed = Self::deserialize(data).is_ok_and(|previous| previous.amount != self.amount);
+        self.pack_into_slice(data)?;
+        if balance_changed {
+            if let Some(mut activity) = extension::get_extension::<TokenAccount, AccountActivity>(data)? {
+                activity.last_activity_slot = Clock::get()?.slot;
+                extension::init_extension::<TokenAccount, _>(data, &activity)?;
+            }
+        }
+        Ok(())
     }
     
     pub fn deserialize(data: &[u8]) -> Result<Self, ProgramError> {
-        // 现在这个应该能正常工作了
-        let slice_74 = &data[..74];
-        Self::try_from_slice(slice_74).map_err(|_| ProgramError::InvalidAccountData)
+        // vote_delegate 为 None 时只占 1 字节，因此按顺序读取、忽略尾部空间
+        let mut account = Self::unpack_from_slice(data)?;
+        // 定时解冻到期后按未冻结读取（链下没有 Clock 时保持冻结）
+        if account.is_frozen() {
+            let thaw_at_slot = extension::get_extension::<TokenAccount, ScheduledThaw>(data)?
+                .and_then(|scheduled| scheduled.thaw_at_slot);
+            if thaw_at_slot.is_some_and(|slot| Clock::get().is_ok_and(|clock| clock.slot >= slot)) {
+                account.set_frozen(false);
+            }
+        }
+        Ok(account)
     }
 }
\ No newline at end of file
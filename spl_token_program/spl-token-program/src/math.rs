@@ -0,0 +1,58 @@
+//! 金额换算：最小单位与带小数的 UI 金额之间的转换，程序与链下工具共用同一套规则。
+//! UI 金额去掉末尾多余的 0（`1_500_000`、6 位精度显示为 `"1.5"`）；
+//! 解析时小数位超过精度且不全为 0 会丢失精度，直接报错而不是静默截断。
+
+use solana_program::{msg, program_error::ProgramError};
+
+use crate::TokenError;
+
+/// 一个完整代币对应的最小单位数量（10^decimals）
+pub fn one_token(decimals: u8) -> Result<u128, ProgramError> {
+    10u128
+        .checked_pow(decimals as u32)
+        .ok_or_else(|| TokenError::Overflow.into())
+}
+
+/// 最小单位金额转为 UI 金额字符串，去掉小数部分末尾的 0
+pub fn to_ui_amount(amount: u64, decimals: u8) -> String {
+    let digits = amount.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+    let padded = format!("{:0>width$}", digits, width = decimals + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+/// UI 金额字符串转为最小单位：格式非法返回 InvalidArgument，
+/// 超出精度的非零小数位返回 InvalidArgument，超出 u64 返回 Overflow
+pub fn from_ui_amount(ui_amount: &str, decimals: u8) -> Result<u64, ProgramError> {
+    let (whole, fraction) = ui_amount.split_once('.').unwrap_or((ui_amount, ""));
+    let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+        msg!("Invalid UI amount: {:?}", ui_amount);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let decimals = decimals as usize;
+    let (fraction, excess) = fraction.split_at(fraction.len().min(decimals));
+    if excess.bytes().any(|byte| byte != b'0') {
+        msg!("UI amount {} has more than {} decimal places", ui_amount, decimals);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let amount = whole.bytes().chain(fraction.bytes()).try_fold(0u64, |amount, digit| {
+        amount.checked_mul(10)?.checked_add((digit - b'0') as u64)
+    });
+    let scale = |amount: u64| match amount {
+        0 => Some(0),
+        _ => amount.checked_mul(10u64.checked_pow((decimals - fraction.len()) as u32)?),
+    };
+    amount.and_then(scale).ok_or_else(|| TokenError::Overflow.into())
+}
@@ -0,0 +1,52 @@
+//! 零堆分配的指令解析（no-alloc 特性开启时由 process_instruction 使用）
+//!
+//! 转账、铸造、销毁等小指令用 pack::ByteReader 按 Borsh 布局手动读取定长字段，不经过 Borsh 反序列化；
+//! 其余指令（含版本化信封与预留标签）回退到 `TokenInstruction::unpack`。
+//! 与 `TokenInstruction::unpack` 一样，末尾多余的字节返回 InvalidInstructionData。
+
+use solana_program::{msg, program_error::ProgramError};
+
+use crate::{pack::ByteReader, TokenError, TokenInstruction};
+
+// Borsh 变体标签，与 TokenInstruction 声明顺序一致
+const INITIALIZE_ACCOUNT_TAG: u8 = 1;
+const MINT_TO_TAG: u8 = 2;
+const TRANSFER_TAG: u8 = 3;
+const BURN_TAG: u8 = 4;
+const THAW_ACCOUNT_TAG: u8 = 26;
+
+fn read_u64(input: &[u8]) -> Result<u64, ProgramError> {
+    let mut reader = ByteReader::new(input);
+    let amount = reader.read_u64().ok_or(TokenError::InvalidInstruction)?;
+    expect_empty(reader.remaining())?;
+    Ok(amount)
+}
+
+fn expect_empty(rest: &[u8]) -> Result<(), ProgramError> {
+    if !rest.is_empty() {
+        msg!("{} trailing bytes after the encoded instruction", rest.len());
+        return Err(TokenError::InvalidInstructionData.into());
+    }
+    Ok(())
+}
+
+/// 解析指令数据，小指令不产生堆分配
+pub fn unpack(input: &[u8]) -> Result<TokenInstruction, ProgramError> {
+    let (&tag, rest) = input.split_first().ok_or(TokenError::InvalidInstruction)?;
+    Ok(match tag {
+        INITIALIZE_ACCOUNT_TAG => {
+            expect_empty(rest)?;
+            TokenInstruction::InitializeAccount
+        }
+        MINT_TO_TAG => TokenInstruction::MintTo { amount: read_u64(rest)? },
+        TRANSFER_TAG => TokenInstruction::Transfer { amount: read_u64(rest)? },
+        BURN_TAG => TokenInstruction::Burn { amount: read_u64(rest)? },
+        THAW_ACCOUNT_TAG => {
+            let mut reader = ByteReader::new(rest);
+            let reason_code = reader.read_u16().ok_or(TokenError::InvalidInstruction)?;
+            expect_empty(reader.remaining())?;
+            TokenInstruction::ThawAccount { reason_code }
+        }
+        _ => TokenInstruction::unpack(input)?,
+    })
+}
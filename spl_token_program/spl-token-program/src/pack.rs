@@ -0,0 +1,197 @@
+//! 手写的小端序读写器：铸币与代币账户几乎每条指令都要反序列化与写回，热路径上用它代替 Borsh 派生代码，
+//! 省去 Borsh 的泛型 io 层与错误类型转换。
+//! 布局与 Borsh 完全一致（Option 为 1 字节标签 + 值，None 时后续字段前移），已有账户无需迁移；
+//! Borsh 派生只在测试中保留，用来校验两者编码一致。
+
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{AccountFlags, Mint, TokenAccount};
+
+/// 顺序读取字节，越界或取值非法时返回 None，由调用方映射为合适的错误
+pub struct ByteReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// 尚未读取的字节
+    pub fn remaining(&self) -> &'a [u8] {
+        self.data
+    }
+
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let bytes = self.data.get(..N)?.try_into().ok()?;
+        self.data = &self.data[N..];
+        Some(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> Option<u8> {
+        self.take::<1>().map(|[byte]| byte)
+    }
+
+    pub fn read_bool(&mut self) -> Option<bool> {
+        match self.read_u8()? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    pub fn read_u16(&mut self) -> Option<u16> {
+        self.take::<2>().map(u16::from_le_bytes)
+    }
+
+    pub fn read_u64(&mut self) -> Option<u64> {
+        self.take::<8>().map(u64::from_le_bytes)
+    }
+
+    pub fn read_pubkey(&mut self) -> Option<Pubkey> {
+        self.take::<32>().map(Pubkey::new_from_array)
+    }
+
+    pub fn read_option_pubkey(&mut self) -> Option<Option<Pubkey>> {
+        match self.read_u8()? {
+            0 => Some(None),
+            1 => self.read_pubkey().map(Some),
+            _ => None,
+        }
+    }
+}
+
+/// 顺序写入字节，空间不足时返回 None
+pub struct ByteWriter<'a> {
+    data: &'a mut [u8],
+    position: usize,
+}
+
+impl<'a> ByteWriter<'a> {
+    pub fn new(data: &'a mut [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    /// 已写入的字节数
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// 缓冲区是否已写满
+    pub fn is_full(&self) -> bool {
+        self.position == self.data.len()
+    }
+
+    fn put(&mut self, bytes: &[u8]) -> Option<()> {
+        let end = self.position.checked_add(bytes.len())?;
+        self.data.get_mut(self.position..end)?.copy_from_slice(bytes);
+        self.position = end;
+        Some(())
+    }
+
+    pub fn write_u8(&mut self, value: u8) -> Option<()> {
+        self.put(&[value])
+    }
+
+    pub fn write_bool(&mut self, value: bool) -> Option<()> {
+        self.write_u8(value as u8)
+    }
+
+    pub fn write_u64(&mut self, value: u64) -> Option<()> {
+        self.put(&value.to_le_bytes())
+    }
+
+    pub fn write_pubkey(&mut self, value: &Pubkey) -> Option<()> {
+        self.put(value.as_ref())
+    }
+
+    pub fn write_option_pubkey(&mut self, value: &Option<Pubkey>) -> Option<()> {
+        match value {
+            Some(key) => {
+                self.write_u8(1)?;
+                self.write_pubkey(key)
+            }
+            None => self.write_u8(0),
+        }
+    }
+}
+
+/// 按固定字段顺序手动编解码的账户状态
+pub trait ManualPack: Sized {
+    /// 基础状态的最大编码长度
+    const PACKED_LEN: usize;
+
+    fn read(reader: &mut ByteReader) -> Option<Self>;
+
+    fn write(&self, writer: &mut ByteWriter) -> Option<()>;
+
+    /// 从账户数据开头解析，忽略尾部空间（扩展区或 Option 为 None 时空出的字节）
+    fn unpack_from_slice(data: &[u8]) -> Result<Self, ProgramError> {
+        Self::read(&mut ByteReader::new(data)).ok_or(ProgramError::InvalidAccountData)
+    }
+
+    /// 写入账户数据开头，其余字节保持不变
+    fn pack_into_slice(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        self.write(&mut ByteWriter::new(data)).ok_or(ProgramError::InvalidAccountData)
+    }
+
+    /// 编码为恰好包含全部字段的字节（不补齐到 PACKED_LEN）
+    fn pack_to_vec(&self) -> Result<Vec<u8>, ProgramError> {
+        let mut data = vec![0u8; Self::PACKED_LEN];
+        let mut writer = ByteWriter::new(&mut data);
+        self.write(&mut writer).ok_or(ProgramError::InvalidAccountData)?;
+        let len = writer.position();
+        data.truncate(len);
+        Ok(data)
+    }
+}
+
+impl ManualPack for Mint {
+    const PACKED_LEN: usize = Mint::LEN;
+
+    fn read(reader: &mut ByteReader) -> Option<Self> {
+        Some(Self {
+            is_initialized: reader.read_bool()?,
+            decimals: reader.read_u8()?,
+            mint_authority: reader.read_option_pubkey()?,
+            supply: reader.read_u64()?,
+            freeze_authority: reader.read_option_pubkey()?,
+            // 见 Mint::LEN：早期铸币账户可能没有这个字节
+            is_nft: if reader.remaining().is_empty() { false } else { reader.read_bool()? },
+        })
+    }
+
+    fn write(&self, writer: &mut ByteWriter) -> Option<()> {
+        writer.write_bool(self.is_initialized)?;
+        writer.write_u8(self.decimals)?;
+        writer.write_option_pubkey(&self.mint_authority)?;
+        writer.write_u64(self.supply)?;
+        writer.write_option_pubkey(&self.freeze_authority)?;
+        if writer.is_full() && !self.is_nft {
+            return Some(());
+        }
+        writer.write_bool(self.is_nft)
+    }
+}
+
+impl ManualPack for TokenAccount {
+    const PACKED_LEN: usize = TokenAccount::LEN;
+
+    fn read(reader: &mut ByteReader) -> Option<Self> {
+        Some(Self {
+            flags: AccountFlags(reader.read_u8()?),
+            mint: reader.read_pubkey()?,
+            owner: reader.read_pubkey()?,
+            amount: reader.read_u64()?,
+            vote_delegate: reader.read_option_pubkey()?,
+        })
+    }
+
+    fn write(&self, writer: &mut ByteWriter) -> Option<()> {
+        writer.write_u8(self.flags.0)?;
+        writer.write_pubkey(&self.mint)?;
+        writer.write_pubkey(&self.owner)?;
+        writer.write_u64(self.amount)?;
+        writer.write_option_pubkey(&self.vote_delegate)
+    }
+}
@@ -0,0 +1,893 @@
+//! 指令解析：输出与 solana-transaction-status 解析指令相同的 JSON 结构
+//! `{ "type": "transfer", "info": { "source": ..., "amount": "100" } }`，供区块浏览器展示
+//!
+//! 与 spl-token 的解析器保持一致：代币数量为字符串，lamports / slot 为数字，
+//! 账户名使用 camelCase，可选的尾部账户（投票检查点等）不展开。
+
+use serde_json::{json, Map, Value};
+use solana_program::pubkey::Pubkey;
+use thiserror::Error;
+
+use crate::{AccountLabel, AuthorityType, CrankItem, CurveType, OwnerCurve, TokenInstruction};
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseInstructionError {
+    #[error("instruction data could not be parsed")]
+    InstructionNotParsable,
+    #[error("instruction requires {0} accounts")]
+    InstructionKeyMismatch(usize),
+}
+
+/// 把按顺序命名的账户放入 info，账户不足时报错
+fn named_accounts(account_keys: &[Pubkey], names: &[&str]) -> Result<Map<String, Value>, ParseInstructionError> {
+    if account_keys.len() < names.len() {
+        return Err(ParseInstructionError::InstructionKeyMismatch(names.len()));
+    }
+    Ok(names
+        .iter()
+        .zip(account_keys)
+        .map(|(name, key)| (name.to_string(), json!(key.to_string())))
+        .collect())
+}
+
+fn optional_pubkey(key: &Option<Pubkey>) -> Value {
+    key.map_or(Value::Null, |key| json!(key.to_string()))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn authority_type_name(authority_type: AuthorityType) -> &'static str {
+    match authority_type {
+        AuthorityType::MintTokens => "mintTokens",
+        AuthorityType::FreezeAccount => "freezeAccount",
+        AuthorityType::BurnTokens => "burnTokens",
+    }
+}
+
+fn crank_item_name(item: CrankItem) -> &'static str {
+    match item {
+        CrankItem::ExpiredSession => "expiredSession",
+        CrankItem::ExpiredHtlc => "expiredHtlc",
+        CrankItem::MaturedThaw => "maturedThaw",
+    }
+}
+
+fn owner_curve_name(owner_curve: OwnerCurve) -> &'static str {
+    match owner_curve {
+        OwnerCurve::OnCurve => "onCurve",
+        OwnerCurve::OffCurve => "offCurve",
+    }
+}
+
+/// 解析本程序的指令数据
+pub fn parse_token_instruction(data: &[u8], account_keys: &[Pubkey]) -> Result<Value, ParseInstructionError> {
+    // 与 process_instruction 使用同一种编码
+    #[cfg(feature = "legacy-codec")]
+    let instruction = crate::legacy::unpack(data).map_err(|_| ParseInstructionError::InstructionNotParsable)?;
+    #[cfg(not(feature = "legacy-codec"))]
+    let instruction = TokenInstruction::unpack(data).map_err(|_| ParseInstructionError::InstructionNotParsable)?;
+
+    let (instruction_type, info) = match instruction {
+        TokenInstruction::InitializeMint { decimals, mint_authority, freeze_authority } => {
+            let mut info = named_accounts(account_keys, &["mint", "rentSysvar"])?;
+            info.insert("decimals".into(), json!(decimals));
+            info.insert("mintAuthority".into(), json!(mint_authority.to_string()));
+            if freeze_authority.is_some() {
+                info.insert("freezeAuthority".into(), optional_pubkey(&freeze_authority));
+            }
+            ("initializeMint", info)
+        }
+        TokenInstruction::InitializeAccount => (
+            "initializeAccount",
+            named_accounts(account_keys, &["account", "mint", "owner", "rentSysvar"])?,
+        ),
+        TokenInstruction::MintTo { amount } => {
+            let mut info = named_accounts(account_keys, &["mint", "account", "mintAuthority"])?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("mintTo", info)
+        }
+        TokenInstruction::Transfer { amount } => {
+            let mut info = named_accounts(account_keys, &["source", "destination", "authority"])?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("transfer", info)
+        }
+        TokenInstruction::Burn { amount } => {
+            let mut info = named_accounts(account_keys, &["account", "mint", "authority"])?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("burn", info)
+        }
+        TokenInstruction::SetMintAuthority { new_authority } => {
+            let mut info = named_accounts(account_keys, &["mint", "authority"])?;
+            info.insert("newAuthority".into(), optional_pubkey(&new_authority));
+            ("setMintAuthority", info)
+        }
+        TokenInstruction::InitializeCurve { curve } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["mint", "curve", "vault", "mintAuthority", "payer", "systemProgram"],
+            )?;
+            let curve = match curve {
+                CurveType::Linear { base_price, slope } => {
+                    json!({ "kind": "linear", "basePrice": base_price, "slope": slope })
+                }
+                CurveType::Exponential { base_price, growth_bps } => {
+                    json!({ "kind": "exponential", "basePrice": base_price, "growthBps": growth_bps })
+                }
+            };
+            info.insert("curveType".into(), curve);
+            ("initializeCurve", info)
+        }
+        TokenInstruction::BuyFromCurve { amount, max_lamports } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["mint", "curve", "vault", "account", "buyer", "systemProgram"],
+            )?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            info.insert("maxLamports".into(), json!(max_lamports));
+            ("buyFromCurve", info)
+        }
+        TokenInstruction::SellToCurve { amount, min_lamports } => {
+            let mut info = named_accounts(account_keys, &["mint", "curve", "vault", "account", "owner"])?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            info.insert("minLamports".into(), json!(min_lamports));
+            ("sellToCurve", info)
+        }
+        TokenInstruction::ConfigureOracle { price_feed, max_staleness_slots, collateral_ratio_bps } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["mint", "oracleConfig", "authority", "payer", "systemProgram"],
+            )?;
+            info.insert("priceFeed".into(), optional_pubkey(&price_feed));
+            info.insert("maxStalenessSlots".into(), json!(max_staleness_slots));
+            info.insert("collateralRatioBps".into(), json!(collateral_ratio_bps));
+            ("configureOracle", info)
+        }
+        TokenInstruction::MintAgainstCollateral { collateral_lamports, min_amount } => {
+            let mut info = named_accounts(
+                account_keys,
+                &[
+                    "mint",
+                    "oracleConfig",
+                    "priceAccount",
+                    "collateralVault",
+                    "account",
+                    "depositor",
+                    "systemProgram",
+                ],
+            )?;
+            info.insert("collateralLamports".into(), json!(collateral_lamports));
+            info.insert("minAmount".into(), json!(min_amount.to_string()));
+            ("mintAgainstCollateral", info)
+        }
+        TokenInstruction::EnableRebase => (
+            "enableRebase",
+            named_accounts(
+                account_keys,
+                &["mint", "rebaseConfig", "mintAuthority", "payer", "systemProgram"],
+            )?,
+        ),
+        TokenInstruction::Rebase { numerator, denominator } => {
+            let mut info = named_accounts(account_keys, &["mint", "rebaseConfig", "rebaseAuthority"])?;
+            info.insert("numerator".into(), json!(numerator));
+            info.insert("denominator".into(), json!(denominator));
+            ("rebase", info)
+        }
+        TokenInstruction::FundDistribution { distribution_id, snapshot_root, snapshot_supply, amount } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["mint", "distribution", "rewardVault", "source", "funder", "systemProgram"],
+            )?;
+            info.insert("distributionId".into(), json!(distribution_id));
+            info.insert("snapshotRoot".into(), json!(hex(&snapshot_root)));
+            info.insert("snapshotSupply".into(), json!(snapshot_supply.to_string()));
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("fundDistribution", info)
+        }
+        TokenInstruction::ClaimDividend { snapshot_balance, proof } => {
+            let mut info = named_accounts(
+                account_keys,
+                &[
+                    "mint",
+                    "distribution",
+                    "rewardVault",
+                    "holderAccount",
+                    "destination",
+                    "claimRecord",
+                    "owner",
+                    "systemProgram",
+                ],
+            )?;
+            info.insert("snapshotBalance".into(), json!(snapshot_balance.to_string()));
+            info.insert("proof".into(), json!(proof.iter().map(|node| hex(node)).collect::<Vec<_>>()));
+            ("claimDividend", info)
+        }
+        TokenInstruction::DelegateVotes { delegatee } => {
+            let mut info = named_accounts(account_keys, &["account", "owner", "systemProgram"])?;
+            info.insert("delegatee".into(), optional_pubkey(&delegatee));
+            ("delegateVotes", info)
+        }
+        TokenInstruction::GetVotesAt { slot } => {
+            let mut info = named_accounts(account_keys, &["voteCheckpoints"])?;
+            info.insert("slot".into(), json!(slot));
+            ("getVotesAt", info)
+        }
+        TokenInstruction::TopUpRent { lamports } => {
+            let mut info = named_accounts(account_keys, &["account", "payer", "systemProgram"])?;
+            info.insert("lamports".into(), json!(lamports));
+            ("topUpRent", info)
+        }
+        TokenInstruction::MintToNewAccount { amount } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["mint", "account", "wallet", "mintAuthority", "payer", "systemProgram"],
+            )?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("mintToNewAccount", info)
+        }
+        TokenInstruction::BurnWithReceipt { amount, receipt_id } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["account", "mint", "authority", "burnReceipt", "systemProgram"],
+            )?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            info.insert("receiptId".into(), json!(receipt_id));
+            ("burnWithReceipt", info)
+        }
+        TokenInstruction::InitializeNftMint { mint_authority, freeze_authority } => {
+            let mut info = named_accounts(account_keys, &["mint", "rentSysvar"])?;
+            info.insert("mintAuthority".into(), json!(mint_authority.to_string()));
+            if freeze_authority.is_some() {
+                info.insert("freezeAuthority".into(), optional_pubkey(&freeze_authority));
+            }
+            ("initializeNftMint", info)
+        }
+        TokenInstruction::CreateMetadata { args, is_mutable } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["mint", "metadata", "mintAuthority", "payer", "systemProgram", "metadataProgram"],
+            )?;
+            info.insert("name".into(), json!(args.name));
+            info.insert("symbol".into(), json!(args.symbol));
+            info.insert("uri".into(), json!(args.uri));
+            info.insert("sellerFeeBasisPoints".into(), json!(args.seller_fee_basis_points));
+            info.insert("isMutable".into(), json!(is_mutable));
+            ("createMetadata", info)
+        }
+        TokenInstruction::CreateMasterEdition { max_supply } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["mint", "masterEdition", "account", "owner", "systemProgram"],
+            )?;
+            info.insert("maxSupply".into(), json!(max_supply));
+            ("createMasterEdition", info)
+        }
+        TokenInstruction::PrintEdition => (
+            "printEdition",
+            named_accounts(
+                account_keys,
+                &[
+                    "masterMint",
+                    "masterEdition",
+                    "masterAccount",
+                    "owner",
+                    "editionMint",
+                    "edition",
+                    "account",
+                    "systemProgram",
+                ],
+            )?,
+        ),
+        TokenInstruction::SetTransferAllowlist { programs } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["mint", "transferAllowlist", "authority", "payer", "systemProgram"],
+            )?;
+            info.insert(
+                "programs".into(),
+                json!(programs.iter().map(|program| program.to_string()).collect::<Vec<_>>()),
+            );
+            ("setTransferAllowlist", info)
+        }
+        TokenInstruction::SetVelocityLimit { max_amount, window_slots } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["account", "mint", "velocityTracker", "freezeAuthority", "payer", "systemProgram"],
+            )?;
+            info.insert("maxAmount".into(), json!(max_amount.to_string()));
+            info.insert("windowSlots".into(), json!(window_slots));
+            ("setVelocityLimit", info)
+        }
+        TokenInstruction::ThawAccount { reason_code } => {
+            let mut info = named_accounts(account_keys, &["account", "mint", "freezeAuthority"])?;
+            info.insert("reasonCode".into(), json!(reason_code));
+            ("thawAccount", info)
+        }
+        TokenInstruction::QueueMint { amount, destination } => {
+            let mut info = named_accounts(account_keys, &["mint", "queuedMint", "mintAuthority", "systemProgram"])?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            info.insert("destination".into(), json!(destination.to_string()));
+            ("queueMint", info)
+        }
+        TokenInstruction::ExecuteQueuedMint => (
+            "executeQueuedMint",
+            named_accounts(account_keys, &["mint", "queuedMint", "account", "mintAuthority"])?,
+        ),
+        TokenInstruction::CancelQueuedMint => (
+            "cancelQueuedMint",
+            named_accounts(account_keys, &["mint", "queuedMint", "mintAuthority"])?,
+        ),
+        TokenInstruction::InitializeTreasury => (
+            "initializeTreasury",
+            named_accounts(account_keys, &["mint", "treasury", "payer", "systemProgram"])?,
+        ),
+        TokenInstruction::BurnTreasury => (
+            "burnTreasury",
+            named_accounts(account_keys, &["mint", "treasury"])?,
+        ),
+        TokenInstruction::PlaceOrder { give_amount, want_mint, want_amount } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["order", "escrow", "source", "makerReceive", "maker", "systemProgram"],
+            )?;
+            info.insert("giveAmount".into(), json!(give_amount.to_string()));
+            info.insert("wantMint".into(), json!(want_mint.to_string()));
+            info.insert("wantAmount".into(), json!(want_amount.to_string()));
+            ("placeOrder", info)
+        }
+        TokenInstruction::FillOrder => (
+            "fillOrder",
+            named_accounts(
+                account_keys,
+                &["order", "escrow", "makerReceive", "takerSource", "takerReceive", "taker", "maker"],
+            )?,
+        ),
+        TokenInstruction::CancelOrder => (
+            "cancelOrder",
+            named_accounts(account_keys, &["order", "escrow", "refundAccount", "maker"])?,
+        ),
+        TokenInstruction::CreateAuction { start_price, floor_price, decay, supply } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["mint", "auction", "vault", "mintAuthority", "payer", "systemProgram"],
+            )?;
+            info.insert("startPrice".into(), json!(start_price));
+            info.insert("floorPrice".into(), json!(floor_price));
+            info.insert("decay".into(), json!(decay));
+            info.insert("supply".into(), json!(supply.to_string()));
+            ("createAuction", info)
+        }
+        TokenInstruction::Purchase { amount, max_lamports } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["mint", "auction", "vault", "account", "buyer", "systemProgram"],
+            )?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            info.insert("maxLamports".into(), json!(max_lamports));
+            ("purchase", info)
+        }
+        TokenInstruction::InitializeSale { price_lamports_per_token, cap } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["mint", "sale", "vault", "mintAuthority", "payer", "systemProgram"],
+            )?;
+            info.insert("priceLamportsPerToken".into(), json!(price_lamports_per_token));
+            info.insert("cap".into(), json!(cap.to_string()));
+            ("initializeSale", info)
+        }
+        TokenInstruction::BuyTokens { amount } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["mint", "sale", "vault", "account", "buyer", "systemProgram"],
+            )?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("buyTokens", info)
+        }
+        TokenInstruction::WithdrawProceeds => (
+            "withdrawProceeds",
+            named_accounts(account_keys, &["mint", "sale", "vault", "authority"])?,
+        ),
+        TokenInstruction::InitializeShareVault => (
+            "initializeShareVault",
+            named_accounts(
+                account_keys,
+                &["shareMint", "shareVault", "assetVault", "mintAuthority", "payer", "systemProgram"],
+            )?,
+        ),
+        TokenInstruction::Deposit { amount } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["shareVault", "shareMint", "assetVault", "source", "shareAccount", "owner"],
+            )?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("deposit", info)
+        }
+        TokenInstruction::Withdraw { shares } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["shareVault", "shareMint", "assetVault", "shareAccount", "destination", "owner"],
+            )?;
+            info.insert("shares".into(), json!(shares.to_string()));
+            ("withdraw", info)
+        }
+        TokenInstruction::DepositGoverningTokens { realm, amount } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["mint", "tokenOwnerRecord", "holding", "source", "owner", "systemProgram"],
+            )?;
+            info.insert("realm".into(), json!(realm.to_string()));
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("depositGoverningTokens", info)
+        }
+        TokenInstruction::WithdrawGoverningTokens { realm, amount } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["mint", "tokenOwnerRecord", "holding", "destination", "owner"],
+            )?;
+            info.insert("realm".into(), json!(realm.to_string()));
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("withdrawGoverningTokens", info)
+        }
+        TokenInstruction::InitializeEmergencyConfig { emergency_admin } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["emergencyConfig", "programData", "upgradeAuthority", "systemProgram"],
+            )?;
+            info.insert("emergencyAdmin".into(), json!(emergency_admin.to_string()));
+            ("initializeEmergencyConfig", info)
+        }
+        TokenInstruction::ProposeAuthorityRecovery { authority_type, new_authority } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["emergencyConfig", "mint", "authorityRecovery", "emergencyAdmin", "systemProgram"],
+            )?;
+            info.insert("authorityType".into(), json!(authority_type_name(authority_type)));
+            info.insert("newAuthority".into(), json!(new_authority.to_string()));
+            ("proposeAuthorityRecovery", info)
+        }
+        TokenInstruction::ExecuteAuthorityRecovery => (
+            "executeAuthorityRecovery",
+            named_accounts(account_keys, &["mint", "authorityRecovery", "emergencyAdmin"])?,
+        ),
+        TokenInstruction::CancelAuthorityRecovery => (
+            "cancelAuthorityRecovery",
+            named_accounts(account_keys, &["mint", "authorityRecovery", "signer", "emergencyAdmin"])?,
+        ),
+        TokenInstruction::EnableAutoClose { close_destination } => {
+            let mut info = named_accounts(account_keys, &["mint", "mintAuthority"])?;
+            info.insert("closeDestination".into(), json!(close_destination.to_string()));
+            ("enableAutoClose", info)
+        }
+        TokenInstruction::CloseEmptyMint => (
+            "closeEmptyMint",
+            named_accounts(account_keys, &["mint", "closeDestination"])?,
+        ),
+        TokenInstruction::TransferSplit { total, shares } => {
+            let mut info = named_accounts(account_keys, &["source", "owner"])?;
+            let destinations: Vec<String> = account_keys
+                .iter()
+                .skip(2)
+                .take(shares.len())
+                .map(|key| key.to_string())
+                .collect();
+            info.insert("destinations".into(), json!(destinations));
+            info.insert("total".into(), json!(total.to_string()));
+            info.insert("shares".into(), json!(shares));
+            ("transferSplit", info)
+        }
+        TokenInstruction::CreateHtlc { hash, amount, timeout } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["htlc", "escrow", "source", "recipient", "sender", "systemProgram"],
+            )?;
+            info.insert("hash".into(), json!(hex(&hash)));
+            info.insert("amount".into(), json!(amount.to_string()));
+            info.insert("timeout".into(), json!(timeout));
+            ("createHtlc", info)
+        }
+        TokenInstruction::RedeemHtlc { preimage } => {
+            let mut info = named_accounts(account_keys, &["htlc", "escrow", "recipient", "sender"])?;
+            info.insert("preimage".into(), json!(hex(&preimage)));
+            ("redeemHtlc", info)
+        }
+        TokenInstruction::RefundHtlc => (
+            "refundHtlc",
+            named_accounts(account_keys, &["htlc", "escrow", "refund", "sender"])?,
+        ),
+        TokenInstruction::AtomicSwap { amount_a, amount_b } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["sourceA", "destinationA", "sourceB", "destinationB", "ownerA", "ownerB"],
+            )?;
+            info.insert("amountA".into(), json!(amount_a.to_string()));
+            info.insert("amountB".into(), json!(amount_b.to_string()));
+            ("atomicSwap", info)
+        }
+        TokenInstruction::PermitApprove { delegate, amount, nonce, deadline } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["account", "allowance", "payer", "instructionsSysvar", "systemProgram"],
+            )?;
+            info.insert("delegate".into(), json!(delegate.to_string()));
+            info.insert("amount".into(), json!(amount.to_string()));
+            info.insert("nonce".into(), json!(nonce.to_string()));
+            info.insert("deadline".into(), json!(deadline));
+            ("permitApprove", info)
+        }
+        TokenInstruction::TransferFrom { amount } => {
+            let mut info = named_accounts(account_keys, &["source", "destination", "allowance", "delegate"])?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("transferFrom", info)
+        }
+        TokenInstruction::TransferWithPermit { amount, tip, nonce, deadline } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["source", "destination", "tipAccount", "permitNonce", "relayer", "instructionsSysvar", "systemProgram"],
+            )?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            info.insert("tip".into(), json!(tip.to_string()));
+            info.insert("nonce".into(), json!(nonce.to_string()));
+            info.insert("deadline".into(), json!(deadline));
+            ("transferWithPermit", info)
+        }
+        TokenInstruction::CreateSession { key, max_amount, expires_at_slot } => {
+            let mut info = named_accounts(account_keys, &["account", "session", "owner", "systemProgram"])?;
+            info.insert("sessionKey".into(), json!(key.to_string()));
+            info.insert("maxAmount".into(), json!(max_amount.to_string()));
+            info.insert("expiresAtSlot".into(), json!(expires_at_slot));
+            ("createSession", info)
+        }
+        TokenInstruction::RevokeSession => (
+            "revokeSession",
+            named_accounts(account_keys, &["account", "session", "owner"])?,
+        ),
+        TokenInstruction::SessionTransfer { amount } => {
+            let mut info = named_accounts(account_keys, &["source", "destination", "session", "sessionKey"])?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("sessionTransfer", info)
+        }
+        TokenInstruction::SetGuardians { guardians, threshold } => {
+            let mut info = named_accounts(account_keys, &["account", "owner"])?;
+            let guardians: Vec<String> = guardians.iter().map(|key| key.to_string()).collect();
+            info.insert("guardians".into(), json!(guardians));
+            info.insert("threshold".into(), json!(threshold));
+            ("setGuardians", info)
+        }
+        TokenInstruction::ProposeOwnerRecovery { new_owner } => {
+            let mut info = named_accounts(account_keys, &["account", "ownerRecovery", "payer", "systemProgram"])?;
+            let guardians: Vec<String> = account_keys.iter().skip(4).map(|key| key.to_string()).collect();
+            info.insert("guardians".into(), json!(guardians));
+            info.insert("newOwner".into(), json!(new_owner.to_string()));
+            ("proposeOwnerRecovery", info)
+        }
+        TokenInstruction::CancelOwnerRecovery => (
+            "cancelOwnerRecovery",
+            named_accounts(account_keys, &["account", "ownerRecovery", "owner", "payer"])?,
+        ),
+        TokenInstruction::RecoverOwner => (
+            "recoverOwner",
+            named_accounts(account_keys, &["account", "ownerRecovery", "payer"])?,
+        ),
+        TokenInstruction::EnableHolderCount => (
+            "enableHolderCount",
+            named_accounts(account_keys, &["mint", "mintAuthority"])?,
+        ),
+        TokenInstruction::EnableActivityTracking => (
+            "enableActivityTracking",
+            named_accounts(account_keys, &["account", "owner"])?,
+        ),
+        TokenInstruction::EnableMemoLog => (
+            "enableMemoLog",
+            named_accounts(account_keys, &["account", "owner"])?,
+        ),
+        TokenInstruction::TransferWithMemo { amount, memo } => {
+            let mut info = named_accounts(account_keys, &["source", "destination", "authority"])?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            info.insert("memo".into(), json!(String::from_utf8_lossy(&memo)));
+            ("transferWithMemo", info)
+        }
+        TokenInstruction::SweepAll { source_count } => {
+            let mut info = named_accounts(account_keys, &["destination", "authority"])?;
+            let sources: Vec<String> = account_keys
+                .iter()
+                .skip(2)
+                .take(source_count as usize)
+                .map(|key| key.to_string())
+                .collect();
+            info.insert("sources".into(), json!(sources));
+            ("sweepAll", info)
+        }
+        TokenInstruction::SettleBatch { deltas } => {
+            let accounts: Vec<String> = account_keys
+                .iter()
+                .take(deltas.len())
+                .map(|key| key.to_string())
+                .collect();
+            let deltas: Vec<String> = deltas.iter().map(|delta| delta.to_string()).collect();
+            let mut info = Map::new();
+            info.insert("accounts".into(), json!(accounts));
+            info.insert("deltas".into(), json!(deltas));
+            ("settleBatch", info)
+        }
+        TokenInstruction::InitializeCompressedTree { depth } => {
+            let mut info = named_accounts(account_keys, &["tree", "mint", "escrow"])?;
+            info.insert("depth".into(), json!(depth));
+            ("initializeCompressedTree", info)
+        }
+        TokenInstruction::Compress { recipient, amount } => {
+            let mut info = named_accounts(account_keys, &["source", "owner", "tree", "escrow"])?;
+            info.insert("recipient".into(), json!(recipient.to_string()));
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("compress", info)
+        }
+        TokenInstruction::CompressedTransfer { leaf, root, recipient, amount, .. } => {
+            let mut info = named_accounts(account_keys, &["tree", "owner"])?;
+            info.insert("leafIndex".into(), json!(leaf.index.to_string()));
+            info.insert("leafAmount".into(), json!(leaf.amount.to_string()));
+            info.insert("root".into(), json!(hex(&root)));
+            info.insert("recipient".into(), json!(recipient.to_string()));
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("compressedTransfer", info)
+        }
+        TokenInstruction::Decompress { leaf, root, .. } => {
+            let mut info = named_accounts(account_keys, &["tree", "escrow", "destination", "owner"])?;
+            info.insert("leafIndex".into(), json!(leaf.index.to_string()));
+            info.insert("amount".into(), json!(leaf.amount.to_string()));
+            info.insert("root".into(), json!(hex(&root)));
+            ("decompress", info)
+        }
+        TokenInstruction::ConfigureBridge { verifier } => {
+            let mut info = named_accounts(account_keys, &["mint", "bridge", "mintAuthority"])?;
+            info.insert("verifier".into(), json!(verifier.to_string()));
+            ("configureBridge", info)
+        }
+        TokenInstruction::BridgeBurn { target_chain, target_address, amount } => {
+            let mut info = named_accounts(account_keys, &["account", "mint", "authority", "bridge"])?;
+            info.insert("targetChain".into(), json!(target_chain));
+            info.insert("targetAddress".into(), json!(hex(&target_address)));
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("bridgeBurn", info)
+        }
+        TokenInstruction::BridgeMint { source_chain, emitter, sequence, amount } => {
+            let mut info = named_accounts(account_keys, &["bridge", "mint", "account", "claim", "verifier"])?;
+            info.insert("sourceChain".into(), json!(source_chain));
+            info.insert("emitter".into(), json!(hex(&emitter)));
+            info.insert("sequence".into(), json!(sequence.to_string()));
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("bridgeMint", info)
+        }
+        TokenInstruction::RescaleMint { new_decimals } => {
+            let mut info = named_accounts(account_keys, &["mint", "mintAuthority"])?;
+            info.insert("newDecimals".into(), json!(new_decimals));
+            ("rescaleMint", info)
+        }
+        TokenInstruction::GlobalFreeze => (
+            "globalFreeze",
+            named_accounts(account_keys, &["mint", "freezeAuthority"])?,
+        ),
+        TokenInstruction::GlobalThaw => (
+            "globalThaw",
+            named_accounts(account_keys, &["mint", "freezeAuthority"])?,
+        ),
+        TokenInstruction::FreezeAccount { thaw_at_slot, reason_code } => {
+            let mut info = named_accounts(account_keys, &["account", "mint", "freezeAuthority"])?;
+            info.insert("thawAtSlot".into(), json!(thaw_at_slot.map(|slot| slot.to_string())));
+            info.insert("reasonCode".into(), json!(reason_code));
+            ("freezeAccount", info)
+        }
+        TokenInstruction::SetAuthority { authority_type, new_authority } => {
+            let mut info = named_accounts(account_keys, &["mint", "authority"])?;
+            info.insert("authorityType".into(), json!(authority_type_name(authority_type)));
+            info.insert("newAuthority".into(), optional_pubkey(&new_authority));
+            ("setAuthority", info)
+        }
+        TokenInstruction::SetFreezeExempt { exempt } => {
+            let mut info = named_accounts(account_keys, &["account", "mint", "freezeAuthority"])?;
+            info.insert("exempt".into(), json!(exempt));
+            ("setFreezeExempt", info)
+        }
+        TokenInstruction::CreateConditionalTransfer { arbiter, amount } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["conditionalTransfer", "escrow", "source", "recipient", "sender", "systemProgram"],
+            )?;
+            info.insert("arbiter".into(), json!(arbiter.to_string()));
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("createConditionalTransfer", info)
+        }
+        TokenInstruction::ReleaseConditionalTransfer => (
+            "releaseConditionalTransfer",
+            named_accounts(account_keys, &["conditionalTransfer", "escrow", "recipient", "sender", "arbiter"])?,
+        ),
+        TokenInstruction::ApproveMulti { approvals } => {
+            let mut info = named_accounts(account_keys, &["owner", "systemProgram"])?;
+            let approvals: Vec<Value> = approvals
+                .iter()
+                .zip(account_keys.iter().skip(2).step_by(2))
+                .map(|((delegate, amount), account)| {
+                    json!({
+                        "account": account.to_string(),
+                        "delegate": delegate.to_string(),
+                        "amount": amount.to_string(),
+                    })
+                })
+                .collect();
+            info.insert("approvals".into(), json!(approvals));
+            ("approveMulti", info)
+        }
+        TokenInstruction::CreateMintWithSeed { seed, decimals, freeze_authority } => {
+            let mut info = named_accounts(account_keys, &["mint", "mintAuthority", "systemProgram"])?;
+            info.insert("seed".into(), json!(seed));
+            info.insert("decimals".into(), json!(decimals));
+            info.insert("freezeAuthority".into(), optional_pubkey(&freeze_authority));
+            ("createMintWithSeed", info)
+        }
+        TokenInstruction::SetAccountLabel { label } => {
+            let mut info = named_accounts(account_keys, &["account", "owner"])?;
+            let label = AccountLabel { label };
+            info.insert("label".into(), label.as_str().map_or_else(|| json!(hex(&label.label)), |text| json!(text)));
+            ("setAccountLabel", info)
+        }
+        TokenInstruction::GetExtensionTypes { is_mint } => {
+            let mut info = named_accounts(account_keys, &["account"])?;
+            info.insert("isMint".into(), json!(is_mint));
+            ("getExtensionTypes", info)
+        }
+        TokenInstruction::InitializeAccountWithOwnerCheck { owner_curve } => {
+            let mut info = named_accounts(account_keys, &["account", "mint", "owner", "rentSysvar"])?;
+            info.insert("ownerCurve".into(), json!(owner_curve_name(owner_curve)));
+            ("initializeAccountWithOwnerCheck", info)
+        }
+        TokenInstruction::Crank { items } => {
+            let mut info = named_accounts(account_keys, &["cranker"])?;
+            let items: Vec<&str> = items.iter().map(|item| crank_item_name(*item)).collect();
+            info.insert("items".into(), json!(items));
+            ("crank", info)
+        }
+        TokenInstruction::CreatePreAuthorization { merchant, amount_per_cycle, cycle_slots } => {
+            let mut info =
+                named_accounts(account_keys, &["account", "preAuthorization", "owner", "systemProgram"])?;
+            info.insert("merchant".into(), json!(merchant.to_string()));
+            info.insert("amountPerCycle".into(), json!(amount_per_cycle.to_string()));
+            info.insert("cycleSlots".into(), json!(cycle_slots));
+            ("createPreAuthorization", info)
+        }
+        TokenInstruction::Debit { amount } => {
+            let mut info = named_accounts(account_keys, &["source", "destination", "preAuthorization", "merchant"])?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("debit", info)
+        }
+        TokenInstruction::ClosePreAuthorization => (
+            "closePreAuthorization",
+            named_accounts(account_keys, &["account", "preAuthorization", "owner"])?,
+        ),
+        TokenInstruction::RefundConditionalTransfer => (
+            "refundConditionalTransfer",
+            named_accounts(account_keys, &["conditionalTransfer", "escrow", "refund", "sender", "arbiter"])?,
+        ),
+        TokenInstruction::InitializeSavingsVault { yield_authority } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["mint", "savingsVault", "vault", "mintAuthority", "payer", "systemProgram"],
+            )?;
+            info.insert("yieldAuthority".into(), json!(yield_authority.to_string()));
+            ("initializeSavingsVault", info)
+        }
+        TokenInstruction::FundYield { amount } => {
+            let mut info = named_accounts(account_keys, &["savingsVault", "vault", "source", "yieldAuthority"])?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("fundYield", info)
+        }
+        TokenInstruction::DepositSavings { amount } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["savingsVault", "vault", "source", "savingsPosition", "owner", "systemProgram"],
+            )?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("depositSavings", info)
+        }
+        TokenInstruction::WithdrawSavings { shares } => {
+            let mut info =
+                named_accounts(account_keys, &["savingsVault", "vault", "savingsPosition", "destination", "owner"])?;
+            info.insert("shares".into(), json!(shares.to_string()));
+            ("withdrawSavings", info)
+        }
+        TokenInstruction::InitializeEmissions { tokens_per_slot, start_slot, end_slot, recipients } => {
+            let mut info =
+                named_accounts(account_keys, &["mint", "emissions", "mintAuthority", "payer", "systemProgram"])?;
+            let recipients: Vec<_> = recipients
+                .iter()
+                .map(|recipient| json!({ "vault": recipient.vault.to_string(), "weight": recipient.weight }))
+                .collect();
+            info.insert("tokensPerSlot".into(), json!(tokens_per_slot.to_string()));
+            info.insert("startSlot".into(), json!(start_slot));
+            info.insert("endSlot".into(), json!(end_slot));
+            info.insert("recipients".into(), json!(recipients));
+            ("initializeEmissions", info)
+        }
+        TokenInstruction::DistributeEmissions => {
+            let mut info = named_accounts(account_keys, &["mint", "emissions"])?;
+            let recipients: Vec<String> = account_keys.iter().skip(2).map(|key| key.to_string()).collect();
+            info.insert("recipients".into(), json!(recipients));
+            ("distributeEmissions", info)
+        }
+        TokenInstruction::EnablePointsExpiry { lifetime_slots } => {
+            let mut info = named_accounts(account_keys, &["mint", "mintAuthority"])?;
+            info.insert("lifetimeSlots".into(), json!(lifetime_slots));
+            ("enablePointsExpiry", info)
+        }
+        TokenInstruction::ExpirePoints => {
+            let mut info = named_accounts(account_keys, &["mint"])?;
+            let accounts: Vec<String> = account_keys.iter().skip(1).map(|key| key.to_string()).collect();
+            info.insert("accounts".into(), json!(accounts));
+            ("expirePoints", info)
+        }
+        TokenInstruction::SetDormancyPolicy { recovery_vault, dormant_epochs, notice_epochs } => {
+            let mut info = named_accounts(account_keys, &["mint", "authority"])?;
+            info.insert("recoveryVault".into(), json!(recovery_vault.to_string()));
+            info.insert("dormantEpochs".into(), json!(dormant_epochs));
+            info.insert("noticeEpochs".into(), json!(notice_epochs));
+            ("setDormancyPolicy", info)
+        }
+        TokenInstruction::WarnDormant => {
+            let info = named_accounts(account_keys, &["mint", "account", "notice", "authority", "systemProgram"])?;
+            ("warnDormant", info)
+        }
+        TokenInstruction::SweepDormant => {
+            let info = named_accounts(account_keys, &["mint", "account", "recoveryVault", "notice", "authority"])?;
+            ("sweepDormant", info)
+        }
+        TokenInstruction::UpdateAttestation { reserves } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["mint", "attestation", "mintAuthority", "attester", "systemProgram"],
+            )?;
+            info.insert("reserves".into(), json!(reserves.to_string()));
+            ("updateAttestation", info)
+        }
+        TokenInstruction::VerifySupplyBacked => {
+            let info = named_accounts(account_keys, &["mint", "attestation"])?;
+            ("verifySupplyBacked", info)
+        }
+        TokenInstruction::InitializeConversion { rate } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["oldMint", "newMint", "conversion", "vault", "mintAuthority", "payer", "systemProgram"],
+            )?;
+            info.insert("rate".into(), json!(rate));
+            ("initializeConversion", info)
+        }
+        TokenInstruction::ConvertMint { amount } => {
+            let mut info = named_accounts(
+                account_keys,
+                &["oldMint", "newMint", "source", "destination", "conversion", "vault", "owner"],
+            )?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("convertMint", info)
+        }
+        TokenInstruction::Rebalance { amount } => {
+            let mut info = named_accounts(account_keys, &["source", "destination", "owner"])?;
+            info.insert("amount".into(), json!(amount.to_string()));
+            ("rebalance", info)
+        }
+        TokenInstruction::SetPauseFlags { pause_transfers, pause_minting, pause_burning } => {
+            let mut info = named_accounts(account_keys, &["mint", "freezeAuthority"])?;
+            info.insert("pauseTransfers".into(), json!(pause_transfers));
+            info.insert("pauseMinting".into(), json!(pause_minting));
+            info.insert("pauseBurning".into(), json!(pause_burning));
+            ("setPauseFlags", info)
+        }
+        TokenInstruction::SetActivationRequired { required } => {
+            let mut info = named_accounts(account_keys, &["mint", "mintAuthority"])?;
+            info.insert("required".into(), json!(required));
+            ("setActivationRequired", info)
+        }
+        TokenInstruction::ActivateAccount => (
+            "activateAccount",
+            named_accounts(account_keys, &["account", "owner"])?,
+        ),
+    };
+
+    Ok(json!({ "type": instruction_type, "info": info }))
+}
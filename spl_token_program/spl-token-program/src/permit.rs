@@ -0,0 +1,120 @@
+//! 链下签名授权（permit）：所有者用 ed25519 私钥对 permit 消息签名，由中继者提交交易。
+//! 交易中紧邻本程序指令之前必须有一条 ed25519 程序指令验证该签名（签名、公钥、消息都内嵌在那条指令里），
+//! 本程序通过指令 sysvar 读取它，确认验证的正是“所有者公钥 + 期望的 permit 消息”。
+
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::AccountInfo,
+    ed25519_program,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+use crate::TokenError;
+
+/// 消息前缀，避免与其他协议的签名消息混用
+pub const PERMIT_DOMAIN: &[u8] = b"spl-token-program:permit";
+
+// ed25519 程序指令数据布局：[签名数量 u8][填充 u8][每个签名 7 个 u16 偏移量]...
+// 偏移量依次为：签名偏移、签名所在指令、公钥偏移、公钥所在指令、消息偏移、消息长度、消息所在指令
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_INSTRUCTION_INDEX: usize = SIGNATURE_OFFSETS_START + 2;
+const PUBKEY_OFFSET: usize = SIGNATURE_OFFSETS_START + 4;
+const PUBKEY_INSTRUCTION_INDEX: usize = SIGNATURE_OFFSETS_START + 6;
+const MESSAGE_OFFSET: usize = SIGNATURE_OFFSETS_START + 8;
+const MESSAGE_SIZE: usize = SIGNATURE_OFFSETS_START + 10;
+const MESSAGE_INSTRUCTION_INDEX: usize = SIGNATURE_OFFSETS_START + 12;
+const PUBKEY_LEN: usize = 32;
+/// 偏移量中的指令索引为 u16::MAX 表示数据就在 ed25519 指令自身中
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// 所有者签名的 permit 内容（PERMIT_DOMAIN 后接 Borsh 编码）
+#[derive(BorshSerialize, Debug, Clone, PartialEq)]
+pub enum PermitMessage {
+    /// 设置 delegate 对 token_account 的额度为 amount
+    Approve {
+        program_id: Pubkey,
+        token_account: Pubkey,
+        delegate: Pubkey,
+        amount: u64,
+        nonce: u64,
+        deadline: i64,
+    },
+    /// 从 source 转出 amount，其中 tip 付给提交交易的中继者，其余转入 destination
+    Transfer {
+        program_id: Pubkey,
+        source: Pubkey,
+        destination: Pubkey,
+        amount: u64,
+        tip: u64,
+        nonce: u64,
+        deadline: i64,
+    },
+}
+
+impl PermitMessage {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ProgramError> {
+        let mut message = PERMIT_DOMAIN.to_vec();
+        self.serialize(&mut message)?;
+        Ok(message)
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, ProgramError> {
+    data.get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+/// 校验紧邻当前指令之前的 ed25519 程序指令验证了 signer 对 message 的签名
+pub fn verify_preceding_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> ProgramResult {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let verify_index = current_index.checked_sub(1).ok_or_else(|| {
+        msg!("Permit requires an ed25519 verify instruction before it");
+        TokenError::InvalidProof
+    })?;
+    let instruction = load_instruction_at_checked(verify_index as usize, instructions_sysvar)?;
+    if instruction.program_id != ed25519_program::id() {
+        msg!("Instruction {} is not an ed25519 verify instruction", verify_index);
+        return Err(TokenError::InvalidProof.into());
+    }
+
+    // 只接受单签名、数据全部内嵌的验证指令，避免签名 / 公钥 / 消息指向其他指令被替换
+    let data = &instruction.data;
+    if data.first() != Some(&1) {
+        msg!("ed25519 verify instruction must check exactly one signature");
+        return Err(TokenError::InvalidProof.into());
+    }
+    for index_offset in [SIGNATURE_INSTRUCTION_INDEX, PUBKEY_INSTRUCTION_INDEX, MESSAGE_INSTRUCTION_INDEX] {
+        if read_u16(data, index_offset)? != CURRENT_INSTRUCTION {
+            msg!("ed25519 verify instruction must embed its own data");
+            return Err(TokenError::InvalidProof.into());
+        }
+    }
+    let pubkey_offset = read_u16(data, PUBKEY_OFFSET)? as usize;
+    let message_offset = read_u16(data, MESSAGE_OFFSET)? as usize;
+    let message_len = read_u16(data, MESSAGE_SIZE)? as usize;
+
+    let signed_pubkey = data
+        .get(pubkey_offset..pubkey_offset + PUBKEY_LEN)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_len)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    if signed_pubkey != signer.as_ref() {
+        msg!("Permit was not signed by {}", signer);
+        return Err(TokenError::InvalidProof.into());
+    }
+    if signed_message != message {
+        msg!("Signed message does not match the permit");
+        return Err(TokenError::InvalidProof.into());
+    }
+    Ok(())
+}
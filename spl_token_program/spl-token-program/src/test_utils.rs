@@ -0,0 +1,172 @@
+//! 测试夹具构造器（test-utils 特性）：按链上布局打包铸币 / 代币账户字节，
+//! 单元测试与下游 crate 不必再手写字节数组，例如
+//! `MintFixture::new().decimals(6).supply(1_000).to_bytes()`。
+//! 扩展按添加顺序写入 TLV 区，编码与程序中的 extension::init_extension 相同。
+
+use solana_program::pubkey::Pubkey;
+
+use crate::extension::{self, BaseState, Extension};
+use crate::{Mint, TokenAccount};
+
+/// 已编码的 TLV 条目（不含基础状态）
+fn encode_extension<S: BaseState, E: Extension>(value: &E) -> Vec<u8> {
+    let mut data = vec![0u8; extension::account_len::<S, E>()];
+    extension::init_extension::<S, E>(&mut data, value).expect("extension fits its own account_len");
+    data.split_off(S::LEN)
+}
+
+/// 基础状态 + 扩展，不足 space 时在尾部补零
+fn pack(mut data: Vec<u8>, base_len: usize, extensions: &[u8], space: Option<usize>) -> Vec<u8> {
+    data.resize(base_len, 0);
+    data.extend_from_slice(extensions);
+    if let Some(space) = space {
+        assert!(space >= data.len(), "space {} is smaller than the packed state ({} bytes)", space, data.len());
+        data.resize(space, 0);
+    }
+    data
+}
+
+/// 铸币账户夹具，默认为已初始化、decimals 为 0、没有权限、供应量为 0
+#[derive(Debug, Clone)]
+pub struct MintFixture {
+    state: Mint,
+    extensions: Vec<u8>,
+    space: Option<usize>,
+}
+
+impl Default for MintFixture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MintFixture {
+    pub fn new() -> Self {
+        Self {
+            state: Mint {
+                is_initialized: true,
+                decimals: 0,
+                mint_authority: None,
+                supply: 0,
+                freeze_authority: None,
+                is_nft: false,
+            },
+            extensions: Vec::new(),
+            space: None,
+        }
+    }
+
+    pub fn decimals(mut self, decimals: u8) -> Self {
+        self.state.decimals = decimals;
+        self
+    }
+
+    pub fn supply(mut self, supply: u64) -> Self {
+        self.state.supply = supply;
+        self
+    }
+
+    pub fn mint_authority(mut self, authority: Pubkey) -> Self {
+        self.state.mint_authority = Some(authority);
+        self
+    }
+
+    pub fn freeze_authority(mut self, authority: Pubkey) -> Self {
+        self.state.freeze_authority = Some(authority);
+        self
+    }
+
+    pub fn nft(mut self) -> Self {
+        self.state.is_nft = true;
+        self
+    }
+
+    pub fn uninitialized(mut self) -> Self {
+        self.state.is_initialized = false;
+        self
+    }
+
+    pub fn extension<E: Extension>(mut self, value: &E) -> Self {
+        self.extensions.extend(encode_extension::<Mint, E>(value));
+        self
+    }
+
+    /// 账户总大小（为之后开启的扩展预留空间）
+    pub fn space(mut self, space: usize) -> Self {
+        self.space = Some(space);
+        self
+    }
+
+    pub fn state(&self) -> Mint {
+        self.state.clone()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let data = borsh::to_vec(&self.state).expect("mint serializes");
+        pack(data, Mint::LEN, &self.extensions, self.space)
+    }
+}
+
+/// 代币账户夹具，默认为已初始化、余额为 0、没有任何标志
+#[derive(Debug, Clone)]
+pub struct TokenAccountFixture {
+    state: TokenAccount,
+    extensions: Vec<u8>,
+    space: Option<usize>,
+}
+
+impl TokenAccountFixture {
+    pub fn new(mint: Pubkey, owner: Pubkey) -> Self {
+        Self { state: TokenAccount::new(mint, owner), extensions: Vec::new(), space: None }
+    }
+
+    pub fn amount(mut self, amount: u64) -> Self {
+        self.state.amount = amount;
+        self
+    }
+
+    pub fn frozen(mut self) -> Self {
+        self.state.set_frozen(true);
+        self
+    }
+
+    pub fn nft(mut self) -> Self {
+        self.state.set_nft(true);
+        self
+    }
+
+    pub fn velocity_limited(mut self) -> Self {
+        self.state.set_velocity_limited(true);
+        self
+    }
+
+    pub fn holder_tracked(mut self) -> Self {
+        self.state.set_holder_tracked(true);
+        self
+    }
+
+    pub fn vote_delegate(mut self, delegate: Pubkey) -> Self {
+        self.state.vote_delegate = Some(delegate);
+        self
+    }
+
+    pub fn extension<E: Extension>(mut self, value: &E) -> Self {
+        self.extensions.extend(encode_extension::<TokenAccount, E>(value));
+        self
+    }
+
+    /// 账户总大小（为之后开启的扩展预留空间）
+    pub fn space(mut self, space: usize) -> Self {
+        self.space = Some(space);
+        self
+    }
+
+    pub fn state(&self) -> TokenAccount {
+        self.state.clone()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let data = borsh::to_vec(&self.state).expect("token account serializes");
+        pack(data, TokenAccount::LEN, &self.extensions, self.space)
+    }
+}
@@ -0,0 +1,89 @@
+//! 账户校验辅助函数：处理函数统一通过这里检查账户所有者、签名、初始化状态、铸币匹配与租金豁免，
+//! 失败时记录出错的账户，返回的错误码与各处理函数原先手写的检查一致。
+
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::IsInitialized,
+    pubkey::Pubkey,
+    rent::Rent,
+};
+
+use crate::{OwnerCurve, TokenError};
+
+/// 账户必须归 owner（通常为本程序）所有
+pub fn assert_owned_by(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+    if account.owner != owner {
+        msg!("Account {} is owned by {}, expected {}", account.key, account.owner, owner);
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+/// 账户必须签名
+pub fn assert_signer(account: &AccountInfo) -> ProgramResult {
+    if !account.is_signer {
+        msg!("Account {} must sign", account.key);
+        return Err(TokenError::Unauthorized.into());
+    }
+    Ok(())
+}
+
+/// 状态必须已初始化
+pub fn assert_initialized<T: IsInitialized>(state: &T) -> ProgramResult {
+    if !state.is_initialized() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(())
+}
+
+/// 账户（或记录）中的铸币必须与期望的铸币一致
+pub fn assert_mint_matches(mint: &Pubkey, expected: &Pubkey) -> ProgramResult {
+    if mint != expected {
+        msg!("Mint {} does not match expected mint {}", mint, expected);
+        return Err(TokenError::MintMismatch.into());
+    }
+    Ok(())
+}
+
+/// 账户余额必须达到租金豁免
+pub fn assert_rent_exempt(rent: &Rent, account: &AccountInfo) -> ProgramResult {
+    if !rent.is_exempt(account.lamports(), account.data_len()) {
+        msg!("Account {} is not rent exempt", account.key);
+        return Err(TokenError::NotRentExempt.into());
+    }
+    Ok(())
+}
+
+/// 地址是否为 ed25519 曲线上的点；链上 Pubkey::is_on_curve 不可用，改用 curve25519 校验系统调用
+fn is_on_curve(key: &Pubkey) -> bool {
+    #[cfg(target_os = "solana")]
+    {
+        const CURVE25519_EDWARDS: u64 = 0;
+        let mut result = 0u8;
+        // SAFETY: 系统调用只读取 key 的 32 字节，result 仅为占位输出
+        let status = unsafe {
+            solana_program::syscalls::sol_curve_validate_point(CURVE25519_EDWARDS, key.as_ref().as_ptr(), &mut result)
+        };
+        status == 0
+    }
+    #[cfg(not(target_os = "solana"))]
+    key.is_on_curve()
+}
+
+/// 所有者地址必须满足 owner_curve：钱包在曲线上，PDA 不在曲线上
+pub fn assert_owner_curve(owner: &Pubkey, owner_curve: OwnerCurve) -> ProgramResult {
+    match (owner_curve, is_on_curve(owner)) {
+        (OwnerCurve::OnCurve, false) => {
+            msg!("Owner {} is off-curve (a PDA), expected a wallet", owner);
+            Err(TokenError::OwnerOffCurve.into())
+        }
+        (OwnerCurve::OffCurve, true) => {
+            msg!("Owner {} is on-curve (a wallet), expected a PDA", owner);
+            Err(TokenError::OwnerOnCurve.into())
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,27 @@
+//! 链上产物体积回归测试：`cargo build-sbf` 之后运行 `cargo test --test binary_size`，
+//! .so 超过 MAX_PROGRAM_SIZE 时失败，提醒检查新增的依赖、日志字符串或应放到 debug-tools 之后的代码。
+//! 产物目录取 SBF_OUT_DIR（默认 target/deploy）；没有产物（未安装 SBF 工具链）时跳过。
+
+use std::path::PathBuf;
+
+/// 程序体积上限；确有需要增大时同步调整部署账户的预留空间
+const MAX_PROGRAM_SIZE: u64 = 640 * 1024;
+
+#[test]
+fn program_binary_stays_under_size_budget() {
+    let out_dir = std::env::var_os("SBF_OUT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target/deploy"));
+    let path = out_dir.join("spl_token_program.so");
+    let Ok(metadata) = std::fs::metadata(&path) else {
+        eprintln!("skipping: {} not found, run cargo build-sbf first", path.display());
+        return;
+    };
+    assert!(
+        metadata.len() <= MAX_PROGRAM_SIZE,
+        "{} is {} bytes, budget is {} bytes",
+        path.display(),
+        metadata.len(),
+        MAX_PROGRAM_SIZE
+    );
+}
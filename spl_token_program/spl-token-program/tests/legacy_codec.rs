@@ -0,0 +1,78 @@
+//! 经典线格式编解码测试：legacy::pack / legacy::unpack 往返一致，定长指令的多余字节被拒绝。
+//! 只调用编解码函数，不依赖 legacy-codec 特性（特性只决定 process_instruction 使用哪种编码）。
+
+use borsh::BorshSerialize;
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use spl_token_program::{legacy, AuthorityType, TokenError, TokenInstruction};
+
+fn classic_instructions() -> Vec<TokenInstruction> {
+    vec![
+        TokenInstruction::InitializeMint {
+            decimals: 6,
+            mint_authority: Pubkey::new_unique(),
+            freeze_authority: Some(Pubkey::new_unique()),
+        },
+        TokenInstruction::InitializeMint { decimals: 0, mint_authority: Pubkey::new_unique(), freeze_authority: None },
+        TokenInstruction::InitializeAccount,
+        TokenInstruction::Transfer { amount: u64::MAX },
+        TokenInstruction::SetAuthority {
+            authority_type: AuthorityType::MintTokens,
+            new_authority: Some(Pubkey::new_unique()),
+        },
+        TokenInstruction::SetAuthority { authority_type: AuthorityType::MintTokens, new_authority: None },
+        TokenInstruction::MintTo { amount: 42 },
+        TokenInstruction::Burn { amount: 7 },
+        TokenInstruction::ThawAccount { reason_code: 3 },
+    ]
+}
+
+/// TokenInstruction 没有实现 PartialEq，按 Borsh 编码比较
+fn assert_same(left: &TokenInstruction, right: &TokenInstruction) {
+    assert_eq!(left.try_to_vec().unwrap(), right.try_to_vec().unwrap(), "{:?} != {:?}", left, right);
+}
+
+#[test]
+fn classic_and_extended_instructions_round_trip() {
+    let extended = [
+        TokenInstruction::SetMintAuthority { new_authority: Some(Pubkey::new_unique()) },
+        TokenInstruction::FreezeAccount { thaw_at_slot: Some(100), reason_code: 1 },
+        TokenInstruction::SetAuthority { authority_type: AuthorityType::BurnTokens, new_authority: None },
+    ];
+    for instruction in classic_instructions().iter().chain(&extended) {
+        let data = legacy::pack(instruction).unwrap();
+        assert_same(&legacy::unpack(&data).unwrap(), instruction);
+    }
+}
+
+#[test]
+fn classic_instructions_use_spl_token_layout() {
+    let mut transfer = vec![legacy::TRANSFER_TAG];
+    transfer.extend_from_slice(&500u64.to_le_bytes());
+    assert_eq!(legacy::pack(&TokenInstruction::Transfer { amount: 500 }).unwrap(), transfer);
+
+    let revoke = TokenInstruction::SetAuthority { authority_type: AuthorityType::MintTokens, new_authority: None };
+    assert_eq!(legacy::pack(&revoke).unwrap(), [legacy::SET_AUTHORITY_TAG, 0, 0]);
+    // 经典程序的 FreezeAccount 权限类型（1）不在本程序支持范围内
+    assert_eq!(
+        legacy::unpack(&[legacy::SET_AUTHORITY_TAG, 1, 0]).unwrap_err(),
+        TokenError::InvalidInstruction.into()
+    );
+    assert_eq!(legacy::pack(&TokenInstruction::BuyTokens { amount: 1 }).unwrap()[0], legacy::EXTENDED_TAG);
+}
+
+#[test]
+fn classic_instructions_reject_trailing_bytes() {
+    for instruction in classic_instructions() {
+        let mut data = legacy::pack(&instruction).unwrap();
+        data.push(0);
+        let error: ProgramError = TokenError::InvalidInstructionData.into();
+        assert_eq!(legacy::unpack(&data).unwrap_err(), error, "{:?}", instruction);
+    }
+}
+
+#[test]
+fn classic_instructions_reject_short_data() {
+    assert_eq!(legacy::unpack(&[legacy::MINT_TO_TAG, 1, 0, 0]).unwrap_err(), TokenError::InvalidInstruction.into());
+    assert_eq!(legacy::unpack(&[legacy::THAW_ACCOUNT_TAG, 1]).unwrap_err(), TokenError::InvalidInstruction.into());
+    assert_eq!(legacy::unpack(&[]).unwrap_err(), TokenError::InvalidInstruction.into());
+}
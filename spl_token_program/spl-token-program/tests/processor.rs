@@ -0,0 +1,1834 @@
+//! 处理函数的进程内单元测试：用构造好的 AccountInfo 直接调用 process_instruction，
+//! 断言执行后的账户字节，不启动验证器，整套测试在毫秒级完成。
+//! Clock / Rent / EpochSchedule 通过 syscall stub 提供，测试中固定为 TEST_SLOT、默认租金与 test_epoch_schedule。
+//! 指令按 Borsh 编码，legacy-codec 特性下线格式不同，因此不编译本文件。
+#![cfg(not(feature = "legacy-codec"))]
+
+use std::sync::Once;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    epoch_schedule::EpochSchedule,
+    entrypoint::ProgramResult,
+    hash::hashv,
+    program_error::ProgramError,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::{self, Sysvar},
+};
+use spl_token_program::{
+    extension, find_allowance_address, find_attestation_address, find_conversion_address, find_dormancy_notice_address,
+    find_savings_position_address, math,
+    pack::ManualPack, process_instruction,
+    test_utils::{MintFixture, TokenAccountFixture},
+    AccountActivation, AccountActivity, AccountLabel, Allowance, Attestation, AuthorityType, ConditionalTransfer,
+    Conversion, CrankItem, DormancyNotice, DormancyPolicy, EmissionRecipient, EmissionsConfig, GlobalFreezeState,
+    HolderCount, MemoLog, MetadataArgs, Mint, MintCloseConfig, OwnerCurve, PauseState, PointsConfig, PointsLot,
+    PointsLots, PreAuthorization, RescaleEpoch, SavingsPosition, SavingsVault, ScheduledThaw, Session, TokenAccount,
+    TokenError, TokenInstruction, VelocityTracker, CRANK_REWARD_LAMPORTS, DISTRIBUTION_SEED,
+    FREEZE_REASON_VELOCITY_LIMIT,
+    MAX_METADATA_NAME_LEN, MAX_METADATA_SYMBOL_LEN, MAX_METADATA_URI_LEN, MAX_TRANSFER_REFERENCES,
+    MINT_WITH_ACTIVATION_POLICY_LEN, MINT_WITH_BURN_AUTHORITY_LEN, MINT_WITH_DECIMALS_RESCALE_LEN,
+    MINT_WITH_GLOBAL_FREEZE_LEN, MINT_WITH_PAUSE_STATE_LEN, TOKEN_ACCOUNT_WITH_ACTIVATION_LEN,
+    TOKEN_ACCOUNT_WITH_FREEZE_EXEMPT_LEN, TOKEN_ACCOUNT_WITH_LABEL_LEN, TOKEN_ACCOUNT_WITH_MEMO_LOG_LEN,
+    TOKEN_ACCOUNT_WITH_RESCALE_EPOCH_LEN, TOKEN_ACCOUNT_WITH_SCHEDULED_THAW_LEN,
+};
+
+const PROGRAM_ID: Pubkey = Pubkey::new_from_array([7; 32]);
+const TEST_SLOT: u64 = 42;
+const TEST_EPOCH_SLOTS: u64 = 32;
+
+struct TestSyscallStubs;
+
+impl SyscallStubs for TestSyscallStubs {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock { slot: TEST_SLOT, ..Clock::default() };
+        unsafe { *(var_addr as *mut Clock) = clock };
+        solana_program::entrypoint::SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        solana_program::entrypoint::SUCCESS
+    }
+
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut EpochSchedule) = test_epoch_schedule() };
+        solana_program::entrypoint::SUCCESS
+    }
+}
+
+/// 每个 epoch TEST_EPOCH_SLOTS 个 slot，不设预热期：TEST_SLOT 位于 epoch 1
+fn test_epoch_schedule() -> EpochSchedule {
+    EpochSchedule::custom(TEST_EPOCH_SLOTS, TEST_EPOCH_SLOTS, false)
+}
+
+fn install_stubs() {
+    static STUBS: Once = Once::new();
+    STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(TestSyscallStubs));
+    });
+}
+
+/// 测试账户：process 时转换为 AccountInfo，执行后可直接检查 data
+struct Fixture {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+impl Fixture {
+    fn program_owned(len: usize) -> Self {
+        Self {
+            key: Pubkey::new_unique(),
+            owner: PROGRAM_ID,
+            lamports: Rent::default().minimum_balance(len),
+            data: vec![0; len],
+            is_signer: false,
+            is_writable: true,
+        }
+    }
+
+    fn mint(mint: MintFixture) -> Self {
+        Self::with_data(mint.to_bytes())
+    }
+
+    fn token(account: TokenAccountFixture) -> Self {
+        Self::with_data(account.to_bytes())
+    }
+
+    fn with_data(data: Vec<u8>) -> Self {
+        Self { lamports: Rent::default().minimum_balance(data.len()), data, ..Self::program_owned(0) }
+    }
+
+    fn signer(key: Pubkey) -> Self {
+        Self {
+            key,
+            owner: solana_program::system_program::id(),
+            lamports: 1_000_000_000,
+            data: vec![],
+            is_signer: true,
+            is_writable: false,
+        }
+    }
+
+    fn rent_sysvar() -> Self {
+        let mut fixture = Self {
+            key: sysvar::rent::id(),
+            owner: sysvar::id(),
+            lamports: 1,
+            data: vec![0; Rent::size_of()],
+            is_signer: false,
+            is_writable: false,
+        };
+        let Fixture { key, owner, lamports, data, .. } = &mut fixture;
+        Rent::default().to_account_info(&mut AccountInfo::new(key, false, false, lamports, data, owner, false, 0));
+        fixture
+    }
+
+    fn not_signer(mut self) -> Self {
+        self.is_signer = false;
+        self
+    }
+
+    fn read_only(mut self) -> Self {
+        self.is_writable = false;
+        self
+    }
+
+    fn mint_state(&self) -> Mint {
+        Mint::deserialize(&self.data).unwrap()
+    }
+
+    fn token_state(&self) -> TokenAccount {
+        TokenAccount::deserialize(&self.data).unwrap()
+    }
+}
+
+fn process(fixtures: &mut [&mut Fixture], instruction: TokenInstruction) -> ProgramResult {
+    process_data(fixtures, &instruction.try_to_vec().unwrap())
+}
+
+/// 以原始指令数据调用，用于构造畸形编码
+fn process_data(fixtures: &mut [&mut Fixture], instruction_data: &[u8]) -> ProgramResult {
+    install_stubs();
+    let accounts: Vec<AccountInfo> = fixtures
+        .iter_mut()
+        .map(|fixture| {
+            let Fixture { key, owner, lamports, data, is_signer, is_writable } = &mut **fixture;
+            AccountInfo::new(key, *is_signer, *is_writable, lamports, data, owner, false, 0)
+        })
+        .collect();
+    process_instruction(&PROGRAM_ID, &accounts, instruction_data)
+}
+
+#[test]
+fn initialize_mint_writes_state() {
+    let authority = Pubkey::new_unique();
+    let mut mint = Fixture::program_owned(Mint::LEN);
+    process(
+        &mut [&mut mint, &mut Fixture::rent_sysvar()],
+        TokenInstruction::InitializeMint { decimals: 9, mint_authority: authority, freeze_authority: None },
+    )
+    .unwrap();
+
+    let state = mint.mint_state();
+    assert!(state.is_initialized);
+    assert_eq!(state.decimals, 9);
+    assert_eq!(state.mint_authority, Some(authority));
+    assert_eq!(state.supply, 0);
+    assert_eq!(&mint.data[..3], &[1, 9, 1]);
+}
+
+#[test]
+fn initialize_mint_rejects_account_below_rent_exemption() {
+    let mut mint = Fixture::program_owned(Mint::LEN);
+    mint.lamports = 0;
+    let result = process(
+        &mut [&mut mint, &mut Fixture::rent_sysvar()],
+        TokenInstruction::InitializeMint { decimals: 9, mint_authority: Pubkey::new_unique(), freeze_authority: None },
+    );
+    assert_eq!(result, Err(TokenError::NotRentExempt.into()));
+}
+
+#[test]
+fn initialize_mint_rejects_account_owned_by_another_program() {
+    let mut mint = Fixture::program_owned(Mint::LEN);
+    mint.owner = Pubkey::new_unique();
+    let result = process(
+        &mut [&mut mint, &mut Fixture::rent_sysvar()],
+        TokenInstruction::InitializeMint { decimals: 9, mint_authority: Pubkey::new_unique(), freeze_authority: None },
+    );
+    assert_eq!(result, Err(ProgramError::IncorrectProgramId));
+}
+
+#[test]
+fn initialize_mint_rejects_initialized_mint() {
+    let authority = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().decimals(6).mint_authority(authority).supply(100));
+    let result = process(
+        &mut [&mut mint, &mut Fixture::rent_sysvar()],
+        TokenInstruction::InitializeMint { decimals: 0, mint_authority: Pubkey::new_unique(), freeze_authority: None },
+    );
+
+    assert_eq!(result, Err(ProgramError::AccountAlreadyInitialized));
+    assert_eq!(mint.mint_state().mint_authority, Some(authority));
+    assert_eq!(mint.mint_state().supply, 100);
+}
+
+#[test]
+fn initialize_account_records_mint_and_owner() {
+    let owner = Pubkey::new_unique();
+    let mut account = Fixture::program_owned(TokenAccount::LEN);
+    let mut mint = Fixture::mint(MintFixture::new().decimals(6).mint_authority(Pubkey::new_unique()).supply(0)).read_only();
+    process(
+        &mut [&mut account, &mut mint, &mut Fixture::signer(owner).not_signer(), &mut Fixture::rent_sysvar()],
+        TokenInstruction::InitializeAccount,
+    )
+    .unwrap();
+
+    let state = account.token_state();
+    assert!(state.is_initialized());
+    assert_eq!(state.mint, mint.key);
+    assert_eq!(state.owner, owner);
+    assert_eq!(state.amount, 0);
+}
+
+#[test]
+fn initialize_account_rejects_initialized_account_and_foreign_mint() {
+    let owner = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().decimals(6)).read_only();
+    let mut account = Fixture::token(TokenAccountFixture::new(mint.key, owner).amount(100));
+    let mut attacker = Fixture::signer(Pubkey::new_unique()).not_signer();
+    let result = process(
+        &mut [&mut account, &mut mint, &mut attacker, &mut Fixture::rent_sysvar()],
+        TokenInstruction::InitializeAccount,
+    );
+    assert_eq!(result, Err(ProgramError::AccountAlreadyInitialized));
+    assert_eq!(account.token_state().owner, owner);
+    assert_eq!(account.token_state().amount, 100);
+
+    let mut fresh = Fixture::program_owned(TokenAccount::LEN);
+    mint.owner = Pubkey::new_unique();
+    let result = process(
+        &mut [&mut fresh, &mut mint, &mut attacker, &mut Fixture::rent_sysvar()],
+        TokenInstruction::InitializeAccount,
+    );
+    assert_eq!(result, Err(ProgramError::IncorrectProgramId));
+    let mut uninitialized_mint = Fixture::mint(MintFixture::new().uninitialized());
+    let result = process(
+        &mut [&mut fresh, &mut uninitialized_mint, &mut attacker, &mut Fixture::rent_sysvar()],
+        TokenInstruction::InitializeAccount,
+    );
+    assert_eq!(result, Err(ProgramError::UninitializedAccount));
+}
+
+#[test]
+fn initialize_account_rejects_account_owned_by_another_program() {
+    let mut account = Fixture::program_owned(TokenAccount::LEN);
+    account.owner = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().decimals(6)).read_only();
+    let mut owner = Fixture::signer(Pubkey::new_unique()).not_signer();
+    let result = process(
+        &mut [&mut account, &mut mint, &mut owner, &mut Fixture::rent_sysvar()],
+        TokenInstruction::InitializeAccount,
+    );
+    assert_eq!(result, Err(ProgramError::IncorrectProgramId));
+}
+
+#[test]
+fn initialize_account_with_owner_check_rejects_wrong_owner_kind() {
+    // ed25519 基点，必然在曲线上
+    let mut base_point = [0x66u8; 32];
+    base_point[0] = 0x58;
+    let wallet = Pubkey::new_from_array(base_point);
+    let (pda, _) = Pubkey::find_program_address(&[b"vault"], &spl_token_program::ID);
+    let mint_fixture = MintFixture::new().decimals(6);
+    let initialize = |owner: Pubkey, owner_curve: OwnerCurve| {
+        let mut account = Fixture::program_owned(TokenAccount::LEN);
+        let mut mint = Fixture::mint(mint_fixture.clone()).read_only();
+        process(
+            &mut [&mut account, &mut mint, &mut Fixture::signer(owner).not_signer(), &mut Fixture::rent_sysvar()],
+            TokenInstruction::InitializeAccountWithOwnerCheck { owner_curve },
+        )
+    };
+
+    assert_eq!(initialize(pda, OwnerCurve::OnCurve), Err(TokenError::OwnerOffCurve.into()));
+    assert_eq!(initialize(wallet, OwnerCurve::OffCurve), Err(TokenError::OwnerOnCurve.into()));
+    initialize(wallet, OwnerCurve::OnCurve).unwrap();
+    initialize(pda, OwnerCurve::OffCurve).unwrap();
+}
+
+#[test]
+fn mint_to_credits_account_and_supply() {
+    let authority = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().decimals(6).mint_authority(authority).supply(100));
+    let mut account = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()).amount(100));
+    process(&mut [&mut mint, &mut account, &mut Fixture::signer(authority)], TokenInstruction::MintTo { amount: 50 })
+        .unwrap();
+
+    assert_eq!(mint.mint_state().supply, 150);
+    assert_eq!(account.token_state().amount, 150);
+}
+
+#[test]
+fn mint_created_before_nft_flag_still_loads_and_saves() {
+    let authority = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().mint_authority(authority).freeze_authority(authority));
+    // 两个权限都存在时 is_nft 恰好落在被截掉的最后一个字节上
+    mint.data.truncate(Mint::LEN - 1);
+    let mut account = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()));
+    process(
+        &mut [&mut mint, &mut account, &mut Fixture::signer(authority)],
+        TokenInstruction::MintTo { amount: 5 },
+    )
+    .unwrap();
+
+    assert_eq!(mint.data.len(), Mint::LEN - 1);
+    assert_eq!(mint.mint_state().supply, 5);
+    assert!(!mint.mint_state().is_nft);
+}
+
+#[test]
+fn mint_to_rejects_account_of_another_mint() {
+    let attacker = Pubkey::new_unique();
+    let mut attacker_mint = Fixture::mint(MintFixture::new().mint_authority(attacker));
+    let victim_mint = Pubkey::new_unique();
+    let mut victim_account = Fixture::token(TokenAccountFixture::new(victim_mint, attacker));
+    let result = process(
+        &mut [&mut attacker_mint, &mut victim_account, &mut Fixture::signer(attacker)],
+        TokenInstruction::MintTo { amount: 1_000_000 },
+    );
+
+    assert_eq!(result, Err(TokenError::MintMismatch.into()));
+    assert_eq!(victim_account.token_state().amount, 0);
+}
+
+#[test]
+fn mint_to_requires_authority_signature() {
+    let authority = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().decimals(6).mint_authority(authority).supply(0));
+    let mut account = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()).amount(0));
+    let result = process(
+        &mut [&mut mint, &mut account, &mut Fixture::signer(authority).not_signer()],
+        TokenInstruction::MintTo { amount: 50 },
+    );
+
+    assert_eq!(result, Err(TokenError::Unauthorized.into()));
+    assert_eq!(mint.mint_state().supply, 0);
+}
+
+#[test]
+fn mint_to_rejects_short_and_trailing_instruction_data() {
+    let authority = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().mint_authority(authority));
+    let mut account = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()));
+    let mut data = TokenInstruction::MintTo { amount: 1 }.try_to_vec().unwrap();
+
+    // amount 只有 3 个字节
+    let result = process_data(&mut [&mut mint, &mut account, &mut Fixture::signer(authority)], &[data[0], 1, 0, 0]);
+    assert_eq!(result, Err(TokenError::InvalidInstruction.into()));
+
+    data.push(0);
+    let result = process_data(&mut [&mut mint, &mut account, &mut Fixture::signer(authority)], &data);
+    assert_eq!(result, Err(TokenError::InvalidInstructionData.into()));
+    assert_eq!(mint.mint_state().supply, 0);
+    assert_eq!(account.token_state().amount, 0);
+}
+
+#[test]
+fn transfer_moves_balance() {
+    let mut mint_account = Fixture::mint(MintFixture::new());
+    let mint = mint_account.key;
+    let owner = Pubkey::new_unique();
+    let mut source = Fixture::token(TokenAccountFixture::new(mint, owner).amount(100));
+    let mut destination = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()).amount(5));
+    process(
+        &mut [&mut source, &mut destination, &mut Fixture::signer(owner), &mut mint_account],
+        TokenInstruction::Transfer { amount: 40 },
+    )
+    .unwrap();
+
+    assert_eq!(source.token_state().amount, 60);
+    assert_eq!(destination.token_state().amount, 45);
+}
+
+#[test]
+fn transfer_rejects_destination_of_another_mint() {
+    let owner = Pubkey::new_unique();
+    let mut source = Fixture::token(TokenAccountFixture::new(Pubkey::new_unique(), owner).amount(100));
+    let mut destination = Fixture::token(TokenAccountFixture::new(Pubkey::new_unique(), Pubkey::new_unique()));
+    let result = process(
+        &mut [&mut source, &mut destination, &mut Fixture::signer(owner)],
+        TokenInstruction::Transfer { amount: 50 },
+    );
+
+    assert_eq!(result, Err(TokenError::MintMismatch.into()));
+    assert_eq!(destination.token_state().amount, 0);
+}
+
+#[test]
+fn transfer_accepts_reference_keys_up_to_limit() {
+    let mut mint_account = Fixture::mint(MintFixture::new());
+    let mint = mint_account.key;
+    let owner = Pubkey::new_unique();
+    let mut source = Fixture::token(TokenAccountFixture::new(mint, owner).amount(100));
+    let mut destination = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()));
+    let mut owner_signer = Fixture::signer(owner);
+    let mut references: Vec<Fixture> =
+        (0..=MAX_TRANSFER_REFERENCES).map(|_| Fixture::signer(Pubkey::new_unique()).not_signer()).collect();
+    let mut transfer = |reference_count: usize| {
+        let mut fixtures: Vec<&mut Fixture> = vec![&mut source, &mut destination, &mut owner_signer, &mut mint_account];
+        fixtures.extend(references.iter_mut().take(reference_count));
+        process(&mut fixtures, TokenInstruction::Transfer { amount: 10 })
+    };
+
+    transfer(2).unwrap();
+    assert_eq!(transfer(MAX_TRANSFER_REFERENCES + 1), Err(ProgramError::InvalidArgument));
+}
+
+#[test]
+fn transfer_rejects_insufficient_funds() {
+    let mint = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let mut source = Fixture::token(TokenAccountFixture::new(mint, owner).amount(10));
+    let mut destination = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()).amount(0));
+    let result = process(
+        &mut [&mut source, &mut destination, &mut Fixture::signer(owner)],
+        TokenInstruction::Transfer { amount: 11 },
+    );
+
+    assert_eq!(result, Err(TokenError::InsufficientFunds.into()));
+    assert_eq!(source.token_state().amount, 10);
+}
+
+#[test]
+fn transfer_rejects_frozen_source() {
+    let mint = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let mut source = Fixture::token(TokenAccountFixture::new(mint, owner).amount(10).frozen());
+    let mut destination = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()).amount(0));
+    let result = process(
+        &mut [&mut source, &mut destination, &mut Fixture::signer(owner)],
+        TokenInstruction::Transfer { amount: 1 },
+    );
+
+    assert_eq!(result, Err(TokenError::AccountFrozen.into()));
+}
+
+#[test]
+fn velocity_limit_freezes_source_and_blocks_later_transfers() {
+    let owner = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new());
+    let mut source = Fixture::token(TokenAccountFixture::new(mint.key, owner).amount(10).velocity_limited());
+    let mut destination = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()));
+    let tracker = VelocityTracker {
+        is_initialized: true,
+        token_account: source.key,
+        max_amount: 1,
+        window_slots: 1000,
+        window_start_slot: TEST_SLOT,
+        window_amount: 0,
+    };
+    let mut tracker = Fixture::with_data(tracker.try_to_vec().unwrap());
+
+    // 超出上限：转账被放弃但交易成功，源账户被冻结
+    process(
+        &mut [&mut source, &mut destination, &mut Fixture::signer(owner), &mut mint, &mut tracker],
+        TokenInstruction::Transfer { amount: 2 },
+    )
+    .unwrap();
+    assert!(source.token_state().is_frozen());
+    assert_eq!(destination.token_state().amount, 0);
+
+    let result = process(
+        &mut [&mut source, &mut destination, &mut Fixture::signer(owner), &mut mint, &mut tracker],
+        TokenInstruction::Transfer { amount: 1 },
+    );
+    assert_eq!(result, Err(TokenError::AccountFrozen.into()));
+    assert_eq!(source.token_state().amount, 10);
+}
+
+#[test]
+fn transfer_rejects_foreign_or_uninitialized_destination() {
+    let mint = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let mut source = Fixture::token(TokenAccountFixture::new(mint, owner).amount(10));
+    let mut owner_signer = Fixture::signer(owner);
+    let mut foreign = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()));
+    foreign.owner = Pubkey::new_unique();
+    let result =
+        process(&mut [&mut source, &mut foreign, &mut owner_signer], TokenInstruction::Transfer { amount: 1 });
+    assert_eq!(result, Err(ProgramError::IncorrectProgramId));
+
+    let mut uninitialized = Fixture::program_owned(TokenAccount::LEN);
+    let result =
+        process(&mut [&mut source, &mut uninitialized, &mut owner_signer], TokenInstruction::Transfer { amount: 1 });
+    assert_eq!(result, Err(ProgramError::UninitializedAccount));
+    assert_eq!(source.token_state().amount, 10);
+}
+
+#[test]
+fn scheduled_thaw_releases_frozen_account_at_slot() {
+    let freezer = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().freeze_authority(freezer)).read_only();
+    let account = || TokenAccountFixture::new(mint.key, owner).amount(10).space(TOKEN_ACCOUNT_WITH_SCHEDULED_THAW_LEN);
+    let mut held = Fixture::token(account());
+    let mut released = Fixture::token(account().frozen().extension(&ScheduledThaw { thaw_at_slot: Some(TEST_SLOT) }));
+    let mut destination = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()));
+    let mut owner_signer = Fixture::signer(owner);
+    process(
+        &mut [&mut held, &mut mint, &mut Fixture::signer(freezer)],
+        TokenInstruction::FreezeAccount { thaw_at_slot: Some(TEST_SLOT + 10), reason_code: 7 },
+    )
+    .unwrap();
+
+    let result = process(
+        &mut [&mut held, &mut destination, &mut owner_signer, &mut mint],
+        TokenInstruction::Transfer { amount: 1 },
+    );
+    assert_eq!(result, Err(TokenError::AccountFrozen.into()));
+    process(
+        &mut [&mut released, &mut destination, &mut owner_signer, &mut mint],
+        TokenInstruction::Transfer { amount: 1 },
+    )
+    .unwrap();
+    assert!(!released.token_state().is_frozen());
+    assert_eq!(destination.token_state().amount, 1);
+}
+
+#[test]
+fn freeze_and_thaw_require_reason_code() {
+    let freezer = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().freeze_authority(freezer)).read_only();
+    let mut account = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()));
+    let mut signer = Fixture::signer(freezer);
+
+    for reason_code in [0, FREEZE_REASON_VELOCITY_LIMIT] {
+        let freeze = TokenInstruction::FreezeAccount { thaw_at_slot: None, reason_code };
+        assert_eq!(process(&mut [&mut account, &mut mint, &mut signer], freeze), Err(ProgramError::InvalidArgument));
+    }
+    assert!(!account.token_state().is_frozen());
+
+    let freeze = TokenInstruction::FreezeAccount { thaw_at_slot: None, reason_code: 3 };
+    process(&mut [&mut account, &mut mint, &mut signer], freeze).unwrap();
+    assert!(account.token_state().is_frozen());
+    let thaw = TokenInstruction::ThawAccount { reason_code: 0 };
+    assert_eq!(process(&mut [&mut account, &mut mint, &mut signer], thaw), Err(ProgramError::InvalidArgument));
+    process(&mut [&mut account, &mut mint, &mut signer], TokenInstruction::ThawAccount { reason_code: 4 }).unwrap();
+    assert!(!account.token_state().is_frozen());
+}
+
+#[test]
+fn transfer_with_memo_records_memo_hash() {
+    let mut mint_account = Fixture::mint(MintFixture::new());
+    let mint = mint_account.key;
+    let owner = Pubkey::new_unique();
+    let mut source = Fixture::token(TokenAccountFixture::new(mint, owner).amount(100).space(TOKEN_ACCOUNT_WITH_MEMO_LOG_LEN));
+    let mut destination = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()).amount(0));
+    let mut signer = Fixture::signer(owner);
+    process(&mut [&mut source, &mut signer], TokenInstruction::EnableMemoLog).unwrap();
+    process(
+        &mut [&mut source, &mut destination, &mut signer, &mut mint_account],
+        TokenInstruction::TransferWithMemo { amount: 30, memo: b"invoice-17".to_vec() },
+    )
+    .unwrap();
+
+    let log = extension::get_extension::<TokenAccount, MemoLog>(&source.data).unwrap().unwrap();
+    assert_eq!(log.next, 1);
+    assert_eq!(log.entries[0].hash, hashv(&[b"invoice-17"]).to_bytes());
+    assert_eq!(log.entries[0].slot, TEST_SLOT);
+    assert_eq!(destination.token_state().amount, 30);
+}
+
+#[test]
+fn crank_closes_expired_session_and_finalizes_matured_thaw() {
+    let owner = Pubkey::new_unique();
+    let mut token = Fixture::token(TokenAccountFixture::new(Pubkey::new_unique(), owner));
+    let session = |expires_at_slot| Session {
+        is_initialized: true,
+        token_account: token.key,
+        key: Pubkey::new_unique(),
+        remaining: 10,
+        expires_at_slot,
+    };
+    let mut live_session = Fixture::with_data(session(TEST_SLOT).try_to_vec().unwrap());
+    let mut expired_session = Fixture::with_data(session(TEST_SLOT - 1).try_to_vec().unwrap());
+    let mut thawed = Fixture::token(
+        TokenAccountFixture::new(Pubkey::new_unique(), owner)
+            .frozen()
+            .extension(&ScheduledThaw { thaw_at_slot: Some(TEST_SLOT) }),
+    );
+    let mut cranker = Fixture::signer(Pubkey::new_unique());
+    let mut owner_account = Fixture::signer(owner).not_signer();
+    let result = process(
+        &mut [&mut cranker, &mut token, &mut live_session, &mut owner_account],
+        TokenInstruction::Crank { items: vec![CrankItem::ExpiredSession] },
+    );
+    assert_eq!(result, Err(TokenError::TimelockNotElapsed.into()));
+
+    let session_rent = expired_session.lamports;
+    process(
+        &mut [&mut cranker, &mut token, &mut expired_session, &mut owner_account, &mut thawed],
+        TokenInstruction::Crank { items: vec![CrankItem::ExpiredSession, CrankItem::MaturedThaw] },
+    )
+    .unwrap();
+
+    assert_eq!(cranker.lamports, 1_000_000_000 + CRANK_REWARD_LAMPORTS);
+    assert_eq!(owner_account.lamports, 1_000_000_000 + session_rent - CRANK_REWARD_LAMPORTS);
+    assert_eq!(expired_session.lamports, 0);
+    assert!(!TokenAccount::unpack_from_slice(&thawed.data).unwrap().is_frozen());
+    let scheduled = extension::get_extension::<TokenAccount, ScheduledThaw>(&thawed.data).unwrap().unwrap();
+    assert_eq!(scheduled.thaw_at_slot, None);
+}
+
+#[test]
+fn debit_respects_per_cycle_limit() {
+    let mut mint_account = Fixture::mint(MintFixture::new());
+    let mint = mint_account.key;
+    let owner = Pubkey::new_unique();
+    let merchant = Pubkey::new_unique();
+    let mut source = Fixture::token(TokenAccountFixture::new(mint, owner).amount(100));
+    let mut destination = Fixture::token(TokenAccountFixture::new(mint, merchant));
+    // 上一周期已用满额度，当前 slot 已进入下一周期
+    let pre_authorization = PreAuthorization {
+        is_initialized: true,
+        token_account: source.key,
+        merchant,
+        amount_per_cycle: 50,
+        cycle_slots: 10,
+        cycle_start_slot: TEST_SLOT - 15,
+        debited_in_cycle: 50,
+    };
+    let mut pre_authorization_account = Fixture::with_data(pre_authorization.try_to_vec().unwrap());
+    let mut debit = |merchant: Pubkey, amount: u64| {
+        process(
+            &mut [
+                &mut source,
+                &mut destination,
+                &mut pre_authorization_account,
+                &mut Fixture::signer(merchant),
+                &mut mint_account,
+            ],
+            TokenInstruction::Debit { amount },
+        )
+    };
+
+    assert_eq!(debit(Pubkey::new_unique(), 10), Err(TokenError::Unauthorized.into()));
+    debit(merchant, 30).unwrap();
+    assert_eq!(debit(merchant, 30), Err(TokenError::InsufficientFunds.into()));
+
+    let state = PreAuthorization::try_from_slice(&pre_authorization_account.data).unwrap();
+    assert_eq!(state.cycle_start_slot, TEST_SLOT - 5);
+    assert_eq!(state.debited_in_cycle, 30);
+    assert_eq!(source.token_state().amount, 70);
+    assert_eq!(destination.token_state().amount, 30);
+
+    let mut owner_account = Fixture::signer(owner);
+    process(
+        &mut [&mut source, &mut pre_authorization_account, &mut owner_account],
+        TokenInstruction::ClosePreAuthorization,
+    )
+    .unwrap();
+    assert_eq!(pre_authorization_account.lamports, 0);
+}
+
+#[test]
+fn savings_yield_raises_share_price() {
+    let mut mint_account = Fixture::mint(MintFixture::new());
+    let mint = mint_account.key;
+    let yield_authority = Pubkey::new_unique();
+    let alice = Pubkey::new_unique();
+    let bob = Pubkey::new_unique();
+    let mut vault = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()).amount(100));
+    let savings_vault = SavingsVault {
+        is_initialized: true,
+        mint,
+        vault: vault.key,
+        yield_authority,
+        total_shares: 100,
+        bump: 255,
+    };
+    let mut savings_vault_account = Fixture::with_data(savings_vault.try_to_vec().unwrap());
+    let position = |owner: Pubkey, shares: u64| {
+        let state = SavingsPosition { is_initialized: true, savings_vault: savings_vault_account.key, owner, shares };
+        let key = find_savings_position_address(&savings_vault_account.key, &owner, &PROGRAM_ID).0;
+        Fixture { key, ..Fixture::with_data(state.try_to_vec().unwrap()) }
+    };
+    let mut alice_position = position(alice, 100);
+    let mut bob_position = position(bob, 0);
+    let mut yield_source = Fixture::token(TokenAccountFixture::new(mint, yield_authority).amount(50));
+    let mut bob_source = Fixture::token(TokenAccountFixture::new(mint, bob).amount(30));
+    let mut alice_destination = Fixture::token(TokenAccountFixture::new(mint, alice));
+
+    let mut fund_yield = |authority: Pubkey| {
+        process(
+            &mut [
+                &mut savings_vault_account,
+                &mut vault,
+                &mut yield_source,
+                &mut Fixture::signer(authority),
+                &mut mint_account,
+            ],
+            TokenInstruction::FundYield { amount: 50 },
+        )
+    };
+    assert_eq!(fund_yield(alice), Err(TokenError::Unauthorized.into()));
+    fund_yield(yield_authority).unwrap();
+
+    // 份额价格 150 / 100：存入 30 只得到 20 份
+    process(
+        &mut [
+            &mut savings_vault_account,
+            &mut vault,
+            &mut bob_source,
+            &mut bob_position,
+            &mut Fixture::signer(bob),
+            &mut Fixture::program_owned(0),
+            &mut mint_account,
+        ],
+        TokenInstruction::DepositSavings { amount: 30 },
+    )
+    .unwrap();
+    assert_eq!(SavingsPosition::try_from_slice(&bob_position.data).unwrap().shares, 20);
+
+    // 赎回只能进入仓位所有者自己的代币账户
+    let result = process(
+        &mut [
+            &mut savings_vault_account,
+            &mut vault,
+            &mut alice_position,
+            &mut bob_source,
+            &mut Fixture::signer(alice),
+            &mut mint_account,
+        ],
+        TokenInstruction::WithdrawSavings { shares: 100 },
+    );
+    assert_eq!(result, Err(TokenError::Unauthorized.into()));
+
+    process(
+        &mut [
+            &mut savings_vault_account,
+            &mut vault,
+            &mut alice_position,
+            &mut alice_destination,
+            &mut Fixture::signer(alice),
+            &mut mint_account,
+        ],
+        TokenInstruction::WithdrawSavings { shares: 100 },
+    )
+    .unwrap();
+    assert_eq!(alice_destination.token_state().amount, 150);
+    assert_eq!(vault.token_state().amount, 30);
+    assert_eq!(SavingsVault::try_from_slice(&savings_vault_account.data).unwrap().total_shares, 20);
+    assert_eq!(alice_position.lamports, 0);
+}
+
+#[test]
+fn savings_deposit_and_withdraw_keep_holder_count() {
+    let holders = |mint: &Fixture| extension::get_extension::<Mint, HolderCount>(&mint.data).unwrap().unwrap().count;
+    let mut mint_account = Fixture::mint(MintFixture::new().supply(30).extension(&HolderCount { count: 1 }));
+    let mint = mint_account.key;
+    let bob = Pubkey::new_unique();
+    let mut vault = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()));
+    let savings_vault = SavingsVault {
+        is_initialized: true,
+        mint,
+        vault: vault.key,
+        yield_authority: Pubkey::new_unique(),
+        total_shares: 0,
+        bump: 255,
+    };
+    let mut savings_vault_account = Fixture::with_data(savings_vault.try_to_vec().unwrap());
+    let position =
+        SavingsPosition { is_initialized: true, savings_vault: savings_vault_account.key, owner: bob, shares: 0 };
+    let position_key = find_savings_position_address(&savings_vault_account.key, &bob, &PROGRAM_ID).0;
+    let mut bob_position = Fixture { key: position_key, ..Fixture::with_data(position.try_to_vec().unwrap()) };
+    let mut bob_account = Fixture::token(TokenAccountFixture::new(mint, bob).amount(30).holder_tracked());
+
+    // 托管账户从 0 变为非 0，持有人多一个
+    process(
+        &mut [
+            &mut savings_vault_account,
+            &mut vault,
+            &mut bob_account,
+            &mut bob_position,
+            &mut Fixture::signer(bob),
+            &mut Fixture::program_owned(0),
+            &mut mint_account,
+        ],
+        TokenInstruction::DepositSavings { amount: 10 },
+    )
+    .unwrap();
+    assert_eq!(holders(&mint_account), 2);
+    assert!(vault.token_state().holder_tracked());
+
+    // 全部赎回后托管账户归 0，持有人少一个
+    process(
+        &mut [
+            &mut savings_vault_account,
+            &mut vault,
+            &mut bob_position,
+            &mut bob_account,
+            &mut Fixture::signer(bob),
+            &mut mint_account,
+        ],
+        TokenInstruction::WithdrawSavings { shares: 10 },
+    )
+    .unwrap();
+    assert_eq!(vault.token_state().amount, 0);
+    assert_eq!(bob_account.token_state().amount, 30);
+    assert_eq!(holders(&mint_account), 1);
+}
+
+#[test]
+fn fund_distribution_requires_mint_authority() {
+    let attacker = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().mint_authority(Pubkey::new_unique()).supply(100));
+    let mut reward_mint = Fixture::mint(MintFixture::new().supply(10));
+    let seeds: &[&[u8]] = &[DISTRIBUTION_SEED, mint.key.as_ref(), &1u64.to_le_bytes()];
+    let distribution_key = Pubkey::find_program_address(seeds, &PROGRAM_ID).0;
+    let mut distribution = Fixture { key: distribution_key, ..Fixture::program_owned(0) };
+    let mut vault = Fixture::token(TokenAccountFixture::new(reward_mint.key, distribution_key));
+    let mut source = Fixture::token(TokenAccountFixture::new(reward_mint.key, attacker).amount(10));
+    let result = process(
+        &mut [
+            &mut mint,
+            &mut distribution,
+            &mut vault,
+            &mut source,
+            &mut Fixture::signer(attacker),
+            &mut Fixture::program_owned(0),
+            &mut reward_mint,
+        ],
+        TokenInstruction::FundDistribution {
+            distribution_id: 1,
+            snapshot_root: [1; 32],
+            snapshot_supply: 100,
+            amount: 10,
+        },
+    );
+
+    assert_eq!(result, Err(TokenError::Unauthorized.into()));
+    assert_eq!(source.token_state().amount, 10);
+}
+
+#[test]
+fn distribute_emissions_splits_by_weight_and_returns_authority() {
+    let authority = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().supply(100));
+    let mut first = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()));
+    let mut second = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()));
+    // 排放期已结束，[TEST_SLOT - 8, TEST_SLOT - 2) 内的 6 个 slot 尚未分发
+    let config = EmissionsConfig {
+        is_initialized: true,
+        mint: mint.key,
+        authority,
+        tokens_per_slot: 5,
+        start_slot: TEST_SLOT - 10,
+        end_slot: TEST_SLOT - 2,
+        distributed_until_slot: TEST_SLOT - 8,
+        recipients: vec![
+            EmissionRecipient { vault: first.key, weight: 1 },
+            EmissionRecipient { vault: second.key, weight: 2 },
+        ],
+    };
+    let mut data = config.try_to_vec().unwrap();
+    data.resize(EmissionsConfig::LEN, 0);
+    let mut emissions = Fixture::with_data(data);
+    mint.data = MintFixture::new().supply(100).mint_authority(emissions.key).to_bytes();
+
+    let result =
+        process(&mut [&mut mint, &mut emissions, &mut second, &mut first], TokenInstruction::DistributeEmissions);
+    assert_eq!(result, Err(ProgramError::InvalidAccountData));
+    process(&mut [&mut mint, &mut emissions, &mut first, &mut second], TokenInstruction::DistributeEmissions).unwrap();
+
+    assert_eq!(first.token_state().amount, 10);
+    assert_eq!(second.token_state().amount, 20);
+    assert_eq!(mint.mint_state().supply, 130);
+    assert_eq!(mint.mint_state().mint_authority, Some(authority));
+    let state = EmissionsConfig::deserialize(&mut emissions.data.as_slice()).unwrap();
+    assert_eq!(state.distributed_until_slot, TEST_SLOT - 2);
+
+    // 已全部分发，再次调用不铸造
+    process(&mut [&mut mint, &mut emissions], TokenInstruction::DistributeEmissions).unwrap();
+    assert_eq!(mint.mint_state().supply, 130);
+}
+
+#[test]
+fn expire_points_burns_balance_not_covered_by_live_lots() {
+    let authority = Pubkey::new_unique();
+    let mint_fixture = MintFixture::new().mint_authority(authority).supply(70);
+    let mut mint = Fixture::mint(mint_fixture.extension(&PointsConfig { lifetime_slots: 10 }));
+    let points_account = |lots: PointsLots, amount: u64| {
+        let account = TokenAccountFixture::new(mint.key, Pubkey::new_unique()).amount(amount);
+        Fixture::token(account.extension(&lots))
+    };
+    let mut fresh = points_account(PointsLots::default(), 0);
+    // 第一批已过期且花掉了 20，第二批未过期：余额 70 中有 30 来自过期批次
+    let mut lots = PointsLots::default();
+    lots.lots[0] = PointsLot { amount: 50, expires_at_slot: TEST_SLOT - 1 };
+    lots.lots[1] = PointsLot { amount: 40, expires_at_slot: TEST_SLOT + 5 };
+    let mut stale = points_account(lots, 70);
+
+    process(&mut [&mut mint, &mut fresh, &mut Fixture::signer(authority)], TokenInstruction::MintTo { amount: 40 })
+        .unwrap();
+    let minted = extension::get_extension::<TokenAccount, PointsLots>(&fresh.data).unwrap().unwrap();
+    assert_eq!(minted.lots[0], PointsLot { amount: 40, expires_at_slot: TEST_SLOT + 10 });
+
+    process(&mut [&mut mint, &mut fresh, &mut stale], TokenInstruction::ExpirePoints).unwrap();
+    assert_eq!(fresh.token_state().amount, 40);
+    assert_eq!(stale.token_state().amount, 40);
+    assert_eq!(mint.mint_state().supply, 80);
+    let remaining = extension::get_extension::<TokenAccount, PointsLots>(&stale.data).unwrap().unwrap();
+    assert_eq!(remaining.lots[0], PointsLot::default());
+    assert_eq!(remaining.lots[1], PointsLot { amount: 40, expires_at_slot: TEST_SLOT + 5 });
+
+    let result = process(
+        &mut [&mut mint, &mut Fixture::signer(authority)],
+        TokenInstruction::EnablePointsExpiry { lifetime_slots: 10 },
+    );
+    assert_eq!(result, Err(TokenError::InvalidInstruction.into()));
+}
+
+#[test]
+fn transferred_points_keep_their_lots() {
+    let authority = Pubkey::new_unique();
+    let alice = Pubkey::new_unique();
+    let mint_fixture = MintFixture::new().mint_authority(authority);
+    let mut mint = Fixture::mint(mint_fixture.extension(&PointsConfig { lifetime_slots: 10 }));
+    let points_account = |owner: Pubkey| {
+        Fixture::token(TokenAccountFixture::new(mint.key, owner).extension(&PointsLots::default()))
+    };
+    let mut source = points_account(alice);
+    let mut destination = points_account(Pubkey::new_unique());
+    let mut without_lots = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()));
+
+    process(&mut [&mut mint, &mut source, &mut Fixture::signer(authority)], TokenInstruction::MintTo { amount: 40 })
+        .unwrap();
+    process(
+        &mut [&mut source, &mut destination, &mut Fixture::signer(alice), &mut mint],
+        TokenInstruction::Transfer { amount: 15 },
+    )
+    .unwrap();
+    let lots =
+        |account: &Fixture| extension::get_extension::<TokenAccount, PointsLots>(&account.data).unwrap().unwrap();
+    assert_eq!(lots(&source).lots[0], PointsLot { amount: 25, expires_at_slot: TEST_SLOT + 10 });
+    assert_eq!(lots(&destination).lots[0], PointsLot { amount: 15, expires_at_slot: TEST_SLOT + 10 });
+
+    // 收到的积分没有过期，任何人调用 ExpirePoints 都销毁不了
+    process(&mut [&mut mint, &mut source, &mut destination], TokenInstruction::ExpirePoints).unwrap();
+    assert_eq!(source.token_state().amount, 25);
+    assert_eq!(destination.token_state().amount, 15);
+    assert_eq!(mint.mint_state().supply, 40);
+
+    // 没有批次扩展的账户收不了积分
+    let result = process(
+        &mut [&mut source, &mut without_lots, &mut Fixture::signer(alice), &mut mint],
+        TokenInstruction::Transfer { amount: 5 },
+    );
+    assert_eq!(result, Err(ProgramError::InvalidAccountData));
+    let result = process(
+        &mut [&mut source, &mut destination, &mut Fixture::signer(alice), &mut mint],
+        TokenInstruction::SettleBatch { deltas: vec![-5, 5] },
+    );
+    assert_eq!(result, Err(ProgramError::InvalidArgument));
+}
+
+#[test]
+fn sweep_dormant_moves_balance_unless_account_became_active() {
+    let authority = Pubkey::new_unique();
+    let mint_key = Pubkey::new_unique();
+    let mut vault = Fixture::token(TokenAccountFixture::new(mint_key, Pubkey::new_unique()));
+    let policy = DormancyPolicy { authority, recovery_vault: vault.key, dormant_epochs: 1, notice_epochs: 1 };
+    let mut mint = Fixture { key: mint_key, ..Fixture::mint(MintFixture::new().supply(200).extension(&policy)) };
+    let dormant_account = |last_activity_slot: u64| {
+        let activity = AccountActivity { created_at: 0, last_activity_slot };
+        Fixture::token(TokenAccountFixture::new(mint_key, Pubkey::new_unique()).amount(100).extension(&activity))
+    };
+    let notice_for = |account: &Fixture, last_activity_slot: u64| {
+        let notice =
+            DormancyNotice { is_initialized: true, token_account: account.key, last_activity_slot, warned_at_slot: 0 };
+        let (key, _) = find_dormancy_notice_address(&account.key, &PROGRAM_ID);
+        Fixture { key, ..Fixture::with_data(notice.try_to_vec().unwrap()) }
+    };
+
+    // 本 epoch 内刚有过活动，不能发出警告
+    let mut active = dormant_account(TEST_SLOT);
+    let (notice_key, _) = find_dormancy_notice_address(&active.key, &PROGRAM_ID);
+    let result = process(
+        &mut [
+            &mut mint,
+            &mut active,
+            &mut Fixture { key: notice_key, ..Fixture::program_owned(0) },
+            &mut Fixture::signer(authority),
+            &mut Fixture::signer(solana_program::system_program::id()).not_signer(),
+        ],
+        TokenInstruction::WarnDormant,
+    );
+    assert_eq!(result, Err(TokenError::TimelockNotElapsed.into()));
+
+    // epoch 0 警告后没有活动：余额归集到回收账户，警告 PDA 关闭
+    let mut dormant = dormant_account(0);
+    let mut notice = notice_for(&dormant, 0);
+    let mut signer = Fixture::signer(authority);
+    process(&mut [&mut mint, &mut dormant, &mut vault, &mut notice, &mut signer], TokenInstruction::SweepDormant)
+        .unwrap();
+    assert_eq!(dormant.token_state().amount, 0);
+    assert_eq!(vault.token_state().amount, 100);
+    assert_eq!(notice.lamports, 0);
+
+    // 警告后有过活动：只撤销警告，余额不动
+    let mut revived = dormant_account(5);
+    let mut notice = notice_for(&revived, 0);
+    process(&mut [&mut mint, &mut revived, &mut vault, &mut notice, &mut signer], TokenInstruction::SweepDormant)
+        .unwrap();
+    assert_eq!(revived.token_state().amount, 100);
+    assert_eq!(vault.token_state().amount, 100);
+    assert_eq!(notice.lamports, 0);
+}
+
+#[test]
+fn update_attestation_requires_attester_signature() {
+    let authority = Pubkey::new_unique();
+    let attester = Pubkey::new_unique();
+    let mint_key = Pubkey::new_unique();
+    let mut mint = Fixture { key: mint_key, ..Fixture::mint(MintFixture::new().mint_authority(authority).supply(900)) };
+    let (attestation_key, _) = find_attestation_address(&mint_key, &PROGRAM_ID);
+    let previous =
+        Attestation { is_initialized: true, mint: mint_key, attester, reserves: 500, attested_at_slot: TEST_SLOT - 10 };
+    let mut attestation = Fixture { key: attestation_key, ..Fixture::with_data(previous.try_to_vec().unwrap()) };
+    let mut system_program = Fixture::signer(solana_program::system_program::id()).not_signer();
+
+    let result = process(
+        &mut [
+            &mut mint,
+            &mut attestation,
+            &mut Fixture::signer(authority),
+            &mut Fixture::signer(attester).not_signer(),
+            &mut system_program,
+        ],
+        TokenInstruction::UpdateAttestation { reserves: 1_000 },
+    );
+    assert_eq!(result, Err(TokenError::Unauthorized.into()));
+
+    process(
+        &mut [
+            &mut mint,
+            &mut attestation,
+            &mut Fixture::signer(authority),
+            &mut Fixture::signer(attester),
+            &mut system_program,
+        ],
+        TokenInstruction::UpdateAttestation { reserves: 1_000 },
+    )
+    .unwrap();
+    let updated = Attestation::try_from_slice(&attestation.data).unwrap();
+    assert_eq!((updated.reserves, updated.attested_at_slot), (1_000, TEST_SLOT));
+
+    process(&mut [&mut mint, &mut attestation], TokenInstruction::VerifySupplyBacked).unwrap();
+    let result = process(
+        &mut [&mut mint, &mut Fixture::with_data(attestation.data.clone())],
+        TokenInstruction::VerifySupplyBacked,
+    );
+    assert_eq!(result, Err(ProgramError::InvalidSeeds));
+}
+
+#[test]
+fn convert_mint_burns_old_tokens_and_pays_from_vault() {
+    let owner = Pubkey::new_unique();
+    let (old_mint_key, new_mint_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut old_mint = Fixture { key: old_mint_key, ..Fixture::mint(MintFixture::new().supply(100)) };
+    let mut new_mint = Fixture { key: new_mint_key, ..Fixture::mint(MintFixture::new().supply(1_000)) };
+    let (conversion_key, _) = find_conversion_address(&old_mint_key, &PROGRAM_ID);
+    let mut vault = Fixture::token(TokenAccountFixture::new(new_mint_key, conversion_key).amount(100));
+    // 每个旧代币换 2.5 个新代币
+    let conversion = Conversion {
+        is_initialized: true,
+        old_mint: old_mint_key,
+        new_mint: new_mint_key,
+        vault: vault.key,
+        rate: 2_500_000_000,
+        total_converted: 0,
+    };
+    let mut conversion = Fixture { key: conversion_key, ..Fixture::with_data(conversion.try_to_vec().unwrap()) };
+    let mut source = Fixture::token(TokenAccountFixture::new(old_mint_key, owner).amount(100));
+    let mut dest = Fixture::token(TokenAccountFixture::new(new_mint_key, owner));
+
+    let mut convert = |amount: u64| {
+        process(
+            &mut [
+                &mut old_mint,
+                &mut new_mint,
+                &mut source,
+                &mut dest,
+                &mut conversion,
+                &mut vault,
+                &mut Fixture::signer(owner),
+            ],
+            TokenInstruction::ConvertMint { amount },
+        )
+    };
+    convert(30).unwrap();
+    assert_eq!(convert(0), Err(ProgramError::InvalidArgument));
+    // 托管账户只剩 25，不够兑换 20 个旧代币（50 个新代币），旧代币不会被销毁
+    assert_eq!(convert(20), Err(TokenError::InsufficientFunds.into()));
+
+    assert_eq!(source.token_state().amount, 70);
+    assert_eq!(old_mint.mint_state().supply, 70);
+    assert_eq!(dest.token_state().amount, 75);
+    assert_eq!(vault.token_state().amount, 25);
+    assert_eq!(Conversion::try_from_slice(&conversion.data).unwrap().total_converted, 30);
+}
+
+#[test]
+fn rebalance_skips_velocity_limit_between_same_owner_accounts() {
+    let owner = Pubkey::new_unique();
+    let mut mint_account = Fixture::mint(MintFixture::new());
+    let mint = mint_account.key;
+    // 带速度限制的账户转账需要跟踪 PDA，调拨不需要
+    let mut source = Fixture::token(TokenAccountFixture::new(mint, owner).amount(100).velocity_limited());
+    let mut dest = Fixture::token(TokenAccountFixture::new(mint, owner));
+    let mut other = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()));
+
+    process(
+        &mut [&mut source, &mut dest, &mut Fixture::signer(owner), &mut mint_account],
+        TokenInstruction::Rebalance { amount: 60 },
+    )
+    .unwrap();
+    assert_eq!((source.token_state().amount, dest.token_state().amount), (40, 60));
+
+    let rebalance = TokenInstruction::Rebalance { amount: 10 };
+    let result = process(&mut [&mut source, &mut other, &mut Fixture::signer(owner), &mut mint_account], rebalance);
+    assert_eq!(result, Err(TokenError::Unauthorized.into()));
+    assert_eq!(other.token_state().amount, 0);
+}
+
+#[test]
+fn set_account_label_writes_extension() {
+    let owner = Pubkey::new_unique();
+    let mut account =
+        Fixture::token(TokenAccountFixture::new(Pubkey::new_unique(), owner).space(TOKEN_ACCOUNT_WITH_LABEL_LEN));
+    let mut label = [0u8; 32];
+    label[..7].copy_from_slice(b"payroll");
+    let result = process(
+        &mut [&mut account, &mut Fixture::signer(Pubkey::new_unique())],
+        TokenInstruction::SetAccountLabel { label },
+    );
+    assert_eq!(result, Err(TokenError::Unauthorized.into()));
+
+    process(&mut [&mut account, &mut Fixture::signer(owner)], TokenInstruction::SetAccountLabel { label }).unwrap();
+
+    let stored = extension::get_extension::<TokenAccount, AccountLabel>(&account.data).unwrap().unwrap();
+    assert_eq!(stored.as_str(), Some("payroll"));
+}
+
+#[test]
+fn extension_bitmap_lists_initialized_extensions() {
+    let mint_fixture = MintFixture::new()
+        .extension(&MintCloseConfig { close_destination: Pubkey::new_unique() })
+        .extension(&GlobalFreezeState { frozen: false });
+    let mut mint = Fixture::mint(mint_fixture.clone());
+    assert_eq!(extension::extension_bitmap::<Mint>(&mint.data).unwrap(), 1 << 1 | 1 << 8);
+    process(&mut [&mut mint], TokenInstruction::GetExtensionTypes { is_mint: true }).unwrap();
+
+    let mut uninitialized = Fixture::mint(mint_fixture.uninitialized());
+    let result = process(&mut [&mut uninitialized], TokenInstruction::GetExtensionTypes { is_mint: true });
+    assert_eq!(result, Err(ProgramError::UninitializedAccount));
+}
+
+#[test]
+fn burn_reduces_balance_and_supply() {
+    let owner = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().decimals(6).mint_authority(Pubkey::new_unique()).supply(100));
+    let mut account = Fixture::token(TokenAccountFixture::new(mint.key, owner).amount(100));
+    process(&mut [&mut account, &mut mint, &mut Fixture::signer(owner)], TokenInstruction::Burn { amount: 25 })
+        .unwrap();
+
+    assert_eq!(account.token_state().amount, 75);
+    assert_eq!(mint.mint_state().supply, 75);
+}
+
+#[test]
+fn burn_rejects_mint_other_than_account_mint() {
+    let owner = Pubkey::new_unique();
+    let mut unrelated_mint = Fixture::mint(MintFixture::new().decimals(6).supply(100));
+    let mut account = Fixture::token(TokenAccountFixture::new(Pubkey::new_unique(), owner).amount(100));
+    let result = process(
+        &mut [&mut account, &mut unrelated_mint, &mut Fixture::signer(owner)],
+        TokenInstruction::Burn { amount: 25 },
+    );
+
+    assert_eq!(result, Err(TokenError::MintMismatch.into()));
+    assert_eq!(account.token_state().amount, 100);
+    assert_eq!(unrelated_mint.mint_state().supply, 100);
+}
+
+#[test]
+fn rescale_mint_converts_balances_on_first_touch() {
+    let authority = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let mut mint = Fixture::mint(
+        MintFixture::new().decimals(2).mint_authority(authority).supply(300).space(MINT_WITH_DECIMALS_RESCALE_LEN),
+    );
+    let account = |amount| {
+        TokenAccountFixture::new(mint.key, owner).amount(amount).space(TOKEN_ACCOUNT_WITH_RESCALE_EPOCH_LEN)
+    };
+    let mut synced = Fixture::token(account(100));
+    let mut stale = Fixture::token(account(200));
+    let mut signer = Fixture::signer(authority);
+    process(&mut [&mut mint, &mut signer], TokenInstruction::RescaleMint { new_decimals: 6 }).unwrap();
+
+    assert_eq!(mint.mint_state().decimals, 6);
+    assert_eq!(mint.mint_state().supply, 3_000_000);
+    assert_eq!(stale.token_state().amount, 200);
+
+    let mut owner_signer = Fixture::signer(owner);
+    process(&mut [&mut synced, &mut mint, &mut owner_signer], TokenInstruction::Burn { amount: 0 }).unwrap();
+    assert_eq!(synced.token_state().amount, 1_000_000);
+    let epoch = extension::get_extension::<TokenAccount, RescaleEpoch>(&synced.data).unwrap();
+    assert_eq!(epoch, Some(RescaleEpoch { epoch: 1 }));
+
+    let result = process(
+        &mut [&mut synced, &mut stale, &mut owner_signer],
+        TokenInstruction::Transfer { amount: 1 },
+    );
+    assert_eq!(result, Err(TokenError::RescaleEpochMismatch.into()));
+
+    // 不经过 Transfer 的记账路径先换算再入账：stale 的 200 按新精度为 2_000_000
+    process(
+        &mut [&mut synced, &mut stale, &mut owner_signer, &mut mint],
+        TokenInstruction::SettleBatch { deltas: vec![-500_000, 500_000] },
+    )
+    .unwrap();
+    assert_eq!(synced.token_state().amount, 500_000);
+    assert_eq!(stale.token_state().amount, 2_500_000);
+    let epoch = extension::get_extension::<TokenAccount, RescaleEpoch>(&stale.data).unwrap();
+    assert_eq!(epoch, Some(RescaleEpoch { epoch: 1 }));
+}
+
+#[test]
+fn global_freeze_blocks_transfers_and_burns() {
+    let authority = Pubkey::new_unique();
+    let freezer = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let mut mint = Fixture::mint(
+        MintFixture::new().mint_authority(authority).freeze_authority(freezer).space(MINT_WITH_GLOBAL_FREEZE_LEN),
+    );
+    let mut source = Fixture::token(TokenAccountFixture::new(mint.key, owner));
+    let mut destination = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()));
+    let (mut freeze_signer, mut owner_signer) = (Fixture::signer(freezer), Fixture::signer(owner));
+    process(&mut [&mut mint, &mut freeze_signer], TokenInstruction::GlobalThaw).unwrap();
+    process(&mut [&mut mint, &mut source, &mut Fixture::signer(authority)], TokenInstruction::MintTo { amount: 10 })
+        .unwrap();
+    assert!(source.token_state().holder_tracked());
+
+    process(&mut [&mut mint, &mut freeze_signer], TokenInstruction::GlobalFreeze).unwrap();
+    let result = process(
+        &mut [&mut source, &mut destination, &mut owner_signer, &mut mint],
+        TokenInstruction::Transfer { amount: 1 },
+    );
+    assert_eq!(result, Err(TokenError::AccountFrozen.into()));
+    let result = process(&mut [&mut source, &mut mint, &mut owner_signer], TokenInstruction::Burn { amount: 1 });
+    assert_eq!(result, Err(TokenError::AccountFrozen.into()));
+
+    process(&mut [&mut mint, &mut freeze_signer], TokenInstruction::GlobalThaw).unwrap();
+    process(
+        &mut [&mut source, &mut destination, &mut owner_signer, &mut mint],
+        TokenInstruction::Transfer { amount: 4 },
+    )
+    .unwrap();
+    assert_eq!(destination.token_state().amount, 4);
+    assert!(destination.token_state().holder_tracked());
+}
+
+#[test]
+fn global_freeze_covers_untracked_balances_in_every_transfer_path() {
+    let owner = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().supply(20).extension(&GlobalFreezeState { frozen: true }));
+    // 扩展写入之前已有的余额，没有 HOLDER_TRACKED 标志
+    let mut source = Fixture::token(TokenAccountFixture::new(mint.key, owner).amount(20));
+    let mut destination = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()));
+    let mut owner_signer = Fixture::signer(owner);
+    let result =
+        process(&mut [&mut source, &mut destination, &mut owner_signer], TokenInstruction::Transfer { amount: 1 });
+    assert_eq!(result, Err(ProgramError::NotEnoughAccountKeys));
+    let result = process(
+        &mut [&mut source, &mut destination, &mut owner_signer, &mut mint],
+        TokenInstruction::Transfer { amount: 1 },
+    );
+    assert_eq!(result, Err(TokenError::AccountFrozen.into()));
+
+    let settle = || TokenInstruction::SettleBatch { deltas: vec![-5, 5] };
+    let result = process(&mut [&mut source, &mut destination, &mut owner_signer], settle());
+    assert_eq!(result, Err(ProgramError::NotEnoughAccountKeys));
+    let result = process(&mut [&mut source, &mut destination, &mut owner_signer, &mut mint], settle());
+    assert_eq!(result, Err(TokenError::AccountFrozen.into()));
+    assert_eq!(destination.token_state().amount, 0);
+}
+
+#[test]
+fn freeze_exempt_account_keeps_working_during_global_freeze() {
+    let freezer = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let mut mint = Fixture::mint(
+        MintFixture::new().freeze_authority(freezer).supply(10).extension(&GlobalFreezeState { frozen: true }),
+    );
+    let mut vault = Fixture::token(
+        TokenAccountFixture::new(mint.key, owner).amount(10).holder_tracked().space(TOKEN_ACCOUNT_WITH_FREEZE_EXEMPT_LEN),
+    );
+    let mut destination = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()));
+    let mut owner_signer = Fixture::signer(owner);
+    let result = process(
+        &mut [&mut vault, &mut destination, &mut owner_signer, &mut mint],
+        TokenInstruction::Transfer { amount: 1 },
+    );
+    assert_eq!(result, Err(TokenError::AccountFrozen.into()));
+
+    process(
+        &mut [&mut vault, &mut mint, &mut Fixture::signer(freezer)],
+        TokenInstruction::SetFreezeExempt { exempt: true },
+    )
+    .unwrap();
+    process(
+        &mut [&mut vault, &mut destination, &mut owner_signer, &mut mint],
+        TokenInstruction::Transfer { amount: 4 },
+    )
+    .unwrap();
+    process(&mut [&mut vault, &mut mint, &mut owner_signer], TokenInstruction::Burn { amount: 1 }).unwrap();
+
+    assert_eq!(vault.token_state().amount, 5);
+    assert_eq!(destination.token_state().amount, 4);
+}
+
+#[test]
+fn pause_flags_toggle_transfers_minting_and_burning_independently() {
+    let authority = Pubkey::new_unique();
+    let freezer = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let mut mint = Fixture::mint(
+        MintFixture::new().mint_authority(authority).freeze_authority(freezer).space(MINT_WITH_PAUSE_STATE_LEN),
+    );
+    let mut source = Fixture::token(TokenAccountFixture::new(mint.key, owner));
+    let mut destination = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()));
+    let (mut freeze_signer, mut owner_signer) = (Fixture::signer(freezer), Fixture::signer(owner));
+    let mut mint_signer = Fixture::signer(authority);
+    let pause = |pause_transfers, pause_minting, pause_burning| TokenInstruction::SetPauseFlags {
+        pause_transfers,
+        pause_minting,
+        pause_burning,
+    };
+    process(&mut [&mut mint, &mut freeze_signer], pause(None, None, None)).unwrap();
+    process(&mut [&mut mint, &mut source, &mut mint_signer], TokenInstruction::MintTo { amount: 10 }).unwrap();
+    assert!(source.token_state().holder_tracked());
+
+    // 只暂停转账：销毁赎回与铸造照常
+    process(&mut [&mut mint, &mut freeze_signer], pause(Some(true), None, None)).unwrap();
+    let result = process(
+        &mut [&mut source, &mut destination, &mut owner_signer, &mut mint],
+        TokenInstruction::Transfer { amount: 1 },
+    );
+    assert_eq!(result, Err(TokenError::OperationPaused.into()));
+    process(&mut [&mut source, &mut mint, &mut owner_signer], TokenInstruction::Burn { amount: 3 }).unwrap();
+    process(&mut [&mut mint, &mut source, &mut mint_signer], TokenInstruction::MintTo { amount: 1 }).unwrap();
+
+    process(&mut [&mut mint, &mut freeze_signer], pause(None, Some(true), Some(true))).unwrap();
+    let result = process(&mut [&mut mint, &mut source, &mut mint_signer], TokenInstruction::MintTo { amount: 1 });
+    assert_eq!(result, Err(TokenError::OperationPaused.into()));
+    let result = process(&mut [&mut source, &mut mint, &mut owner_signer], TokenInstruction::Burn { amount: 1 });
+    assert_eq!(result, Err(TokenError::OperationPaused.into()));
+    let state = extension::get_extension::<Mint, PauseState>(&mint.data).unwrap();
+    assert_eq!(state, Some(PauseState { pause_transfers: true, pause_minting: true, pause_burning: true }));
+
+    let result = process(&mut [&mut mint, &mut owner_signer], pause(Some(false), None, None));
+    assert_eq!(result, Err(TokenError::Unauthorized.into()));
+    process(&mut [&mut mint, &mut freeze_signer], pause(Some(false), None, None)).unwrap();
+    process(
+        &mut [&mut source, &mut destination, &mut owner_signer, &mut mint],
+        TokenInstruction::Transfer { amount: 4 },
+    )
+    .unwrap();
+    assert_eq!(source.token_state().amount, 4);
+    assert_eq!(mint.mint_state().supply, 8);
+}
+
+#[test]
+fn paused_transfers_cover_untracked_balances_and_savings_deposits() {
+    let owner = Pubkey::new_unique();
+    let paused = PauseState { pause_transfers: true, pause_minting: false, pause_burning: false };
+    let mut mint = Fixture::mint(MintFixture::new().supply(20).extension(&paused));
+    let mut source = Fixture::token(TokenAccountFixture::new(mint.key, owner).amount(20));
+    let mut destination = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()));
+    let mut owner_signer = Fixture::signer(owner);
+    let result = process(
+        &mut [&mut source, &mut destination, &mut owner_signer, &mut mint],
+        TokenInstruction::Transfer { amount: 1 },
+    );
+    assert_eq!(result, Err(TokenError::OperationPaused.into()));
+
+    let mut vault = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()));
+    let savings_vault = SavingsVault {
+        is_initialized: true,
+        mint: mint.key,
+        vault: vault.key,
+        yield_authority: Pubkey::new_unique(),
+        total_shares: 0,
+        bump: 255,
+    };
+    let mut savings_vault_account = Fixture::with_data(savings_vault.try_to_vec().unwrap());
+    let position_key = find_savings_position_address(&savings_vault_account.key, &owner, &PROGRAM_ID).0;
+    let mut position = Fixture { key: position_key, ..Fixture::program_owned(0) };
+    let result = process(
+        &mut [
+            &mut savings_vault_account,
+            &mut vault,
+            &mut source,
+            &mut position,
+            &mut owner_signer,
+            &mut Fixture::program_owned(0),
+            &mut mint,
+        ],
+        TokenInstruction::DepositSavings { amount: 5 },
+    );
+    assert_eq!(result, Err(TokenError::OperationPaused.into()));
+    assert_eq!(source.token_state().amount, 20);
+    assert_eq!(vault.token_state().amount, 0);
+}
+
+#[test]
+fn new_account_receives_only_after_owner_activates_it() {
+    let authority = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().mint_authority(authority).space(MINT_WITH_ACTIVATION_POLICY_LEN));
+    let mut mint_signer = Fixture::signer(authority);
+    process(&mut [&mut mint, &mut mint_signer], TokenInstruction::SetActivationRequired { required: true }).unwrap();
+
+    let mut account = Fixture::program_owned(TOKEN_ACCOUNT_WITH_ACTIVATION_LEN);
+    process(
+        &mut [&mut account, &mut mint, &mut Fixture::signer(recipient).not_signer(), &mut Fixture::rent_sysvar()],
+        TokenInstruction::InitializeAccount,
+    )
+    .unwrap();
+    let mut source = Fixture::token(TokenAccountFixture::new(mint.key, owner).amount(10));
+    let mut owner_signer = Fixture::signer(owner);
+    let result = process(
+        &mut [&mut source, &mut account, &mut owner_signer, &mut mint],
+        TokenInstruction::Transfer { amount: 1 },
+    );
+    assert_eq!(result, Err(TokenError::AccountNotActivated.into()));
+    let result = process(&mut [&mut mint, &mut account, &mut mint_signer], TokenInstruction::MintTo { amount: 1 });
+    assert_eq!(result, Err(TokenError::AccountNotActivated.into()));
+    let result = process(
+        &mut [&mut source, &mut account, &mut owner_signer, &mut mint],
+        TokenInstruction::SettleBatch { deltas: vec![-1, 1] },
+    );
+    assert_eq!(result, Err(TokenError::AccountNotActivated.into()));
+
+    let result = process(&mut [&mut account, &mut owner_signer], TokenInstruction::ActivateAccount);
+    assert_eq!(result, Err(TokenError::Unauthorized.into()));
+    process(&mut [&mut account, &mut Fixture::signer(recipient)], TokenInstruction::ActivateAccount).unwrap();
+    let activation = extension::get_extension::<TokenAccount, AccountActivation>(&account.data).unwrap();
+    assert_eq!(activation, Some(AccountActivation { activated: true }));
+
+    process(&mut [&mut source, &mut account, &mut owner_signer, &mut mint], TokenInstruction::Transfer { amount: 4 })
+        .unwrap();
+    assert_eq!(account.token_state().amount, 4);
+    // 没有激活扩展的账户不受限制，ActivateAccount 对其无操作
+    process(&mut [&mut source, &mut owner_signer], TokenInstruction::ActivateAccount).unwrap();
+}
+
+#[test]
+fn delegate_burns_within_allowance() {
+    let owner = Pubkey::new_unique();
+    let delegate = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().supply(100));
+    let mut account = Fixture::token(TokenAccountFixture::new(mint.key, owner).amount(100));
+    let allowance = Allowance { is_initialized: true, token_account: account.key, delegate, amount: 50, nonce: 0 };
+    let mut allowance = Fixture::with_data(allowance.try_to_vec().unwrap());
+    let mut delegate_signer = Fixture::signer(delegate);
+    process(
+        &mut [&mut account, &mut mint, &mut delegate_signer, &mut allowance],
+        TokenInstruction::Burn { amount: 30 },
+    )
+    .unwrap();
+
+    assert_eq!(account.token_state().amount, 70);
+    assert_eq!(mint.mint_state().supply, 70);
+    assert_eq!(Allowance::try_from_slice(&allowance.data).unwrap().amount, 20);
+    let result = process(
+        &mut [&mut account, &mut mint, &mut delegate_signer, &mut allowance],
+        TokenInstruction::Burn { amount: 21 },
+    );
+    assert_eq!(result, Err(TokenError::InsufficientFunds.into()));
+}
+
+#[test]
+fn emptying_account_revokes_remaining_allowance() {
+    let mut mint_account = Fixture::mint(MintFixture::new());
+    let mint = mint_account.key;
+    let delegate = Pubkey::new_unique();
+    let mut source = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()).amount(40));
+    let mut destination = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()));
+    let allowance = Allowance { is_initialized: true, token_account: source.key, delegate, amount: 100, nonce: 3 };
+    let mut allowance = Fixture::with_data(allowance.try_to_vec().unwrap());
+    process(
+        &mut [&mut source, &mut destination, &mut allowance, &mut Fixture::signer(delegate), &mut mint_account],
+        TokenInstruction::TransferFrom { amount: 40 },
+    )
+    .unwrap();
+
+    assert_eq!(source.token_state().amount, 0);
+    let allowance = Allowance::try_from_slice(&allowance.data).unwrap();
+    assert_eq!(allowance.amount, 0);
+    assert_eq!(allowance.nonce, 3);
+}
+
+#[test]
+fn approve_multi_sets_allowances_across_accounts() {
+    let owner = Pubkey::new_unique();
+    let (delegate_a, delegate_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut account_a = Fixture::token(TokenAccountFixture::new(Pubkey::new_unique(), owner));
+    let mut account_b = Fixture::token(TokenAccountFixture::new(Pubkey::new_unique(), owner));
+    let allowance = |account: &Fixture, delegate: Pubkey| {
+        let state = Allowance { is_initialized: true, token_account: account.key, delegate, amount: 5, nonce: 2 };
+        let (key, _) = find_allowance_address(&account.key, &delegate, &PROGRAM_ID);
+        Fixture { key, ..Fixture::with_data(state.try_to_vec().unwrap()) }
+    };
+    let (mut allowance_a, mut allowance_b) = (allowance(&account_a, delegate_a), allowance(&account_b, delegate_b));
+    let mut system_program = Fixture::signer(solana_program::system_program::id()).not_signer();
+    let approvals = vec![(delegate_a, 100), (delegate_b, 0)];
+    let result = process(
+        &mut [
+            &mut Fixture::signer(Pubkey::new_unique()),
+            &mut system_program,
+            &mut account_a,
+            &mut allowance_a,
+        ],
+        TokenInstruction::ApproveMulti { approvals: approvals[..1].to_vec() },
+    );
+    assert_eq!(result, Err(TokenError::Unauthorized.into()));
+
+    process(
+        &mut [
+            &mut Fixture::signer(owner),
+            &mut system_program,
+            &mut account_a,
+            &mut allowance_a,
+            &mut account_b,
+            &mut allowance_b,
+        ],
+        TokenInstruction::ApproveMulti { approvals },
+    )
+    .unwrap();
+
+    let allowance_a = Allowance::try_from_slice(&allowance_a.data).unwrap();
+    assert_eq!((allowance_a.amount, allowance_a.nonce), (100, 2));
+    assert_eq!(Allowance::try_from_slice(&allowance_b.data).unwrap().amount, 0);
+}
+
+#[test]
+fn set_mint_authority_replaces_authority() {
+    let authority = Pubkey::new_unique();
+    let new_authority = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().decimals(6).mint_authority(authority).supply(0));
+    process(
+        &mut [&mut mint, &mut Fixture::signer(authority)],
+        TokenInstruction::SetMintAuthority { new_authority: Some(new_authority) },
+    )
+    .unwrap();
+
+    assert_eq!(mint.mint_state().mint_authority, Some(new_authority));
+}
+
+#[test]
+fn set_mint_authority_rejects_non_authority() {
+    let authority = Pubkey::new_unique();
+    let attacker = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().mint_authority(authority));
+    let result = process(
+        &mut [&mut mint, &mut Fixture::signer(attacker)],
+        TokenInstruction::SetMintAuthority { new_authority: Some(attacker) },
+    );
+
+    assert_eq!(result, Err(TokenError::Unauthorized.into()));
+    assert_eq!(mint.mint_state().mint_authority, Some(authority));
+}
+
+#[test]
+fn burn_authority_burns_from_any_account() {
+    let mint_authority = Pubkey::new_unique();
+    let burner = Pubkey::new_unique();
+    let mut mint = Fixture::mint(
+        MintFixture::new().mint_authority(mint_authority).supply(100).space(MINT_WITH_BURN_AUTHORITY_LEN),
+    );
+    let mut account = Fixture::token(TokenAccountFixture::new(mint.key, Pubkey::new_unique()).amount(100));
+    let mut burner_signer = Fixture::signer(burner);
+    let result = process(&mut [&mut account, &mut mint, &mut burner_signer], TokenInstruction::Burn { amount: 10 });
+    assert!(result.is_err());
+
+    process(
+        &mut [&mut mint, &mut Fixture::signer(mint_authority)],
+        TokenInstruction::SetAuthority { authority_type: AuthorityType::BurnTokens, new_authority: Some(burner) },
+    )
+    .unwrap();
+    process(&mut [&mut account, &mut mint, &mut burner_signer], TokenInstruction::Burn { amount: 10 }).unwrap();
+
+    assert_eq!(account.token_state().amount, 90);
+    assert_eq!(mint.mint_state().supply, 90);
+    // 设置后只能由销毁权限本身变更
+    let result = process(
+        &mut [&mut mint, &mut Fixture::signer(mint_authority)],
+        TokenInstruction::SetAuthority { authority_type: AuthorityType::BurnTokens, new_authority: None },
+    );
+    assert_eq!(result, Err(TokenError::Unauthorized.into()));
+
+    // 撤销后铸币权限不能重新设置销毁权限
+    process(
+        &mut [&mut mint, &mut Fixture::signer(burner)],
+        TokenInstruction::SetAuthority { authority_type: AuthorityType::BurnTokens, new_authority: None },
+    )
+    .unwrap();
+    let reinstall = TokenInstruction::SetAuthority {
+        authority_type: AuthorityType::BurnTokens,
+        new_authority: Some(mint_authority),
+    };
+    let result = process(&mut [&mut mint, &mut Fixture::signer(mint_authority)], reinstall);
+    assert_eq!(result, Err(TokenError::Unauthorized.into()));
+    let result = process(&mut [&mut account, &mut mint, &mut burner_signer], TokenInstruction::Burn { amount: 10 });
+    assert!(result.is_err());
+    assert_eq!(account.token_state().amount, 90);
+}
+
+#[test]
+fn arbiter_releases_conditional_transfer() {
+    let mut mint_account = Fixture::mint(MintFixture::new());
+    let mint = mint_account.key;
+    let (sender, arbiter) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut escrow = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()).amount(25));
+    let refund = Fixture::token(TokenAccountFixture::new(mint, sender));
+    let mut recipient = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()));
+    let conditional = ConditionalTransfer {
+        is_initialized: true,
+        sender,
+        escrow: escrow.key,
+        refund: refund.key,
+        recipient: recipient.key,
+        mint,
+        arbiter,
+        amount: 25,
+    };
+    let mut conditional = Fixture::with_data(conditional.try_to_vec().unwrap());
+    let mut sender_account = Fixture::signer(sender).not_signer();
+    let result = process(
+        &mut [
+            &mut conditional,
+            &mut escrow,
+            &mut recipient,
+            &mut sender_account,
+            &mut Fixture::signer(sender),
+            &mut mint_account,
+        ],
+        TokenInstruction::ReleaseConditionalTransfer,
+    );
+    assert_eq!(result, Err(TokenError::Unauthorized.into()));
+    let result = process(
+        &mut [
+            &mut conditional,
+            &mut escrow,
+            &mut recipient,
+            &mut sender_account,
+            &mut Fixture::signer(arbiter),
+            &mut mint_account,
+        ],
+        TokenInstruction::RefundConditionalTransfer,
+    );
+    assert_eq!(result, Err(ProgramError::InvalidAccountData));
+
+    process(
+        &mut [
+            &mut conditional,
+            &mut escrow,
+            &mut recipient,
+            &mut sender_account,
+            &mut Fixture::signer(arbiter),
+            &mut mint_account,
+        ],
+        TokenInstruction::ReleaseConditionalTransfer,
+    )
+    .unwrap();
+
+    assert_eq!(escrow.token_state().amount, 0);
+    assert_eq!(recipient.token_state().amount, 25);
+    assert_eq!(refund.token_state().amount, 0);
+    assert_eq!(conditional.lamports, 0);
+}
+
+#[test]
+fn closed_mint_is_tombstoned() {
+    let destination = Pubkey::new_unique();
+    let mut mint = Fixture::mint(MintFixture::new().extension(&MintCloseConfig { close_destination: destination }));
+    let mut destination_account = Fixture::signer(destination).not_signer();
+    process(&mut [&mut mint, &mut destination_account], TokenInstruction::CloseEmptyMint).unwrap();
+
+    assert_eq!(mint.lamports, 0);
+    assert_eq!(mint.data[0], spl_token_program::CLOSED_ACCOUNT_DISCRIMINATOR);
+    assert!(mint.data[1..].iter().all(|&byte| byte == 0));
+    assert_eq!(Mint::deserialize(&mint.data).unwrap_err(), ProgramError::InvalidAccountData);
+}
+
+#[test]
+fn settle_batch_applies_zero_sum_deltas() {
+    let mut mint_account = Fixture::mint(MintFixture::new());
+    let mint = mint_account.key;
+    let payer = Pubkey::new_unique();
+    let mut debited = Fixture::token(TokenAccountFixture::new(mint, payer).amount(100));
+    let mut first = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()).amount(0));
+    let mut second = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()).amount(10));
+    process(
+        &mut [&mut debited, &mut first, &mut second, &mut Fixture::signer(payer), &mut mint_account],
+        TokenInstruction::SettleBatch { deltas: vec![-70, 50, 20] },
+    )
+    .unwrap();
+
+    assert_eq!(debited.token_state().amount, 30);
+    assert_eq!(first.token_state().amount, 50);
+    assert_eq!(second.token_state().amount, 30);
+}
+
+#[test]
+fn settle_batch_rejects_non_zero_net() {
+    let mut mint_account = Fixture::mint(MintFixture::new());
+    let mint = mint_account.key;
+    let payer = Pubkey::new_unique();
+    let mut debited = Fixture::token(TokenAccountFixture::new(mint, payer).amount(100));
+    let mut credited = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()).amount(0));
+    let result = process(
+        &mut [&mut debited, &mut credited, &mut Fixture::signer(payer), &mut mint_account],
+        TokenInstruction::SettleBatch { deltas: vec![-10, 11] },
+    );
+
+    assert_eq!(result, Err(ProgramError::InvalidArgument));
+    assert_eq!(credited.token_state().amount, 0);
+}
+
+#[test]
+fn transfer_rejects_trailing_instruction_bytes() {
+    let mint = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let mut source = Fixture::token(TokenAccountFixture::new(mint, owner).amount(10));
+    let mut destination = Fixture::token(TokenAccountFixture::new(mint, Pubkey::new_unique()).amount(0));
+    let mut data = TokenInstruction::Transfer { amount: 1 }.try_to_vec().unwrap();
+    data.push(0);
+
+    let result = process_data(&mut [&mut source, &mut destination, &mut Fixture::signer(owner)], &data);
+
+    assert_eq!(result, Err(TokenError::InvalidInstructionData.into()));
+    assert_eq!(source.token_state().amount, 10);
+}
+
+#[test]
+fn unpack_rejects_data_over_instruction_max_len() {
+    let instruction = TokenInstruction::CreateMetadata {
+        args: MetadataArgs {
+            name: "n".repeat(MAX_METADATA_NAME_LEN),
+            symbol: "s".repeat(MAX_METADATA_SYMBOL_LEN),
+            uri: "u".repeat(MAX_METADATA_URI_LEN + 1),
+            seller_fee_basis_points: 0,
+        },
+        is_mutable: true,
+    };
+    let data = instruction.try_to_vec().unwrap();
+
+    assert_eq!(data.len(), instruction.max_data_len() + 1);
+    assert_eq!(TokenInstruction::unpack(&data).unwrap_err(), TokenError::InvalidInstructionData.into());
+}
+
+#[test]
+fn ui_amount_round_trips() {
+    assert_eq!(math::to_ui_amount(1_500_000, 6), "1.5");
+    assert_eq!(math::to_ui_amount(42, 6), "0.000042");
+    assert_eq!(math::to_ui_amount(7, 0), "7");
+    assert_eq!(math::from_ui_amount("1.5", 6), Ok(1_500_000));
+    assert_eq!(math::from_ui_amount(".000042", 6), Ok(42));
+    assert_eq!(math::from_ui_amount("2.500", 1), Ok(25));
+    assert_eq!(math::from_ui_amount("0", 30), Ok(0));
+}
+
+#[test]
+fn from_ui_amount_rejects_precision_loss_and_overflow() {
+    assert_eq!(math::from_ui_amount("1.05", 1), Err(ProgramError::InvalidArgument));
+    assert_eq!(math::from_ui_amount("1.2.3", 2), Err(ProgramError::InvalidArgument));
+    assert_eq!(math::from_ui_amount("-1", 2), Err(ProgramError::InvalidArgument));
+    assert_eq!(math::from_ui_amount(".", 2), Err(ProgramError::InvalidArgument));
+    assert_eq!(math::from_ui_amount("18446744073709551616", 0), Err(TokenError::Overflow.into()));
+    assert_eq!(math::from_ui_amount("1", 20), Err(TokenError::Overflow.into()));
+}
+
+#[test]
+fn manual_pack_matches_borsh_layout() {
+    let authority = Pubkey::new_unique();
+    for mint in [
+        MintFixture::new().decimals(6).supply(1_000).mint_authority(authority).freeze_authority(authority).state(),
+        MintFixture::new().supply(1).nft().state(),
+    ] {
+        let packed = mint.pack_to_vec().unwrap();
+        assert_eq!(packed, borsh::to_vec(&mint).unwrap());
+        assert_eq!(Mint::deserialize(&packed).unwrap().supply, mint.supply);
+    }
+    for account in [
+        TokenAccountFixture::new(Pubkey::new_unique(), authority).amount(7).frozen().state(),
+        TokenAccountFixture::new(Pubkey::new_unique(), authority).vote_delegate(authority).state(),
+    ] {
+        let packed = account.pack_to_vec().unwrap();
+        assert_eq!(packed, borsh::to_vec(&account).unwrap());
+        assert_eq!(TokenAccount::deserialize(&packed).unwrap().vote_delegate, account.vote_delegate);
+    }
+}
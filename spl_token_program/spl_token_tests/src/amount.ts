@@ -0,0 +1,41 @@
+/**
+ * 金额换算：最小单位与带小数的 UI 金额之间的转换，规则与 Rust 端 math 模块一致
+ *
+ * UI 金额去掉末尾多余的 0（1500000、6 位精度显示为 "1.5"）；
+ * 解析时小数位超过精度且不全为 0 会丢失精度，直接报错而不是静默截断。
+ */
+
+const U64_MAX = (BigInt(1) << BigInt(64)) - BigInt(1);
+
+/**
+ * 最小单位金额转为 UI 金额字符串，去掉小数部分末尾的 0
+ */
+export function toUiAmount(amount: bigint, decimals: number): string {
+  const digits = amount.toString();
+  if (decimals === 0) {
+    return digits;
+  }
+  const padded = digits.padStart(decimals + 1, '0');
+  const whole = padded.slice(0, padded.length - decimals);
+  const fraction = padded.slice(padded.length - decimals).replace(/0+$/, '');
+  return fraction ? `${whole}.${fraction}` : whole;
+}
+
+/**
+ * UI 金额字符串转为最小单位，格式非法、超出精度的非零小数位或超出 u64 时报错
+ */
+export function fromUiAmount(uiAmount: string, decimals: number): bigint {
+  const match = /^(\d*)(?:\.(\d*))?$/.exec(uiAmount);
+  if (!match || (match[1] === '' && !match[2])) {
+    throw new Error(`无效的金额: ${uiAmount}`);
+  }
+  const fraction = match[2] ?? '';
+  if (/[^0]/.test(fraction.slice(decimals))) {
+    throw new Error(`金额 ${uiAmount} 超过铸币精度 ${decimals} 位小数`);
+  }
+  const amount = BigInt((match[1] || '0') + fraction.slice(0, decimals).padEnd(decimals, '0'));
+  if (amount > U64_MAX) {
+    throw new Error(`金额 ${uiAmount} 超出 u64 范围`);
+  }
+  return amount;
+}
@@ -0,0 +1,140 @@
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  Transaction,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+  sendAndConfirmTransaction
+} from '@solana/web3.js';
+import { homedir } from 'os';
+import { join } from 'path';
+import { bootstrap, loadKeypair, parseArgs, resolveUrl } from './cli';
+import {
+  createBurnInstruction,
+  createMintToInstruction,
+  createMintToNewAccountInstruction,
+  createTransferInstruction,
+  findAssociatedTokenAddress
+} from './utils';
+
+/**
+ * 链上计算单元对比：对同一组指令分别模拟执行多个已部署的程序，输出各自消耗的计算单元
+ *
+ * 用于验证手写 pack 相对 Borsh 的收益：在改动前后的提交上分别 cargo build-sbf 并部署，
+ * 再运行 npm run bench-cu -- <旧程序 ID> <新程序 ID> [--url localhost] [--keypair 路径]。
+ * 每个程序先用 bootstrap 建好铸币与两个代币账户，指令只模拟不落地，结果不受执行顺序影响。
+ */
+
+export interface CuRow {
+  instruction: string;
+  units: (number | null)[];
+}
+
+const DECIMALS = 6;
+
+/**
+ * 模拟交易并返回消耗的计算单元，模拟失败时报错并带上程序日志
+ */
+async function simulateUnits(
+  connection: Connection,
+  payer: PublicKey,
+  instruction: TransactionInstruction
+): Promise<number> {
+  const { blockhash } = await connection.getLatestBlockhash();
+  const transaction = new VersionedTransaction(
+    new TransactionMessage({ payerKey: payer, recentBlockhash: blockhash, instructions: [instruction] }).compileToV0Message()
+  );
+  const { value } = await connection.simulateTransaction(transaction, { sigVerify: false, replaceRecentBlockhash: true });
+  if (value.err) {
+    throw new Error(`模拟失败: ${JSON.stringify(value.err)}\n${(value.logs ?? []).join('\n')}`);
+  }
+  return value.unitsConsumed ?? 0;
+}
+
+/**
+ * 在一个程序上准备账户并测量各指令的计算单元
+ */
+async function measureProgram(
+  connection: Connection,
+  wallet: Keypair,
+  programId: PublicKey
+): Promise<Map<string, number>> {
+  const { mint, tokenAccount } = await bootstrap(connection, wallet, programId, '1000', DECIMALS, 2);
+  const receiver = Keypair.generate();
+  await sendAndConfirmTransaction(
+    connection,
+    new Transaction().add(
+      createMintToNewAccountInstruction(mint, receiver.publicKey, wallet.publicKey, wallet.publicKey, BigInt(1), programId)
+    ),
+    [wallet]
+  );
+  const [receiverAccount] = findAssociatedTokenAddress(receiver.publicKey, mint, programId);
+
+  const instructions: [string, TransactionInstruction][] = [
+    ['MintTo', createMintToInstruction(mint, tokenAccount, wallet.publicKey, BigInt(1), programId)],
+    [
+      'Transfer',
+      createTransferInstruction(tokenAccount, receiverAccount, wallet.publicKey, BigInt(1), mint, programId),
+    ],
+    ['Burn', createBurnInstruction(tokenAccount, mint, wallet.publicKey, BigInt(1), programId)],
+  ];
+  const units = new Map<string, number>();
+  for (const [name, instruction] of instructions) {
+    units.set(name, await simulateUnits(connection, wallet.publicKey, instruction));
+  }
+  return units;
+}
+
+/**
+ * 输出对比表，第一个程序为基准，其余列附带相对基准的变化
+ */
+export function formatCuTable(programIds: PublicKey[], rows: CuRow[]): string {
+  const header = ['Instruction', ...programIds.map(id => id.toBase58().slice(0, 8))];
+  const lines = rows.map(row => {
+    const base = row.units[0];
+    return [
+      row.instruction,
+      ...row.units.map((units, i) => {
+        if (units === null) {
+          return '-';
+        }
+        if (i === 0 || !base) {
+          return `${units}`;
+        }
+        const delta = ((units - base) / base) * 100;
+        return `${units} (${delta >= 0 ? '+' : ''}${delta.toFixed(1)}%)`;
+      }),
+    ];
+  });
+  return [header, ...lines].map(columns => columns.map(column => column.padEnd(20)).join('')).join('\n');
+}
+
+export async function main(argv: string[]): Promise<void> {
+  const args = parseArgs(argv);
+  if (args.positional.length === 0) {
+    throw new Error('用法: bench-cu <程序 ID>... [--url <RPC>] [--keypair <路径>]');
+  }
+  const connection = new Connection(resolveUrl(args.options.get('url')?.[0]), 'confirmed');
+  const wallet = loadKeypair(args.options.get('keypair')?.[0] ?? join(homedir(), '.config', 'solana', 'id.json'));
+  const programIds = args.positional.map(id => new PublicKey(id));
+
+  const results: Map<string, number>[] = [];
+  for (const programId of programIds) {
+    results.push(await measureProgram(connection, wallet, programId));
+  }
+  const names = [...new Set(results.flatMap(result => [...result.keys()]))];
+  const rows = names.map(instruction => ({
+    instruction,
+    units: results.map(result => result.get(instruction) ?? null),
+  }));
+  console.log(formatCuTable(programIds, rows));
+}
+
+if (require.main === module) {
+  main(process.argv.slice(2)).catch(error => {
+    console.error(error instanceof Error ? error.message : error);
+    process.exit(1);
+  });
+}
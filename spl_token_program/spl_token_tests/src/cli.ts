@@ -0,0 +1,352 @@
+import {
+  Connection,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  clusterApiUrl,
+  sendAndConfirmTransaction
+} from '@solana/web3.js';
+import { readFileSync } from 'fs';
+import { homedir } from 'os';
+import { join } from 'path';
+import {
+  MessageLifetime,
+  attachSignatures,
+  broadcastSignedTransaction,
+  buildUnsignedMessage,
+  fetchNonceLifetime,
+  requiredSigners,
+  signMessage
+} from './offline';
+import { fromUiAmount, toUiAmount } from './amount';
+import {
+  createInitializeMintInstruction,
+  createMintToInstruction,
+  createMintToNewAccountInstruction,
+  createSetMintAuthorityInstruction,
+  findAssociatedTokenAddress,
+  requestAirdrop,
+  checkProgramId,
+  TOKEN_PROGRAM_ID
+} from './utils';
+
+/**
+ * token-cli：本程序的命令行工具（npm run cli -- <命令> ...）
+ *
+ * 多签/离线签名沿用 solana-cli 的约定：各参与方用相同的参数与 --blockhash 运行同一命令并加上 --sign-only，
+ * 只签名不发送，输出 Pubkey=Signature；最后一方用 --signer 带上其他人的签名运行，组装后广播。
+ * 使用持久 nonce 时加 --nonce <账户>，--blockhash 传 nonce 账户中当前的 nonce 值。
+ */
+
+const USAGE = `用法: token-cli <命令> [参数] [选项]
+
+命令:
+  bootstrap [SUPPLY]                       开发环境一键初始化：空投 SOL、创建铸币、创建关联账户并铸造 SUPPLY（默认 1000）
+  mint-to <MINT> <ACCOUNT> <AMOUNT>        铸造 AMOUNT（最小单位）到 ACCOUNT，--mint-authority 为铸币权限
+  set-authority <MINT> <NEW_AUTHORITY|none> 变更铸币权限，--authority 为当前铸币权限
+
+选项:
+  --url <URL|devnet|testnet|mainnet-beta|localhost>  RPC 地址或集群名（默认取配置文件，否则 localhost）
+  --program-id <PUBKEY>          程序 ID（默认取环境变量 TOKEN_PROGRAM_ID，都未指定时为规范部署）
+  --allow-program-id-override    允许 --program-id 不是规范部署
+  --config <PATH>                部署配置文件（默认取环境变量 TOKEN_CLI_CONFIG），提供 url / programId 的默认值
+  --keypair <PATH>               本地签名者的密钥文件，可重复（默认 ~/.config/solana/id.json）
+  --fee-payer <PUBKEY>           手续费支付者（默认第一个 --keypair）
+  --mint-authority <PUBKEY>      mint-to 的铸币权限（默认手续费支付者）
+  --authority <PUBKEY>           set-authority 的当前铸币权限（默认手续费支付者）
+  --blockhash <HASH>             使用指定的区块哈希（或 nonce 值），--sign-only 时必须提供
+  --nonce <PUBKEY>               使用持久 nonce 账户
+  --nonce-authority <PUBKEY>     nonce 权限（默认手续费支付者）
+  --sign-only                    只签名不发送，输出签名
+  --signer <PUBKEY=SIGNATURE>    附加其他参与方的签名，可重复
+  --decimals <N>                 bootstrap 创建的铸币精度（默认 9）
+  --airdrop <SOL>                bootstrap 余额不足该数量时请求空投（默认 2）`;
+
+/** 铸币账户大小，与 Rust 中的 Mint::LEN 一致 */
+const MINT_SIZE = 77;
+
+/** 可重复出现的选项 */
+const REPEATED = new Set(['keypair', 'signer']);
+/** 不带值的开关 */
+const SWITCHES = new Set(['sign-only', 'help', 'allow-program-id-override']);
+
+export interface ParsedArgs {
+  positional: string[];
+  options: Map<string, string[]>;
+}
+
+/**
+ * 解析命令行参数：--name value / --name=value，开关不带值
+ */
+export function parseArgs(argv: string[]): ParsedArgs {
+  const positional: string[] = [];
+  const options = new Map<string, string[]>();
+  for (let i = 0; i < argv.length; i++) {
+    const arg = argv[i];
+    if (!arg.startsWith('--')) {
+      positional.push(arg);
+      continue;
+    }
+    const [name, inline] = arg.slice(2).split(/=(.*)/s, 2);
+    let value: string;
+    if (SWITCHES.has(name)) {
+      value = 'true';
+    } else if (inline !== undefined) {
+      value = inline;
+    } else if (i + 1 < argv.length) {
+      value = argv[++i];
+    } else {
+      throw new Error(`选项 --${name} 缺少值`);
+    }
+    const values = options.get(name) ?? [];
+    if (values.length > 0 && !REPEATED.has(name)) {
+      throw new Error(`选项 --${name} 只能出现一次`);
+    }
+    options.set(name, [...values, value]);
+  }
+  return { positional, options };
+}
+
+function option(args: ParsedArgs, name: string): string | undefined {
+  return args.options.get(name)?.[0];
+}
+
+/**
+ * 与 solana-cli 一样接受集群名作为 --url
+ */
+export function resolveUrl(url = 'localhost'): string {
+  switch (url) {
+    case 'localhost':
+      return 'http://localhost:8899';
+    case 'devnet':
+    case 'testnet':
+    case 'mainnet-beta':
+      return clusterApiUrl(url);
+    default:
+      return url;
+  }
+}
+
+function expandHome(path: string): string {
+  return path.startsWith('~') ? join(homedir(), path.slice(1)) : path;
+}
+
+/**
+ * 读取 solana-cli 格式的密钥文件（64 字节的 JSON 数组）
+ */
+export function loadKeypair(path: string): Keypair {
+  return Keypair.fromSecretKey(Uint8Array.from(JSON.parse(readFileSync(expandHome(path), 'utf8'))));
+}
+
+/**
+ * 部署配置文件：每个环境（devnet / testnet / mainnet-beta 上的各个部署）一份，同一套工具按文件切换目标，例如
+ * { "url": "devnet", "programId": "<PUBKEY>", "allowProgramIdOverride": true }
+ */
+export interface CliConfig {
+  url?: string;
+  programId?: string;
+  /** programId 不是规范部署时必须为 true，与命令行的 --allow-program-id-override 等价 */
+  allowProgramIdOverride?: boolean;
+}
+
+/**
+ * 读取部署配置文件，未指定路径时返回空配置
+ */
+export function loadConfig(path: string | undefined): CliConfig {
+  if (!path) {
+    return {};
+  }
+  return JSON.parse(readFileSync(expandHome(path), 'utf8')) as CliConfig;
+}
+
+/**
+ * 按 --program-id、环境变量 TOKEN_PROGRAM_ID、配置文件的顺序取程序 ID，都未指定时为规范部署；
+ * 非规范部署需 --allow-program-id-override 或配置文件中的 allowProgramIdOverride
+ */
+export function resolveProgramId(args: ParsedArgs, config: CliConfig): PublicKey {
+  const programId = option(args, 'program-id') ?? process.env.TOKEN_PROGRAM_ID ?? config.programId;
+  if (!programId) {
+    return TOKEN_PROGRAM_ID;
+  }
+  const allowOverride = args.options.has('allow-program-id-override') || config.allowProgramIdOverride === true;
+  return checkProgramId(new PublicKey(programId), allowOverride);
+}
+
+/**
+ * 解析 --signer PUBKEY=SIGNATURE
+ */
+export function parseSignerArg(value: string): { publicKey: string; signature: string } {
+  const [publicKey, signature] = value.split('=', 2);
+  if (!publicKey || !signature) {
+    throw new Error(`--signer 格式应为 PUBKEY=SIGNATURE: ${value}`);
+  }
+  return { publicKey: new PublicKey(publicKey).toBase58(), signature };
+}
+
+/**
+ * 按命令构造指令
+ */
+function buildInstructions(args: ParsedArgs, feePayer: PublicKey, programId: PublicKey): TransactionInstruction[] {
+  const [command, ...rest] = args.positional;
+  switch (command) {
+    case 'mint-to': {
+      if (rest.length !== 3) {
+        throw new Error('用法: mint-to <MINT> <ACCOUNT> <AMOUNT>');
+      }
+      const authority = new PublicKey(option(args, 'mint-authority') ?? feePayer);
+      return [createMintToInstruction(new PublicKey(rest[0]), new PublicKey(rest[1]), authority, BigInt(rest[2]), programId)];
+    }
+    case 'set-authority': {
+      if (rest.length !== 2) {
+        throw new Error('用法: set-authority <MINT> <NEW_AUTHORITY|none>');
+      }
+      const authority = new PublicKey(option(args, 'authority') ?? feePayer);
+      const newAuthority = rest[1] === 'none' ? null : new PublicKey(rest[1]);
+      return [createSetMintAuthorityInstruction(new PublicKey(rest[0]), authority, newAuthority, programId)];
+    }
+    default:
+      throw new Error(`未知命令: ${command ?? ''}\n\n${USAGE}`);
+  }
+}
+
+/**
+ * bootstrap：钱包余额不足时空投，创建铸币（钱包为铸币与冻结权限），
+ * 再用 MintToNewAccount 一次完成关联账户的创建与初始供应量的铸造
+ */
+export async function bootstrap(
+  connection: Connection,
+  wallet: Keypair,
+  programId: PublicKey,
+  supply: string,
+  decimals: number,
+  airdropSol: number
+): Promise<{ mint: PublicKey; tokenAccount: PublicKey; amount: bigint }> {
+  const amount = fromUiAmount(supply, decimals);
+  if ((await connection.getBalance(wallet.publicKey)) < airdropSol * LAMPORTS_PER_SOL) {
+    await requestAirdrop(connection, wallet.publicKey, airdropSol);
+  }
+
+  const mint = Keypair.generate();
+  const [tokenAccount] = findAssociatedTokenAddress(wallet.publicKey, mint.publicKey, programId);
+  const transaction = new Transaction().add(
+    SystemProgram.createAccount({
+      fromPubkey: wallet.publicKey,
+      newAccountPubkey: mint.publicKey,
+      lamports: await connection.getMinimumBalanceForRentExemption(MINT_SIZE),
+      space: MINT_SIZE,
+      programId,
+    }),
+    createInitializeMintInstruction(mint.publicKey, decimals, wallet.publicKey, wallet.publicKey, programId),
+    createMintToNewAccountInstruction(mint.publicKey, wallet.publicKey, wallet.publicKey, wallet.publicKey, amount, programId)
+  );
+  await sendAndConfirmTransaction(connection, transaction, [wallet, mint]);
+  return { mint: mint.publicKey, tokenAccount, amount };
+}
+
+/**
+ * 确定消息有效期：--nonce 优先（--blockhash 作为 nonce 值），否则 --blockhash，联网时缺省取最新区块哈希
+ */
+async function resolveLifetime(
+  connection: Connection,
+  args: ParsedArgs,
+  feePayer: PublicKey
+): Promise<MessageLifetime> {
+  const blockhash = option(args, 'blockhash');
+  const nonce = option(args, 'nonce');
+  const signOnly = args.options.has('sign-only');
+  if (signOnly && !blockhash) {
+    throw new Error('--sign-only 需要 --blockhash（使用 --nonce 时为 nonce 值）');
+  }
+  if (nonce) {
+    const nonceAccount = new PublicKey(nonce);
+    if (blockhash) {
+      const nonceAuthority = new PublicKey(option(args, 'nonce-authority') ?? feePayer);
+      return { nonceAccount, nonceAuthority, nonceValue: blockhash };
+    }
+    return fetchNonceLifetime(connection, nonceAccount);
+  }
+  return { blockhash: blockhash ?? (await connection.getLatestBlockhash()).blockhash };
+}
+
+/**
+ * 按 solana-cli 的格式输出 --sign-only 的结果
+ */
+export function formatSignOnly(
+  message: string,
+  blockhash: string,
+  signatures: { publicKey: string; signature: string }[]
+): string {
+  const signed = new Set(signatures.map(entry => entry.publicKey));
+  const absent = requiredSigners(message).filter(key => !signed.has(key.toBase58()));
+  const lines = [`Blockhash: ${blockhash}`];
+  if (signatures.length > 0) {
+    lines.push('Signers (Pubkey=Signature):', ...signatures.map(entry => `  ${entry.publicKey}=${entry.signature}`));
+  }
+  if (absent.length > 0) {
+    lines.push('Absent Signers (Pubkey):', ...absent.map(key => `  ${key.toBase58()}`));
+  }
+  return lines.join('\n');
+}
+
+export async function main(argv: string[]): Promise<void> {
+  const args = parseArgs(argv);
+  if (args.positional.length === 0 || args.options.has('help')) {
+    console.log(USAGE);
+    return;
+  }
+  const config = loadConfig(option(args, 'config') ?? process.env.TOKEN_CLI_CONFIG);
+  const connection = new Connection(resolveUrl(option(args, 'url') ?? config.url), 'confirmed');
+  const programId = resolveProgramId(args, config);
+
+  const keypairPaths = args.options.get('keypair') ?? [join(homedir(), '.config', 'solana', 'id.json')];
+  const keypairs = keypairPaths.map(loadKeypair);
+  const feePayer = new PublicKey(option(args, 'fee-payer') ?? keypairs[0].publicKey);
+
+  if (args.positional[0] === 'bootstrap') {
+    const decimals = Number(option(args, 'decimals') ?? 9);
+    const result = await bootstrap(
+      connection,
+      keypairs[0],
+      programId,
+      args.positional[1] ?? '1000',
+      decimals,
+      Number(option(args, 'airdrop') ?? 2)
+    );
+    console.log(`Mint: ${result.mint.toBase58()}`);
+    console.log(`Token account: ${result.tokenAccount.toBase58()}`);
+    console.log(`Minted: ${toUiAmount(result.amount, decimals)}`);
+    return;
+  }
+
+  const instructions = buildInstructions(args, feePayer, programId);
+  const lifetime = await resolveLifetime(connection, args, feePayer);
+  const message = buildUnsignedMessage(feePayer, instructions, lifetime);
+
+  // 本地密钥只为消息实际需要的签名者签名，--signer 提供的签名放在后面
+  const signers = requiredSigners(message);
+  const signatures = [
+    ...keypairs
+      .filter(keypair => signers.some(key => key.equals(keypair.publicKey)))
+      .map(keypair => signMessage(message, keypair)),
+    ...(args.options.get('signer') ?? []).map(parseSignerArg),
+  ];
+
+  if (args.options.has('sign-only')) {
+    const blockhash = 'nonceAccount' in lifetime ? lifetime.nonceValue : lifetime.blockhash;
+    console.log(formatSignOnly(message, blockhash, signatures));
+    return;
+  }
+  const transaction = attachSignatures(message, signatures);
+  const signature = await broadcastSignedTransaction(connection, transaction);
+  console.log(`Signature: ${signature}`);
+}
+
+if (require.main === module) {
+  main(process.argv.slice(2)).catch(error => {
+    console.error(error instanceof Error ? error.message : error);
+    process.exit(1);
+  });
+}
@@ -0,0 +1,163 @@
+import {
+  Connection,
+  PublicKey,
+  SystemProgram,
+  TransactionInstruction
+} from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { TokenInstruction } from './types';
+import { createInitializeAccountInstruction } from './utils';
+
+/**
+ * 交易所充值地址辅助函数
+ *
+ * 每个 (交易所钱包, 用户 id, 铸币) 对应一个确定的代币账户：地址由 createWithSeed(交易所钱包, seed, 本程序) 推导，
+ * 不需要在链下保存地址表，任何时候都能从用户 id 重新算出；账户所有者为交易所钱包，便于之后用 SweepAll 归集。
+ */
+
+/** 代币账户大小，与 Rust 中的 TokenAccount::LEN 一致 */
+const TOKEN_ACCOUNT_SIZE = 106;
+
+/**
+ * 充值账户的 seed：sha256("deposit" | 铸币 | 用户 id) 的前 16 字节十六进制（createWithSeed 的 seed 最长 32 字符）
+ */
+export function depositSeed(userId: string, mint: PublicKey): string {
+  return createHash('sha256')
+    .update('deposit')
+    .update(mint.toBuffer())
+    .update(userId, 'utf8')
+    .digest('hex')
+    .slice(0, 32);
+}
+
+/**
+ * 推导用户在某铸币下的充值地址
+ */
+export async function findDepositAddress(
+  exchangeWallet: PublicKey,
+  userId: string,
+  mint: PublicKey,
+  programId: PublicKey
+): Promise<PublicKey> {
+  return PublicKey.createWithSeed(exchangeWallet, depositSeed(userId, mint), programId);
+}
+
+/**
+ * 创建用户充值账户的指令：[createAccountWithSeed, InitializeAccount]
+ * 交易所钱包作为 seed 基础账户必须签名；payer 支付租金
+ */
+export async function createDepositAccountInstructions(
+  connection: Connection,
+  payer: PublicKey,
+  exchangeWallet: PublicKey,
+  userId: string,
+  mint: PublicKey,
+  programId: PublicKey
+): Promise<{ depositAddress: PublicKey; instructions: TransactionInstruction[] }> {
+  const seed = depositSeed(userId, mint);
+  const depositAddress = await PublicKey.createWithSeed(exchangeWallet, seed, programId);
+
+  const instructions = [
+    SystemProgram.createAccountWithSeed({
+      fromPubkey: payer,
+      newAccountPubkey: depositAddress,
+      basePubkey: exchangeWallet,
+      seed,
+      lamports: await connection.getMinimumBalanceForRentExemption(TOKEN_ACCOUNT_SIZE),
+      space: TOKEN_ACCOUNT_SIZE,
+      programId,
+    }),
+    createInitializeAccountInstruction(depositAddress, mint, exchangeWallet, programId),
+  ];
+  return { depositAddress, instructions };
+}
+
+/**
+ * 为一批用户建立 充值地址 -> 用户 id 的索引，供扫描时反查
+ */
+export async function buildDepositIndex(
+  exchangeWallet: PublicKey,
+  userIds: string[],
+  mint: PublicKey,
+  programId: PublicKey
+): Promise<Map<string, string>> {
+  const index = new Map<string, string>();
+  for (const userId of userIds) {
+    const address = await findDepositAddress(exchangeWallet, userId, mint, programId);
+    index.set(address.toBase58(), userId);
+  }
+  return index;
+}
+
+/**
+ * 匹配到用户的一笔入账
+ */
+export interface DepositMatch {
+  userId: string;
+  depositAddress: PublicKey;
+  source: PublicKey;
+  amount: bigint;
+  signature: string;
+  slot: number;
+}
+
+/**
+ * 判断一条本程序指令是否为转入某个充值地址的转账（Transfer / TransferWithMemo，目标均为第 2 个账户），
+ * 是则返回 { userId, 充值地址, 源账户, 金额 }
+ */
+export function matchDepositInstruction(
+  keys: PublicKey[],
+  data: Buffer,
+  index: Map<string, string>
+): Omit<DepositMatch, 'signature' | 'slot'> | null {
+  const tag = data[0];
+  if ((tag !== TokenInstruction.Transfer && tag !== TokenInstruction.TransferWithMemo) || data.length < 9 || keys.length < 2) {
+    return null;
+  }
+  const userId = index.get(keys[1].toBase58());
+  if (userId === undefined) {
+    return null;
+  }
+  return { userId, depositAddress: keys[1], source: keys[0], amount: data.readBigUInt64LE(1) };
+}
+
+/**
+ * 扫描充值地址最近的交易，返回匹配到用户的入账（只看顶层指令，其他程序 CPI 转入的不在此列）
+ * until 为上次扫描到的最新签名，传入后只返回之后的交易
+ */
+export async function scanDeposits(
+  connection: Connection,
+  index: Map<string, string>,
+  programId: PublicKey,
+  until?: string,
+  limit = 100
+): Promise<DepositMatch[]> {
+  const matches: DepositMatch[] = [];
+  const seen = new Set<string>();
+  for (const address of index.keys()) {
+    const signatures = await connection.getSignaturesForAddress(new PublicKey(address), { until, limit });
+    for (const { signature, err } of signatures) {
+      if (err || seen.has(signature)) {
+        continue;
+      }
+      seen.add(signature);
+      const transaction = await connection.getTransaction(signature, { maxSupportedTransactionVersion: 0 });
+      if (!transaction) {
+        continue;
+      }
+      const message = transaction.transaction.message;
+      const accountKeys = message.getAccountKeys({ accountKeysFromLookups: transaction.meta?.loadedAddresses });
+      for (const instruction of message.compiledInstructions) {
+        if (!accountKeys.get(instruction.programIdIndex)?.equals(programId)) {
+          continue;
+        }
+        const keys = instruction.accountKeyIndexes.map(i => accountKeys.get(i)!);
+        const match = matchDepositInstruction(keys, Buffer.from(instruction.data), index);
+        if (match) {
+          matches.push({ ...match, signature, slot: transaction.slot });
+        }
+      }
+    }
+  }
+  return matches.sort((a, b) => a.slot - b.slot);
+}
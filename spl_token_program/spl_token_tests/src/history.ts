@@ -0,0 +1,257 @@
+import { Connection, PublicKey, VersionedTransactionResponse } from '@solana/web3.js';
+import bs58 from 'bs58';
+import { TokenInstruction, TokenAccount, Mint } from './types';
+
+/**
+ * 历史余额重建
+ *
+ * 逐页读取代币账户的 getSignaturesForAddress，解码每笔交易中本程序的指令（含 CPI 内层指令），
+ * 得到每笔交易对该账户余额的变化；再以当前链上余额为终点倒推出每笔交易之后的余额，供审计与报税使用。
+ *
+ * 只有金额能从指令数据与账户列表确定的指令才能解码，其余触及该账户的指令（如曲线买卖、归集、变基）
+ * 记为 delta = null，它之前的余额无法确定，timeline 中对应为 null，complete 为 false。
+ */
+
+/**
+ * 一笔交易对账户余额的影响
+ */
+export interface BalanceEntry {
+  signature: string;
+  slot: number;
+  blockTime: number | null;
+  /** 交易中触及该账户的本程序指令名 */
+  instructions: string[];
+  /** 余额变化，无法确定时为 null */
+  delta: bigint | null;
+  /** 该交易之后的余额，无法确定时为 null */
+  balance: bigint | null;
+}
+
+export interface BalanceHistory {
+  tokenAccount: PublicKey;
+  /** 从旧到新 */
+  entries: BalanceEntry[];
+  currentBalance: bigint;
+  /** 所有变化都已确定 */
+  complete: boolean;
+}
+
+/**
+ * 按 8 位小数截断金额，与 Rust 中的 bridge::normalize_amount 一致，返回实际销毁的金额
+ */
+function bridgeBurnAmount(amount: bigint, decimals: number): bigint {
+  if (decimals <= 8) {
+    return amount;
+  }
+  const factor = 10n ** BigInt(decimals - 8);
+  return (amount / factor) * factor;
+}
+
+/**
+ * 解码一条本程序指令对 account 余额的影响：不触及该账户返回 0n，触及但无法确定金额返回 null
+ * decimals 为铸币精度（BridgeBurn 需要）；velocityFrozen 表示该交易中 account 因速度限制被冻结，
+ * 此时从它转出的转账实际上没有执行
+ */
+export function decodeBalanceDelta(
+  keys: PublicKey[],
+  data: Buffer,
+  account: PublicKey,
+  decimals: number,
+  velocityFrozen = false
+): bigint | null {
+  const at = (index: number) => keys.length > index && keys[index].equals(account);
+  if (!keys.some(key => key.equals(account))) {
+    return 0n;
+  }
+  const amount = () => data.readBigUInt64LE(1);
+  // 转账类：源账户为 keys[0]，目标为 keys[1]
+  const transfer = (value: bigint) => (velocityFrozen ? 0n : (at(0) ? -value : 0n) + (at(1) ? value : 0n));
+
+  switch (data[0]) {
+    case TokenInstruction.MintTo:
+    case TokenInstruction.MintToNewAccount:
+      return at(1) ? amount() : 0n;
+    case TokenInstruction.Burn:
+    case TokenInstruction.BurnWithReceipt:
+      return at(0) ? -amount() : 0n;
+    case TokenInstruction.ConvertMint:
+      // 兑换出的新代币数量取决于链上的兑换比率，接收账户与托管账户的变化无法从指令数据确定
+      return at(2) ? -amount() : at(3) || at(5) ? null : 0n;
+    case TokenInstruction.Transfer:
+    case TokenInstruction.TransferWithMemo:
+    case TokenInstruction.TransferFrom:
+    case TokenInstruction.SessionTransfer:
+    case TokenInstruction.Debit:
+      return transfer(amount());
+    // 调拨不检查速度限制，不会因冻结而放弃
+    case TokenInstruction.Rebalance:
+      return (at(0) ? -amount() : 0n) + (at(1) ? amount() : 0n);
+    case TokenInstruction.TransferWithPermit: {
+      if (velocityFrozen) {
+        return 0n;
+      }
+      const tip = data.readBigUInt64LE(9);
+      return (at(0) ? -amount() : 0n) + (at(1) ? amount() - tip : 0n) + (at(2) ? tip : 0n);
+    }
+    case TokenInstruction.SettleBatch: {
+      const count = data.readUInt32LE(1);
+      let delta = 0n;
+      for (let i = 0; i < count; i++) {
+        if (at(i)) {
+          delta += data.readBigInt64LE(5 + 8 * i);
+        }
+      }
+      return delta;
+    }
+    case TokenInstruction.BridgeBurn:
+      return at(0) ? -bridgeBurnAmount(data.readBigUInt64LE(35), decimals) : 0n;
+    case TokenInstruction.BridgeMint:
+      return at(2) ? data.readBigUInt64LE(43) : 0n;
+    case TokenInstruction.InitializeAccount:
+    case TokenInstruction.InitializeAccountWithOwnerCheck:
+    case TokenInstruction.DelegateVotes:
+    case TokenInstruction.SetVelocityLimit:
+    case TokenInstruction.ThawAccount:
+    case TokenInstruction.FreezeAccount:
+    case TokenInstruction.SetFreezeExempt:
+    case TokenInstruction.SetAccountLabel:
+    case TokenInstruction.GetExtensionTypes:
+    case TokenInstruction.EnableActivityTracking:
+    case TokenInstruction.EnableMemoLog:
+    case TokenInstruction.SetGuardians:
+    case TokenInstruction.EnablePointsExpiry:
+    case TokenInstruction.SetDormancyPolicy:
+    case TokenInstruction.WarnDormant:
+    case TokenInstruction.UpdateAttestation:
+    case TokenInstruction.VerifySupplyBacked:
+    case TokenInstruction.SetPauseFlags:
+    case TokenInstruction.SetActivationRequired:
+    case TokenInstruction.ActivateAccount:
+      return 0n;
+    default:
+      return null;
+  }
+}
+
+/**
+ * 取出交易中本程序的全部指令（顶层与内层），返回 [账户列表, 指令数据]
+ */
+function programInstructions(
+  transaction: VersionedTransactionResponse,
+  programId: PublicKey
+): [PublicKey[], Buffer][] {
+  const message = transaction.transaction.message;
+  const accountKeys = message.getAccountKeys({ accountKeysFromLookups: transaction.meta?.loadedAddresses });
+  const compiled = [
+    ...message.compiledInstructions.map(ix => ({
+      programIdIndex: ix.programIdIndex,
+      accounts: ix.accountKeyIndexes,
+      data: Buffer.from(ix.data),
+    })),
+    ...(transaction.meta?.innerInstructions ?? []).flatMap(inner =>
+      inner.instructions.map(ix => ({
+        programIdIndex: ix.programIdIndex,
+        accounts: ix.accounts,
+        data: Buffer.from(bs58.decode(ix.data)),
+      }))
+    ),
+  ];
+  return compiled
+    .filter(ix => accountKeys.get(ix.programIdIndex)?.equals(programId))
+    .map(ix => [ix.accounts.map(i => accountKeys.get(i)!), ix.data]);
+}
+
+/**
+ * 重建代币账户的余额时间线；maxSignatures 限制回溯的交易数量，更早的交易不出现在时间线中
+ */
+export async function reconstructBalanceHistory(
+  connection: Connection,
+  tokenAccount: PublicKey,
+  programId: PublicKey,
+  maxSignatures = 1000
+): Promise<BalanceHistory> {
+  const { value: accountInfo, context } = await connection.getAccountInfoAndContext(tokenAccount, 'confirmed');
+  if (!accountInfo) {
+    throw new Error(`代币账户不存在: ${tokenAccount.toString()}`);
+  }
+  const state = TokenAccount.deserialize(accountInfo.data);
+  const mintInfo = await connection.getAccountInfo(new PublicKey(state.mint));
+  if (!mintInfo) {
+    throw new Error(`铸币账户不存在: ${new PublicKey(state.mint).toString()}`);
+  }
+  const mint = Mint.deserialize(mintInfo.data);
+
+  const entries: BalanceEntry[] = [];
+  let before: string | undefined;
+  while (entries.length < maxSignatures) {
+    const page = await connection.getSignaturesForAddress(
+      tokenAccount,
+      { before, limit: Math.min(1000, maxSignatures - entries.length) },
+      'confirmed'
+    );
+    if (page.length === 0) {
+      break;
+    }
+    before = page[page.length - 1].signature;
+    for (const { signature, err, slot } of page) {
+      // 晚于读取余额的交易不计入，保证倒推的终点一致
+      if (err || slot > context.slot) {
+        continue;
+      }
+      const transaction = await connection.getTransaction(signature, {
+        commitment: 'confirmed',
+        maxSupportedTransactionVersion: 0,
+      });
+      if (!transaction) {
+        continue;
+      }
+      const velocityFrozen = (transaction.meta?.logMessages ?? []).some(
+        log => log.includes('Velocity limit exceeded') && log.includes(`account ${tokenAccount.toBase58()} frozen`)
+      );
+      let delta: bigint | null = 0n;
+      const names: string[] = [];
+      for (const [keys, data] of programInstructions(transaction, programId)) {
+        if (!keys.some(key => key.equals(tokenAccount))) {
+          continue;
+        }
+        names.push(TokenInstruction[data[0]] ?? `Unknown(${data[0]})`);
+        const change = decodeBalanceDelta(keys, data, tokenAccount, mint.decimals, velocityFrozen);
+        delta = delta === null || change === null ? null : delta + change;
+      }
+      entries.push({ signature, slot, blockTime: transaction.blockTime ?? null, instructions: names, delta, balance: null });
+    }
+  }
+
+  // 从当前余额倒推：每条记录的 balance 为该交易之后的余额
+  entries.reverse();
+  let balance: bigint | null = state.amount;
+  for (let i = entries.length - 1; i >= 0; i--) {
+    entries[i].balance = balance;
+    const delta = entries[i].delta;
+    balance = balance === null || delta === null ? null : balance - delta;
+  }
+
+  return {
+    tokenAccount,
+    entries,
+    currentBalance: state.amount,
+    complete: entries.every(entry => entry.delta !== null),
+  };
+}
+
+/**
+ * 把时间线导出为 CSV（signature,slot,blockTime,instructions,delta,balance），未知值留空
+ */
+export function balanceHistoryToCsv(history: BalanceHistory): string {
+  const rows = history.entries.map(entry =>
+    [
+      entry.signature,
+      entry.slot,
+      entry.blockTime === null ? '' : new Date(entry.blockTime * 1000).toISOString(),
+      entry.instructions.join('|'),
+      entry.delta === null ? '' : entry.delta.toString(),
+      entry.balance === null ? '' : entry.balance.toString(),
+    ].join(',')
+  );
+  return ['signature,slot,blockTime,instructions,delta,balance', ...rows].join('\n');
+}
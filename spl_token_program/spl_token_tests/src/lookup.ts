@@ -0,0 +1,191 @@
+import {
+  AddressLookupTableAccount,
+  AddressLookupTableProgram,
+  Connection,
+  Keypair,
+  PACKET_DATA_SIZE,
+  PublicKey,
+  Signer,
+  Transaction,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+  sendAndConfirmTransaction
+} from '@solana/web3.js';
+
+/**
+ * 地址查找表（Address Lookup Table）辅助函数
+ *
+ * SweepAll、SettleBatch、TransferSplit 等批量指令的账户列表随批量大小增长，
+ * 旧版交易每个账户占 32 字节，几十个账户就会超过 1232 字节的交易上限。
+ * v0 交易可以把非签名账户放进查找表，每个只占 1 字节索引；sendBatchInstructions 在交易装不下时
+ * 自动创建查找表并改用 v0 交易发送。
+ */
+
+/** 单条 extendLookupTable 指令写入的地址数量，保证扩展交易本身不超限 */
+const EXTEND_CHUNK_SIZE = 20;
+
+/**
+ * 交易序列化后的大小（签名数 + 签名 + 消息）
+ */
+export function transactionSize(
+  payer: PublicKey,
+  instructions: TransactionInstruction[],
+  lookupTables: AddressLookupTableAccount[] = []
+): number {
+  const message = new TransactionMessage({
+    payerKey: payer,
+    recentBlockhash: PublicKey.default.toBase58(),
+    instructions,
+  }).compileToV0Message(lookupTables);
+  const signatures = message.header.numRequiredSignatures;
+  return 1 + 64 * signatures + message.serialize().length;
+}
+
+/**
+ * 收集可以放进查找表的地址：签名者与被调用的程序必须直接出现在交易中，其余账户都可以
+ */
+export function lookupTableCandidates(instructions: TransactionInstruction[]): PublicKey[] {
+  const programIds = new Set(instructions.map(ix => ix.programId.toBase58()));
+  const signers = new Set(
+    instructions.flatMap(ix => ix.keys.filter(key => key.isSigner).map(key => key.pubkey.toBase58()))
+  );
+  const addresses = new Map<string, PublicKey>();
+  for (const ix of instructions) {
+    for (const { pubkey } of ix.keys) {
+      const key = pubkey.toBase58();
+      if (!programIds.has(key) && !signers.has(key)) {
+        addresses.set(key, pubkey);
+      }
+    }
+  }
+  return [...addresses.values()];
+}
+
+/**
+ * 创建查找表并写入 addresses（authority 同时支付租金），等到查找表可用后返回
+ * 新写入的地址要到下一个 slot 才能被交易使用
+ */
+export async function createLookupTable(
+  connection: Connection,
+  authority: Keypair,
+  addresses: PublicKey[]
+): Promise<AddressLookupTableAccount> {
+  const recentSlot = await connection.getSlot('finalized');
+  const [createInstruction, lookupTable] = AddressLookupTableProgram.createLookupTable({
+    authority: authority.publicKey,
+    payer: authority.publicKey,
+    recentSlot,
+  });
+  await sendAndConfirmTransaction(connection, new Transaction().add(createInstruction), [authority]);
+  await extendLookupTable(connection, authority, lookupTable, addresses);
+  return fetchLookupTable(connection, lookupTable);
+}
+
+/**
+ * 向已有查找表追加地址（已存在的地址会被跳过），等到新地址可用后返回
+ */
+export async function extendLookupTable(
+  connection: Connection,
+  authority: Keypair,
+  lookupTable: PublicKey,
+  addresses: PublicKey[]
+): Promise<void> {
+  const existing = await connection.getAddressLookupTable(lookupTable);
+  const known = new Set((existing.value?.state.addresses ?? []).map(address => address.toBase58()));
+  const missing = addresses.filter(address => !known.has(address.toBase58()));
+
+  for (let i = 0; i < missing.length; i += EXTEND_CHUNK_SIZE) {
+    const extendInstruction = AddressLookupTableProgram.extendLookupTable({
+      lookupTable,
+      authority: authority.publicKey,
+      payer: authority.publicKey,
+      addresses: missing.slice(i, i + EXTEND_CHUNK_SIZE),
+    });
+    await sendAndConfirmTransaction(connection, new Transaction().add(extendInstruction), [authority]);
+  }
+  if (missing.length > 0) {
+    await waitForNextSlot(connection);
+  }
+}
+
+/**
+ * 读取查找表账户
+ */
+export async function fetchLookupTable(
+  connection: Connection,
+  lookupTable: PublicKey
+): Promise<AddressLookupTableAccount> {
+  const { value } = await connection.getAddressLookupTable(lookupTable);
+  if (!value) {
+    throw new Error(`查找表不存在: ${lookupTable.toString()}`);
+  }
+  return value;
+}
+
+async function waitForNextSlot(connection: Connection): Promise<void> {
+  const start = await connection.getSlot('confirmed');
+  while ((await connection.getSlot('confirmed')) <= start) {
+    await new Promise(resolve => setTimeout(resolve, 200));
+  }
+}
+
+/**
+ * 用最新区块哈希构造并签名 v0 交易
+ */
+export async function buildV0Transaction(
+  connection: Connection,
+  payer: PublicKey,
+  instructions: TransactionInstruction[],
+  signers: Signer[],
+  lookupTables: AddressLookupTableAccount[] = []
+): Promise<VersionedTransaction> {
+  const { blockhash } = await connection.getLatestBlockhash();
+  const message = new TransactionMessage({
+    payerKey: payer,
+    recentBlockhash: blockhash,
+    instructions,
+  }).compileToV0Message(lookupTables);
+  const transaction = new VersionedTransaction(message);
+  transaction.sign(signers);
+  return transaction;
+}
+
+/**
+ * 发送批量指令：装得下时按旧版交易发送，否则把非签名账户写入查找表（传入 lookupTable 时复用并扩展它）后发送 v0 交易
+ * signers 的第一个为手续费支付者，同时作为新建查找表的 authority
+ */
+export async function sendBatchInstructions(
+  connection: Connection,
+  instructions: TransactionInstruction[],
+  signers: Keypair[],
+  lookupTable?: PublicKey
+): Promise<{ signature: string; lookupTable: PublicKey | null }> {
+  const payer = signers[0];
+  if (transactionSize(payer.publicKey, instructions) <= PACKET_DATA_SIZE) {
+    const signature = await sendAndConfirmTransaction(connection, new Transaction().add(...instructions), signers);
+    return { signature, lookupTable: null };
+  }
+
+  const addresses = lookupTableCandidates(instructions);
+  let table: AddressLookupTableAccount;
+  if (lookupTable) {
+    await extendLookupTable(connection, payer, lookupTable, addresses);
+    table = await fetchLookupTable(connection, lookupTable);
+  } else {
+    table = await createLookupTable(connection, payer, addresses);
+  }
+  const size = transactionSize(payer.publicKey, instructions, [table]);
+  if (size > PACKET_DATA_SIZE) {
+    throw new Error(`使用查找表后交易仍有 ${size} 字节，超过上限 ${PACKET_DATA_SIZE}，请拆分批量`);
+  }
+
+  const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+  const transaction = new VersionedTransaction(
+    new TransactionMessage({ payerKey: payer.publicKey, recentBlockhash: blockhash, instructions }).compileToV0Message([table])
+  );
+  transaction.sign(signers);
+  const signature = await connection.sendTransaction(transaction);
+  await connection.confirmTransaction({ signature, blockhash, lastValidBlockHeight }, 'confirmed');
+  return { signature, lookupTable: table.key };
+}
@@ -0,0 +1,124 @@
+import {
+  Connection,
+  Keypair,
+  Message,
+  NonceAccount,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction
+} from '@solana/web3.js';
+import bs58 from 'bs58';
+import { createPrivateKey, sign } from 'crypto';
+
+/**
+ * 离线签名辅助函数
+ *
+ * 铸币权限等高权限密钥可以放在离线机器上：联网机器用 buildUnsignedMessage 生成待签名消息（base64），
+ * 拷贝到离线机器用 signMessage 签名，再把签名（base58）拷回联网机器，用 attachSignatures 组装后广播。
+ * 普通区块哈希约 1 分钟后过期，离线流程一般需要持久 nonce：传入 nonce 账户后消息以 nonceAdvance 开头，
+ * 用 nonce 值代替区块哈希，签名在 nonce 被推进之前一直有效。
+ */
+
+const ED25519_PKCS8_PREFIX = Buffer.from('302e020100300506032b657004220420', 'hex');
+
+/**
+ * 消息的有效期来源：最新区块哈希，或持久 nonce 账户（nonceValue 为其中当前存放的 nonce）
+ */
+export type MessageLifetime =
+  | { blockhash: string }
+  | { nonceAccount: PublicKey; nonceAuthority: PublicKey; nonceValue: string };
+
+/**
+ * 读取持久 nonce 账户，返回可直接传给 buildUnsignedMessage 的有效期
+ */
+export async function fetchNonceLifetime(
+  connection: Connection,
+  nonceAccount: PublicKey
+): Promise<MessageLifetime> {
+  const accountInfo = await connection.getAccountInfo(nonceAccount);
+  if (!accountInfo) {
+    throw new Error(`nonce 账户不存在: ${nonceAccount.toString()}`);
+  }
+  const nonce = NonceAccount.fromAccountData(accountInfo.data);
+  return { nonceAccount, nonceAuthority: nonce.authorizedPubkey, nonceValue: nonce.nonce };
+}
+
+/**
+ * 构造待签名消息，返回 base64 编码的消息字节
+ */
+export function buildUnsignedMessage(
+  feePayer: PublicKey,
+  instructions: TransactionInstruction[],
+  lifetime: MessageLifetime
+): string {
+  const transaction = new Transaction({ feePayer });
+  if ('nonceAccount' in lifetime) {
+    transaction.add(
+      SystemProgram.nonceAdvance({ noncePubkey: lifetime.nonceAccount, authorizedPubkey: lifetime.nonceAuthority })
+    );
+    transaction.recentBlockhash = lifetime.nonceValue;
+  } else {
+    transaction.recentBlockhash = lifetime.blockhash;
+  }
+  transaction.add(...instructions);
+  return transaction.serializeMessage().toString('base64');
+}
+
+/**
+ * 列出消息需要的签名者，离线机器可以据此确认要签的是哪些账户
+ */
+export function requiredSigners(message: string): PublicKey[] {
+  const decoded = Message.from(Buffer.from(message, 'base64'));
+  return decoded.accountKeys.slice(0, decoded.header.numRequiredSignatures);
+}
+
+/**
+ * 在离线机器上对消息签名，返回 { publicKey, signature }（均为 base58）
+ * 直接对消息字节签名，不重新编译消息，保证签名与联网机器上的消息一致
+ */
+export function signMessage(message: string, signer: Keypair): { publicKey: string; signature: string } {
+  if (!requiredSigners(message).some(key => key.equals(signer.publicKey))) {
+    throw new Error(`${signer.publicKey.toString()} 不是该消息的签名者`);
+  }
+  // PKCS#8 封装的 ed25519 私钥：固定前缀 + 32 字节种子
+  const privateKey = createPrivateKey({
+    key: Buffer.concat([ED25519_PKCS8_PREFIX, Buffer.from(signer.secretKey.subarray(0, 32))]),
+    format: 'der',
+    type: 'pkcs8',
+  });
+  const signature = sign(null, Buffer.from(message, 'base64'), privateKey);
+  return { publicKey: signer.publicKey.toBase58(), signature: bs58.encode(signature) };
+}
+
+/**
+ * 把各方的签名附加到消息上；缺少签名或任一签名校验失败时报错
+ */
+export function attachSignatures(
+  message: string,
+  signatures: { publicKey: string; signature: string }[]
+): Transaction {
+  const bySigner = new Map(signatures.map(entry => [new PublicKey(entry.publicKey).toBase58(), entry.signature]));
+  const signers = requiredSigners(message);
+  const missing = signers.filter(key => !bySigner.has(key.toBase58())).map(key => key.toString());
+  if (missing.length > 0) {
+    throw new Error(`缺少签名: ${missing.join(', ')}`);
+  }
+  const transaction = Transaction.populate(
+    Message.from(Buffer.from(message, 'base64')),
+    signers.map(key => bySigner.get(key.toBase58())!)
+  );
+  if (!transaction.verifySignatures()) {
+    throw new Error('签名校验失败');
+  }
+  return transaction;
+}
+
+/**
+ * 广播已附加全部签名的交易
+ */
+export async function broadcastSignedTransaction(connection: Connection, transaction: Transaction): Promise<string> {
+  const signature = await connection.sendRawTransaction(transaction.serialize());
+  await connection.confirmTransaction(signature, 'confirmed');
+  return signature;
+}
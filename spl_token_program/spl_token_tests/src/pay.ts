@@ -0,0 +1,174 @@
+import {
+  Connection,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction
+} from '@solana/web3.js';
+import { fromUiAmount } from './amount';
+import {
+  createTransferInstruction,
+  createTransferWithMemoInstruction,
+  findAssociatedTokenAddress,
+  getMintData
+} from './utils';
+
+/**
+ * Solana Pay 辅助函数
+ *
+ * 转账请求 URL（solana:<收款钱包>?amount=..&spl-token=..）由支持 Solana Pay 的钱包直接解析，
+ * 但钱包只会构造标准 SPL Token 程序的转账，因此本程序的铸币需要走交易请求：
+ * 二维码内容为 solana:<https 链接>，钱包向该链接 POST 付款人地址，由服务端用本程序的指令构造交易后返回。
+ * 两种 URL 的字符串本身就是二维码的内容。
+ */
+
+/**
+ * 支付意图：收款方、铸币与金额（金额为带小数的字符串，如 "12.5"）
+ * reference 为一次性的随机公钥，附加在交易中用于之后用 findPaymentByReference 定位这笔付款
+ */
+export interface PaymentIntent {
+  recipient: PublicKey;
+  mint: PublicKey;
+  amount: string;
+  reference?: PublicKey[];
+  label?: string;
+  message?: string;
+  memo?: string;
+}
+
+/**
+ * 生成转账请求 URL
+ */
+export function createTransferRequestURL(intent: PaymentIntent): string {
+  const params = new URLSearchParams();
+  params.append('amount', intent.amount);
+  params.append('spl-token', intent.mint.toBase58());
+  for (const reference of intent.reference ?? []) {
+    params.append('reference', reference.toBase58());
+  }
+  if (intent.label) {
+    params.append('label', intent.label);
+  }
+  if (intent.message) {
+    params.append('message', intent.message);
+  }
+  if (intent.memo) {
+    params.append('memo', intent.memo);
+  }
+  return `solana:${intent.recipient.toBase58()}?${params.toString()}`;
+}
+
+/**
+ * 解析转账请求 URL，得到支付意图
+ */
+export function parseTransferRequestURL(url: string): PaymentIntent {
+  if (!url.startsWith('solana:')) {
+    throw new Error(`不是 Solana Pay URL: ${url}`);
+  }
+  const [path, query = ''] = url.slice('solana:'.length).split('?', 2);
+  const params = new URLSearchParams(query);
+  const amount = params.get('amount');
+  const mint = params.get('spl-token');
+  if (!amount || !mint) {
+    throw new Error('转账请求缺少 amount 或 spl-token');
+  }
+  return {
+    recipient: new PublicKey(path),
+    mint: new PublicKey(mint),
+    amount,
+    reference: params.getAll('reference').map(reference => new PublicKey(reference)),
+    label: params.get('label') ?? undefined,
+    message: params.get('message') ?? undefined,
+    memo: params.get('memo') ?? undefined,
+  };
+}
+
+/**
+ * 生成交易请求 URL（link 为服务端处理 handleTransactionRequest 的 https 地址，可带查询参数区分订单）
+ */
+export function createTransactionRequestURL(link: URL | string): string {
+  const url = new URL(link.toString());
+  if (url.protocol !== 'https:') {
+    throw new Error(`交易请求链接必须是 https: ${url.toString()}`);
+  }
+  const encoded = url.search ? encodeURIComponent(url.toString()) : url.toString();
+  return `solana:${encoded}`;
+}
+
+/**
+ * 交易请求的 GET 响应：钱包展示的商户名称与图标
+ */
+export function transactionRequestMetadata(label: string, icon: string): { label: string; icon: string } {
+  return { label, icon };
+}
+
+/**
+ * 构造支付意图对应的指令：付款人的关联代币账户转入收款方的关联代币账户，有 memo 时使用 TransferWithMemo
+ *
+ * 本程序的转账指令会按位置解析尾部的可选账户，reference 不能直接追加在转账指令上，
+ * 因此放在一笔付款人给自己转 0 lamports 的系统转账中（系统程序忽略多余账户）
+ */
+export async function createPaymentInstructions(
+  connection: Connection,
+  payer: PublicKey,
+  intent: PaymentIntent,
+  programId: PublicKey
+): Promise<TransactionInstruction[]> {
+  const mint = await getMintData(connection, intent.mint);
+  const amount = fromUiAmount(intent.amount, mint.decimals);
+  const [source] = findAssociatedTokenAddress(payer, intent.mint, programId);
+  const [destination] = findAssociatedTokenAddress(intent.recipient, intent.mint, programId);
+  if (!(await connection.getAccountInfo(destination))) {
+    throw new Error(`收款方没有该铸币的关联代币账户: ${destination.toString()}`);
+  }
+
+  const instructions = [
+    intent.memo
+      ? createTransferWithMemoInstruction(source, destination, payer, amount, intent.memo, intent.mint, programId)
+      : createTransferInstruction(source, destination, payer, amount, intent.mint, programId),
+  ];
+  if (intent.reference && intent.reference.length > 0) {
+    const referenceInstruction = SystemProgram.transfer({ fromPubkey: payer, toPubkey: payer, lamports: 0 });
+    referenceInstruction.keys.push(
+      ...intent.reference.map(pubkey => ({ pubkey, isSigner: false, isWritable: false }))
+    );
+    instructions.push(referenceInstruction);
+  }
+  return instructions;
+}
+
+/**
+ * 交易请求的 POST 处理：body 为钱包提交的 { account }，返回付款人待签名的交易（base64）与提示信息
+ * 服务端不签名，交易只需付款人（钱包）签名后发送
+ */
+export async function handleTransactionRequest(
+  connection: Connection,
+  body: { account?: string },
+  intent: PaymentIntent,
+  programId: PublicKey
+): Promise<{ transaction: string; message?: string }> {
+  if (!body.account) {
+    throw new Error('交易请求缺少 account');
+  }
+  const payer = new PublicKey(body.account);
+  const instructions = await createPaymentInstructions(connection, payer, intent, programId);
+  const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+  const transaction = new Transaction({ feePayer: payer, blockhash, lastValidBlockHeight }).add(...instructions);
+
+  return {
+    transaction: transaction.serialize({ requireAllSignatures: false, verifySignatures: false }).toString('base64'),
+    message: intent.message,
+  };
+}
+
+/**
+ * 按 reference 查找已确认的付款签名，尚未付款时返回 null
+ */
+export async function findPaymentByReference(
+  connection: Connection,
+  reference: PublicKey
+): Promise<string | null> {
+  const signatures = await connection.getSignaturesForAddress(reference, { limit: 1 }, 'confirmed');
+  const found = signatures.find(({ err }) => !err);
+  return found ? found.signature : null;
+}
@@ -0,0 +1,141 @@
+import {
+  Connection,
+  PublicKey,
+  TransactionInstruction,
+  TransactionMessage
+} from '@solana/web3.js';
+import * as multisig from '@sqds/multisig';
+import {
+  createMintToInstruction,
+  createSetMintAuthorityInstruction
+} from './utils';
+
+/**
+ * Squads v4 多签辅助函数
+ *
+ * 铸币权限等交给 Squads 金库（vault PDA）持有时，本程序的指令不能直接签名发送，
+ * 而是包装成金库交易 + 提案：成员投票通过后由 Squads 程序以金库 PDA 身份 CPI 执行。
+ * 因此内层指令中唯一的签名者必须是金库 PDA，付费账户（创建 PDA 时）也必须是金库。
+ */
+
+/**
+ * 推导 Squads 金库 PDA（作为本程序中的铸币权限 / 账户所有者）
+ */
+export function getSquadsVaultAddress(
+  multisigPda: PublicKey,
+  vaultIndex = 0,
+  programId: PublicKey = multisig.PROGRAM_ID
+): PublicKey {
+  const [vault] = multisig.getVaultPda({ multisigPda, index: vaultIndex, programId });
+  return vault;
+}
+
+/**
+ * 校验内层指令只要求金库 PDA 签名
+ */
+function assertOnlyVaultSigns(instructions: TransactionInstruction[], vault: PublicKey): void {
+  for (const instruction of instructions) {
+    for (const key of instruction.keys) {
+      if (key.isSigner && !key.pubkey.equals(vault)) {
+        throw new Error(`多签交易中的签名者必须是金库 ${vault.toString()}，实际为 ${key.pubkey.toString()}`);
+      }
+    }
+  }
+}
+
+/**
+ * 把本程序的指令包装成 Squads 金库交易和提案
+ * 返回的指令需由多签成员（creator）签名发送；之后按 Squads 流程投票并执行
+ */
+export async function createSquadsProposalInstructions(
+  connection: Connection,
+  multisigPda: PublicKey,
+  creator: PublicKey,
+  instructions: TransactionInstruction[],
+  vaultIndex = 0,
+  memo?: string,
+  programId: PublicKey = multisig.PROGRAM_ID
+): Promise<{ transactionIndex: bigint; instructions: TransactionInstruction[] }> {
+  const vault = getSquadsVaultAddress(multisigPda, vaultIndex, programId);
+  assertOnlyVaultSigns(instructions, vault);
+
+  const multisigAccount = await multisig.accounts.Multisig.fromAccountAddress(connection, multisigPda);
+  const transactionIndex = BigInt(multisigAccount.transactionIndex.toString()) + BigInt(1);
+
+  // blockhash 由 Squads 在执行时替换，这里只用于编译消息
+  const { blockhash } = await connection.getLatestBlockhash();
+  const transactionMessage = new TransactionMessage({
+    payerKey: vault,
+    recentBlockhash: blockhash,
+    instructions,
+  });
+
+  return {
+    transactionIndex,
+    instructions: [
+      multisig.instructions.vaultTransactionCreate({
+        multisigPda,
+        transactionIndex,
+        creator,
+        vaultIndex,
+        ephemeralSigners: 0,
+        transactionMessage,
+        memo,
+        programId,
+      }),
+      multisig.instructions.proposalCreate({
+        multisigPda,
+        transactionIndex,
+        creator,
+        programId,
+      }),
+    ],
+  };
+}
+
+/**
+ * 创建由多签金库铸造代币的提案（金库需已是铸币权限）
+ */
+export async function createSquadsMintToProposal(
+  connection: Connection,
+  multisigPda: PublicKey,
+  creator: PublicKey,
+  mint: PublicKey,
+  tokenAccount: PublicKey,
+  amount: bigint,
+  tokenProgramId: PublicKey,
+  vaultIndex = 0
+): Promise<{ transactionIndex: bigint; instructions: TransactionInstruction[] }> {
+  const vault = getSquadsVaultAddress(multisigPda, vaultIndex);
+  return createSquadsProposalInstructions(
+    connection,
+    multisigPda,
+    creator,
+    [createMintToInstruction(mint, tokenAccount, vault, amount, tokenProgramId)],
+    vaultIndex,
+    `MintTo ${amount}`
+  );
+}
+
+/**
+ * 创建由多签金库变更铸币权限的提案
+ */
+export async function createSquadsSetMintAuthorityProposal(
+  connection: Connection,
+  multisigPda: PublicKey,
+  creator: PublicKey,
+  mint: PublicKey,
+  newAuthority: PublicKey | null,
+  tokenProgramId: PublicKey,
+  vaultIndex = 0
+): Promise<{ transactionIndex: bigint; instructions: TransactionInstruction[] }> {
+  const vault = getSquadsVaultAddress(multisigPda, vaultIndex);
+  return createSquadsProposalInstructions(
+    connection,
+    multisigPda,
+    creator,
+    [createSetMintAuthorityInstruction(mint, vault, newAuthority, tokenProgramId)],
+    vaultIndex,
+    'SetMintAuthority'
+  );
+}
@@ -0,0 +1,118 @@
+import { AccountInfo, Commitment, Connection, Context, PublicKey } from '@solana/web3.js';
+import { Mint, TokenAccount } from './types';
+
+/**
+ * 账户订阅辅助函数
+ *
+ * 通过 websocket 的 accountSubscribe 推送，把账户数据解码为 TokenAccount / Mint 后以异步迭代器的形式交给调用方，
+ * 机器人与界面可以用 for await 直接响应余额变化而不必轮询；跳出循环（break / return）时自动取消订阅。
+ */
+
+/**
+ * 一次账户更新：slot 为推送时的上下文，state 为解码后的账户状态
+ */
+export interface AccountUpdate<T> {
+  slot: number;
+  state: T;
+}
+
+/**
+ * 可迭代的账户订阅，close 用于在迭代器之外主动取消
+ */
+export interface AccountSubscription<T> extends AsyncIterableIterator<AccountUpdate<T>> {
+  close(): Promise<void>;
+}
+
+/**
+ * 订阅任意账户并用 decode 解码；解码失败的推送（如账户被关闭）会让迭代抛出错误并结束订阅
+ */
+export function subscribeAccount<T>(
+  connection: Connection,
+  pubkey: PublicKey,
+  decode: (data: Buffer) => T,
+  commitment: Commitment = 'confirmed'
+): AccountSubscription<T> {
+  // 尚未被消费的更新与正在等待的消费者，二者任一时刻至多一个非空
+  const pending: AccountUpdate<T>[] = [];
+  const waiting: { resolve: (result: IteratorResult<AccountUpdate<T>>) => void; reject: (error: Error) => void }[] = [];
+  let failure: Error | null = null;
+  let closed = false;
+
+  const subscriptionId = connection.onAccountChange(
+    pubkey,
+    (accountInfo: AccountInfo<Buffer>, context: Context) => {
+      let update: AccountUpdate<T>;
+      try {
+        update = { slot: context.slot, state: decode(accountInfo.data) };
+      } catch (error) {
+        failure = error instanceof Error ? error : new Error(String(error));
+        waiting.splice(0).forEach(consumer => consumer.reject(failure!));
+        void close();
+        return;
+      }
+      const consumer = waiting.shift();
+      if (consumer) {
+        consumer.resolve({ value: update, done: false });
+      } else {
+        pending.push(update);
+      }
+    },
+    commitment
+  );
+
+  async function close(): Promise<void> {
+    if (closed) {
+      return;
+    }
+    closed = true;
+    waiting.splice(0).forEach(consumer => consumer.resolve({ value: undefined, done: true }));
+    await connection.removeAccountChangeListener(subscriptionId);
+  }
+
+  const subscription: AccountSubscription<T> = {
+    next(): Promise<IteratorResult<AccountUpdate<T>>> {
+      const update = pending.shift();
+      if (update) {
+        return Promise.resolve({ value: update, done: false });
+      }
+      if (failure) {
+        return Promise.reject(failure);
+      }
+      if (closed) {
+        return Promise.resolve({ value: undefined, done: true });
+      }
+      return new Promise((resolve, reject) => waiting.push({ resolve, reject }));
+    },
+    async return(): Promise<IteratorResult<AccountUpdate<T>>> {
+      await close();
+      return { value: undefined, done: true };
+    },
+    close,
+    [Symbol.asyncIterator]() {
+      return subscription;
+    },
+  };
+  return subscription;
+}
+
+/**
+ * 订阅代币账户，每次变化产出解码后的 TokenAccount
+ */
+export function subscribeTokenAccount(
+  connection: Connection,
+  tokenAccount: PublicKey,
+  commitment: Commitment = 'confirmed'
+): AccountSubscription<TokenAccount> {
+  return subscribeAccount(connection, tokenAccount, data => TokenAccount.deserialize(data), commitment);
+}
+
+/**
+ * 订阅铸币账户，每次变化产出解码后的 Mint
+ */
+export function subscribeMint(
+  connection: Connection,
+  mint: PublicKey,
+  commitment: Commitment = 'confirmed'
+): AccountSubscription<Mint> {
+  return subscribeAccount(connection, mint, data => Mint.deserialize(data), commitment);
+}
@@ -0,0 +1,298 @@
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+} from '@solana/web3.js';
+import { TestResult, TokenError, TokenInstruction } from './types';
+import {
+  getBalance,
+  requestAirdrop,
+  createInitializeMintInstruction,
+  createInitializeAccountInstruction,
+  createMintToInstruction,
+  createTransferInstruction,
+  createSetMintAuthorityInstruction,
+  createBurnInstruction,
+  createSetVelocityLimitInstruction,
+  TOKEN_PROGRAM_ID,
+} from './utils';
+
+/**
+ * 负面测试套件
+ * 逐一触发文档中列出的失败路径，并断言程序返回的确切错误码
+ */
+class NegativeTester {
+  private connection: Connection;
+  private payer: Keypair;
+  private programId: PublicKey;
+  private testResults: TestResult[] = [];
+
+  constructor(programId: string) {
+    this.connection = new Connection('http://localhost:8899', 'confirmed');
+    this.payer = Keypair.generate();
+    this.programId = new PublicKey(programId);
+  }
+
+  /**
+   * 运行全部负面测试
+   */
+  async runAllTests(): Promise<void> {
+    console.log('🚀 开始负面测试');
+    console.log('========================================');
+
+    try {
+      if (await getBalance(this.connection, this.payer.publicKey) < 0.1) {
+        await requestAirdrop(this.connection, this.payer.publicKey, 1);
+      }
+
+      const mint = await this.createProgramOwnedAccount();
+      const tokenAccount = await this.createProgramOwnedAccount();
+      const receiverTokenAccount = await this.createProgramOwnedAccount();
+      const amount = BigInt(100 * (10 ** 6));
+      // 冻结权限设为付款账户，用于速度限制冻结测试
+      await sendAndConfirmTransaction(this.connection, new Transaction().add(
+        createInitializeMintInstruction(mint.publicKey, 6, this.payer.publicKey, this.payer.publicKey, this.programId),
+        createInitializeAccountInstruction(tokenAccount.publicKey, mint.publicKey, this.payer.publicKey, this.programId),
+        createInitializeAccountInstruction(receiverTokenAccount.publicKey, mint.publicKey, this.payer.publicKey, this.programId),
+        createMintToInstruction(mint.publicKey, tokenAccount.publicKey, this.payer.publicKey, amount, this.programId)
+      ), [this.payer]);
+
+      await this.testMissingSigner(tokenAccount.publicKey);
+      await this.testWrongProgramOwner(mint.publicKey);
+      await this.testMismatchedMint(mint.publicKey, tokenAccount.publicKey);
+      await this.testInsufficientFunds(mint.publicKey, tokenAccount.publicKey, receiverTokenAccount.publicKey, amount);
+      await this.testDataTooShort(mint.publicKey, tokenAccount.publicKey);
+      await this.testTrailingData(mint.publicKey, tokenAccount.publicKey);
+      await this.testUnauthorizedAuthority(mint.publicKey);
+      await this.testFrozenAccount(mint.publicKey, receiverTokenAccount.publicKey, tokenAccount.publicKey);
+    } catch (error) {
+      console.error('❌ 负面测试准备失败:', error);
+      this.recordTestResult('测试准备', false, error instanceof Error ? error.message : String(error));
+    }
+
+    this.printTestReport();
+  }
+
+  /**
+   * 创建归本程序所有的空账户
+   */
+  private async createProgramOwnedAccount(space = 82, owner = this.programId): Promise<Keypair> {
+    const account = Keypair.generate();
+    await sendAndConfirmTransaction(this.connection, new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: this.payer.publicKey,
+        newAccountPubkey: account.publicKey,
+        lamports: await this.connection.getMinimumBalanceForRentExemption(space),
+        space,
+        programId: owner,
+      })
+    ), [this.payer, account]);
+    return account;
+  }
+
+  /**
+   * 发送预期失败的交易，并断言错误信息中包含期望的错误
+   */
+  private async expectFailure(
+    name: string,
+    instructions: TransactionInstruction[],
+    signers: Keypair[],
+    expected: TokenError | string
+  ): Promise<void> {
+    const expectedMessage = typeof expected === 'string'
+      ? expected
+      : `custom program error: 0x${expected.toString(16)}`;
+    const label = typeof expected === 'string' ? expected : TokenError[expected];
+
+    try {
+      await sendAndConfirmTransaction(this.connection, new Transaction().add(...instructions), signers);
+      this.recordTestResult(name, false, `预期返回 ${label}，但交易成功了`);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      if (message.includes(expectedMessage)) {
+        console.log(`✅ ${name} - 返回 ${label}`);
+        this.recordTestResult(name, true, { expected: label });
+      } else {
+        this.recordTestResult(name, false, `预期返回 ${label}，实际错误: ${message}`);
+      }
+    }
+  }
+
+  /**
+   * 铸币权限未签名
+   */
+  private async testMissingSigner(tokenAccount: PublicKey): Promise<void> {
+    // 铸币权限不能是付款账户，否则运行时会把它视为签名者
+    const authority = Keypair.generate();
+    const mint = await this.createProgramOwnedAccount();
+    await sendAndConfirmTransaction(this.connection, new Transaction().add(
+      createInitializeMintInstruction(mint.publicKey, 6, authority.publicKey, null, this.programId)
+    ), [this.payer]);
+
+    const instruction = createMintToInstruction(mint.publicKey, tokenAccount, authority.publicKey, BigInt(1), this.programId);
+    instruction.keys[2].isSigner = false;
+    await this.expectFailure('缺少签名', [instruction], [this.payer], TokenError.Unauthorized);
+  }
+
+  /**
+   * 代币账户不归本程序所有
+   */
+  private async testWrongProgramOwner(mint: PublicKey): Promise<void> {
+    const foreignAccount = await this.createProgramOwnedAccount(82, SystemProgram.programId);
+    await this.expectFailure(
+      '账户所有者错误',
+      [createInitializeAccountInstruction(foreignAccount.publicKey, mint, this.payer.publicKey, this.programId)],
+      [this.payer],
+      'incorrect program id for instruction'
+    );
+  }
+
+  /**
+   * 代币账户与铸币不匹配：MintTo、Transfer、Burn 都必须拒绝其他铸币的账户
+   */
+  private async testMismatchedMint(mint: PublicKey, tokenAccount: PublicKey): Promise<void> {
+    const otherMint = await this.createProgramOwnedAccount();
+    const otherTokenAccount = await this.createProgramOwnedAccount();
+    await sendAndConfirmTransaction(this.connection, new Transaction().add(
+      createInitializeMintInstruction(otherMint.publicKey, 6, this.payer.publicKey, null, this.programId),
+      createInitializeAccountInstruction(
+        otherTokenAccount.publicKey, otherMint.publicKey, this.payer.publicKey, this.programId
+      )
+    ), [this.payer]);
+    await this.expectFailure(
+      '铸币不匹配 (MintTo)',
+      [createMintToInstruction(otherMint.publicKey, tokenAccount, this.payer.publicKey, BigInt(1), this.programId)],
+      [this.payer],
+      TokenError.MintMismatch
+    );
+    await this.expectFailure(
+      '铸币不匹配 (Transfer)',
+      [createTransferInstruction(
+        tokenAccount, otherTokenAccount.publicKey, this.payer.publicKey, BigInt(1), mint, this.programId
+      )],
+      [this.payer],
+      TokenError.MintMismatch
+    );
+    await this.expectFailure(
+      '铸币不匹配 (Burn)',
+      [createBurnInstruction(tokenAccount, otherMint.publicKey, this.payer.publicKey, BigInt(1), this.programId)],
+      [this.payer],
+      TokenError.MintMismatch
+    );
+  }
+
+  /**
+   * 转账金额超过余额
+   */
+  private async testInsufficientFunds(
+    mint: PublicKey, source: PublicKey, destination: PublicKey, balance: bigint
+  ): Promise<void> {
+    await this.expectFailure(
+      '余额不足',
+      [createTransferInstruction(source, destination, this.payer.publicKey, balance + BigInt(1), mint, this.programId)],
+      [this.payer],
+      TokenError.InsufficientFunds
+    );
+  }
+
+  /**
+   * 指令数据缺少 amount 字段
+   */
+  private async testDataTooShort(mint: PublicKey, tokenAccount: PublicKey): Promise<void> {
+    const instruction = createMintToInstruction(mint, tokenAccount, this.payer.publicKey, BigInt(1), this.programId);
+    instruction.data = Buffer.from([TokenInstruction.MintTo, 1, 0, 0]);
+    await this.expectFailure('指令数据过短', [instruction], [this.payer], TokenError.InvalidInstruction);
+  }
+
+  /**
+   * 指令数据在 amount 之后带有多余字节
+   */
+  private async testTrailingData(mint: PublicKey, tokenAccount: PublicKey): Promise<void> {
+    const instruction = createMintToInstruction(mint, tokenAccount, this.payer.publicKey, BigInt(1), this.programId);
+    instruction.data = Buffer.concat([instruction.data, Buffer.from([0])]);
+    await this.expectFailure('指令数据含多余字节', [instruction], [this.payer], TokenError.InvalidInstructionData);
+  }
+
+  /**
+   * 非铸币权限尝试修改铸币权限
+   */
+  private async testUnauthorizedAuthority(mint: PublicKey): Promise<void> {
+    const attacker = Keypair.generate();
+    await this.expectFailure(
+      '无权限修改铸币权限',
+      [createSetMintAuthorityInstruction(mint, attacker.publicKey, attacker.publicKey, this.programId)],
+      [this.payer, attacker],
+      TokenError.Unauthorized
+    );
+  }
+
+  /**
+   * 超出速度限制被冻结的账户不能再转出
+   */
+  private async testFrozenAccount(mint: PublicKey, frozenAccount: PublicKey, counterparty: PublicKey): Promise<void> {
+    try {
+      await sendAndConfirmTransaction(this.connection, new Transaction().add(
+        createTransferInstruction(counterparty, frozenAccount, this.payer.publicKey, BigInt(10), mint, this.programId),
+        createSetVelocityLimitInstruction(
+          frozenAccount, mint, this.payer.publicKey, this.payer.publicKey, BigInt(1), BigInt(1000), this.programId
+        ),
+        // 超出上限：转账被放弃，账户被冻结
+        createTransferInstruction(
+          frozenAccount, counterparty, this.payer.publicKey, BigInt(2), mint, this.programId, undefined, null, true
+        )
+      ), [this.payer]);
+    } catch (error) {
+      this.recordTestResult('冻结账户', false, error instanceof Error ? error.message : String(error));
+      return;
+    }
+
+    await this.expectFailure(
+      '冻结账户',
+      [createTransferInstruction(
+        frozenAccount, counterparty, this.payer.publicKey, BigInt(1), mint, this.programId, undefined, null, true
+      )],
+      [this.payer],
+      TokenError.AccountFrozen
+    );
+  }
+
+  private recordTestResult(name: string, success: boolean, details?: any): void {
+    this.testResults.push({
+      name,
+      success,
+      details: success ? details : { error: details }
+    });
+  }
+
+  private printTestReport(): void {
+    const passedTests = this.testResults.filter(result => result.success).length;
+    console.log('\n📊 负面测试报告');
+    console.log(`✅ 通过: ${passedTests} / ${this.testResults.length}`);
+    this.testResults.forEach((result, index) => {
+      const status = result.success ? '✅' : '❌';
+      console.log(`  ${index + 1}. ${status} ${result.name}`);
+      if (!result.success && result.details?.error) {
+        console.log(`     错误: ${result.details.error}`);
+      }
+    });
+    if (passedTests !== this.testResults.length) {
+      process.exitCode = 1;
+    }
+  }
+}
+
+async function main() {
+  const programId = process.argv[2] || process.env.TOKEN_PROGRAM_ID || TOKEN_PROGRAM_ID.toBase58();
+  console.log(`🔧 程序ID: ${programId}`);
+
+  await new NegativeTester(programId).runAllTests();
+}
+
+main().catch(error => {
+  console.error('💥 负面测试执行过程中发生致命错误:', error);
+  process.exit(1);
+});
@@ -22,8 +22,14 @@ import {
   createTransferInstruction,
   createBurnInstruction,
   createSetMintAuthorityInstruction,
+  createInitializeCurveInstruction,
+  createBuyFromCurveInstruction,
+  createSellToCurveInstruction,
+  findCurveVaultAddress,
+  isOwnedByPda,
   getMintData,
-  getTokenAccountData
+  getTokenAccountData,
+  TOKEN_PROGRAM_ID
 } from './utils';
 
 /**
@@ -65,6 +71,8 @@ class TokenProgramTester {
       await this.testTransfer(accounts);
       await this.testBurn(accounts);
       await this.testSetMintAuthority(accounts);
+      await this.testBondingCurve();
+      await this.testPdaOwnedAccount();
       await this.testErrorCases(accounts);
       
       // 4. 输出测试报告
@@ -356,6 +364,7 @@ class TokenProgramTester {
         accounts.receiverTokenAccount, // 目标账户
         this.payer.publicKey, // 所有者（签名者）
         transferAmount,
+        accounts.mint,
         this.programId
       ));
       
@@ -511,6 +520,154 @@ class TokenProgramTester {
     }
   }
 
+  /**
+   * 创建一个由程序拥有的空账户
+   */
+  private async createProgramOwnedAccount(space = 82): Promise<Keypair> {
+    const account = Keypair.generate();
+    const transaction = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: this.payer.publicKey,
+        newAccountPubkey: account.publicKey,
+        lamports: await this.connection.getMinimumBalanceForRentExemption(space),
+        space,
+        programId: this.programId,
+      })
+    );
+
+    await sendAndConfirmTransaction(this.connection, transaction, [this.payer, account]);
+    return account;
+  }
+
+  /**
+   * 测试联合曲线买入/卖出
+   */
+  private async testBondingCurve(): Promise<void> {
+    console.log('\n🧪 测试: 联合曲线买入/卖出');
+
+    try {
+      const mint = await this.createProgramOwnedAccount();
+      const tokenAccount = await this.createProgramOwnedAccount();
+
+      const setupTransaction = new Transaction();
+      setupTransaction.add(createInitializeMintInstruction(
+        mint.publicKey, 6, this.payer.publicKey, null, this.programId
+      ));
+      setupTransaction.add(createInitializeAccountInstruction(
+        tokenAccount.publicKey, mint.publicKey, this.payer.publicKey, this.programId
+      ));
+      setupTransaction.add(createInitializeCurveInstruction(
+        mint.publicKey,
+        this.payer.publicKey,
+        this.payer.publicKey,
+        { kind: 'Linear', base_price: BigInt(1_000), slope: BigInt(10) },
+        this.programId
+      ));
+      await sendAndConfirmTransaction(this.connection, setupTransaction, [this.payer]);
+
+      // 买入 100 个代币，再卖出 40 个
+      const buyAmount = BigInt(100 * (10 ** 6));
+      const sellAmount = BigInt(40 * (10 ** 6));
+      const [vault] = findCurveVaultAddress(mint.publicKey, this.programId);
+      const vaultBefore = await this.connection.getBalance(vault);
+
+      await sendAndConfirmTransaction(this.connection, new Transaction().add(
+        createBuyFromCurveInstruction(
+          mint.publicKey, tokenAccount.publicKey, this.payer.publicKey,
+          buyAmount, BigInt(LAMPORTS_PER_SOL), this.programId
+        )
+      ), [this.payer]);
+      const vaultAfterBuy = await this.connection.getBalance(vault);
+
+      const signature = await sendAndConfirmTransaction(this.connection, new Transaction().add(
+        createSellToCurveInstruction(
+          mint.publicKey, tokenAccount.publicKey, this.payer.publicKey,
+          sellAmount, BigInt(0), this.programId
+        )
+      ), [this.payer]);
+      const vaultAfterSell = await this.connection.getBalance(vault);
+
+      const mintData = await getMintData(this.connection, mint.publicKey);
+      const tokenAccountData = await getTokenAccountData(this.connection, tokenAccount.publicKey);
+      const expected = buyAmount - sellAmount;
+
+      // 线性曲线: 买入 100 个代币成本 = 1000*100 + 10*100^2/2 = 150000 lamports
+      const isValid = mintData.supply === expected &&
+                     tokenAccountData.amount === expected &&
+                     vaultAfterBuy - vaultBefore === 150_000 &&
+                     vaultAfterSell < vaultAfterBuy;
+
+      if (isValid) {
+        this.recordTestResult('联合曲线买入/卖出', true, {
+          signature,
+          supply: mintData.supply.toString(),
+          vaultLamports: vaultAfterSell
+        });
+      } else {
+        throw new Error('联合曲线数据验证失败');
+      }
+
+    } catch (error) {
+      this.recordTestResult('联合曲线买入/卖出', false, error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  /**
+   * 测试 PDA 所有的代币账户：所有者是其他程序的 PDA，只能由该程序 invoke_signed 转出
+   */
+  private async testPdaOwnedAccount(): Promise<void> {
+    console.log('\n🧪 测试: PDA 所有的代币账户');
+
+    try {
+      // 用随机地址模拟金库程序，按 token-vault-example 的 seeds 推导所有者 PDA
+      const vaultProgramId = Keypair.generate().publicKey;
+      const mint = await this.createProgramOwnedAccount();
+      const vaultTokenAccount = await this.createProgramOwnedAccount();
+      const receiverTokenAccount = await this.createProgramOwnedAccount();
+      const seeds = [Buffer.from('vault_authority'), vaultTokenAccount.publicKey.toBuffer()];
+      const [vaultAuthority] = PublicKey.findProgramAddressSync(seeds, vaultProgramId);
+
+      const amount = BigInt(50 * (10 ** 6));
+      await sendAndConfirmTransaction(this.connection, new Transaction().add(
+        createInitializeMintInstruction(mint.publicKey, 6, this.payer.publicKey, null, this.programId),
+        createInitializeAccountInstruction(vaultTokenAccount.publicKey, mint.publicKey, vaultAuthority, this.programId),
+        createInitializeAccountInstruction(receiverTokenAccount.publicKey, mint.publicKey, this.payer.publicKey, this.programId),
+        createMintToInstruction(mint.publicKey, vaultTokenAccount.publicKey, this.payer.publicKey, amount, this.programId)
+      ), [this.payer]);
+
+      const vaultData = await getTokenAccountData(this.connection, vaultTokenAccount.publicKey);
+      if (!isOwnedByPda(vaultData, seeds, vaultProgramId) || isOwnedByPda(vaultData, seeds, this.programId)) {
+        throw new Error('PDA 所有者校验失败');
+      }
+
+      // 任何钱包都无法直接为 PDA 签名，转账必须被拒绝
+      let rejected = false;
+      try {
+        await sendAndConfirmTransaction(this.connection, new Transaction().add(
+          createTransferInstruction(
+            vaultTokenAccount.publicKey, receiverTokenAccount.publicKey, this.payer.publicKey, amount, mint.publicKey,
+            this.programId
+          )
+        ), [this.payer]);
+      } catch (error) {
+        rejected = true;
+      }
+
+      const afterData = await getTokenAccountData(this.connection, vaultTokenAccount.publicKey);
+      if (rejected && afterData.amount === amount) {
+        this.recordTestResult('PDA 所有的代币账户', true, {
+          owner: vaultAuthority.toString(),
+          amount: afterData.amount.toString()
+        });
+      } else {
+        throw new Error('PDA 所有的代币账户被非 PDA 签名者转出');
+      }
+
+    } catch (error) {
+      this.recordTestResult('PDA 所有的代币账户', false, error instanceof Error ? error.message : String(error));
+    }
+  }
+
   /**
    * 测试错误情况
    */
@@ -574,6 +731,7 @@ class TokenProgramTester {
         accounts.receiverTokenAccount,
         this.payer.publicKey,
         excessiveAmount,
+        accounts.mint,
         this.programId
       ));
       
@@ -678,7 +836,7 @@ async function main() {
   console.log('========================================');
   
   // 从命令行参数获取程序ID，或使用默认值
-  const programId = process.argv[2] || 'ByiUxkVUtZM8fHoVFM3wsWVmaxL43i81G8eAHWKbwBBu';
+  const programId = process.argv[2] || process.env.TOKEN_PROGRAM_ID || TOKEN_PROGRAM_ID.toBase58();
   
   console.log(`🔧 程序ID: ${programId}`);
   console.log('🌐 网络: Devnet');
@@ -11,6 +11,213 @@ export enum TokenInstruction {
   Transfer = 3,
   Burn = 4,
   SetMintAuthority = 5,
+  InitializeCurve = 6,
+  BuyFromCurve = 7,
+  SellToCurve = 8,
+  ConfigureOracle = 9,
+  MintAgainstCollateral = 10,
+  EnableRebase = 11,
+  Rebase = 12,
+  FundDistribution = 13,
+  ClaimDividend = 14,
+  DelegateVotes = 15,
+  GetVotesAt = 16,
+  TopUpRent = 17,
+  MintToNewAccount = 18,
+  BurnWithReceipt = 19,
+  InitializeNftMint = 20,
+  CreateMetadata = 21,
+  CreateMasterEdition = 22,
+  PrintEdition = 23,
+  SetTransferAllowlist = 24,
+  SetVelocityLimit = 25,
+  ThawAccount = 26,
+  QueueMint = 27,
+  ExecuteQueuedMint = 28,
+  CancelQueuedMint = 29,
+  InitializeTreasury = 30,
+  BurnTreasury = 31,
+  PlaceOrder = 32,
+  FillOrder = 33,
+  CancelOrder = 34,
+  CreateAuction = 35,
+  Purchase = 36,
+  InitializeSale = 37,
+  BuyTokens = 38,
+  WithdrawProceeds = 39,
+  InitializeShareVault = 40,
+  Deposit = 41,
+  Withdraw = 42,
+  DepositGoverningTokens = 43,
+  WithdrawGoverningTokens = 44,
+  InitializeEmergencyConfig = 45,
+  ProposeAuthorityRecovery = 46,
+  ExecuteAuthorityRecovery = 47,
+  CancelAuthorityRecovery = 48,
+  EnableAutoClose = 49,
+  CloseEmptyMint = 50,
+  TransferSplit = 51,
+  CreateHtlc = 52,
+  RedeemHtlc = 53,
+  RefundHtlc = 54,
+  AtomicSwap = 55,
+  PermitApprove = 56,
+  TransferFrom = 57,
+  TransferWithPermit = 58,
+  CreateSession = 59,
+  RevokeSession = 60,
+  SessionTransfer = 61,
+  SetGuardians = 62,
+  ProposeOwnerRecovery = 63,
+  CancelOwnerRecovery = 64,
+  RecoverOwner = 65,
+  EnableHolderCount = 66,
+  EnableActivityTracking = 67,
+  EnableMemoLog = 68,
+  TransferWithMemo = 69,
+  SweepAll = 70,
+  SettleBatch = 71,
+  InitializeCompressedTree = 72,
+  Compress = 73,
+  CompressedTransfer = 74,
+  Decompress = 75,
+  ConfigureBridge = 76,
+  BridgeBurn = 77,
+  BridgeMint = 78,
+  RescaleMint = 79,
+  GlobalFreeze = 80,
+  GlobalThaw = 81,
+  FreezeAccount = 82,
+  SetAuthority = 83,
+  SetFreezeExempt = 84,
+  CreateConditionalTransfer = 85,
+  ReleaseConditionalTransfer = 86,
+  RefundConditionalTransfer = 87,
+  ApproveMulti = 88,
+  SetAccountLabel = 89,
+  CreateMintWithSeed = 90,
+  GetExtensionTypes = 91,
+  InitializeAccountWithOwnerCheck = 92,
+  Crank = 93,
+  CreatePreAuthorization = 94,
+  Debit = 95,
+  ClosePreAuthorization = 96,
+  InitializeSavingsVault = 97,
+  FundYield = 98,
+  DepositSavings = 99,
+  WithdrawSavings = 100,
+  InitializeEmissions = 101,
+  DistributeEmissions = 102,
+  EnablePointsExpiry = 103,
+  ExpirePoints = 104,
+  SetDormancyPolicy = 105,
+  WarnDormant = 106,
+  SweepDormant = 107,
+  UpdateAttestation = 108,
+  VerifySupplyBacked = 109,
+  InitializeConversion = 110,
+  ConvertMint = 111,
+  Rebalance = 112,
+  SetPauseFlags = 113,
+  SetActivationRequired = 114,
+  ActivateAccount = 115,
+}
+
+/**
+ * 程序自定义错误码，与 Rust 端 TokenError 声明顺序一致（ProgramError::Custom(code)）
+ */
+export enum TokenError {
+  InvalidInstruction = 0,
+  NotRentExempt = 1,
+  InsufficientFunds = 2,
+  Unauthorized = 3,
+  MintMismatch = 4,
+  AccountFrozen = 5,
+  Overflow = 6,
+  SlippageExceeded = 7,
+  InvalidCurve = 8,
+  InvalidOracle = 9,
+  StaleOraclePrice = 10,
+  InvalidRebase = 11,
+  InvalidProof = 12,
+  AlreadyClaimed = 13,
+  CheckpointUnavailable = 14,
+  ReceiptAlreadyExists = 15,
+  NftSupplyExceeded = 16,
+  InvalidMetadata = 17,
+  NotNftMint = 18,
+  EditionSupplyExceeded = 19,
+  TransferProgramNotAllowed = 20,
+  TimelockNotElapsed = 21,
+  InvalidAuction = 22,
+  AuctionSoldOut = 23,
+  SaleCapReached = 24,
+  UnsupportedInstructionVersion = 25,
+  AuthorityRevoked = 26,
+  HtlcExpired = 27,
+  InvalidInstructionData = 28,
+  RescaleEpochMismatch = 29,
+  OwnerOffCurve = 30,
+  OwnerOnCurve = 31,
+  OperationPaused = 32,
+  AccountNotActivated = 33,
+}
+
+/**
+ * 铸币上的权限类型，对应 Rust 端 AuthorityType
+ */
+export enum AuthorityType {
+  MintTokens = 0,
+  FreezeAccount = 1,
+  BurnTokens = 2,
+}
+
+/**
+ * InitializeAccountWithOwnerCheck 对所有者地址的要求，对应 Rust 端 OwnerCurve
+ */
+export enum OwnerCurve {
+  /** 在曲线上：持有私钥的钱包 */
+  OnCurve = 0,
+  /** 不在曲线上：PDA */
+  OffCurve = 1,
+}
+
+/**
+ * Crank 可清理的到期状态，对应 Rust 端 CrankItem
+ */
+export enum CrankItem {
+  ExpiredSession = 0,
+  ExpiredHtlc = 1,
+  MaturedThaw = 2,
+}
+
+/**
+ * Crank 每关闭一个 PDA 付给调用者的奖励，与 Rust 中的 CRANK_REWARD_LAMPORTS 一致
+ */
+export const CRANK_REWARD_LAMPORTS = 5000;
+
+/**
+ * 铸币 / 代币账户扩展类型，对应 Rust 端 extension::ExtensionType，GetExtensionTypes 位图的第 n 位即类型 n
+ */
+export enum ExtensionType {
+  MintCloseConfig = 1,
+  GuardianConfig = 2,
+  HolderCount = 3,
+  AccountActivity = 4,
+  MemoLog = 5,
+  DecimalsRescale = 6,
+  RescaleEpoch = 7,
+  GlobalFreezeState = 8,
+  ScheduledThaw = 9,
+  BurnAuthority = 10,
+  FreezeExempt = 11,
+  AccountLabel = 12,
+  PointsConfig = 13,
+  PointsLots = 14,
+  DormancyPolicy = 15,
+  PauseState = 16,
+  ActivationPolicy = 17,
+  AccountActivation = 18,
 }
 
 /**
@@ -76,138 +283,2749 @@ export class SetMintAuthorityData {
   instruction: TokenInstruction = TokenInstruction.SetMintAuthority;
   new_authority: Uint8Array | null;
 
-  constructor(fields: { new_authority: Uint8Array | null }) {
-    this.new_authority = fields.new_authority;
+  constructor(fields: { new_authority: Uint8Array | null }) {
+    this.new_authority = fields.new_authority;
+  }
+}
+
+/**
+ * 联合曲线类型（对应 Rust 中的 CurveType 枚举）
+ */
+export type CurveType =
+  | { kind: 'Linear'; base_price: bigint; slope: bigint }
+  | { kind: 'Exponential'; base_price: bigint; growth_bps: number };
+
+/**
+ * 初始化联合曲线指令数据结构
+ */
+export class InitializeCurveData {
+  instruction: TokenInstruction = TokenInstruction.InitializeCurve;
+  curve: CurveType;
+
+  constructor(fields: { curve: CurveType }) {
+    this.curve = fields.curve;
+  }
+}
+
+/**
+ * 曲线购买指令数据结构
+ */
+export class BuyFromCurveData {
+  instruction: TokenInstruction = TokenInstruction.BuyFromCurve;
+  amount: bigint;
+  max_lamports: bigint;
+
+  constructor(fields: { amount: bigint; max_lamports: bigint }) {
+    this.amount = fields.amount;
+    this.max_lamports = fields.max_lamports;
+  }
+}
+
+/**
+ * 曲线卖出指令数据结构
+ */
+export class SellToCurveData {
+  instruction: TokenInstruction = TokenInstruction.SellToCurve;
+  amount: bigint;
+  min_lamports: bigint;
+
+  constructor(fields: { amount: bigint; min_lamports: bigint }) {
+    this.amount = fields.amount;
+    this.min_lamports = fields.min_lamports;
+  }
+}
+
+/**
+ * 配置预言机指令数据结构
+ */
+export class ConfigureOracleData {
+  instruction: TokenInstruction = TokenInstruction.ConfigureOracle;
+  price_feed: Uint8Array | null;
+  max_staleness_slots: bigint;
+  collateral_ratio_bps: number;
+
+  constructor(fields: {
+    price_feed: Uint8Array | null;
+    max_staleness_slots: bigint;
+    collateral_ratio_bps: number;
+  }) {
+    this.price_feed = fields.price_feed;
+    this.max_staleness_slots = fields.max_staleness_slots;
+    this.collateral_ratio_bps = fields.collateral_ratio_bps;
+  }
+}
+
+/**
+ * 抵押铸造指令数据结构
+ */
+export class MintAgainstCollateralData {
+  instruction: TokenInstruction = TokenInstruction.MintAgainstCollateral;
+  collateral_lamports: bigint;
+  min_amount: bigint;
+
+  constructor(fields: { collateral_lamports: bigint; min_amount: bigint }) {
+    this.collateral_lamports = fields.collateral_lamports;
+    this.min_amount = fields.min_amount;
+  }
+}
+
+/**
+ * rebase 指令数据结构
+ */
+export class RebaseData {
+  instruction: TokenInstruction = TokenInstruction.Rebase;
+  numerator: bigint;
+  denominator: bigint;
+
+  constructor(fields: { numerator: bigint; denominator: bigint }) {
+    this.numerator = fields.numerator;
+    this.denominator = fields.denominator;
+  }
+}
+
+/**
+ * 注入分红指令数据结构
+ */
+export class FundDistributionData {
+  instruction: TokenInstruction = TokenInstruction.FundDistribution;
+  distribution_id: bigint;
+  snapshot_root: Uint8Array;
+  snapshot_supply: bigint;
+  amount: bigint;
+
+  constructor(fields: {
+    distribution_id: bigint;
+    snapshot_root: Uint8Array;
+    snapshot_supply: bigint;
+    amount: bigint;
+  }) {
+    this.distribution_id = fields.distribution_id;
+    this.snapshot_root = fields.snapshot_root;
+    this.snapshot_supply = fields.snapshot_supply;
+    this.amount = fields.amount;
+  }
+}
+
+/**
+ * 领取分红指令数据结构
+ */
+export class ClaimDividendData {
+  instruction: TokenInstruction = TokenInstruction.ClaimDividend;
+  snapshot_balance: bigint;
+  proof: Uint8Array[];
+
+  constructor(fields: { snapshot_balance: bigint; proof: Uint8Array[] }) {
+    this.snapshot_balance = fields.snapshot_balance;
+    this.proof = fields.proof;
+  }
+}
+
+/**
+ * 委托投票指令数据结构
+ */
+export class DelegateVotesData {
+  instruction: TokenInstruction = TokenInstruction.DelegateVotes;
+  delegatee: Uint8Array | null;
+
+  constructor(fields: { delegatee: Uint8Array | null }) {
+    this.delegatee = fields.delegatee;
+  }
+}
+
+/**
+ * 查询历史投票权指令数据结构
+ */
+export class GetVotesAtData {
+  instruction: TokenInstruction = TokenInstruction.GetVotesAt;
+  slot: bigint;
+
+  constructor(fields: { slot: bigint }) {
+    this.slot = fields.slot;
+  }
+}
+
+/**
+ * 补充租金指令数据结构
+ */
+export class TopUpRentData {
+  instruction: TokenInstruction = TokenInstruction.TopUpRent;
+  lamports: bigint;
+
+  constructor(fields: { lamports: bigint }) {
+    this.lamports = fields.lamports;
+  }
+}
+
+/**
+ * 创建关联账户并铸造指令数据结构
+ */
+export class MintToNewAccountData {
+  instruction: TokenInstruction = TokenInstruction.MintToNewAccount;
+  amount: bigint;
+
+  constructor(fields: { amount: bigint }) {
+    this.amount = fields.amount;
+  }
+}
+
+/**
+ * 带凭证销毁指令数据结构
+ */
+export class BurnWithReceiptData {
+  instruction: TokenInstruction = TokenInstruction.BurnWithReceipt;
+  amount: bigint;
+  receipt_id: bigint;
+
+  constructor(fields: { amount: bigint; receipt_id: bigint }) {
+    this.amount = fields.amount;
+    this.receipt_id = fields.receipt_id;
+  }
+}
+
+/**
+ * 初始化 NFT 铸币账户指令数据结构（decimals 固定为 0）
+ */
+export class InitializeNftMintData {
+  instruction: TokenInstruction = TokenInstruction.InitializeNftMint;
+  mint_authority: Uint8Array;
+  freeze_authority: Uint8Array | null;
+
+  constructor(fields: { mint_authority: Uint8Array; freeze_authority: Uint8Array | null }) {
+    this.mint_authority = fields.mint_authority;
+    this.freeze_authority = fields.freeze_authority;
+  }
+}
+
+/**
+ * Metaplex 元数据内容（对应 Rust 中的 MetadataArgs）
+ */
+export interface MetadataArgs {
+  name: string;
+  symbol: string;
+  uri: string;
+  seller_fee_basis_points: number;
+}
+
+/**
+ * 创建 Metaplex 元数据指令数据结构
+ */
+export class CreateMetadataData {
+  instruction: TokenInstruction = TokenInstruction.CreateMetadata;
+  args: MetadataArgs;
+  is_mutable: boolean;
+
+  constructor(fields: { args: MetadataArgs; is_mutable: boolean }) {
+    this.args = fields.args;
+    this.is_mutable = fields.is_mutable;
+  }
+}
+
+/**
+ * 创建母版版本指令数据结构
+ */
+export class CreateMasterEditionData {
+  instruction: TokenInstruction = TokenInstruction.CreateMasterEdition;
+  max_supply: bigint | null;
+
+  constructor(fields: { max_supply: bigint | null }) {
+    this.max_supply = fields.max_supply;
+  }
+}
+
+/**
+ * 设置 NFT 转账程序白名单指令数据结构
+ */
+export class SetTransferAllowlistData {
+  instruction: TokenInstruction = TokenInstruction.SetTransferAllowlist;
+  programs: Uint8Array[];
+
+  constructor(fields: { programs: Uint8Array[] }) {
+    this.programs = fields.programs;
+  }
+}
+
+/**
+ * 设置转账速度限制指令数据结构
+ */
+export class SetVelocityLimitData {
+  instruction: TokenInstruction = TokenInstruction.SetVelocityLimit;
+  max_amount: bigint;
+  window_slots: bigint;
+
+  constructor(fields: { max_amount: bigint; window_slots: bigint }) {
+    this.max_amount = fields.max_amount;
+    this.window_slots = fields.window_slots;
+  }
+}
+
+/**
+ * 排队铸造指令数据结构
+ */
+export class QueueMintData {
+  instruction: TokenInstruction = TokenInstruction.QueueMint;
+  amount: bigint;
+  destination: Uint8Array;
+
+  constructor(fields: { amount: bigint; destination: Uint8Array }) {
+    this.amount = fields.amount;
+    this.destination = fields.destination;
+  }
+}
+
+export class PlaceOrderData {
+  instruction: TokenInstruction = TokenInstruction.PlaceOrder;
+  give_amount: bigint;
+  want_mint: Uint8Array;
+  want_amount: bigint;
+
+  constructor(fields: { give_amount: bigint; want_mint: Uint8Array; want_amount: bigint }) {
+    this.give_amount = fields.give_amount;
+    this.want_mint = fields.want_mint;
+    this.want_amount = fields.want_amount;
+  }
+}
+
+export class CreateAuctionData {
+  instruction: TokenInstruction = TokenInstruction.CreateAuction;
+  start_price: bigint;
+  floor_price: bigint;
+  decay: bigint;
+  supply: bigint;
+
+  constructor(fields: { start_price: bigint; floor_price: bigint; decay: bigint; supply: bigint }) {
+    this.start_price = fields.start_price;
+    this.floor_price = fields.floor_price;
+    this.decay = fields.decay;
+    this.supply = fields.supply;
+  }
+}
+
+export class PurchaseData {
+  instruction: TokenInstruction = TokenInstruction.Purchase;
+  amount: bigint;
+  max_lamports: bigint;
+
+  constructor(fields: { amount: bigint; max_lamports: bigint }) {
+    this.amount = fields.amount;
+    this.max_lamports = fields.max_lamports;
+  }
+}
+
+export class InitializeSaleData {
+  instruction: TokenInstruction = TokenInstruction.InitializeSale;
+  price_lamports_per_token: bigint;
+  cap: bigint;
+
+  constructor(fields: { price_lamports_per_token: bigint; cap: bigint }) {
+    this.price_lamports_per_token = fields.price_lamports_per_token;
+    this.cap = fields.cap;
+  }
+}
+
+export class BuyTokensData {
+  instruction: TokenInstruction = TokenInstruction.BuyTokens;
+  amount: bigint;
+
+  constructor(fields: { amount: bigint }) {
+    this.amount = fields.amount;
+  }
+}
+
+export class DepositData {
+  instruction: TokenInstruction = TokenInstruction.Deposit;
+  amount: bigint;
+
+  constructor(fields: { amount: bigint }) {
+    this.amount = fields.amount;
+  }
+}
+
+export class WithdrawData {
+  instruction: TokenInstruction = TokenInstruction.Withdraw;
+  shares: bigint;
+
+  constructor(fields: { shares: bigint }) {
+    this.shares = fields.shares;
+  }
+}
+
+export class GoverningTokensData {
+  instruction: TokenInstruction.DepositGoverningTokens | TokenInstruction.WithdrawGoverningTokens;
+  realm: Uint8Array;
+  amount: bigint;
+
+  constructor(fields: {
+    instruction: TokenInstruction.DepositGoverningTokens | TokenInstruction.WithdrawGoverningTokens;
+    realm: Uint8Array;
+    amount: bigint;
+  }) {
+    this.instruction = fields.instruction;
+    this.realm = fields.realm;
+    this.amount = fields.amount;
+  }
+}
+
+export class InitializeEmergencyConfigData {
+  instruction: TokenInstruction = TokenInstruction.InitializeEmergencyConfig;
+  emergency_admin: Uint8Array;
+
+  constructor(fields: { emergency_admin: Uint8Array }) {
+    this.emergency_admin = fields.emergency_admin;
+  }
+}
+
+export class ProposeAuthorityRecoveryData {
+  instruction: TokenInstruction = TokenInstruction.ProposeAuthorityRecovery;
+  authority_type: AuthorityType;
+  new_authority: Uint8Array;
+
+  constructor(fields: { authority_type: AuthorityType; new_authority: Uint8Array }) {
+    this.authority_type = fields.authority_type;
+    this.new_authority = fields.new_authority;
+  }
+}
+
+export class EnableAutoCloseData {
+  instruction: TokenInstruction = TokenInstruction.EnableAutoClose;
+  close_destination: Uint8Array;
+
+  constructor(fields: { close_destination: Uint8Array }) {
+    this.close_destination = fields.close_destination;
+  }
+}
+
+export class TransferSplitData {
+  instruction: TokenInstruction = TokenInstruction.TransferSplit;
+  total: bigint;
+  shares: number[];
+
+  constructor(fields: { total: bigint; shares: number[] }) {
+    this.total = fields.total;
+    this.shares = fields.shares;
+  }
+}
+
+export class CreateHtlcData {
+  instruction: TokenInstruction = TokenInstruction.CreateHtlc;
+  hash: Uint8Array;
+  amount: bigint;
+  timeout: bigint;
+
+  constructor(fields: { hash: Uint8Array; amount: bigint; timeout: bigint }) {
+    this.hash = fields.hash;
+    this.amount = fields.amount;
+    this.timeout = fields.timeout;
+  }
+}
+
+export class RedeemHtlcData {
+  instruction: TokenInstruction = TokenInstruction.RedeemHtlc;
+  preimage: Uint8Array;
+
+  constructor(fields: { preimage: Uint8Array }) {
+    this.preimage = fields.preimage;
+  }
+}
+
+export class CreateConditionalTransferData {
+  instruction: TokenInstruction = TokenInstruction.CreateConditionalTransfer;
+  arbiter: Uint8Array;
+  amount: bigint;
+
+  constructor(fields: { arbiter: Uint8Array; amount: bigint }) {
+    this.arbiter = fields.arbiter;
+    this.amount = fields.amount;
+  }
+}
+
+export class AtomicSwapData {
+  instruction: TokenInstruction = TokenInstruction.AtomicSwap;
+  amount_a: bigint;
+  amount_b: bigint;
+
+  constructor(fields: { amount_a: bigint; amount_b: bigint }) {
+    this.amount_a = fields.amount_a;
+    this.amount_b = fields.amount_b;
+  }
+}
+
+export class PermitApproveData {
+  instruction: TokenInstruction = TokenInstruction.PermitApprove;
+  delegate: Uint8Array;
+  amount: bigint;
+  nonce: bigint;
+  deadline: bigint;
+
+  constructor(fields: { delegate: Uint8Array; amount: bigint; nonce: bigint; deadline: bigint }) {
+    this.delegate = fields.delegate;
+    this.amount = fields.amount;
+    this.nonce = fields.nonce;
+    this.deadline = fields.deadline;
+  }
+}
+
+export class TransferFromData {
+  instruction: TokenInstruction = TokenInstruction.TransferFrom;
+  amount: bigint;
+
+  constructor(fields: { amount: bigint }) {
+    this.amount = fields.amount;
+  }
+}
+
+export class TransferWithPermitData {
+  instruction: TokenInstruction = TokenInstruction.TransferWithPermit;
+  amount: bigint;
+  tip: bigint;
+  nonce: bigint;
+  deadline: bigint;
+
+  constructor(fields: { amount: bigint; tip: bigint; nonce: bigint; deadline: bigint }) {
+    this.amount = fields.amount;
+    this.tip = fields.tip;
+    this.nonce = fields.nonce;
+    this.deadline = fields.deadline;
+  }
+}
+
+export class CreateSessionData {
+  instruction: TokenInstruction = TokenInstruction.CreateSession;
+  key: Uint8Array;
+  max_amount: bigint;
+  expires_at_slot: bigint;
+
+  constructor(fields: { key: Uint8Array; max_amount: bigint; expires_at_slot: bigint }) {
+    this.key = fields.key;
+    this.max_amount = fields.max_amount;
+    this.expires_at_slot = fields.expires_at_slot;
+  }
+}
+
+export class SessionTransferData {
+  instruction: TokenInstruction = TokenInstruction.SessionTransfer;
+  amount: bigint;
+
+  constructor(fields: { amount: bigint }) {
+    this.amount = fields.amount;
+  }
+}
+
+export class SetGuardiansData {
+  instruction: TokenInstruction = TokenInstruction.SetGuardians;
+  guardians: Uint8Array[];
+  threshold: number;
+
+  constructor(fields: { guardians: Uint8Array[]; threshold: number }) {
+    this.guardians = fields.guardians;
+    this.threshold = fields.threshold;
+  }
+}
+
+export class ProposeOwnerRecoveryData {
+  instruction: TokenInstruction = TokenInstruction.ProposeOwnerRecovery;
+  new_owner: Uint8Array;
+
+  constructor(fields: { new_owner: Uint8Array }) {
+    this.new_owner = fields.new_owner;
+  }
+}
+
+export class SweepAllData {
+  instruction: TokenInstruction = TokenInstruction.SweepAll;
+  source_count: number;
+
+  constructor(fields: { source_count: number }) {
+    this.source_count = fields.source_count;
+  }
+}
+
+export class SettleBatchData {
+  instruction: TokenInstruction = TokenInstruction.SettleBatch;
+  deltas: bigint[];
+
+  constructor(fields: { deltas: bigint[] }) {
+    this.deltas = fields.deltas;
+  }
+}
+
+export class ApproveMultiData {
+  instruction: TokenInstruction = TokenInstruction.ApproveMulti;
+  approvals: { delegate: Uint8Array; amount: bigint }[];
+
+  constructor(fields: { approvals: { delegate: Uint8Array; amount: bigint }[] }) {
+    this.approvals = fields.approvals;
+  }
+}
+
+export class SetAccountLabelData {
+  instruction: TokenInstruction = TokenInstruction.SetAccountLabel;
+  label: Uint8Array;
+
+  constructor(fields: { label: Uint8Array }) {
+    this.label = fields.label;
+  }
+}
+
+export class CreateMintWithSeedData {
+  instruction: TokenInstruction = TokenInstruction.CreateMintWithSeed;
+  seed: string;
+  decimals: number;
+  freeze_authority: Uint8Array | null;
+
+  constructor(fields: { seed: string; decimals: number; freeze_authority: Uint8Array | null }) {
+    this.seed = fields.seed;
+    this.decimals = fields.decimals;
+    this.freeze_authority = fields.freeze_authority;
+  }
+}
+
+export class GetExtensionTypesData {
+  instruction: TokenInstruction = TokenInstruction.GetExtensionTypes;
+  is_mint: boolean;
+
+  constructor(fields: { is_mint: boolean }) {
+    this.is_mint = fields.is_mint;
+  }
+}
+
+export class InitializeAccountWithOwnerCheckData {
+  instruction: TokenInstruction = TokenInstruction.InitializeAccountWithOwnerCheck;
+  owner_curve: OwnerCurve;
+
+  constructor(fields: { owner_curve: OwnerCurve }) {
+    this.owner_curve = fields.owner_curve;
+  }
+}
+
+export class CrankData {
+  instruction: TokenInstruction = TokenInstruction.Crank;
+  items: CrankItem[];
+
+  constructor(fields: { items: CrankItem[] }) {
+    this.items = fields.items;
+  }
+}
+
+export class CreatePreAuthorizationData {
+  instruction: TokenInstruction = TokenInstruction.CreatePreAuthorization;
+  merchant: Uint8Array;
+  amount_per_cycle: bigint;
+  cycle_slots: bigint;
+
+  constructor(fields: { merchant: Uint8Array; amount_per_cycle: bigint; cycle_slots: bigint }) {
+    this.merchant = fields.merchant;
+    this.amount_per_cycle = fields.amount_per_cycle;
+    this.cycle_slots = fields.cycle_slots;
+  }
+}
+
+export class DebitData {
+  instruction: TokenInstruction = TokenInstruction.Debit;
+  amount: bigint;
+
+  constructor(fields: { amount: bigint }) {
+    this.amount = fields.amount;
+  }
+}
+
+export class InitializeSavingsVaultData {
+  instruction: TokenInstruction = TokenInstruction.InitializeSavingsVault;
+  yield_authority: Uint8Array;
+
+  constructor(fields: { yield_authority: Uint8Array }) {
+    this.yield_authority = fields.yield_authority;
+  }
+}
+
+export class SavingsAmountData {
+  instruction: TokenInstruction.FundYield | TokenInstruction.DepositSavings;
+  amount: bigint;
+
+  constructor(fields: { instruction: TokenInstruction.FundYield | TokenInstruction.DepositSavings; amount: bigint }) {
+    this.instruction = fields.instruction;
+    this.amount = fields.amount;
+  }
+}
+
+export class WithdrawSavingsData {
+  instruction: TokenInstruction = TokenInstruction.WithdrawSavings;
+  shares: bigint;
+
+  constructor(fields: { shares: bigint }) {
+    this.shares = fields.shares;
+  }
+}
+
+/**
+ * 排放接收账户及其分配权重，对应 Rust 中的 EmissionRecipient
+ */
+export interface EmissionRecipient {
+  vault: Uint8Array;
+  weight: number;
+}
+
+/**
+ * 每个排放计划最多登记的接收账户数
+ */
+export const MAX_EMISSION_RECIPIENTS = 8;
+
+export class EnablePointsExpiryData {
+  instruction: TokenInstruction = TokenInstruction.EnablePointsExpiry;
+  lifetime_slots: bigint;
+
+  constructor(fields: { lifetime_slots: bigint }) {
+    this.lifetime_slots = fields.lifetime_slots;
+  }
+}
+
+export class SetDormancyPolicyData {
+  instruction: TokenInstruction = TokenInstruction.SetDormancyPolicy;
+  recovery_vault: Uint8Array;
+  dormant_epochs: bigint;
+  notice_epochs: bigint;
+
+  constructor(fields: { recovery_vault: Uint8Array; dormant_epochs: bigint; notice_epochs: bigint }) {
+    this.recovery_vault = fields.recovery_vault;
+    this.dormant_epochs = fields.dormant_epochs;
+    this.notice_epochs = fields.notice_epochs;
+  }
+}
+
+export class UpdateAttestationData {
+  instruction: TokenInstruction = TokenInstruction.UpdateAttestation;
+  reserves: bigint;
+
+  constructor(fields: { reserves: bigint }) {
+    this.reserves = fields.reserves;
+  }
+}
+
+export class InitializeConversionData {
+  instruction: TokenInstruction = TokenInstruction.InitializeConversion;
+  rate: bigint;
+
+  constructor(fields: { rate: bigint }) {
+    this.rate = fields.rate;
+  }
+}
+
+export class ConvertMintData {
+  instruction: TokenInstruction = TokenInstruction.ConvertMint;
+  amount: bigint;
+
+  constructor(fields: { amount: bigint }) {
+    this.amount = fields.amount;
+  }
+}
+
+export class RebalanceData {
+  instruction: TokenInstruction = TokenInstruction.Rebalance;
+  amount: bigint;
+
+  constructor(fields: { amount: bigint }) {
+    this.amount = fields.amount;
+  }
+}
+
+/**
+ * 分项暂停标志，null 表示该项保持不变
+ */
+export class SetPauseFlagsData {
+  instruction: TokenInstruction = TokenInstruction.SetPauseFlags;
+  pause_transfers: boolean | null;
+  pause_minting: boolean | null;
+  pause_burning: boolean | null;
+
+  constructor(fields: {
+    pause_transfers: boolean | null;
+    pause_minting: boolean | null;
+    pause_burning: boolean | null;
+  }) {
+    this.pause_transfers = fields.pause_transfers;
+    this.pause_minting = fields.pause_minting;
+    this.pause_burning = fields.pause_burning;
+  }
+}
+
+export class SetActivationRequiredData {
+  instruction: TokenInstruction = TokenInstruction.SetActivationRequired;
+  required: boolean;
+
+  constructor(fields: { required: boolean }) {
+    this.required = fields.required;
+  }
+}
+
+export class InitializeEmissionsData {
+  instruction: TokenInstruction = TokenInstruction.InitializeEmissions;
+  tokens_per_slot: bigint;
+  start_slot: bigint;
+  end_slot: bigint;
+  recipients: EmissionRecipient[];
+
+  constructor(fields: {
+    tokens_per_slot: bigint;
+    start_slot: bigint;
+    end_slot: bigint;
+    recipients: EmissionRecipient[];
+  }) {
+    this.tokens_per_slot = fields.tokens_per_slot;
+    this.start_slot = fields.start_slot;
+    this.end_slot = fields.end_slot;
+    this.recipients = fields.recipients;
+  }
+}
+
+/**
+ * 压缩余额叶子，对应 Rust 中的 CompressedLeaf
+ */
+export interface CompressedLeaf {
+  owner: Uint8Array;
+  amount: bigint;
+  index: bigint;
+}
+
+export class InitializeCompressedTreeData {
+  instruction: TokenInstruction = TokenInstruction.InitializeCompressedTree;
+  depth: number;
+
+  constructor(fields: { depth: number }) {
+    this.depth = fields.depth;
+  }
+}
+
+export class CompressData {
+  instruction: TokenInstruction = TokenInstruction.Compress;
+  recipient: Uint8Array;
+  amount: bigint;
+
+  constructor(fields: { recipient: Uint8Array; amount: bigint }) {
+    this.recipient = fields.recipient;
+    this.amount = fields.amount;
+  }
+}
+
+export class CompressedTransferData {
+  instruction: TokenInstruction = TokenInstruction.CompressedTransfer;
+  leaf: CompressedLeaf;
+  root: Uint8Array;
+  proof: Uint8Array[];
+  recipient: Uint8Array;
+  amount: bigint;
+
+  constructor(fields: {
+    leaf: CompressedLeaf;
+    root: Uint8Array;
+    proof: Uint8Array[];
+    recipient: Uint8Array;
+    amount: bigint;
+  }) {
+    this.leaf = fields.leaf;
+    this.root = fields.root;
+    this.proof = fields.proof;
+    this.recipient = fields.recipient;
+    this.amount = fields.amount;
+  }
+}
+
+export class DecompressData {
+  instruction: TokenInstruction = TokenInstruction.Decompress;
+  leaf: CompressedLeaf;
+  root: Uint8Array;
+  proof: Uint8Array[];
+
+  constructor(fields: { leaf: CompressedLeaf; root: Uint8Array; proof: Uint8Array[] }) {
+    this.leaf = fields.leaf;
+    this.root = fields.root;
+    this.proof = fields.proof;
+  }
+}
+
+export class ConfigureBridgeData {
+  instruction: TokenInstruction = TokenInstruction.ConfigureBridge;
+  verifier: Uint8Array;
+
+  constructor(fields: { verifier: Uint8Array }) {
+    this.verifier = fields.verifier;
+  }
+}
+
+export class BridgeBurnData {
+  instruction: TokenInstruction = TokenInstruction.BridgeBurn;
+  target_chain: number;
+  target_address: Uint8Array;
+  amount: bigint;
+
+  constructor(fields: { target_chain: number; target_address: Uint8Array; amount: bigint }) {
+    this.target_chain = fields.target_chain;
+    this.target_address = fields.target_address;
+    this.amount = fields.amount;
+  }
+}
+
+export class BridgeMintData {
+  instruction: TokenInstruction = TokenInstruction.BridgeMint;
+  source_chain: number;
+  emitter: Uint8Array;
+  sequence: bigint;
+  amount: bigint;
+
+  constructor(fields: { source_chain: number; emitter: Uint8Array; sequence: bigint; amount: bigint }) {
+    this.source_chain = fields.source_chain;
+    this.emitter = fields.emitter;
+    this.sequence = fields.sequence;
+    this.amount = fields.amount;
+  }
+}
+
+export class RescaleMintData {
+  instruction: TokenInstruction = TokenInstruction.RescaleMint;
+  new_decimals: number;
+
+  constructor(fields: { new_decimals: number }) {
+    this.new_decimals = fields.new_decimals;
+  }
+}
+
+/**
+ * 速度限制触发的冻结在 AccountFrozen 事件中使用的原因码，与 Rust 中的 FREEZE_REASON_VELOCITY_LIMIT 一致；
+ * 冻结权限不能使用它或 0
+ */
+export const FREEZE_REASON_VELOCITY_LIMIT = 0xffff;
+
+export class FreezeAccountData {
+  instruction: TokenInstruction = TokenInstruction.FreezeAccount;
+  thaw_at_slot: bigint | null;
+  reason_code: number;
+
+  constructor(fields: { thaw_at_slot: bigint | null; reason_code: number }) {
+    this.thaw_at_slot = fields.thaw_at_slot;
+    this.reason_code = fields.reason_code;
+  }
+}
+
+export class ThawAccountData {
+  instruction: TokenInstruction = TokenInstruction.ThawAccount;
+  reason_code: number;
+
+  constructor(fields: { reason_code: number }) {
+    this.reason_code = fields.reason_code;
+  }
+}
+
+export class SetAuthorityData {
+  instruction: TokenInstruction = TokenInstruction.SetAuthority;
+  authority_type: AuthorityType;
+  new_authority: Uint8Array | null;
+
+  constructor(fields: { authority_type: AuthorityType; new_authority: Uint8Array | null }) {
+    this.authority_type = fields.authority_type;
+    this.new_authority = fields.new_authority;
+  }
+}
+
+export class SetFreezeExemptData {
+  instruction: TokenInstruction = TokenInstruction.SetFreezeExempt;
+  exempt: boolean;
+
+  constructor(fields: { exempt: boolean }) {
+    this.exempt = fields.exempt;
+  }
+}
+
+/**
+ * 序列化 叶子 | 根 | 证明（Vec<[u8; 32]>），CompressedTransfer 与 Decompress 共用
+ */
+function serializeLeafProof(leaf: CompressedLeaf, root: Uint8Array, proof: Uint8Array[]): Buffer {
+  const buffer = Buffer.alloc(48 + 32 + 4 + 32 * proof.length);
+  buffer.set(leaf.owner, 0);
+  buffer.writeBigUInt64LE(leaf.amount, 32);
+  buffer.writeBigUInt64LE(leaf.index, 40);
+  buffer.set(root, 48);
+  buffer.writeUInt32LE(proof.length, 80);
+  proof.forEach((node, i) => buffer.set(node, 84 + 32 * i));
+  return buffer;
+}
+
+export class TransferWithMemoData {
+  instruction: TokenInstruction = TokenInstruction.TransferWithMemo;
+  amount: bigint;
+  memo: Uint8Array;
+
+  constructor(fields: { amount: bigint; memo: Uint8Array }) {
+    this.amount = fields.amount;
+    this.memo = fields.memo;
+  }
+}
+
+/**
+ * 指令数据联合类型
+ */
+export type InstructionData = 
+  | InitializeMintData 
+  | MintToData 
+  | TransferData 
+  | BurnData 
+  | SetMintAuthorityData 
+  | InitializeCurveData
+  | BuyFromCurveData
+  | SellToCurveData
+  | ConfigureOracleData
+  | MintAgainstCollateralData
+  | RebaseData
+  | { instruction: TokenInstruction.EnableRebase }
+  | FundDistributionData
+  | ClaimDividendData
+  | DelegateVotesData
+  | GetVotesAtData
+  | TopUpRentData
+  | MintToNewAccountData
+  | BurnWithReceiptData
+  | InitializeNftMintData
+  | CreateMetadataData
+  | CreateMasterEditionData
+  | { instruction: TokenInstruction.PrintEdition }
+  | SetTransferAllowlistData
+  | SetVelocityLimitData
+  | ThawAccountData
+  | QueueMintData
+  | { instruction: TokenInstruction.ExecuteQueuedMint }
+  | { instruction: TokenInstruction.CancelQueuedMint }
+  | { instruction: TokenInstruction.InitializeTreasury }
+  | { instruction: TokenInstruction.BurnTreasury }
+  | PlaceOrderData
+  | { instruction: TokenInstruction.FillOrder }
+  | { instruction: TokenInstruction.CancelOrder }
+  | CreateAuctionData
+  | PurchaseData
+  | InitializeSaleData
+  | BuyTokensData
+  | { instruction: TokenInstruction.WithdrawProceeds }
+  | { instruction: TokenInstruction.InitializeShareVault }
+  | DepositData
+  | WithdrawData
+  | GoverningTokensData
+  | InitializeEmergencyConfigData
+  | ProposeAuthorityRecoveryData
+  | { instruction: TokenInstruction.ExecuteAuthorityRecovery }
+  | { instruction: TokenInstruction.CancelAuthorityRecovery }
+  | EnableAutoCloseData
+  | { instruction: TokenInstruction.CloseEmptyMint }
+  | TransferSplitData
+  | CreateHtlcData
+  | RedeemHtlcData
+  | { instruction: TokenInstruction.RefundHtlc }
+  | CreateConditionalTransferData
+  | { instruction: TokenInstruction.ReleaseConditionalTransfer }
+  | { instruction: TokenInstruction.RefundConditionalTransfer }
+  | ApproveMultiData
+  | SetAccountLabelData
+  | CreateMintWithSeedData
+  | GetExtensionTypesData
+  | InitializeAccountWithOwnerCheckData
+  | CrankData
+  | CreatePreAuthorizationData
+  | DebitData
+  | { instruction: TokenInstruction.ClosePreAuthorization }
+  | InitializeSavingsVaultData
+  | SavingsAmountData
+  | WithdrawSavingsData
+  | InitializeEmissionsData
+  | { instruction: TokenInstruction.DistributeEmissions }
+  | EnablePointsExpiryData
+  | { instruction: TokenInstruction.ExpirePoints }
+  | SetDormancyPolicyData
+  | { instruction: TokenInstruction.WarnDormant }
+  | { instruction: TokenInstruction.SweepDormant }
+  | UpdateAttestationData
+  | { instruction: TokenInstruction.VerifySupplyBacked }
+  | InitializeConversionData
+  | ConvertMintData
+  | RebalanceData
+  | SetPauseFlagsData
+  | SetActivationRequiredData
+  | { instruction: TokenInstruction.ActivateAccount }
+  | AtomicSwapData
+  | PermitApproveData
+  | TransferFromData
+  | TransferWithPermitData
+  | CreateSessionData
+  | { instruction: TokenInstruction.RevokeSession }
+  | SessionTransferData
+  | SetGuardiansData
+  | ProposeOwnerRecoveryData
+  | { instruction: TokenInstruction.CancelOwnerRecovery }
+  | { instruction: TokenInstruction.RecoverOwner }
+  | { instruction: TokenInstruction.EnableHolderCount }
+  | { instruction: TokenInstruction.EnableActivityTracking }
+  | { instruction: TokenInstruction.EnableMemoLog }
+  | TransferWithMemoData
+  | SweepAllData
+  | SettleBatchData
+  | InitializeCompressedTreeData
+  | CompressData
+  | CompressedTransferData
+  | DecompressData
+  | ConfigureBridgeData
+  | BridgeBurnData
+  | BridgeMintData
+  | RescaleMintData
+  | { instruction: TokenInstruction.GlobalFreeze }
+  | { instruction: TokenInstruction.GlobalThaw }
+  | FreezeAccountData
+  | SetAuthorityData
+  | SetFreezeExemptData
+  | { instruction: TokenInstruction.InitializeAccount };
+
+/**
+ * 携带 MintCloseConfig 扩展的铸币账户大小，与 Rust 中的 MINT_WITH_CLOSE_CONFIG_LEN 一致
+ * （基础状态 77 字节 + TLV 头 4 字节 + 回收地址 32 字节）
+ */
+export const MINT_WITH_CLOSE_CONFIG_SIZE = 77 + 4 + 32;
+
+/**
+ * 携带 HolderCount 扩展的铸币账户大小，与 Rust 中的 MINT_WITH_HOLDER_COUNT_LEN 一致
+ * （基础状态 77 字节 + TLV 头 4 字节 + 计数 8 字节）
+ */
+export const MINT_WITH_HOLDER_COUNT_SIZE = 77 + 4 + 8;
+
+/**
+ * 携带 GlobalFreezeState 扩展的铸币账户大小，与 Rust 中的 MINT_WITH_GLOBAL_FREEZE_LEN 一致
+ * （基础状态 77 字节 + TLV 头 4 字节 + 冻结标志 1 字节）
+ */
+export const MINT_WITH_GLOBAL_FREEZE_SIZE = 77 + 4 + 1;
+
+/**
+ * 携带 BurnAuthority 扩展的铸币账户大小，与 Rust 中的 MINT_WITH_BURN_AUTHORITY_LEN 一致
+ * （基础状态 77 字节 + TLV 头 4 字节 + Option<Pubkey> 33 字节）
+ */
+export const MINT_WITH_BURN_AUTHORITY_SIZE = 77 + 4 + 33;
+
+/**
+ * 一个铸币最多调整精度的次数，与 Rust 中的 MAX_RESCALE_EPOCHS 一致
+ */
+export const MAX_RESCALE_EPOCHS = 4;
+
+/**
+ * 携带 DecimalsRescale 扩展的铸币账户大小，与 Rust 中的 MINT_WITH_DECIMALS_RESCALE_LEN 一致
+ * （基础状态 77 字节 + TLV 头 4 字节 + 纪元 1 字节 + 各纪元精度 MAX_RESCALE_EPOCHS + 1 字节）
+ */
+export const MINT_WITH_DECIMALS_RESCALE_SIZE = 77 + 4 + 1 + MAX_RESCALE_EPOCHS + 1;
+
+/**
+ * 每个代币账户最多的社交恢复监护人数量，与 Rust 中的 MAX_GUARDIANS 一致
+ */
+export const MAX_GUARDIANS = 5;
+
+/**
+ * 携带 GuardianConfig 扩展的代币账户大小，与 Rust 中的 TOKEN_ACCOUNT_WITH_GUARDIANS_LEN 一致
+ * （基础状态 106 字节 + TLV 头 4 字节 + 门限 1 字节 + 监护人列表 4 + 32 * MAX_GUARDIANS 字节）
+ */
+export const TOKEN_ACCOUNT_WITH_GUARDIANS_SIZE = 106 + 4 + 1 + 4 + 32 * MAX_GUARDIANS;
+
+/**
+ * 携带 AccountActivity 扩展的代币账户大小，与 Rust 中的 TOKEN_ACCOUNT_WITH_ACTIVITY_LEN 一致
+ * （基础状态 106 字节 + TLV 头 4 字节 + created_at 8 + last_activity_slot 8 字节）
+ */
+export const TOKEN_ACCOUNT_WITH_ACTIVITY_SIZE = 106 + 4 + 8 + 8;
+
+/**
+ * 每个代币账户保留的转账备注条数，与 Rust 中的 MEMO_LOG_CAPACITY 一致
+ */
+export const MEMO_LOG_CAPACITY = 8;
+
+/**
+ * 携带 MemoLog 扩展的代币账户大小，与 Rust 中的 TOKEN_ACCOUNT_WITH_MEMO_LOG_LEN 一致
+ * （基础状态 106 字节 + TLV 头 4 字节 + 写入位置 1 字节 + 每条记录 32 字节哈希 + 8 字节 slot）
+ */
+export const TOKEN_ACCOUNT_WITH_MEMO_LOG_SIZE = 106 + 4 + 1 + (32 + 8) * MEMO_LOG_CAPACITY;
+
+/**
+ * 携带 RescaleEpoch 扩展的代币账户大小，与 Rust 中的 TOKEN_ACCOUNT_WITH_RESCALE_EPOCH_LEN 一致
+ * （基础状态 106 字节 + TLV 头 4 字节 + 纪元 1 字节）
+ */
+export const TOKEN_ACCOUNT_WITH_RESCALE_EPOCH_SIZE = 106 + 4 + 1;
+
+/**
+ * 携带 ScheduledThaw 扩展的代币账户大小，与 Rust 中的 TOKEN_ACCOUNT_WITH_SCHEDULED_THAW_LEN 一致
+ * （基础状态 106 字节 + TLV 头 4 字节 + Option<u64> 9 字节）
+ */
+export const TOKEN_ACCOUNT_WITH_SCHEDULED_THAW_SIZE = 106 + 4 + 1 + 8;
+
+/**
+ * 携带 FreezeExempt 扩展的代币账户大小，与 Rust 中的 TOKEN_ACCOUNT_WITH_FREEZE_EXEMPT_LEN 一致
+ * （基础状态 106 字节 + TLV 头 4 字节 + 豁免标志 1 字节）
+ */
+export const TOKEN_ACCOUNT_WITH_FREEZE_EXEMPT_SIZE = 106 + 4 + 1;
+
+/**
+ * 代币账户标签的字节长度，与 Rust 中的 ACCOUNT_LABEL_LEN 一致
+ */
+export const ACCOUNT_LABEL_LENGTH = 32;
+
+/**
+ * 携带 AccountLabel 扩展的代币账户大小，与 Rust 中的 TOKEN_ACCOUNT_WITH_LABEL_LEN 一致
+ * （基础状态 106 字节 + TLV 头 4 字节 + 标签 32 字节）
+ */
+export const TOKEN_ACCOUNT_WITH_LABEL_SIZE = 106 + 4 + ACCOUNT_LABEL_LENGTH;
+
+/**
+ * 携带 PointsConfig 扩展的铸币账户大小，与 Rust 中的 MINT_WITH_POINTS_CONFIG_LEN 一致
+ * （基础状态 77 字节 + TLV 头 4 字节 + 有效期 8 字节）
+ */
+export const MINT_WITH_POINTS_CONFIG_SIZE = 77 + 4 + 8;
+
+/**
+ * 每个代币账户保留的积分批次数，与 Rust 中的 MAX_POINTS_LOTS 一致
+ */
+export const MAX_POINTS_LOTS = 8;
+
+/**
+ * 携带 PointsLots 扩展的代币账户大小，与 Rust 中的 TOKEN_ACCOUNT_WITH_POINTS_LOTS_LEN 一致
+ * （基础状态 106 字节 + TLV 头 4 字节 + 每个批次 8 字节数量 + 8 字节到期 slot）
+ */
+export const TOKEN_ACCOUNT_WITH_POINTS_LOTS_SIZE = 106 + 4 + 16 * MAX_POINTS_LOTS;
+
+/**
+ * 携带 DormancyPolicy 扩展的铸币账户大小，与 Rust 中的 MINT_WITH_DORMANCY_POLICY_LEN 一致
+ * （基础状态 77 字节 + TLV 头 4 字节 + 权限与回收账户各 32 字节 + 两个 epoch 数各 8 字节）
+ */
+export const MINT_WITH_DORMANCY_POLICY_SIZE = 77 + 4 + 32 + 32 + 8 + 8;
+
+/**
+ * 携带 PauseState 扩展的铸币账户大小，与 Rust 中的 MINT_WITH_PAUSE_STATE_LEN 一致
+ * （基础状态 77 字节 + TLV 头 4 字节 + 转账、铸造、销毁三个暂停标志各 1 字节）
+ */
+export const MINT_WITH_PAUSE_STATE_SIZE = 77 + 4 + 3;
+
+/**
+ * 携带 ActivationPolicy 扩展的铸币账户大小，与 Rust 中的 MINT_WITH_ACTIVATION_POLICY_LEN 一致
+ * （基础状态 77 字节 + TLV 头 4 字节 + required 1 字节）
+ */
+export const MINT_WITH_ACTIVATION_POLICY_SIZE = 77 + 4 + 1;
+
+/**
+ * 携带 AccountActivation 扩展的代币账户大小，与 Rust 中的 TOKEN_ACCOUNT_WITH_ACTIVATION_LEN 一致
+ * （基础状态 106 字节 + TLV 头 4 字节 + activated 1 字节）；铸币要求激活时新账户需按此分配
+ */
+export const TOKEN_ACCOUNT_WITH_ACTIVATION_SIZE = 106 + 4 + 1;
+
+/**
+ * 铸币账户数据结构（用于反序列化）
+ */
+export class Mint {
+  is_initialized: boolean;
+  decimals: number;
+  mint_authority: Uint8Array | null;
+  supply: bigint;
+  freeze_authority: Uint8Array | null;
+  is_nft: boolean;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    decimals: number;
+    mint_authority: Uint8Array | null;
+    supply: bigint;
+    freeze_authority: Uint8Array | null;
+    is_nft: boolean;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.decimals = fields.decimals;
+    this.mint_authority = fields.mint_authority;
+    this.supply = fields.supply;
+    this.freeze_authority = fields.freeze_authority;
+    this.is_nft = fields.is_nft;
+  }
+
+  /**
+   * 从字节数据反序列化 Mint 账户
+   */
+  static deserialize(data: Buffer): Mint {
+    const view = new DataView(data.buffer, data.byteOffset, data.byteLength);
+    let offset = 0;
+    
+    const is_initialized = Boolean(view.getUint8(offset)); offset += 1;
+    const decimals = view.getUint8(offset); offset += 1;
+    
+    // 反序列化 mint_authority (Some: 33 bytes, None: 1 byte)
+    const mintAuthorityOption = view.getUint8(offset); offset += 1;
+    let mint_authority: Uint8Array | null = null;
+    if (mintAuthorityOption !== 0) {
+      mint_authority = new Uint8Array(data.subarray(offset, offset + 32));
+      offset += 32;
+    }
+    
+    const supply = view.getBigUint64(offset, true); offset += 8;
+    
+    // 反序列化 freeze_authority (Some: 33 bytes, None: 1 byte)
+    const freezeAuthorityOption = view.getUint8(offset); offset += 1;
+    let freeze_authority: Uint8Array | null = null;
+    if (freezeAuthorityOption !== 0) {
+      freeze_authority = new Uint8Array(data.subarray(offset, offset + 32));
+      offset += 32;
+    }
+    
+    const is_nft = data.length > offset && view.getUint8(offset) !== 0;
+    
+    return new Mint({
+      is_initialized,
+      decimals,
+      mint_authority,
+      supply,
+      freeze_authority,
+      is_nft,
+    });
+  }
+}
+
+/**
+ * 代币账户标志位，对应 Rust 端 AccountFlags
+ */
+export const AccountFlags = {
+  INITIALIZED: 1 << 0,
+  FROZEN: 1 << 1,
+  NFT: 1 << 2,
+  VELOCITY_LIMITED: 1 << 3,
+  IMMUTABLE_OWNER: 1 << 4,
+  CPI_GUARD: 1 << 5,
+  MEMO_REQUIRED: 1 << 6,
+  HOLDER_TRACKED: 1 << 7,
+} as const;
+
+/**
+ * 代币账户数据结构（用于反序列化）
+ */
+export class TokenAccount {
+  is_initialized: boolean;
+  mint: Uint8Array;
+  owner: Uint8Array;
+  amount: bigint;
+  is_frozen: boolean;
+  vote_delegate: Uint8Array | null;
+  is_nft: boolean;
+  velocity_limited: boolean;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    mint: Uint8Array;
+    owner: Uint8Array;
+    amount: bigint;
+    is_frozen: boolean;
+    vote_delegate: Uint8Array | null;
+    is_nft: boolean;
+    velocity_limited: boolean;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.mint = fields.mint;
+    this.owner = fields.owner;
+    this.amount = fields.amount;
+    this.is_frozen = fields.is_frozen;
+    this.vote_delegate = fields.vote_delegate;
+    this.is_nft = fields.is_nft;
+    this.velocity_limited = fields.velocity_limited;
+  }
+
+  /**
+   * 从字节数据反序列化 TokenAccount
+   */
+  static deserialize(data: Buffer): TokenAccount {
+    const view = new DataView(data.buffer, data.byteOffset, data.byteLength);
+    let offset = 0;
+    
+    // 第 0 字节为标志位（AccountFlags）
+    const flags = view.getUint8(offset); offset += 1;
+    const is_initialized = (flags & AccountFlags.INITIALIZED) !== 0;
+    const is_frozen = (flags & AccountFlags.FROZEN) !== 0;
+    const is_nft = (flags & AccountFlags.NFT) !== 0;
+    const velocity_limited = (flags & AccountFlags.VELOCITY_LIMITED) !== 0;
+    
+    // mint (32 bytes)
+    const mint = new Uint8Array(data.subarray(offset, offset + 32));
+    offset += 32;
+    
+    // owner (32 bytes)
+    const owner = new Uint8Array(data.subarray(offset, offset + 32));
+    offset += 32;
+    
+    const amount = view.getBigUint64(offset, true); offset += 8;
+    
+    // vote_delegate: Option<Pubkey>
+    let vote_delegate: Uint8Array | null = null;
+    if (data.length > offset && view.getUint8(offset) !== 0) {
+      vote_delegate = new Uint8Array(data.subarray(offset + 1, offset + 33));
+    }
+    
+    return new TokenAccount({
+      is_initialized,
+      mint,
+      owner,
+      amount,
+      is_frozen,
+      vote_delegate,
+      is_nft,
+      velocity_limited,
+    });
+  }
+}
+
+/**
+ * 联合曲线状态账户（用于反序列化）
+ */
+export class BondingCurve {
+  is_initialized: boolean;
+  mint: Uint8Array;
+  curve: CurveType;
+  bump: number;
+  vault_bump: number;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    mint: Uint8Array;
+    curve: CurveType;
+    bump: number;
+    vault_bump: number;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.mint = fields.mint;
+    this.curve = fields.curve;
+    this.bump = fields.bump;
+    this.vault_bump = fields.vault_bump;
+  }
+
+  /**
+   * 从字节数据反序列化 BondingCurve 账户
+   */
+  static deserialize(data: Buffer): BondingCurve {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+
+    let curve: CurveType;
+    const tag = data.readUInt8(offset); offset += 1;
+    const base_price = data.readBigUInt64LE(offset); offset += 8;
+    if (tag === 0) {
+      const slope = data.readBigUInt64LE(offset); offset += 8;
+      curve = { kind: 'Linear', base_price, slope };
+    } else {
+      const growth_bps = data.readUInt16LE(offset); offset += 2;
+      curve = { kind: 'Exponential', base_price, growth_bps };
+    }
+
+    const bump = data.readUInt8(offset); offset += 1;
+    const vault_bump = data.readUInt8(offset);
+
+    return new BondingCurve({ is_initialized, mint, curve, bump, vault_bump });
+  }
+}
+
+/**
+ * rebase 缩放因子精度（1.0 = REBASE_SCALE），与 Rust 中的 REBASE_SCALE 一致
+ */
+export const REBASE_SCALE = BigInt('1000000000000000000');
+
+/**
+ * 弹性供应配置账户（用于反序列化）
+ */
+export class RebaseConfig {
+  is_initialized: boolean;
+  mint: Uint8Array;
+  authority: Uint8Array;
+  multiplier: bigint;
+  rebase_count: bigint;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    mint: Uint8Array;
+    authority: Uint8Array;
+    multiplier: bigint;
+    rebase_count: bigint;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.mint = fields.mint;
+    this.authority = fields.authority;
+    this.multiplier = fields.multiplier;
+    this.rebase_count = fields.rebase_count;
+  }
+
+  /**
+   * 从字节数据反序列化 RebaseConfig 账户
+   */
+  static deserialize(data: Buffer): RebaseConfig {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const authority = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const low = data.readBigUInt64LE(offset); offset += 8;
+    const high = data.readBigUInt64LE(offset); offset += 8;
+    const multiplier = (high << BigInt(64)) | low;
+    const rebase_count = data.readBigUInt64LE(offset);
+
+    return new RebaseConfig({ is_initialized, mint, authority, multiplier, rebase_count });
+  }
+
+  /**
+   * 原始份额 -> 展示余额
+   */
+  toRebasedAmount(rawAmount: bigint): bigint {
+    return rawAmount * this.multiplier / REBASE_SCALE;
+  }
+
+  /**
+   * 展示余额 -> 原始份额（向下取整，与链上一致）
+   */
+  toRawAmount(rebasedAmount: bigint): bigint {
+    return rebasedAmount * REBASE_SCALE / this.multiplier;
+  }
+}
+
+/**
+ * 受托人投票权检查点账户（用于反序列化）
+ */
+export class VoteCheckpoints {
+  is_initialized: boolean;
+  mint: Uint8Array;
+  delegatee: Uint8Array;
+  truncated: boolean;
+  checkpoints: { slot: bigint; votes: bigint }[];
+
+  constructor(fields: {
+    is_initialized: boolean;
+    mint: Uint8Array;
+    delegatee: Uint8Array;
+    truncated: boolean;
+    checkpoints: { slot: bigint; votes: bigint }[];
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.mint = fields.mint;
+    this.delegatee = fields.delegatee;
+    this.truncated = fields.truncated;
+    this.checkpoints = fields.checkpoints;
+  }
+
+  /**
+   * 从字节数据反序列化 VoteCheckpoints 账户
+   */
+  static deserialize(data: Buffer): VoteCheckpoints {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const delegatee = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const truncated = data.readUInt8(offset) !== 0; offset += 1;
+    const count = data.readUInt32LE(offset); offset += 4;
+
+    const checkpoints: { slot: bigint; votes: bigint }[] = [];
+    for (let i = 0; i < count; i++) {
+      const slot = data.readBigUInt64LE(offset); offset += 8;
+      const votes = data.readBigUInt64LE(offset); offset += 8;
+      checkpoints.push({ slot, votes });
+    }
+
+    return new VoteCheckpoints({ is_initialized, mint, delegatee, truncated, checkpoints });
+  }
+
+  /**
+   * 与链上 votes_at 相同的查询逻辑（离线计算）
+   */
+  votesAt(slot: bigint): bigint {
+    let votes: bigint | null = null;
+    for (const checkpoint of this.checkpoints) {
+      if (checkpoint.slot > slot) break;
+      votes = checkpoint.votes;
+    }
+    if (votes === null) {
+      if (this.truncated) {
+        throw new Error(`slot ${slot} 早于保留的最早检查点`);
+      }
+      return BigInt(0);
+    }
+    return votes;
+  }
+}
+
+/**
+ * 销毁凭证账户（用于反序列化）
+ */
+export class BurnReceipt {
+  is_initialized: boolean;
+  mint: Uint8Array;
+  burner: Uint8Array;
+  token_account: Uint8Array;
+  receipt_id: bigint;
+  amount: bigint;
+  slot: bigint;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    mint: Uint8Array;
+    burner: Uint8Array;
+    token_account: Uint8Array;
+    receipt_id: bigint;
+    amount: bigint;
+    slot: bigint;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.mint = fields.mint;
+    this.burner = fields.burner;
+    this.token_account = fields.token_account;
+    this.receipt_id = fields.receipt_id;
+    this.amount = fields.amount;
+    this.slot = fields.slot;
+  }
+
+  /**
+   * 从字节数据反序列化 BurnReceipt 账户
+   */
+  static deserialize(data: Buffer): BurnReceipt {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const burner = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const token_account = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const receipt_id = data.readBigUInt64LE(offset); offset += 8;
+    const amount = data.readBigUInt64LE(offset); offset += 8;
+    const slot = data.readBigUInt64LE(offset);
+
+    return new BurnReceipt({ is_initialized, mint, burner, token_account, receipt_id, amount, slot });
+  }
+}
+
+/**
+ * 母版版本账户（用于反序列化）
+ */
+export class MasterEdition {
+  is_initialized: boolean;
+  mint: Uint8Array;
+  max_supply: bigint | null;
+  supply: bigint;
+  bump: number;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    mint: Uint8Array;
+    max_supply: bigint | null;
+    supply: bigint;
+    bump: number;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.mint = fields.mint;
+    this.max_supply = fields.max_supply;
+    this.supply = fields.supply;
+    this.bump = fields.bump;
+  }
+
+  /**
+   * 从字节数据反序列化 MasterEdition 账户
+   */
+  static deserialize(data: Buffer): MasterEdition {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    let max_supply: bigint | null = null;
+    if (data.readUInt8(offset) !== 0) {
+      max_supply = data.readBigUInt64LE(offset + 1);
+      offset += 9;
+    } else {
+      offset += 1;
+    }
+    const supply = data.readBigUInt64LE(offset); offset += 8;
+    const bump = data.readUInt8(offset);
+
+    return new MasterEdition({ is_initialized, mint, max_supply, supply, bump });
+  }
+}
+
+/**
+ * 印刷副本账户（用于反序列化）
+ */
+export class Edition {
+  is_initialized: boolean;
+  parent: Uint8Array;
+  mint: Uint8Array;
+  edition: bigint;
+
+  constructor(fields: { is_initialized: boolean; parent: Uint8Array; mint: Uint8Array; edition: bigint }) {
+    this.is_initialized = fields.is_initialized;
+    this.parent = fields.parent;
+    this.mint = fields.mint;
+    this.edition = fields.edition;
+  }
+
+  /**
+   * 从字节数据反序列化 Edition 账户
+   */
+  static deserialize(data: Buffer): Edition {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const parent = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const edition = data.readBigUInt64LE(offset);
+
+    return new Edition({ is_initialized, parent, mint, edition });
+  }
+}
+
+/**
+ * 转账速度跟踪账户（用于反序列化）
+ */
+export class VelocityTracker {
+  is_initialized: boolean;
+  token_account: Uint8Array;
+  max_amount: bigint;
+  window_slots: bigint;
+  window_start_slot: bigint;
+  window_amount: bigint;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    token_account: Uint8Array;
+    max_amount: bigint;
+    window_slots: bigint;
+    window_start_slot: bigint;
+    window_amount: bigint;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.token_account = fields.token_account;
+    this.max_amount = fields.max_amount;
+    this.window_slots = fields.window_slots;
+    this.window_start_slot = fields.window_start_slot;
+    this.window_amount = fields.window_amount;
+  }
+
+  /**
+   * 从字节数据反序列化 VelocityTracker 账户
+   */
+  static deserialize(data: Buffer): VelocityTracker {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const token_account = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const max_amount = data.readBigUInt64LE(offset); offset += 8;
+    const window_slots = data.readBigUInt64LE(offset); offset += 8;
+    const window_start_slot = data.readBigUInt64LE(offset); offset += 8;
+    const window_amount = data.readBigUInt64LE(offset);
+
+    return new VelocityTracker({
+      is_initialized,
+      token_account,
+      max_amount,
+      window_slots,
+      window_start_slot,
+      window_amount,
+    });
+  }
+}
+
+/**
+ * 排队铸造账户（用于反序列化）
+ */
+export class QueuedMint {
+  is_initialized: boolean;
+  mint: Uint8Array;
+  destination: Uint8Array;
+  authority: Uint8Array;
+  amount: bigint;
+  queued_slot: bigint;
+  executable_slot: bigint;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    mint: Uint8Array;
+    destination: Uint8Array;
+    authority: Uint8Array;
+    amount: bigint;
+    queued_slot: bigint;
+    executable_slot: bigint;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.mint = fields.mint;
+    this.destination = fields.destination;
+    this.authority = fields.authority;
+    this.amount = fields.amount;
+    this.queued_slot = fields.queued_slot;
+    this.executable_slot = fields.executable_slot;
+  }
+
+  /**
+   * 从字节数据反序列化 QueuedMint 账户
+   */
+  static deserialize(data: Buffer): QueuedMint {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const destination = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const authority = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const amount = data.readBigUInt64LE(offset); offset += 8;
+    const queued_slot = data.readBigUInt64LE(offset); offset += 8;
+    const executable_slot = data.readBigUInt64LE(offset);
+
+    return new QueuedMint({
+      is_initialized,
+      mint,
+      destination,
+      authority,
+      amount,
+      queued_slot,
+      executable_slot,
+    });
+  }
+}
+
+/**
+ * 国库销毁事件（用于从日志中反序列化）
+ */
+export class TreasuryBurnEvent {
+  static readonly LEN = 32 + 8 + 8 + 8;
+
+  mint: Uint8Array;
+  amount: bigint;
+  supply: bigint;
+  slot: bigint;
+
+  constructor(fields: { mint: Uint8Array; amount: bigint; supply: bigint; slot: bigint }) {
+    this.mint = fields.mint;
+    this.amount = fields.amount;
+    this.supply = fields.supply;
+    this.slot = fields.slot;
+  }
+
+  /**
+   * 从字节数据反序列化 TreasuryBurnEvent
+   */
+  static deserialize(data: Buffer): TreasuryBurnEvent {
+    let offset = 0;
+    const mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const amount = data.readBigUInt64LE(offset); offset += 8;
+    const supply = data.readBigUInt64LE(offset); offset += 8;
+    const slot = data.readBigUInt64LE(offset);
+
+    return new TreasuryBurnEvent({ mint, amount, supply, slot });
+  }
+}
+
+/**
+ * OTC 挂单账户数据结构
+ */
+export class Order {
+  is_initialized: boolean;
+  maker: Uint8Array;
+  escrow: Uint8Array;
+  maker_receive: Uint8Array;
+  give_mint: Uint8Array;
+  give_amount: bigint;
+  want_mint: Uint8Array;
+  want_amount: bigint;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    maker: Uint8Array;
+    escrow: Uint8Array;
+    maker_receive: Uint8Array;
+    give_mint: Uint8Array;
+    give_amount: bigint;
+    want_mint: Uint8Array;
+    want_amount: bigint;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.maker = fields.maker;
+    this.escrow = fields.escrow;
+    this.maker_receive = fields.maker_receive;
+    this.give_mint = fields.give_mint;
+    this.give_amount = fields.give_amount;
+    this.want_mint = fields.want_mint;
+    this.want_amount = fields.want_amount;
+  }
+
+  /**
+   * 从字节数据反序列化 Order 账户
+   */
+  static deserialize(data: Buffer): Order {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const maker = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const escrow = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const maker_receive = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const give_mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const give_amount = data.readBigUInt64LE(offset); offset += 8;
+    const want_mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const want_amount = data.readBigUInt64LE(offset);
+
+    return new Order({
+      is_initialized,
+      maker,
+      escrow,
+      maker_receive,
+      give_mint,
+      give_amount,
+      want_mint,
+      want_amount,
+    });
+  }
+}
+
+/**
+ * 受托人转账额度账户数据结构
+ */
+export class Allowance {
+  is_initialized: boolean;
+  token_account: Uint8Array;
+  delegate: Uint8Array;
+  amount: bigint;
+  nonce: bigint;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    token_account: Uint8Array;
+    delegate: Uint8Array;
+    amount: bigint;
+    nonce: bigint;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.token_account = fields.token_account;
+    this.delegate = fields.delegate;
+    this.amount = fields.amount;
+    this.nonce = fields.nonce;
+  }
+
+  /**
+   * 从字节数据反序列化 Allowance 账户
+   */
+  static deserialize(data: Buffer): Allowance {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const token_account = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const delegate = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const amount = data.readBigUInt64LE(offset); offset += 8;
+    const nonce = data.readBigUInt64LE(offset);
+
+    return new Allowance({ is_initialized, token_account, delegate, amount, nonce });
+  }
+}
+
+/**
+ * 会话密钥授权账户数据结构
+ */
+export class Session {
+  is_initialized: boolean;
+  token_account: Uint8Array;
+  key: Uint8Array;
+  remaining: bigint;
+  expires_at_slot: bigint;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    token_account: Uint8Array;
+    key: Uint8Array;
+    remaining: bigint;
+    expires_at_slot: bigint;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.token_account = fields.token_account;
+    this.key = fields.key;
+    this.remaining = fields.remaining;
+    this.expires_at_slot = fields.expires_at_slot;
+  }
+
+  /**
+   * 从字节数据反序列化 Session 账户
+   */
+  static deserialize(data: Buffer): Session {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const token_account = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const key = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const remaining = data.readBigUInt64LE(offset); offset += 8;
+    const expires_at_slot = data.readBigUInt64LE(offset);
+
+    return new Session({ is_initialized, token_account, key, remaining, expires_at_slot });
+  }
+}
+
+/**
+ * 预授权扣款账户数据结构，对应 Rust 中的 PreAuthorization
+ */
+export class PreAuthorization {
+  is_initialized: boolean;
+  token_account: Uint8Array;
+  merchant: Uint8Array;
+  amount_per_cycle: bigint;
+  cycle_slots: bigint;
+  cycle_start_slot: bigint;
+  debited_in_cycle: bigint;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    token_account: Uint8Array;
+    merchant: Uint8Array;
+    amount_per_cycle: bigint;
+    cycle_slots: bigint;
+    cycle_start_slot: bigint;
+    debited_in_cycle: bigint;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.token_account = fields.token_account;
+    this.merchant = fields.merchant;
+    this.amount_per_cycle = fields.amount_per_cycle;
+    this.cycle_slots = fields.cycle_slots;
+    this.cycle_start_slot = fields.cycle_start_slot;
+    this.debited_in_cycle = fields.debited_in_cycle;
+  }
+
+  /**
+   * 从字节数据反序列化 PreAuthorization 账户
+   */
+  static deserialize(data: Buffer): PreAuthorization {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const token_account = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const merchant = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const amount_per_cycle = data.readBigUInt64LE(offset); offset += 8;
+    const cycle_slots = data.readBigUInt64LE(offset); offset += 8;
+    const cycle_start_slot = data.readBigUInt64LE(offset); offset += 8;
+    const debited_in_cycle = data.readBigUInt64LE(offset);
+    return new PreAuthorization({
+      is_initialized,
+      token_account,
+      merchant,
+      amount_per_cycle,
+      cycle_slots,
+      cycle_start_slot,
+      debited_in_cycle,
+    });
+  }
+}
+
+/**
+ * 储蓄金库账户数据结构，份额价格 = 托管账户余额 / total_shares
+ */
+export class SavingsVault {
+  is_initialized: boolean;
+  mint: Uint8Array;
+  vault: Uint8Array;
+  yield_authority: Uint8Array;
+  total_shares: bigint;
+  bump: number;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    mint: Uint8Array;
+    vault: Uint8Array;
+    yield_authority: Uint8Array;
+    total_shares: bigint;
+    bump: number;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.mint = fields.mint;
+    this.vault = fields.vault;
+    this.yield_authority = fields.yield_authority;
+    this.total_shares = fields.total_shares;
+    this.bump = fields.bump;
+  }
+
+  /**
+   * 从字节数据反序列化 SavingsVault 账户
+   */
+  static deserialize(data: Buffer): SavingsVault {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const vault = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const yield_authority = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const total_shares = data.readBigUInt64LE(offset); offset += 8;
+    const bump = data.readUInt8(offset);
+
+    return new SavingsVault({ is_initialized, mint, vault, yield_authority, total_shares, bump });
+  }
+}
+
+/**
+ * 存款人的储蓄仓位账户数据结构
+ */
+export class SavingsPosition {
+  is_initialized: boolean;
+  savings_vault: Uint8Array;
+  owner: Uint8Array;
+  shares: bigint;
+
+  constructor(fields: { is_initialized: boolean; savings_vault: Uint8Array; owner: Uint8Array; shares: bigint }) {
+    this.is_initialized = fields.is_initialized;
+    this.savings_vault = fields.savings_vault;
+    this.owner = fields.owner;
+    this.shares = fields.shares;
+  }
+
+  /**
+   * 从字节数据反序列化 SavingsPosition 账户
+   */
+  static deserialize(data: Buffer): SavingsPosition {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const savings_vault = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const owner = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const shares = data.readBigUInt64LE(offset);
+
+    return new SavingsPosition({ is_initialized, savings_vault, owner, shares });
+  }
+}
+
+/**
+ * 排放计划账户数据结构，distributed_until_slot 之前的排放已铸造
+ */
+export class EmissionsConfig {
+  is_initialized: boolean;
+  mint: Uint8Array;
+  authority: Uint8Array;
+  tokens_per_slot: bigint;
+  start_slot: bigint;
+  end_slot: bigint;
+  distributed_until_slot: bigint;
+  recipients: EmissionRecipient[];
+
+  constructor(fields: {
+    is_initialized: boolean;
+    mint: Uint8Array;
+    authority: Uint8Array;
+    tokens_per_slot: bigint;
+    start_slot: bigint;
+    end_slot: bigint;
+    distributed_until_slot: bigint;
+    recipients: EmissionRecipient[];
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.mint = fields.mint;
+    this.authority = fields.authority;
+    this.tokens_per_slot = fields.tokens_per_slot;
+    this.start_slot = fields.start_slot;
+    this.end_slot = fields.end_slot;
+    this.distributed_until_slot = fields.distributed_until_slot;
+    this.recipients = fields.recipients;
+  }
+
+  /**
+   * 从字节数据反序列化 EmissionsConfig 账户
+   */
+  static deserialize(data: Buffer): EmissionsConfig {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const authority = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const tokens_per_slot = data.readBigUInt64LE(offset); offset += 8;
+    const start_slot = data.readBigUInt64LE(offset); offset += 8;
+    const end_slot = data.readBigUInt64LE(offset); offset += 8;
+    const distributed_until_slot = data.readBigUInt64LE(offset); offset += 8;
+    const count = data.readUInt32LE(offset); offset += 4;
+
+    const recipients: EmissionRecipient[] = [];
+    for (let i = 0; i < count; i++) {
+      const vault = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+      const weight = data.readUInt16LE(offset); offset += 2;
+      recipients.push({ vault, weight });
+    }
+
+    return new EmissionsConfig({
+      is_initialized,
+      mint,
+      authority,
+      tokens_per_slot,
+      start_slot,
+      end_slot,
+      distributed_until_slot,
+      recipients,
+    });
+  }
+}
+
+/**
+ * 休眠警告账户数据结构，last_activity_slot 为警告时账户的最近活动 slot
+ */
+export class DormancyNotice {
+  is_initialized: boolean;
+  token_account: Uint8Array;
+  last_activity_slot: bigint;
+  warned_at_slot: bigint;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    token_account: Uint8Array;
+    last_activity_slot: bigint;
+    warned_at_slot: bigint;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.token_account = fields.token_account;
+    this.last_activity_slot = fields.last_activity_slot;
+    this.warned_at_slot = fields.warned_at_slot;
+  }
+
+  /**
+   * 从字节数据反序列化 DormancyNotice 账户
+   */
+  static deserialize(data: Buffer): DormancyNotice {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const token_account = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const last_activity_slot = data.readBigUInt64LE(offset); offset += 8;
+    const warned_at_slot = data.readBigUInt64LE(offset);
+
+    return new DormancyNotice({ is_initialized, token_account, last_activity_slot, warned_at_slot });
+  }
+}
+
+/**
+ * 储备证明账户数据结构，reserves 与供应量同为最小单位
+ */
+export class Attestation {
+  is_initialized: boolean;
+  mint: Uint8Array;
+  attester: Uint8Array;
+  reserves: bigint;
+  attested_at_slot: bigint;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    mint: Uint8Array;
+    attester: Uint8Array;
+    reserves: bigint;
+    attested_at_slot: bigint;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.mint = fields.mint;
+    this.attester = fields.attester;
+    this.reserves = fields.reserves;
+    this.attested_at_slot = fields.attested_at_slot;
+  }
+
+  /**
+   * 从字节数据反序列化 Attestation 账户
+   */
+  static deserialize(data: Buffer): Attestation {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const attester = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const reserves = data.readBigUInt64LE(offset); offset += 8;
+    const attested_at_slot = data.readBigUInt64LE(offset);
+
+    return new Attestation({ is_initialized, mint, attester, reserves, attested_at_slot });
+  }
+}
+
+/**
+ * 兑换比率的定点精度，与 Rust 中的 CONVERSION_RATE_SCALE 一致（rate 等于它时按最小单位 1:1 兑换）
+ */
+export const CONVERSION_RATE_SCALE = 1_000_000_000n;
+
+/**
+ * 代币迁移账户数据结构，旧铸币按 rate / CONVERSION_RATE_SCALE 兑换新铸币
+ */
+export class Conversion {
+  is_initialized: boolean;
+  old_mint: Uint8Array;
+  new_mint: Uint8Array;
+  vault: Uint8Array;
+  rate: bigint;
+  total_converted: bigint;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    old_mint: Uint8Array;
+    new_mint: Uint8Array;
+    vault: Uint8Array;
+    rate: bigint;
+    total_converted: bigint;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.old_mint = fields.old_mint;
+    this.new_mint = fields.new_mint;
+    this.vault = fields.vault;
+    this.rate = fields.rate;
+    this.total_converted = fields.total_converted;
+  }
+
+  /**
+   * 兑换 amount 个旧代币得到的新代币数量（向下取整），与链上计算一致
+   */
+  convertedAmount(amount: bigint): bigint {
+    return (amount * this.rate) / CONVERSION_RATE_SCALE;
+  }
+
+  /**
+   * 从字节数据反序列化 Conversion 账户
+   */
+  static deserialize(data: Buffer): Conversion {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const old_mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const new_mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const vault = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const rate = data.readBigUInt64LE(offset); offset += 8;
+    const total_converted = data.readBigUInt64LE(offset);
+
+    return new Conversion({ is_initialized, old_mint, new_mint, vault, rate, total_converted });
+  }
+}
+
+/**
+ * 待执行的所有者恢复账户数据结构
+ */
+export class OwnerRecovery {
+  is_initialized: boolean;
+  token_account: Uint8Array;
+  new_owner: Uint8Array;
+  payer: Uint8Array;
+  executable_slot: bigint;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    token_account: Uint8Array;
+    new_owner: Uint8Array;
+    payer: Uint8Array;
+    executable_slot: bigint;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.token_account = fields.token_account;
+    this.new_owner = fields.new_owner;
+    this.payer = fields.payer;
+    this.executable_slot = fields.executable_slot;
+  }
+
+  /**
+   * 从字节数据反序列化 OwnerRecovery 账户
+   */
+  static deserialize(data: Buffer): OwnerRecovery {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const token_account = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const new_owner = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const payer = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const executable_slot = data.readBigUInt64LE(offset);
+
+    return new OwnerRecovery({ is_initialized, token_account, new_owner, payer, executable_slot });
+  }
+}
+
+/**
+ * 哈希时间锁账户数据结构
+ */
+export class Htlc {
+  is_initialized: boolean;
+  sender: Uint8Array;
+  escrow: Uint8Array;
+  refund: Uint8Array;
+  recipient: Uint8Array;
+  mint: Uint8Array;
+  amount: bigint;
+  hash: Uint8Array;
+  timeout: bigint;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    sender: Uint8Array;
+    escrow: Uint8Array;
+    refund: Uint8Array;
+    recipient: Uint8Array;
+    mint: Uint8Array;
+    amount: bigint;
+    hash: Uint8Array;
+    timeout: bigint;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.sender = fields.sender;
+    this.escrow = fields.escrow;
+    this.refund = fields.refund;
+    this.recipient = fields.recipient;
+    this.mint = fields.mint;
+    this.amount = fields.amount;
+    this.hash = fields.hash;
+    this.timeout = fields.timeout;
+  }
+
+  /**
+   * 从字节数据反序列化 Htlc 账户
+   */
+  static deserialize(data: Buffer): Htlc {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const sender = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const escrow = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const refund = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const recipient = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const amount = data.readBigUInt64LE(offset); offset += 8;
+    const hash = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const timeout = data.readBigInt64LE(offset);
+
+    return new Htlc({
+      is_initialized,
+      sender,
+      escrow,
+      refund,
+      recipient,
+      mint,
+      amount,
+      hash,
+      timeout,
+    });
+  }
+}
+
+/**
+ * 带仲裁人的条件转账账户数据结构
+ */
+export class ConditionalTransfer {
+  is_initialized: boolean;
+  sender: Uint8Array;
+  escrow: Uint8Array;
+  refund: Uint8Array;
+  recipient: Uint8Array;
+  mint: Uint8Array;
+  arbiter: Uint8Array;
+  amount: bigint;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    sender: Uint8Array;
+    escrow: Uint8Array;
+    refund: Uint8Array;
+    recipient: Uint8Array;
+    mint: Uint8Array;
+    arbiter: Uint8Array;
+    amount: bigint;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.sender = fields.sender;
+    this.escrow = fields.escrow;
+    this.refund = fields.refund;
+    this.recipient = fields.recipient;
+    this.mint = fields.mint;
+    this.arbiter = fields.arbiter;
+    this.amount = fields.amount;
+  }
+
+  /**
+   * 从字节数据反序列化 ConditionalTransfer 账户
+   */
+  static deserialize(data: Buffer): ConditionalTransfer {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const sender = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const escrow = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const refund = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const recipient = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const arbiter = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const amount = data.readBigUInt64LE(offset);
+
+    return new ConditionalTransfer({
+      is_initialized,
+      sender,
+      escrow,
+      refund,
+      recipient,
+      mint,
+      arbiter,
+      amount,
+    });
+  }
+}
+
+/**
+ * 荷兰式拍卖状态账户（用于反序列化）
+ */
+export class DutchAuction {
+  is_initialized: boolean;
+  mint: Uint8Array;
+  authority: Uint8Array;
+  start_slot: bigint;
+  start_price: bigint;
+  floor_price: bigint;
+  decay: bigint;
+  supply: bigint;
+  sold: bigint;
+  bump: number;
+  vault_bump: number;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    mint: Uint8Array;
+    authority: Uint8Array;
+    start_slot: bigint;
+    start_price: bigint;
+    floor_price: bigint;
+    decay: bigint;
+    supply: bigint;
+    sold: bigint;
+    bump: number;
+    vault_bump: number;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.mint = fields.mint;
+    this.authority = fields.authority;
+    this.start_slot = fields.start_slot;
+    this.start_price = fields.start_price;
+    this.floor_price = fields.floor_price;
+    this.decay = fields.decay;
+    this.supply = fields.supply;
+    this.sold = fields.sold;
+    this.bump = fields.bump;
+    this.vault_bump = fields.vault_bump;
+  }
+
+  /**
+   * 指定 slot 时每个完整代币的价格（与链上 price_at 一致）
+   */
+  priceAt(slot: bigint): bigint {
+    const elapsed = slot > this.start_slot ? slot - this.start_slot : BigInt(0);
+    const drop = elapsed * this.decay;
+    const price = this.start_price > drop ? this.start_price - drop : BigInt(0);
+    return price > this.floor_price ? price : this.floor_price;
+  }
+
+  /**
+   * 从字节数据反序列化 DutchAuction 账户
+   */
+  static deserialize(data: Buffer): DutchAuction {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const authority = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const start_slot = data.readBigUInt64LE(offset); offset += 8;
+    const start_price = data.readBigUInt64LE(offset); offset += 8;
+    const floor_price = data.readBigUInt64LE(offset); offset += 8;
+    const decay = data.readBigUInt64LE(offset); offset += 8;
+    const supply = data.readBigUInt64LE(offset); offset += 8;
+    const sold = data.readBigUInt64LE(offset); offset += 8;
+    const bump = data.readUInt8(offset); offset += 1;
+    const vault_bump = data.readUInt8(offset);
+
+    return new DutchAuction({
+      is_initialized,
+      mint,
+      authority,
+      start_slot,
+      start_price,
+      floor_price,
+      decay,
+      supply,
+      sold,
+      bump,
+      vault_bump,
+    });
+  }
+}
+
+/**
+ * 固定价格众筹状态账户（用于反序列化）
+ */
+export class Sale {
+  is_initialized: boolean;
+  mint: Uint8Array;
+  authority: Uint8Array;
+  price_lamports_per_token: bigint;
+  cap: bigint;
+  sold: bigint;
+  bump: number;
+  vault_bump: number;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    mint: Uint8Array;
+    authority: Uint8Array;
+    price_lamports_per_token: bigint;
+    cap: bigint;
+    sold: bigint;
+    bump: number;
+    vault_bump: number;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.mint = fields.mint;
+    this.authority = fields.authority;
+    this.price_lamports_per_token = fields.price_lamports_per_token;
+    this.cap = fields.cap;
+    this.sold = fields.sold;
+    this.bump = fields.bump;
+    this.vault_bump = fields.vault_bump;
+  }
+
+  /**
+   * 从字节数据反序列化 Sale 账户
+   */
+  static deserialize(data: Buffer): Sale {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const authority = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const price_lamports_per_token = data.readBigUInt64LE(offset); offset += 8;
+    const cap = data.readBigUInt64LE(offset); offset += 8;
+    const sold = data.readBigUInt64LE(offset); offset += 8;
+    const bump = data.readUInt8(offset); offset += 1;
+    const vault_bump = data.readUInt8(offset);
+
+    return new Sale({
+      is_initialized,
+      mint,
+      authority,
+      price_lamports_per_token,
+      cap,
+      sold,
+      bump,
+      vault_bump,
+    });
   }
 }
 
 /**
- * 指令数据联合类型
+ * 份额金库账户数据结构
  */
-export type InstructionData = 
-  | InitializeMintData 
-  | MintToData 
-  | TransferData 
-  | BurnData 
-  | SetMintAuthorityData 
-  | { instruction: TokenInstruction.InitializeAccount };
+export class ShareVault {
+  is_initialized: boolean;
+  asset_mint: Uint8Array;
+  share_mint: Uint8Array;
+  asset_vault: Uint8Array;
+  bump: number;
+
+  constructor(fields: {
+    is_initialized: boolean;
+    asset_mint: Uint8Array;
+    share_mint: Uint8Array;
+    asset_vault: Uint8Array;
+    bump: number;
+  }) {
+    this.is_initialized = fields.is_initialized;
+    this.asset_mint = fields.asset_mint;
+    this.share_mint = fields.share_mint;
+    this.asset_vault = fields.asset_vault;
+    this.bump = fields.bump;
+  }
+
+  /**
+   * 从字节数据反序列化 ShareVault 账户
+   */
+  static deserialize(data: Buffer): ShareVault {
+    let offset = 0;
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const asset_mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const share_mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const asset_vault = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const bump = data.readUInt8(offset);
+
+    return new ShareVault({ is_initialized, asset_mint, share_mint, asset_vault, bump });
+  }
+}
 
 /**
- * 铸币账户数据结构（用于反序列化）
+ * 治理锁仓记录（代币持有人在某领域的投票权重）
  */
-export class Mint {
+export class TokenOwnerRecord {
   is_initialized: boolean;
-  decimals: number;
-  mint_authority: Uint8Array | null;
-  supply: bigint;
-  freeze_authority: Uint8Array | null;
+  realm: Uint8Array;
+  governing_token_mint: Uint8Array;
+  governing_token_owner: Uint8Array;
+  governing_token_deposit_amount: bigint;
 
   constructor(fields: {
     is_initialized: boolean;
-    decimals: number;
-    mint_authority: Uint8Array | null;
-    supply: bigint;
-    freeze_authority: Uint8Array | null;
+    realm: Uint8Array;
+    governing_token_mint: Uint8Array;
+    governing_token_owner: Uint8Array;
+    governing_token_deposit_amount: bigint;
   }) {
     this.is_initialized = fields.is_initialized;
-    this.decimals = fields.decimals;
-    this.mint_authority = fields.mint_authority;
-    this.supply = fields.supply;
-    this.freeze_authority = fields.freeze_authority;
+    this.realm = fields.realm;
+    this.governing_token_mint = fields.governing_token_mint;
+    this.governing_token_owner = fields.governing_token_owner;
+    this.governing_token_deposit_amount = fields.governing_token_deposit_amount;
   }
 
   /**
-   * 从字节数据反序列化 Mint 账户
+   * 从字节数据反序列化 TokenOwnerRecord 账户
    */
-  static deserialize(data: Buffer): Mint {
-    const view = new DataView(data.buffer, data.byteOffset, data.byteLength);
+  static deserialize(data: Buffer): TokenOwnerRecord {
     let offset = 0;
-    
-    const is_initialized = Boolean(view.getUint8(offset)); offset += 1;
-    const decimals = view.getUint8(offset); offset += 1;
-    
-    // 反序列化 mint_authority (33 bytes)
-    const mintAuthorityOption = view.getUint8(offset); offset += 1;
-    let mint_authority: Uint8Array | null = null;
-    if (mintAuthorityOption !== 0) {
-      mint_authority = new Uint8Array(data.subarray(offset, offset + 32));
-      offset += 32;
-    } else {
-      offset += 32; // 跳过空数据
-    }
-    
-    const supply = view.getBigUint64(offset, true); offset += 8;
-    
-    // 反序列化 freeze_authority (33 bytes)
-    const freezeAuthorityOption = view.getUint8(offset); offset += 1;
-    let freeze_authority: Uint8Array | null = null;
-    if (freezeAuthorityOption !== 0) {
-      freeze_authority = new Uint8Array(data.subarray(offset, offset + 32));
-      offset += 32;
-    } else {
-      offset += 32; // 跳过空数据
-    }
-    
-    return new Mint({
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const realm = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const governing_token_mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const governing_token_owner = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const governing_token_deposit_amount = data.readBigUInt64LE(offset);
+
+    return new TokenOwnerRecord({
       is_initialized,
-      decimals,
-      mint_authority,
-      supply,
-      freeze_authority,
+      realm,
+      governing_token_mint,
+      governing_token_owner,
+      governing_token_deposit_amount,
     });
   }
 }
 
 /**
- * 代币账户数据结构（用于反序列化）
+ * 紧急管理员配置
  */
-export class TokenAccount {
+export class EmergencyConfig {
+  is_initialized: boolean;
+  emergency_admin: Uint8Array;
+
+  constructor(fields: { is_initialized: boolean; emergency_admin: Uint8Array }) {
+    this.is_initialized = fields.is_initialized;
+    this.emergency_admin = fields.emergency_admin;
+  }
+
+  static deserialize(data: Buffer): EmergencyConfig {
+    return new EmergencyConfig({
+      is_initialized: data.readUInt8(0) !== 0,
+      emergency_admin: new Uint8Array(data.subarray(1, 33)),
+    });
+  }
+}
+
+/**
+ * 待执行的权限恢复提议
+ */
+export class AuthorityRecovery {
   is_initialized: boolean;
   mint: Uint8Array;
-  owner: Uint8Array;
-  amount: bigint;
-  is_frozen: boolean;
+  authority_type: AuthorityType;
+  new_authority: Uint8Array;
+  emergency_admin: Uint8Array;
+  proposed_slot: bigint;
+  executable_slot: bigint;
 
   constructor(fields: {
     is_initialized: boolean;
     mint: Uint8Array;
-    owner: Uint8Array;
-    amount: bigint;
-    is_frozen: boolean;
+    authority_type: AuthorityType;
+    new_authority: Uint8Array;
+    emergency_admin: Uint8Array;
+    proposed_slot: bigint;
+    executable_slot: bigint;
   }) {
     this.is_initialized = fields.is_initialized;
     this.mint = fields.mint;
-    this.owner = fields.owner;
-    this.amount = fields.amount;
-    this.is_frozen = fields.is_frozen;
+    this.authority_type = fields.authority_type;
+    this.new_authority = fields.new_authority;
+    this.emergency_admin = fields.emergency_admin;
+    this.proposed_slot = fields.proposed_slot;
+    this.executable_slot = fields.executable_slot;
   }
 
   /**
-   * 从字节数据反序列化 TokenAccount
+   * 从字节数据反序列化 AuthorityRecovery 账户
    */
-  static deserialize(data: Buffer): TokenAccount {
-    const view = new DataView(data.buffer, data.byteOffset, data.byteLength);
+  static deserialize(data: Buffer): AuthorityRecovery {
     let offset = 0;
-    
-    const is_initialized = Boolean(view.getUint8(offset)); offset += 1;
-    
-    // mint (32 bytes)
-    const mint = new Uint8Array(data.subarray(offset, offset + 32));
-    offset += 32;
-    
-    // owner (32 bytes)
-    const owner = new Uint8Array(data.subarray(offset, offset + 32));
-    offset += 32;
-    
-    const amount = view.getBigUint64(offset, true); offset += 8;
-    const is_frozen = Boolean(view.getUint8(offset));
-    
-    return new TokenAccount({
+    const is_initialized = data.readUInt8(offset) !== 0; offset += 1;
+    const mint = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const authority_type = data.readUInt8(offset) as AuthorityType; offset += 1;
+    const new_authority = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const emergency_admin = new Uint8Array(data.subarray(offset, offset + 32)); offset += 32;
+    const proposed_slot = data.readBigUInt64LE(offset); offset += 8;
+    const executable_slot = data.readBigUInt64LE(offset);
+
+    return new AuthorityRecovery({
       is_initialized,
       mint,
-      owner,
-      amount,
-      is_frozen,
+      authority_type,
+      new_authority,
+      emergency_admin,
+      proposed_slot,
+      executable_slot,
     });
   }
 }
@@ -232,6 +3050,23 @@ export interface ProgramAccounts {
   newMintAuthority: PublicKey;
 }
 
+/**
+ * 指令数据版本号，对应 Rust 端 INSTRUCTION_VERSION
+ */
+export const INSTRUCTION_VERSION = 1;
+
+/**
+ * 版本化信封前缀，对应 Rust 端 VERSIONED_TAG；128..=253 为预留标签
+ */
+export const VERSIONED_TAG = 254;
+
+/**
+ * 以版本化信封 [VERSIONED_TAG, version, ...] 包装已序列化的指令数据
+ */
+export function wrapVersioned(data: Buffer, version: number = INSTRUCTION_VERSION): Buffer {
+  return Buffer.concat([Buffer.from([VERSIONED_TAG, version]), data]);
+}
+
 /**
  * 序列化指令数据的通用函数
  */
@@ -249,6 +3084,597 @@ export function serializeInstructionData(data: InstructionData): Buffer {
       return serializeBurnData(data as BurnData);
     case TokenInstruction.SetMintAuthority:
       return serializeSetMintAuthorityData(data as SetMintAuthorityData);
+    case TokenInstruction.InitializeCurve:
+      return serializeInitializeCurveData(data as InitializeCurveData);
+    case TokenInstruction.BuyFromCurve: {
+      const buy = data as BuyFromCurveData;
+      return serializeTwoU64(buy.instruction, buy.amount, buy.max_lamports);
+    }
+    case TokenInstruction.SellToCurve: {
+      const sell = data as SellToCurveData;
+      return serializeTwoU64(sell.instruction, sell.amount, sell.min_lamports);
+    }
+    case TokenInstruction.ConfigureOracle:
+      return serializeConfigureOracleData(data as ConfigureOracleData);
+    case TokenInstruction.MintAgainstCollateral: {
+      const collateral = data as MintAgainstCollateralData;
+      return serializeTwoU64(collateral.instruction, collateral.collateral_lamports, collateral.min_amount);
+    }
+    case TokenInstruction.EnableRebase:
+      return Buffer.from([data.instruction]);
+    case TokenInstruction.Rebase: {
+      const rebase = data as RebaseData;
+      return serializeTwoU64(rebase.instruction, rebase.numerator, rebase.denominator);
+    }
+    case TokenInstruction.FundDistribution:
+      return serializeFundDistributionData(data as FundDistributionData);
+    case TokenInstruction.ClaimDividend:
+      return serializeClaimDividendData(data as ClaimDividendData);
+    case TokenInstruction.DelegateVotes: {
+      const delegate = data as DelegateVotesData;
+      const buffer = Buffer.alloc(1 + (delegate.delegatee ? 33 : 1));
+      buffer.writeUInt8(delegate.instruction, 0);
+      writeOptionPubkey(buffer, 1, delegate.delegatee);
+      return buffer;
+    }
+    case TokenInstruction.GetVotesAt: {
+      const query = data as GetVotesAtData;
+      const buffer = Buffer.alloc(1 + 8);
+      buffer.writeUInt8(query.instruction, 0);
+      buffer.writeBigUInt64LE(query.slot, 1);
+      return buffer;
+    }
+    case TokenInstruction.TopUpRent: {
+      const topUp = data as TopUpRentData;
+      const buffer = Buffer.alloc(1 + 8);
+      buffer.writeUInt8(topUp.instruction, 0);
+      buffer.writeBigUInt64LE(topUp.lamports, 1);
+      return buffer;
+    }
+    case TokenInstruction.MintToNewAccount: {
+      const mintTo = data as MintToNewAccountData;
+      const buffer = Buffer.alloc(1 + 8);
+      buffer.writeUInt8(mintTo.instruction, 0);
+      buffer.writeBigUInt64LE(mintTo.amount, 1);
+      return buffer;
+    }
+    case TokenInstruction.BurnWithReceipt: {
+      const burn = data as BurnWithReceiptData;
+      return serializeTwoU64(burn.instruction, burn.amount, burn.receipt_id);
+    }
+    case TokenInstruction.InitializeNftMint: {
+      const nft = data as InitializeNftMintData;
+      const buffer = Buffer.alloc(1 + 32 + (nft.freeze_authority ? 33 : 1));
+      buffer.writeUInt8(nft.instruction, 0);
+      buffer.set(nft.mint_authority, 1);
+      writeOptionPubkey(buffer, 33, nft.freeze_authority);
+      return buffer;
+    }
+    case TokenInstruction.CreateMetadata:
+      return serializeCreateMetadataData(data as CreateMetadataData);
+    case TokenInstruction.CreateMasterEdition: {
+      const master = data as CreateMasterEditionData;
+      const buffer = Buffer.alloc(1 + (master.max_supply !== null ? 9 : 1));
+      buffer.writeUInt8(master.instruction, 0);
+      if (master.max_supply !== null) {
+        buffer.writeUInt8(1, 1);
+        buffer.writeBigUInt64LE(master.max_supply, 2);
+      }
+      return buffer;
+    }
+    case TokenInstruction.PrintEdition:
+      return Buffer.from([data.instruction]);
+    case TokenInstruction.SetTransferAllowlist: {
+      const allowlist = data as SetTransferAllowlistData;
+      const buffer = Buffer.alloc(1 + 4 + 32 * allowlist.programs.length);
+      buffer.writeUInt8(allowlist.instruction, 0);
+      buffer.writeUInt32LE(allowlist.programs.length, 1);
+      allowlist.programs.forEach((program, i) => buffer.set(program, 5 + 32 * i));
+      return buffer;
+    }
+    case TokenInstruction.SetVelocityLimit: {
+      const limit = data as SetVelocityLimitData;
+      return serializeTwoU64(limit.instruction, limit.max_amount, limit.window_slots);
+    }
+    case TokenInstruction.ThawAccount: {
+      const thaw = data as ThawAccountData;
+      const buffer = Buffer.alloc(1 + 2);
+      buffer.writeUInt8(thaw.instruction, 0);
+      buffer.writeUInt16LE(thaw.reason_code, 1);
+      return buffer;
+    }
+    case TokenInstruction.QueueMint: {
+      const queue = data as QueueMintData;
+      const buffer = Buffer.alloc(1 + 8 + 32);
+      buffer.writeUInt8(queue.instruction, 0);
+      buffer.writeBigUInt64LE(queue.amount, 1);
+      buffer.set(queue.destination, 9);
+      return buffer;
+    }
+    case TokenInstruction.ExecuteQueuedMint:
+    case TokenInstruction.CancelQueuedMint:
+      return Buffer.from([data.instruction]);
+    case TokenInstruction.InitializeTreasury:
+    case TokenInstruction.BurnTreasury:
+      return Buffer.from([data.instruction]);
+    case TokenInstruction.PlaceOrder: {
+      const order = data as PlaceOrderData;
+      const buffer = Buffer.alloc(1 + 8 + 32 + 8);
+      buffer.writeUInt8(order.instruction, 0);
+      buffer.writeBigUInt64LE(order.give_amount, 1);
+      buffer.set(order.want_mint, 9);
+      buffer.writeBigUInt64LE(order.want_amount, 41);
+      return buffer;
+    }
+    case TokenInstruction.FillOrder:
+    case TokenInstruction.CancelOrder:
+      return Buffer.from([data.instruction]);
+    case TokenInstruction.CreateAuction: {
+      const auction = data as CreateAuctionData;
+      const buffer = Buffer.alloc(1 + 8 * 4);
+      buffer.writeUInt8(auction.instruction, 0);
+      buffer.writeBigUInt64LE(auction.start_price, 1);
+      buffer.writeBigUInt64LE(auction.floor_price, 9);
+      buffer.writeBigUInt64LE(auction.decay, 17);
+      buffer.writeBigUInt64LE(auction.supply, 25);
+      return buffer;
+    }
+    case TokenInstruction.Purchase: {
+      const purchase = data as PurchaseData;
+      return serializeTwoU64(purchase.instruction, purchase.amount, purchase.max_lamports);
+    }
+    case TokenInstruction.InitializeSale: {
+      const sale = data as InitializeSaleData;
+      return serializeTwoU64(sale.instruction, sale.price_lamports_per_token, sale.cap);
+    }
+    case TokenInstruction.BuyTokens: {
+      const buy = data as BuyTokensData;
+      const buffer = Buffer.alloc(1 + 8);
+      buffer.writeUInt8(buy.instruction, 0);
+      buffer.writeBigUInt64LE(buy.amount, 1);
+      return buffer;
+    }
+    case TokenInstruction.WithdrawProceeds:
+      return Buffer.from([data.instruction]);
+    case TokenInstruction.InitializeShareVault:
+      return Buffer.from([data.instruction]);
+    case TokenInstruction.Deposit: {
+      const deposit = data as DepositData;
+      const buffer = Buffer.alloc(1 + 8);
+      buffer.writeUInt8(deposit.instruction, 0);
+      buffer.writeBigUInt64LE(deposit.amount, 1);
+      return buffer;
+    }
+    case TokenInstruction.Withdraw: {
+      const withdraw = data as WithdrawData;
+      const buffer = Buffer.alloc(1 + 8);
+      buffer.writeUInt8(withdraw.instruction, 0);
+      buffer.writeBigUInt64LE(withdraw.shares, 1);
+      return buffer;
+    }
+    case TokenInstruction.DepositGoverningTokens:
+    case TokenInstruction.WithdrawGoverningTokens: {
+      const governing = data as GoverningTokensData;
+      const buffer = Buffer.alloc(1 + 32 + 8);
+      buffer.writeUInt8(governing.instruction, 0);
+      buffer.set(governing.realm, 1);
+      buffer.writeBigUInt64LE(governing.amount, 33);
+      return buffer;
+    }
+    case TokenInstruction.InitializeEmergencyConfig: {
+      const config = data as InitializeEmergencyConfigData;
+      const buffer = Buffer.alloc(1 + 32);
+      buffer.writeUInt8(config.instruction, 0);
+      buffer.set(config.emergency_admin, 1);
+      return buffer;
+    }
+    case TokenInstruction.ProposeAuthorityRecovery: {
+      const proposal = data as ProposeAuthorityRecoveryData;
+      const buffer = Buffer.alloc(1 + 1 + 32);
+      buffer.writeUInt8(proposal.instruction, 0);
+      buffer.writeUInt8(proposal.authority_type, 1);
+      buffer.set(proposal.new_authority, 2);
+      return buffer;
+    }
+    case TokenInstruction.EnableAutoClose: {
+      const autoClose = data as EnableAutoCloseData;
+      const buffer = Buffer.alloc(1 + 32);
+      buffer.writeUInt8(autoClose.instruction, 0);
+      buffer.set(autoClose.close_destination, 1);
+      return buffer;
+    }
+    case TokenInstruction.TransferSplit: {
+      const split = data as TransferSplitData;
+      const buffer = Buffer.alloc(1 + 8 + 4 + 2 * split.shares.length);
+      buffer.writeUInt8(split.instruction, 0);
+      buffer.writeBigUInt64LE(split.total, 1);
+      buffer.writeUInt32LE(split.shares.length, 9);
+      split.shares.forEach((share, i) => buffer.writeUInt16LE(share, 13 + 2 * i));
+      return buffer;
+    }
+    case TokenInstruction.CreateConditionalTransfer: {
+      const conditional = data as CreateConditionalTransferData;
+      const buffer = Buffer.alloc(1 + 32 + 8);
+      buffer.writeUInt8(conditional.instruction, 0);
+      buffer.set(conditional.arbiter, 1);
+      buffer.writeBigUInt64LE(conditional.amount, 33);
+      return buffer;
+    }
+    case TokenInstruction.CreateHtlc: {
+      const htlc = data as CreateHtlcData;
+      const buffer = Buffer.alloc(1 + 32 + 8 + 8);
+      buffer.writeUInt8(htlc.instruction, 0);
+      buffer.set(htlc.hash, 1);
+      buffer.writeBigUInt64LE(htlc.amount, 33);
+      buffer.writeBigInt64LE(htlc.timeout, 41);
+      return buffer;
+    }
+    case TokenInstruction.TransferWithMemo: {
+      const transfer = data as TransferWithMemoData;
+      const buffer = Buffer.alloc(1 + 8 + 4 + transfer.memo.length);
+      buffer.writeUInt8(transfer.instruction, 0);
+      buffer.writeBigUInt64LE(transfer.amount, 1);
+      buffer.writeUInt32LE(transfer.memo.length, 9);
+      buffer.set(transfer.memo, 13);
+      return buffer;
+    }
+    case TokenInstruction.SweepAll: {
+      const sweep = data as SweepAllData;
+      return Buffer.from([sweep.instruction, sweep.source_count]);
+    }
+    case TokenInstruction.SettleBatch: {
+      const settle = data as SettleBatchData;
+      const buffer = Buffer.alloc(1 + 4 + 8 * settle.deltas.length);
+      buffer.writeUInt8(settle.instruction, 0);
+      buffer.writeUInt32LE(settle.deltas.length, 1);
+      settle.deltas.forEach((delta, i) => buffer.writeBigInt64LE(delta, 5 + 8 * i));
+      return buffer;
+    }
+    case TokenInstruction.CreateMintWithSeed: {
+      const create = data as CreateMintWithSeedData;
+      const seed = Buffer.from(create.seed, 'utf8');
+      const buffer = Buffer.alloc(1 + 4 + seed.length + 1 + 1 + (create.freeze_authority ? 32 : 0));
+      buffer.writeUInt8(create.instruction, 0);
+      buffer.writeUInt32LE(seed.length, 1);
+      buffer.set(seed, 5);
+      let offset = 5 + seed.length;
+      buffer.writeUInt8(create.decimals, offset); offset += 1;
+      if (create.freeze_authority) {
+        buffer.writeUInt8(1, offset);
+        buffer.set(create.freeze_authority, offset + 1);
+      }
+      return buffer;
+    }
+    case TokenInstruction.SetAccountLabel: {
+      const label = data as SetAccountLabelData;
+      const buffer = Buffer.alloc(1 + ACCOUNT_LABEL_LENGTH);
+      buffer.writeUInt8(label.instruction, 0);
+      buffer.set(label.label, 1);
+      return buffer;
+    }
+    case TokenInstruction.ApproveMulti: {
+      const approve = data as ApproveMultiData;
+      const buffer = Buffer.alloc(1 + 4 + 40 * approve.approvals.length);
+      buffer.writeUInt8(approve.instruction, 0);
+      buffer.writeUInt32LE(approve.approvals.length, 1);
+      approve.approvals.forEach(({ delegate, amount }, i) => {
+        buffer.set(delegate, 5 + 40 * i);
+        buffer.writeBigUInt64LE(amount, 37 + 40 * i);
+      });
+      return buffer;
+    }
+    case TokenInstruction.InitializeCompressedTree: {
+      const init = data as InitializeCompressedTreeData;
+      return Buffer.from([init.instruction, init.depth]);
+    }
+    case TokenInstruction.Compress: {
+      const compress = data as CompressData;
+      const buffer = Buffer.alloc(1 + 32 + 8);
+      buffer.writeUInt8(compress.instruction, 0);
+      buffer.set(compress.recipient, 1);
+      buffer.writeBigUInt64LE(compress.amount, 33);
+      return buffer;
+    }
+    case TokenInstruction.CompressedTransfer: {
+      const transfer = data as CompressedTransferData;
+      const tail = Buffer.alloc(32 + 8);
+      tail.set(transfer.recipient, 0);
+      tail.writeBigUInt64LE(transfer.amount, 32);
+      return Buffer.concat([
+        Buffer.from([transfer.instruction]),
+        serializeLeafProof(transfer.leaf, transfer.root, transfer.proof),
+        tail,
+      ]);
+    }
+    case TokenInstruction.Decompress: {
+      const decompress = data as DecompressData;
+      return Buffer.concat([
+        Buffer.from([decompress.instruction]),
+        serializeLeafProof(decompress.leaf, decompress.root, decompress.proof),
+      ]);
+    }
+    case TokenInstruction.ConfigureBridge: {
+      const configure = data as ConfigureBridgeData;
+      return Buffer.concat([Buffer.from([configure.instruction]), Buffer.from(configure.verifier)]);
+    }
+    case TokenInstruction.BridgeBurn: {
+      const burn = data as BridgeBurnData;
+      const buffer = Buffer.alloc(1 + 2 + 32 + 8);
+      buffer.writeUInt8(burn.instruction, 0);
+      buffer.writeUInt16LE(burn.target_chain, 1);
+      buffer.set(burn.target_address, 3);
+      buffer.writeBigUInt64LE(burn.amount, 35);
+      return buffer;
+    }
+    case TokenInstruction.BridgeMint: {
+      const mint = data as BridgeMintData;
+      const buffer = Buffer.alloc(1 + 2 + 32 + 8 + 8);
+      buffer.writeUInt8(mint.instruction, 0);
+      buffer.writeUInt16LE(mint.source_chain, 1);
+      buffer.set(mint.emitter, 3);
+      buffer.writeBigUInt64LE(mint.sequence, 35);
+      buffer.writeBigUInt64LE(mint.amount, 43);
+      return buffer;
+    }
+    case TokenInstruction.FreezeAccount: {
+      const freeze = data as FreezeAccountData;
+      if (freeze.thaw_at_slot === null) {
+        const buffer = Buffer.alloc(1 + 1 + 2);
+        buffer.writeUInt8(freeze.instruction, 0);
+        buffer.writeUInt8(0, 1);
+        buffer.writeUInt16LE(freeze.reason_code, 2);
+        return buffer;
+      }
+      const buffer = Buffer.alloc(1 + 1 + 8 + 2);
+      buffer.writeUInt8(freeze.instruction, 0);
+      buffer.writeUInt8(1, 1);
+      buffer.writeBigUInt64LE(freeze.thaw_at_slot, 2);
+      buffer.writeUInt16LE(freeze.reason_code, 10);
+      return buffer;
+    }
+    case TokenInstruction.SetFreezeExempt: {
+      const exempt = data as SetFreezeExemptData;
+      return Buffer.from([exempt.instruction, exempt.exempt ? 1 : 0]);
+    }
+    case TokenInstruction.GetExtensionTypes: {
+      const query = data as GetExtensionTypesData;
+      return Buffer.from([query.instruction, query.is_mint ? 1 : 0]);
+    }
+    case TokenInstruction.InitializeAccountWithOwnerCheck: {
+      const initialize = data as InitializeAccountWithOwnerCheckData;
+      return Buffer.from([initialize.instruction, initialize.owner_curve]);
+    }
+    case TokenInstruction.Crank: {
+      const crank = data as CrankData;
+      const buffer = Buffer.alloc(1 + 4 + crank.items.length);
+      buffer.writeUInt8(crank.instruction, 0);
+      buffer.writeUInt32LE(crank.items.length, 1);
+      buffer.set(crank.items, 5);
+      return buffer;
+    }
+    case TokenInstruction.SetAuthority: {
+      const setAuthority = data as SetAuthorityData;
+      if (setAuthority.new_authority === null) {
+        return Buffer.from([setAuthority.instruction, setAuthority.authority_type, 0]);
+      }
+      const buffer = Buffer.alloc(1 + 1 + 33);
+      buffer.writeUInt8(setAuthority.instruction, 0);
+      buffer.writeUInt8(setAuthority.authority_type, 1);
+      buffer.writeUInt8(1, 2);
+      buffer.set(setAuthority.new_authority, 3);
+      return buffer;
+    }
+    case TokenInstruction.RescaleMint: {
+      const rescale = data as RescaleMintData;
+      return Buffer.from([rescale.instruction, rescale.new_decimals]);
+    }
+    case TokenInstruction.RedeemHtlc: {
+      const redeem = data as RedeemHtlcData;
+      const buffer = Buffer.alloc(1 + 4 + redeem.preimage.length);
+      buffer.writeUInt8(redeem.instruction, 0);
+      buffer.writeUInt32LE(redeem.preimage.length, 1);
+      buffer.set(redeem.preimage, 5);
+      return buffer;
+    }
+    case TokenInstruction.AtomicSwap: {
+      const swap = data as AtomicSwapData;
+      const buffer = Buffer.alloc(1 + 8 + 8);
+      buffer.writeUInt8(swap.instruction, 0);
+      buffer.writeBigUInt64LE(swap.amount_a, 1);
+      buffer.writeBigUInt64LE(swap.amount_b, 9);
+      return buffer;
+    }
+    case TokenInstruction.PermitApprove: {
+      const permit = data as PermitApproveData;
+      const buffer = Buffer.alloc(1 + 32 + 8 + 8 + 8);
+      buffer.writeUInt8(permit.instruction, 0);
+      buffer.set(permit.delegate, 1);
+      buffer.writeBigUInt64LE(permit.amount, 33);
+      buffer.writeBigUInt64LE(permit.nonce, 41);
+      buffer.writeBigInt64LE(permit.deadline, 49);
+      return buffer;
+    }
+    case TokenInstruction.TransferFrom: {
+      const transfer = data as TransferFromData;
+      const buffer = Buffer.alloc(1 + 8);
+      buffer.writeUInt8(transfer.instruction, 0);
+      buffer.writeBigUInt64LE(transfer.amount, 1);
+      return buffer;
+    }
+    case TokenInstruction.TransferWithPermit: {
+      const permit = data as TransferWithPermitData;
+      const buffer = Buffer.alloc(1 + 8 + 8 + 8 + 8);
+      buffer.writeUInt8(permit.instruction, 0);
+      buffer.writeBigUInt64LE(permit.amount, 1);
+      buffer.writeBigUInt64LE(permit.tip, 9);
+      buffer.writeBigUInt64LE(permit.nonce, 17);
+      buffer.writeBigInt64LE(permit.deadline, 25);
+      return buffer;
+    }
+    case TokenInstruction.CreateSession: {
+      const session = data as CreateSessionData;
+      const buffer = Buffer.alloc(1 + 32 + 8 + 8);
+      buffer.writeUInt8(session.instruction, 0);
+      buffer.set(session.key, 1);
+      buffer.writeBigUInt64LE(session.max_amount, 33);
+      buffer.writeBigUInt64LE(session.expires_at_slot, 41);
+      return buffer;
+    }
+    case TokenInstruction.CreatePreAuthorization: {
+      const preAuthorization = data as CreatePreAuthorizationData;
+      const buffer = Buffer.alloc(1 + 32 + 8 + 8);
+      buffer.writeUInt8(preAuthorization.instruction, 0);
+      buffer.set(preAuthorization.merchant, 1);
+      buffer.writeBigUInt64LE(preAuthorization.amount_per_cycle, 33);
+      buffer.writeBigUInt64LE(preAuthorization.cycle_slots, 41);
+      return buffer;
+    }
+    case TokenInstruction.Debit: {
+      const debit = data as DebitData;
+      const buffer = Buffer.alloc(1 + 8);
+      buffer.writeUInt8(debit.instruction, 0);
+      buffer.writeBigUInt64LE(debit.amount, 1);
+      return buffer;
+    }
+    case TokenInstruction.InitializeSavingsVault: {
+      const savingsVault = data as InitializeSavingsVaultData;
+      const buffer = Buffer.alloc(1 + 32);
+      buffer.writeUInt8(savingsVault.instruction, 0);
+      buffer.set(savingsVault.yield_authority, 1);
+      return buffer;
+    }
+    case TokenInstruction.FundYield:
+    case TokenInstruction.DepositSavings: {
+      const savings = data as SavingsAmountData;
+      const buffer = Buffer.alloc(1 + 8);
+      buffer.writeUInt8(savings.instruction, 0);
+      buffer.writeBigUInt64LE(savings.amount, 1);
+      return buffer;
+    }
+    case TokenInstruction.WithdrawSavings: {
+      const withdraw = data as WithdrawSavingsData;
+      const buffer = Buffer.alloc(1 + 8);
+      buffer.writeUInt8(withdraw.instruction, 0);
+      buffer.writeBigUInt64LE(withdraw.shares, 1);
+      return buffer;
+    }
+    case TokenInstruction.EnablePointsExpiry: {
+      const points = data as EnablePointsExpiryData;
+      const buffer = Buffer.alloc(1 + 8);
+      buffer.writeUInt8(points.instruction, 0);
+      buffer.writeBigUInt64LE(points.lifetime_slots, 1);
+      return buffer;
+    }
+    case TokenInstruction.SetDormancyPolicy: {
+      const policy = data as SetDormancyPolicyData;
+      const buffer = Buffer.alloc(1 + 32 + 8 + 8);
+      buffer.writeUInt8(policy.instruction, 0);
+      buffer.set(policy.recovery_vault, 1);
+      buffer.writeBigUInt64LE(policy.dormant_epochs, 33);
+      buffer.writeBigUInt64LE(policy.notice_epochs, 41);
+      return buffer;
+    }
+    case TokenInstruction.UpdateAttestation: {
+      const attestation = data as UpdateAttestationData;
+      const buffer = Buffer.alloc(1 + 8);
+      buffer.writeUInt8(attestation.instruction, 0);
+      buffer.writeBigUInt64LE(attestation.reserves, 1);
+      return buffer;
+    }
+    case TokenInstruction.InitializeConversion: {
+      const conversion = data as InitializeConversionData;
+      const buffer = Buffer.alloc(1 + 8);
+      buffer.writeUInt8(conversion.instruction, 0);
+      buffer.writeBigUInt64LE(conversion.rate, 1);
+      return buffer;
+    }
+    case TokenInstruction.ConvertMint: {
+      const convert = data as ConvertMintData;
+      const buffer = Buffer.alloc(1 + 8);
+      buffer.writeUInt8(convert.instruction, 0);
+      buffer.writeBigUInt64LE(convert.amount, 1);
+      return buffer;
+    }
+    case TokenInstruction.Rebalance: {
+      const rebalance = data as RebalanceData;
+      const buffer = Buffer.alloc(1 + 8);
+      buffer.writeUInt8(rebalance.instruction, 0);
+      buffer.writeBigUInt64LE(rebalance.amount, 1);
+      return buffer;
+    }
+    case TokenInstruction.SetPauseFlags: {
+      const flags = data as SetPauseFlagsData;
+      const encodeOption = (flag: boolean | null) => (flag === null ? [0] : [1, flag ? 1 : 0]);
+      return Buffer.from([
+        flags.instruction,
+        ...encodeOption(flags.pause_transfers),
+        ...encodeOption(flags.pause_minting),
+        ...encodeOption(flags.pause_burning),
+      ]);
+    }
+    case TokenInstruction.SetActivationRequired: {
+      const activation = data as SetActivationRequiredData;
+      return Buffer.from([activation.instruction, activation.required ? 1 : 0]);
+    }
+    case TokenInstruction.InitializeEmissions: {
+      const emissions = data as InitializeEmissionsData;
+      const buffer = Buffer.alloc(1 + 8 + 8 + 8 + 4 + 34 * emissions.recipients.length);
+      buffer.writeUInt8(emissions.instruction, 0);
+      buffer.writeBigUInt64LE(emissions.tokens_per_slot, 1);
+      buffer.writeBigUInt64LE(emissions.start_slot, 9);
+      buffer.writeBigUInt64LE(emissions.end_slot, 17);
+      buffer.writeUInt32LE(emissions.recipients.length, 25);
+      emissions.recipients.forEach((recipient, i) => {
+        buffer.set(recipient.vault, 29 + 34 * i);
+        buffer.writeUInt16LE(recipient.weight, 29 + 34 * i + 32);
+      });
+      return buffer;
+    }
+    case TokenInstruction.SessionTransfer: {
+      const transfer = data as SessionTransferData;
+      const buffer = Buffer.alloc(1 + 8);
+      buffer.writeUInt8(transfer.instruction, 0);
+      buffer.writeBigUInt64LE(transfer.amount, 1);
+      return buffer;
+    }
+    case TokenInstruction.SetGuardians: {
+      const config = data as SetGuardiansData;
+      const buffer = Buffer.alloc(1 + 4 + 32 * config.guardians.length + 1);
+      buffer.writeUInt8(config.instruction, 0);
+      buffer.writeUInt32LE(config.guardians.length, 1);
+      config.guardians.forEach((guardian, i) => buffer.set(guardian, 5 + 32 * i));
+      buffer.writeUInt8(config.threshold, 5 + 32 * config.guardians.length);
+      return buffer;
+    }
+    case TokenInstruction.ProposeOwnerRecovery: {
+      const proposal = data as ProposeOwnerRecoveryData;
+      const buffer = Buffer.alloc(1 + 32);
+      buffer.writeUInt8(proposal.instruction, 0);
+      buffer.set(proposal.new_owner, 1);
+      return buffer;
+    }
+    case TokenInstruction.ExecuteAuthorityRecovery:
+    case TokenInstruction.CancelAuthorityRecovery:
+    case TokenInstruction.CloseEmptyMint:
+    case TokenInstruction.RevokeSession:
+    case TokenInstruction.CancelOwnerRecovery:
+    case TokenInstruction.RecoverOwner:
+    case TokenInstruction.EnableHolderCount:
+    case TokenInstruction.EnableActivityTracking:
+    case TokenInstruction.EnableMemoLog:
+    case TokenInstruction.GlobalFreeze:
+    case TokenInstruction.GlobalThaw:
+    case TokenInstruction.RefundHtlc:
+    case TokenInstruction.ReleaseConditionalTransfer:
+    case TokenInstruction.RefundConditionalTransfer:
+    case TokenInstruction.ClosePreAuthorization:
+    case TokenInstruction.DistributeEmissions:
+    case TokenInstruction.ExpirePoints:
+    case TokenInstruction.WarnDormant:
+    case TokenInstruction.SweepDormant:
+    case TokenInstruction.VerifySupplyBacked:
+    case TokenInstruction.ActivateAccount:
+      return Buffer.from([data.instruction]);
     default:
       throw new Error(`未知指令类型: ${(data as any).instruction}`);
   }
@@ -397,7 +3823,8 @@ function serializeBurnData(data: BurnData): Buffer {
  * 序列化设置铸币权限指令数据 - 修复版本
  */
 function serializeSetMintAuthorityData(data: SetMintAuthorityData): Buffer {
-  const buffer = Buffer.alloc(1 + 33); // instruction + new_authority
+  // instruction + new_authority（None 只占 1 字节，多余的补零会被程序当作尾随数据拒绝）
+  const buffer = Buffer.alloc(data.new_authority ? 1 + 33 : 1 + 1);
   let offset = 0;
   
   buffer.writeUInt8(data.instruction, offset); offset += 1;
@@ -408,7 +3835,6 @@ function serializeSetMintAuthorityData(data: SetMintAuthorityData): Buffer {
     buffer.set(data.new_authority, offset);
   } else {
     buffer.writeUInt8(0, offset); offset += 1; // None variant
-    // 剩余部分已经是0
   }
   
   console.log(`🔧 序列化 SetMintAuthority 数据:`, {
@@ -420,6 +3846,123 @@ function serializeSetMintAuthorityData(data: SetMintAuthorityData): Buffer {
   return buffer;
 }
 
+/**
+ * 序列化初始化联合曲线指令数据
+ */
+function serializeInitializeCurveData(data: InitializeCurveData): Buffer {
+  if (data.curve.kind === 'Linear') {
+    const buffer = Buffer.alloc(1 + 1 + 8 + 8);
+    buffer.writeUInt8(data.instruction, 0);
+    buffer.writeUInt8(0, 1);
+    buffer.writeBigUInt64LE(data.curve.base_price, 2);
+    buffer.writeBigUInt64LE(data.curve.slope, 10);
+    return buffer;
+  }
+
+  const buffer = Buffer.alloc(1 + 1 + 8 + 2);
+  buffer.writeUInt8(data.instruction, 0);
+  buffer.writeUInt8(1, 1);
+  buffer.writeBigUInt64LE(data.curve.base_price, 2);
+  buffer.writeUInt16LE(data.curve.growth_bps, 10);
+  return buffer;
+}
+
+/**
+ * 序列化配置预言机指令数据
+ */
+function serializeConfigureOracleData(data: ConfigureOracleData): Buffer {
+  const feedLength = data.price_feed ? 1 + 32 : 1;
+  const buffer = Buffer.alloc(1 + feedLength + 8 + 2);
+  let offset = 0;
+
+  buffer.writeUInt8(data.instruction, offset); offset += 1;
+  offset = writeOptionPubkey(buffer, offset, data.price_feed);
+  buffer.writeBigUInt64LE(data.max_staleness_slots, offset); offset += 8;
+  buffer.writeUInt16LE(data.collateral_ratio_bps, offset);
+  return buffer;
+}
+
+/**
+ * 按 Borsh 规则写入 Option<Pubkey>（None 只占 1 字节）
+ */
+function writeOptionPubkey(buffer: Buffer, offset: number, value: Uint8Array | null): number {
+  if (value && value.length === 32) {
+    buffer.writeUInt8(1, offset);
+    buffer.set(value, offset + 1);
+    return offset + 1 + 32;
+  }
+  buffer.writeUInt8(0, offset);
+  return offset + 1;
+}
+
+/**
+ * 按 Borsh 规则编码 String（u32 小端长度 + UTF-8 字节）
+ */
+function borshString(value: string): Buffer {
+  const bytes = Buffer.from(value, 'utf8');
+  const length = Buffer.alloc(4);
+  length.writeUInt32LE(bytes.length);
+  return Buffer.concat([length, bytes]);
+}
+
+/**
+ * 序列化创建元数据指令数据
+ */
+function serializeCreateMetadataData(data: CreateMetadataData): Buffer {
+  const fee = Buffer.alloc(2);
+  fee.writeUInt16LE(data.args.seller_fee_basis_points);
+  return Buffer.concat([
+    Buffer.from([data.instruction]),
+    borshString(data.args.name),
+    borshString(data.args.symbol),
+    borshString(data.args.uri),
+    fee,
+    Buffer.from([data.is_mutable ? 1 : 0]),
+  ]);
+}
+
+/**
+ * 序列化 “指令 + 两个 u64” 结构的指令数据
+ */
+function serializeTwoU64(instruction: TokenInstruction, first: bigint, second: bigint): Buffer {
+  const buffer = Buffer.alloc(1 + 8 + 8);
+  buffer.writeUInt8(instruction, 0);
+  buffer.writeBigUInt64LE(first, 1);
+  buffer.writeBigUInt64LE(second, 9);
+  return buffer;
+}
+
+/**
+ * 序列化注入分红指令数据
+ */
+function serializeFundDistributionData(data: FundDistributionData): Buffer {
+  const buffer = Buffer.alloc(1 + 8 + 32 + 8 + 8);
+  let offset = 0;
+
+  buffer.writeUInt8(data.instruction, offset); offset += 1;
+  buffer.writeBigUInt64LE(data.distribution_id, offset); offset += 8;
+  buffer.set(data.snapshot_root, offset); offset += 32;
+  buffer.writeBigUInt64LE(data.snapshot_supply, offset); offset += 8;
+  buffer.writeBigUInt64LE(data.amount, offset);
+  return buffer;
+}
+
+/**
+ * 序列化领取分红指令数据（Vec 前缀为 u32 长度）
+ */
+function serializeClaimDividendData(data: ClaimDividendData): Buffer {
+  const buffer = Buffer.alloc(1 + 8 + 4 + 32 * data.proof.length);
+  let offset = 0;
+
+  buffer.writeUInt8(data.instruction, offset); offset += 1;
+  buffer.writeBigUInt64LE(data.snapshot_balance, offset); offset += 8;
+  buffer.writeUInt32LE(data.proof.length, offset); offset += 4;
+  for (const node of data.proof) {
+    buffer.set(node, offset); offset += 32;
+  }
+  return buffer;
+}
+
 /**
  * 验证指令数据格式
  */
@@ -6,7 +6,9 @@ import {
   Transaction, 
   sendAndConfirmTransaction,
   TransactionInstruction,
-  LAMPORTS_PER_SOL
+  LAMPORTS_PER_SOL,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Ed25519Program
 } from '@solana/web3.js';
 import { 
   TokenInstruction,
@@ -15,11 +17,143 @@ import {
   TransferData,
   BurnData,
   SetMintAuthorityData,
+  InitializeCurveData,
+  BuyFromCurveData,
+  SellToCurveData,
+  ConfigureOracleData,
+  MintAgainstCollateralData,
+  CurveType,
+  BondingCurve,
+  RebaseData,
+  RebaseConfig,
+  FundDistributionData,
+  ClaimDividendData,
+  DelegateVotesData,
+  GetVotesAtData,
+  VoteCheckpoints,
+  TopUpRentData,
+  MintToNewAccountData,
+  BurnWithReceiptData,
+  BurnReceipt,
+  InitializeNftMintData,
+  CreateMetadataData,
+  MetadataArgs,
+  CreateMasterEditionData,
+  MasterEdition,
+  Edition,
+  SetTransferAllowlistData,
+  SetVelocityLimitData,
+  VelocityTracker,
+  QueueMintData,
+  QueuedMint,
+  TreasuryBurnEvent,
+  PlaceOrderData,
+  Order,
+  CreateAuctionData,
+  PurchaseData,
+  DutchAuction,
+  InitializeSaleData,
+  BuyTokensData,
+  Sale,
+  DepositData,
+  WithdrawData,
+  ShareVault,
+  GoverningTokensData,
+  TokenOwnerRecord,
+  AuthorityType,
+  InitializeEmergencyConfigData,
+  ProposeAuthorityRecoveryData,
+  EmergencyConfig,
+  AuthorityRecovery,
+  EnableAutoCloseData,
+  TransferSplitData,
+  AtomicSwapData,
+  PermitApproveData,
+  TransferFromData,
+  TransferWithPermitData,
+  CreateSessionData,
+  SessionTransferData,
+  SetGuardiansData,
+  ProposeOwnerRecoveryData,
+  TransferWithMemoData,
+  SweepAllData,
+  SettleBatchData,
+  ApproveMultiData,
+  CompressedLeaf,
+  InitializeCompressedTreeData,
+  CompressData,
+  CompressedTransferData,
+  DecompressData,
+  ConfigureBridgeData,
+  BridgeBurnData,
+  BridgeMintData,
+  RescaleMintData,
+  FreezeAccountData,
+  ThawAccountData,
+  SetAuthorityData,
+  SetFreezeExemptData,
+  SetAccountLabelData,
+  CreateMintWithSeedData,
+  GetExtensionTypesData,
+  InitializeAccountWithOwnerCheckData,
+  OwnerCurve,
+  CrankData,
+  CrankItem,
+  CreatePreAuthorizationData,
+  DebitData,
+  PreAuthorization,
+  InitializeSavingsVaultData,
+  SavingsAmountData,
+  WithdrawSavingsData,
+  SavingsVault,
+  SavingsPosition,
+  InitializeEmissionsData,
+  EnablePointsExpiryData,
+  MAX_POINTS_LOTS,
+  SetDormancyPolicyData,
+  DormancyNotice,
+  UpdateAttestationData,
+  Attestation,
+  InitializeConversionData,
+  ConvertMintData,
+  Conversion,
+  RebalanceData,
+  SetPauseFlagsData,
+  SetActivationRequiredData,
+  EmissionRecipient,
+  EmissionsConfig,
+  ExtensionType,
+  ACCOUNT_LABEL_LENGTH,
+  MEMO_LOG_CAPACITY,
+  OwnerRecovery,
+  Session,
+  Allowance,
+  CreateHtlcData,
+  RedeemHtlcData,
+  Htlc,
+  CreateConditionalTransferData,
+  ConditionalTransfer,
   Mint,
   TokenAccount,
   InstructionData,
   serializeInstructionData
 } from './types';
+import { createHash } from 'crypto';
+
+/**
+ * 规范部署的程序 ID，与 Rust 中 declare_id! 一致
+ */
+export const TOKEN_PROGRAM_ID = new PublicKey('ByiUxkVUtZM8fHoVFM3wsWVmaxL43i81G8eAHWKbwBBu');
+
+/**
+ * 校验 programId 为规范部署，避免复制粘贴把交易发给错误的程序；面向其他部署时传 allowOverride 显式放行
+ */
+export function checkProgramId(programId: PublicKey, allowOverride = false): PublicKey {
+  if (!allowOverride && !programId.equals(TOKEN_PROGRAM_ID)) {
+    throw new Error(`程序 ID ${programId.toBase58()} 不是规范部署 ${TOKEN_PROGRAM_ID.toBase58()}，如确需使用请显式放行`);
+  }
+  return programId;
+}
 
 /**
  * 等待确认的工具函数
@@ -85,15 +219,45 @@ export function createInitializeMintInstruction(
 }
 
 /**
- * 创建初始化代币账户指令
+ * 创建初始化 NFT 铸币账户指令（decimals = 0，供应量上限 1）
+ */
+export function createInitializeNftMintInstruction(
+  mint: PublicKey,
+  mintAuthority: PublicKey,
+  freezeAuthority: PublicKey | null,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new InitializeNftMintData({
+    mint_authority: mintAuthority.toBuffer(),
+    freeze_authority: freezeAuthority ? freezeAuthority.toBuffer() : null,
+  });
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: new PublicKey('SysvarRent111111111111111111111111111111111'), isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建初始化代币账户指令；ownerCurve 为 OnCurve 时要求 owner 是钱包，为 OffCurve 时要求 owner 是 PDA
  */
 export function createInitializeAccountInstruction(
   tokenAccount: PublicKey,
   mint: PublicKey,
   owner: PublicKey,
-  programId: PublicKey
+  programId: PublicKey,
+  ownerCurve?: OwnerCurve
 ): TransactionInstruction {
-  const data: InstructionData = { instruction: TokenInstruction.InitializeAccount };
+  // 指定 ownerCurve 时改用 InitializeAccountWithOwnerCheck，由程序校验 owner 是钱包还是 PDA
+  const data: InstructionData = ownerCurve === undefined
+    ? { instruction: TokenInstruction.InitializeAccount }
+    : new InitializeAccountWithOwnerCheckData({ owner_curve: ownerCurve });
   
   const keys = [
     { pubkey: tokenAccount, isSigner: false, isWritable: true },
@@ -117,7 +281,8 @@ export function createMintToInstruction(
   tokenAccount: PublicKey,
   mintAuthority: PublicKey,
   amount: bigint,
-  programId: PublicKey
+  programId: PublicKey,
+  voteDelegate: PublicKey | null = null
 ): TransactionInstruction {
   const data = new MintToData({ amount });
   
@@ -125,6 +290,7 @@ export function createMintToInstruction(
     { pubkey: mint, isSigner: false, isWritable: true },
     { pubkey: tokenAccount, isSigner: false, isWritable: true },
     { pubkey: mintAuthority, isSigner: true, isWritable: false },
+    ...voteCheckpointAccounts(mint, null, voteDelegate, programId),
   ];
   
   return new TransactionInstruction({
@@ -135,15 +301,29 @@ export function createMintToInstruction(
 }
 
 /**
- * 创建转移代币指令
+ * Transfer 可附带的引用键上限，与 Rust 中的 MAX_TRANSFER_REFERENCES 一致
+ */
+export const MAX_TRANSFER_REFERENCES = 8;
+
+/**
+ * 创建转移代币指令；references 为 Solana Pay 风格的引用键（如订单 ID 对应的地址），
+ * 以只读账户附在最后并记入 TransferEvent，可用 getSignaturesForAddress 按引用键查到这笔支付
  */
 export function createTransferInstruction(
   sourceTokenAccount: PublicKey,
   destinationTokenAccount: PublicKey,
   owner: PublicKey,
   amount: bigint,
-  programId: PublicKey
+  mint: PublicKey,
+  programId: PublicKey,
+  voteDelegates?: { mint: PublicKey; source: PublicKey | null; destination: PublicKey | null },
+  nftMint: PublicKey | null = null,
+  velocityLimited = false,
+  references: PublicKey[] = []
 ): TransactionInstruction {
+  if (references.length > MAX_TRANSFER_REFERENCES) {
+    throw new Error(`引用键至多 ${MAX_TRANSFER_REFERENCES} 个`);
+  }
   const data = new TransferData({ amount });
   
   const keys = [
@@ -151,6 +331,24 @@ export function createTransferInstruction(
     { pubkey: destinationTokenAccount, isSigner: false, isWritable: true },
     { pubkey: owner, isSigner: true, isWritable: false },
   ];
+  // 源账户所属的铸币账户：同步持有人计数、检查全局冻结与转账暂停
+  keys.push({ pubkey: mint, isSigner: false, isWritable: true });
+  if (velocityLimited) {
+    keys.push({ pubkey: findVelocityTrackerAddress(sourceTokenAccount, programId)[0], isSigner: false, isWritable: true });
+  }
+  if (voteDelegates) {
+    keys.push(...voteCheckpointAccounts(
+      voteDelegates.mint, voteDelegates.source, voteDelegates.destination, programId
+    ));
+  }
+  // NFT 账户转账需附带白名单 PDA 与指令 sysvar
+  if (nftMint) {
+    keys.push(
+      { pubkey: findTransferAllowlistAddress(nftMint, programId)[0], isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
+    );
+  }
+  keys.push(...references.map(pubkey => ({ pubkey, isSigner: false, isWritable: false })));
   
   return new TransactionInstruction({
     keys,
@@ -159,6 +357,89 @@ export function createTransferInstruction(
   });
 }
 
+/**
+ * 创建按基点份额拆分转账指令（shares 与 destinations 一一对应，之和必须为 10000，取整余数归第一个目标）
+ */
+export function createTransferSplitInstruction(
+  sourceTokenAccount: PublicKey,
+  owner: PublicKey,
+  destinations: PublicKey[],
+  shares: number[],
+  total: bigint,
+  mint: PublicKey,
+  programId: PublicKey,
+  voteDelegates?: { mint: PublicKey; source: PublicKey | null; destinations: (PublicKey | null)[] },
+  nftMint: PublicKey | null = null,
+  velocityLimited = false
+): TransactionInstruction {
+  const data = new TransferSplitData({ total, shares });
+
+  const keys = [
+    { pubkey: sourceTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: false },
+    ...destinations.map(destination => ({ pubkey: destination, isSigner: false, isWritable: true })),
+  ];
+  keys.push({ pubkey: mint, isSigner: false, isWritable: true });
+  if (velocityLimited) {
+    keys.push({ pubkey: findVelocityTrackerAddress(sourceTokenAccount, programId)[0], isSigner: false, isWritable: true });
+  }
+  // 每个目标依次附带各自需要的投票检查点
+  if (voteDelegates) {
+    voteDelegates.destinations.forEach(destination => {
+      keys.push(...voteCheckpointAccounts(voteDelegates.mint, voteDelegates.source, destination, programId));
+    });
+  }
+  if (nftMint) {
+    keys.push(
+      { pubkey: findTransferAllowlistAddress(nftMint, programId)[0], isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
+    );
+  }
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建双方原子互换指令（A 方的 X 换 B 方的 Y，双方都需签名）
+ */
+export function createAtomicSwapInstruction(
+  sourceA: PublicKey,
+  destinationA: PublicKey,
+  sourceB: PublicKey,
+  destinationB: PublicKey,
+  ownerA: PublicKey,
+  ownerB: PublicKey,
+  amountA: bigint,
+  amountB: bigint,
+  mintX: PublicKey,
+  mintY: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new AtomicSwapData({ amount_a: amountA, amount_b: amountB });
+
+  const keys = [
+    { pubkey: sourceA, isSigner: false, isWritable: true },
+    { pubkey: destinationA, isSigner: false, isWritable: true },
+    { pubkey: sourceB, isSigner: false, isWritable: true },
+    { pubkey: destinationB, isSigner: false, isWritable: true },
+    { pubkey: ownerA, isSigner: true, isWritable: false },
+    { pubkey: ownerB, isSigner: true, isWritable: false },
+  ];
+  for (const mint of [mintX, mintY]) {
+    keys.push({ pubkey: mint, isSigner: false, isWritable: true });
+  }
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
 /**
  * 创建销毁代币指令
  */
@@ -167,7 +448,8 @@ export function createBurnInstruction(
   mint: PublicKey,
   owner: PublicKey,
   amount: bigint,
-  programId: PublicKey
+  programId: PublicKey,
+  voteDelegate: PublicKey | null = null
 ): TransactionInstruction {
   const data = new BurnData({ amount });
   
@@ -175,6 +457,7 @@ export function createBurnInstruction(
     { pubkey: tokenAccount, isSigner: false, isWritable: true },
     { pubkey: mint, isSigner: false, isWritable: true },
     { pubkey: owner, isSigner: true, isWritable: false },
+    ...voteCheckpointAccounts(mint, voteDelegate, null, programId),
   ];
   
   return new TransactionInstruction({
@@ -186,6 +469,8 @@ export function createBurnInstruction(
 
 /**
  * 创建设置铸币权限指令
+ * @deprecated 改用 createSetAuthorityInstruction(mint, authority, AuthorityType.MintTokens, ...)；
+ * 旧指令仍可执行，但每次都会发出 DeprecatedSetMintAuthority 事件，已构造的旧指令可用 migrateSetMintAuthorityInstruction 改写
  */
 export function createSetMintAuthorityInstruction(
   mint: PublicKey,
@@ -215,31 +500,4389 @@ export function createSetMintAuthorityInstruction(
 }
 
 /**
- * 读取并解析铸币账户数据
+ * 推导联合曲线状态 PDA
  */
-export async function getMintData(
+export function findCurveAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('curve'), mint.toBuffer()], programId);
+}
+
+/**
+ * 推导联合曲线 SOL 金库 PDA
+ */
+export function findCurveVaultAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('curve_vault'), mint.toBuffer()], programId);
+}
+
+/**
+ * 创建初始化联合曲线指令
+ */
+export function createInitializeCurveInstruction(
+  mint: PublicKey,
+  mintAuthority: PublicKey,
+  payer: PublicKey,
+  curve: CurveType,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new InitializeCurveData({ curve });
+  const [curveAddress] = findCurveAddress(mint, programId);
+  const [vaultAddress] = findCurveVaultAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: curveAddress, isSigner: false, isWritable: true },
+    { pubkey: vaultAddress, isSigner: false, isWritable: true },
+    { pubkey: mintAuthority, isSigner: true, isWritable: false },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建曲线购买指令
+ */
+export function createBuyFromCurveInstruction(
+  mint: PublicKey,
+  buyerTokenAccount: PublicKey,
+  buyer: PublicKey,
+  amount: bigint,
+  maxLamports: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new BuyFromCurveData({ amount, max_lamports: maxLamports });
+  const [curveAddress] = findCurveAddress(mint, programId);
+  const [vaultAddress] = findCurveVaultAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: curveAddress, isSigner: false, isWritable: false },
+    { pubkey: vaultAddress, isSigner: false, isWritable: true },
+    { pubkey: buyerTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: buyer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建曲线卖出指令
+ */
+export function createSellToCurveInstruction(
+  mint: PublicKey,
+  sellerTokenAccount: PublicKey,
+  owner: PublicKey,
+  amount: bigint,
+  minLamports: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new SellToCurveData({ amount, min_lamports: minLamports });
+  const [curveAddress] = findCurveAddress(mint, programId);
+  const [vaultAddress] = findCurveVaultAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: curveAddress, isSigner: false, isWritable: false },
+    { pubkey: vaultAddress, isSigner: false, isWritable: true },
+    { pubkey: sellerTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 推导预言机配置 PDA
+ */
+export function findOracleConfigAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('oracle'), mint.toBuffer()], programId);
+}
+
+/**
+ * 推导抵押品金库 PDA
+ */
+export function findCollateralVaultAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('collateral_vault'), mint.toBuffer()], programId);
+}
+
+/**
+ * 创建配置预言机指令
+ */
+export function createConfigureOracleInstruction(
+  mint: PublicKey,
+  authority: PublicKey,
+  payer: PublicKey,
+  priceFeed: PublicKey | null,
+  maxStalenessSlots: bigint,
+  collateralRatioBps: number,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new ConfigureOracleData({
+    price_feed: priceFeed ? priceFeed.toBuffer() : null,
+    max_staleness_slots: maxStalenessSlots,
+    collateral_ratio_bps: collateralRatioBps,
+  });
+  const [configAddress] = findOracleConfigAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: configAddress, isSigner: false, isWritable: true },
+    { pubkey: authority, isSigner: true, isWritable: false },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建抵押铸造指令
+ */
+export function createMintAgainstCollateralInstruction(
+  mint: PublicKey,
+  priceFeed: PublicKey,
+  destination: PublicKey,
+  depositor: PublicKey,
+  collateralLamports: bigint,
+  minAmount: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new MintAgainstCollateralData({
+    collateral_lamports: collateralLamports,
+    min_amount: minAmount,
+  });
+  const [configAddress] = findOracleConfigAddress(mint, programId);
+  const [vaultAddress] = findCollateralVaultAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: configAddress, isSigner: false, isWritable: false },
+    { pubkey: priceFeed, isSigner: false, isWritable: false },
+    { pubkey: vaultAddress, isSigner: false, isWritable: true },
+    { pubkey: destination, isSigner: false, isWritable: true },
+    { pubkey: depositor, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取并解析联合曲线状态
+ */
+export async function getBondingCurveData(
   connection: Connection,
-  mint: PublicKey
-): Promise<Mint> {
-  const accountInfo = await connection.getAccountInfo(mint);
+  mint: PublicKey,
+  programId: PublicKey
+): Promise<BondingCurve> {
+  const [curveAddress] = findCurveAddress(mint, programId);
+  const accountInfo = await connection.getAccountInfo(curveAddress);
   if (!accountInfo) {
-    throw new Error(`铸币账户不存在: ${mint.toString()}`);
+    throw new Error(`联合曲线账户不存在: ${curveAddress.toString()}`);
   }
-  
-  return Mint.deserialize(accountInfo.data);
+
+  return BondingCurve.deserialize(accountInfo.data);
 }
 
 /**
- * 读取并解析代币账户数据
+ * 推导 rebase 配置 PDA
  */
-export async function getTokenAccountData(
+export function findRebaseConfigAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('rebase'), mint.toBuffer()], programId);
+}
+
+/**
+ * 创建开启 rebase 模式指令
+ */
+export function createEnableRebaseInstruction(
+  mint: PublicKey,
+  mintAuthority: PublicKey,
+  payer: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.EnableRebase };
+  const [configAddress] = findRebaseConfigAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: configAddress, isSigner: false, isWritable: true },
+    { pubkey: mintAuthority, isSigner: true, isWritable: false },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建 rebase 指令
+ */
+export function createRebaseInstruction(
+  mint: PublicKey,
+  rebaseAuthority: PublicKey,
+  numerator: bigint,
+  denominator: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new RebaseData({ numerator, denominator });
+  const [configAddress] = findRebaseConfigAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: configAddress, isSigner: false, isWritable: true },
+    { pubkey: rebaseAuthority, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取 rebase 配置
+ */
+export async function getRebaseConfigData(
   connection: Connection,
-  tokenAccount: PublicKey
-): Promise<TokenAccount> {
-  const accountInfo = await connection.getAccountInfo(tokenAccount);
+  mint: PublicKey,
+  programId: PublicKey
+): Promise<RebaseConfig> {
+  const [configAddress] = findRebaseConfigAddress(mint, programId);
+  const accountInfo = await connection.getAccountInfo(configAddress);
   if (!accountInfo) {
-    throw new Error(`代币账户不存在: ${tokenAccount.toString()}`);
+    throw new Error(`rebase 配置账户不存在: ${configAddress.toString()}`);
   }
-  
-  return TokenAccount.deserialize(accountInfo.data);
-}
\ No newline at end of file
+
+  return RebaseConfig.deserialize(accountInfo.data);
+}
+
+/**
+ * 读取代币账户的 rebase 后余额
+ */
+export async function getRebasedBalance(
+  connection: Connection,
+  tokenAccount: PublicKey,
+  programId: PublicKey
+): Promise<bigint> {
+  const account = await getTokenAccountData(connection, tokenAccount);
+  const config = await getRebaseConfigData(connection, new PublicKey(account.mint), programId);
+  return config.toRebasedAmount(account.amount);
+}
+
+/**
+ * 推导分红 PDA
+ */
+export function findDistributionAddress(
+  mint: PublicKey,
+  distributionId: bigint,
+  programId: PublicKey
+): [PublicKey, number] {
+  const id = Buffer.alloc(8);
+  id.writeBigUInt64LE(distributionId);
+  return PublicKey.findProgramAddressSync([Buffer.from('distribution'), mint.toBuffer(), id], programId);
+}
+
+/**
+ * 推导分红领取记录 PDA
+ */
+export function findDividendClaimAddress(
+  distribution: PublicKey,
+  holderTokenAccount: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('dividend_claim'), distribution.toBuffer(), holderTokenAccount.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * 分红余额快照：叶子与默克尔树构造规则与链上 Distribution::leaf / verify_proof 一致
+ */
+export class DividendSnapshot {
+  readonly entries: { tokenAccount: PublicKey; balance: bigint }[];
+  readonly supply: bigint;
+  private layers: Buffer[][];
+
+  constructor(entries: { tokenAccount: PublicKey; balance: bigint }[]) {
+    if (entries.length === 0) {
+      throw new Error('快照不能为空');
+    }
+    this.entries = entries;
+    this.supply = entries.reduce((sum, entry) => sum + entry.balance, BigInt(0));
+
+    const leaves = entries.map(entry => DividendSnapshot.leaf(entry.tokenAccount, entry.balance));
+    this.layers = [leaves];
+    while (this.layers[this.layers.length - 1].length > 1) {
+      const current = this.layers[this.layers.length - 1];
+      const next: Buffer[] = [];
+      for (let i = 0; i < current.length; i += 2) {
+        // 奇数个节点时最后一个直接上提
+        next.push(i + 1 < current.length ? DividendSnapshot.node(current[i], current[i + 1]) : current[i]);
+      }
+      this.layers.push(next);
+    }
+  }
+
+  static leaf(tokenAccount: PublicKey, balance: bigint): Buffer {
+    const amount = Buffer.alloc(8);
+    amount.writeBigUInt64LE(balance);
+    return createHash('sha256').update(Buffer.from([0])).update(tokenAccount.toBuffer()).update(amount).digest();
+  }
+
+  static node(a: Buffer, b: Buffer): Buffer {
+    const [left, right] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+    return createHash('sha256').update(Buffer.from([1])).update(left).update(right).digest();
+  }
+
+  get root(): Buffer {
+    return this.layers[this.layers.length - 1][0];
+  }
+
+  proof(tokenAccount: PublicKey): Buffer[] {
+    let index = this.entries.findIndex(entry => entry.tokenAccount.equals(tokenAccount));
+    if (index < 0) {
+      throw new Error(`快照中不存在该账户: ${tokenAccount.toString()}`);
+    }
+    const proof: Buffer[] = [];
+    for (const layer of this.layers.slice(0, -1)) {
+      const sibling = index ^ 1;
+      if (sibling < layer.length) {
+        proof.push(layer[sibling]);
+      }
+      index = Math.floor(index / 2);
+    }
+    return proof;
+  }
+}
+
+/**
+ * 创建注入分红指令（funder 须为持仓铸币的铸币权限）
+ */
+export function createFundDistributionInstruction(
+  mint: PublicKey,
+  distributionId: bigint,
+  rewardVault: PublicKey,
+  funderTokenAccount: PublicKey,
+  funder: PublicKey,
+  snapshot: DividendSnapshot,
+  amount: bigint,
+  rewardMint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new FundDistributionData({
+    distribution_id: distributionId,
+    snapshot_root: snapshot.root,
+    snapshot_supply: snapshot.supply,
+    amount,
+  });
+  const [distribution] = findDistributionAddress(mint, distributionId, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: distribution, isSigner: false, isWritable: true },
+    { pubkey: rewardVault, isSigner: false, isWritable: true },
+    { pubkey: funderTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: funder, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: rewardMint, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建领取分红指令
+ */
+export function createClaimDividendInstruction(
+  mint: PublicKey,
+  distributionId: bigint,
+  rewardVault: PublicKey,
+  holderTokenAccount: PublicKey,
+  destination: PublicKey,
+  owner: PublicKey,
+  snapshot: DividendSnapshot,
+  rewardMint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const entry = snapshot.entries.find(item => item.tokenAccount.equals(holderTokenAccount));
+  if (!entry) {
+    throw new Error(`快照中不存在该账户: ${holderTokenAccount.toString()}`);
+  }
+  const data = new ClaimDividendData({
+    snapshot_balance: entry.balance,
+    proof: snapshot.proof(holderTokenAccount),
+  });
+  const [distribution] = findDistributionAddress(mint, distributionId, programId);
+  const [claim] = findDividendClaimAddress(distribution, holderTokenAccount, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: distribution, isSigner: false, isWritable: true },
+    { pubkey: rewardVault, isSigner: false, isWritable: true },
+    { pubkey: holderTokenAccount, isSigner: false, isWritable: false },
+    { pubkey: destination, isSigner: false, isWritable: true },
+    { pubkey: claim, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: rewardMint, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 推导受托人投票检查点 PDA
+ */
+export function findVoteCheckpointsAddress(
+  mint: PublicKey,
+  delegatee: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('votes'), mint.toBuffer(), delegatee.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * 计算余额变化时需要附加的投票检查点账户（转出方在前，转入方在后）
+ */
+export function voteCheckpointAccounts(
+  mint: PublicKey,
+  fromDelegate: PublicKey | null,
+  toDelegate: PublicKey | null,
+  programId: PublicKey
+): { pubkey: PublicKey; isSigner: boolean; isWritable: boolean }[] {
+  if (fromDelegate && toDelegate && fromDelegate.equals(toDelegate)) {
+    return [];
+  }
+  return [fromDelegate, toDelegate]
+    .filter((delegate): delegate is PublicKey => delegate !== null)
+    .map(delegate => ({
+      pubkey: findVoteCheckpointsAddress(mint, delegate, programId)[0],
+      isSigner: false,
+      isWritable: true,
+    }));
+}
+
+/**
+ * 创建委托投票指令
+ */
+export function createDelegateVotesInstruction(
+  tokenAccount: PublicKey,
+  mint: PublicKey,
+  owner: PublicKey,
+  currentDelegate: PublicKey | null,
+  newDelegate: PublicKey | null,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new DelegateVotesData({
+    delegatee: newDelegate ? newDelegate.toBuffer() : null,
+  });
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ...voteCheckpointAccounts(mint, currentDelegate, newDelegate, programId),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建查询历史投票权指令（结果通过 return data 返回）
+ */
+export function createGetVotesAtInstruction(
+  mint: PublicKey,
+  delegatee: PublicKey,
+  slot: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new GetVotesAtData({ slot });
+  const [votesAddress] = findVoteCheckpointsAddress(mint, delegatee, programId);
+
+  return new TransactionInstruction({
+    keys: [{ pubkey: votesAddress, isSigner: false, isWritable: false }],
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建补充租金指令（lamports 传 0 时由程序自动补足差额）
+ */
+export function createTopUpRentInstruction(
+  account: PublicKey,
+  payer: PublicKey,
+  lamports: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new TopUpRentData({ lamports });
+
+  const keys = [
+    { pubkey: account, isSigner: false, isWritable: true },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 推导本程序内的关联代币账户地址
+ */
+export function findAssociatedTokenAddress(
+  wallet: PublicKey,
+  mint: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('associated'), wallet.toBuffer(), mint.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * 创建 “关联账户 + 铸造” 组合指令
+ */
+export function createMintToNewAccountInstruction(
+  mint: PublicKey,
+  wallet: PublicKey,
+  mintAuthority: PublicKey,
+  payer: PublicKey,
+  amount: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new MintToNewAccountData({ amount });
+  const [associatedAccount] = findAssociatedTokenAddress(wallet, mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: associatedAccount, isSigner: false, isWritable: true },
+    { pubkey: wallet, isSigner: false, isWritable: false },
+    { pubkey: mintAuthority, isSigner: true, isWritable: false },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 推导销毁凭证 PDA
+ */
+export function findBurnReceiptAddress(
+  mint: PublicKey,
+  burner: PublicKey,
+  receiptId: bigint,
+  programId: PublicKey
+): [PublicKey, number] {
+  const id = Buffer.alloc(8);
+  id.writeBigUInt64LE(receiptId);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('burn_receipt'), mint.toBuffer(), burner.toBuffer(), id],
+    programId
+  );
+}
+
+/**
+ * 创建带凭证销毁指令
+ */
+export function createBurnWithReceiptInstruction(
+  tokenAccount: PublicKey,
+  mint: PublicKey,
+  owner: PublicKey,
+  amount: bigint,
+  receiptId: bigint,
+  programId: PublicKey,
+  voteDelegate: PublicKey | null = null
+): TransactionInstruction {
+  const data = new BurnWithReceiptData({ amount, receipt_id: receiptId });
+  const [receipt] = findBurnReceiptAddress(mint, owner, receiptId, programId);
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: true },
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: true },
+    { pubkey: receipt, isSigner: false, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ...voteCheckpointAccounts(mint, voteDelegate, null, programId),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取销毁凭证
+ */
+export async function getBurnReceiptData(
+  connection: Connection,
+  mint: PublicKey,
+  burner: PublicKey,
+  receiptId: bigint,
+  programId: PublicKey
+): Promise<BurnReceipt> {
+  const [receiptAddress] = findBurnReceiptAddress(mint, burner, receiptId, programId);
+  const accountInfo = await connection.getAccountInfo(receiptAddress);
+  if (!accountInfo) {
+    throw new Error(`销毁凭证不存在: ${receiptAddress.toString()}`);
+  }
+
+  return BurnReceipt.deserialize(accountInfo.data);
+}
+
+/**
+ * Metaplex Token Metadata 程序 ID
+ */
+export const METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
+
+/**
+ * 推导铸币的 Metaplex 元数据 PDA
+ */
+export function findMetadataAddress(mint: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('metadata'), METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    METADATA_PROGRAM_ID
+  );
+}
+
+/**
+ * 创建元数据指令（由本程序 CPI 调用 Metaplex）
+ */
+export function createCreateMetadataInstruction(
+  mint: PublicKey,
+  mintAuthority: PublicKey,
+  payer: PublicKey,
+  args: MetadataArgs,
+  programId: PublicKey,
+  isMutable = true
+): TransactionInstruction {
+  const data = new CreateMetadataData({ args, is_mutable: isMutable });
+  const [metadata] = findMetadataAddress(mint);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: metadata, isSigner: false, isWritable: true },
+    { pubkey: mintAuthority, isSigner: true, isWritable: false },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: METADATA_PROGRAM_ID, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 推导母版版本 PDA
+ */
+export function findMasterEditionAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('master_edition'), mint.toBuffer()], programId);
+}
+
+/**
+ * 推导印刷副本 PDA
+ */
+export function findEditionAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('edition'), mint.toBuffer()], programId);
+}
+
+/**
+ * 创建母版版本指令
+ */
+export function createCreateMasterEditionInstruction(
+  mint: PublicKey,
+  tokenAccount: PublicKey,
+  owner: PublicKey,
+  maxSupply: bigint | null,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new CreateMasterEditionData({ max_supply: maxSupply });
+  const [masterEdition] = findMasterEditionAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: masterEdition, isSigner: false, isWritable: true },
+    { pubkey: tokenAccount, isSigner: false, isWritable: false },
+    { pubkey: owner, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建印刷副本指令（newMint 需事先通过 InitializeNftMint 初始化，铸币权限为母版持有人）
+ */
+export function createPrintEditionInstruction(
+  masterMint: PublicKey,
+  masterTokenAccount: PublicKey,
+  owner: PublicKey,
+  newMint: PublicKey,
+  destination: PublicKey,
+  programId: PublicKey,
+  voteDelegate: PublicKey | null = null
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.PrintEdition };
+  const [masterEdition] = findMasterEditionAddress(masterMint, programId);
+  const [edition] = findEditionAddress(newMint, programId);
+
+  const keys = [
+    { pubkey: masterMint, isSigner: false, isWritable: false },
+    { pubkey: masterEdition, isSigner: false, isWritable: true },
+    { pubkey: masterTokenAccount, isSigner: false, isWritable: false },
+    { pubkey: owner, isSigner: true, isWritable: true },
+    { pubkey: newMint, isSigner: false, isWritable: true },
+    { pubkey: edition, isSigner: false, isWritable: true },
+    { pubkey: destination, isSigner: false, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ...voteCheckpointAccounts(newMint, null, voteDelegate, programId),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取母版版本
+ */
+export async function getMasterEditionData(
+  connection: Connection,
+  mint: PublicKey,
+  programId: PublicKey
+): Promise<MasterEdition> {
+  const [masterEdition] = findMasterEditionAddress(mint, programId);
+  const accountInfo = await connection.getAccountInfo(masterEdition);
+  if (!accountInfo) {
+    throw new Error(`母版版本账户不存在: ${masterEdition.toString()}`);
+  }
+
+  return MasterEdition.deserialize(accountInfo.data);
+}
+
+/**
+ * 读取印刷副本信息
+ */
+export async function getEditionData(
+  connection: Connection,
+  mint: PublicKey,
+  programId: PublicKey
+): Promise<Edition> {
+  const [edition] = findEditionAddress(mint, programId);
+  const accountInfo = await connection.getAccountInfo(edition);
+  if (!accountInfo) {
+    throw new Error(`副本版本账户不存在: ${edition.toString()}`);
+  }
+
+  return Edition.deserialize(accountInfo.data);
+}
+
+/**
+ * 推导 NFT 转账白名单 PDA
+ */
+export function findTransferAllowlistAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('transfer_allowlist'), mint.toBuffer()], programId);
+}
+
+/**
+ * 创建设置转账白名单指令
+ */
+export function createSetTransferAllowlistInstruction(
+  mint: PublicKey,
+  authority: PublicKey,
+  payer: PublicKey,
+  programs: PublicKey[],
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new SetTransferAllowlistData({ programs: programs.map(program => program.toBuffer()) });
+  const [allowlist] = findTransferAllowlistAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: allowlist, isSigner: false, isWritable: true },
+    { pubkey: authority, isSigner: true, isWritable: false },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 推导代币账户的转账速度跟踪 PDA
+ */
+export function findVelocityTrackerAddress(tokenAccount: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('velocity'), tokenAccount.toBuffer()], programId);
+}
+
+/**
+ * 创建设置转账速度限制指令（maxAmount 为 0 时关闭）
+ */
+export function createSetVelocityLimitInstruction(
+  tokenAccount: PublicKey,
+  mint: PublicKey,
+  freezeAuthority: PublicKey,
+  payer: PublicKey,
+  maxAmount: bigint,
+  windowSlots: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new SetVelocityLimitData({ max_amount: maxAmount, window_slots: windowSlots });
+  const [tracker] = findVelocityTrackerAddress(tokenAccount, programId);
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: true },
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: tracker, isSigner: false, isWritable: true },
+    { pubkey: freezeAuthority, isSigner: true, isWritable: false },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建解冻代币账户指令；reasonCode 为发行方定义的原因码（不能为 0），记入链上的 AccountThawed 事件
+ */
+export function createThawAccountInstruction(
+  tokenAccount: PublicKey,
+  mint: PublicKey,
+  freezeAuthority: PublicKey,
+  reasonCode: number,
+  programId: PublicKey,
+  velocityLimited = false
+): TransactionInstruction {
+  const data = new ThawAccountData({ reason_code: reasonCode });
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: true },
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: freezeAuthority, isSigner: true, isWritable: false },
+  ];
+  if (velocityLimited) {
+    keys.push({ pubkey: findVelocityTrackerAddress(tokenAccount, programId)[0], isSigner: false, isWritable: true });
+  }
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建冻结代币账户指令（仅冻结权限）；reasonCode 为发行方定义的原因码（不能为 0 或 FREEZE_REASON_VELOCITY_LIMIT），
+ * 记入链上的 AccountFrozen 事件。thawAtSlot 不为空时到达该 slot 后自动解冻，
+ * 代币账户需按 TOKEN_ACCOUNT_WITH_SCHEDULED_THAW_SIZE 分配
+ */
+export function createFreezeAccountInstruction(
+  tokenAccount: PublicKey,
+  mint: PublicKey,
+  freezeAuthority: PublicKey,
+  reasonCode: number,
+  programId: PublicKey,
+  thawAtSlot: bigint | null = null
+): TransactionInstruction {
+  const data = new FreezeAccountData({ thaw_at_slot: thawAtSlot, reason_code: reasonCode });
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: true },
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: freezeAuthority, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取转账速度跟踪状态
+ */
+export async function getVelocityTrackerData(
+  connection: Connection,
+  tokenAccount: PublicKey,
+  programId: PublicKey
+): Promise<VelocityTracker> {
+  const [tracker] = findVelocityTrackerAddress(tokenAccount, programId);
+  const accountInfo = await connection.getAccountInfo(tracker);
+  if (!accountInfo) {
+    throw new Error(`速度跟踪账户不存在: ${tracker.toString()}`);
+  }
+
+  return VelocityTracker.deserialize(accountInfo.data);
+}
+
+/**
+ * 推导排队铸造 PDA
+ */
+export function findQueuedMintAddress(
+  mint: PublicKey,
+  destination: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('queued_mint'), mint.toBuffer(), destination.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * 创建排队铸造指令
+ */
+export function createQueueMintInstruction(
+  mint: PublicKey,
+  mintAuthority: PublicKey,
+  destination: PublicKey,
+  amount: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new QueueMintData({ amount, destination: destination.toBuffer() });
+  const [queued] = findQueuedMintAddress(mint, destination, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: queued, isSigner: false, isWritable: true },
+    { pubkey: mintAuthority, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建执行排队铸造指令（时间锁到期后任何人可发送）
+ */
+export function createExecuteQueuedMintInstruction(
+  mint: PublicKey,
+  destination: PublicKey,
+  mintAuthority: PublicKey,
+  programId: PublicKey,
+  voteDelegate: PublicKey | null = null
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.ExecuteQueuedMint };
+  const [queued] = findQueuedMintAddress(mint, destination, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: queued, isSigner: false, isWritable: true },
+    { pubkey: destination, isSigner: false, isWritable: true },
+    { pubkey: mintAuthority, isSigner: false, isWritable: true },
+    ...voteCheckpointAccounts(mint, null, voteDelegate, programId),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建取消排队铸造指令
+ */
+export function createCancelQueuedMintInstruction(
+  mint: PublicKey,
+  destination: PublicKey,
+  mintAuthority: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.CancelQueuedMint };
+  const [queued] = findQueuedMintAddress(mint, destination, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: queued, isSigner: false, isWritable: true },
+    { pubkey: mintAuthority, isSigner: true, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取排队铸造记录
+ */
+export async function getQueuedMintData(
+  connection: Connection,
+  mint: PublicKey,
+  destination: PublicKey,
+  programId: PublicKey
+): Promise<QueuedMint> {
+  const [queued] = findQueuedMintAddress(mint, destination, programId);
+  const accountInfo = await connection.getAccountInfo(queued);
+  if (!accountInfo) {
+    throw new Error(`排队铸造不存在: ${queued.toString()}`);
+  }
+
+  return QueuedMint.deserialize(accountInfo.data);
+}
+
+/**
+ * 推导国库代币账户 PDA
+ */
+export function findTreasuryAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('treasury'), mint.toBuffer()], programId);
+}
+
+/**
+ * 创建初始化国库指令
+ */
+export function createInitializeTreasuryInstruction(
+  mint: PublicKey,
+  payer: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.InitializeTreasury };
+  const [treasury] = findTreasuryAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: treasury, isSigner: false, isWritable: true },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建销毁国库余额指令（无需签名）
+ */
+export function createBurnTreasuryInstruction(
+  mint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.BurnTreasury };
+  const [treasury] = findTreasuryAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: treasury, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 从交易日志中解析国库销毁事件（"Program data: <base64>"）
+ */
+export function parseTreasuryBurnEvents(logs: string[]): TreasuryBurnEvent[] {
+  const prefix = 'Program data: ';
+  return logs
+    .filter(log => log.startsWith(prefix))
+    .map(log => Buffer.from(log.slice(prefix.length), 'base64'))
+    .filter(data => data.length === TreasuryBurnEvent.LEN)
+    .map(data => TreasuryBurnEvent.deserialize(data));
+}
+
+/**
+ * 推导托管代币账户对应的挂单 PDA
+ */
+export function findOrderAddress(escrow: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('order'), escrow.toBuffer()], programId);
+}
+
+/**
+ * 创建 OTC 挂单指令（托管账户需事先以挂单 PDA 为所有者初始化）
+ */
+export function createPlaceOrderInstruction(
+  escrow: PublicKey,
+  makerTokenAccount: PublicKey,
+  makerReceiveAccount: PublicKey,
+  maker: PublicKey,
+  giveAmount: bigint,
+  wantMint: PublicKey,
+  wantAmount: bigint,
+  giveMint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new PlaceOrderData({
+    give_amount: giveAmount,
+    want_mint: wantMint.toBuffer(),
+    want_amount: wantAmount,
+  });
+  const [order] = findOrderAddress(escrow, programId);
+
+  const keys = [
+    { pubkey: order, isSigner: false, isWritable: true },
+    { pubkey: escrow, isSigner: false, isWritable: true },
+    { pubkey: makerTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: makerReceiveAccount, isSigner: false, isWritable: false },
+    { pubkey: maker, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: giveMint, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建 OTC 吃单指令
+ */
+export function createFillOrderInstruction(
+  escrow: PublicKey,
+  makerReceiveAccount: PublicKey,
+  takerTokenAccount: PublicKey,
+  takerReceiveAccount: PublicKey,
+  taker: PublicKey,
+  maker: PublicKey,
+  wantMint: PublicKey,
+  giveMint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.FillOrder };
+  const [order] = findOrderAddress(escrow, programId);
+
+  const keys = [
+    { pubkey: order, isSigner: false, isWritable: true },
+    { pubkey: escrow, isSigner: false, isWritable: true },
+    { pubkey: makerReceiveAccount, isSigner: false, isWritable: true },
+    { pubkey: takerTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: takerReceiveAccount, isSigner: false, isWritable: true },
+    { pubkey: taker, isSigner: true, isWritable: false },
+    { pubkey: maker, isSigner: false, isWritable: true },
+    { pubkey: wantMint, isSigner: false, isWritable: true },
+    { pubkey: giveMint, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建 OTC 撤单指令
+ */
+export function createCancelOrderInstruction(
+  escrow: PublicKey,
+  refundAccount: PublicKey,
+  maker: PublicKey,
+  giveMint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.CancelOrder };
+  const [order] = findOrderAddress(escrow, programId);
+
+  const keys = [
+    { pubkey: order, isSigner: false, isWritable: true },
+    { pubkey: escrow, isSigner: false, isWritable: true },
+    { pubkey: refundAccount, isSigner: false, isWritable: true },
+    { pubkey: maker, isSigner: true, isWritable: true },
+    { pubkey: giveMint, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取 OTC 挂单
+ */
+export async function getOrderData(
+  connection: Connection,
+  escrow: PublicKey,
+  programId: PublicKey
+): Promise<Order> {
+  const [order] = findOrderAddress(escrow, programId);
+  const accountInfo = await connection.getAccountInfo(order);
+  if (!accountInfo) {
+    throw new Error(`挂单不存在: ${order.toString()}`);
+  }
+
+  return Order.deserialize(accountInfo.data);
+}
+
+/**
+ * 推导荷兰式拍卖状态 PDA
+ */
+export function findAuctionAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('auction'), mint.toBuffer()], programId);
+}
+
+/**
+ * 推导荷兰式拍卖 SOL 金库 PDA
+ */
+export function findAuctionVaultAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('auction_vault'), mint.toBuffer()], programId);
+}
+
+/**
+ * 创建荷兰式拍卖指令
+ */
+export function createCreateAuctionInstruction(
+  mint: PublicKey,
+  mintAuthority: PublicKey,
+  payer: PublicKey,
+  startPrice: bigint,
+  floorPrice: bigint,
+  decay: bigint,
+  supply: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new CreateAuctionData({
+    start_price: startPrice,
+    floor_price: floorPrice,
+    decay,
+    supply,
+  });
+  const [auction] = findAuctionAddress(mint, programId);
+  const [vault] = findAuctionVaultAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: auction, isSigner: false, isWritable: true },
+    { pubkey: vault, isSigner: false, isWritable: true },
+    { pubkey: mintAuthority, isSigner: true, isWritable: false },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建拍卖购买指令
+ */
+export function createPurchaseInstruction(
+  mint: PublicKey,
+  buyerTokenAccount: PublicKey,
+  buyer: PublicKey,
+  amount: bigint,
+  maxLamports: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new PurchaseData({ amount, max_lamports: maxLamports });
+  const [auction] = findAuctionAddress(mint, programId);
+  const [vault] = findAuctionVaultAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: auction, isSigner: false, isWritable: true },
+    { pubkey: vault, isSigner: false, isWritable: true },
+    { pubkey: buyerTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: buyer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取荷兰式拍卖状态
+ */
+export async function getAuctionData(
+  connection: Connection,
+  mint: PublicKey,
+  programId: PublicKey
+): Promise<DutchAuction> {
+  const [auction] = findAuctionAddress(mint, programId);
+  const accountInfo = await connection.getAccountInfo(auction);
+  if (!accountInfo) {
+    throw new Error(`拍卖不存在: ${auction.toString()}`);
+  }
+
+  return DutchAuction.deserialize(accountInfo.data);
+}
+
+/**
+ * 推导众筹状态 PDA
+ */
+export function findSaleAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('sale'), mint.toBuffer()], programId);
+}
+
+/**
+ * 推导众筹 SOL 金库 PDA
+ */
+export function findSaleVaultAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('sale_vault'), mint.toBuffer()], programId);
+}
+
+/**
+ * 创建初始化众筹指令
+ */
+export function createInitializeSaleInstruction(
+  mint: PublicKey,
+  mintAuthority: PublicKey,
+  payer: PublicKey,
+  priceLamportsPerToken: bigint,
+  cap: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new InitializeSaleData({ price_lamports_per_token: priceLamportsPerToken, cap });
+  const [sale] = findSaleAddress(mint, programId);
+  const [vault] = findSaleVaultAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: sale, isSigner: false, isWritable: true },
+    { pubkey: vault, isSigner: false, isWritable: true },
+    { pubkey: mintAuthority, isSigner: true, isWritable: false },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建众筹购买指令
+ */
+export function createBuyTokensInstruction(
+  mint: PublicKey,
+  buyerTokenAccount: PublicKey,
+  buyer: PublicKey,
+  amount: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new BuyTokensData({ amount });
+  const [sale] = findSaleAddress(mint, programId);
+  const [vault] = findSaleVaultAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: sale, isSigner: false, isWritable: true },
+    { pubkey: vault, isSigner: false, isWritable: true },
+    { pubkey: buyerTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: buyer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建提取众筹货款指令
+ */
+export function createWithdrawProceedsInstruction(
+  mint: PublicKey,
+  authority: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.WithdrawProceeds };
+  const [sale] = findSaleAddress(mint, programId);
+  const [vault] = findSaleVaultAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: sale, isSigner: false, isWritable: false },
+    { pubkey: vault, isSigner: false, isWritable: true },
+    { pubkey: authority, isSigner: true, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取众筹状态
+ */
+export async function getSaleData(
+  connection: Connection,
+  mint: PublicKey,
+  programId: PublicKey
+): Promise<Sale> {
+  const [sale] = findSaleAddress(mint, programId);
+  const accountInfo = await connection.getAccountInfo(sale);
+  if (!accountInfo) {
+    throw new Error(`众筹不存在: ${sale.toString()}`);
+  }
+
+  return Sale.deserialize(accountInfo.data);
+}
+
+/**
+ * 推导份额金库 PDA
+ */
+export function findShareVaultAddress(shareMint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('share_vault'), shareMint.toBuffer()], programId);
+}
+
+/**
+ * 创建初始化份额金库指令（资产托管账户需事先以金库 PDA 为所有者初始化）
+ */
+export function createInitializeShareVaultInstruction(
+  shareMint: PublicKey,
+  assetVault: PublicKey,
+  shareMintAuthority: PublicKey,
+  payer: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.InitializeShareVault };
+  const [vault] = findShareVaultAddress(shareMint, programId);
+
+  const keys = [
+    { pubkey: shareMint, isSigner: false, isWritable: true },
+    { pubkey: vault, isSigner: false, isWritable: true },
+    { pubkey: assetVault, isSigner: false, isWritable: false },
+    { pubkey: shareMintAuthority, isSigner: true, isWritable: false },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建份额金库存款指令
+ */
+export function createDepositInstruction(
+  shareMint: PublicKey,
+  assetVault: PublicKey,
+  sourceTokenAccount: PublicKey,
+  shareTokenAccount: PublicKey,
+  owner: PublicKey,
+  amount: bigint,
+  assetMint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new DepositData({ amount });
+  const [vault] = findShareVaultAddress(shareMint, programId);
+
+  const keys = [
+    { pubkey: vault, isSigner: false, isWritable: false },
+    { pubkey: shareMint, isSigner: false, isWritable: true },
+    { pubkey: assetVault, isSigner: false, isWritable: true },
+    { pubkey: sourceTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: shareTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: false },
+    { pubkey: assetMint, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建份额金库取款指令
+ */
+export function createWithdrawInstruction(
+  shareMint: PublicKey,
+  assetVault: PublicKey,
+  shareTokenAccount: PublicKey,
+  destinationTokenAccount: PublicKey,
+  owner: PublicKey,
+  shares: bigint,
+  assetMint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new WithdrawData({ shares });
+  const [vault] = findShareVaultAddress(shareMint, programId);
+
+  const keys = [
+    { pubkey: vault, isSigner: false, isWritable: false },
+    { pubkey: shareMint, isSigner: false, isWritable: true },
+    { pubkey: assetVault, isSigner: false, isWritable: true },
+    { pubkey: shareTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: destinationTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: false },
+    { pubkey: assetMint, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取份额金库
+ */
+export async function getShareVaultData(
+  connection: Connection,
+  shareMint: PublicKey,
+  programId: PublicKey
+): Promise<ShareVault> {
+  const [vault] = findShareVaultAddress(shareMint, programId);
+  const accountInfo = await connection.getAccountInfo(vault);
+  if (!accountInfo) {
+    throw new Error(`份额金库不存在: ${vault.toString()}`);
+  }
+
+  return ShareVault.deserialize(accountInfo.data);
+}
+
+/**
+ * 推导治理领域内某铸币的托管代币账户
+ */
+export function findGoverningTokenHoldingAddress(
+  realm: PublicKey,
+  mint: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('governance'), realm.toBuffer(), mint.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * 推导代币持有人记录
+ */
+export function findTokenOwnerRecordAddress(
+  realm: PublicKey,
+  mint: PublicKey,
+  owner: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('governance'), realm.toBuffer(), mint.toBuffer(), owner.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * 创建锁入治理代币指令
+ */
+export function createDepositGoverningTokensInstruction(
+  realm: PublicKey,
+  mint: PublicKey,
+  sourceTokenAccount: PublicKey,
+  owner: PublicKey,
+  amount: bigint,
+  programId: PublicKey,
+  voteDelegate: PublicKey | null = null
+): TransactionInstruction {
+  const data = new GoverningTokensData({
+    instruction: TokenInstruction.DepositGoverningTokens,
+    realm: realm.toBuffer(),
+    amount,
+  });
+  const [record] = findTokenOwnerRecordAddress(realm, mint, owner, programId);
+  const [holding] = findGoverningTokenHoldingAddress(realm, mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: record, isSigner: false, isWritable: true },
+    { pubkey: holding, isSigner: false, isWritable: true },
+    { pubkey: sourceTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ...voteCheckpointAccounts(mint, voteDelegate, null, programId),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建取回治理代币指令
+ */
+export function createWithdrawGoverningTokensInstruction(
+  realm: PublicKey,
+  mint: PublicKey,
+  destinationTokenAccount: PublicKey,
+  owner: PublicKey,
+  amount: bigint,
+  programId: PublicKey,
+  voteDelegate: PublicKey | null = null
+): TransactionInstruction {
+  const data = new GoverningTokensData({
+    instruction: TokenInstruction.WithdrawGoverningTokens,
+    realm: realm.toBuffer(),
+    amount,
+  });
+  const [record] = findTokenOwnerRecordAddress(realm, mint, owner, programId);
+  const [holding] = findGoverningTokenHoldingAddress(realm, mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: record, isSigner: false, isWritable: true },
+    { pubkey: holding, isSigner: false, isWritable: true },
+    { pubkey: destinationTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: false },
+    ...voteCheckpointAccounts(mint, null, voteDelegate, programId),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取代币持有人记录（投票权重）
+ */
+export async function getTokenOwnerRecordData(
+  connection: Connection,
+  realm: PublicKey,
+  mint: PublicKey,
+  owner: PublicKey,
+  programId: PublicKey
+): Promise<TokenOwnerRecord> {
+  const [record] = findTokenOwnerRecordAddress(realm, mint, owner, programId);
+  const accountInfo = await connection.getAccountInfo(record);
+  if (!accountInfo) {
+    throw new Error(`代币持有人记录不存在: ${record.toString()}`);
+  }
+
+  return TokenOwnerRecord.deserialize(accountInfo.data);
+}
+
+const BPF_LOADER_UPGRADEABLE_ID = new PublicKey('BPFLoaderUpgradeab1e11111111111111111111111');
+
+/**
+ * 推导紧急配置 PDA
+ */
+export function findEmergencyConfigAddress(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('emergency_config')], programId);
+}
+
+/**
+ * 推导权限恢复 PDA
+ */
+export function findAuthorityRecoveryAddress(
+  mint: PublicKey,
+  authorityType: AuthorityType,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('authority_recovery'), mint.toBuffer(), Buffer.from([authorityType])],
+    programId
+  );
+}
+
+/**
+ * 创建设置紧急管理员指令（需程序升级权限签名）
+ */
+export function createInitializeEmergencyConfigInstruction(
+  upgradeAuthority: PublicKey,
+  emergencyAdmin: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new InitializeEmergencyConfigData({ emergency_admin: emergencyAdmin.toBuffer() });
+  const [config] = findEmergencyConfigAddress(programId);
+  const [programData] = PublicKey.findProgramAddressSync([programId.toBuffer()], BPF_LOADER_UPGRADEABLE_ID);
+
+  const keys = [
+    { pubkey: config, isSigner: false, isWritable: true },
+    { pubkey: programData, isSigner: false, isWritable: false },
+    { pubkey: upgradeAuthority, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建权限恢复提议指令（紧急管理员签名）
+ */
+export function createProposeAuthorityRecoveryInstruction(
+  mint: PublicKey,
+  emergencyAdmin: PublicKey,
+  authorityType: AuthorityType,
+  newAuthority: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new ProposeAuthorityRecoveryData({
+    authority_type: authorityType,
+    new_authority: newAuthority.toBuffer(),
+  });
+  const [config] = findEmergencyConfigAddress(programId);
+  const [recovery] = findAuthorityRecoveryAddress(mint, authorityType, programId);
+
+  const keys = [
+    { pubkey: config, isSigner: false, isWritable: false },
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: recovery, isSigner: false, isWritable: true },
+    { pubkey: emergencyAdmin, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建执行权限恢复指令（时间锁到期后任何人可发送）
+ */
+export function createExecuteAuthorityRecoveryInstruction(
+  mint: PublicKey,
+  emergencyAdmin: PublicKey,
+  authorityType: AuthorityType,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.ExecuteAuthorityRecovery };
+  const [recovery] = findAuthorityRecoveryAddress(mint, authorityType, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: recovery, isSigner: false, isWritable: true },
+    { pubkey: emergencyAdmin, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建取消权限恢复指令（紧急管理员或当前权限签名）
+ */
+export function createCancelAuthorityRecoveryInstruction(
+  mint: PublicKey,
+  signer: PublicKey,
+  emergencyAdmin: PublicKey,
+  authorityType: AuthorityType,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.CancelAuthorityRecovery };
+  const [recovery] = findAuthorityRecoveryAddress(mint, authorityType, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: recovery, isSigner: false, isWritable: true },
+    { pubkey: signer, isSigner: true, isWritable: false },
+    { pubkey: emergencyAdmin, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建开启空铸币自动关闭指令（铸币权限签名，铸币账户需按 MINT_WITH_CLOSE_CONFIG_SIZE 分配）
+ */
+export function createEnableAutoCloseInstruction(
+  mint: PublicKey,
+  mintAuthority: PublicKey,
+  closeDestination: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new EnableAutoCloseData({ close_destination: closeDestination.toBuffer() });
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: mintAuthority, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建开启持有人计数指令（铸币权限签名，只能在供应量为 0 时调用，铸币账户需按 MINT_WITH_HOLDER_COUNT_SIZE 分配）
+ */
+export function createEnableHolderCountInstruction(
+  mint: PublicKey,
+  mintAuthority: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.EnableHolderCount };
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: mintAuthority, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 在账户的 TLV 扩展区（从基础状态 baseLen 字节之后开始：类型 u16 | 长度 u16 | 值）中查找扩展，返回值的字节
+ */
+export function findExtensionData(data: Buffer, baseLen: number, extensionType: number): Buffer | null {
+  let offset = baseLen;
+  while (offset + 4 <= data.length) {
+    const type = data.readUInt16LE(offset);
+    const length = data.readUInt16LE(offset + 2);
+    if (type === 0) {
+      break;
+    }
+    if (type === extensionType) {
+      return data.subarray(offset + 4, offset + 4 + length);
+    }
+    offset += 4 + length;
+  }
+  return null;
+}
+
+/**
+ * 读取铸币的持有人计数（HolderCount 扩展，类型 3），未开启时返回 null
+ */
+export async function getHolderCount(
+  connection: Connection,
+  mint: PublicKey
+): Promise<bigint | null> {
+  const accountInfo = await connection.getAccountInfo(mint);
+  if (!accountInfo) {
+    throw new Error(`铸币账户不存在: ${mint.toString()}`);
+  }
+  const value = findExtensionData(accountInfo.data, 77, 3);
+  return value ? value.readBigUInt64LE(0) : null;
+}
+
+/**
+ * 创建开启活动时间记录指令（所有者签名，代币账户需按 TOKEN_ACCOUNT_WITH_ACTIVITY_SIZE 分配，
+ * 建议与 InitializeAccount 放在同一交易中，使 created_at 即创建 slot）
+ */
+export function createEnableActivityTrackingInstruction(
+  tokenAccount: PublicKey,
+  owner: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.EnableActivityTracking };
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取代币账户的活动时间（AccountActivity 扩展，类型 4），未开启时返回 null
+ */
+export async function getAccountActivity(
+  connection: Connection,
+  tokenAccount: PublicKey
+): Promise<{ createdAt: bigint; lastActivitySlot: bigint } | null> {
+  const accountInfo = await connection.getAccountInfo(tokenAccount);
+  if (!accountInfo) {
+    throw new Error(`代币账户不存在: ${tokenAccount.toString()}`);
+  }
+  const value = findExtensionData(accountInfo.data, 106, 4);
+  return value ? { createdAt: value.readBigUInt64LE(0), lastActivitySlot: value.readBigUInt64LE(8) } : null;
+}
+
+/**
+ * 创建开启转账备注记录指令（所有者签名，代币账户需按 TOKEN_ACCOUNT_WITH_MEMO_LOG_SIZE 分配）
+ */
+export function createEnableMemoLogInstruction(
+  tokenAccount: PublicKey,
+  owner: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.EnableMemoLog };
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建带备注的转账指令（账户与 createTransferInstruction 相同）
+ */
+export function createTransferWithMemoInstruction(
+  sourceTokenAccount: PublicKey,
+  destinationTokenAccount: PublicKey,
+  owner: PublicKey,
+  amount: bigint,
+  memo: string | Uint8Array,
+  mint: PublicKey,
+  programId: PublicKey,
+  voteDelegates?: { mint: PublicKey; source: PublicKey | null; destination: PublicKey | null },
+  nftMint: PublicKey | null = null,
+  velocityLimited = false,
+  references: PublicKey[] = []
+): TransactionInstruction {
+  const instruction = createTransferInstruction(
+    sourceTokenAccount, destinationTokenAccount, owner, amount, mint, programId,
+    voteDelegates, nftMint, velocityLimited, references
+  );
+  const memoBytes = typeof memo === 'string' ? Buffer.from(memo, 'utf8') : memo;
+  instruction.data = serializeInstructionData(new TransferWithMemoData({ amount, memo: memoBytes }));
+  return instruction;
+}
+
+/**
+ * 创建归集指令：把 sources 的全部余额转入 destination（authority 签名）
+ * delegatedSources 为 authority 以受托人身份归集的源账户（附带额度 PDA），
+ * velocityLimitedSources 为开启速度限制的源账户（只需列出余额不为 0 的）；投票检查点未包含
+ */
+export function createSweepAllInstruction(
+  destination: PublicKey,
+  authority: PublicKey,
+  sources: PublicKey[],
+  mint: PublicKey,
+  programId: PublicKey,
+  delegatedSources: PublicKey[] = [],
+  velocityLimitedSources: PublicKey[] = []
+): TransactionInstruction {
+  const data = new SweepAllData({ source_count: sources.length });
+  const isListed = (list: PublicKey[], source: PublicKey) => list.some(key => key.equals(source));
+
+  const keys = [
+    { pubkey: destination, isSigner: false, isWritable: true },
+    { pubkey: authority, isSigner: true, isWritable: false },
+    ...sources.map(source => ({ pubkey: source, isSigner: false, isWritable: true })),
+  ];
+  sources.filter(source => isListed(delegatedSources, source)).forEach(source => {
+    keys.push({ pubkey: findAllowanceAddress(source, authority, programId)[0], isSigner: false, isWritable: true });
+  });
+  keys.push({ pubkey: mint, isSigner: false, isWritable: true });
+  sources.filter(source => isListed(velocityLimitedSources, source)).forEach(source => {
+    keys.push({ pubkey: findVelocityTrackerAddress(source, programId)[0], isSigner: false, isWritable: true });
+  });
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建批量结算指令：entries 中每个铸币的 delta 之和必须为 0，
+ * owners 为所有被扣款账户的所有者（需签名，每个所有者列一次），mints 为 delta 不为 0 的账户所属的铸币（每个铸币列一次）；
+ * 积分铸币的账户不能批量结算
+ */
+export function createSettleBatchInstruction(
+  entries: { account: PublicKey; delta: bigint }[],
+  owners: PublicKey[],
+  mints: PublicKey[],
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new SettleBatchData({ deltas: entries.map(entry => entry.delta) });
+
+  const keys = [
+    ...entries.map(entry => ({ pubkey: entry.account, isSigner: false, isWritable: true })),
+    ...owners.map(owner => ({ pubkey: owner, isSigner: true, isWritable: false })),
+    ...mints.map(mint => ({ pubkey: mint, isSigner: false, isWritable: false })),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建批量设置额度指令：approvals 中的代币账户都必须归 owner 所有，
+ * 额度直接覆盖（不改变 permit nonce），额度 PDA 不存在时由 owner 支付租金创建
+ */
+export function createApproveMultiInstruction(
+  owner: PublicKey,
+  approvals: { tokenAccount: PublicKey; delegate: PublicKey; amount: bigint }[],
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new ApproveMultiData({
+    approvals: approvals.map(approval => ({ delegate: approval.delegate.toBuffer(), amount: approval.amount })),
+  });
+
+  const keys = [
+    { pubkey: owner, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ...approvals.flatMap(approval => [
+      { pubkey: approval.tokenAccount, isSigner: false, isWritable: false },
+      {
+        pubkey: findAllowanceAddress(approval.tokenAccount, approval.delegate, programId)[0],
+        isSigner: false,
+        isWritable: true,
+      },
+    ]),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 压缩树账户头部大小，与 Rust 中的 CompressedTree::LEN 一致（ROOT_HISTORY = 16，最大深度 20）
+ */
+export const COMPRESSED_TREE_HEADER_SIZE = 1 + 32 + 32 + 1 + 8 + 1 + 32 * 16 + 32 * 20;
+
+/**
+ * 深度为 depth 的压缩树账户大小（头部 + 已花费位图），与 CompressedTree::account_len 一致
+ */
+export function compressedTreeAccountSize(depth: number): number {
+  return COMPRESSED_TREE_HEADER_SIZE + Math.ceil(2 ** depth / 8);
+}
+
+/**
+ * 推导压缩树的 authority PDA（托管代币账户的所有者）
+ */
+export function findCompressedTreeAuthority(tree: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('compressed_tree'), tree.toBuffer()], programId);
+}
+
+/**
+ * 压缩余额叶子哈希 sha256(0x00 || owner || amount || index)
+ */
+export function compressedLeafHash(leaf: CompressedLeaf): Buffer {
+  const data = Buffer.alloc(1 + 32 + 8 + 8);
+  data.set(leaf.owner, 1);
+  data.writeBigUInt64LE(leaf.amount, 33);
+  data.writeBigUInt64LE(leaf.index, 41);
+  return createHash('sha256').update(data).digest();
+}
+
+/**
+ * 由全部叶子哈希（按位置排列，来自 CompressionEvent 日志）计算根与第 index 个叶子的证明（自底向上）
+ */
+export function compressedProof(leafHashes: Buffer[], index: number, depth: number): { root: Buffer; proof: Buffer[] } {
+  const hashNodes = (left: Buffer, right: Buffer) =>
+    createHash('sha256').update(Buffer.from([1])).update(left).update(right).digest();
+  let zero = Buffer.alloc(32);
+  let level = leafHashes;
+  let position = index;
+  const proof: Buffer[] = [];
+  for (let i = 0; i < depth; i++) {
+    proof.push(level[position ^ 1] ?? zero);
+    const next: Buffer[] = [];
+    for (let j = 0; j < level.length; j += 2) {
+      next.push(hashNodes(level[j], level[j + 1] ?? zero));
+    }
+    level = next;
+    zero = hashNodes(zero, zero);
+    position >>= 1;
+  }
+  return { root: level[0] ?? zero, proof };
+}
+
+/**
+ * 创建初始化压缩树指令（树账户需先以 compressedTreeAccountSize(depth) 创建并归本程序所有，
+ * 托管账户需以 findCompressedTreeAuthority(tree) 为所有者初始化）
+ */
+export function createInitializeCompressedTreeInstruction(
+  tree: PublicKey,
+  mint: PublicKey,
+  escrow: PublicKey,
+  depth: number,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new InitializeCompressedTreeData({ depth });
+
+  const keys = [
+    { pubkey: tree, isSigner: false, isWritable: true },
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: escrow, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建压缩指令：从代币账户转入托管账户，并为 recipient 追加压缩余额叶子（所有者签名）
+ */
+export function createCompressInstruction(
+  sourceTokenAccount: PublicKey,
+  owner: PublicKey,
+  tree: PublicKey,
+  escrow: PublicKey,
+  recipient: PublicKey,
+  amount: bigint,
+  mint: PublicKey,
+  programId: PublicKey,
+  velocityLimited = false
+): TransactionInstruction {
+  const data = new CompressData({ recipient: recipient.toBuffer(), amount });
+
+  const keys = [
+    { pubkey: sourceTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: false },
+    { pubkey: tree, isSigner: false, isWritable: true },
+    { pubkey: escrow, isSigner: false, isWritable: true },
+  ];
+  keys.push({ pubkey: mint, isSigner: false, isWritable: true });
+  if (velocityLimited) {
+    keys.push({ pubkey: findVelocityTrackerAddress(sourceTokenAccount, programId)[0], isSigner: false, isWritable: true });
+  }
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建压缩余额转账指令（叶子所有者签名，root 与 proof 由 compressedProof 计算）
+ */
+export function createCompressedTransferInstruction(
+  tree: PublicKey,
+  leaf: CompressedLeaf,
+  root: Uint8Array,
+  proof: Uint8Array[],
+  recipient: PublicKey,
+  amount: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new CompressedTransferData({ leaf, root, proof, recipient: recipient.toBuffer(), amount });
+
+  const keys = [
+    { pubkey: tree, isSigner: false, isWritable: true },
+    { pubkey: new PublicKey(leaf.owner), isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建解压指令：叶子的全部余额从托管账户转入 destination（叶子所有者签名）
+ */
+export function createDecompressInstruction(
+  tree: PublicKey,
+  escrow: PublicKey,
+  destination: PublicKey,
+  leaf: CompressedLeaf,
+  root: Uint8Array,
+  proof: Uint8Array[],
+  mint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new DecompressData({ leaf, root, proof });
+
+  const keys = [
+    { pubkey: tree, isSigner: false, isWritable: true },
+    { pubkey: escrow, isSigner: false, isWritable: true },
+    { pubkey: destination, isSigner: false, isWritable: true },
+    { pubkey: new PublicKey(leaf.owner), isSigner: true, isWritable: false },
+  ];
+  keys.push({ pubkey: mint, isSigner: false, isWritable: true });
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * Wormhole 中 Solana 的链 ID，与 Rust 中的 bridge::SOLANA_CHAIN_ID 一致
+ */
+export const SOLANA_CHAIN_ID = 1;
+
+/**
+ * 推导铸币的跨链桥配置 PDA
+ */
+export function findBridgeAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('bridge'), mint.toBuffer()], programId);
+}
+
+/**
+ * 推导入桥消息的领取 PDA（存在即表示该消息已铸造）
+ */
+export function findBridgeClaimAddress(
+  mint: PublicKey,
+  sourceChain: number,
+  emitter: Uint8Array,
+  sequence: bigint,
+  programId: PublicKey
+): [PublicKey, number] {
+  const chain = Buffer.alloc(2);
+  chain.writeUInt16LE(sourceChain);
+  const seq = Buffer.alloc(8);
+  seq.writeBigUInt64LE(sequence);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('bridge_claim'), mint.toBuffer(), chain, Buffer.from(emitter), seq],
+    programId
+  );
+}
+
+/**
+ * 把目标链地址左侧补零到 32 字节（如 20 字节的 EVM 地址）
+ */
+export function toBridgeAddress(address: Uint8Array): Buffer {
+  if (address.length > 32) {
+    throw new Error(`跨链地址超过 32 字节: ${address.length}`);
+  }
+  const padded = Buffer.alloc(32);
+  padded.set(address, 32 - address.length);
+  return padded;
+}
+
+/**
+ * 创建设置跨链桥验证者指令（铸币权限签名并支付桥配置 PDA 的租金）
+ */
+export function createConfigureBridgeInstruction(
+  mint: PublicKey,
+  mintAuthority: PublicKey,
+  verifier: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new ConfigureBridgeData({ verifier: verifier.toBuffer() });
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: findBridgeAddress(mint, programId)[0], isSigner: false, isWritable: true },
+    { pubkey: mintAuthority, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建销毁出桥指令：金额按 8 位小数截断后销毁，程序日志中输出 Token Bridge 转账载荷
+ * targetAddress 可直接传目标链的原始地址，不足 32 字节时左侧补零
+ */
+export function createBridgeBurnInstruction(
+  tokenAccount: PublicKey,
+  mint: PublicKey,
+  owner: PublicKey,
+  targetChain: number,
+  targetAddress: Uint8Array,
+  amount: bigint,
+  programId: PublicKey,
+  voteDelegate: PublicKey | null = null
+): TransactionInstruction {
+  const data = new BridgeBurnData({
+    target_chain: targetChain,
+    target_address: toBridgeAddress(targetAddress),
+    amount,
+  });
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: true },
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: false },
+    { pubkey: findBridgeAddress(mint, programId)[0], isSigner: false, isWritable: true },
+    ...voteCheckpointAccounts(mint, voteDelegate, null, programId),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建入桥铸造指令（验证者签名并支付领取 PDA 的租金，同一条来源消息只能铸造一次）
+ */
+export function createBridgeMintInstruction(
+  mint: PublicKey,
+  destination: PublicKey,
+  verifier: PublicKey,
+  sourceChain: number,
+  emitter: Uint8Array,
+  sequence: bigint,
+  amount: bigint,
+  programId: PublicKey,
+  voteDelegate: PublicKey | null = null
+): TransactionInstruction {
+  const data = new BridgeMintData({ source_chain: sourceChain, emitter, sequence, amount });
+
+  const keys = [
+    { pubkey: findBridgeAddress(mint, programId)[0], isSigner: false, isWritable: false },
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: destination, isSigner: false, isWritable: true },
+    {
+      pubkey: findBridgeClaimAddress(mint, sourceChain, emitter, sequence, programId)[0],
+      isSigner: false,
+      isWritable: true,
+    },
+    { pubkey: verifier, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ...voteCheckpointAccounts(mint, null, voteDelegate, programId),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取代币账户的转账备注记录（MemoLog 扩展，类型 5），按从旧到新排列，未开启时返回 null；
+ * 用 hashPreimage(memo) 与记录中的 hash 比对即可定位某条备注对应的转账
+ */
+export async function getMemoLog(
+  connection: Connection,
+  tokenAccount: PublicKey
+): Promise<{ hash: Buffer; slot: bigint }[] | null> {
+  const accountInfo = await connection.getAccountInfo(tokenAccount);
+  if (!accountInfo) {
+    throw new Error(`代币账户不存在: ${tokenAccount.toString()}`);
+  }
+  const value = findExtensionData(accountInfo.data, 106, 5);
+  if (!value) {
+    return null;
+  }
+  const next = value.readUInt8(0);
+  const entries: { hash: Buffer; slot: bigint }[] = [];
+  for (let i = 0; i < MEMO_LOG_CAPACITY; i++) {
+    const offset = 1 + 40 * ((next + i) % MEMO_LOG_CAPACITY);
+    const slot = value.readBigUInt64LE(offset + 32);
+    if (slot !== 0n) {
+      entries.push({ hash: Buffer.from(value.subarray(offset, offset + 32)), slot });
+    }
+  }
+  return entries;
+}
+
+/**
+ * 创建关闭空铸币指令（任何人可发送，租金退回预设的回收地址）
+ */
+export function createCloseEmptyMintInstruction(
+  mint: PublicKey,
+  closeDestination: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.CloseEmptyMint };
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: closeDestination, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 推导托管代币账户对应的 HTLC PDA
+ */
+export function findHtlcAddress(escrow: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('htlc'), escrow.toBuffer()], programId);
+}
+
+/**
+ * 计算 HTLC 哈希锁 sha256(preimage)
+ */
+export function hashPreimage(preimage: Uint8Array): Buffer {
+  return createHash('sha256').update(preimage).digest();
+}
+
+/**
+ * 创建 HTLC 指令（托管账户需事先以 HTLC PDA 为所有者初始化，timeout 为 unix 时间戳，秒）
+ */
+export function createCreateHtlcInstruction(
+  escrow: PublicKey,
+  senderTokenAccount: PublicKey,
+  recipientTokenAccount: PublicKey,
+  sender: PublicKey,
+  hash: Uint8Array,
+  amount: bigint,
+  timeout: bigint,
+  mint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new CreateHtlcData({ hash, amount, timeout });
+  const [htlc] = findHtlcAddress(escrow, programId);
+
+  const keys = [
+    { pubkey: htlc, isSigner: false, isWritable: true },
+    { pubkey: escrow, isSigner: false, isWritable: true },
+    { pubkey: senderTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: recipientTokenAccount, isSigner: false, isWritable: false },
+    { pubkey: sender, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: mint, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建 HTLC 赎回指令（任何人可发送，须在 timeout 之前）
+ */
+export function createRedeemHtlcInstruction(
+  escrow: PublicKey,
+  recipientTokenAccount: PublicKey,
+  sender: PublicKey,
+  preimage: Uint8Array,
+  mint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new RedeemHtlcData({ preimage });
+  const [htlc] = findHtlcAddress(escrow, programId);
+
+  const keys = [
+    { pubkey: htlc, isSigner: false, isWritable: true },
+    { pubkey: escrow, isSigner: false, isWritable: true },
+    { pubkey: recipientTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: sender, isSigner: false, isWritable: true },
+    { pubkey: mint, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建 HTLC 过期退款指令（任何人可发送，timeout 之后）
+ */
+export function createRefundHtlcInstruction(
+  escrow: PublicKey,
+  senderTokenAccount: PublicKey,
+  sender: PublicKey,
+  mint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.RefundHtlc };
+  const [htlc] = findHtlcAddress(escrow, programId);
+
+  const keys = [
+    { pubkey: htlc, isSigner: false, isWritable: true },
+    { pubkey: escrow, isSigner: false, isWritable: true },
+    { pubkey: senderTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: sender, isSigner: false, isWritable: true },
+    { pubkey: mint, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取 HTLC 状态
+ */
+export async function getHtlcData(
+  connection: Connection,
+  escrow: PublicKey,
+  programId: PublicKey
+): Promise<Htlc> {
+  const [htlc] = findHtlcAddress(escrow, programId);
+  const accountInfo = await connection.getAccountInfo(htlc);
+  if (!accountInfo) {
+    throw new Error(`HTLC 不存在: ${htlc.toString()}`);
+  }
+
+  return Htlc.deserialize(accountInfo.data);
+}
+
+/**
+ * 推导托管代币账户对应的条件转账 PDA
+ */
+export function findConditionalTransferAddress(escrow: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('conditional'), escrow.toBuffer()], programId);
+}
+
+/**
+ * 创建带仲裁人的条件转账指令（托管账户需事先以条件转账 PDA 为所有者初始化）
+ */
+export function createCreateConditionalTransferInstruction(
+  escrow: PublicKey,
+  senderTokenAccount: PublicKey,
+  recipientTokenAccount: PublicKey,
+  sender: PublicKey,
+  arbiter: PublicKey,
+  amount: bigint,
+  mint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new CreateConditionalTransferData({ arbiter: arbiter.toBuffer(), amount });
+  const [conditional] = findConditionalTransferAddress(escrow, programId);
+
+  const keys = [
+    { pubkey: conditional, isSigner: false, isWritable: true },
+    { pubkey: escrow, isSigner: false, isWritable: true },
+    { pubkey: senderTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: recipientTokenAccount, isSigner: false, isWritable: false },
+    { pubkey: sender, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: mint, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建仲裁人放款（release 为 true，转给接收方）或退款（退回发送方代币账户）指令，destination 为对应的代币账户
+ */
+export function createSettleConditionalTransferInstruction(
+  escrow: PublicKey,
+  destinationTokenAccount: PublicKey,
+  sender: PublicKey,
+  arbiter: PublicKey,
+  release: boolean,
+  mint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = {
+    instruction: release ? TokenInstruction.ReleaseConditionalTransfer : TokenInstruction.RefundConditionalTransfer,
+  };
+  const [conditional] = findConditionalTransferAddress(escrow, programId);
+
+  const keys = [
+    { pubkey: conditional, isSigner: false, isWritable: true },
+    { pubkey: escrow, isSigner: false, isWritable: true },
+    { pubkey: destinationTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: sender, isSigner: false, isWritable: true },
+    { pubkey: arbiter, isSigner: true, isWritable: false },
+    { pubkey: mint, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取条件转账状态
+ */
+export async function getConditionalTransferData(
+  connection: Connection,
+  escrow: PublicKey,
+  programId: PublicKey
+): Promise<ConditionalTransfer> {
+  const [conditional] = findConditionalTransferAddress(escrow, programId);
+  const accountInfo = await connection.getAccountInfo(conditional);
+  if (!accountInfo) {
+    throw new Error(`条件转账不存在: ${conditional.toString()}`);
+  }
+
+  return ConditionalTransfer.deserialize(accountInfo.data);
+}
+
+/**
+ * 推导代币账户对受托人的额度 PDA
+ */
+export function findAllowanceAddress(
+  tokenAccount: PublicKey,
+  delegate: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('allowance'), tokenAccount.toBuffer(), delegate.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * permit 消息前缀，与 Rust 中的 PERMIT_DOMAIN 一致
+ */
+export const PERMIT_DOMAIN = Buffer.from('spl-token-program:permit');
+
+/**
+ * 所有者需要链下签名的 PermitMessage::Approve 消息
+ */
+export function permitApproveMessage(
+  tokenAccount: PublicKey,
+  delegate: PublicKey,
+  amount: bigint,
+  nonce: bigint,
+  deadline: bigint,
+  programId: PublicKey
+): Buffer {
+  const body = Buffer.alloc(1 + 32 + 32 + 32 + 8 + 8 + 8);
+  body.writeUInt8(0, 0); // PermitMessage::Approve
+  body.set(programId.toBuffer(), 1);
+  body.set(tokenAccount.toBuffer(), 33);
+  body.set(delegate.toBuffer(), 65);
+  body.writeBigUInt64LE(amount, 97);
+  body.writeBigUInt64LE(nonce, 105);
+  body.writeBigInt64LE(deadline, 113);
+  return Buffer.concat([PERMIT_DOMAIN, body]);
+}
+
+/**
+ * 创建 permit 授权指令对：[ed25519 签名验证, PermitApprove]，两条指令必须相邻且按此顺序放入交易
+ * signature 为所有者对 permitApproveMessage(...) 的 ed25519 签名
+ */
+export function createPermitApproveInstructions(
+  tokenAccount: PublicKey,
+  owner: PublicKey,
+  delegate: PublicKey,
+  amount: bigint,
+  nonce: bigint,
+  deadline: bigint,
+  signature: Uint8Array,
+  payer: PublicKey,
+  programId: PublicKey
+): TransactionInstruction[] {
+  const data = new PermitApproveData({ delegate: delegate.toBuffer(), amount, nonce, deadline });
+  const [allowance] = findAllowanceAddress(tokenAccount, delegate, programId);
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: false },
+    { pubkey: allowance, isSigner: false, isWritable: true },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return [
+    Ed25519Program.createInstructionWithPublicKey({
+      publicKey: owner.toBytes(),
+      message: permitApproveMessage(tokenAccount, delegate, amount, nonce, deadline, programId),
+      signature,
+    }),
+    new TransactionInstruction({
+      keys,
+      programId,
+      data: serializeInstructionData(data),
+    }),
+  ];
+}
+
+/**
+ * 创建受托人额度内转账指令
+ */
+export function createTransferFromInstruction(
+  sourceTokenAccount: PublicKey,
+  destinationTokenAccount: PublicKey,
+  delegate: PublicKey,
+  amount: bigint,
+  mint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new TransferFromData({ amount });
+  const [allowance] = findAllowanceAddress(sourceTokenAccount, delegate, programId);
+
+  const keys = [
+    { pubkey: sourceTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: destinationTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: allowance, isSigner: false, isWritable: true },
+    { pubkey: delegate, isSigner: true, isWritable: false },
+  ];
+  keys.push({ pubkey: mint, isSigner: false, isWritable: true });
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建受托人代为销毁指令（额度 PDA 中的剩余额度随之扣减）
+ */
+export function createBurnFromDelegateInstruction(
+  tokenAccount: PublicKey,
+  mint: PublicKey,
+  delegate: PublicKey,
+  amount: bigint,
+  programId: PublicKey,
+  voteDelegate: PublicKey | null = null
+): TransactionInstruction {
+  const data = new BurnData({ amount });
+  const [allowance] = findAllowanceAddress(tokenAccount, delegate, programId);
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: true },
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: delegate, isSigner: true, isWritable: false },
+    { pubkey: allowance, isSigner: false, isWritable: true },
+    ...voteCheckpointAccounts(mint, voteDelegate, null, programId),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 推导代币账户的转账 permit nonce PDA
+ */
+export function findPermitNonceAddress(tokenAccount: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('permit_nonce'), tokenAccount.toBuffer()], programId);
+}
+
+/**
+ * 所有者需要链下签名的 PermitMessage::Transfer 消息
+ */
+export function permitTransferMessage(
+  source: PublicKey,
+  destination: PublicKey,
+  amount: bigint,
+  tip: bigint,
+  nonce: bigint,
+  deadline: bigint,
+  programId: PublicKey
+): Buffer {
+  const body = Buffer.alloc(1 + 32 + 32 + 32 + 8 + 8 + 8 + 8);
+  body.writeUInt8(1, 0); // PermitMessage::Transfer
+  body.set(programId.toBuffer(), 1);
+  body.set(source.toBuffer(), 33);
+  body.set(destination.toBuffer(), 65);
+  body.writeBigUInt64LE(amount, 97);
+  body.writeBigUInt64LE(tip, 105);
+  body.writeBigUInt64LE(nonce, 113);
+  body.writeBigInt64LE(deadline, 121);
+  return Buffer.concat([PERMIT_DOMAIN, body]);
+}
+
+/**
+ * 创建中继者代付转账指令对：[ed25519 签名验证, TransferWithPermit]
+ * signature 为所有者对 permitTransferMessage(...) 的 ed25519 签名，relayer 签名并支付手续费
+ */
+export function createTransferWithPermitInstructions(
+  sourceTokenAccount: PublicKey,
+  destinationTokenAccount: PublicKey,
+  owner: PublicKey,
+  amount: bigint,
+  tip: bigint,
+  nonce: bigint,
+  deadline: bigint,
+  signature: Uint8Array,
+  relayer: PublicKey,
+  relayerTokenAccount: PublicKey,
+  mint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction[] {
+  const data = new TransferWithPermitData({ amount, tip, nonce, deadline });
+  const [permitNonce] = findPermitNonceAddress(sourceTokenAccount, programId);
+
+  const keys = [
+    { pubkey: sourceTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: destinationTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: relayerTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: permitNonce, isSigner: false, isWritable: true },
+    { pubkey: relayer, isSigner: true, isWritable: true },
+    { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+  keys.push({ pubkey: mint, isSigner: false, isWritable: true });
+
+  return [
+    Ed25519Program.createInstructionWithPublicKey({
+      publicKey: owner.toBytes(),
+      message: permitTransferMessage(sourceTokenAccount, destinationTokenAccount, amount, tip, nonce, deadline, programId),
+      signature,
+    }),
+    new TransactionInstruction({
+      keys,
+      programId,
+      data: serializeInstructionData(data),
+    }),
+  ];
+}
+
+/**
+ * 推导代币账户的会话 PDA
+ */
+export function findSessionAddress(
+  tokenAccount: PublicKey,
+  sessionKey: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('session'), tokenAccount.toBuffer(), sessionKey.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * 创建会话密钥授权指令（所有者签名）
+ */
+export function createCreateSessionInstruction(
+  tokenAccount: PublicKey,
+  owner: PublicKey,
+  sessionKey: PublicKey,
+  maxAmount: bigint,
+  expiresAtSlot: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new CreateSessionData({
+    key: sessionKey.toBuffer(),
+    max_amount: maxAmount,
+    expires_at_slot: expiresAtSlot,
+  });
+  const [session] = findSessionAddress(tokenAccount, sessionKey, programId);
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: false },
+    { pubkey: session, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建撤销会话指令（所有者签名）
+ */
+export function createRevokeSessionInstruction(
+  tokenAccount: PublicKey,
+  owner: PublicKey,
+  sessionKey: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.RevokeSession };
+  const [session] = findSessionAddress(tokenAccount, sessionKey, programId);
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: false },
+    { pubkey: session, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建会话密钥转账指令（会话密钥签名）
+ */
+export function createSessionTransferInstruction(
+  sourceTokenAccount: PublicKey,
+  destinationTokenAccount: PublicKey,
+  sessionKey: PublicKey,
+  amount: bigint,
+  mint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new SessionTransferData({ amount });
+  const [session] = findSessionAddress(sourceTokenAccount, sessionKey, programId);
+
+  const keys = [
+    { pubkey: sourceTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: destinationTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: session, isSigner: false, isWritable: true },
+    { pubkey: sessionKey, isSigner: true, isWritable: false },
+  ];
+  keys.push({ pubkey: mint, isSigner: false, isWritable: true });
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取会话密钥授权
+ */
+export async function getSessionData(
+  connection: Connection,
+  tokenAccount: PublicKey,
+  sessionKey: PublicKey,
+  programId: PublicKey
+): Promise<Session> {
+  const [session] = findSessionAddress(tokenAccount, sessionKey, programId);
+  const accountInfo = await connection.getAccountInfo(session);
+  if (!accountInfo) {
+    throw new Error(`会话不存在: ${session.toString()}`);
+  }
+
+  return Session.deserialize(accountInfo.data);
+}
+
+/**
+ * 推导代币账户的所有者恢复 PDA
+ */
+export function findOwnerRecoveryAddress(tokenAccount: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('owner_recovery'), tokenAccount.toBuffer()], programId);
+}
+
+/**
+ * 创建设置社交恢复监护人指令（所有者签名，代币账户需按 TOKEN_ACCOUNT_WITH_GUARDIANS_SIZE 分配）
+ */
+export function createSetGuardiansInstruction(
+  tokenAccount: PublicKey,
+  owner: PublicKey,
+  guardians: PublicKey[],
+  threshold: number,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new SetGuardiansData({ guardians: guardians.map(guardian => guardian.toBuffer()), threshold });
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建监护人提议恢复所有者指令（guardians 中至少 threshold 个需签名）
+ */
+export function createProposeOwnerRecoveryInstruction(
+  tokenAccount: PublicKey,
+  newOwner: PublicKey,
+  payer: PublicKey,
+  guardians: PublicKey[],
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new ProposeOwnerRecoveryData({ new_owner: newOwner.toBuffer() });
+  const [recovery] = findOwnerRecoveryAddress(tokenAccount, programId);
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: false },
+    { pubkey: recovery, isSigner: false, isWritable: true },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ...guardians.map(guardian => ({ pubkey: guardian, isSigner: true, isWritable: false })),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建取消所有者恢复指令（原所有者签名）
+ */
+export function createCancelOwnerRecoveryInstruction(
+  tokenAccount: PublicKey,
+  owner: PublicKey,
+  payer: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.CancelOwnerRecovery };
+  const [recovery] = findOwnerRecoveryAddress(tokenAccount, programId);
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: false },
+    { pubkey: recovery, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: false },
+    { pubkey: payer, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建执行所有者恢复指令（时间锁到期后任何人可发送）
+ */
+export function createRecoverOwnerInstruction(
+  tokenAccount: PublicKey,
+  payer: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.RecoverOwner };
+  const [recovery] = findOwnerRecoveryAddress(tokenAccount, programId);
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: true },
+    { pubkey: recovery, isSigner: false, isWritable: true },
+    { pubkey: payer, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取待执行的所有者恢复
+ */
+export async function getOwnerRecoveryData(
+  connection: Connection,
+  tokenAccount: PublicKey,
+  programId: PublicKey
+): Promise<OwnerRecovery> {
+  const [recovery] = findOwnerRecoveryAddress(tokenAccount, programId);
+  const accountInfo = await connection.getAccountInfo(recovery);
+  if (!accountInfo) {
+    throw new Error(`所有者恢复提议不存在: ${recovery.toString()}`);
+  }
+
+  return OwnerRecovery.deserialize(accountInfo.data);
+}
+
+/**
+ * 读取受托人额度
+ */
+export async function getAllowanceData(
+  connection: Connection,
+  tokenAccount: PublicKey,
+  delegate: PublicKey,
+  programId: PublicKey
+): Promise<Allowance> {
+  const [allowance] = findAllowanceAddress(tokenAccount, delegate, programId);
+  const accountInfo = await connection.getAccountInfo(allowance);
+  if (!accountInfo) {
+    throw new Error(`额度不存在: ${allowance.toString()}`);
+  }
+
+  return Allowance.deserialize(accountInfo.data);
+}
+
+/**
+ * 读取紧急管理员配置
+ */
+export async function getEmergencyConfigData(
+  connection: Connection,
+  programId: PublicKey
+): Promise<EmergencyConfig> {
+  const [config] = findEmergencyConfigAddress(programId);
+  const accountInfo = await connection.getAccountInfo(config);
+  if (!accountInfo) {
+    throw new Error(`紧急配置不存在: ${config.toString()}`);
+  }
+
+  return EmergencyConfig.deserialize(accountInfo.data);
+}
+
+/**
+ * 读取权限恢复提议
+ */
+export async function getAuthorityRecoveryData(
+  connection: Connection,
+  mint: PublicKey,
+  authorityType: AuthorityType,
+  programId: PublicKey
+): Promise<AuthorityRecovery> {
+  const [recovery] = findAuthorityRecoveryAddress(mint, authorityType, programId);
+  const accountInfo = await connection.getAccountInfo(recovery);
+  if (!accountInfo) {
+    throw new Error(`权限恢复提议不存在: ${recovery.toString()}`);
+  }
+
+  return AuthorityRecovery.deserialize(accountInfo.data);
+}
+
+/**
+ * 读取并解析铸币账户数据
+ */
+export async function getMintData(
+  connection: Connection,
+  mint: PublicKey
+): Promise<Mint> {
+  const accountInfo = await connection.getAccountInfo(mint);
+  if (!accountInfo) {
+    throw new Error(`铸币账户不存在: ${mint.toString()}`);
+  }
+  
+  return Mint.deserialize(accountInfo.data);
+}
+
+/**
+ * 检查代币账户的所有者是否为 ownerProgramId 下由 seeds（不含 bump）推导出的 PDA
+ */
+export function isOwnedByPda(
+  tokenAccount: TokenAccount,
+  seeds: Buffer[],
+  ownerProgramId: PublicKey
+): boolean {
+  const [pda] = PublicKey.findProgramAddressSync(seeds, ownerProgramId);
+  return tokenAccount.owner.equals(pda);
+}
+
+/**
+ * 读取并解析代币账户数据
+ */
+export async function getTokenAccountData(
+  connection: Connection,
+  tokenAccount: PublicKey
+): Promise<TokenAccount> {
+  const accountInfo = await connection.getAccountInfo(tokenAccount);
+  if (!accountInfo) {
+    throw new Error(`代币账户不存在: ${tokenAccount.toString()}`);
+  }
+  
+  return TokenAccount.deserialize(accountInfo.data);
+}
+
+/**
+ * 创建调整铸币精度指令（铸币权限签名，只能调大）：供应量立即换算，代币账户余额在下一次 MintTo / Burn 时换算，
+ * 铸币账户需按 MINT_WITH_DECIMALS_RESCALE_SIZE 分配，代币账户需按 TOKEN_ACCOUNT_WITH_RESCALE_EPOCH_SIZE 分配
+ */
+export function createRescaleMintInstruction(
+  mint: PublicKey,
+  mintAuthority: PublicKey,
+  newDecimals: number,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new RescaleMintData({ new_decimals: newDecimals });
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: mintAuthority, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建全局冻结 / 解除全局冻结指令（冻结权限签名），冻结期间该铸币的转账与销毁全部失败；
+ * 应在首次铸造前执行一次 GlobalThaw 写入扩展，铸币账户需按 MINT_WITH_GLOBAL_FREEZE_SIZE 分配
+ */
+export function createSetGlobalFreezeInstruction(
+  mint: PublicKey,
+  freezeAuthority: PublicKey,
+  frozen: boolean,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = {
+    instruction: frozen ? TokenInstruction.GlobalFreeze : TokenInstruction.GlobalThaw,
+  };
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: freezeAuthority, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建通用权限变更指令（当前权限签名，newAuthority 为 null 时撤销）；
+ * BurnTokens 尚未设置时由铸币权限签名，铸币账户需按 MINT_WITH_BURN_AUTHORITY_SIZE 分配。
+ * 设置后销毁权限可用 createBurnInstruction（以销毁权限作为签名者）从该铸币的任意账户销毁
+ */
+export function createSetAuthorityInstruction(
+  mint: PublicKey,
+  currentAuthority: PublicKey,
+  authorityType: AuthorityType,
+  newAuthority: PublicKey | null,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new SetAuthorityData({
+    authority_type: authorityType,
+    new_authority: newAuthority ? newAuthority.toBuffer() : null,
+  });
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: currentAuthority, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 把已弃用的 SetMintAuthority 指令改写为等价的 SetAuthority（MintTokens），账户列表不变；
+ * 其他程序或其他类型的指令原样返回。旧负载的 None 可能补零到 34 字节，只看选项标签
+ */
+export function migrateSetMintAuthorityInstruction(
+  instruction: TransactionInstruction,
+  programId: PublicKey
+): TransactionInstruction {
+  if (!instruction.programId.equals(programId) || instruction.data[0] !== TokenInstruction.SetMintAuthority) {
+    return instruction;
+  }
+  let newAuthority: PublicKey | null;
+  if (instruction.data[1] === 0) {
+    newAuthority = null;
+  } else if (instruction.data[1] === 1 && instruction.data.length >= 34) {
+    newAuthority = new PublicKey(instruction.data.subarray(2, 34));
+  } else {
+    throw new Error('无效的 SetMintAuthority 指令数据');
+  }
+  const data = new SetAuthorityData({
+    authority_type: AuthorityType.MintTokens,
+    new_authority: newAuthority ? newAuthority.toBuffer() : null,
+  });
+  return new TransactionInstruction({
+    keys: instruction.keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建设置全局冻结豁免指令（冻结权限签名），豁免账户在 GlobalFreeze 期间仍可转出与销毁；
+ * 代币账户需按 TOKEN_ACCOUNT_WITH_FREEZE_EXEMPT_SIZE 分配
+ */
+export function createSetFreezeExemptInstruction(
+  tokenAccount: PublicKey,
+  mint: PublicKey,
+  freezeAuthority: PublicKey,
+  exempt: boolean,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new SetFreezeExemptData({ exempt });
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: true },
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: freezeAuthority, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建设置代币账户标签指令（所有者签名），label 按 UTF-8 编码后不超过 ACCOUNT_LABEL_LENGTH 字节，空字符串表示清除；
+ * 代币账户需按 TOKEN_ACCOUNT_WITH_LABEL_SIZE 分配
+ */
+export function createSetAccountLabelInstruction(
+  tokenAccount: PublicKey,
+  owner: PublicKey,
+  label: string,
+  programId: PublicKey
+): TransactionInstruction {
+  const encoded = Buffer.from(label, 'utf8');
+  if (encoded.length > ACCOUNT_LABEL_LENGTH) {
+    throw new Error(`标签超过 ${ACCOUNT_LABEL_LENGTH} 字节: ${label}`);
+  }
+  const padded = Buffer.alloc(ACCOUNT_LABEL_LENGTH);
+  padded.set(encoded);
+  const data = new SetAccountLabelData({ label: padded });
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 推导 (铸币权限, 种子) 对应的铸币地址
+ */
+export function findMintAddress(authority: PublicKey, seed: string, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('mint'), authority.toBuffer(), Buffer.from(seed, 'utf8')], programId);
+}
+
+/**
+ * 创建确定性地址铸币指令：铸币地址由 (authority, seed) 推导，authority 为铸币权限并支付租金，seed 不超过 32 字节
+ */
+export function createCreateMintWithSeedInstruction(
+  authority: PublicKey,
+  seed: string,
+  decimals: number,
+  freezeAuthority: PublicKey | null,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new CreateMintWithSeedData({
+    seed,
+    decimals,
+    freeze_authority: freezeAuthority ? freezeAuthority.toBuffer() : null,
+  });
+  const [mint] = findMintAddress(authority, seed, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: authority, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建查询扩展位图指令（结果通过 return data 返回 u64 LE，可用 extensionTypesFromBitmap 解码），
+ * isMint 指明 account 是铸币还是代币账户
+ */
+export function createGetExtensionTypesInstruction(
+  account: PublicKey,
+  isMint: boolean,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new GetExtensionTypesData({ is_mint: isMint });
+
+  return new TransactionInstruction({
+    keys: [{ pubkey: account, isSigner: false, isWritable: false }],
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 把 GetExtensionTypes 返回的位图解码为扩展类型列表（保留本客户端未知的类型编号）
+ */
+export function extensionTypesFromBitmap(bitmap: bigint): ExtensionType[] {
+  const types: ExtensionType[] = [];
+  for (let type = 1; type < 64; type++) {
+    if ((bitmap >> BigInt(type)) & 1n) {
+      types.push(type as ExtensionType);
+    }
+  }
+  return types;
+}
+
+/**
+ * Crank 的一项：会话按 (代币账户, 会话密钥) 定位，HTLC 按托管账户定位
+ */
+export type CrankTarget =
+  | { item: CrankItem.ExpiredSession; tokenAccount: PublicKey; sessionKey: PublicKey; owner: PublicKey }
+  | { item: CrankItem.ExpiredHtlc; escrow: PublicKey; senderTokenAccount: PublicKey; sender: PublicKey }
+  | { item: CrankItem.MaturedThaw; tokenAccount: PublicKey };
+
+/**
+ * 创建清理到期状态指令（任何人可调用），每关闭一个 PDA 由 cranker 获得 CRANK_REWARD_LAMPORTS；
+ * 发送方代币账户已委托投票的 HTLC 需由调用方在该项之后补上投票检查点 PDA，此处不处理
+ */
+export function createCrankInstruction(
+  cranker: PublicKey,
+  targets: CrankTarget[],
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new CrankData({ items: targets.map(target => target.item) });
+
+  const keys = [
+    { pubkey: cranker, isSigner: true, isWritable: true },
+    ...targets.flatMap(target => {
+      switch (target.item) {
+        case CrankItem.ExpiredSession:
+          return [
+            { pubkey: target.tokenAccount, isSigner: false, isWritable: false },
+            {
+              pubkey: findSessionAddress(target.tokenAccount, target.sessionKey, programId)[0],
+              isSigner: false,
+              isWritable: true,
+            },
+            { pubkey: target.owner, isSigner: false, isWritable: true },
+          ];
+        case CrankItem.ExpiredHtlc:
+          return [
+            { pubkey: findHtlcAddress(target.escrow, programId)[0], isSigner: false, isWritable: true },
+            { pubkey: target.escrow, isSigner: false, isWritable: true },
+            { pubkey: target.senderTokenAccount, isSigner: false, isWritable: true },
+            { pubkey: target.sender, isSigner: false, isWritable: true },
+          ];
+        case CrankItem.MaturedThaw:
+          return [{ pubkey: target.tokenAccount, isSigner: false, isWritable: true }];
+      }
+    }),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 推导代币账户对某个商户的预授权 PDA
+ */
+export function findPreAuthorizationAddress(
+  tokenAccount: PublicKey,
+  merchant: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('preauth'), tokenAccount.toBuffer(), merchant.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * 创建预授权扣款指令（所有者签名并支付租金）：merchant 每 cycleSlots 个 slot 最多扣走 amountPerCycle，
+ * 对同一 merchant 再次调用会覆盖额度并重新开始周期
+ */
+export function createCreatePreAuthorizationInstruction(
+  tokenAccount: PublicKey,
+  owner: PublicKey,
+  merchant: PublicKey,
+  amountPerCycle: bigint,
+  cycleSlots: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new CreatePreAuthorizationData({
+    merchant: merchant.toBuffer(),
+    amount_per_cycle: amountPerCycle,
+    cycle_slots: cycleSlots,
+  });
+  const [preAuthorization] = findPreAuthorizationAddress(tokenAccount, merchant, programId);
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: false },
+    { pubkey: preAuthorization, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建商户扣款指令（商户签名），金额不能超过当前周期的剩余额度
+ */
+export function createDebitInstruction(
+  sourceTokenAccount: PublicKey,
+  destinationTokenAccount: PublicKey,
+  merchant: PublicKey,
+  amount: bigint,
+  mint: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new DebitData({ amount });
+  const [preAuthorization] = findPreAuthorizationAddress(sourceTokenAccount, merchant, programId);
+
+  const keys = [
+    { pubkey: sourceTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: destinationTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: preAuthorization, isSigner: false, isWritable: true },
+    { pubkey: merchant, isSigner: true, isWritable: false },
+  ];
+  keys.push({ pubkey: mint, isSigner: false, isWritable: true });
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建撤销预授权指令（所有者签名，租金退回所有者）
+ */
+export function createClosePreAuthorizationInstruction(
+  tokenAccount: PublicKey,
+  owner: PublicKey,
+  merchant: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.ClosePreAuthorization };
+  const [preAuthorization] = findPreAuthorizationAddress(tokenAccount, merchant, programId);
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: false },
+    { pubkey: preAuthorization, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取预授权扣款状态
+ */
+export async function getPreAuthorizationData(
+  connection: Connection,
+  tokenAccount: PublicKey,
+  merchant: PublicKey,
+  programId: PublicKey
+): Promise<PreAuthorization> {
+  const [preAuthorization] = findPreAuthorizationAddress(tokenAccount, merchant, programId);
+  const accountInfo = await connection.getAccountInfo(preAuthorization);
+  if (!accountInfo) {
+    throw new Error(`预授权不存在: ${preAuthorization.toString()}`);
+  }
+
+  return PreAuthorization.deserialize(accountInfo.data);
+}
+
+/**
+ * 推导铸币的储蓄金库 PDA
+ */
+export function findSavingsVaultAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('savings'), mint.toBuffer()], programId);
+}
+
+/**
+ * 推导存款人在储蓄金库中的仓位 PDA
+ */
+export function findSavingsPositionAddress(
+  savingsVault: PublicKey,
+  owner: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('savings_position'), savingsVault.toBuffer(), owner.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * 创建储蓄金库初始化指令（铸币权限签名）
+ * vault 须为所有者是储蓄金库 PDA 的空代币账户
+ */
+export function createInitializeSavingsVaultInstruction(
+  mint: PublicKey,
+  vault: PublicKey,
+  mintAuthority: PublicKey,
+  payer: PublicKey,
+  yieldAuthority: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new InitializeSavingsVaultData({ yield_authority: yieldAuthority.toBytes() });
+  const [savingsVault] = findSavingsVaultAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: savingsVault, isSigner: false, isWritable: true },
+    { pubkey: vault, isSigner: false, isWritable: false },
+    { pubkey: mintAuthority, isSigner: true, isWritable: false },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建注入收益指令（收益权限签名），注入后每份额可赎回的资产增加
+ */
+export function createFundYieldInstruction(
+  mint: PublicKey,
+  vault: PublicKey,
+  sourceTokenAccount: PublicKey,
+  yieldAuthority: PublicKey,
+  amount: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new SavingsAmountData({ instruction: TokenInstruction.FundYield, amount });
+  const [savingsVault] = findSavingsVaultAddress(mint, programId);
+
+  const keys = [
+    { pubkey: savingsVault, isSigner: false, isWritable: false },
+    { pubkey: vault, isSigner: false, isWritable: true },
+    { pubkey: sourceTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: yieldAuthority, isSigner: true, isWritable: false },
+    { pubkey: mint, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建存入储蓄指令（所有者签名并支付仓位租金）
+ */
+export function createDepositSavingsInstruction(
+  mint: PublicKey,
+  vault: PublicKey,
+  sourceTokenAccount: PublicKey,
+  owner: PublicKey,
+  amount: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new SavingsAmountData({ instruction: TokenInstruction.DepositSavings, amount });
+  const [savingsVault] = findSavingsVaultAddress(mint, programId);
+  const [position] = findSavingsPositionAddress(savingsVault, owner, programId);
+
+  const keys = [
+    { pubkey: savingsVault, isSigner: false, isWritable: true },
+    { pubkey: vault, isSigner: false, isWritable: true },
+    { pubkey: sourceTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: position, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: mint, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建赎回储蓄份额指令（所有者签名），份额清零时仓位关闭、租金退回所有者
+ */
+export function createWithdrawSavingsInstruction(
+  mint: PublicKey,
+  vault: PublicKey,
+  destinationTokenAccount: PublicKey,
+  owner: PublicKey,
+  shares: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new WithdrawSavingsData({ shares });
+  const [savingsVault] = findSavingsVaultAddress(mint, programId);
+  const [position] = findSavingsPositionAddress(savingsVault, owner, programId);
+
+  const keys = [
+    { pubkey: savingsVault, isSigner: false, isWritable: true },
+    { pubkey: vault, isSigner: false, isWritable: true },
+    { pubkey: position, isSigner: false, isWritable: true },
+    { pubkey: destinationTokenAccount, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: true },
+    { pubkey: mint, isSigner: false, isWritable: true },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取储蓄金库
+ */
+export async function getSavingsVaultData(
+  connection: Connection,
+  mint: PublicKey,
+  programId: PublicKey
+): Promise<SavingsVault> {
+  const [savingsVault] = findSavingsVaultAddress(mint, programId);
+  const accountInfo = await connection.getAccountInfo(savingsVault);
+  if (!accountInfo) {
+    throw new Error(`储蓄金库不存在: ${savingsVault.toString()}`);
+  }
+
+  return SavingsVault.deserialize(accountInfo.data);
+}
+
+/**
+ * 读取存款人的储蓄仓位
+ */
+export async function getSavingsPositionData(
+  connection: Connection,
+  mint: PublicKey,
+  owner: PublicKey,
+  programId: PublicKey
+): Promise<SavingsPosition> {
+  const [savingsVault] = findSavingsVaultAddress(mint, programId);
+  const [position] = findSavingsPositionAddress(savingsVault, owner, programId);
+  const accountInfo = await connection.getAccountInfo(position);
+  if (!accountInfo) {
+    throw new Error(`储蓄仓位不存在: ${position.toString()}`);
+  }
+
+  return SavingsPosition.deserialize(accountInfo.data);
+}
+
+/**
+ * 推导铸币的排放配置 PDA
+ */
+export function findEmissionsAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('emissions'), mint.toBuffer()], programId);
+}
+
+/**
+ * 创建排放计划指令（铸币权限签名），铸币权限随即移交给排放配置 PDA，排放结束后交还
+ */
+export function createInitializeEmissionsInstruction(
+  mint: PublicKey,
+  mintAuthority: PublicKey,
+  payer: PublicKey,
+  tokensPerSlot: bigint,
+  startSlot: bigint,
+  endSlot: bigint,
+  recipients: { vault: PublicKey; weight: number }[],
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new InitializeEmissionsData({
+    tokens_per_slot: tokensPerSlot,
+    start_slot: startSlot,
+    end_slot: endSlot,
+    recipients: recipients.map(({ vault, weight }): EmissionRecipient => ({ vault: vault.toBytes(), weight })),
+  });
+  const [emissions] = findEmissionsAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: emissions, isSigner: false, isWritable: true },
+    { pubkey: mintAuthority, isSigner: true, isWritable: false },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建分发排放指令（任何人可发送），recipientVaults 须与登记顺序一致
+ */
+export function createDistributeEmissionsInstruction(
+  mint: PublicKey,
+  recipientVaults: PublicKey[],
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.DistributeEmissions };
+  const [emissions] = findEmissionsAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: emissions, isSigner: false, isWritable: true },
+    ...recipientVaults.map((vault) => ({ pubkey: vault, isSigner: false, isWritable: true })),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取排放计划
+ */
+export async function getEmissionsConfigData(
+  connection: Connection,
+  mint: PublicKey,
+  programId: PublicKey
+): Promise<EmissionsConfig> {
+  const [emissions] = findEmissionsAddress(mint, programId);
+  const accountInfo = await connection.getAccountInfo(emissions);
+  if (!accountInfo) {
+    throw new Error(`排放计划不存在: ${emissions.toString()}`);
+  }
+
+  return EmissionsConfig.deserialize(accountInfo.data);
+}
+
+/**
+ * 创建开启积分模式指令（铸币权限签名，只能在供应量为 0 时调用，铸币账户需按 MINT_WITH_POINTS_CONFIG_SIZE 分配；
+ * 之后的代币账户需按 TOKEN_ACCOUNT_WITH_POINTS_LOTS_SIZE 分配）
+ */
+export function createEnablePointsExpiryInstruction(
+  mint: PublicKey,
+  mintAuthority: PublicKey,
+  lifetimeSlots: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new EnablePointsExpiryData({ lifetime_slots: lifetimeSlots });
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: mintAuthority, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建销毁过期积分指令（任何人可发送）；设置了投票受托人的账户需在 tokenAccounts 中紧跟其投票检查点 PDA
+ */
+export function createExpirePointsInstruction(
+  mint: PublicKey,
+  tokenAccounts: PublicKey[],
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.ExpirePoints };
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    ...tokenAccounts.map((account) => ({ pubkey: account, isSigner: false, isWritable: true })),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取代币账户的积分批次（PointsLots 扩展，类型 14），按到期先后排列，未开启时返回 null
+ */
+export async function getPointsLots(
+  connection: Connection,
+  tokenAccount: PublicKey
+): Promise<{ amount: bigint; expiresAtSlot: bigint }[] | null> {
+  const accountInfo = await connection.getAccountInfo(tokenAccount);
+  if (!accountInfo) {
+    throw new Error(`代币账户不存在: ${tokenAccount.toString()}`);
+  }
+  const value = findExtensionData(accountInfo.data, 106, 14);
+  if (!value) {
+    return null;
+  }
+  const lots: { amount: bigint; expiresAtSlot: bigint }[] = [];
+  for (let i = 0; i < MAX_POINTS_LOTS; i++) {
+    const amount = value.readBigUInt64LE(16 * i);
+    if (amount !== 0n) {
+      lots.push({ amount, expiresAtSlot: value.readBigUInt64LE(16 * i + 8) });
+    }
+  }
+  return lots.sort((a, b) => (a.expiresAtSlot < b.expiresAtSlot ? -1 : a.expiresAtSlot > b.expiresAtSlot ? 1 : 0));
+}
+
+/**
+ * 推导代币账户的休眠警告 PDA
+ */
+export function findDormancyNoticeAddress(tokenAccount: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('dormancy'), tokenAccount.toBuffer()], programId);
+}
+
+/**
+ * 创建设置休眠账户政策指令（首次由铸币权限签名，之后由当前政策权限签名；签名者成为政策权限，
+ * 铸币账户需按 MINT_WITH_DORMANCY_POLICY_SIZE 分配，之后的代币账户需按 TOKEN_ACCOUNT_WITH_ACTIVITY_SIZE 分配）
+ */
+export function createSetDormancyPolicyInstruction(
+  mint: PublicKey,
+  authority: PublicKey,
+  recoveryVault: PublicKey,
+  dormantEpochs: bigint,
+  noticeEpochs: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new SetDormancyPolicyData({
+    recovery_vault: recoveryVault.toBuffer(),
+    dormant_epochs: dormantEpochs,
+    notice_epochs: noticeEpochs,
+  });
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: authority, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建休眠警告指令（政策权限签名并支付警告 PDA 的租金），成功后链上发出 DormancyEvent::Warned
+ */
+export function createWarnDormantInstruction(
+  mint: PublicKey,
+  tokenAccount: PublicKey,
+  authority: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.WarnDormant };
+  const [notice] = findDormancyNoticeAddress(tokenAccount, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: tokenAccount, isSigner: false, isWritable: false },
+    { pubkey: notice, isSigner: false, isWritable: true },
+    { pubkey: authority, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建归集休眠账户指令（政策权限签名）；警告后账户有过活动时只撤销警告。
+ * 代币账户设置了投票受托人时需在 voteCheckpoints 中传入其投票检查点 PDA
+ */
+export function createSweepDormantInstruction(
+  mint: PublicKey,
+  tokenAccount: PublicKey,
+  recoveryVault: PublicKey,
+  authority: PublicKey,
+  programId: PublicKey,
+  voteCheckpoints: PublicKey[] = []
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.SweepDormant };
+  const [notice] = findDormancyNoticeAddress(tokenAccount, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: tokenAccount, isSigner: false, isWritable: true },
+    { pubkey: recoveryVault, isSigner: false, isWritable: true },
+    { pubkey: notice, isSigner: false, isWritable: true },
+    { pubkey: authority, isSigner: true, isWritable: true },
+    ...voteCheckpoints.map((checkpoints) => ({ pubkey: checkpoints, isSigner: false, isWritable: true })),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取代币账户的休眠警告，不存在时返回 null
+ */
+export async function getDormancyNotice(
+  connection: Connection,
+  tokenAccount: PublicKey,
+  programId: PublicKey
+): Promise<DormancyNotice | null> {
+  const [notice] = findDormancyNoticeAddress(tokenAccount, programId);
+  const accountInfo = await connection.getAccountInfo(notice);
+  if (!accountInfo) {
+    return null;
+  }
+
+  return DormancyNotice.deserialize(accountInfo.data);
+}
+
+/**
+ * 推导铸币的储备证明 PDA
+ */
+export function findAttestationAddress(mint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('attestation'), mint.toBuffer()], programId);
+}
+
+/**
+ * 创建更新储备证明指令（铸币权限与审计方共同签名，首次更新时由铸币权限支付证明 PDA 的租金）
+ */
+export function createUpdateAttestationInstruction(
+  mint: PublicKey,
+  mintAuthority: PublicKey,
+  attester: PublicKey,
+  reserves: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new UpdateAttestationData({ reserves });
+  const [attestation] = findAttestationAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: attestation, isSigner: false, isWritable: true },
+    { pubkey: mintAuthority, isSigner: true, isWritable: true },
+    { pubkey: attester, isSigner: true, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建核对供应量储备指令（任何人可发送），结果以 ReserveEvent::Verified 事件写入日志
+ */
+export function createVerifySupplyBackedInstruction(mint: PublicKey, programId: PublicKey): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.VerifySupplyBacked };
+  const [attestation] = findAttestationAddress(mint, programId);
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: attestation, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取铸币的储备证明，不存在时返回 null
+ */
+export async function getAttestation(
+  connection: Connection,
+  mint: PublicKey,
+  programId: PublicKey
+): Promise<Attestation | null> {
+  const [attestation] = findAttestationAddress(mint, programId);
+  const accountInfo = await connection.getAccountInfo(attestation);
+  if (!accountInfo) {
+    return null;
+  }
+
+  return Attestation.deserialize(accountInfo.data);
+}
+
+/**
+ * 推导旧铸币的兑换 PDA
+ */
+export function findConversionAddress(oldMint: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('conversion'), oldMint.toBuffer()], programId);
+}
+
+/**
+ * 创建开启代币迁移指令（旧铸币的铸币权限签名）；vault 为新铸币的代币账户，所有者须为兑换 PDA，
+ * rate 为每个旧代币最小单位兑换的新代币最小单位数乘以 CONVERSION_RATE_SCALE
+ */
+export function createInitializeConversionInstruction(
+  oldMint: PublicKey,
+  newMint: PublicKey,
+  vault: PublicKey,
+  mintAuthority: PublicKey,
+  payer: PublicKey,
+  rate: bigint,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new InitializeConversionData({ rate });
+  const [conversion] = findConversionAddress(oldMint, programId);
+
+  const keys = [
+    { pubkey: oldMint, isSigner: false, isWritable: false },
+    { pubkey: newMint, isSigner: false, isWritable: false },
+    { pubkey: conversion, isSigner: false, isWritable: true },
+    { pubkey: vault, isSigner: false, isWritable: false },
+    { pubkey: mintAuthority, isSigner: true, isWritable: false },
+    { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建兑换指令（旧代币账户所有者签名）：销毁 amount 个旧代币，从托管账户领取新代币。
+ * 设置了投票受托人时，voteCheckpoints 依次为旧代币账户受托人、接收账户受托人的投票检查点 PDA
+ */
+export function createConvertMintInstruction(
+  oldMint: PublicKey,
+  newMint: PublicKey,
+  source: PublicKey,
+  destination: PublicKey,
+  vault: PublicKey,
+  owner: PublicKey,
+  amount: bigint,
+  programId: PublicKey,
+  voteCheckpoints: PublicKey[] = []
+): TransactionInstruction {
+  const data = new ConvertMintData({ amount });
+  const [conversion] = findConversionAddress(oldMint, programId);
+
+  const keys = [
+    { pubkey: oldMint, isSigner: false, isWritable: true },
+    { pubkey: newMint, isSigner: false, isWritable: true },
+    { pubkey: source, isSigner: false, isWritable: true },
+    { pubkey: destination, isSigner: false, isWritable: true },
+    { pubkey: conversion, isSigner: false, isWritable: true },
+    { pubkey: vault, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: false },
+    ...voteCheckpoints.map((checkpoints) => ({ pubkey: checkpoints, isSigner: false, isWritable: true })),
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 读取旧铸币的兑换配置，不存在时返回 null
+ */
+export async function getConversion(
+  connection: Connection,
+  oldMint: PublicKey,
+  programId: PublicKey
+): Promise<Conversion | null> {
+  const [conversion] = findConversionAddress(oldMint, programId);
+  const accountInfo = await connection.getAccountInfo(conversion);
+  if (!accountInfo) {
+    return null;
+  }
+
+  return Conversion.deserialize(accountInfo.data);
+}
+
+/**
+ * 创建同一所有者账户间的调拨指令（所有者签名）：不执行转账钩子与速度限制。
+ * mint 为两个账户所属的铸币；设置了投票受托人时在 voteCheckpoints 中传入检查点 PDA
+ */
+export function createRebalanceInstruction(
+  source: PublicKey,
+  destination: PublicKey,
+  owner: PublicKey,
+  amount: bigint,
+  mint: PublicKey,
+  programId: PublicKey,
+  voteCheckpoints: PublicKey[] = []
+): TransactionInstruction {
+  const data = new RebalanceData({ amount });
+
+  const keys = [
+    { pubkey: source, isSigner: false, isWritable: true },
+    { pubkey: destination, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: false },
+  ];
+  keys.push({ pubkey: mint, isSigner: false, isWritable: true });
+  keys.push(...voteCheckpoints.map((checkpoints) => ({ pubkey: checkpoints, isSigner: false, isWritable: true })));
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建分项暂停指令（冻结权限签名）：转账、铸造、销毁各自开关，传 null 的项保持不变，
+ * 例如只暂停转账而保留销毁赎回。铸币账户需按 MINT_WITH_PAUSE_STATE_SIZE 分配
+ */
+export function createSetPauseFlagsInstruction(
+  mint: PublicKey,
+  freezeAuthority: PublicKey,
+  flags: { pauseTransfers?: boolean | null; pauseMinting?: boolean | null; pauseBurning?: boolean | null },
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new SetPauseFlagsData({
+    pause_transfers: flags.pauseTransfers ?? null,
+    pause_minting: flags.pauseMinting ?? null,
+    pause_burning: flags.pauseBurning ?? null,
+  });
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: freezeAuthority, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建设置激活要求指令（铸币权限签名）：开启后新建的代币账户需按 TOKEN_ACCOUNT_WITH_ACTIVATION_SIZE 分配，
+ * 并由所有者执行 ActivateAccount 后才能收款。铸币账户需按 MINT_WITH_ACTIVATION_POLICY_SIZE 分配
+ */
+export function createSetActivationRequiredInstruction(
+  mint: PublicKey,
+  mintAuthority: PublicKey,
+  required: boolean,
+  programId: PublicKey
+): TransactionInstruction {
+  const data = new SetActivationRequiredData({ required });
+
+  const keys = [
+    { pubkey: mint, isSigner: false, isWritable: true },
+    { pubkey: mintAuthority, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
+
+/**
+ * 创建激活代币账户指令（所有者签名），建议与 InitializeAccount 放在同一交易中
+ */
+export function createActivateAccountInstruction(
+  tokenAccount: PublicKey,
+  owner: PublicKey,
+  programId: PublicKey
+): TransactionInstruction {
+  const data: InstructionData = { instruction: TokenInstruction.ActivateAccount };
+
+  const keys = [
+    { pubkey: tokenAccount, isSigner: false, isWritable: true },
+    { pubkey: owner, isSigner: true, isWritable: false },
+  ];
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: serializeInstructionData(data),
+  });
+}
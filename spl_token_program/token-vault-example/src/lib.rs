@@ -0,0 +1,267 @@
+//! 代币金库示例程序：演示如何通过 CPI 调用 spl-token-program
+//!
+//! - Deposit: 用户签名，程序用 `invoke` 把用户的代币转入金库（签名权限随 CPI 传递）
+//! - Withdraw: 金库代币账户的所有者是本程序的 PDA，程序通过 `transfer_signed`（invoke_signed）代签转出
+//!
+//! 金库代币账户需事先通过代币程序的 InitializeAccount 创建，owner 设为
+//! `find_vault_authority(vault_token_account)` 推导出的 PDA。
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint,
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction, system_program,
+    sysvar::Sysvar,
+};
+use spl_token_program::{is_owned_by_pda, transfer_instruction, transfer_signed, TokenAccount};
+
+pub const VAULT_AUTHORITY_SEED: &[u8] = b"vault_authority";
+pub const RECEIPT_SEED: &[u8] = b"receipt";
+
+// 指令枚举
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub enum VaultInstruction {
+    /// 存入代币
+    /// 账户列表:
+    /// [0] 用户代币账户 (可写)
+    /// [1] 金库代币账户 (可写)
+    /// [2] 存款凭证 PDA (可写) seeds = ["receipt", vault_token_account, user]
+    /// [3] 用户 (签名者, 可写)
+    /// [4] 代币程序
+    /// [5] 系统程序
+    /// [6] 金库铸币账户 (可写)
+    Deposit { amount: u64 },
+
+    /// 取回代币
+    /// 账户列表:
+    /// [0] 金库代币账户 (可写)
+    /// [1] 用户代币账户 (可写)
+    /// [2] 金库权限 PDA seeds = ["vault_authority", vault_token_account]
+    /// [3] 存款凭证 PDA (可写)
+    /// [4] 用户 (签名者)
+    /// [5] 代币程序
+    /// [6] 金库铸币账户 (可写)
+    Withdraw { amount: u64 },
+}
+
+// 用户存款凭证
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct DepositReceipt {
+    pub is_initialized: bool, //1
+    pub vault: Pubkey, //32
+    pub user: Pubkey, //32
+    pub amount: u64, //8
+}
+
+impl DepositReceipt {
+    pub const LEN: usize = 1 + 32 + 32 + 8;
+}
+
+/// 推导金库代币账户的所有者 PDA
+pub fn find_vault_authority(vault_token_account: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_AUTHORITY_SEED, vault_token_account.as_ref()], program_id)
+}
+
+/// 推导用户存款凭证 PDA
+pub fn find_receipt(vault_token_account: &Pubkey, user: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[RECEIPT_SEED, vault_token_account.as_ref(), user.as_ref()],
+        program_id,
+    )
+}
+
+// 程序入口点
+#[cfg(not(feature = "no-entrypoint"))]
+entrypoint!(process_instruction);
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = VaultInstruction::try_from_slice(instruction_data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    match instruction {
+        VaultInstruction::Deposit { amount } => {
+            msg!("====Deposit====");
+            process_deposit(program_id, accounts, amount)
+        }
+        VaultInstruction::Withdraw { amount } => {
+            msg!("====Withdraw====");
+            process_withdraw(program_id, accounts, amount)
+        }
+    }
+}
+
+/// 存入代币：用户已签名，直接 invoke 即可把签名权限传给代币程序
+fn process_deposit(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_token_account = next_account_info(account_info_iter)?;
+    let vault_token_account = next_account_info(account_info_iter)?;
+    let receipt_account = next_account_info(account_info_iter)?;
+    let user_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+
+    if !user_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if *system_program_account.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // 金库代币账户必须属于代币程序，且所有者是本程序的金库 PDA
+    if vault_token_account.owner != token_program.key {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let vault = TokenAccount::deserialize(&vault_token_account.data.borrow())?;
+    if !is_owned_by_pda(&vault, &[VAULT_AUTHORITY_SEED, vault_token_account.key.as_ref()], program_id) {
+        msg!("Vault token account is not owned by the vault authority PDA");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let user_token = TokenAccount::deserialize(&user_token_account.data.borrow())?;
+    if user_token.mint != vault.mint {
+        msg!("User token account mint does not match the vault mint");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let (receipt_key, receipt_bump) =
+        find_receipt(vault_token_account.key, user_account.key, program_id);
+    if receipt_key != *receipt_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // 首次存款时创建凭证账户
+    if receipt_account.data_is_empty() {
+        let lamports = Rent::get()?.minimum_balance(DepositReceipt::LEN);
+        invoke_signed(
+            &system_instruction::create_account(
+                user_account.key,
+                receipt_account.key,
+                lamports,
+                DepositReceipt::LEN as u64,
+                program_id,
+            ),
+            &[
+                user_account.clone(),
+                receipt_account.clone(),
+                system_program_account.clone(),
+            ],
+            &[&[
+                RECEIPT_SEED,
+                vault_token_account.key.as_ref(),
+                user_account.key.as_ref(),
+                &[receipt_bump],
+            ]],
+        )?;
+        let receipt = DepositReceipt {
+            is_initialized: true,
+            vault: *vault_token_account.key,
+            user: *user_account.key,
+            amount: 0,
+        };
+        borsh::to_writer(&mut receipt_account.data.borrow_mut()[..], &receipt)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+    }
+
+    // 转账成功不代表代币已到账（例如用户账户触发速度限制时只会被冻结），凭证按金库余额的实际变化记账
+    let vault_before = vault.amount;
+    invoke(
+        &transfer_instruction(
+            token_program.key,
+            user_token_account.key,
+            vault_token_account.key,
+            user_account.key,
+            mint_account.key,
+            amount,
+        )?,
+        &[
+            user_token_account.clone(),
+            vault_token_account.clone(),
+            user_account.clone(),
+            mint_account.clone(),
+            token_program.clone(),
+        ],
+    )?;
+    let received = TokenAccount::deserialize(&vault_token_account.data.borrow())?
+        .amount
+        .checked_sub(vault_before)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if received == 0 {
+        msg!("Transfer succeeded but the vault balance did not change");
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    let mut receipt = DepositReceipt::deserialize(&mut &receipt_account.data.borrow()[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    receipt.amount = receipt
+        .amount
+        .checked_add(received)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    borsh::to_writer(&mut receipt_account.data.borrow_mut()[..], &receipt)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    msg!("Deposited {} tokens, receipt balance {}", received, receipt.amount);
+    Ok(())
+}
+
+/// 取回代币：金库代币账户的所有者是 PDA，需要 invoke_signed 代签
+fn process_withdraw(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let vault_token_account = next_account_info(account_info_iter)?;
+    let user_token_account = next_account_info(account_info_iter)?;
+    let vault_authority = next_account_info(account_info_iter)?;
+    let receipt_account = next_account_info(account_info_iter)?;
+    let user_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+
+    if !user_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if receipt_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let (authority_key, authority_bump) = find_vault_authority(vault_token_account.key, program_id);
+    if authority_key != *vault_authority.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut receipt = DepositReceipt::deserialize(&mut &receipt_account.data.borrow()[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if receipt.vault != *vault_token_account.key || receipt.user != *user_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if receipt.amount < amount {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    // PDA 没有私钥：由运行时根据 seeds + bump 为 vault_authority 赋予签名权限
+    transfer_signed(
+        program_id,
+        token_program,
+        vault_token_account,
+        user_token_account,
+        vault_authority,
+        mint_account,
+        amount,
+        &[VAULT_AUTHORITY_SEED, vault_token_account.key.as_ref(), &[authority_bump]],
+    )?;
+
+    receipt.amount -= amount;
+    borsh::to_writer(&mut receipt_account.data.borrow_mut()[..], &receipt)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    msg!("Withdrew {} tokens, receipt balance {}", amount, receipt.amount);
+    Ok(())
+}
@@ -0,0 +1,189 @@
+//! 金库示例的端到端测试：代币程序与金库程序都以 processor! 注册到 solana-program-test，
+//! Deposit 经 invoke、Withdraw 经 transfer_signed（invoke_signed）真正走一遍 CPI。
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{instruction::AccountMeta, instruction::Instruction, pubkey::Pubkey, rent::Rent, system_program};
+use solana_program_test::{processor, tokio, BanksClient, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use spl_token_program::{
+    test_utils::{MintFixture, TokenAccountFixture},
+    transfer_instruction, TokenAccount,
+};
+use token_vault_example::{find_receipt, find_vault_authority, DepositReceipt, VaultInstruction};
+
+struct Setup {
+    vault_program: Pubkey,
+    mint: Pubkey,
+    user: Keypair,
+    user_token: Pubkey,
+    vault_token: Pubkey,
+}
+
+fn program_test(user_balance: u64, vault_balance: u64) -> (ProgramTest, Setup) {
+    let vault_program = Pubkey::new_unique();
+    let mut test = ProgramTest::new(
+        "spl_token_program",
+        spl_token_program::id(),
+        processor!(spl_token_program::process_instruction),
+    );
+    test.add_program("token_vault_example", vault_program, processor!(token_vault_example::process_instruction));
+
+    let setup = Setup {
+        vault_program,
+        mint: Pubkey::new_unique(),
+        user: Keypair::new(),
+        user_token: Pubkey::new_unique(),
+        vault_token: Pubkey::new_unique(),
+    };
+    let (vault_authority, _) = find_vault_authority(&setup.vault_token, &vault_program);
+    let token_owned = |data: Vec<u8>| Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: spl_token_program::id(),
+        ..Account::default()
+    };
+    let mint = MintFixture::new().decimals(6).supply(user_balance + vault_balance);
+    test.add_account(setup.mint, token_owned(mint.to_bytes()));
+    test.add_account(
+        setup.user_token,
+        token_owned(TokenAccountFixture::new(setup.mint, setup.user.pubkey()).amount(user_balance).to_bytes()),
+    );
+    test.add_account(
+        setup.vault_token,
+        token_owned(TokenAccountFixture::new(setup.mint, vault_authority).amount(vault_balance).to_bytes()),
+    );
+    test.add_account(
+        setup.user.pubkey(),
+        Account { lamports: 1_000_000_000, owner: system_program::id(), ..Account::default() },
+    );
+    (test, setup)
+}
+
+fn deposit(setup: &Setup, amount: u64) -> Instruction {
+    let (receipt, _) = find_receipt(&setup.vault_token, &setup.user.pubkey(), &setup.vault_program);
+    Instruction {
+        program_id: setup.vault_program,
+        accounts: vec![
+            AccountMeta::new(setup.user_token, false),
+            AccountMeta::new(setup.vault_token, false),
+            AccountMeta::new(receipt, false),
+            AccountMeta::new(setup.user.pubkey(), true),
+            AccountMeta::new_readonly(spl_token_program::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(setup.mint, false),
+        ],
+        data: VaultInstruction::Deposit { amount }.try_to_vec().unwrap(),
+    }
+}
+
+fn withdraw(setup: &Setup, amount: u64) -> Instruction {
+    let (receipt, _) = find_receipt(&setup.vault_token, &setup.user.pubkey(), &setup.vault_program);
+    let (vault_authority, _) = find_vault_authority(&setup.vault_token, &setup.vault_program);
+    Instruction {
+        program_id: setup.vault_program,
+        accounts: vec![
+            AccountMeta::new(setup.vault_token, false),
+            AccountMeta::new(setup.user_token, false),
+            AccountMeta::new_readonly(vault_authority, false),
+            AccountMeta::new(receipt, false),
+            AccountMeta::new_readonly(setup.user.pubkey(), true),
+            AccountMeta::new_readonly(spl_token_program::id(), false),
+            AccountMeta::new(setup.mint, false),
+        ],
+        data: VaultInstruction::Withdraw { amount }.try_to_vec().unwrap(),
+    }
+}
+
+async fn send(banks: &mut BanksClient, payer: &Keypair, setup: &Setup, instruction: Instruction) -> bool {
+    let blockhash = banks.get_latest_blockhash().await.unwrap();
+    let transaction =
+        Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[payer, &setup.user], blockhash);
+    banks.process_transaction(transaction).await.is_ok()
+}
+
+async fn token_amount(banks: &mut BanksClient, key: Pubkey) -> u64 {
+    let account = banks.get_account(key).await.unwrap().unwrap();
+    TokenAccount::deserialize(&account.data).unwrap().amount
+}
+
+async fn receipt_amount(banks: &mut BanksClient, setup: &Setup) -> u64 {
+    let (receipt, _) = find_receipt(&setup.vault_token, &setup.user.pubkey(), &setup.vault_program);
+    let account = banks.get_account(receipt).await.unwrap().unwrap();
+    DepositReceipt::try_from_slice(&account.data).unwrap().amount
+}
+
+#[tokio::test]
+async fn deposit_and_withdraw_round_trip() {
+    let (test, setup) = program_test(1_000, 0);
+    let (mut banks, payer, _) = test.start().await;
+
+    assert!(send(&mut banks, &payer, &setup, deposit(&setup, 300)).await);
+    assert_eq!(token_amount(&mut banks, setup.user_token).await, 700);
+    assert_eq!(token_amount(&mut banks, setup.vault_token).await, 300);
+    assert_eq!(receipt_amount(&mut banks, &setup).await, 300);
+
+    // 第二次存款复用已创建的凭证
+    assert!(send(&mut banks, &payer, &setup, deposit(&setup, 200)).await);
+    assert_eq!(receipt_amount(&mut banks, &setup).await, 500);
+
+    assert!(send(&mut banks, &payer, &setup, withdraw(&setup, 400)).await);
+    assert_eq!(token_amount(&mut banks, setup.user_token).await, 900);
+    assert_eq!(token_amount(&mut banks, setup.vault_token).await, 100);
+    assert_eq!(receipt_amount(&mut banks, &setup).await, 100);
+
+    // 超出凭证余额的取款被拒绝，余额不变
+    assert!(!send(&mut banks, &payer, &setup, withdraw(&setup, 101)).await);
+    assert_eq!(token_amount(&mut banks, setup.vault_token).await, 100);
+    assert_eq!(receipt_amount(&mut banks, &setup).await, 100);
+}
+
+#[tokio::test]
+async fn withdraw_signs_for_the_vault_authority_pda() {
+    let (mut test, setup) = program_test(0, 500);
+    let (receipt, _) = find_receipt(&setup.vault_token, &setup.user.pubkey(), &setup.vault_program);
+    let receipt_data = DepositReceipt {
+        is_initialized: true,
+        vault: setup.vault_token,
+        user: setup.user.pubkey(),
+        amount: 500,
+    }
+    .try_to_vec()
+    .unwrap();
+    test.add_account(
+        receipt,
+        Account {
+            lamports: Rent::default().minimum_balance(receipt_data.len()),
+            data: receipt_data,
+            owner: setup.vault_program,
+            ..Account::default()
+        },
+    );
+    let (mut banks, payer, _) = test.start().await;
+
+    // 金库 PDA 没有私钥，绕过金库程序直接调用 Transfer 无法提供它的签名
+    let (vault_authority, _) = find_vault_authority(&setup.vault_token, &setup.vault_program);
+    let mut direct = transfer_instruction(
+        &spl_token_program::id(),
+        &setup.vault_token,
+        &setup.user_token,
+        &vault_authority,
+        &setup.mint,
+        200,
+    )
+    .unwrap();
+    direct.accounts[2].is_signer = false;
+    let blockhash = banks.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(&[direct], Some(&payer.pubkey()), &[&payer], blockhash);
+    assert!(banks.process_transaction(transaction).await.is_err());
+    assert_eq!(token_amount(&mut banks, setup.vault_token).await, 500);
+
+    // Withdraw 通过 transfer_signed 以 seeds 代 PDA 签名
+    assert!(send(&mut banks, &payer, &setup, withdraw(&setup, 200)).await);
+    assert_eq!(token_amount(&mut banks, setup.vault_token).await, 300);
+    assert_eq!(token_amount(&mut banks, setup.user_token).await, 200);
+    assert_eq!(receipt_amount(&mut banks, &setup).await, 300);
+}